@@ -5,6 +5,7 @@ use core::bytes_31::{
 };
 use core::clone::Clone;
 use core::cmp::min;
+use core::hash::{Hash, HashStateTrait};
 use core::integer::{u128_safe_divmod, U32TryIntoNonZero};
 use core::option::OptionTrait;
 use core::traits::{Into, TryInto};
@@ -354,6 +355,19 @@ impl ByteArrayAddEq of AddEq<ByteArray> {
     }
 }
 
+impl ByteArrayHash<S, +HashStateTrait<S>, +Drop<S>> of Hash<ByteArray, S> {
+    /// Updates the state with the number of full words, the full words, the pending word and its
+    /// length - a word at a time rather than a byte at a time.
+    fn update_state(state: S, value: ByteArray) -> S {
+        let mut state = state.update(value.data.len().into());
+        let mut data = value.data.span();
+        while let Option::Some(word) = data.pop_front() {
+            state = state.update((*word).into());
+        };
+        state.update(value.pending_word).update(value.pending_word_len.into())
+    }
+}
+
 pub(crate) impl ByteArrayIndexView of IndexView<ByteArray, usize, u8> {
     fn index(self: @ByteArray, index: usize) -> u8 {
         self.at(index).expect('Index out of bounds')
@@ -148,3 +148,6 @@ impl TupleSize4Hash<
         state.update_with(e0).update_with(e1).update_with(e2).update_with(e3)
     }
 }
+
+/// The Blake2s hash function.
+pub mod blake2s;
@@ -0,0 +1,44 @@
+use core::integer::Bitwise;
+
+/// The initial state of Blake2s-256 - the Blake2s IV, mixed with the parameter block of an unkeyed
+/// hash with a 32 byte digest.
+pub const BLAKE2S_256_INITIAL_STATE: [
+    u32
+    ; 8] = [
+    0x6B08E647, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// Applies the Blake2s compression function on a message block that is not the last one.
+/// `byte_count` is the number of message bytes hashed so far, including the given block.
+/// The message words are the little endian interpretation of the block bytes.
+pub extern fn blake2s_compress(
+    state: [u32; 8], byte_count: u32, msg: [u32; 16]
+    ) -> [
+    u32
+; 8] implicits(RangeCheck, Bitwise) nopanic;
+
+/// Applies the Blake2s compression function on the last block of a message.
+/// `byte_count` is the total number of bytes of the message.
+/// The message words are the little endian interpretation of the block bytes, zero padded.
+pub extern fn blake2s_finalize(
+    state: [u32; 8], byte_count: u32, msg: [u32; 16]
+    ) -> [
+    u32
+; 8] implicits(RangeCheck, Bitwise) nopanic;
+
+/// Computes the Blake2s-256 hash of a message of `byte_count` bytes, given as 64 byte blocks of
+/// little endian words, where the last block is zero padded.
+/// Returns the digest as little endian words.
+pub fn compute_blake2s_256(mut blocks: Span<[u32; 16]>, byte_count: u32) -> [u32; 8] {
+    let last_block = match blocks.pop_back() {
+        Option::Some(block) => *block,
+        Option::None => [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    };
+    let mut state = BLAKE2S_256_INITIAL_STATE;
+    let mut hashed_bytes = 0;
+    while let Option::Some(block) = blocks.pop_front() {
+        hashed_bytes += 64;
+        state = blake2s_compress(state, hashed_bytes, *block);
+    };
+    blake2s_finalize(state, byte_count, last_block)
+}
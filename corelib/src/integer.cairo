@@ -1256,6 +1256,22 @@ fn u256_safe_div_rem(lhs: u256, rhs: NonZero<u256>) -> (u256, u256) implicits(Ra
     let (q, r, _) = u256_safe_divmod(lhs, rhs);
     (q, r)
 }
+
+/// Calculates division with remainder of a u256 by a non-zero u128.
+/// Additionally returns a `U128MulGuarantee` that is required for validating the calculation.
+extern fn u256_safe_divmod_by_u128(
+    lhs: u256, rhs: NonZero<u128>
+) -> (u256, u128, U128MulGuarantee) implicits(RangeCheck) nopanic;
+
+/// Calculates division with remainder of a u256 by a non-zero u128.
+#[inline(always)]
+pub fn u256_safe_div_rem_by_u128(
+    lhs: u256, rhs: NonZero<u128>
+) -> (u256, u128) implicits(RangeCheck) nopanic {
+    let (q, r, _) = u256_safe_divmod_by_u128(lhs, rhs);
+    (q, r)
+}
+
 pub extern fn u256_sqrt(a: u256) -> u128 implicits(RangeCheck) nopanic;
 
 #[panic_with('u256 is 0', u256_as_non_zero)]
@@ -1324,35 +1340,21 @@ pub struct u512 {
     pub limb3: u128,
 }
 
-// Returns the result of u128 addition, including an overflow word.
-fn u128_add_with_carry(a: u128, b: u128) -> (u128, u128) nopanic {
-    match u128_overflowing_add(a, b) {
-        Result::Ok(v) => (v, 0),
-        Result::Err(v) => (v, 1),
-    }
-}
-
+/// Calculates the full 512-bit product of two u256 values.
 pub fn u256_wide_mul(a: u256, b: u256) -> u512 nopanic {
-    let (limb1, limb0) = u128_wide_mul(a.low, b.low);
-    let (limb2, limb1_part) = u128_wide_mul(a.low, b.high);
-    let (limb1, limb1_overflow0) = u128_add_with_carry(limb1, limb1_part);
-    let (limb2_part, limb1_part) = u128_wide_mul(a.high, b.low);
-    let (limb1, limb1_overflow1) = u128_add_with_carry(limb1, limb1_part);
-    let (limb2, limb2_overflow) = u128_add_with_carry(limb2, limb2_part);
-    let (limb3, limb2_part) = u128_wide_mul(a.high, b.high);
-    // No overflow since no limb4.
-    let limb3 = u128_wrapping_add(limb3, limb2_overflow);
-    let (limb2, limb2_overflow) = u128_add_with_carry(limb2, limb2_part);
-    // No overflow since no limb4.
-    let limb3 = u128_wrapping_add(limb3, limb2_overflow);
-    // No overflow possible in this addition since both operands are 0/1.
-    let limb1_overflow = u128_wrapping_add(limb1_overflow0, limb1_overflow1);
-    let (limb2, limb2_overflow) = u128_add_with_carry(limb2, limb1_overflow);
-    // No overflow since no limb4.
-    let limb3 = u128_wrapping_add(limb3, limb2_overflow);
-    u512 { limb0, limb1, limb2, limb3 }
+    let (r, _, _, _, _) = u256_guarantee_wide_mul(a, b);
+    r
 }
 
+/// Calculates the full 512-bit product of two u256 values.
+/// Additionally returns the `U128MulGuarantee`s of the products of the limbs, that are required for
+/// validating the calculation.
+extern fn u256_guarantee_wide_mul(
+    a: u256, b: u256
+) -> (
+    u512, U128MulGuarantee, U128MulGuarantee, U128MulGuarantee, U128MulGuarantee
+) implicits(RangeCheck) nopanic;
+
 /// Calculates division with remainder of a u512 by a non-zero u256.
 #[inline(always)]
 pub fn u512_safe_div_rem_by_u256(
@@ -1376,6 +1378,16 @@ extern fn u512_safe_divmod_by_u256(
     U128MulGuarantee
 ) implicits(RangeCheck) nopanic;
 
+/// Adds two u512 values, returning `Result::Err` with the wrapped sum on overflow.
+pub extern fn u512_overflowing_add(
+    lhs: u512, rhs: u512
+) -> Result<u512, u512> implicits(RangeCheck) nopanic;
+
+/// Subtracts two u512 values, returning `Result::Err` with the wrapped difference on underflow.
+pub extern fn u512_overflowing_sub(
+    lhs: u512, rhs: u512
+) -> Result<u512, u512> implicits(RangeCheck) nopanic;
+
 impl U512TryIntoU256 of TryInto<u512, u256> {
     fn try_into(self: u512) -> Option<u256> {
         if self.limb2 != 0 || self.limb3 != 0 {
@@ -1948,6 +1960,10 @@ extern fn i8_overflowing_add_impl(
 extern fn i8_overflowing_sub_impl(
     lhs: i8, rhs: i8
 ) -> SignedIntegerResult<i8> implicits(RangeCheck) nopanic;
+extern fn i8_saturating_add(lhs: i8, rhs: i8) -> i8 implicits(RangeCheck) nopanic;
+extern fn i8_saturating_sub(lhs: i8, rhs: i8) -> i8 implicits(RangeCheck) nopanic;
+extern fn i8_wrapping_add(lhs: i8, rhs: i8) -> i8 implicits(RangeCheck) nopanic;
+extern fn i8_wrapping_sub(lhs: i8, rhs: i8) -> i8 implicits(RangeCheck) nopanic;
 impl I8Add of Add<i8> {
     fn add(lhs: i8, rhs: i8) -> i8 {
         match i8_overflowing_add_impl(lhs, rhs) {
@@ -2054,6 +2070,10 @@ extern fn i16_overflowing_add_impl(
 extern fn i16_overflowing_sub_impl(
     lhs: i16, rhs: i16
 ) -> SignedIntegerResult<i16> implicits(RangeCheck) nopanic;
+extern fn i16_saturating_add(lhs: i16, rhs: i16) -> i16 implicits(RangeCheck) nopanic;
+extern fn i16_saturating_sub(lhs: i16, rhs: i16) -> i16 implicits(RangeCheck) nopanic;
+extern fn i16_wrapping_add(lhs: i16, rhs: i16) -> i16 implicits(RangeCheck) nopanic;
+extern fn i16_wrapping_sub(lhs: i16, rhs: i16) -> i16 implicits(RangeCheck) nopanic;
 impl I16Add of Add<i16> {
     fn add(lhs: i16, rhs: i16) -> i16 {
         match i16_overflowing_add_impl(lhs, rhs) {
@@ -2160,6 +2180,10 @@ extern fn i32_overflowing_add_impl(
 extern fn i32_overflowing_sub_impl(
     lhs: i32, rhs: i32
 ) -> SignedIntegerResult<i32> implicits(RangeCheck) nopanic;
+extern fn i32_saturating_add(lhs: i32, rhs: i32) -> i32 implicits(RangeCheck) nopanic;
+extern fn i32_saturating_sub(lhs: i32, rhs: i32) -> i32 implicits(RangeCheck) nopanic;
+extern fn i32_wrapping_add(lhs: i32, rhs: i32) -> i32 implicits(RangeCheck) nopanic;
+extern fn i32_wrapping_sub(lhs: i32, rhs: i32) -> i32 implicits(RangeCheck) nopanic;
 impl I32Add of Add<i32> {
     fn add(lhs: i32, rhs: i32) -> i32 {
         match i32_overflowing_add_impl(lhs, rhs) {
@@ -2266,6 +2290,10 @@ extern fn i64_overflowing_add_impl(
 extern fn i64_overflowing_sub_impl(
     lhs: i64, rhs: i64
 ) -> SignedIntegerResult<i64> implicits(RangeCheck) nopanic;
+extern fn i64_saturating_add(lhs: i64, rhs: i64) -> i64 implicits(RangeCheck) nopanic;
+extern fn i64_saturating_sub(lhs: i64, rhs: i64) -> i64 implicits(RangeCheck) nopanic;
+extern fn i64_wrapping_add(lhs: i64, rhs: i64) -> i64 implicits(RangeCheck) nopanic;
+extern fn i64_wrapping_sub(lhs: i64, rhs: i64) -> i64 implicits(RangeCheck) nopanic;
 impl I64Add of Add<i64> {
     fn add(lhs: i64, rhs: i64) -> i64 {
         match i64_overflowing_add_impl(lhs, rhs) {
@@ -2372,6 +2400,10 @@ extern fn i128_overflowing_add_impl(
 extern fn i128_overflowing_sub_impl(
     lhs: i128, rhs: i128
 ) -> SignedIntegerResult<i128> implicits(RangeCheck) nopanic;
+extern fn i128_saturating_add(lhs: i128, rhs: i128) -> i128 implicits(RangeCheck) nopanic;
+extern fn i128_saturating_sub(lhs: i128, rhs: i128) -> i128 implicits(RangeCheck) nopanic;
+extern fn i128_wrapping_add(lhs: i128, rhs: i128) -> i128 implicits(RangeCheck) nopanic;
+extern fn i128_wrapping_sub(lhs: i128, rhs: i128) -> i128 implicits(RangeCheck) nopanic;
 impl I128Add of Add<i128> {
     fn add(lhs: i128, rhs: i128) -> i128 {
         match i128_overflowing_add_impl(lhs, rhs) {
@@ -3049,11 +3081,36 @@ impl U32WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappin
 impl U64WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<u64>;
 impl U128WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<u128>;
 impl U256WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<u256>;
-impl I8WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<i8>;
-impl I16WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<i16>;
-impl I32WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<i32>;
-impl I64WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<i64>;
-impl I128WrappingAdd = core::num::traits::ops::wrapping::overflow_based::TWrappingAdd<i128>;
+impl I8WrappingAdd of core::num::traits::WrappingAdd<i8> {
+    #[inline(always)]
+    fn wrapping_add(self: i8, v: i8) -> i8 {
+        i8_wrapping_add(self, v)
+    }
+}
+impl I16WrappingAdd of core::num::traits::WrappingAdd<i16> {
+    #[inline(always)]
+    fn wrapping_add(self: i16, v: i16) -> i16 {
+        i16_wrapping_add(self, v)
+    }
+}
+impl I32WrappingAdd of core::num::traits::WrappingAdd<i32> {
+    #[inline(always)]
+    fn wrapping_add(self: i32, v: i32) -> i32 {
+        i32_wrapping_add(self, v)
+    }
+}
+impl I64WrappingAdd of core::num::traits::WrappingAdd<i64> {
+    #[inline(always)]
+    fn wrapping_add(self: i64, v: i64) -> i64 {
+        i64_wrapping_add(self, v)
+    }
+}
+impl I128WrappingAdd of core::num::traits::WrappingAdd<i128> {
+    #[inline(always)]
+    fn wrapping_add(self: i128, v: i128) -> i128 {
+        i128_wrapping_add(self, v)
+    }
+}
 
 /// WrappingSub implementations
 impl U8WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<u8>;
@@ -3062,11 +3119,100 @@ impl U32WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappin
 impl U64WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<u64>;
 impl U128WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<u128>;
 impl U256WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<u256>;
-impl I8WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<i8>;
-impl I16WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<i16>;
-impl I32WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<i32>;
-impl I64WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<i64>;
-impl I128WrappingSub = core::num::traits::ops::wrapping::overflow_based::TWrappingSub<i128>;
+impl I8WrappingSub of core::num::traits::WrappingSub<i8> {
+    #[inline(always)]
+    fn wrapping_sub(self: i8, v: i8) -> i8 {
+        i8_wrapping_sub(self, v)
+    }
+}
+impl I16WrappingSub of core::num::traits::WrappingSub<i16> {
+    #[inline(always)]
+    fn wrapping_sub(self: i16, v: i16) -> i16 {
+        i16_wrapping_sub(self, v)
+    }
+}
+impl I32WrappingSub of core::num::traits::WrappingSub<i32> {
+    #[inline(always)]
+    fn wrapping_sub(self: i32, v: i32) -> i32 {
+        i32_wrapping_sub(self, v)
+    }
+}
+impl I64WrappingSub of core::num::traits::WrappingSub<i64> {
+    #[inline(always)]
+    fn wrapping_sub(self: i64, v: i64) -> i64 {
+        i64_wrapping_sub(self, v)
+    }
+}
+impl I128WrappingSub of core::num::traits::WrappingSub<i128> {
+    #[inline(always)]
+    fn wrapping_sub(self: i128, v: i128) -> i128 {
+        i128_wrapping_sub(self, v)
+    }
+}
+
+/// SaturatingAdd implementations
+impl I8SaturatingAdd of core::num::traits::SaturatingAdd<i8> {
+    #[inline(always)]
+    fn saturating_add(self: i8, v: i8) -> i8 {
+        i8_saturating_add(self, v)
+    }
+}
+impl I16SaturatingAdd of core::num::traits::SaturatingAdd<i16> {
+    #[inline(always)]
+    fn saturating_add(self: i16, v: i16) -> i16 {
+        i16_saturating_add(self, v)
+    }
+}
+impl I32SaturatingAdd of core::num::traits::SaturatingAdd<i32> {
+    #[inline(always)]
+    fn saturating_add(self: i32, v: i32) -> i32 {
+        i32_saturating_add(self, v)
+    }
+}
+impl I64SaturatingAdd of core::num::traits::SaturatingAdd<i64> {
+    #[inline(always)]
+    fn saturating_add(self: i64, v: i64) -> i64 {
+        i64_saturating_add(self, v)
+    }
+}
+impl I128SaturatingAdd of core::num::traits::SaturatingAdd<i128> {
+    #[inline(always)]
+    fn saturating_add(self: i128, v: i128) -> i128 {
+        i128_saturating_add(self, v)
+    }
+}
+
+/// SaturatingSub implementations
+impl I8SaturatingSub of core::num::traits::SaturatingSub<i8> {
+    #[inline(always)]
+    fn saturating_sub(self: i8, v: i8) -> i8 {
+        i8_saturating_sub(self, v)
+    }
+}
+impl I16SaturatingSub of core::num::traits::SaturatingSub<i16> {
+    #[inline(always)]
+    fn saturating_sub(self: i16, v: i16) -> i16 {
+        i16_saturating_sub(self, v)
+    }
+}
+impl I32SaturatingSub of core::num::traits::SaturatingSub<i32> {
+    #[inline(always)]
+    fn saturating_sub(self: i32, v: i32) -> i32 {
+        i32_saturating_sub(self, v)
+    }
+}
+impl I64SaturatingSub of core::num::traits::SaturatingSub<i64> {
+    #[inline(always)]
+    fn saturating_sub(self: i64, v: i64) -> i64 {
+        i64_saturating_sub(self, v)
+    }
+}
+impl I128SaturatingSub of core::num::traits::SaturatingSub<i128> {
+    #[inline(always)]
+    fn saturating_sub(self: i128, v: i128) -> i128 {
+        i128_saturating_sub(self, v)
+    }
+}
 
 /// WrappingMul implementations
 impl U8WrappingMul = core::num::traits::ops::wrapping::overflow_based::TWrappingMul<u8>;
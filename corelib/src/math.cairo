@@ -1,7 +1,7 @@
 use core::zeroable::{IsZeroResult, NonZeroIntoImpl, Zeroable};
 use core::traits::{Into, TryInto};
 use core::option::OptionTrait;
-use core::integer::{u256_wide_mul, u512_safe_div_rem_by_u256, U128MulGuarantee};
+use core::integer::U128MulGuarantee;
 
 // TODO(yuval): use signed integers once supported.
 // TODO(yuval): use a single impl of a trait with associated impls, once associated impls are
@@ -114,11 +114,47 @@ pub fn u256_div_mod_n(a: u256, b: u256, n: NonZero<u256>) -> Option<u256> {
 }
 
 /// Returns `a * b (mod n)`.
+/// Additionally returns several `U128MulGuarantee`s that are required for validating the
+/// calculation.
+extern fn u256_guarantee_mul_mod_n(
+    a: u256, b: u256, n: NonZero<u256>
+) -> (
+    u256,
+    U128MulGuarantee,
+    U128MulGuarantee,
+    U128MulGuarantee,
+    U128MulGuarantee,
+    U128MulGuarantee,
+    U128MulGuarantee,
+    U128MulGuarantee,
+    U128MulGuarantee,
+    U128MulGuarantee
+) implicits(RangeCheck) nopanic;
+
+/// Returns `a * b (mod n)`.
+#[inline(always)]
 pub fn u256_mul_mod_n(a: u256, b: u256, n: NonZero<u256>) -> u256 {
-    let (_, r) = u512_safe_div_rem_by_u256(u256_wide_mul(a, b), n);
+    let (r, _, _, _, _, _, _, _, _, _) = u256_guarantee_mul_mod_n(a, b, n);
     r
 }
 
+/// Returns `base ^ exp (mod n)`.
+pub fn u256_pow_mod_n(base: u256, exp: u256, n: NonZero<u256>) -> u256 {
+    let (_, mut result) = DivRem::div_rem(1_u256, n);
+    let (_, mut base) = DivRem::div_rem(base, n);
+    let mut exp = exp;
+    // Square and multiply, keeping `result * base ^ exp (mod n)` unchanged.
+    while exp != 0 {
+        let (rest, bit) = DivRem::div_rem(exp, 2_u256.try_into().unwrap());
+        if bit != 0 {
+            result = u256_mul_mod_n(result, base, n);
+        }
+        base = u256_mul_mod_n(base, base, n);
+        exp = rest;
+    };
+    result
+}
+
 // === Oneable ===
 
 trait Oneable<T> {
@@ -10,3 +10,4 @@ pub use bit_size::BitSize;
 pub mod ops;
 pub use ops::overflowing::{OverflowingAdd, OverflowingSub, OverflowingMul};
 pub use ops::wrapping::{WrappingAdd, WrappingSub, WrappingMul};
+pub use ops::saturating::{SaturatingAdd, SaturatingSub};
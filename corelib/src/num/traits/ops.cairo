@@ -1,2 +1,3 @@
 pub mod overflowing;
+pub mod saturating;
 pub mod wrapping;
@@ -0,0 +1,13 @@
+/// Performs addition that saturates at the bounds of the type on overflow.
+pub trait SaturatingAdd<T> {
+    /// Saturating addition. Computes `self + other`, saturating at the relevant high or low
+    /// boundary of the type.
+    fn saturating_add(self: T, v: T) -> T;
+}
+
+/// Performs subtraction that saturates at the bounds of the type on overflow.
+pub trait SaturatingSub<T> {
+    /// Saturating subtraction. Computes `self - other`, saturating at the relevant high or low
+    /// boundary of the type.
+    fn saturating_sub(self: T, v: T) -> T;
+}
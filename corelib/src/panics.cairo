@@ -17,3 +17,16 @@ pub fn panic_with_byte_array(err: @ByteArray) -> core::never {
     err.serialize(ref serialized);
     panic(serialized)
 }
+
+/// The selector of the revert-with-reason error data, i.e. the selector of `Error(string)`.
+pub const REVERT_WITH_REASON_SELECTOR: felt252 = 0x08c379a0;
+
+/// Encodes the panic data `data` with the revert-with-reason ABI. That is, returns
+/// `REVERT_WITH_REASON_SELECTOR`, followed by the length of `data`, and then `data` itself.
+///
+/// Used as the panic data of every `panic` call when compiling with the revert panic ABI.
+pub fn encode_revert_reason(data: Array<felt252>) -> Array<felt252> {
+    let mut encoded = array![REVERT_WITH_REASON_SELECTOR, data.len().into()];
+    encoded.append_span(data.span());
+    encoded
+}
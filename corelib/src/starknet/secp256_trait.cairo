@@ -40,6 +40,11 @@ pub trait Secp256PointTrait<Secp256Point> {
     fn get_coordinates(self: Secp256Point) -> SyscallResult<(u256, u256)>;
     fn add(self: Secp256Point, other: Secp256Point) -> SyscallResult<Secp256Point>;
     fn mul(self: Secp256Point, scalar: u256) -> SyscallResult<Secp256Point>;
+    /// Computes `self * scalar + other * other_scalar` in a single system call.
+    /// Only allowed by the experimental libfuncs list, as the Starknet OS does not support it yet.
+    fn mul_add(
+        self: Secp256Point, scalar: u256, other: Secp256Point, other_scalar: u256
+    ) -> SyscallResult<Secp256Point>;
 }
 
 /// Checks whether `value` is in the range [1, N), where N is the size of the curve.
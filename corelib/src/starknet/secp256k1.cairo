@@ -47,6 +47,11 @@ pub(crate) impl Secp256k1PointImpl of Secp256PointTrait<Secp256k1Point> {
     fn mul(self: Secp256k1Point, scalar: u256) -> SyscallResult<Secp256k1Point> {
         secp256k1_mul_syscall(self, scalar)
     }
+    fn mul_add(
+        self: Secp256k1Point, scalar: u256, other: Secp256k1Point, other_scalar: u256
+    ) -> SyscallResult<Secp256k1Point> {
+        secp256k1_mul_add_syscall(self, scalar, other, other_scalar)
+    }
 }
 
 /// Creates a secp256k1 EC point from the given x and y coordinates.
@@ -63,6 +68,11 @@ extern fn secp256k1_add_syscall(
 extern fn secp256k1_mul_syscall(
     p: Secp256k1Point, scalar: u256
 ) -> SyscallResult<Secp256k1Point> implicits(GasBuiltin, System) nopanic;
+/// Computes the multi-scalar product `p0 * scalar0 + p1 * scalar1` of secp256k1 EC points.
+/// Only allowed by the experimental libfuncs list, as the Starknet OS does not support it yet.
+extern fn secp256k1_mul_add_syscall(
+    p0: Secp256k1Point, scalar0: u256, p1: Secp256k1Point, scalar1: u256
+) -> SyscallResult<Secp256k1Point> implicits(GasBuiltin, System) nopanic;
 
 /// Computes the point on the secp256k1 curve that matches the given `x` coordinate, if such exists.
 /// Out of the two possible y's, chooses according to `y_parity`.
@@ -44,6 +44,11 @@ pub(crate) impl Secp256r1PointImpl of Secp256PointTrait<Secp256r1Point> {
     fn mul(self: Secp256r1Point, scalar: u256) -> SyscallResult<Secp256r1Point> {
         secp256r1_mul_syscall(self, scalar)
     }
+    fn mul_add(
+        self: Secp256r1Point, scalar: u256, other: Secp256r1Point, other_scalar: u256
+    ) -> SyscallResult<Secp256r1Point> {
+        secp256r1_mul_add_syscall(self, scalar, other, other_scalar)
+    }
 }
 
 /// Creates a secp256r1 EC point from the given x and y coordinates.
@@ -60,6 +65,11 @@ extern fn secp256r1_add_syscall(
 extern fn secp256r1_mul_syscall(
     p: Secp256r1Point, scalar: u256
 ) -> SyscallResult<Secp256r1Point> implicits(GasBuiltin, System) nopanic;
+/// Computes the multi-scalar product `p0 * scalar0 + p1 * scalar1` of secp256r1 EC points.
+/// Only allowed by the experimental libfuncs list, as the Starknet OS does not support it yet.
+extern fn secp256r1_mul_add_syscall(
+    p0: Secp256r1Point, scalar0: u256, p1: Secp256r1Point, scalar1: u256
+) -> SyscallResult<Secp256r1Point> implicits(GasBuiltin, System) nopanic;
 
 /// Computes the point on the secp256r1 curve that matches the given `x` coordinate, if such exists.
 /// Out of the two possible y's, chooses according to `y_parity`.
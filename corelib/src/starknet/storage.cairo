@@ -69,3 +69,208 @@ pub impl StorageMapMemberAccessImpl<
         )
     }
 }
+
+/// A path to a value in the contract's storage, through which the value can be read and written.
+/// Can be passed to functions for reading and writing the value, so they may operate on any storage
+/// location holding a value of type `T`.
+pub struct StoragePath<T> {
+    base: starknet::StorageBaseAddress,
+}
+impl StoragePathCopy<T> of Copy<StoragePath<T>>;
+impl StoragePathDrop<T> of Drop<StoragePath<T>>;
+
+/// Trait for accessing the value a storage path points to.
+/// Writable paths can't be created from an address, and are only obtained from a storage member
+/// through `path_mut`.
+pub trait StoragePathTrait<T> {
+    /// Returns the address of the value.
+    fn address(self: @StoragePath<T>) -> starknet::StorageBaseAddress;
+    /// Returns a read-only path to the value.
+    fn read_only(self: StoragePath<T>) -> ReadOnlyStoragePath<T>;
+    fn read(self: @StoragePath<T>) -> T;
+    fn write(self: StoragePath<T>, value: T);
+}
+
+pub impl StoragePathImpl<T, +starknet::Store<T>> of StoragePathTrait<T> {
+    fn address(self: @StoragePath<T>) -> starknet::StorageBaseAddress {
+        *self.base
+    }
+    fn read_only(self: StoragePath<T>) -> ReadOnlyStoragePath<T> {
+        ReadOnlyStoragePathTrait::new(self.base)
+    }
+    fn read(self: @StoragePath<T>) -> T {
+        (*self).read_only().read()
+    }
+    fn write(self: StoragePath<T>, value: T) {
+        // Only address_domain 0 is currently supported.
+        let address_domain = 0_u32;
+        starknet::SyscallResultTrait::unwrap_syscall(
+            starknet::Store::<T>::write(address_domain, self.base, value)
+        )
+    }
+}
+
+/// A read-only path to a value in the contract's storage.
+/// Obtained from a snapshot of the contract's state, so the value can only be read through it.
+pub struct ReadOnlyStoragePath<T> {
+    base: starknet::StorageBaseAddress,
+}
+impl ReadOnlyStoragePathCopy<T> of Copy<ReadOnlyStoragePath<T>>;
+impl ReadOnlyStoragePathDrop<T> of Drop<ReadOnlyStoragePath<T>>;
+
+/// Trait for reading the value a read-only storage path points to.
+pub trait ReadOnlyStoragePathTrait<T> {
+    /// Creates a read-only path to the value stored at `base`.
+    fn new(base: starknet::StorageBaseAddress) -> ReadOnlyStoragePath<T>;
+    /// Returns the address of the value.
+    fn address(self: @ReadOnlyStoragePath<T>) -> starknet::StorageBaseAddress;
+    fn read(self: @ReadOnlyStoragePath<T>) -> T;
+}
+
+pub impl ReadOnlyStoragePathImpl<T, +starknet::Store<T>> of ReadOnlyStoragePathTrait<T> {
+    fn new(base: starknet::StorageBaseAddress) -> ReadOnlyStoragePath<T> {
+        ReadOnlyStoragePath { base }
+    }
+    fn address(self: @ReadOnlyStoragePath<T>) -> starknet::StorageBaseAddress {
+        *self.base
+    }
+    fn read(self: @ReadOnlyStoragePath<T>) -> T {
+        // Only address_domain 0 is currently supported.
+        let address_domain = 0_u32;
+        starknet::SyscallResultTrait::unwrap_syscall(
+            starknet::Store::<T>::read(address_domain, *self.base)
+        )
+    }
+}
+
+/// A path to a mapping in the contract's storage, from keys of type `K` to values of type `V`,
+/// through which the entries of the mapping can be read and written.
+/// Can be passed to functions for reading and writing the entries of the mapping, so they may
+/// operate on any storage mapping of the same types.
+pub struct StorageMapPath<K, V> {
+    base: felt252,
+}
+impl StorageMapPathCopy<K, V> of Copy<StorageMapPath<K, V>>;
+impl StorageMapPathDrop<K, V> of Drop<StorageMapPath<K, V>>;
+
+/// Trait for accessing the entries of a mapping a storage path points to.
+/// Writable paths can't be created from an address, and are only obtained from a storage member
+/// through `path_mut`.
+pub trait StorageMapPathTrait<K, V> {
+    /// Returns the path to the value of the entry of `key`.
+    fn entry(self: @StorageMapPath<K, V>, key: K) -> StoragePath<V>;
+    /// Returns a read-only path to the mapping.
+    fn read_only(self: StorageMapPath<K, V>) -> ReadOnlyStorageMapPath<K, V>;
+    fn read(self: @StorageMapPath<K, V>, key: K) -> V;
+    fn write(self: StorageMapPath<K, V>, key: K, value: V);
+}
+
+pub impl StorageMapPathImpl<
+    K, V, +core::hash::LegacyHash<K>, +starknet::Store<V>, +Drop<V>
+> of StorageMapPathTrait<K, V> {
+    fn entry(self: @StorageMapPath<K, V>, key: K) -> StoragePath<V> {
+        let read_only = ReadOnlyStorageMapPath::<K, V> { base: *self.base };
+        StoragePath { base: read_only.entry(key).address() }
+    }
+    fn read_only(self: StorageMapPath<K, V>) -> ReadOnlyStorageMapPath<K, V> {
+        ReadOnlyStorageMapPathTrait::new(self.base)
+    }
+    fn read(self: @StorageMapPath<K, V>, key: K) -> V {
+        let read_only = ReadOnlyStorageMapPath::<K, V> { base: *self.base };
+        read_only.read(key)
+    }
+    fn write(self: StorageMapPath<K, V>, key: K, value: V) {
+        self.entry(key).write(value)
+    }
+}
+
+/// A read-only path to a mapping in the contract's storage, from keys of type `K` to values of
+/// type `V`.
+/// Obtained from a snapshot of the contract's state, so the entries can only be read through it.
+pub struct ReadOnlyStorageMapPath<K, V> {
+    base: felt252,
+}
+impl ReadOnlyStorageMapPathCopy<K, V> of Copy<ReadOnlyStorageMapPath<K, V>>;
+impl ReadOnlyStorageMapPathDrop<K, V> of Drop<ReadOnlyStorageMapPath<K, V>>;
+
+/// Trait for reading the entries of a mapping a read-only storage path points to.
+pub trait ReadOnlyStorageMapPathTrait<K, V> {
+    /// Creates a read-only path to the mapping whose entries are located by hashing `base` with
+    /// the key.
+    fn new(base: felt252) -> ReadOnlyStorageMapPath<K, V>;
+    /// Returns the read-only path to the value of the entry of `key`.
+    fn entry(self: @ReadOnlyStorageMapPath<K, V>, key: K) -> ReadOnlyStoragePath<V>;
+    fn read(self: @ReadOnlyStorageMapPath<K, V>, key: K) -> V;
+}
+
+pub impl ReadOnlyStorageMapPathImpl<
+    K, V, +core::hash::LegacyHash<K>, +starknet::Store<V>
+> of ReadOnlyStorageMapPathTrait<K, V> {
+    fn new(base: felt252) -> ReadOnlyStorageMapPath<K, V> {
+        ReadOnlyStorageMapPath { base }
+    }
+    fn entry(self: @ReadOnlyStorageMapPath<K, V>, key: K) -> ReadOnlyStoragePath<V> {
+        ReadOnlyStoragePathTrait::new(
+            starknet::storage_access::storage_base_address_from_felt252(
+                core::hash::LegacyHash::<K>::hash(*self.base, key)
+            )
+        )
+    }
+    fn read(self: @ReadOnlyStorageMapPath<K, V>, key: K) -> V {
+        self.entry(key).read()
+    }
+}
+
+/// Trait for getting a path to any contract/component storage member.
+pub trait StorageMemberPathTrait<TMemberState, TValue> {
+    /// Returns a read-only path to the member.
+    fn path(self: @TMemberState) -> ReadOnlyStoragePath<TValue>;
+    /// Returns a path to the member through which it can also be written.
+    fn path_mut(ref self: TMemberState) -> StoragePath<TValue>;
+}
+
+/// Implementation of StorageMemberPathTrait for types that implement StorageMemberAddressTrait.
+pub impl StorageMemberPathImpl<
+    TMemberState,
+    TValue,
+    +StorageMemberAddressTrait<TMemberState, TValue>,
+    +starknet::Store<TValue>,
+    +Drop<TMemberState>,
+> of StorageMemberPathTrait<TMemberState, TValue> {
+    fn path(self: @TMemberState) -> ReadOnlyStoragePath<TValue> {
+        ReadOnlyStoragePathTrait::new(self.address())
+    }
+    fn path_mut(ref self: TMemberState) -> StoragePath<TValue> {
+        StoragePath { base: self.address() }
+    }
+}
+
+/// Trait for getting a path to any contract/component mapping storage member.
+pub trait StorageMapMemberPathTrait<TMemberState, TKey, TValue> {
+    /// Returns a read-only path to the mapping member.
+    fn path(self: @TMemberState) -> ReadOnlyStorageMapPath<TKey, TValue>;
+    /// Returns a path to the mapping member through which its entries can also be written.
+    fn path_mut(ref self: TMemberState) -> StorageMapPath<TKey, TValue>;
+}
+
+/// Trait for getting the base of the addresses of the entries of any contract/component mapping
+/// storage member, which are located by hashing the base with the key.
+pub trait StorageMapMemberBaseTrait<TMemberState, TKey, TValue> {
+    fn base(self: @TMemberState) -> felt252;
+}
+
+/// Implementation of StorageMapMemberPathTrait for types that implement StorageMapMemberBaseTrait.
+pub impl StorageMapMemberPathImpl<
+    TMemberState,
+    TKey,
+    TValue,
+    +StorageMapMemberBaseTrait<TMemberState, TKey, TValue>,
+    +Drop<TMemberState>,
+> of StorageMapMemberPathTrait<TMemberState, TKey, TValue> {
+    fn path(self: @TMemberState) -> ReadOnlyStorageMapPath<TKey, TValue> {
+        ReadOnlyStorageMapPath { base: self.base() }
+    }
+    fn path_mut(ref self: TMemberState) -> StorageMapPath<TKey, TValue> {
+        StorageMapPath { base: self.base() }
+    }
+}
@@ -14,6 +14,7 @@ mod hash_test;
 mod integer_test;
 mod keccak_test;
 mod sha256_test;
+mod blake2s_test;
 mod num_test;
 mod math_test;
 mod nullable_test;
@@ -0,0 +1,26 @@
+use core::hash::blake2s::compute_blake2s_256;
+
+#[test]
+fn test_blake2s_empty() {
+    let [a, b, c, d, e, f, g, h] = compute_blake2s_256(array![].span(), 0);
+    assert_eq!((a, b, c, d), (0x307A2169, 0x94809079, 0xD02111E1, 0x7C4A3542));
+    assert_eq!((e, f, g, h), (0x48B6551F, 0x1EA5A12C, 0xFD0D251B, 0xF9EED01E));
+}
+
+#[test]
+fn test_blake2s_single_block() {
+    let msg = [0x00636261, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let [a, b, c, d, e, f, g, h] = compute_blake2s_256(array![msg].span(), 3);
+    assert_eq!((a, b, c, d), (0x8C5E8C50, 0xE2147C32, 0xA32BA7E1, 0x2F45EB4E));
+    assert_eq!((e, f, g, h), (0x208B4537, 0x293AD69E, 0x4C9B994D, 0x82596786));
+}
+
+#[test]
+fn test_blake2s_multiple_blocks() {
+    let w = 0x61616161;
+    let first = [w, w, w, w, w, w, w, w, w, w, w, w, w, w, w, w];
+    let second = [w, w, w, w, w, w, w, w, w, 0, 0, 0, 0, 0, 0, 0];
+    let [a, b, c, d, e, f, g, h] = compute_blake2s_256(array![first, second].span(), 100);
+    assert_eq!((a, b, c, d), (0xFE244F21, 0x85EB1811, 0x8E235044, 0x22BEBE11));
+    assert_eq!((e, f, g, h), (0x7E93E3D2, 0x967C5CD8, 0x1010C0C6, 0xD32A756B));
+}
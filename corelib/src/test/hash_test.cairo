@@ -87,3 +87,27 @@ fn test_user_defined_hash() {
         'Bad hash of StructForHash',
     );
 }
+
+#[test]
+fn test_byte_array_hash() {
+    let mut ba: ByteArray = "0123456789012345678901234567890";
+    ba.append_word('ab', 2);
+    assert_eq(
+        @PoseidonTrait::new().update_with(ba).finalize(),
+        @PoseidonTrait::new()
+            .update(1)
+            .update('0123456789012345678901234567890')
+            .update('ab')
+            .update(2)
+            .finalize(),
+        'Bad hash of ByteArray',
+    );
+    let empty: ByteArray = Default::default();
+    let mut zero: ByteArray = Default::default();
+    zero.append_byte(0);
+    assert_ne(
+        @PoseidonTrait::new().update_with(empty).finalize(),
+        @PoseidonTrait::new().update_with(zero).finalize(),
+        'Bad hash of empty ByteArray',
+    );
+}
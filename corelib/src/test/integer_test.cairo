@@ -2,7 +2,8 @@ use core::{
     integer,
     integer::{
         BoundedInt, u128_sqrt, u128_wrapping_sub, u16_sqrt, u256_sqrt, u256_wide_mul, u32_sqrt,
-        u512_safe_div_rem_by_u256, u512, u64_sqrt, u8_sqrt
+        u256_safe_div_rem_by_u128, u512_safe_div_rem_by_u256, u512, u512_overflowing_add,
+        u512_overflowing_sub, u64_sqrt, u8_sqrt
     }
 };
 use core::test::test_utils::{assert_eq, assert_ne, assert_le, assert_lt, assert_gt, assert_ge};
@@ -795,6 +796,79 @@ fn test_u512_safe_div_rem_by_u256() {
     assert(r == 0x1e0eb905027d0150d2618bbd71844d50, 'large rem failed');
 }
 
+#[test]
+fn test_u256_safe_div_rem_by_u128() {
+    let (q, r) = u256_safe_div_rem_by_u128(0, 1_u128.try_into().unwrap());
+    assert(q == 0, '0 / 1 != 0');
+    assert(r == 0, '0 % 1 != 0');
+    let (q, r) = u256_safe_div_rem_by_u128(BoundedInt::max(), 1_u128.try_into().unwrap());
+    assert(q == BoundedInt::max(), 'MAX / 1 != MAX');
+    assert(r == 0, 'MAX % 1 != 0');
+    let (q, r) = u256_safe_div_rem_by_u128(
+        0x5445544554454454444443443343333333233223222222122112111111011001,
+        0x1001101111112112_u128.try_into().unwrap()
+    );
+    assert(q == 0x543fbbaffffff010ad6b9866291d7f029906e7e65801b8f1c, 'large div failed');
+    assert(r == 0xee0813924c26409, 'large rem failed');
+}
+
+#[test]
+fn test_u512_overflowing_add() {
+    let zero = u512 { limb0: 0, limb1: 0, limb2: 0, limb3: 0 };
+    let one = u512 { limb0: 1, limb1: 0, limb2: 0, limb3: 0 };
+    let max = u512 {
+        limb0: BoundedInt::max(),
+        limb1: BoundedInt::max(),
+        limb2: BoundedInt::max(),
+        limb3: BoundedInt::max()
+    };
+    assert!(u512_overflowing_add(zero, one) == Result::Ok(one));
+    assert!(
+        u512_overflowing_add(
+            u512 { limb0: BoundedInt::max(), limb1: 0, limb2: BoundedInt::max(), limb3: 0 }, one
+        ) == Result::Ok(u512 { limb0: 0, limb1: 1, limb2: BoundedInt::max(), limb3: 0 })
+    );
+    assert!(u512_overflowing_add(max, one) == Result::Err(zero));
+    assert!(
+        u512_overflowing_add(
+            max, max
+        ) == Result::Err(
+            u512 {
+                limb0: BoundedInt::max() - 1,
+                limb1: BoundedInt::max(),
+                limb2: BoundedInt::max(),
+                limb3: BoundedInt::max()
+            }
+        )
+    );
+}
+
+#[test]
+fn test_u512_overflowing_sub() {
+    let zero = u512 { limb0: 0, limb1: 0, limb2: 0, limb3: 0 };
+    let one = u512 { limb0: 1, limb1: 0, limb2: 0, limb3: 0 };
+    let max = u512 {
+        limb0: BoundedInt::max(),
+        limb1: BoundedInt::max(),
+        limb2: BoundedInt::max(),
+        limb3: BoundedInt::max()
+    };
+    assert!(u512_overflowing_sub(one, one) == Result::Ok(zero));
+    assert!(
+        u512_overflowing_sub(
+            u512 { limb0: 0, limb1: 0, limb2: 0, limb3: 1 }, one
+        ) == Result::Ok(
+            u512 {
+                limb0: BoundedInt::max(),
+                limb1: BoundedInt::max(),
+                limb2: BoundedInt::max(),
+                limb3: 0
+            }
+        )
+    );
+    assert!(u512_overflowing_sub(zero, one) == Result::Err(max));
+}
+
 #[test]
 fn test_u512_try_into_u256() {
     assert!(
@@ -1,5 +1,6 @@
 use core::option::OptionTrait;
 use core::math;
+use core::integer::BoundedInt;
 
 #[test]
 fn test_egcd() {
@@ -78,6 +79,35 @@ fn test_u256_div_mod_n() {
     );
 }
 
+#[test]
+fn test_u256_mul_mod_n() {
+    assert(math::u256_mul_mod_n(3, 4, 7) == 5, '3 * 4 != 5 (7)');
+    assert(math::u256_mul_mod_n(0, 4, 7) == 0, '0 * 4 != 0 (7)');
+    assert(math::u256_mul_mod_n(9, 5, 1) == 0, '9 * 5 != 0 (1)');
+    assert(
+        math::u256_mul_mod_n(BoundedInt::max(), BoundedInt::max(), 7) == 1, 'MAX * MAX != 1 (7)'
+    );
+    let p: u256 = 0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f;
+    assert(
+        math::u256_mul_mod_n(
+            BoundedInt::max(), BoundedInt::max(), p.try_into().unwrap()
+        ) == 0x1000007a0000e8900,
+        'MAX * MAX (p)'
+    );
+    let a = 0xfa855081cc80656250605b2ecd7958ba4f0aa6799053da0d68bf76f2484decc6;
+    let b = 0xe8e94a59a951af1b4c8cbd45fb8d01c1dd946de2533e3ad18845f9dbb6d12f4f;
+    assert(
+        math::u256_mul_mod_n(
+            a, b, 0xa3db605888ac3cd19e70c5b52220ad693566b996ef078e907578fec7758dabc9
+        ) == 0x679cff33379a53347d24a9f2a8cf3df1f049823df974e6ce2a573fb4c4dba5e0,
+        'Random large values'
+    );
+    assert(
+        math::u256_mul_mod_n(a, b, 0x1001101111112112) == 0x14933c4ead057f8,
+        'Random large values (u64)'
+    );
+}
+
 #[test]
 fn test_u256_inv_mod() {
     assert(math::u256_inv_mod(5, 24).unwrap().into() == 5_u256, 'inv_mov(5, 24) != 5');
@@ -121,3 +151,17 @@ fn test_u256_inv_mod() {
         0x74c5ef92be07ee4ad43ae8ca337390e4a5dfdbf4f1a5f09cdf412ab7ce343503;
     assert(math::u256_inv_mod(very_large_gcd.into(), very_large_gcd).is_none(), 'gcd ~ 2**256');
 }
+
+#[test]
+fn test_u256_pow_mod_n() {
+    assert(math::u256_pow_mod_n(2, 10, 1000) == 24, '2 ^ 10 != 24 (1000)');
+    assert(math::u256_pow_mod_n(3, 0, 7) == 1, '3 ^ 0 != 1 (7)');
+    assert(math::u256_pow_mod_n(0, 0, 7) == 1, '0 ^ 0 != 1 (7)');
+    assert(math::u256_pow_mod_n(0, 5, 7) == 0, '0 ^ 5 != 0 (7)');
+    assert(math::u256_pow_mod_n(9, 3, 1) == 0, '9 ^ 3 != 0 (1)');
+    assert(math::u256_pow_mod_n(10, 6, 7) == 1, '10 ^ 6 != 1 (7)');
+    let p: u256 = 0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f;
+    let p_nz = p.try_into().unwrap();
+    assert(math::u256_pow_mod_n(5, p - 1, p_nz) == 1, 'Fermat little theorem');
+    assert(math::u256_pow_mod_n(p - 1, p - 2, p_nz) == p - 1, '-1 ^ (p - 2) != -1 (p)');
+}
@@ -1,6 +1,7 @@
 use core::num::traits::BitSize;
 use core::num::traits::{
-    OverflowingAdd, OverflowingSub, OverflowingMul, WrappingAdd, WrappingSub, WrappingMul
+    OverflowingAdd, OverflowingSub, OverflowingMul, WrappingAdd, WrappingSub, WrappingMul,
+    SaturatingAdd, SaturatingSub
 };
 use core::integer::BoundedInt;
 
@@ -220,6 +221,106 @@ fn test_wrapping_sub_negative_signed_integers() {
     assert!(BoundedInt::<i128>::max().wrapping_sub(-1) == BoundedInt::<i128>::min());
 }
 
+#[test]
+fn test_saturating_add_positive_signed_integers() {
+    assert!(1_i8.saturating_add(2) == 3);
+    assert!(BoundedInt::<i8>::max().saturating_add(1) == BoundedInt::<i8>::max());
+    assert!(BoundedInt::<i8>::max().saturating_add(BoundedInt::<i8>::max()) == 0x7f);
+    assert!(1_i16.saturating_add(2) == 3);
+    assert!(BoundedInt::<i16>::max().saturating_add(1) == BoundedInt::<i16>::max());
+    assert!(BoundedInt::<i16>::max().saturating_add(BoundedInt::<i16>::max()) == 0x7fff);
+    assert!(1_i32.saturating_add(2) == 3);
+    assert!(BoundedInt::<i32>::max().saturating_add(1) == BoundedInt::<i32>::max());
+    assert!(BoundedInt::<i32>::max().saturating_add(BoundedInt::<i32>::max()) == 0x7fffffff);
+    assert!(1_i64.saturating_add(2) == 3);
+    assert!(BoundedInt::<i64>::max().saturating_add(1) == BoundedInt::<i64>::max());
+    assert!(
+        BoundedInt::<i64>::max().saturating_add(BoundedInt::<i64>::max()) == 0x7fffffffffffffff
+    );
+    assert!(1_i128.saturating_add(2) == 3);
+    assert!(BoundedInt::<i128>::max().saturating_add(1) == BoundedInt::<i128>::max());
+    assert!(
+        BoundedInt::<i128>::max()
+            .saturating_add(BoundedInt::<i128>::max()) == 0x7fffffffffffffffffffffffffffffff
+    );
+}
+
+#[test]
+fn test_saturating_add_negative_signed_integers() {
+    assert!((-1_i8).saturating_add(-2) == -3);
+    assert!(BoundedInt::<i8>::min().saturating_add(-1) == BoundedInt::<i8>::min());
+    assert!(BoundedInt::<i8>::min().saturating_add(BoundedInt::<i8>::max()) == -1);
+    assert!((-1_i16).saturating_add(-2) == -3);
+    assert!(BoundedInt::<i16>::min().saturating_add(-1) == BoundedInt::<i16>::min());
+    assert!(BoundedInt::<i16>::min().saturating_add(BoundedInt::<i16>::max()) == -1);
+    assert!((-1_i32).saturating_add(-2) == -3);
+    assert!(BoundedInt::<i32>::min().saturating_add(-1) == BoundedInt::<i32>::min());
+    assert!(BoundedInt::<i32>::min().saturating_add(BoundedInt::<i32>::max()) == -1);
+    assert!((-1_i64).saturating_add(-2) == -3);
+    assert!(BoundedInt::<i64>::min().saturating_add(-1) == BoundedInt::<i64>::min());
+    assert!(BoundedInt::<i64>::min().saturating_add(BoundedInt::<i64>::max()) == -1);
+    assert!((-1_i128).saturating_add(-2) == -3);
+    assert!(BoundedInt::<i128>::min().saturating_add(-1) == BoundedInt::<i128>::min());
+    assert!(BoundedInt::<i128>::min().saturating_add(BoundedInt::<i128>::max()) == -1);
+}
+
+#[test]
+fn test_saturating_sub_positive_signed_integers() {
+    assert!(3_i8.saturating_sub(2) == 1);
+    assert!(BoundedInt::<i8>::max().saturating_sub(-1) == BoundedInt::<i8>::max());
+    assert!(BoundedInt::<i8>::max().saturating_sub(BoundedInt::<i8>::min()) == 0x7f);
+    assert!(3_i16.saturating_sub(2) == 1);
+    assert!(BoundedInt::<i16>::max().saturating_sub(-1) == BoundedInt::<i16>::max());
+    assert!(BoundedInt::<i16>::max().saturating_sub(BoundedInt::<i16>::min()) == 0x7fff);
+    assert!(3_i32.saturating_sub(2) == 1);
+    assert!(BoundedInt::<i32>::max().saturating_sub(-1) == BoundedInt::<i32>::max());
+    assert!(BoundedInt::<i32>::max().saturating_sub(BoundedInt::<i32>::min()) == 0x7fffffff);
+    assert!(3_i64.saturating_sub(2) == 1);
+    assert!(BoundedInt::<i64>::max().saturating_sub(-1) == BoundedInt::<i64>::max());
+    assert!(
+        BoundedInt::<i64>::max().saturating_sub(BoundedInt::<i64>::min()) == 0x7fffffffffffffff
+    );
+    assert!(3_i128.saturating_sub(2) == 1);
+    assert!(BoundedInt::<i128>::max().saturating_sub(-1) == BoundedInt::<i128>::max());
+    assert!(
+        BoundedInt::<i128>::max()
+            .saturating_sub(BoundedInt::<i128>::min()) == 0x7fffffffffffffffffffffffffffffff
+    );
+}
+
+#[test]
+fn test_saturating_sub_negative_signed_integers() {
+    assert!((-3_i8).saturating_sub(-2) == -1);
+    assert!(BoundedInt::<i8>::min().saturating_sub(1) == BoundedInt::<i8>::min());
+    assert!(
+        BoundedInt::<i8>::min().saturating_sub(BoundedInt::<i8>::max()) == BoundedInt::<i8>::min()
+    );
+    assert!((-3_i16).saturating_sub(-2) == -1);
+    assert!(BoundedInt::<i16>::min().saturating_sub(1) == BoundedInt::<i16>::min());
+    assert!(
+        BoundedInt::<i16>::min()
+            .saturating_sub(BoundedInt::<i16>::max()) == BoundedInt::<i16>::min()
+    );
+    assert!((-3_i32).saturating_sub(-2) == -1);
+    assert!(BoundedInt::<i32>::min().saturating_sub(1) == BoundedInt::<i32>::min());
+    assert!(
+        BoundedInt::<i32>::min()
+            .saturating_sub(BoundedInt::<i32>::max()) == BoundedInt::<i32>::min()
+    );
+    assert!((-3_i64).saturating_sub(-2) == -1);
+    assert!(BoundedInt::<i64>::min().saturating_sub(1) == BoundedInt::<i64>::min());
+    assert!(
+        BoundedInt::<i64>::min()
+            .saturating_sub(BoundedInt::<i64>::max()) == BoundedInt::<i64>::min()
+    );
+    assert!((-3_i128).saturating_sub(-2) == -1);
+    assert!(BoundedInt::<i128>::min().saturating_sub(1) == BoundedInt::<i128>::min());
+    assert!(
+        BoundedInt::<i128>::min()
+            .saturating_sub(BoundedInt::<i128>::max()) == BoundedInt::<i128>::min()
+    );
+}
+
 #[test]
 fn test_wrapping_mul_unsigned_integers() {
     assert_eq!(2_u8.wrapping_mul(3), 6);
@@ -144,3 +144,14 @@ fn test_panic_macro_basic_string() {
 fn test_panic_macro_with_input() {
     panic!("some_format({})", 1)
 }
+
+#[test]
+fn test_encode_revert_reason() {
+    assert_eq!(
+        panics::encode_revert_reason(array!['error', 11]),
+        array![panics::REVERT_WITH_REASON_SELECTOR, 2, 'error', 11]
+    );
+    assert_eq!(
+        panics::encode_revert_reason(array![]), array![panics::REVERT_WITH_REASON_SELECTOR, 0]
+    );
+}
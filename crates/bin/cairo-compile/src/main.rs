@@ -2,8 +2,15 @@ use std::fs;
 use std::path::PathBuf;
 
 use anyhow::Context;
-use cairo_lang_compiler::project::check_compiler_path;
-use cairo_lang_compiler::{compile_cairo_project_at_path, CompilerConfig};
+use cairo_lang_compiler::crash_report::CrashReporter;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::explain_error_code;
+use cairo_lang_compiler::progress::ProgressReporter;
+use cairo_lang_compiler::project::{check_compiler_path, setup_project};
+use cairo_lang_compiler::{compile_prepared_db, CompilerConfig};
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::statements_remapping::StatementsRemapping;
 use cairo_lang_utils::logging::init_logging;
 use clap::Parser;
 
@@ -13,7 +20,8 @@ use clap::Parser;
 #[clap(version, verbatim_doc_comment)]
 struct Args {
     /// The Cairo project path.
-    path: PathBuf,
+    #[arg(required_unless_present = "explain")]
+    path: Option<PathBuf>,
     /// Whether path is a single file.
     #[arg(short, long)]
     single_file: bool,
@@ -22,6 +30,33 @@ struct Args {
     /// Replaces sierra ids with human-readable ones.
     #[arg(short, long, default_value_t = false)]
     replace_ids: bool,
+    /// Explains ownership errors in depth - the moves of a moved variable, the impls considered
+    /// for it, and the possible fixes.
+    #[arg(long, default_value_t = false)]
+    explain_ownership: bool,
+    /// Optimizes the program as a whole, after merging the functions of all the crates - inlining
+    /// functions with a single call site, and dropping unused functions of library crates.
+    #[arg(long, default_value_t = false)]
+    whole_program: bool,
+    /// Inlines the Sierra functions with at most the given number of statements into their
+    /// callers, when their code runs straight to a single return.
+    #[arg(long, value_name = "STATEMENTS")]
+    sierra_inlining_threshold: Option<usize>,
+    /// Removes the Sierra variables that are created only to be dropped - along with their drops.
+    #[arg(long, default_value_t = false)]
+    eliminate_redundant_drops: bool,
+    /// A file to write the remapping of the original Sierra statements to the statements of the
+    /// optimized program to, as JSON - for using debug info and profiling data keyed by the
+    /// original statement indices.
+    #[arg(long)]
+    statements_remapping_output: Option<PathBuf>,
+    /// Reports the progress of the compilation - its phases, diagnostics and written files - as
+    /// newline-delimited JSON events to stderr, for build orchestration tools.
+    #[arg(long, default_value_t = false)]
+    json_progress: bool,
+    /// Prints the documentation of the given error code, e.g. `E0001`, instead of compiling.
+    #[arg(long, value_name = "CODE", exclusive = true)]
+    explain: Option<String>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -30,17 +65,70 @@ fn main() -> anyhow::Result<()> {
 
     let args = Args::parse();
 
-    // Check if args.path is a file or a directory.
-    check_compiler_path(args.single_file, &args.path)?;
+    if let Some(code) = &args.explain {
+        let db = RootDatabase::builder().build()?;
+        println!("{}", explain_error_code(&db, code)?);
+        return Ok(());
+    }
+    let path = args.path.clone().expect("The path is required when not explaining an error code.");
+
+    CrashReporter::new("cairo-compile")
+        .with_input(&path)
+        .with_config("single_file", args.single_file)
+        .with_config("replace_ids", args.replace_ids)
+        .with_config("explain_ownership", args.explain_ownership)
+        .with_config("whole_program", args.whole_program)
+        .with_config("sierra_inlining_threshold", args.sierra_inlining_threshold)
+        .with_config("eliminate_redundant_drops", args.eliminate_redundant_drops)
+        .with_config("statements_remapping_output", &args.statements_remapping_output)
+        .with_minimization()
+        .run(|| run(path, args))
+}
 
-    let sierra_program = compile_cairo_project_at_path(
-        &args.path,
-        CompilerConfig { replace_ids: args.replace_ids, ..CompilerConfig::default() },
-    )?;
+fn run(path: PathBuf, args: Args) -> anyhow::Result<()> {
+    let progress = ProgressReporter::new(args.json_progress);
+    let SierraProgramWithDebug { program: sierra_program, debug_info } =
+        progress.phase("compile", || {
+            // Check if path is a file or a directory.
+            check_compiler_path(args.single_file, &path)?;
+
+            let mut db = RootDatabase::builder()
+                .detect_corelib()
+                .with_explain_ownership(args.explain_ownership)
+                .with_whole_program(args.whole_program)
+                .build()?;
+            let main_crate_ids = setup_project(&mut db, &path)?;
+            compile_prepared_db(
+                &mut db,
+                main_crate_ids,
+                CompilerConfig {
+                    diagnostics_reporter: progress.diagnostics_reporter(),
+                    replace_ids: args.replace_ids,
+                    explain_ownership: args.explain_ownership,
+                    whole_program: args.whole_program,
+                    sierra_inlining_threshold: args.sierra_inlining_threshold,
+                    eliminate_redundant_drops: args.eliminate_redundant_drops,
+                    ..CompilerConfig::default()
+                },
+            )
+        })?;
+
+    if let Some(remapping_output) = &args.statements_remapping_output {
+        // Without Sierra-level optimizations, every statement is remapped to itself.
+        let statements_remapping =
+            debug_info.statements_remapping.unwrap_or_else(|| StatementsRemapping {
+                origins: (0..sierra_program.statements.len()).map(StatementIdx).collect(),
+            });
+        let remapping = serde_json::to_string_pretty(&statements_remapping.remapped_statements())
+            .context("Statements remapping serialization failed.")?;
+        fs::write(remapping_output, remapping).context("Failed to write statements remapping.")?;
+        progress.artifact_written(remapping_output);
+    }
 
     match args.output {
         Some(path) => {
-            fs::write(path, format!("{sierra_program}")).context("Failed to write output.")?
+            fs::write(&path, format!("{sierra_program}")).context("Failed to write output.")?;
+            progress.artifact_written(path);
         }
         None => println!("{sierra_program}"),
     }
@@ -3,6 +3,9 @@ use std::path::Path;
 use std::process::ExitCode;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use cairo_lang_filesystem::db::Edition;
+use cairo_lang_formatter::cairo_formatter::{FormattableInput, FormattingError};
+use cairo_lang_formatter::migration::{parse_edition, Migrator};
 use cairo_lang_formatter::{CairoFormatter, FormatOutcome, FormatterConfig, StdinFmt};
 use cairo_lang_utils::logging::init_logging;
 use clap::Parser;
@@ -18,7 +21,7 @@ fn eprintln_if_verbose(s: &str, verbose: bool) {
     }
 }
 
-/// Formats a file or directory with the Cairo formatter.
+/// Formats a file or directory with the Cairo formatter, or migrates it between editions.
 /// Exits with 0/1 if the input is formatted correctly/incorrectly.
 #[derive(Parser, Debug)]
 #[clap(version, verbatim_doc_comment)]
@@ -39,10 +42,22 @@ struct FormatterArgs {
     /// Enable sorting the module level items (imports, mod definitions...).
     #[arg(short, long, default_value_t = false)]
     sort_mod_level_items: bool,
+    /// Migrate the files from the given edition instead of formatting them, e.g. `2023_01`.
+    /// In check mode, outputs the diff between the original and the migrated file.
+    #[arg(long, value_parser = parse_edition_arg)]
+    migrate_from: Option<Edition>,
+    /// The edition to migrate the files to. Defaults to the latest edition.
+    #[arg(long, value_parser = parse_edition_arg, requires = "migrate_from")]
+    migrate_to: Option<Edition>,
     /// A list of files and directories to format. Use "-" for stdin.
     files: Vec<String>,
 }
 
+/// Parses an edition argument.
+fn parse_edition_arg(name: &str) -> Result<Edition, String> {
+    parse_edition(name).ok_or_else(|| format!("Unknown edition `{name}`."))
+}
+
 fn print_error(error: String, path: String, args: &FormatterArgs) {
     let parsed_errors = if args.print_parsing_errors {
         error.red()
@@ -58,16 +73,44 @@ fn print_error(error: String, path: String, args: &FormatterArgs) {
     );
 }
 
+/// Formats the input, or migrates it if a migrator is given.
+struct InputFormatter<'t> {
+    fmt: &'t CairoFormatter,
+    migrator: Option<&'t Migrator>,
+}
+
+impl<'t> InputFormatter<'t> {
+    fn format_to_string(
+        &self,
+        input: &dyn FormattableInput,
+    ) -> Result<FormatOutcome, FormattingError> {
+        match self.migrator {
+            Some(migrator) => migrator.migrate_to_string(input),
+            None => self.fmt.format_to_string(input),
+        }
+    }
+
+    fn format_in_place(
+        &self,
+        input: &dyn FormattableInput,
+    ) -> Result<FormatOutcome, FormattingError> {
+        match self.migrator {
+            Some(migrator) => migrator.migrate_in_place(input),
+            None => self.fmt.format_in_place(input),
+        }
+    }
+}
+
 struct PathFormatter<'t> {
     all_correct: &'t AtomicBool,
     args: &'t FormatterArgs,
-    fmt: &'t CairoFormatter,
+    fmt: &'t InputFormatter<'t>,
 }
 
 struct PathFormatterBuilder<'t> {
     all_correct: &'t AtomicBool,
     args: &'t FormatterArgs,
-    fmt: &'t CairoFormatter,
+    fmt: &'t InputFormatter<'t>,
 }
 
 impl<'s, 't> ParallelVisitorBuilder<'s> for PathFormatterBuilder<'t>
@@ -79,7 +122,7 @@ where
     }
 }
 
-fn check_file_formatting(fmt: &CairoFormatter, args: &FormatterArgs, path: &Path) -> bool {
+fn check_file_formatting(fmt: &InputFormatter<'_>, args: &FormatterArgs, path: &Path) -> bool {
     match fmt.format_to_string(&path) {
         Ok(FormatOutcome::Identical(_)) => true,
         Ok(FormatOutcome::DiffFound(diff)) => {
@@ -93,7 +136,7 @@ fn check_file_formatting(fmt: &CairoFormatter, args: &FormatterArgs, path: &Path
     }
 }
 
-fn format_file_in_place(fmt: &CairoFormatter, args: &FormatterArgs, path: &Path) -> bool {
+fn format_file_in_place(fmt: &InputFormatter<'_>, args: &FormatterArgs, path: &Path) -> bool {
     if let Err(parsing_error) = fmt.format_in_place(&path) {
         print_error(parsing_error.to_string(), path.display().to_string(), args);
         false
@@ -141,9 +184,9 @@ impl<'t> ParallelVisitor for PathFormatter<'t> {
     }
 }
 
-fn format_path(start_path: &str, args: &FormatterArgs, fmt: &CairoFormatter) -> bool {
+fn format_path(start_path: &str, args: &FormatterArgs, fmt: &InputFormatter<'_>) -> bool {
     let base = Path::new(start_path);
-    let mut walk = fmt.walk(base);
+    let mut walk = fmt.fmt.walk(base);
     if !args.recursive {
         walk.max_depth(Some(1));
     }
@@ -155,7 +198,7 @@ fn format_path(start_path: &str, args: &FormatterArgs, fmt: &CairoFormatter) ->
     builder.all_correct.load(Ordering::Acquire)
 }
 
-fn format_stdin(args: &FormatterArgs, fmt: &CairoFormatter) -> bool {
+fn format_stdin(args: &FormatterArgs, fmt: &InputFormatter<'_>) -> bool {
     match fmt.format_to_string(&StdinFmt) {
         Ok(outcome) => {
             if args.check {
@@ -185,6 +228,19 @@ fn main() -> ExitCode {
     let args = FormatterArgs::parse();
     let config = FormatterConfig::default().sort_module_level_items(args.sort_mod_level_items);
     let fmt = CairoFormatter::new(config);
+    let migrator = match args.migrate_from {
+        Some(from) => {
+            match Migrator::for_editions(from, args.migrate_to.unwrap_or(Edition::latest())) {
+                Ok(migrator) => Some(migrator),
+                Err(err) => {
+                    eprintln!("{}", err.to_string().red());
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        None => None,
+    };
+    let fmt = InputFormatter { fmt: &fmt, migrator: migrator.as_ref() };
 
     eprintln_if_verbose(
         &format!("Start formatting. Check: {}, Recursive: {}.", args.check, args.recursive),
@@ -1,13 +1,16 @@
 //! Compiles and runs a Cairo program.
 
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Ok};
+use cairo_lang_compiler::crash_report::CrashReporter;
 use cairo_lang_compiler::db::RootDatabase;
-use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_compiler::progress::ProgressReporter;
 use cairo_lang_compiler::project::{check_compiler_path, setup_project};
 use cairo_lang_diagnostics::ToOption;
+use cairo_lang_runner::casm_run::decode_revert_reason;
 use cairo_lang_runner::profiling::ProfilingInfoProcessor;
 use cairo_lang_runner::short_string::as_cairo_short_string;
 use cairo_lang_runner::{ProfilingInfoCollectionConfig, SierraCasmRunner, StarknetState};
@@ -36,14 +39,33 @@ struct Args {
     /// Whether to print the memory.
     #[arg(long, default_value_t = false)]
     print_full_memory: bool,
+    /// Whether to print the statistics of the memory segments used by the run.
+    #[arg(long, default_value_t = false)]
+    print_segment_stats: bool,
     /// Whether to run the profiler.
     #[arg(long, default_value_t = false)]
     run_profiler: bool,
+    /// Whether to print the source files of the project annotated with the costs of each line.
+    #[arg(long, default_value_t = false)]
+    print_cost_annotations: bool,
+    /// Reports the progress of the run - its phases and the compilation diagnostics - as
+    /// newline-delimited JSON events to stderr, for build orchestration tools.
+    #[arg(long, default_value_t = false)]
+    json_progress: bool,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
+    CrashReporter::new("cairo-run")
+        .with_input(&args.path)
+        .with_config("single_file", args.single_file)
+        .with_config("available_gas", args.available_gas)
+        .with_config("run_profiler", args.run_profiler)
+        .run(|| run(args))
+}
+
+fn run(args: Args) -> anyhow::Result<()> {
     // Check if args.path is a file or a directory.
     check_compiler_path(args.single_file, &args.path)?;
 
@@ -56,19 +78,23 @@ fn main() -> anyhow::Result<()> {
 
     let main_crate_ids = setup_project(db, Path::new(&args.path))?;
 
-    let mut reporter = DiagnosticsReporter::stderr();
-    if args.allow_warnings {
-        reporter = reporter.allow_warnings();
-    }
-    if reporter.check(db) {
-        anyhow::bail!("failed to compile: {}", args.path.display());
-    }
+    let progress = ProgressReporter::new(args.json_progress);
+    let SierraProgramWithDebug { program: sierra_program, debug_info } =
+        progress.phase("compile", || {
+            let mut reporter = progress.diagnostics_reporter();
+            if args.allow_warnings {
+                reporter = reporter.allow_warnings();
+            }
+            if reporter.check(db) {
+                anyhow::bail!("failed to compile: {}", args.path.display());
+            }
 
-    let SierraProgramWithDebug { program: sierra_program, debug_info } = Arc::unwrap_or_clone(
-        db.get_sierra_program(main_crate_ids.clone())
-            .to_option()
-            .with_context(|| "Compilation failed without any diagnostics.")?,
-    );
+            Ok(Arc::unwrap_or_clone(
+                db.get_sierra_program(main_crate_ids.clone())
+                    .to_option()
+                    .with_context(|| "Compilation failed without any diagnostics.")?,
+            ))
+        })?;
     let replacer = DebugReplacer { db };
     if args.available_gas.is_none() && sierra_program.requires_gas_counter() {
         anyhow::bail!("Program requires gas counter, please provide `--available-gas` argument.");
@@ -83,15 +109,19 @@ fn main() -> anyhow::Result<()> {
         contracts_info,
         if args.run_profiler { Some(ProfilingInfoCollectionConfig::default()) } else { None },
     )
-    .with_context(|| "Failed setting up runner.")?;
-    let result = runner
-        .run_function_with_starknet_context(
-            runner.find_function("::main")?,
-            &[],
-            args.available_gas,
-            StarknetState::default(),
-        )
-        .with_context(|| "Failed to run the function.")?;
+    .with_context(|| "Failed setting up runner.")?
+    .with_source_locations(&debug_info.statements_locations.get_source_code_locations(db));
+    let func = runner.find_function("::main")?;
+    let result = progress.phase("run", || {
+        runner
+            .run_function_with_starknet_context(
+                func,
+                &[],
+                args.available_gas,
+                StarknetState::default(),
+            )
+            .with_context(|| "Failed to run the function.")
+    })?;
 
     if args.run_profiler {
         let profiling_info_processor = ProfilingInfoProcessor::new(
@@ -108,13 +138,31 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
+    if args.print_cost_annotations {
+        let overlay = runner
+            .get_source_cost_overlay()
+            .with_context(|| "Failed computing the cost annotations.")?;
+        let project_path = args.path.canonicalize()?;
+        for file in overlay.files.keys() {
+            if !Path::new(file).canonicalize().is_ok_and(|path| path.starts_with(&project_path)) {
+                continue;
+            }
+            let content =
+                fs::read_to_string(file).with_context(|| format!("Failed to read `{file}`."))?;
+            if let Some(annotated) = overlay.annotate(file, &content) {
+                println!("Cost annotations of `{file}`:\n{annotated}");
+            }
+        }
+    }
+
     match result.value {
         cairo_lang_runner::RunResultValue::Success(values) => {
+            let values = runner.flatten_return_value(func, &values, &result.memory);
             println!("Run completed successfully, returning {values:?}")
         }
         cairo_lang_runner::RunResultValue::Panic(values) => {
             print!("Run panicked with [");
-            for value in &values {
+            for value in decode_revert_reason(&values).unwrap_or(&values) {
                 match as_cairo_short_string(value) {
                     Some(as_string) => print!("{value} ('{as_string}'), "),
                     None => print!("{value}, "),
@@ -126,6 +174,9 @@ fn main() -> anyhow::Result<()> {
     if let Some(gas) = result.gas_counter {
         println!("Remaining gas: {gas}");
     }
+    if args.print_segment_stats {
+        print!("Segment stats:\n{}", result.segment_stats);
+    }
     if args.print_full_memory {
         print!("Full memory: [");
         for cell in &result.memory {
@@ -3,8 +3,10 @@
 use std::path::PathBuf;
 
 use anyhow::Ok;
+use cairo_lang_compiler::crash_report::CrashReporter;
+use cairo_lang_compiler::progress::ProgressReporter;
 use cairo_lang_compiler::project::check_compiler_path;
-use cairo_lang_test_runner::{RunProfilerConfig, TestRunConfig, TestRunner};
+use cairo_lang_test_runner::{RunProfilerConfig, TestRunConfig, TestRunner, TestShard};
 use clap::{Parser, ValueEnum};
 use serde::Serialize;
 
@@ -32,7 +34,8 @@ impl From<RunProfilerConfigArg> for RunProfilerConfig {
 #[derive(Parser, Debug)]
 #[clap(version, verbatim_doc_comment)]
 struct Args {
-    /// The Cairo project path to compile and run its tests.
+    /// The Cairo project path to compile and run its tests - or the path of a workspace directory,
+    /// to run the tests of all the Cairo projects in it.
     path: PathBuf,
     /// Whether path is a single file.
     #[arg(short, long)]
@@ -62,25 +65,54 @@ struct Args {
     /// Whether to print resource usage after each test.
     #[arg(long, default_value_t = false)]
     print_resource_usage: bool,
+    /// Whether to print the output of tests as they run, rather than only for failing tests.
+    #[arg(long, default_value_t = false)]
+    nocapture: bool,
+    /// Runs only a part of the tests, given as `<index>/<count>` (e.g. `2/4`), for splitting the
+    /// tests across multiple machines.
+    #[arg(long)]
+    shard: Option<TestShard>,
+    /// Reports the progress of the run - its phases and the compilation diagnostics - as
+    /// newline-delimited JSON events to stderr, for build orchestration tools.
+    #[arg(long, default_value_t = false)]
+    json_progress: bool,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
-    // Check if args.path is a file or a directory.
-    check_compiler_path(args.single_file, &args.path)?;
+    CrashReporter::new("cairo-test")
+        .with_input(&args.path)
+        .with_config("single_file", args.single_file)
+        .with_config("starknet", args.starknet)
+        .with_config("gas_disabled", args.gas_disabled)
+        .run(|| run(args))
+}
+
+fn run(args: Args) -> anyhow::Result<()> {
+    let progress = ProgressReporter::new(args.json_progress);
+    let runner = progress.phase("compile", || {
+        // Check if args.path is a file or a directory.
+        check_compiler_path(args.single_file, &args.path)?;
 
-    let config = TestRunConfig {
-        filter: args.filter,
-        ignored: args.ignored,
-        include_ignored: args.include_ignored,
-        run_profiler: args.run_profiler.into(),
-        gas_enabled: !args.gas_disabled,
-        print_resource_usage: args.print_resource_usage,
-    };
+        let config = TestRunConfig {
+            filter: args.filter,
+            ignored: args.ignored,
+            include_ignored: args.include_ignored,
+            run_profiler: args.run_profiler.into(),
+            gas_enabled: !args.gas_disabled,
+            print_resource_usage: args.print_resource_usage,
+            nocapture: args.nocapture,
+            shard: args.shard,
+        };
 
-    let runner = TestRunner::new(&args.path, args.starknet, args.allow_warnings, config)?;
-    runner.run()?;
+        let mut reporter = progress.diagnostics_reporter();
+        if args.allow_warnings {
+            reporter = reporter.allow_warnings();
+        }
+        TestRunner::new_with_reporter(&args.path, args.starknet, reporter, config)
+    })?;
+    progress.phase("test", || runner.run())?;
 
     Ok(())
 }
@@ -1,7 +1,9 @@
 use std::fs;
 
 use anyhow::Context;
+use cairo_lang_sierra::validate::validate_program;
 use cairo_lang_sierra::ProgramParser;
+use cairo_lang_sierra_to_casm::branch_align::insert_branch_aligns;
 use cairo_lang_sierra_to_casm::compiler::SierraToCasmConfig;
 use cairo_lang_sierra_to_casm::metadata::calc_metadata;
 use cairo_lang_utils::logging::init_logging;
@@ -16,6 +18,19 @@ struct Args {
     /// The path of the file to compile.
     file: String,
     output: String,
+    /// Whether to apply peephole optimizations to the compiled CASM.
+    #[arg(long, default_value_t = false)]
+    optimize: bool,
+    /// Whether to skip the compilation of statements unreachable from the functions' entry points.
+    #[arg(long, default_value_t = false)]
+    eliminate_dead_code: bool,
+    /// Whether to insert the `branch_align` statements missing at the targets of branching
+    /// libfuncs, instead of failing the compilation.
+    #[arg(long, default_value_t = false)]
+    insert_branch_aligns: bool,
+    /// Whether to coalesce the `ap` updates of consecutive statements.
+    #[arg(long, default_value_t = false)]
+    coalesce_ap_updates: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -25,18 +40,32 @@ fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
     let sierra_code = fs::read_to_string(args.file).with_context(|| "Could not read file!")?;
-    let Ok(program) = ProgramParser::new().parse(&sierra_code) else {
+    let Ok(mut program) = ProgramParser::new().parse(&sierra_code) else {
         anyhow::bail!(indoc! {"
             Failed to parse sierra program.
             Note: StarkNet contracts should be compiled with `starknet-sierra-compile`."
         })
     };
+    if let Err(errors) = validate_program(&program) {
+        anyhow::bail!(
+            "Invalid Sierra program:\n{}",
+            errors.iter().map(|error| error.to_string()).collect::<Vec<_>>().join("\n")
+        );
+    }
+    if args.insert_branch_aligns {
+        program = insert_branch_aligns(&program).into_owned();
+    }
 
     let cairo_program = cairo_lang_sierra_to_casm::compiler::compile(
         &program,
         &calc_metadata(&program, Default::default())
             .with_context(|| "Failed calculating Sierra variables.")?,
-        SierraToCasmConfig { gas_usage_check: true, max_bytecode_size: usize::MAX },
+        SierraToCasmConfig {
+            optimize: args.optimize,
+            eliminate_dead_code: args.eliminate_dead_code,
+            coalesce_ap_updates: args.coalesce_ap_updates,
+            ..Default::default()
+        },
     )
     .with_context(|| "Compilation failed.")?;
 
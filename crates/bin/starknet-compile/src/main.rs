@@ -2,20 +2,29 @@ use std::fs;
 use std::path::PathBuf;
 
 use anyhow::Context;
-use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_compiler::crash_report::CrashReporter;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::explain_error_code;
+use cairo_lang_compiler::progress::ProgressReporter;
 use cairo_lang_compiler::project::check_compiler_path;
 use cairo_lang_compiler::CompilerConfig;
-use cairo_lang_starknet::compile::starknet_compile;
+use cairo_lang_starknet::compile::{compile_path_with_claim, estimate_path_gas, starknet_compile};
+use cairo_lang_starknet::starknet_plugin_suite;
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
 use clap::Parser;
 
+/// The maximal size of the bytecode of the compiled class in a claim, as the default of
+/// `starknet-sierra-compile`.
+const MAX_BYTECODE_SIZE: usize = 180000;
+
 /// Compiles the specified contract from a Cairo project, into a contract class file.
 /// Exits with 0/1 if the compilation succeeds/fails.
 #[derive(Parser, Debug)]
 #[clap(version, verbatim_doc_comment)]
 struct Args {
     /// The path of the crate to compile.
-    path: PathBuf,
+    #[arg(required_unless_present = "explain")]
+    path: Option<PathBuf>,
     /// Whether path is a single file.
     #[arg(short, long)]
     single_file: bool,
@@ -36,33 +45,129 @@ struct Args {
     /// A file of the allowed libfuncs list to use.
     #[arg(long)]
     allowed_libfuncs_list_file: Option<String>,
+    /// Encodes the panic data with the revert-with-reason ABI, i.e. as the `Error(string)`
+    /// selector followed by the length-prefixed panic data.
+    #[arg(long, default_value_t = false)]
+    revert_panic_abi: bool,
+    /// Explains ownership errors in depth - the moves of a moved variable, the impls considered
+    /// for it, and the possible fixes.
+    #[arg(long, default_value_t = false)]
+    explain_ownership: bool,
+    /// Optimizes the program as a whole, after merging the functions of all the crates - inlining
+    /// functions with a single call site, and dropping unused functions of library crates.
+    #[arg(long, default_value_t = false)]
+    whole_program: bool,
+    /// A file to write a compilation claim to - binding the digest of the sources, the compiler
+    /// version and configuration, and the hashes of the resulting classes - for verifying the
+    /// compilation offline.
+    #[arg(long)]
+    claim_output: Option<PathBuf>,
+    /// Outputs the gas costs of the entry points of the contract, instead of its contract class -
+    /// stopping after solving the gas costs, without compiling the contract class to CASM.
+    #[arg(long, default_value_t = false, conflicts_with = "claim_output")]
+    estimate_gas: bool,
+    /// Embeds the gas costs of the entry points of the contract into its contract class, for
+    /// estimating the fees of calling the contract without compiling it again.
+    #[arg(long, default_value_t = false, conflicts_with = "estimate_gas")]
+    embed_gas_estimate: bool,
+    /// Warns about storage writes following external calls in the entry points of the contract,
+    /// through which the called contracts may re-enter it in an outdated state.
+    #[arg(long, default_value_t = false)]
+    reentrancy_analysis: bool,
+    /// Reports the progress of the compilation - its phases, diagnostics and written files - as
+    /// newline-delimited JSON events to stderr, for build orchestration tools.
+    #[arg(long, default_value_t = false)]
+    json_progress: bool,
+    /// Prints the documentation of the given error code, e.g. `E0001`, instead of compiling.
+    #[arg(long, value_name = "CODE", exclusive = true)]
+    explain: Option<String>,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
-    // Check if args.path is a file or a directory.
-    check_compiler_path(args.single_file, &args.path)?;
-
-    let list_selector =
-        ListSelector::new(args.allowed_libfuncs_list_name, args.allowed_libfuncs_list_file)
-            .expect("Both allowed libfunc list name and file were supplied.");
-    let mut diagnostics_reporter = DiagnosticsReporter::stderr();
-    if args.allow_warnings {
-        diagnostics_reporter = diagnostics_reporter.allow_warnings();
+    if let Some(code) = &args.explain {
+        let db = RootDatabase::builder().with_plugin_suite(starknet_plugin_suite()).build()?;
+        println!("{}", explain_error_code(&db, code)?);
+        return Ok(());
     }
-    let res = starknet_compile(
-        args.path,
-        args.contract_path,
-        Some(CompilerConfig {
+    let path = args.path.clone().expect("The path is required when not explaining an error code.");
+
+    CrashReporter::new("starknet-compile")
+        .with_input(&path)
+        .with_config("single_file", args.single_file)
+        .with_config("contract_path", &args.contract_path)
+        .with_config("replace_ids", args.replace_ids)
+        .with_config("allowed_libfuncs_list_name", &args.allowed_libfuncs_list_name)
+        .with_config("allowed_libfuncs_list_file", &args.allowed_libfuncs_list_file)
+        .with_config("revert_panic_abi", args.revert_panic_abi)
+        .with_config("explain_ownership", args.explain_ownership)
+        .with_config("whole_program", args.whole_program)
+        .with_config("claim_output", &args.claim_output)
+        .with_config("estimate_gas", args.estimate_gas)
+        .with_config("embed_gas_estimate", args.embed_gas_estimate)
+        .with_config("reentrancy_analysis", args.reentrancy_analysis)
+        .run(|| run(path, args))
+}
+
+fn run(path: PathBuf, args: Args) -> anyhow::Result<()> {
+    let progress = ProgressReporter::new(args.json_progress);
+    let res = progress.phase("compile", || {
+        // Check if path is a file or a directory.
+        check_compiler_path(args.single_file, &path)?;
+
+        let list_selector =
+            ListSelector::new(args.allowed_libfuncs_list_name, args.allowed_libfuncs_list_file)
+                .expect("Both allowed libfunc list name and file were supplied.");
+        let mut diagnostics_reporter = progress.diagnostics_reporter();
+        if args.allow_warnings {
+            diagnostics_reporter = diagnostics_reporter.allow_warnings();
+        }
+        let compiler_config = CompilerConfig {
             replace_ids: args.replace_ids,
+            revert_panic_abi: args.revert_panic_abi,
+            explain_ownership: args.explain_ownership,
+            whole_program: args.whole_program,
+            embed_gas_estimate: args.embed_gas_estimate,
+            reentrancy_analysis: args.reentrancy_analysis,
             diagnostics_reporter,
             ..CompilerConfig::default()
-        }),
-        Some(list_selector),
-    )?;
+        };
+        Ok(match &args.claim_output {
+            _ if args.estimate_gas => {
+                let estimate =
+                    estimate_path_gas(&path, args.contract_path.as_deref(), compiler_config, true)?;
+                serde_json::to_string_pretty(&estimate)
+                    .with_context(|| "Gas estimate serialization failed.")?
+            }
+            Some(claim_output) => {
+                let (contract, claim) = compile_path_with_claim(
+                    &path,
+                    args.contract_path.as_deref(),
+                    compiler_config,
+                    false,
+                    MAX_BYTECODE_SIZE,
+                )?;
+                contract.validate_version_compatible(list_selector)?;
+                let claim = serde_json::to_string_pretty(&claim)
+                    .with_context(|| "Claim serialization failed.")?;
+                fs::write(claim_output, claim).with_context(|| "Failed to write claim.")?;
+                progress.artifact_written(claim_output);
+                serde_json::to_string_pretty(&contract).with_context(|| "Serialization failed.")?
+            }
+            None => starknet_compile(
+                path,
+                args.contract_path,
+                Some(compiler_config),
+                Some(list_selector),
+            )?,
+        })
+    })?;
     match args.output {
-        Some(path) => fs::write(path, res).with_context(|| "Failed to write output.")?,
+        Some(path) => {
+            fs::write(&path, res).with_context(|| "Failed to write output.")?;
+            progress.artifact_written(path);
+        }
         None => println!("{res}"),
     }
 
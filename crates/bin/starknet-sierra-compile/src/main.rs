@@ -4,6 +4,7 @@ use anyhow::Context;
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_lang_starknet_classes::contract_class::{ContractClass, ContractEntryPoints};
+use cairo_lang_starknet_classes::contract_size::{ContractSizeInfo, ContractSizeLimits};
 use cairo_lang_utils::bigint::BigUintAsHex;
 use clap::Parser;
 use serde::Deserialize;
@@ -29,9 +30,20 @@ struct Args {
     /// The max bytecode size.
     #[arg(long, default_value_t = 180000)]
     max_bytecode_size: usize,
+    /// The bytecode size limit of the target network, in felts, to warn about exceeding.
+    #[arg(long)]
+    network_max_bytecode_size: Option<usize>,
+    /// The contract class object size limit of the target network, in bytes, to warn about
+    /// exceeding.
+    #[arg(long)]
+    network_max_contract_class_object_size: Option<usize>,
+    /// Print the sizes of the contract class to stderr, e.g. for comparing the costs of contract
+    /// options such as `strict_calldata`.
+    #[arg(long, default_value_t = false)]
+    size_report: bool,
 }
 
-/// Same as `ContractClass` - but ignores `abi` in deserialization.
+/// Same as `ContractClass` - but keeps `abi` as raw JSON in deserialization.
 /// Enables loading old contract classes.
 #[derive(Deserialize)]
 pub struct ContractClassIgnoreAbi {
@@ -39,7 +51,8 @@ pub struct ContractClassIgnoreAbi {
     pub sierra_program_debug_info: Option<cairo_lang_sierra::debug_info::DebugInfo>,
     pub contract_class_version: String,
     pub entry_points_by_type: ContractEntryPoints,
-    pub _abi: Option<serde_json::Value>,
+    #[serde(default)]
+    pub abi: Option<serde_json::Value>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -52,7 +65,7 @@ fn main() -> anyhow::Result<()> {
         sierra_program_debug_info,
         contract_class_version,
         entry_points_by_type,
-        _abi,
+        abi,
     } = serde_json::from_str(
         &fs::read_to_string(&args.file)
             .with_context(|| format!("Failed to read {}.", &args.file))?,
@@ -67,11 +80,33 @@ fn main() -> anyhow::Result<()> {
     };
     contract_class.validate_version_compatible(list_selector)?;
     let casm_contract = CasmContractClass::from_contract_class(
-        contract_class,
+        contract_class.clone(),
         args.add_pythonic_hints,
         args.max_bytecode_size,
     )
     .with_context(|| "Compilation failed.")?;
+    if args.size_report
+        || args.network_max_bytecode_size.is_some()
+        || args.network_max_contract_class_object_size.is_some()
+    {
+        let abi_length = abi.map(|abi| abi.to_string().len()).unwrap_or_default();
+        let size_info = ContractSizeInfo {
+            abi_length,
+            ..ContractSizeInfo::new(&contract_class, &casm_contract)
+        };
+        if args.size_report {
+            eprint!("{size_info}");
+        }
+        let limits = ContractSizeLimits {
+            max_bytecode_size: args.network_max_bytecode_size.unwrap_or(usize::MAX),
+            max_contract_class_object_size: args
+                .network_max_contract_class_object_size
+                .unwrap_or(usize::MAX),
+        };
+        for warning in size_info.limit_warnings(&limits) {
+            eprintln!("Warning: {warning}");
+        }
+    }
 
     let res = serde_json::to_string_pretty(&casm_contract)
         .with_context(|| "Casm contract Serialization failed.")?;
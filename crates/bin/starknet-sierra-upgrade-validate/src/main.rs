@@ -4,11 +4,10 @@ use std::sync::Mutex;
 
 use anyhow::Context;
 use cairo_lang_starknet_classes::allowed_libfuncs::{AllowedLibfuncsError, ListSelector};
-use cairo_lang_starknet_classes::casm_contract_class::{
-    CasmContractClass, StarknetSierraCompilationError,
-};
+use cairo_lang_starknet_classes::casm_contract_class::StarknetSierraCompilationError;
 use cairo_lang_starknet_classes::compiler_version::VersionId;
 use cairo_lang_starknet_classes::contract_class::{ContractClass, ContractEntryPoints};
+use cairo_lang_starknet_classes::recompilation::check_recompilation;
 use cairo_lang_utils::bigint::BigUintAsHex;
 use clap::Parser;
 use indicatif::{ProgressBar, ProgressState, ProgressStyle};
@@ -208,21 +207,18 @@ fn run_single(mut sierra_class: ContractClassInfo, config: &RunConfig) -> RunRes
     if let Err(err) = contract_class.validate_version_compatible(config.list_selector.clone()) {
         return RunResult::ValidationFailure(ValidationFailure { class_hash, err });
     };
-    let compiled_contract_class = match CasmContractClass::from_contract_class(
+    match check_recompilation(
         contract_class,
-        false,
+        &sierra_class.compiled_class_hash.value,
+        None,
         config.max_bytecode_size,
     ) {
-        Ok(compiled_contract_class) => compiled_contract_class,
-        Err(err) => {
-            return RunResult::CompilationFailure(CompilationFailure { class_hash, err });
-        }
-    };
-    let old = sierra_class.compiled_class_hash;
-    let new = BigUintAsHex { value: compiled_contract_class.compiled_class_hash().to_biguint() };
-    if old != new {
-        RunResult::CompilationMismatch(CompilationMismatch { class_hash, old, new })
-    } else {
-        RunResult::Success
+        Ok(None) => RunResult::Success,
+        Ok(Some(mismatch)) => RunResult::CompilationMismatch(CompilationMismatch {
+            class_hash,
+            old: BigUintAsHex { value: mismatch.expected_hash },
+            new: BigUintAsHex { value: mismatch.actual_hash },
+        }),
+        Err(err) => RunResult::CompilationFailure(CompilationFailure { class_hash, err }),
     }
 }
@@ -9,6 +9,7 @@ use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Register, ResOpera
 mod test;
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ApChange {
     Known(usize),
     Unknown,
@@ -1,11 +1,16 @@
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 
+use cairo_lang_utils::bigint::BigIntAsHex;
 use num_bigint::{BigInt, ToBigInt};
 
+use crate::encoder::DecodingError;
 use crate::hints::Hint;
-use crate::instructions::{Instruction, InstructionBody};
-use crate::operand::{DerefOrImmediate, Operation, Register, ResOperand};
+use crate::instructions::{
+    AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
+    JnzInstruction, JumpInstruction, RetInstruction,
+};
+use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand};
 
 #[cfg(test)]
 #[path = "assembler_test.rs"]
@@ -82,6 +87,143 @@ pub struct AssembledCairoProgram {
     /// The list of hints, and the instruction index they refer to.
     pub hints: Vec<(usize, Vec<Hint>)>,
 }
+impl AssembledCairoProgram {
+    /// Returns the hints of the program in the current versioned hints format.
+    pub fn versioned_hints(&self) -> VersionedHints {
+        VersionedHints::new(self.hints.clone())
+    }
+
+    /// Creates an assembled program from its bytecode and its hints in a versioned hints format.
+    pub fn from_versioned_hints(
+        bytecode: Vec<BigInt>,
+        hints: VersionedHints,
+    ) -> Result<Self, UnsupportedHintsVersion> {
+        Ok(Self { bytecode, hints: hints.into_hints()? })
+    }
+
+    /// Disassembles the bytecode of the program back into instructions, with their hints.
+    ///
+    /// The entire bytecode is expected to be instructions, so data following the code (such as
+    /// const segments) should be removed first.
+    pub fn disassemble(&self) -> Result<Vec<Instruction>, DisassemblerError> {
+        disassemble_ex(&self.bytecode, &self.hints)
+    }
+}
+
+/// The current version of the [VersionedHints] format.
+pub const HINTS_FORMAT_VERSION: u32 = 1;
+
+/// The hints of an assembled program, in a versioned serialization format.
+///
+/// In version 1, `hints` is serialized as the `hints` field of Starknet CASM contract classes: a
+/// list of pairs of a bytecode offset and the hints to run before the instruction at it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct VersionedHints {
+    /// The version of the format.
+    pub version: u32,
+    /// The hints, and the bytecode offset they refer to.
+    pub hints: Vec<(usize, Vec<Hint>)>,
+}
+impl VersionedHints {
+    /// Creates the versioned hints of `hints` in the current format.
+    pub fn new(hints: Vec<(usize, Vec<Hint>)>) -> Self {
+        Self { version: HINTS_FORMAT_VERSION, hints }
+    }
+
+    /// Returns the hints, if their format version is supported.
+    pub fn into_hints(self) -> Result<Vec<(usize, Vec<Hint>)>, UnsupportedHintsVersion> {
+        if self.version != HINTS_FORMAT_VERSION {
+            return Err(UnsupportedHintsVersion { version: self.version });
+        }
+        Ok(self.hints)
+    }
+}
+
+/// An error for hints in a format version that is not supported.
+#[derive(Debug, Eq, PartialEq)]
+pub struct UnsupportedHintsVersion {
+    pub version: u32,
+}
+impl core::fmt::Display for UnsupportedHintsVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Unsupported hints format version {}, expected version {HINTS_FORMAT_VERSION}.",
+            self.version
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnsupportedHintsVersion {}
+
+/// An error disassembling bytecode.
+#[derive(Debug, Eq, PartialEq)]
+pub enum DisassemblerError {
+    /// The bytecode at `pc` is not a valid instruction encoding.
+    Decoding { pc: usize, error: DecodingError },
+    /// The instruction at `pc` is valid, but is not the assembly of any [Instruction].
+    UnsupportedInstruction { pc: usize },
+    /// Hints refer to `pc`, which is not the start of an instruction.
+    InvalidHintPc { pc: usize },
+}
+impl core::fmt::Display for DisassemblerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DisassemblerError::Decoding { pc, error } => {
+                write!(f, "Failed decoding the instruction at pc {pc}: {error}")
+            }
+            DisassemblerError::UnsupportedInstruction { pc } => {
+                write!(f, "Unsupported instruction at pc {pc}.")
+            }
+            DisassemblerError::InvalidHintPc { pc } => {
+                write!(f, "Hints at pc {pc}, which is not the start of an instruction.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DisassemblerError {}
+
+/// Disassembles `bytecode` back into instructions, the inverse of assembling and encoding each of
+/// the instructions.
+///
+/// Immediates are kept as they appear in the bytecode, so bytecode holding field elements (where
+/// negative values are represented modulo the prime) is disassembled into their non-negative
+/// representatives.
+pub fn disassemble(bytecode: &[BigInt]) -> Result<Vec<Instruction>, DisassemblerError> {
+    disassemble_ex(bytecode, &[])
+}
+
+/// Disassembles `bytecode`, attaching `hints` to the instructions at their pcs.
+fn disassemble_ex(
+    bytecode: &[BigInt],
+    hints: &[(usize, Vec<Hint>)],
+) -> Result<Vec<Instruction>, DisassemblerError> {
+    let mut instructions = vec![];
+    let mut hints = hints.iter().peekable();
+    let mut pc = 0;
+    while pc < bytecode.len() {
+        let repr = InstructionRepr::decode(&bytecode[pc..])
+            .map_err(|error| DisassemblerError::Decoding { pc, error })?;
+        let mut instruction =
+            repr.disassemble().ok_or(DisassemblerError::UnsupportedInstruction { pc })?;
+        if let Some((hint_pc, instruction_hints)) = hints.next_if(|(hint_pc, _)| *hint_pc <= pc) {
+            if *hint_pc != pc {
+                return Err(DisassemblerError::InvalidHintPc { pc: *hint_pc });
+            }
+            instruction.hints = instruction_hints.clone();
+        }
+        pc += instruction.body.op_size();
+        instructions.push(instruction);
+    }
+    if let Some((hint_pc, _)) = hints.next() {
+        return Err(DisassemblerError::InvalidHintPc { pc: *hint_pc });
+    }
+    Ok(instructions)
+}
 
 impl Instruction {
     pub fn assemble(&self) -> InstructionRepr {
@@ -194,6 +336,86 @@ impl Instruction {
     }
 }
 
+impl InstructionRepr {
+    /// Returns the instruction assembled into this representation, the inverse of
+    /// [Instruction::assemble].
+    ///
+    /// Returns `None` if the representation is not the assembly of any instruction.
+    pub fn disassemble(&self) -> Option<Instruction> {
+        let inc_ap = self.ap_update == ApUpdate::Add1;
+        let body = match (&self.opcode, &self.pc_update) {
+            (Opcode::Ret, _) if !inc_ap => InstructionBody::Ret(RetInstruction {}),
+            (Opcode::Call, PcUpdate::Jump | PcUpdate::JumpRel) if !inc_ap => {
+                InstructionBody::Call(CallInstruction {
+                    target: self.op1_deref_or_immediate()?,
+                    relative: self.pc_update == PcUpdate::JumpRel,
+                })
+            }
+            (Opcode::AssertEq, PcUpdate::Regular) => {
+                InstructionBody::AssertEq(AssertEqInstruction {
+                    a: CellRef { register: self.dst_register, offset: self.off0 },
+                    b: self.res_operand()?,
+                })
+            }
+            (Opcode::Nop, PcUpdate::Regular) if self.ap_update == ApUpdate::Add => {
+                InstructionBody::AddAp(AddApInstruction { operand: self.res_operand()? })
+            }
+            (Opcode::Nop, PcUpdate::Jump | PcUpdate::JumpRel) => {
+                InstructionBody::Jump(JumpInstruction {
+                    target: self.op1_deref_or_immediate()?,
+                    relative: self.pc_update == PcUpdate::JumpRel,
+                })
+            }
+            (Opcode::Nop, PcUpdate::Jnz) => InstructionBody::Jnz(JnzInstruction {
+                jump_offset: self.op1_deref_or_immediate()?,
+                condition: CellRef { register: self.dst_register, offset: self.off0 },
+            }),
+            _ => return None,
+        };
+        let instruction = Instruction::new(body, inc_ap);
+        // Fields that do not affect the instruction must have their assembled values, so that
+        // reassembling the instruction reproduces the same encoding.
+        (&instruction.assemble() == self).then_some(instruction)
+    }
+
+    /// Returns the res operand described by the representation.
+    fn res_operand(&self) -> Option<ResOperand> {
+        let op = match self.res {
+            Res::Op1 => {
+                return Some(match self.op1_addr {
+                    Op1Addr::Op0 => ResOperand::DoubleDeref(
+                        CellRef { register: self.op0_register, offset: self.off1 },
+                        self.off2,
+                    ),
+                    _ => self.op1_deref_or_immediate()?.into(),
+                });
+            }
+            Res::Add => Operation::Add,
+            Res::Mul => Operation::Mul,
+            Res::Unconstrained => return None,
+        };
+        Some(ResOperand::BinOp(BinOpOperand {
+            op,
+            a: CellRef { register: self.op0_register, offset: self.off1 },
+            b: self.op1_deref_or_immediate()?,
+        }))
+    }
+
+    /// Returns the op1 operand, if it is not based on op0.
+    fn op1_deref_or_immediate(&self) -> Option<DerefOrImmediate> {
+        Some(match self.op1_addr {
+            Op1Addr::Imm => DerefOrImmediate::Immediate(BigIntAsHex { value: self.imm.clone()? }),
+            Op1Addr::AP => {
+                DerefOrImmediate::Deref(CellRef { register: Register::AP, offset: self.off2 })
+            }
+            Op1Addr::FP => {
+                DerefOrImmediate::Deref(CellRef { register: Register::FP, offset: self.off2 })
+            }
+            Op1Addr::Op0 => return None,
+        })
+    }
+}
+
 impl Register {
     fn to_op1_addr(self) -> Op1Addr {
         match self {
@@ -1,12 +1,19 @@
-use num_bigint::ToBigInt;
+use itertools::Itertools;
+use num_bigint::{BigInt, ToBigInt};
 use pretty_assertions::assert_eq;
 use test_log::test;
 
-use super::InstructionRepr;
+use super::{
+    disassemble, AssembledCairoProgram, DisassemblerError, InstructionRepr,
+    UnsupportedHintsVersion, VersionedHints, HINTS_FORMAT_VERSION,
+};
 use crate::assembler::{ApUpdate, FpUpdate, Op1Addr, Opcode, PcUpdate, Res};
 use crate::casm;
+use crate::encoder::DecodingError;
+use crate::hints::{CoreHint, Hint};
 use crate::inline::CasmContext;
-use crate::operand::Register;
+use crate::instructions::Instruction;
+use crate::operand::{CellRef, Register};
 
 /// Takes a casm instruction, which can be constructed using the macro casm!, and
 /// returns its assembled representation.
@@ -266,3 +273,126 @@ fn test_instruction_with_hint() {
         },
     );
 }
+
+#[test]
+fn test_disassemble() {
+    let instructions = casm! {
+        %{ memory[ap + 0] = segments.add() %}
+        [ap + 0] = 1, ap++;
+        [ap + 0] = 13, ap++;
+        call rel 3;
+        ret;
+        jmp rel 5 if [fp + -3] != 0;
+        [ap + 0] = [fp + -5], ap++;
+        jmp abs [ap + 2];
+        %{ memory[ap + 0] = segments.add() %}
+        [ap + 0] = [[fp + -4] + 3], ap++;
+        [ap + 0] = [fp + -5] + [fp + -4], ap++;
+        [fp + -3] = [ap + 0] * 7, ap++;
+        ap += [fp + 1];
+        call abs [fp + -2];
+        ret;
+    }
+    .instructions;
+    let mut bytecode = vec![];
+    let mut hints = vec![];
+    for instruction in &instructions {
+        if !instruction.hints.is_empty() {
+            hints.push((bytecode.len(), instruction.hints.clone()));
+        }
+        bytecode.extend(instruction.assemble().encode());
+    }
+    assert_eq!(
+        disassemble(&bytecode),
+        Ok(instructions.iter().cloned().map(without_hints).collect())
+    );
+    assert_eq!(AssembledCairoProgram { bytecode, hints }.disassemble(), Ok(instructions));
+}
+
+/// Returns the instruction without its hints.
+fn without_hints(instruction: Instruction) -> Instruction {
+    Instruction { hints: vec![], ..instruction }
+}
+
+#[test]
+fn test_disassemble_failure() {
+    let bytecode = casm! { [ap + 0] = 1, ap++; ret; }
+        .instructions
+        .iter()
+        .flat_map(|instruction| instruction.assemble().encode())
+        .collect_vec();
+    let hint = || {
+        vec![Hint::Core(
+            CoreHint::AllocSegment { dst: CellRef { register: Register::AP, offset: 0 } }.into(),
+        )]
+    };
+
+    // A hint pc inside an instruction.
+    assert_eq!(
+        AssembledCairoProgram { bytecode: bytecode.clone(), hints: vec![(1, hint())] }
+            .disassemble(),
+        Err(DisassemblerError::InvalidHintPc { pc: 1 })
+    );
+    // A hint pc after the end of the bytecode.
+    assert_eq!(
+        AssembledCairoProgram { bytecode: bytecode.clone(), hints: vec![(3, hint())] }
+            .disassemble(),
+        Err(DisassemblerError::InvalidHintPc { pc: 3 })
+    );
+    // A missing immediate.
+    assert_eq!(
+        disassemble(&bytecode[..1]),
+        Err(DisassemblerError::Decoding { pc: 0, error: DecodingError::UnexpectedEnd })
+    );
+    // A valid encoding that no instruction is assembled into: `ret` with `ap++`.
+    let ret_with_inc_ap = &bytecode[2] + (BigInt::from(1) << (48 + 11));
+    assert_eq!(
+        disassemble(&[bytecode[0].clone(), bytecode[1].clone(), ret_with_inc_ap]),
+        Err(DisassemblerError::UnsupportedInstruction { pc: 2 })
+    );
+    // A call whose op0 offset is not the assembled one.
+    let mut call = casm! { call abs [fp + -2]; }.instructions[0].assemble();
+    call.off1 = 3;
+    assert_eq!(
+        disassemble(&call.encode()),
+        Err(DisassemblerError::UnsupportedInstruction { pc: 0 })
+    );
+}
+
+#[test]
+fn test_versioned_hints() {
+    let hint = Hint::Core(
+        CoreHint::AllocSegment { dst: CellRef { register: Register::AP, offset: 0 } }.into(),
+    );
+    let program = AssembledCairoProgram {
+        bytecode: vec![BigInt::from(0x480680017fff8000_i64), BigInt::from(1)],
+        hints: vec![(0, vec![hint])],
+    };
+    let versioned_hints = program.versioned_hints();
+    assert_eq!(versioned_hints.version, HINTS_FORMAT_VERSION);
+    let converted =
+        AssembledCairoProgram::from_versioned_hints(program.bytecode.clone(), versioned_hints)
+            .unwrap();
+    assert_eq!(converted.hints, program.hints);
+
+    assert_eq!(
+        VersionedHints { version: HINTS_FORMAT_VERSION + 1, hints: vec![] }.into_hints(),
+        Err(UnsupportedHintsVersion { version: HINTS_FORMAT_VERSION + 1 })
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_versioned_hints_serialization() {
+    let hint = Hint::Core(
+        CoreHint::AllocSegment { dst: CellRef { register: Register::AP, offset: 0 } }.into(),
+    );
+    let versioned_hints = VersionedHints::new(vec![(2, vec![hint])]);
+    // The hints are serialized as in Starknet CASM contract classes.
+    let serialized = serde_json::to_string(&versioned_hints).unwrap();
+    assert_eq!(
+        serialized,
+        r#"{"version":1,"hints":[[2,[{"AllocSegment":{"dst":{"register":"AP","offset":0}}}]]]}"#
+    );
+    assert_eq!(serde_json::from_str::<VersionedHints>(&serialized).unwrap(), versioned_hints);
+}
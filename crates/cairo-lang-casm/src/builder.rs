@@ -1,7 +1,7 @@
 #[cfg(not(feature = "std"))]
 pub use alloc::borrow::ToOwned;
 #[cfg(not(feature = "std"))]
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 #[cfg(feature = "std")]
 pub use std::borrow::ToOwned;
 
@@ -103,6 +103,16 @@ enum Statement {
     Jump(String, Instruction),
     /// A target label for jumps.
     Label(String),
+    /// The position of the deferred block starting at a label.
+    DeferredBlock(String),
+}
+
+/// A block of code added before the state at its starting label was known.
+enum DeferredBlock {
+    /// The block awaiting a jump or call to its label.
+    Pending(Box<dyn FnOnce(&mut CasmBuilder)>),
+    /// The statements of the block, once built.
+    Built(Vec<Statement>),
 }
 
 /// The builder result.
@@ -132,6 +142,8 @@ pub struct CasmBuilder {
     /// Is the current state reachable.
     /// Example for unreachable state is after a unconditional jump, before any label is stated.
     reachable: bool,
+    /// The deferred blocks, per starting label.
+    deferred_blocks: OrderedHashMap<String, DeferredBlock>,
 }
 impl CasmBuilder {
     /// Finalizes the builder, with the requested labels as the returning branches.
@@ -144,6 +156,9 @@ impl CasmBuilder {
             self.current_hints.is_empty(),
             "Build cannot be called with hints as the last addition."
         );
+        self.build_deferred_blocks();
+        let statements = core::mem::take(&mut self.statements);
+        self.statements = flatten_statements(statements, &mut self.deferred_blocks);
         let label_offsets = self.compute_label_offsets();
         if self.reachable {
             self.label_state.insert("Fallthrough".to_owned(), self.main_state);
@@ -189,6 +204,7 @@ impl CasmBuilder {
                 Statement::Label(name) => {
                     self.label_state.remove(&name);
                 }
+                Statement::DeferredBlock(_) => unreachable!("Deferred blocks are flattened."),
             }
         }
         let branches = branch_names.map(|label| {
@@ -222,6 +238,7 @@ impl CasmBuilder {
                 Statement::Label(name) => {
                     label_offsets.insert(name.clone(), offset);
                 }
+                Statement::DeferredBlock(_) => unreachable!("Deferred blocks are flattened."),
             }
         }
         label_offsets
@@ -483,6 +500,50 @@ impl CasmBuilder {
         self.reachable = true;
     }
 
+    /// Adds a block of code starting at a label named `name`, built by `block` with the state at
+    /// the label once all the code is added, i.e. after all the jumps and calls to the label.
+    /// Allows placing code that is only reached by later jumps, such as shared epilogues or loop
+    /// bodies, before these jumps.
+    /// Must be added at unreachable code, and the block must end at unreachable code.
+    pub fn deferred_block(&mut self, name: String, block: impl FnOnce(&mut CasmBuilder) + 'static) {
+        assert!(!self.reachable, "Deferred block {name} cannot be added at reachable code.");
+        assert!(
+            !self.deferred_blocks.contains_key(&name),
+            "Deferred block {name} was already added."
+        );
+        self.statements.push(Statement::DeferredBlock(name.clone()));
+        self.deferred_blocks.insert(name, DeferredBlock::Pending(Box::new(block)));
+    }
+
+    /// Builds the pending deferred blocks that are jumped to, in the order they were added, until
+    /// no such block remains.
+    fn build_deferred_blocks(&mut self) {
+        while let Some(label) = self.deferred_blocks.iter().find_map(|(label, block)| {
+            (matches!(block, DeferredBlock::Pending(_)) && self.label_state.contains_key(label))
+                .then(|| label.clone())
+        }) {
+            let Some(DeferredBlock::Pending(block)) =
+                self.deferred_blocks.insert(label.clone(), DeferredBlock::Built(vec![]))
+            else {
+                unreachable!("Only pending blocks are built.");
+            };
+            let main_state = core::mem::take(&mut self.main_state);
+            let reachable = core::mem::replace(&mut self.reachable, false);
+            let statements = core::mem::take(&mut self.statements);
+            self.label(label.clone());
+            block(self);
+            assert!(!self.reachable, "Deferred block {label} must end at unreachable code.");
+            assert!(
+                self.current_hints.is_empty(),
+                "Deferred block {label} cannot end with hints as the last addition."
+            );
+            let block_statements = core::mem::replace(&mut self.statements, statements);
+            self.deferred_blocks.insert(label, DeferredBlock::Built(block_statements));
+            self.main_state = main_state;
+            self.reachable = reachable;
+        }
+    }
+
     /// Rescoping the values, while ignoring all vars not stated in `vars` and giving the vars on
     /// the left side the values of the vars on the right side.
     pub fn rescope<const VAR_COUNT: usize>(&mut self, vars: [(Var, Var); VAR_COUNT]) {
@@ -670,10 +731,32 @@ impl Default for CasmBuilder {
             current_hints: Default::default(),
             var_count: Default::default(),
             reachable: true,
+            deferred_blocks: Default::default(),
         }
     }
 }
 
+/// Replaces the deferred blocks in `statements` with their built statements, recursively.
+fn flatten_statements(
+    statements: Vec<Statement>,
+    deferred_blocks: &mut OrderedHashMap<String, DeferredBlock>,
+) -> Vec<Statement> {
+    let mut flattened = vec![];
+    for statement in statements {
+        let Statement::DeferredBlock(label) = statement else {
+            flattened.push(statement);
+            continue;
+        };
+        match deferred_blocks.swap_remove(&label) {
+            Some(DeferredBlock::Built(block_statements)) => {
+                flattened.extend(flatten_statements(block_statements, deferred_blocks));
+            }
+            _ => panic!("No jump or call to deferred block {label}."),
+        }
+    }
+    flattened
+}
+
 #[macro_export]
 macro_rules! casm_build_extend {
     ($builder:ident,) => {};
@@ -881,6 +964,15 @@ macro_rules! casm_build_extend {
         $builder.ret();
         $crate::casm_build_extend!($builder, $($tok)*)
     };
+    ($builder:ident, block $label:ident { $($block:tt)* } $($tok:tt)*) => {
+        $builder.deferred_block(
+            $crate::builder::ToOwned::to_owned(core::stringify!($label)),
+            move |$builder: &mut $crate::builder::CasmBuilder| {
+                $crate::casm_build_extend!($builder, $($block)*);
+            },
+        );
+        $crate::casm_build_extend!($builder, $($tok)*)
+    };
     ($builder:ident, $label:ident: $($tok:tt)*) => {
         $builder.label($crate::builder::ToOwned::to_owned(core::stringify!($label)));
         $crate::casm_build_extend!($builder, $($tok)*)
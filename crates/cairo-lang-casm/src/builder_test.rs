@@ -355,3 +355,87 @@ fn test_fail() {
         "}
     );
 }
+
+#[test]
+fn test_deferred_shared_epilogue() {
+    let mut builder = CasmBuilder::default();
+    casm_build_extend! {builder,
+        const one = 1;
+        tempvar a = one;
+        jump MAIN;
+        block EPILOGUE {
+            tempvar b = a + one;
+            jump Done;
+        }
+        MAIN:
+        jump EPILOGUE if a != 0;
+        jump EPILOGUE;
+    };
+    let CasmBuildResult { instructions, branches: [(state, awaiting_relocations)] } =
+        builder.build(["Done"]);
+    assert_eq!(state.steps, 6);
+    assert_eq!(awaiting_relocations, [3]);
+    assert_eq!(
+        join(instructions.iter().map(|inst| format!("{inst};\n")), ""),
+        indoc! {"
+            [ap + 0] = 1, ap++;
+            jmp rel 6;
+            [ap + 0] = [ap + -1] + 1, ap++;
+            jmp rel 0;
+            jmp rel -4 if [ap + -1] != 0;
+            jmp rel -6;
+        "}
+    );
+}
+
+#[test]
+fn test_deferred_loop_body() {
+    let mut builder = CasmBuilder::default();
+    casm_build_extend! {builder,
+        const zero = 0;
+        const one = 1;
+        const three = 3;
+        tempvar n = three;
+        tempvar count = zero;
+        rescope{n = n, count = count, one = one};
+        jump COND;
+        block BODY {
+            #{ steps = 0; }
+            tempvar new_n = n - one;
+            tempvar new_count = count + one;
+            rescope{n = new_n, count = new_count, one = one};
+            jump COND;
+        }
+        COND:
+        #{ steps = 0; }
+        jump BODY if n != 0;
+    };
+    let CasmBuildResult { instructions, branches: [(_, awaiting_relocations)] } =
+        builder.build(["Fallthrough"]);
+    assert!(awaiting_relocations.is_empty());
+    assert_eq!(
+        join(instructions.iter().map(|inst| format!("{inst};\n")), ""),
+        indoc! {"
+            [ap + 0] = 3, ap++;
+            [ap + 0] = 0, ap++;
+            jmp rel 8;
+            [ap + -2] = [ap + 0] + 1, ap++;
+            [ap + 0] = [ap + -2] + 1, ap++;
+            jmp rel 2;
+            jmp rel -6 if [ap + -2] != 0;
+        "}
+    );
+}
+
+#[test]
+#[should_panic(expected = "No jump or call to deferred block UNUSED.")]
+fn should_panic_test_deferred_block_without_jump() {
+    let mut builder = CasmBuilder::default();
+    casm_build_extend! {builder,
+        ret;
+        block UNUSED {
+            ret;
+        }
+    };
+    builder.build::<0>([]);
+}
@@ -6,6 +6,7 @@ use crate::ap_change::ApplyApChange;
 use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Operation, ResOperand};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum CellOperator {
     Add,
     Sub,
@@ -26,6 +27,7 @@ impl core::fmt::Display for CellOperator {
 
 /// The expression representing a cell in the casm memory.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum CellExpression {
     Deref(CellRef),
     /// Represents an expression of the form `[[cell_ref] + offset]`.
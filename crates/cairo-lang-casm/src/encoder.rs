@@ -2,6 +2,7 @@
 use alloc::{vec, vec::Vec};
 
 use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 
 use crate::assembler::{ApUpdate, FpUpdate, InstructionRepr, Op1Addr, Opcode, PcUpdate, Res};
 use crate::operand::Register;
@@ -27,6 +28,29 @@ const AP_ADD1_BIT: i32 = 11;
 const OPCODE_CALL_BIT: i32 = 12;
 const OPCODE_RET_BIT: i32 = 13;
 const OPCODE_ASSERT_EQ_BIT: i32 = 14;
+const FLAGS_BITS: u32 = 15;
+
+/// An error decoding an instruction from its encoding.
+#[derive(Debug, Eq, PartialEq)]
+pub enum DecodingError {
+    /// The first word is not a valid instruction encoding.
+    InvalidEncoding(BigInt),
+    /// The bytecode ends before the end of the instruction.
+    UnexpectedEnd,
+}
+impl core::fmt::Display for DecodingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodingError::InvalidEncoding(encoding) => {
+                write!(f, "Invalid instruction encoding: {encoding:#x}.")
+            }
+            DecodingError::UnexpectedEnd => write!(f, "Unexpected end of bytecode."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodingError {}
 
 impl InstructionRepr {
     pub fn encode(&self) -> Vec<BigInt> {
@@ -121,3 +145,92 @@ impl InstructionRepr {
         }
     }
 }
+
+impl InstructionRepr {
+    /// Decodes the instruction at the start of `bytecode`, the inverse of [Self::encode].
+    ///
+    /// Reads the immediate from the second word if the instruction has one. Words following the
+    /// instruction are ignored.
+    pub fn decode(bytecode: &[BigInt]) -> Result<Self, DecodingError> {
+        let Some(encoding_word) = bytecode.first() else {
+            return Err(DecodingError::UnexpectedEnd);
+        };
+        let invalid = || DecodingError::InvalidEncoding(encoding_word.clone());
+        let encoding = encoding_word.to_u64().ok_or_else(invalid)?;
+        let flags = encoding >> (3 * OFFSET_BITS);
+        if flags >> FLAGS_BITS != 0 {
+            return Err(invalid());
+        }
+        let flag = |bit: i32| flags & (1 << bit) != 0;
+        // Returns the index of the set flag among `bits`, or `None` if none is set, and fails if
+        // more than one is set.
+        let choice = |bits: &[i32]| -> Result<Option<usize>, DecodingError> {
+            let mut set = bits.iter().enumerate().filter(|(_, bit)| flag(**bit)).map(|(i, _)| i);
+            let chosen = set.next();
+            if set.next().is_some() { Err(invalid()) } else { Ok(chosen) }
+        };
+        let decode_offset = |index: u32| {
+            let offset_enc = (encoding >> (index * OFFSET_BITS)) & ((1 << OFFSET_BITS) - 1);
+            (offset_enc as i32 - (1 << (OFFSET_BITS - 1))) as i16
+        };
+        let register = |bit: i32| if flag(bit) { Register::FP } else { Register::AP };
+
+        let op1_addr = match choice(&[OP1_IMM_BIT, OP1_AP_BIT, OP1_FP_BIT])? {
+            Some(0) => Op1Addr::Imm,
+            Some(1) => Op1Addr::AP,
+            Some(_) => Op1Addr::FP,
+            None => Op1Addr::Op0,
+        };
+        let pc_update = match choice(&[PC_JUMP_ABS_BIT, PC_JUMP_REL_BIT, PC_JNZ_BIT])? {
+            Some(0) => PcUpdate::Jump,
+            Some(1) => PcUpdate::JumpRel,
+            Some(_) => PcUpdate::Jnz,
+            None => PcUpdate::Regular,
+        };
+        let res = match choice(&[RES_ADD_BIT, RES_MUL_BIT])? {
+            Some(_) if pc_update == PcUpdate::Jnz => return Err(invalid()),
+            Some(0) => Res::Add,
+            Some(_) => Res::Mul,
+            None if pc_update == PcUpdate::Jnz => Res::Unconstrained,
+            None => Res::Op1,
+        };
+        let opcode = match choice(&[OPCODE_CALL_BIT, OPCODE_RET_BIT, OPCODE_ASSERT_EQ_BIT])? {
+            Some(0) => Opcode::Call,
+            Some(1) => Opcode::Ret,
+            Some(_) => Opcode::AssertEq,
+            None => Opcode::Nop,
+        };
+        let ap_update = match choice(&[AP_ADD_BIT, AP_ADD1_BIT])? {
+            Some(_) if opcode == Opcode::Call => return Err(invalid()),
+            Some(0) => ApUpdate::Add,
+            Some(_) => ApUpdate::Add1,
+            None if opcode == Opcode::Call => ApUpdate::Add2,
+            None => ApUpdate::Regular,
+        };
+        let fp_update = match opcode {
+            Opcode::Nop | Opcode::AssertEq => FpUpdate::Regular,
+            Opcode::Call => FpUpdate::ApPlus2,
+            Opcode::Ret => FpUpdate::Dst,
+        };
+        let imm = if op1_addr == Op1Addr::Imm {
+            Some(bytecode.get(1).ok_or(DecodingError::UnexpectedEnd)?.clone())
+        } else {
+            None
+        };
+
+        Ok(InstructionRepr {
+            off0: decode_offset(0),
+            off1: decode_offset(1),
+            off2: decode_offset(2),
+            imm,
+            dst_register: register(DST_REG_BIT),
+            op0_register: register(OP0_REG_BIT),
+            op1_addr,
+            res,
+            pc_update,
+            ap_update,
+            fp_update,
+            opcode,
+        })
+    }
+}
@@ -5,7 +5,9 @@ use num_bigint::BigInt;
 use pretty_assertions::assert_eq;
 use test_case::test_case;
 
+use crate::assembler::InstructionRepr;
 use crate::casm;
+use crate::encoder::DecodingError;
 use crate::inline::CasmContext;
 
 #[test_case(
@@ -111,3 +113,45 @@ fn test_encode_multiple(casm: CasmContext, expected: Vec<i128>) {
         casm.instructions.iter().flat_map(|inst| inst.assemble().encode()).collect();
     assert_eq!(enc, exp);
 }
+
+#[test_case(casm!(jmp abs 3;); "jmp abs 3;")]
+#[test_case(casm!(call rel (-5);); "call rel (-5);")]
+#[test_case(casm!(jmp rel 2 if [ap - 1] != 0, ap++;); "jmp rel 2 if [ap + (-1)] != 0, ap++;")]
+#[test_case(casm!([ap + 5] = 205;); "[ap + 5] = 205;")]
+#[test_case(casm!(ret;); "ret;")]
+#[test_case(casm!(ap += 205;); "ap += 205;")]
+#[test_case(casm!([fp + -3] = [[fp + -2] + 7], ap++;); "[fp + -3] = [[fp + -2] + 7], ap++;")]
+fn test_decode(mut casm: CasmContext) {
+    let repr = casm.instructions.remove(0).assemble();
+    let mut bytecode = repr.encode();
+    // Words following the instruction are ignored.
+    bytecode.push(BigInt::from(17));
+    assert_eq!(InstructionRepr::decode(&bytecode), Ok(repr));
+}
+
+#[test_case(&[], DecodingError::UnexpectedEnd; "empty")]
+#[test_case(&[0x8780017fff7fff], DecodingError::UnexpectedEnd; "missing immediate")]
+#[test_case(
+    &[-1],
+    DecodingError::InvalidEncoding(BigInt::from(-1));
+    "negative"
+)]
+#[test_case(
+    &[0x8000800080008000],
+    DecodingError::InvalidEncoding(BigInt::from(0x8000800080008000u64));
+    "flags overflow"
+)]
+#[test_case(
+    &[0x8f80017fff7fff],
+    DecodingError::InvalidEncoding(BigInt::from(0x8f80017fff7fffu64));
+    "two op1 sources"
+)]
+#[test_case(
+    &[0x22680017fff8005],
+    DecodingError::InvalidEncoding(BigInt::from(0x22680017fff8005u64));
+    "jnz with res"
+)]
+fn test_decode_failure(bytecode: &[i128], error: DecodingError) {
+    let bytecode: Vec<BigInt> = bytecode.iter().copied().map(BigInt::from).collect();
+    assert_eq!(InstructionRepr::decode(&bytecode), Err(error));
+}
@@ -201,7 +201,7 @@ macro_rules! casm_extend {
     ($ctx:ident, %{ syscall_handler.syscall(syscall_ptr=memory $addr:tt + $offset:tt) %} $($tok:tt)*) => {
         $ctx.current_hints.push($crate::hints::CoreHint::SystemCall {
             system: $crate::operand::ResOperand::BinOp($crate::operand::BinOpOperand {
-                op: cairo_lang_casm::operand::Operation::Add,
+                op: $crate::operand::Operation::Add,
                 a: $crate::deref!($addr),
                 b: $crate::deref_or_immediate!(num_bigint::BigInt::from($offset)),
             })}.into());
@@ -213,6 +213,38 @@ macro_rules! casm_extend {
         }.into());
         $crate::casm_extend!($ctx, $($tok)*)
     };
+    ($ctx:ident, %{ hint $hint_head:ident$(::$hint_tail:ident)+ {
+            $($field:ident : $value:tt),* $(,)?
+        } %} $($tok:tt)*) => {
+        $ctx.current_hints.push($hint_head$(::$hint_tail)+ {
+            $($field: $crate::hint_arg!($value).into(),)*
+        }.into());
+        $crate::casm_extend!($ctx, $($tok)*)
+    };
+    ($ctx:ident, %{ hint $hint_name:ident {
+            $($field:ident : $value:tt),* $(,)?
+        } %} $($tok:tt)*) => {
+        $crate::casm_extend!($ctx, %{ hint $crate::hints::CoreHint::$hint_name {
+            $($field : $value),*
+        } %} $($tok)*)
+    };
+}
+
+/// Converts an argument of a hint in the `casm!` macro into an operand, to be converted into the
+/// type of the hint field.
+/// A cell reference such as `[ap + 1]` converts into a `CellRef` or a `ResOperand`, while a double
+/// dereference such as `[[fp - 3] + 2]` or an immediate only converts into a `ResOperand`.
+#[macro_export]
+macro_rules! hint_arg {
+    ([[$($inner:tt)*] $($outer:tt)*]) => {
+        $crate::res!([[$($inner)*] $($outer)*])
+    };
+    ([$($cell:tt)*]) => {
+        $crate::deref!([$($cell)*])
+    };
+    ($a:tt) => {
+        $crate::deref_or_immediate!($a)
+    };
 }
 
 #[macro_export]
@@ -76,3 +76,27 @@ fn test_assert() {
             ret"}
     );
 }
+
+#[test]
+fn test_structured_hints() {
+    let cell = deref!([fp + 2]);
+    let ctx = casm! {
+        %{ hint AllocSegment { dst: [ap + 5] } %}
+        %{ hint TestLessThan { lhs: [fp - 3], rhs: 45, dst: [ap] } %}
+        %{ hint TestLessThanOrEqual { lhs: (1 << 8), rhs: [&cell], dst: [&cell + 1] } %}
+        %{ hint DivMod { lhs: [[fp - 3] + 2], rhs: [[ap]], quotient: [ap], remainder: [ap + 1] } %}
+        %{ hint crate::hints::StarknetHint::SystemCall { system: [fp - 3] } %}
+        [ap] = [fp - 5];
+    };
+
+    assert_eq!(
+        ctx.instructions[0].to_string(),
+        indoc! {"
+            %{ memory[ap + 5] = segments.add() %}
+            %{ memory[ap + 0] = memory[fp + -3] < 45 %}
+            %{ memory[fp + 3] = 256 <= memory[fp + 2] %}
+            %{ (memory[ap + 0], memory[ap + 1]) = divmod(memory[memory[fp + -3] + 2], memory[memory[ap + 0] + 0]) %}
+            %{ syscall_handler.syscall(syscall_ptr=memory[fp + -3]) %}
+            [ap + 0] = [fp + -5]"}
+    );
+}
@@ -11,6 +11,7 @@ mod test;
 
 // An enum of Cairo instructions.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum InstructionBody {
     AddAp(AddApInstruction),
     AssertEq(AssertEqInstruction),
@@ -47,6 +48,7 @@ impl Display for InstructionBody {
 
 /// Represents an instruction, including the ap++ flag (inc_ap).
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Instruction {
     pub body: InstructionBody,
     pub inc_ap: bool,
@@ -80,6 +82,7 @@ impl Display for Instruction {
 
 /// Represents a call instruction "call rel/abs target".
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct CallInstruction {
     pub target: DerefOrImmediate,
     pub relative: bool,
@@ -100,6 +103,7 @@ impl CallInstruction {
 
 /// Represents the InstructionBody "jmp rel/abs target".
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct JumpInstruction {
     pub target: DerefOrImmediate,
     pub relative: bool,
@@ -120,6 +124,7 @@ impl Display for JumpInstruction {
 
 /// Represents the InstructionBody "jmp rel <jump_offset> if condition != 0".
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct JnzInstruction {
     pub jump_offset: DerefOrImmediate,
     pub condition: CellRef,
@@ -153,6 +158,7 @@ pub fn op_size_based_on_res_operands(operand: &ResOperand) -> usize {
 
 /// Represents the InstructionBody "a = b" for two operands a, b.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct AssertEqInstruction {
     pub a: CellRef,
     pub b: ResOperand,
@@ -170,6 +176,7 @@ impl Display for AssertEqInstruction {
 
 /// Represents a return instruction, "ret".
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct RetInstruction {}
 impl Display for RetInstruction {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -185,6 +192,7 @@ impl RetInstruction {
 
 /// Represents the InstructionBody "ap += op" for a given operand op.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct AddApInstruction {
     pub operand: ResOperand,
 }
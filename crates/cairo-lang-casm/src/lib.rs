@@ -14,3 +14,4 @@ pub mod hints;
 pub mod inline;
 pub mod instructions;
 pub mod operand;
+pub mod parser;
@@ -65,6 +65,12 @@ impl From<DerefOrImmediate> for ResOperand {
     }
 }
 
+impl From<CellRef> for ResOperand {
+    fn from(x: CellRef) -> Self {
+        ResOperand::Deref(x)
+    }
+}
+
 impl<T: Into<BigIntAsHex>> From<T> for ResOperand {
     fn from(imm: T) -> Self {
         ResOperand::Immediate(imm.into())
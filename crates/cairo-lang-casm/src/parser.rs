@@ -0,0 +1,477 @@
+//! Parsing of the textual representation of CASM programs, as printed by the compiler, back into
+//! instructions.
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use cairo_lang_utils::bigint::BigIntAsHex;
+use num_bigint::BigInt;
+
+use crate::hints::{CoreHint, Hint, StarknetHint};
+use crate::instructions::{
+    AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
+    JnzInstruction, JumpInstruction, RetInstruction,
+};
+use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand};
+
+#[cfg(test)]
+#[path = "parser_test.rs"]
+mod test;
+
+/// A statement of a CASM program.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum CasmStatement {
+    /// An instruction, with its hints.
+    Instruction(Instruction),
+    /// A `dw` directive, placing a single value in the bytecode.
+    DataWord(BigInt),
+}
+impl CasmStatement {
+    /// Returns the bytecode of the statement.
+    pub fn encode(&self) -> Vec<BigInt> {
+        match self {
+            CasmStatement::Instruction(instruction) => instruction.assemble().encode(),
+            CasmStatement::DataWord(value) => vec![value.clone()],
+        }
+    }
+}
+
+/// An error parsing a CASM program. Lines are 1-based.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The text at `line` does not match the expected syntax.
+    UnexpectedToken { line: usize, expected: &'static str, found: String },
+    /// The hint at `line` is not one of the supported hints.
+    UnsupportedHint { line: usize, hint: String },
+    /// The hint starting at `line` is not closed with `%}`.
+    UnterminatedHint { line: usize },
+    /// The hints before `line` are not followed by an instruction.
+    DanglingHints { line: usize },
+    /// The instruction at `line` cannot have an `ap++`.
+    InvalidApIncrement { line: usize },
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::UnexpectedToken { line, expected, found } => {
+                write!(f, "Line {line}: Expected {expected}, found `{found}`.")
+            }
+            ParseError::UnsupportedHint { line, hint } => {
+                write!(f, "Line {line}: Unsupported hint `{hint}`.")
+            }
+            ParseError::UnterminatedHint { line } => write!(f, "Line {line}: Unterminated hint."),
+            ParseError::DanglingHints { line } => {
+                write!(f, "Line {line}: Hints must be followed by an instruction.")
+            }
+            ParseError::InvalidApIncrement { line } => {
+                write!(f, "Line {line}: The instruction cannot have an `ap++`.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parses the textual representation of a CASM program, as printed by the `Display`
+/// implementations of the compiled programs and of [Instruction] (with a `;` after each
+/// instruction).
+///
+/// Besides the printed syntax, the parser accepts cell references without an offset (`[ap]`) or
+/// with a subtracted offset (`[fp - 3]`), and `//` comments. Only the hints that print on a single
+/// line and have a matching syntax in the [crate::casm] macro (and `WideMul128`) are supported.
+pub fn parse_casm(text: &str) -> Result<Vec<CasmStatement>, ParseError> {
+    let mut parser = Parser { text, pos: 0 };
+    let mut statements = vec![];
+    let mut hints = vec![];
+    loop {
+        parser.skip_whitespace();
+        if parser.rest().is_empty() {
+            break;
+        }
+        let line = parser.line();
+        if parser.eat("%{") {
+            let Some(end) = parser.rest().find("%}") else {
+                return Err(ParseError::UnterminatedHint { line });
+            };
+            let hint_text = parser.rest()[..end].trim();
+            hints.push(parse_hint(hint_text).ok_or_else(|| ParseError::UnsupportedHint {
+                line,
+                hint: hint_text.to_string(),
+            })?);
+            parser.pos += end + "%}".len();
+            continue;
+        }
+        if parser.eat_word("dw") {
+            if !hints.is_empty() {
+                return Err(ParseError::DanglingHints { line });
+            }
+            statements.push(CasmStatement::DataWord(parser.integer()?));
+        } else {
+            let body = parser.instruction_body()?;
+            let inc_ap = parser.eat(",");
+            if inc_ap {
+                parser.expect_word("ap")?;
+                parser.expect("++")?;
+                if matches!(
+                    body,
+                    InstructionBody::AddAp(_) | InstructionBody::Call(_) | InstructionBody::Ret(_)
+                ) {
+                    return Err(ParseError::InvalidApIncrement { line });
+                }
+            }
+            statements.push(CasmStatement::Instruction(Instruction {
+                body,
+                inc_ap,
+                hints: core::mem::take(&mut hints),
+            }));
+        }
+        parser.expect(";")?;
+    }
+    if !hints.is_empty() {
+        return Err(ParseError::DanglingHints { line: parser.line() });
+    }
+    Ok(statements)
+}
+
+/// Parses the pythonic representation of a hint, if it is one of the supported hints.
+fn parse_hint(text: &str) -> Option<Hint> {
+    let mut parser = Parser { text, pos: 0 };
+    let hint = parser.hint().ok()?;
+    parser.skip_whitespace();
+    parser.rest().is_empty().then_some(hint)
+}
+
+/// A parser of CASM text, holding the position of the text yet to be parsed.
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+impl<'a> Parser<'a> {
+    /// Returns the text yet to be parsed.
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// Returns the 1-based line of the current position.
+    fn line(&self) -> usize {
+        self.text[..self.pos].matches('\n').count() + 1
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with("//") {
+                return;
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    /// Consumes `token` if the text continues with it.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if !self.rest().starts_with(token) {
+            return false;
+        }
+        self.pos += token.len();
+        true
+    }
+
+    /// Consumes `word` if the text continues with it as a whole word.
+    fn eat_word(&mut self, word: &str) -> bool {
+        self.skip_whitespace();
+        let Some(after) = self.rest().strip_prefix(word) else {
+            return false;
+        };
+        if after.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+            return false;
+        }
+        self.pos += word.len();
+        true
+    }
+
+    /// Consumes `token`, or fails if the text does not continue with it.
+    fn expect(&mut self, token: &'static str) -> Result<(), ParseError> {
+        if self.eat(token) { Ok(()) } else { Err(self.unexpected(token)) }
+    }
+
+    /// Consumes `word`, or fails if the text does not continue with it as a whole word.
+    fn expect_word(&mut self, word: &'static str) -> Result<(), ParseError> {
+        if self.eat_word(word) { Ok(()) } else { Err(self.unexpected(word)) }
+    }
+
+    /// Returns an error for text that does not continue with `expected`.
+    fn unexpected(&mut self, expected: &'static str) -> ParseError {
+        self.skip_whitespace();
+        let found = self.rest().split_whitespace().next().unwrap_or_default().to_string();
+        ParseError::UnexpectedToken { line: self.line(), expected, found }
+    }
+
+    /// Parses a decimal integer, possibly negative.
+    fn integer(&mut self) -> Result<BigInt, ParseError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let digits_start = usize::from(rest.starts_with('-'));
+        let len = rest[digits_start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest.len(), |n_digits| digits_start + n_digits);
+        let value = rest[..len].parse().map_err(|_| self.unexpected("an integer"))?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    /// Parses an offset, possibly negative.
+    fn offset(&mut self) -> Result<i16, ParseError> {
+        let start = self.pos;
+        self.integer()?.try_into().map_err(|_| {
+            self.pos = start;
+            self.unexpected("an offset")
+        })
+    }
+
+    /// Parses a register.
+    fn register(&mut self) -> Result<Register, ParseError> {
+        if self.eat_word("ap") {
+            Ok(Register::AP)
+        } else if self.eat_word("fp") {
+            Ok(Register::FP)
+        } else {
+            Err(self.unexpected("a register"))
+        }
+    }
+
+    /// Parses a cell reference, e.g. `[ap + 1]`.
+    fn cell_ref(&mut self) -> Result<CellRef, ParseError> {
+        self.expect("[")?;
+        let cell_ref = self.cell_ref_content()?;
+        self.expect("]")?;
+        Ok(cell_ref)
+    }
+
+    /// Parses a cell reference without its brackets, e.g. `ap + 1`.
+    fn cell_ref_content(&mut self) -> Result<CellRef, ParseError> {
+        let register = self.register()?;
+        let offset = self.added_offset()?.unwrap_or_default();
+        Ok(CellRef { register, offset })
+    }
+
+    /// Parses an optional offset added to a value, e.g. `+ 1`, `+ -1` or `- 1`.
+    fn added_offset(&mut self) -> Result<Option<i16>, ParseError> {
+        if self.eat("+") {
+            Ok(Some(self.offset()?))
+        } else if self.eat("-") {
+            let offset = self.offset()?;
+            Ok(Some(offset.checked_neg().ok_or_else(|| self.unexpected("an offset"))?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses a cell reference or an immediate.
+    fn deref_or_immediate(&mut self) -> Result<DerefOrImmediate, ParseError> {
+        self.skip_whitespace();
+        if self.rest().starts_with('[') {
+            Ok(DerefOrImmediate::Deref(self.cell_ref()?))
+        } else {
+            Ok(DerefOrImmediate::Immediate(BigIntAsHex { value: self.integer()? }))
+        }
+    }
+
+    /// Parses a res operand, e.g. `[ap + 1]`, `[[ap + 1] + 2]`, `[ap + 1] * [fp + 2]` or `5`.
+    fn res_operand(&mut self) -> Result<ResOperand, ParseError> {
+        self.skip_whitespace();
+        if !self.rest().starts_with('[') {
+            return Ok(ResOperand::Immediate(BigIntAsHex { value: self.integer()? }));
+        }
+        if self.eat("[[") {
+            let inner = self.cell_ref_content()?;
+            self.expect("]")?;
+            let offset = self.added_offset()?.unwrap_or_default();
+            self.expect("]")?;
+            return Ok(ResOperand::DoubleDeref(inner, offset));
+        }
+        let a = self.cell_ref()?;
+        let op = if self.eat("+") {
+            Operation::Add
+        } else if self.eat("*") {
+            Operation::Mul
+        } else {
+            return Ok(ResOperand::Deref(a));
+        };
+        Ok(ResOperand::BinOp(BinOpOperand { op, a, b: self.deref_or_immediate()? }))
+    }
+
+    /// Parses the target of a jump or a call, e.g. `rel 5`, returning whether it is relative.
+    fn jump_target(&mut self) -> Result<(bool, DerefOrImmediate), ParseError> {
+        let relative = if self.eat_word("rel") {
+            true
+        } else if self.eat_word("abs") {
+            false
+        } else {
+            return Err(self.unexpected("`rel` or `abs`"));
+        };
+        Ok((relative, self.deref_or_immediate()?))
+    }
+
+    /// Parses the body of an instruction.
+    fn instruction_body(&mut self) -> Result<InstructionBody, ParseError> {
+        if self.eat_word("ret") {
+            return Ok(InstructionBody::Ret(RetInstruction {}));
+        }
+        if self.eat_word("call") {
+            let (relative, target) = self.jump_target()?;
+            return Ok(InstructionBody::Call(CallInstruction { target, relative }));
+        }
+        if self.eat_word("jmp") {
+            let (relative, target) = self.jump_target()?;
+            if !self.eat_word("if") {
+                return Ok(InstructionBody::Jump(JumpInstruction { target, relative }));
+            }
+            if !relative {
+                return Err(self.unexpected("`;` after an absolute jump"));
+            }
+            let condition = self.cell_ref()?;
+            self.expect("!=")?;
+            self.expect("0")?;
+            return Ok(InstructionBody::Jnz(JnzInstruction { jump_offset: target, condition }));
+        }
+        if self.eat_word("ap") {
+            self.expect("+=")?;
+            return Ok(InstructionBody::AddAp(AddApInstruction { operand: self.res_operand()? }));
+        }
+        self.skip_whitespace();
+        if !self.rest().starts_with('[') {
+            return Err(self.unexpected("an instruction"));
+        }
+        let a = self.cell_ref()?;
+        self.expect("=")?;
+        Ok(InstructionBody::AssertEq(AssertEqInstruction { a, b: self.res_operand()? }))
+    }
+
+    /// Parses a supported hint.
+    fn hint(&mut self) -> Result<Hint, ParseError> {
+        if self.eat("syscall_handler.syscall(syscall_ptr=") {
+            let system = self.hint_address_operand()?;
+            self.expect(")")?;
+            return Ok(StarknetHint::SystemCall { system }.into());
+        }
+        if self.eat("(") {
+            let first = self.hint_cell_ref()?;
+            self.expect(",")?;
+            let second = self.hint_cell_ref()?;
+            self.expect(")")?;
+            self.expect("=")?;
+            self.expect("divmod(")?;
+            let lhs = self.hint_integer_operand()?;
+            if self.eat("*") {
+                let rhs = self.hint_integer_operand()?;
+                self.expect(",")?;
+                self.expect("2**128")?;
+                self.expect(")")?;
+                return Ok(CoreHint::WideMul128 { lhs, rhs, high: first, low: second }.into());
+            }
+            self.expect(",")?;
+            let rhs = self.hint_integer_operand()?;
+            self.expect(")")?;
+            return Ok(CoreHint::DivMod { lhs, rhs, quotient: first, remainder: second }.into());
+        }
+        let dst = self.hint_cell_ref()?;
+        self.expect("=")?;
+        if self.eat("segments.add()") {
+            return Ok(CoreHint::AllocSegment { dst }.into());
+        }
+        let lhs = self.hint_integer_operand()?;
+        let or_equal = if self.eat("<=") {
+            true
+        } else {
+            self.expect("<")?;
+            false
+        };
+        let rhs = self.hint_integer_operand()?;
+        Ok(if or_equal {
+            CoreHint::TestLessThanOrEqual { lhs, rhs, dst }
+        } else {
+            CoreHint::TestLessThan { lhs, rhs, dst }
+        }
+        .into())
+    }
+
+    /// Parses a memory cell in a hint, e.g. `memory[ap + 1]`.
+    fn hint_cell_ref(&mut self) -> Result<CellRef, ParseError> {
+        self.expect_word("memory")?;
+        self.cell_ref()
+    }
+
+    /// Parses a doubly dereferenced operand in a hint, e.g. `memory[memory[ap + 1] + 2]`, if the
+    /// text continues with one.
+    fn hint_double_deref(&mut self) -> Result<Option<ResOperand>, ParseError> {
+        let start = self.pos;
+        if !(self.eat_word("memory") && self.eat("[") && self.eat_word("memory")) {
+            self.pos = start;
+            return Ok(None);
+        }
+        let inner = self.cell_ref()?;
+        let offset = self.added_offset()?.unwrap_or_default();
+        self.expect("]")?;
+        Ok(Some(ResOperand::DoubleDeref(inner, offset)))
+    }
+
+    /// Parses a memory cell or an immediate in a hint.
+    fn hint_deref_or_immediate(&mut self) -> Result<DerefOrImmediate, ParseError> {
+        self.skip_whitespace();
+        if self.rest().starts_with("memory") {
+            Ok(DerefOrImmediate::Deref(self.hint_cell_ref()?))
+        } else {
+            Ok(DerefOrImmediate::Immediate(BigIntAsHex { value: self.integer()? }))
+        }
+    }
+
+    /// Parses a binary operation between a memory cell and another operand in a hint, e.g.
+    /// `memory[ap + 1] + 5`.
+    fn hint_bin_op(&mut self) -> Result<ResOperand, ParseError> {
+        let a = self.hint_cell_ref()?;
+        let op = if self.eat("+") {
+            Operation::Add
+        } else if self.eat("*") {
+            Operation::Mul
+        } else {
+            return Ok(ResOperand::Deref(a));
+        };
+        Ok(ResOperand::BinOp(BinOpOperand { op, a, b: self.hint_deref_or_immediate()? }))
+    }
+
+    /// Parses an operand of a hint used as an integer, e.g. `(memory[ap + 1] + 5) % PRIME`.
+    fn hint_integer_operand(&mut self) -> Result<ResOperand, ParseError> {
+        if let Some(operand) = self.hint_double_deref()? {
+            return Ok(operand);
+        }
+        if self.eat("(") {
+            let operand = self.hint_bin_op()?;
+            self.expect(")")?;
+            self.expect("%")?;
+            self.expect_word("PRIME")?;
+            return Ok(operand);
+        }
+        self.skip_whitespace();
+        if self.rest().starts_with("memory") {
+            return Ok(ResOperand::Deref(self.hint_cell_ref()?));
+        }
+        Ok(ResOperand::Immediate(BigIntAsHex { value: self.integer()? }))
+    }
+
+    /// Parses an operand of a hint used as an address, e.g. `memory[ap + 1] + 5`.
+    fn hint_address_operand(&mut self) -> Result<ResOperand, ParseError> {
+        if let Some(operand) = self.hint_double_deref()? {
+            return Ok(operand);
+        }
+        self.hint_bin_op()
+    }
+}
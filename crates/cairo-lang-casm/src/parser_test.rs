@@ -0,0 +1,168 @@
+use indoc::indoc;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::{parse_casm, CasmStatement, ParseError};
+use crate::casm;
+use crate::inline::CasmContext;
+use crate::instructions::Instruction;
+
+/// Returns the textual representation of the instructions, as printed by the compiler.
+fn to_text(instructions: &[Instruction]) -> String {
+    instructions.iter().map(|instruction| format!("{instruction};\n")).join("")
+}
+
+#[test_case(casm!(jmp abs 3;); "jmp abs")]
+#[test_case(casm!(jmp rel -5, ap++;); "jmp rel with ap++")]
+#[test_case(casm!(jmp abs [ap + 2];); "jmp to deref")]
+#[test_case(casm!(call abs 3;); "call abs")]
+#[test_case(casm!(call rel [fp - 2];); "call rel to deref")]
+#[test_case(casm!(jmp rel 205 if [ap + 5] != 0;); "jnz")]
+#[test_case(casm!(jmp rel [fp + 1] if [ap - 1] != 0, ap++;); "jnz to deref with ap++")]
+#[test_case(casm!([ap + 5] = 205;); "assert immediate")]
+#[test_case(casm!([ap + 5] = (-205), ap++;); "assert negative immediate")]
+#[test_case(casm!([fp - 3] = [[fp - 2] + 7];); "assert double deref")]
+#[test_case(casm!([fp - 3] = [[ap]];); "assert double deref without offset")]
+#[test_case(casm!([ap] = [fp - 5] + [fp - 4], ap++;); "assert add")]
+#[test_case(casm!([fp - 3] = [ap] * (-7);); "assert mul")]
+#[test_case(casm!(ap += 205;); "add ap")]
+#[test_case(casm!(ap += [fp + 3];); "add ap deref")]
+#[test_case(casm!(ret;); "ret")]
+#[test_case(casm! {
+    %{ memory[ap + 0] = segments.add() %}
+    %{ memory[ap + 1] = memory[fp + -3] < memory[[fp + -4]] %}
+    %{ memory[ap + 2] = memory[fp + 3] <= 5 %}
+    %{ (memory[ap + 3], memory[ap + 4]) = divmod(memory[fp - 3], 7) %}
+    [ap] = 1, ap++;
+}; "hints")]
+fn test_parse_round_trip(casm: CasmContext) {
+    let text = to_text(&casm.instructions);
+    assert_eq!(
+        parse_casm(&text),
+        Ok(casm.instructions.into_iter().map(CasmStatement::Instruction).collect()),
+        "{text}"
+    );
+}
+
+#[test]
+fn test_parse_hints_outside_casm_macro() {
+    let statements = parse_casm(indoc! {"
+        %{ (memory[ap + 0], memory[ap + 1]) = divmod(memory[fp + -3] * 5, 2**128) %}
+        %{ memory[ap + 2] = (memory[fp + -3] + memory[fp + -4]) % PRIME < memory[memory[fp + 1] + 2] %}
+        %{ syscall_handler.syscall(syscall_ptr=memory[fp + -4] + 3) %}
+        ret;
+    "})
+    .unwrap();
+    let [CasmStatement::Instruction(instruction)] = &statements[..] else {
+        panic!("Expected a single instruction.");
+    };
+    assert_eq!(
+        to_text(std::slice::from_ref(instruction)),
+        indoc! {"
+        %{ (memory[ap + 0], memory[ap + 1]) = divmod(memory[fp + -3] * 5, 2**128) %}
+        %{ memory[ap + 2] = (memory[fp + -3] + memory[fp + -4]) % PRIME < memory[memory[fp + 1] + 2] %}
+        %{ syscall_handler.syscall(syscall_ptr=memory[fp + -4] + 3) %}
+        ret;
+    "}
+    );
+}
+
+#[test]
+fn test_parse_program_with_data() {
+    let statements = parse_casm(indoc! {"
+        [ap + 0] = 5, ap++; // 0
+        call rel 4; // 2
+        ret; // 4
+        ret; // 5
+        dw 5; // 6
+        dw -17; // 7
+    "})
+    .unwrap();
+    let mut expected = casm! {
+        [ap + 0] = 5, ap++;
+        call rel 4;
+        ret;
+        ret;
+    }
+    .instructions
+    .into_iter()
+    .map(CasmStatement::Instruction)
+    .collect_vec();
+    expected.push(CasmStatement::DataWord(BigInt::from(5)));
+    expected.push(CasmStatement::DataWord(BigInt::from(-17)));
+    assert_eq!(statements, expected);
+    assert_eq!(
+        statements.iter().flat_map(CasmStatement::encode).collect_vec(),
+        [
+            0x480680017fff8000_i64,
+            5,
+            0x1104800180018000,
+            4,
+            0x208b7fff7fff7ffe,
+            0x208b7fff7fff7ffe,
+            5,
+            -17
+        ]
+        .map(BigInt::from)
+    );
+}
+
+#[test_case(
+    "ret",
+    ParseError::UnexpectedToken { line: 1, expected: ";", found: "".into() };
+    "missing semicolon"
+)]
+#[test_case(
+    "ret;\n[ap + 0] = [bp + 1];",
+    ParseError::UnexpectedToken { line: 2, expected: "a register", found: "bp".into() };
+    "invalid register"
+)]
+#[test_case(
+    "[ap + 40000] = 1;",
+    ParseError::UnexpectedToken { line: 1, expected: "an offset", found: "40000]".into() };
+    "offset out of range"
+)]
+#[test_case(
+    "nop;",
+    ParseError::UnexpectedToken { line: 1, expected: "an instruction", found: "nop;".into() };
+    "unknown instruction"
+)]
+#[test_case(
+    "jmp abs 5 if [ap + 0] != 0;",
+    ParseError::UnexpectedToken {
+        line: 1,
+        expected: "`;` after an absolute jump",
+        found: "[ap".into(),
+    };
+    "absolute jnz"
+)]
+#[test_case(
+    "ret, ap++;",
+    ParseError::InvalidApIncrement { line: 1 };
+    "ret with ap++"
+)]
+#[test_case(
+    "%{ memory[ap + 0] = segments.add() %}\ndw 5;",
+    ParseError::DanglingHints { line: 2 };
+    "hint before data"
+)]
+#[test_case(
+    "ret;\n%{ memory[ap + 0] = segments.add() %}",
+    ParseError::DanglingHints { line: 2 };
+    "hint at the end"
+)]
+#[test_case(
+    "%{ memory[ap + 0] = segments.add()\nret;",
+    ParseError::UnterminatedHint { line: 1 };
+    "unterminated hint"
+)]
+#[test_case(
+    "%{ raise NotImplementedError %}\nret;",
+    ParseError::UnsupportedHint { line: 1, hint: "raise NotImplementedError".into() };
+    "unsupported hint"
+)]
+fn test_parse_failure(text: &str, error: ParseError) {
+    assert_eq!(parse_casm(text), Err(error));
+}
@@ -0,0 +1,323 @@
+//! Crash reporting for the compiler CLI drivers.
+//!
+//! A driver wraps its main logic with [CrashReporter::run]. If the compiler panics, a crash report
+//! is written to a file, containing the panic message, the compiler frames of the backtrace at the
+//! time of the panic, the involved input files and the compiler configuration. Paths of the machine
+//! running the compiler are redacted from the report.
+//!
+//! The frames are taken from the native backtrace, so they show the compiler functions being run
+//! (e.g. the lowering of a function). The salsa query stack is not captured, so the report doesn't
+//! include the queries being computed or their keys.
+//!
+//! The behavior can be controlled with the following environment variables:
+//! - `CAIRO_CRASH_REPORT_DIR` - the directory to write crash reports to (default: the temporary
+//!   directory of the system).
+//! - `CAIRO_CRASH_MINIMIZE` - if set, and the crash is reproduced by compiling a single input file,
+//!   the report will include a minimized version of that file which still crashes the compiler.
+//!   Only `cairo-compile` enables this, with [CrashReporter::with_minimization].
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs, process};
+
+use crate::diagnostics::DiagnosticsReporter;
+use crate::{compile_cairo_project_at_path, CompilerConfig};
+
+#[cfg(test)]
+#[path = "crash_report_test.rs"]
+mod test;
+
+/// The environment variable for the directory crash reports are written to.
+const CRASH_REPORT_DIR_ENV: &str = "CAIRO_CRASH_REPORT_DIR";
+/// The environment variable enabling minimization of the crashing source.
+const CRASH_MINIMIZE_ENV: &str = "CAIRO_CRASH_MINIMIZE";
+
+/// The information recorded by the panic hook about the last panic.
+#[derive(Clone, Debug)]
+struct PanicRecord {
+    message: String,
+    location: Option<String>,
+    compiler_frames: Vec<String>,
+}
+
+/// The last panic recorded by the panic hook.
+static LAST_PANIC: Mutex<Option<PanicRecord>> = Mutex::new(None);
+/// Whether the panic hook should avoid printing the panic, used while minimizing.
+static QUIET_PANICS: Mutex<bool> = Mutex::new(false);
+
+/// Collects the context of a CLI driver run, and writes a crash report if the run panics.
+pub struct CrashReporter {
+    tool: String,
+    inputs: Vec<PathBuf>,
+    config: Vec<(String, String)>,
+    minimize: bool,
+}
+impl CrashReporter {
+    /// Creates a crash reporter for the given tool name.
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self { tool: tool.into(), inputs: vec![], config: vec![], minimize: false }
+    }
+
+    /// Adds an input path of the run, to be mentioned in the report.
+    pub fn with_input(mut self, path: impl Into<PathBuf>) -> Self {
+        self.inputs.push(path.into());
+        self
+    }
+
+    /// Adds a configuration entry of the run, to be mentioned in the report.
+    pub fn with_config(mut self, key: impl Into<String>, value: impl std::fmt::Debug) -> Self {
+        self.config.push((key.into(), format!("{value:?}")));
+        self
+    }
+
+    /// Enables minimizing the crashing input when `CAIRO_CRASH_MINIMIZE` is set.
+    /// The minimizer reproduces the crash with [compile_cairo_project_at_path] and the default
+    /// configuration, so it only fits drivers compiling a plain Cairo project, i.e.
+    /// `cairo-compile`.
+    pub fn with_minimization(mut self) -> Self {
+        self.minimize = true;
+        self
+    }
+
+    /// Runs `f`, writing a crash report if it panics. The panic is resumed after the report is
+    /// written.
+    pub fn run<T>(self, f: impl FnOnce() -> T) -> T {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            record_panic(info.payload(), info.location());
+            if !*QUIET_PANICS.lock().unwrap() {
+                previous_hook(info);
+            }
+        }));
+        let payload = match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => return result,
+            Err(payload) => payload,
+        };
+        let Some(record) = LAST_PANIC.lock().unwrap().take() else {
+            panic::resume_unwind(payload);
+        };
+        let reproduction = if self.minimize && env::var_os(CRASH_MINIMIZE_ENV).is_some() {
+            self.minimized_reproduction(&record)
+        } else {
+            None
+        };
+        let report = CrashReport {
+            tool: self.tool,
+            version: env!("CARGO_PKG_VERSION").into(),
+            message: record.message,
+            location: record.location,
+            compiler_frames: record.compiler_frames,
+            inputs: self.inputs.iter().map(|path| path.display().to_string()).collect(),
+            config: self.config,
+            reproduction,
+        };
+        let report_dir =
+            env::var_os(CRASH_REPORT_DIR_ENV).map(PathBuf::from).unwrap_or_else(env::temp_dir);
+        match report.write_to_dir(&report_dir) {
+            Ok(path) => eprintln!(
+                "error: The compiler unexpectedly crashed. A crash report was written to \
+                 `{}`.\nPlease attach it when reporting the issue.",
+                path.display()
+            ),
+            Err(err) => eprintln!("error: Failed to write a crash report: {err}"),
+        }
+        panic::resume_unwind(payload);
+    }
+
+    /// Tries to minimize the single input file of the run, keeping the same panic message when
+    /// compiling it.
+    fn minimized_reproduction(&self, record: &PanicRecord) -> Option<String> {
+        let [input] = &self.inputs[..] else {
+            return None;
+        };
+        let source = fs::read_to_string(input).ok()?;
+        let file_name = input.file_name()?;
+        let dir = env::temp_dir().join(format!("cairo-crash-minimize-{}", process::id()));
+        fs::create_dir_all(&dir).ok()?;
+        let path = dir.join(file_name);
+        *QUIET_PANICS.lock().unwrap() = true;
+        let mut reproduces = |candidate: &str| {
+            fs::write(&path, candidate).is_ok()
+                && compilation_panic(&path).is_some_and(|message| message == record.message)
+        };
+        let minimized = reproduces(&source).then(|| minimize_source(&source, &mut reproduces));
+        *QUIET_PANICS.lock().unwrap() = false;
+        fs::remove_dir_all(&dir).ok();
+        minimized
+    }
+}
+
+/// Records the panic with the given payload and location as the last panic.
+///
+/// Takes the parts of the panic info rather than the info itself, as the name of its type differs
+/// between the supported toolchains.
+fn record_panic(payload: &(dyn Any + Send), location: Option<&Location<'_>>) {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".into()
+    };
+    let location = location.map(|location| location.to_string());
+    let compiler_frames = compiler_frames(&Backtrace::force_capture().to_string());
+    *LAST_PANIC.lock().unwrap() = Some(PanicRecord { message, location, compiler_frames });
+}
+
+/// Compiles the project at the given path, returning the panic message if the compilation panics.
+fn compilation_panic(path: &Path) -> Option<String> {
+    LAST_PANIC.lock().unwrap().take();
+    let result = panic::catch_unwind(|| {
+        compile_cairo_project_at_path(
+            path,
+            CompilerConfig {
+                diagnostics_reporter: DiagnosticsReporter::ignoring(),
+                ..CompilerConfig::default()
+            },
+        )
+    });
+    if result.is_ok() {
+        return None;
+    }
+    LAST_PANIC.lock().unwrap().take().map(|record| record.message)
+}
+
+/// Extracts the compiler frames from a rendered backtrace, innermost first.
+/// Frames of the standard library, of dependencies and of the crash reporter itself are dropped,
+/// so that the remaining frames describe the compiler functions being run.
+pub fn compiler_frames(backtrace: &str) -> Vec<String> {
+    let mut frames: Vec<String> = backtrace
+        .lines()
+        .filter_map(|line| {
+            let (index, symbol) = line.trim().split_once(": ")?;
+            index.parse::<usize>().ok()?;
+            Some(strip_symbol_hash(symbol))
+        })
+        .filter(|symbol| {
+            symbol.starts_with("cairo_lang_")
+                && !symbol.starts_with("cairo_lang_compiler::crash_report::")
+        })
+        .map(|symbol| symbol.to_string())
+        .collect();
+    frames.dedup();
+    frames
+}
+
+/// Removes the trailing `::h<hash>` part of a symbol name, if any.
+fn strip_symbol_hash(symbol: &str) -> &str {
+    match symbol.rsplit_once("::h") {
+        Some((prefix, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            prefix
+        }
+        _ => symbol,
+    }
+}
+
+/// Replaces machine specific paths in `text` with placeholders.
+pub fn redact_paths(text: &str) -> String {
+    let replacements = [
+        (env::current_dir().ok(), "<cwd>"),
+        (env::var_os("HOME").map(PathBuf::from), "<home>"),
+        (Some(env::temp_dir()), "<tmp>"),
+    ];
+    let mut text = text.to_string();
+    for (path, placeholder) in replacements {
+        let Some(path) = path else { continue };
+        let path = path.display().to_string();
+        let path = path.trim_end_matches(std::path::MAIN_SEPARATOR);
+        if !path.is_empty() && path != "/" {
+            text = text.replace(path, placeholder);
+        }
+    }
+    text
+}
+
+/// Minimizes `source` while `still_fails` holds for the result.
+/// `still_fails` is expected to hold for the original source.
+///
+/// Removes chunks of lines, starting with large chunks and moving to smaller ones, keeping each
+/// removal after which `still_fails` still holds.
+pub fn minimize_source(source: &str, mut still_fails: impl FnMut(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let mut chunk_size = lines.len().div_ceil(2);
+    while chunk_size > 0 {
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk_size).min(lines.len());
+            let candidate = without_range(&lines, start, end);
+            if still_fails(&candidate.join("\n")) {
+                lines = candidate;
+            } else {
+                start = end;
+            }
+        }
+        chunk_size /= 2;
+    }
+    lines.join("\n")
+}
+
+/// Returns `lines` without the range `start..end`.
+fn without_range<'a>(lines: &[&'a str], start: usize, end: usize) -> Vec<&'a str> {
+    lines[..start].iter().chain(&lines[end..]).copied().collect()
+}
+
+/// A report of a compiler crash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrashReport {
+    /// The name of the crashing tool.
+    pub tool: String,
+    /// The version of the compiler.
+    pub version: String,
+    /// The panic message.
+    pub message: String,
+    /// The location in the compiler code of the panic.
+    pub location: Option<String>,
+    /// The compiler frames of the backtrace at the time of the panic, innermost first.
+    pub compiler_frames: Vec<String>,
+    /// The input paths of the run.
+    pub inputs: Vec<String>,
+    /// The configuration of the run.
+    pub config: Vec<(String, String)>,
+    /// A minimized source reproducing the crash, if available.
+    pub reproduction: Option<String>,
+}
+impl CrashReport {
+    /// Writes the redacted report into a new file in the given directory, returning its path.
+    pub fn write_to_dir(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let path = dir.join(format!("cairo-crash-{timestamp}-{}.txt", process::id()));
+        fs::write(&path, redact_paths(&self.to_string()))?;
+        Ok(path)
+    }
+}
+impl Display for CrashReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Tool: {} {}", self.tool, self.version)?;
+        writeln!(f, "Panic: {}", self.message)?;
+        if let Some(location) = &self.location {
+            writeln!(f, "Location: {location}")?;
+        }
+        writeln!(f, "\nInputs:")?;
+        for input in &self.inputs {
+            writeln!(f, "  {input}")?;
+        }
+        writeln!(f, "\nConfig:")?;
+        for (key, value) in &self.config {
+            writeln!(f, "  {key}: {value}")?;
+        }
+        writeln!(f, "\nCompiler frames:")?;
+        for frame in &self.compiler_frames {
+            writeln!(f, "  {frame}")?;
+        }
+        if let Some(reproduction) = &self.reproduction {
+            writeln!(f, "\nMinimized reproduction:\n{reproduction}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use indoc::indoc;
+
+use super::{compiler_frames, minimize_source, redact_paths, CrashReport};
+
+#[test]
+fn test_minimize_source() {
+    let source = indoc! {"
+        fn foo() {}
+        fn bar() {
+            crash();
+        }
+        fn baz() {}
+    "};
+    let minimized = minimize_source(source, |candidate| candidate.contains("crash();"));
+    assert_eq!(minimized, "    crash();");
+}
+
+#[test]
+fn test_compiler_frames() {
+    let backtrace = indoc! {"
+           0: std::backtrace::Backtrace::force_capture
+                     at /rustc/library/std/src/backtrace.rs:310:9
+           1: cairo_lang_compiler::crash_report::record_panic::h0123456789abcdef
+           2: cairo_lang_lowering::lower::lower_function::h0123456789abcdef
+                     at ./crates/cairo-lang-lowering/src/lower/mod.rs:10:5
+           3: cairo_lang_lowering::lower::lower_function::h0123456789abcdef
+           4: salsa::runtime::Runtime::execute_query_implementation
+           5: cairo_lang_lowering::db::priv_function_with_body_multi_lowering
+    "};
+    assert_eq!(
+        compiler_frames(backtrace),
+        vec![
+            "cairo_lang_lowering::lower::lower_function",
+            "cairo_lang_lowering::db::priv_function_with_body_multi_lowering",
+        ]
+    );
+}
+
+#[test]
+fn test_redacted_report() {
+    let cwd = std::env::current_dir().unwrap().display().to_string();
+    let report = CrashReport {
+        tool: "cairo-compile".into(),
+        version: "1.0.0".into(),
+        message: "oops".into(),
+        location: Some("crates/cairo-lang-lowering/src/lower/mod.rs:10:5".into()),
+        compiler_frames: vec!["cairo_lang_lowering::lower::lower_function".into()],
+        inputs: vec![format!("{cwd}/src/lib.cairo")],
+        config: vec![("replace_ids".into(), "false".into())],
+        reproduction: Some("fn foo() {}".into()),
+    };
+    assert_eq!(
+        redact_paths(&report.to_string()),
+        indoc! {"
+            Tool: cairo-compile 1.0.0
+            Panic: oops
+            Location: crates/cairo-lang-lowering/src/lower/mod.rs:10:5
+
+            Inputs:
+              <cwd>/src/lib.cairo
+
+            Config:
+              replace_ids: false
+
+            Compiler frames:
+              cairo_lang_lowering::lower::lower_function
+
+            Minimized reproduction:
+            fn foo() {}
+        "}
+    );
+}
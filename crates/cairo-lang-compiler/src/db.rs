@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
@@ -5,12 +6,12 @@ use cairo_lang_defs::db::{DefsDatabase, DefsGroup};
 use cairo_lang_defs::plugin::{InlineMacroExprPlugin, MacroPlugin};
 use cairo_lang_filesystem::cfg::CfgSet;
 use cairo_lang_filesystem::db::{
-    init_dev_corelib, init_files_group, AsFilesGroupMut, FilesDatabase, FilesGroup, FilesGroupEx,
-    CORELIB_CRATE_NAME,
+    init_dev_corelib, init_files_group, AsFilesGroupMut, CrateConfiguration, FilesDatabase,
+    FilesGroup, FilesGroupEx, PrivRawFileContentQuery, CORELIB_CRATE_NAME,
 };
 use cairo_lang_filesystem::detect::detect_corelib;
 use cairo_lang_filesystem::flag::Flag;
-use cairo_lang_filesystem::ids::FlagId;
+use cairo_lang_filesystem::ids::{CrateLongId, Directory, FileLongId, FlagId};
 use cairo_lang_lowering::db::{init_lowering_group, LoweringDatabase, LoweringGroup};
 use cairo_lang_parser::db::ParserDatabase;
 use cairo_lang_project::ProjectConfig;
@@ -22,7 +23,11 @@ use cairo_lang_syntax::node::db::{SyntaxDatabase, SyntaxGroup};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::Upcast;
 
-use crate::project::{update_crate_root, update_crate_roots_from_project_config};
+use crate::project::{update_corelib_from_project_config, update_crate_roots_from_project_config};
+
+#[cfg(test)]
+#[path = "db_test.rs"]
+mod test;
 
 #[salsa::database(
     DefsDatabase,
@@ -69,6 +74,58 @@ impl RootDatabase {
     pub fn snapshot(&self) -> RootDatabase {
         RootDatabase { storage: self.storage.snapshot() }
     }
+
+    /// Replaces the plugins of the database without rebuilding it, e.g. after rebuilding the
+    /// plugins during their development.
+    /// Only the plugin inputs that changed are set, so the cached queries that do not depend on
+    /// them remain valid.
+    pub fn reload_plugin_suite(&mut self, suite: PluginSuite) {
+        let PluginSuite { plugins, inline_macro_plugins, analyzer_plugins } = suite;
+        if !same_arcs(self.macro_plugins().iter(), plugins.iter()) {
+            self.set_macro_plugins(plugins);
+        }
+        let current_inline_macro_plugins = self.inline_macro_plugins();
+        if !current_inline_macro_plugins.keys().eq(inline_macro_plugins.keys())
+            || !same_arcs(current_inline_macro_plugins.values(), inline_macro_plugins.values())
+        {
+            self.set_inline_macro_plugins(inline_macro_plugins.into());
+        }
+        if !same_arcs(self.analyzer_plugins().iter(), analyzer_plugins.iter()) {
+            self.set_analyzer_plugins(analyzer_plugins);
+        }
+    }
+
+    /// Reloads the corelib from the given directory without rebuilding the database.
+    /// If the corelib was already loaded from this directory, its files are read again, so that
+    /// changes to them take effect. Only the inputs of the corelib are invalidated.
+    pub fn reload_corelib(&mut self, path: PathBuf) {
+        let core_crate = self.intern_crate(CrateLongId::Real(CORELIB_CRATE_NAME.into()));
+        match self.crate_config(core_crate) {
+            Some(CrateConfiguration { root: Directory::Real(root), .. }) if root == path => {
+                for module_id in self.crate_modules(core_crate).iter() {
+                    let Ok(files) = self.module_files(*module_id) else {
+                        continue;
+                    };
+                    for file_id in files.iter() {
+                        if matches!(self.lookup_intern_file(*file_id), FileLongId::OnDisk(_)) {
+                            PrivRawFileContentQuery
+                                .in_db_mut(self.as_files_group_mut())
+                                .invalidate(file_id);
+                        }
+                    }
+                }
+            }
+            _ => init_dev_corelib(self, path),
+        }
+    }
+}
+
+/// Returns whether the two sequences hold the same objects, in the same order.
+fn same_arcs<'a, T: ?Sized + 'a>(
+    lhs: impl ExactSizeIterator<Item = &'a Arc<T>>,
+    rhs: impl ExactSizeIterator<Item = &'a Arc<T>>,
+) -> bool {
+    lhs.len() == rhs.len() && lhs.zip(rhs).all(|(lhs, rhs)| Arc::ptr_eq(lhs, rhs))
 }
 
 impl Default for RootDatabase {
@@ -82,6 +139,9 @@ pub struct RootDatabaseBuilder {
     plugin_suite: PluginSuite,
     detect_corelib: bool,
     auto_withdraw_gas: bool,
+    revert_panic_abi: bool,
+    explain_ownership: bool,
+    whole_program: bool,
     project_config: Option<Box<ProjectConfig>>,
     cfg_set: Option<CfgSet>,
 }
@@ -92,6 +152,9 @@ impl RootDatabaseBuilder {
             plugin_suite: get_default_plugin_suite(),
             detect_corelib: false,
             auto_withdraw_gas: true,
+            revert_panic_abi: false,
+            explain_ownership: false,
+            whole_program: false,
             project_config: None,
             cfg_set: None,
         }
@@ -127,6 +190,25 @@ impl RootDatabaseBuilder {
         self
     }
 
+    /// Sets whether to encode the panic data with the revert-with-reason ABI, see
+    /// [Flag::RevertPanicAbi].
+    pub fn with_revert_panic_abi(&mut self, revert_panic_abi: bool) -> &mut Self {
+        self.revert_panic_abi = revert_panic_abi;
+        self
+    }
+
+    /// Sets whether to explain ownership errors in depth, see [Flag::ExplainOwnership].
+    pub fn with_explain_ownership(&mut self, explain_ownership: bool) -> &mut Self {
+        self.explain_ownership = explain_ownership;
+        self
+    }
+
+    /// Sets whether to optimize the program as a whole, see [Flag::WholeProgram].
+    pub fn with_whole_program(&mut self, whole_program: bool) -> &mut Self {
+        self.whole_program = whole_program;
+        self
+    }
+
     pub fn build(&mut self) -> Result<RootDatabase> {
         // NOTE: Order of operations matters here!
         //   Errors if something is not OK are very subtle, mostly this results in missing
@@ -153,12 +235,22 @@ impl RootDatabaseBuilder {
             add_withdraw_gas_flag_id,
             Some(Arc::new(Flag::AddWithdrawGas(self.auto_withdraw_gas))),
         );
+        let revert_panic_abi_flag_id = FlagId::new(db.upcast(), "revert_panic_abi");
+        db.set_flag(
+            revert_panic_abi_flag_id,
+            Some(Arc::new(Flag::RevertPanicAbi(self.revert_panic_abi))),
+        );
+        let explain_ownership_flag_id = FlagId::new(db.upcast(), "explain_ownership");
+        db.set_flag(
+            explain_ownership_flag_id,
+            Some(Arc::new(Flag::ExplainOwnership(self.explain_ownership))),
+        );
+        let whole_program_flag_id = FlagId::new(db.upcast(), "whole_program");
+        db.set_flag(whole_program_flag_id, Some(Arc::new(Flag::WholeProgram(self.whole_program))));
 
         if let Some(config) = &self.project_config {
             update_crate_roots_from_project_config(&mut db, config.as_ref());
-            if let Some(corelib) = &config.corelib {
-                update_crate_root(&mut db, config, CORELIB_CRATE_NAME.into(), corelib.clone());
-            }
+            update_corelib_from_project_config(&mut db, config.as_ref());
         }
 
         Ok(db)
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginResult};
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, FilesGroupEx, CORELIB_CRATE_NAME};
+use cairo_lang_filesystem::ids::{
+    CrateId, CrateLongId, Directory, FileKind, FileLongId, VirtualFile,
+};
+use cairo_lang_semantic::inline_macros::get_default_plugin_suite;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::{ast, TypedStablePtr, TypedSyntaxNode};
+
+use super::RootDatabase;
+
+/// A plugin reporting a diagnostic on every item.
+#[derive(Debug, Default)]
+struct ReportingPlugin;
+impl MacroPlugin for ReportingPlugin {
+    fn generate_code(
+        &self,
+        db: &dyn SyntaxGroup,
+        item_ast: ast::ModuleItem,
+        _metadata: &MacroPluginMetadata<'_>,
+    ) -> PluginResult {
+        PluginResult {
+            diagnostics: vec![PluginDiagnostic::error(
+                item_ast.stable_ptr().untyped(),
+                format!("Reported `{}`.", item_ast.as_syntax_node().get_text_without_trivia(db)),
+            )],
+            ..Default::default()
+        }
+    }
+
+    fn declared_attributes(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// Adds a crate with the given lib file content to the database.
+fn add_crate(db: &mut RootDatabase, content: &str) -> CrateId {
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(content.into()),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let crate_id = db.intern_crate(CrateLongId::Real("test".into()));
+    db.set_crate_config(
+        crate_id,
+        Some(CrateConfiguration::default_for_root(Directory::Virtual {
+            files: [("lib.cairo".into(), file_id)].into(),
+            dirs: Default::default(),
+        })),
+    );
+    crate_id
+}
+
+/// Returns the number of plugin diagnostics of the root module of the crate.
+fn plugin_diagnostics_count(db: &RootDatabase, crate_id: CrateId) -> usize {
+    db.module_plugin_diagnostics(ModuleId::CrateRoot(crate_id)).unwrap().len()
+}
+
+#[test]
+fn test_reload_plugin_suite() {
+    let mut db = RootDatabase::empty();
+    let crate_id = add_crate(&mut db, "fn foo() {}\nfn bar() {}\n");
+    assert_eq!(plugin_diagnostics_count(&db, crate_id), 0);
+
+    let mut suite = get_default_plugin_suite();
+    suite.add_plugin::<ReportingPlugin>();
+    db.reload_plugin_suite(suite.clone());
+    assert_eq!(plugin_diagnostics_count(&db, crate_id), 2);
+
+    db.reload_plugin_suite(suite);
+    assert_eq!(plugin_diagnostics_count(&db, crate_id), 2);
+
+    db.reload_plugin_suite(get_default_plugin_suite());
+    assert_eq!(plugin_diagnostics_count(&db, crate_id), 0);
+}
+
+#[test]
+fn test_reload_corelib() {
+    let corelib_dir = std::env::temp_dir()
+        .join(format!("cairo_lang_compiler_reload_corelib_{}", std::process::id()));
+    std::fs::create_dir_all(&corelib_dir).unwrap();
+    let lib_path = corelib_dir.join("lib.cairo");
+    std::fs::write(&lib_path, "fn foo() {}\n").unwrap();
+
+    let mut db = RootDatabase::empty();
+    db.reload_corelib(corelib_dir.clone());
+    let core_crate = db.intern_crate(CrateLongId::Real(CORELIB_CRATE_NAME.into()));
+    let lib_file = db.module_main_file(ModuleId::CrateRoot(core_crate)).unwrap();
+    assert_eq!(db.file_content(lib_file).unwrap().as_str(), "fn foo() {}\n");
+
+    // The content of the corelib is cached until the corelib is reloaded.
+    std::fs::write(&lib_path, "fn bar() {}\n").unwrap();
+    assert_eq!(db.file_content(lib_file).unwrap().as_str(), "fn foo() {}\n");
+    db.reload_corelib(corelib_dir.clone());
+    assert_eq!(db.file_content(lib_file).unwrap().as_str(), "fn bar() {}\n");
+
+    std::fs::remove_dir_all(&corelib_dir).unwrap();
+}
@@ -1,13 +1,18 @@
 use std::fmt::Write;
+use std::io::IsTerminal;
 
+use anyhow::Context;
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::ModuleId;
-use cairo_lang_diagnostics::{DiagnosticEntry, Diagnostics, FormattedDiagnosticEntry, Severity};
+use cairo_lang_diagnostics::{
+    ColorTheme, DiagnosticEntry, Diagnostics, FormattedDiagnosticEntry, RenderConfig, Severity,
+};
 use cairo_lang_filesystem::db::FilesGroup;
 use cairo_lang_filesystem::ids::{CrateId, FileLongId};
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::diagnostic::error_code_registry;
 use cairo_lang_utils::Upcast;
 use thiserror::Error;
 
@@ -39,17 +44,26 @@ pub struct DiagnosticsReporter<'a> {
     crate_ids: Vec<CrateId>,
     /// If true, compilation will not fail due to warnings.
     allow_warnings: bool,
+    /// The configuration for rendering the locations of the diagnostics as source snippets.
+    /// If `None`, locations are formatted with their first line only.
+    render_config: Option<RenderConfig>,
 }
 
 impl DiagnosticsReporter<'static> {
     /// Create a reporter which does not print or collect diagnostics at all.
     pub fn ignoring() -> Self {
-        Self { callback: None, crate_ids: vec![], allow_warnings: false }
+        Self { callback: None, crate_ids: vec![], allow_warnings: false, render_config: None }
     }
 
-    /// Create a reporter which prints all diagnostics to [`std::io::Stderr`].
+    /// Create a reporter which prints all diagnostics to [`std::io::Stderr`], rendering their
+    /// locations as source snippets.
+    /// Colors are used if stderr is a terminal, unless the `NO_COLOR` environment variable is set.
     pub fn stderr() -> Self {
-        Self::callback(|diagnostic| eprint!("{diagnostic}"))
+        let colored = std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        Self::callback(|diagnostic| eprint!("{diagnostic}")).with_render_config(RenderConfig {
+            context_lines: 1,
+            theme: if colored { ColorTheme::DARK } else { ColorTheme::NO_COLOR },
+        })
     }
 }
 
@@ -82,7 +96,12 @@ impl<'a> DiagnosticsReporter<'a> {
 
     /// Create a reporter which calls [`DiagnosticCallback::on_diagnostic`].
     fn new(callback: impl DiagnosticCallback + 'a) -> Self {
-        Self { callback: Some(Box::new(callback)), crate_ids: vec![], allow_warnings: false }
+        Self {
+            callback: Some(Box::new(callback)),
+            crate_ids: vec![],
+            allow_warnings: false,
+            render_config: None,
+        }
     }
 
     /// Sets crates to be checked, instead of all crates in the db.
@@ -91,6 +110,12 @@ impl<'a> DiagnosticsReporter<'a> {
         self
     }
 
+    /// Sets the configuration for rendering the locations of the diagnostics as source snippets.
+    pub fn with_render_config(mut self, render_config: RenderConfig) -> Self {
+        self.render_config = Some(render_config);
+        self
+    }
+
     /// Allows the compilation to succeed if only warnings are emitted.
     pub fn allow_warnings(mut self) -> Self {
         self.allow_warnings = true;
@@ -103,6 +128,9 @@ impl<'a> DiagnosticsReporter<'a> {
         let mut found_diagnostics = false;
         let crates = if self.crate_ids.is_empty() { db.crates() } else { self.crate_ids.clone() };
         for crate_id in crates {
+            // Only errors are reported for external crates.
+            let errors_only =
+                db.crate_config(crate_id).is_some_and(|config| config.settings.external);
             let Ok(module_file) = db.module_main_file(ModuleId::CrateRoot(crate_id)) else {
                 found_diagnostics = true;
                 self.callback.on_diagnostic(FormattedDiagnosticEntry::new(
@@ -129,16 +157,19 @@ impl<'a> DiagnosticsReporter<'a> {
 
             for module_id in &*db.crate_modules(crate_id) {
                 for file_id in db.module_files(*module_id).unwrap_or_default().iter().copied() {
-                    found_diagnostics |=
-                        self.check_diag_group(db.upcast(), db.file_syntax_diagnostics(file_id));
+                    found_diagnostics |= self.check_diag_group(
+                        db.upcast(),
+                        db.file_syntax_diagnostics(file_id),
+                        errors_only,
+                    );
                 }
 
                 if let Ok(group) = db.module_semantic_diagnostics(*module_id) {
-                    found_diagnostics |= self.check_diag_group(db.upcast(), group);
+                    found_diagnostics |= self.check_diag_group(db.upcast(), group, errors_only);
                 }
 
                 if let Ok(group) = db.module_lowering_diagnostics(*module_id) {
-                    found_diagnostics |= self.check_diag_group(db.upcast(), group);
+                    found_diagnostics |= self.check_diag_group(db.upcast(), group, errors_only);
                 }
             }
         }
@@ -147,14 +178,20 @@ impl<'a> DiagnosticsReporter<'a> {
 
     /// Checks if a diagnostics group contains any diagnostics and reports them to the provided
     /// callback as strings. Returns `true` if diagnostics were found.
+    /// If `errors_only` is true, diagnostics which are not errors are ignored.
     fn check_diag_group<TEntry: DiagnosticEntry>(
         &mut self,
         db: &TEntry::DbType,
         group: Diagnostics<TEntry>,
+        errors_only: bool,
     ) -> bool {
         let mut found: bool = false;
-        for entry in group.format_with_severity(db) {
-            if !entry.is_empty() {
+        let entries = match &self.render_config {
+            Some(render_config) => group.format_with_config(db, render_config),
+            None => group.format_with_severity(db),
+        };
+        for entry in entries {
+            if !entry.is_empty() && (!errors_only || entry.severity() == Severity::Error) {
                 self.callback.on_diagnostic(entry);
                 found |= !self.allow_warnings || group.check_error_free().is_err();
             }
@@ -183,3 +220,9 @@ pub fn get_diagnostics_as_string(db: &RootDatabase, extra_crate_ids: &[CrateId])
     DiagnosticsReporter::write_to_string(&mut diagnostics).with_crates(extra_crate_ids).check(db);
     diagnostics
 }
+
+/// Returns the documentation of the error code `code`, as registered by the compiler or by one of
+/// the plugins of `db`.
+pub fn explain_error_code(db: &RootDatabase, code: &str) -> anyhow::Result<&'static str> {
+    error_code_registry(db).explain(code).with_context(|| format!("Unknown error code `{code}`."))
+}
@@ -1,8 +1,12 @@
-use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, FilesGroupEx};
-use cairo_lang_filesystem::ids::{CrateLongId, Directory};
+use std::sync::Arc;
+
+use cairo_lang_diagnostics::RenderConfig;
+use cairo_lang_filesystem::db::{CrateConfiguration, CrateSettings, FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{CrateLongId, Directory, FileKind, FileLongId, VirtualFile};
+use indoc::indoc;
 
 use crate::db::RootDatabase;
-use crate::diagnostics::get_diagnostics_as_string;
+use crate::diagnostics::{explain_error_code, get_diagnostics_as_string, DiagnosticsReporter};
 
 #[test]
 fn test_diagnostics() {
@@ -16,3 +20,106 @@ fn test_diagnostics() {
 
     assert_eq!(get_diagnostics_as_string(&db, &[]), "error: no/such/path/lib.cairo not found\n");
 }
+
+#[test]
+fn test_rendered_diagnostics() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(
+            indoc! {"
+                fn foo() -> felt252 {
+                    let x = 1;
+                    x + bar()
+                }
+            "}
+            .into(),
+        ),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let crate_id = db.intern_crate(CrateLongId::Virtual {
+        name: "test".into(),
+        config: CrateConfiguration::default_for_root(Directory::Virtual {
+            files: [("lib.cairo".into(), file_id)].into(),
+            dirs: Default::default(),
+        }),
+    });
+
+    let mut diagnostics = String::new();
+    DiagnosticsReporter::write_to_string(&mut diagnostics)
+        .with_crates(&[crate_id])
+        .with_render_config(RenderConfig { context_lines: 1, ..RenderConfig::default() })
+        .check(&db);
+    assert_eq!(
+        diagnostics,
+        indoc! {"
+            error: Function not found.
+             --> lib.cairo:3:9
+              |
+            2 |     let x = 1;
+            3 |     x + bar()
+              |         ^^^
+            4 | }
+              |
+
+        "}
+    );
+}
+
+#[test]
+fn test_external_crate_diagnostics() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(
+            indoc! {"
+                fn foo() -> felt252 {
+                    let x = 1;
+                    bar()
+                }
+            "}
+            .into(),
+        ),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let crate_id = db.intern_crate(CrateLongId::Virtual {
+        name: "vendored".into(),
+        config: CrateConfiguration {
+            root: Directory::Virtual {
+                files: [("lib.cairo".into(), file_id)].into(),
+                dirs: Default::default(),
+            },
+            settings: CrateSettings { external: true, ..CrateSettings::default() },
+        },
+    });
+
+    // The unused variable warning is not reported, while the error is.
+    let mut diagnostics = String::new();
+    let found =
+        DiagnosticsReporter::write_to_string(&mut diagnostics).with_crates(&[crate_id]).check(&db);
+    assert!(found);
+    assert_eq!(
+        diagnostics,
+        indoc! {"
+            error: Function not found.
+             --> lib.cairo:3:5
+                bar()
+                ^*^
+
+        "}
+    );
+}
+
+#[test]
+fn test_explain_error_code() {
+    let db = RootDatabase::default();
+    assert!(explain_error_code(&db, "E0001").unwrap().starts_with("A variable is bound"));
+    assert_eq!(
+        explain_error_code(&db, "E9999").unwrap_err().to_string(),
+        "Unknown error code `E9999`."
+    );
+}
@@ -6,22 +6,39 @@ use std::path::Path;
 use std::sync::Arc;
 
 use ::cairo_lang_diagnostics::ToOption;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{LanguageElementId, ModuleId, NamedLanguageElementId};
+use cairo_lang_filesystem::db::{FilesGroup, FilesGroupEx};
 use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
 use cairo_lang_sierra::debug_info::{Annotations, DebugInfo};
 use cairo_lang_sierra::program::{Program, ProgramArtifact};
 use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::drop_elimination::eliminate_redundant_drops;
+use cairo_lang_sierra_generator::inlining::inline_small_functions;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
 use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_syntax::node::ast::MaybeModuleBody;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::Upcast;
+use smol_str::SmolStr;
 
 use crate::db::RootDatabase;
 use crate::diagnostics::DiagnosticsReporter;
 use crate::project::{get_main_crate_ids_from_project, setup_project, ProjectConfig};
 
+pub mod crash_report;
 pub mod db;
 pub mod diagnostics;
+pub mod progress;
 pub mod project;
 
+#[cfg(test)]
+#[path = "lib_test.rs"]
+mod test;
+
 /// Configuration for the compiler.
 #[derive(Default)]
 pub struct CompilerConfig<'c> {
@@ -37,6 +54,41 @@ pub struct CompilerConfig<'c> {
     /// Adds mapping used by [cairo-profiler](https://github.com/software-mansion/cairo-profiler) to
     /// [cairo_lang_sierra::debug_info::Annotations] in [cairo_lang_sierra::debug_info::DebugInfo].
     pub add_statements_functions: bool,
+
+    /// Encodes the panic data with the revert-with-reason ABI, see
+    /// [cairo_lang_filesystem::flag::Flag::RevertPanicAbi].
+    /// Only applies to the compilations building their own database - a prepared database should
+    /// be built with [db::RootDatabaseBuilder::with_revert_panic_abi] instead.
+    pub revert_panic_abi: bool,
+
+    /// Explains ownership errors in depth, see
+    /// [cairo_lang_filesystem::flag::Flag::ExplainOwnership].
+    /// Only applies to the compilations building their own database - a prepared database should
+    /// be built with [db::RootDatabaseBuilder::with_explain_ownership] instead.
+    pub explain_ownership: bool,
+
+    /// Optimizes the program as a whole, see [cairo_lang_filesystem::flag::Flag::WholeProgram].
+    /// Only applies to the compilations building their own database - a prepared database should
+    /// be built with [db::RootDatabaseBuilder::with_whole_program] instead.
+    pub whole_program: bool,
+
+    /// Inlines the functions of the generated Sierra program with at most this number of
+    /// statements into their callers, see [cairo_lang_sierra_generator::inlining].
+    pub sierra_inlining_threshold: Option<usize>,
+
+    /// Eliminates the variables of the generated Sierra program that are created only to be
+    /// dropped, see [cairo_lang_sierra_generator::drop_elimination].
+    pub eliminate_redundant_drops: bool,
+
+    /// Embeds the solved gas costs of the entry points of a compiled contract into its contract
+    /// class, see `cairo_lang_starknet_classes::gas_estimation::ContractGasEstimate::embed`.
+    /// Only applies to the compilations of Starknet contracts.
+    pub embed_gas_estimate: bool,
+
+    /// Warns about storage writes following external calls in the entry points of contracts,
+    /// through which the called contracts may re-enter the contract in an outdated state.
+    /// Only applies to the compilations of Starknet contracts building their own database.
+    pub reentrancy_analysis: bool,
 }
 
 /// Compiles a Cairo project at the given path.
@@ -53,7 +105,12 @@ pub fn compile_cairo_project_at_path(
     path: &Path,
     compiler_config: CompilerConfig<'_>,
 ) -> Result<Program> {
-    let mut db = RootDatabase::builder().detect_corelib().build()?;
+    let mut db = RootDatabase::builder()
+        .detect_corelib()
+        .with_revert_panic_abi(compiler_config.revert_panic_abi)
+        .with_explain_ownership(compiler_config.explain_ownership)
+        .with_whole_program(compiler_config.whole_program)
+        .build()?;
     let main_crate_ids = setup_project(&mut db, path)?;
     compile_prepared_db_program(&mut db, main_crate_ids, compiler_config)
 }
@@ -71,7 +128,12 @@ pub fn compile(
     project_config: ProjectConfig,
     compiler_config: CompilerConfig<'_>,
 ) -> Result<Program> {
-    let mut db = RootDatabase::builder().with_project_config(project_config.clone()).build()?;
+    let mut db = RootDatabase::builder()
+        .with_project_config(project_config.clone())
+        .with_revert_panic_abi(compiler_config.revert_panic_abi)
+        .with_explain_ownership(compiler_config.explain_ownership)
+        .with_whole_program(compiler_config.whole_program)
+        .build()?;
     let main_crate_ids = get_main_crate_ids_from_project(&mut db, &project_config);
 
     compile_prepared_db_program(&mut db, main_crate_ids, compiler_config)
@@ -123,6 +185,164 @@ pub fn compile_prepared_db(
             .context("Compilation failed without any diagnostics")?,
     );
 
+    if let Some(max_statements) = compiler_config.sierra_inlining_threshold {
+        sierra_program_with_debug =
+            inline_small_functions(db, &sierra_program_with_debug, max_statements);
+    }
+    if compiler_config.eliminate_redundant_drops {
+        sierra_program_with_debug = eliminate_redundant_drops(db, &sierra_program_with_debug);
+    }
+    if compiler_config.replace_ids {
+        sierra_program_with_debug.program =
+            replace_sierra_ids_in_program(db, &sierra_program_with_debug.program);
+    }
+
+    Ok(sierra_program_with_debug)
+}
+
+/// Compiles a single function, given by its code, as if it was defined in the module `module_id`,
+/// so that it may use all the items and imports visible in that module.
+///
+/// The function is added at the end of the module, and the database is restored to its previous
+/// state once the compilation is done.
+///
+/// # Arguments
+/// * `db` - Preloaded compilation database.
+/// * `module_id` - The module in which context the function is compiled.
+/// * `function_code` - The code of a single non-generic function, e.g. `fn foo() -> u8 { 5 }`.
+/// * `compiler_config` - The compiler configuration.
+/// # Returns
+/// * `Ok(SierraProgramWithDebug)` - The compiled function, along with all the functions it calls.
+/// * `Err(anyhow::Error)` - Compilation failed.
+pub fn compile_function_in_module(
+    db: &mut RootDatabase,
+    module_id: ModuleId,
+    function_code: &str,
+    compiler_config: CompilerConfig<'_>,
+) -> Result<SierraProgramWithDebug> {
+    let file_id =
+        db.module_main_file(module_id).to_option().context("Failed to find the module file.")?;
+    let content = db.file_content(file_id).context("Failed to read the module file.")?;
+    let insertion_offset = module_end_offset(db, module_id, &content);
+    let existing_functions = module_free_function_names(db, module_id)?;
+    // The ids of submodules are invalidated by the change of their file, so they are resolved
+    // again by their path.
+    let crate_id = module_id.owning_crate(db);
+    let module_path = submodule_path(db, module_id);
+
+    let previous_override = db.file_overrides().get(&file_id).cloned();
+    db.override_file_content(
+        file_id,
+        Some(Arc::new(format!(
+            "{}\n{function_code}\n{}",
+            &content[..insertion_offset],
+            &content[insertion_offset..]
+        ))),
+    );
+    let result =
+        find_submodule(db, ModuleId::CrateRoot(crate_id), &module_path).and_then(|module_id| {
+            compile_added_function(db, module_id, &existing_functions, compiler_config)
+        });
+    db.override_file_content(file_id, previous_override);
+    result
+}
+
+/// Returns the offset in the content of the main file of the given module, at which items can be
+/// added to the module.
+fn module_end_offset(db: &RootDatabase, module_id: ModuleId, content: &str) -> usize {
+    // Inline modules end at their closing brace, and file modules at the end of their file.
+    let ModuleId::Submodule(submodule_id) = module_id else {
+        return content.len();
+    };
+    match submodule_id.stable_ptr(db).lookup(db.upcast()).body(db.upcast()) {
+        MaybeModuleBody::Some(body) => {
+            content.len()
+                - body.rbrace(db.upcast()).as_syntax_node().offset().take_from(content).len()
+        }
+        MaybeModuleBody::None(_) => content.len(),
+    }
+}
+
+/// Returns the names of the modules leading from the crate root to the given module.
+fn submodule_path(db: &RootDatabase, module_id: ModuleId) -> Vec<SmolStr> {
+    let mut path = vec![];
+    let mut current = module_id;
+    while let ModuleId::Submodule(submodule_id) = current {
+        path.push(submodule_id.name(db));
+        current = submodule_id.parent_module(db);
+    }
+    path.reverse();
+    path
+}
+
+/// Finds the submodule of `module_id` by the names of the modules leading to it.
+fn find_submodule(db: &RootDatabase, module_id: ModuleId, path: &[SmolStr]) -> Result<ModuleId> {
+    let Some((name, path)) = path.split_first() else {
+        return Ok(module_id);
+    };
+    let submodule_id = db
+        .module_submodules_ids(module_id)
+        .to_option()
+        .context("Failed to get the module submodules.")?
+        .iter()
+        .find(|submodule_id| submodule_id.name(db) == *name)
+        .copied()
+        .with_context(|| format!("Failed to find the module `{name}`."))?;
+    find_submodule(db, ModuleId::Submodule(submodule_id), path)
+}
+
+/// Returns the names of the free functions of the given module.
+fn module_free_function_names(
+    db: &RootDatabase,
+    module_id: ModuleId,
+) -> Result<OrderedHashSet<SmolStr>> {
+    Ok(db
+        .module_free_functions_ids(module_id)
+        .to_option()
+        .context("Failed to get the module functions.")?
+        .iter()
+        .map(|function_id| function_id.name(db))
+        .collect())
+}
+
+/// Compiles the single free function of `module_id` which is not in `existing_functions`.
+fn compile_added_function(
+    db: &mut RootDatabase,
+    module_id: ModuleId,
+    existing_functions: &OrderedHashSet<SmolStr>,
+    mut compiler_config: CompilerConfig<'_>,
+) -> Result<SierraProgramWithDebug> {
+    let added_functions: Vec<_> = db
+        .module_free_functions_ids(module_id)
+        .to_option()
+        .context("Failed to get the module functions.")?
+        .iter()
+        .filter(|function_id| !existing_functions.contains(&function_id.name(db)))
+        .copied()
+        .collect();
+    let [function_id] = added_functions[..] else {
+        bail!(
+            "Expected the code to define a single new function, found {}.",
+            added_functions.len()
+        );
+    };
+    compiler_config.diagnostics_reporter.ensure(db)?;
+
+    let function_id = ConcreteFunctionWithBodyId::from_no_generics_free(db, function_id)
+        .context("Generic functions are not supported.")?;
+    let mut sierra_program_with_debug = Arc::unwrap_or_clone(
+        db.get_sierra_program_for_functions(vec![function_id])
+            .to_option()
+            .context("Compilation failed without any diagnostics")?,
+    );
+
+    if let Some(max_statements) = compiler_config.sierra_inlining_threshold {
+        sierra_program_with_debug =
+            inline_small_functions(db, &sierra_program_with_debug, max_statements);
+    }
+    if compiler_config.eliminate_redundant_drops {
+        sierra_program_with_debug = eliminate_redundant_drops(db, &sierra_program_with_debug);
+    }
     if compiler_config.replace_ids {
         sierra_program_with_debug.program =
             replace_sierra_ids_in_program(db, &sierra_program_with_debug.program);
@@ -134,7 +354,9 @@ pub fn compile_prepared_db(
 /// Runs Cairo compiler.
 ///
 /// Wrapper over [`compile_prepared_db`], but this function returns [`ProgramArtifact`]
-/// with requested debug info.
+/// with requested debug info - along with the remapping of the statements of the program, if they
+/// were remapped by Sierra-level optimizations, see
+/// [cairo_lang_sierra_generator::statements_remapping].
 ///
 /// # Arguments
 /// * `db` - Preloaded compilation database.
@@ -152,20 +374,30 @@ pub fn compile_prepared_db_program_artifact(
 ) -> Result<ProgramArtifact> {
     let add_statements_functions = compiler_config.add_statements_functions;
 
-    let sierra_program_with_debug = compile_prepared_db(db, main_crate_ids, compiler_config)?;
-    let mut program_artifact = ProgramArtifact::stripped(sierra_program_with_debug.program);
+    let SierraProgramWithDebug { program, debug_info } =
+        compile_prepared_db(db, main_crate_ids, compiler_config)?;
+    let mut program_artifact = ProgramArtifact::stripped(program);
 
+    let mut annotations = Annotations::default();
     if add_statements_functions {
-        let statements_functions = sierra_program_with_debug
-            .debug_info
-            .statements_locations
-            .extract_statements_functions(db);
-
+        let statements_functions = debug_info.statements_locations.extract_statements_functions(db);
+        annotations.extend(Annotations::from(statements_functions));
+    }
+    if let Some(statements_remapping) = &debug_info.statements_remapping {
+        annotations.extend(Annotations::from(statements_remapping));
+    }
+    if !debug_info.dict_squash_info.is_default_squashing() {
+        annotations.extend(Annotations::from(&debug_info.dict_squash_info));
+    }
+    if !debug_info.bounds_check_elision_info.is_empty() {
+        annotations.extend(Annotations::from(&debug_info.bounds_check_elision_info));
+    }
+    if !annotations.is_empty() {
         let debug_info = DebugInfo {
             type_names: Default::default(),
             libfunc_names: Default::default(),
             user_func_names: Default::default(),
-            annotations: Annotations::from(statements_functions),
+            annotations,
         };
         program_artifact = program_artifact.with_debug_info(debug_info);
     }
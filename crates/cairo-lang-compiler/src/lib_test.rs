@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{
+    CrateId, CrateLongId, Directory, FileKind, FileLongId, VirtualFile,
+};
+use indoc::indoc;
+use test_case::test_case;
+
+use crate::db::RootDatabase;
+use crate::diagnostics::DiagnosticsReporter;
+use crate::{compile_function_in_module, compile_prepared_db, CompilerConfig};
+
+const LIB_CONTENT: &str = indoc! {"
+    use core::integer::u8_wrapping_add;
+
+    #[inline(never)]
+    fn double(x: u8) -> u8 {
+        u8_wrapping_add(x, x)
+    }
+
+    mod inner {
+        #[inline(never)]
+        fn triple(x: u8) -> u8 {
+            super::double(x) + x
+        }
+    }
+"};
+
+/// Sets up a database with the corelib and a virtual crate with the given lib file content.
+fn setup_db(content: &str) -> (RootDatabase, CrateId) {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(content.into()),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let crate_id = db.intern_crate(CrateLongId::Virtual {
+        name: "test".into(),
+        config: CrateConfiguration::default_for_root(Directory::Virtual {
+            files: [("lib.cairo".into(), file_id)].into(),
+            dirs: Default::default(),
+        }),
+    });
+    (db, crate_id)
+}
+
+/// Returns the names of the functions of the compiled function in the given module.
+fn compiled_function_names(
+    db: &mut RootDatabase,
+    module_id: ModuleId,
+    function_code: &str,
+) -> Vec<String> {
+    let crate_id = module_id.owning_crate(db);
+    compile_function_in_module(
+        db,
+        module_id,
+        function_code,
+        CompilerConfig {
+            diagnostics_reporter: DiagnosticsReporter::stderr().with_crates(&[crate_id]),
+            replace_ids: true,
+            ..CompilerConfig::default()
+        },
+    )
+    .unwrap()
+    .program
+    .funcs
+    .into_iter()
+    .map(|func| func.id.to_string())
+    .collect()
+}
+
+#[test]
+fn compile_function_in_crate_root() {
+    let (mut db, crate_id) = setup_db(LIB_CONTENT);
+    let module_id = ModuleId::CrateRoot(crate_id);
+    assert_eq!(
+        compiled_function_names(
+            &mut db,
+            module_id,
+            "fn snippet() -> u8 { double(u8_wrapping_add(1, 2)) }"
+        ),
+        vec!["test::snippet", "test::double"]
+    );
+    // The module is left unchanged.
+    let file_id = db.module_main_file(module_id).unwrap();
+    assert_eq!(db.file_content(file_id).unwrap().as_str(), LIB_CONTENT);
+}
+
+#[test]
+fn compile_function_in_inline_module() {
+    let (mut db, crate_id) = setup_db(LIB_CONTENT);
+    let module_id =
+        *db.module_submodules_ids(ModuleId::CrateRoot(crate_id)).unwrap().first().unwrap();
+    assert_eq!(
+        compiled_function_names(
+            &mut db,
+            ModuleId::Submodule(module_id),
+            "fn snippet() -> u8 { triple(2) }"
+        ),
+        vec!["test::inner::snippet", "test::inner::triple", "test::double"]
+    );
+}
+
+#[test]
+fn compile_invalid_function() {
+    let (mut db, crate_id) = setup_db(LIB_CONTENT);
+    let module_id = ModuleId::CrateRoot(crate_id);
+    for function_code in ["fn snippet() -> u8 { undefined(1) }", "fn double() {}", ""] {
+        assert!(
+            compile_function_in_module(
+                &mut db,
+                module_id,
+                function_code,
+                CompilerConfig {
+                    diagnostics_reporter: DiagnosticsReporter::ignoring().with_crates(&[crate_id]),
+                    ..CompilerConfig::default()
+                },
+            )
+            .is_err()
+        );
+    }
+    let file_id = db.module_main_file(module_id).unwrap();
+    assert_eq!(db.file_content(file_id).unwrap().as_str(), LIB_CONTENT);
+}
+
+const WORKSPACE_LIB_CONTENT: &str = indoc! {"
+    pub fn once(x: u128) -> u128 {
+        let a = x * 3 + 7;
+        let b = a / 5 - 2;
+        let c = b * b + a;
+        c % 11 + b * 13 - a / 3
+    }
+
+    pub fn twice(x: u128) -> u128 {
+        let a = x * 5 + 3;
+        let b = a / 7 - 1;
+        b * b + a % 13 - x / 3
+    }
+
+    pub fn unused(x: u128) -> u128 {
+        let a = x * 7 + 5;
+        let b = a / 3 - 1;
+        b * b + a % 17 - x / 5
+    }
+"};
+
+const WORKSPACE_APP_CONTENT: &str = indoc! {"
+    fn main(x: u128) -> u128 {
+        lib::twice(lib::once(x)) + lib::twice(x)
+    }
+"};
+
+#[test_case(false, &["lib::once", "lib::twice", "lib::unused", "app::main"]; "per crate")]
+#[test_case(true, &["app::main", "lib::twice"]; "whole program")]
+fn compile_workspace(whole_program: bool, expected_function_names: &[&str]) {
+    let mut db =
+        RootDatabase::builder().detect_corelib().with_whole_program(whole_program).build().unwrap();
+    let crate_ids =
+        [("lib", WORKSPACE_LIB_CONTENT), ("app", WORKSPACE_APP_CONTENT)].map(|(name, content)| {
+            let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+                parent: None,
+                name: "lib.cairo".into(),
+                content: Arc::new(content.into()),
+                code_mappings: Default::default(),
+                kind: FileKind::Module,
+            }));
+            let crate_id = db.intern_crate(CrateLongId::Real(name.into()));
+            db.set_crate_config(
+                crate_id,
+                Some(CrateConfiguration::default_for_root(Directory::Virtual {
+                    files: [("lib.cairo".into(), file_id)].into(),
+                    dirs: Default::default(),
+                })),
+            );
+            crate_id
+        });
+    let program = compile_prepared_db(
+        &mut db,
+        crate_ids.to_vec(),
+        CompilerConfig {
+            diagnostics_reporter: DiagnosticsReporter::stderr().with_crates(&crate_ids),
+            replace_ids: true,
+            ..CompilerConfig::default()
+        },
+    )
+    .unwrap()
+    .program;
+    assert_eq!(
+        program.funcs.iter().map(|func| func.id.to_string()).collect::<Vec<_>>(),
+        expected_function_names
+    );
+}
@@ -0,0 +1,127 @@
+//! Structured progress reporting for the compiler CLI drivers.
+//!
+//! When enabled, a driver reports its progress - the phases it runs, the diagnostics it finds and
+//! the artifacts it writes - as newline-delimited JSON events to stderr, so that build
+//! orchestration tools can follow it without parsing its human-readable output. The regular
+//! output of the driver (e.g. the compiled program when no output file is given) is unaffected.
+
+use std::path::Path;
+use std::time::Instant;
+
+use cairo_lang_diagnostics::FormattedDiagnosticEntry;
+use serde::Serialize;
+
+use crate::diagnostics::DiagnosticsReporter;
+
+#[cfg(test)]
+#[path = "progress_test.rs"]
+mod test;
+
+/// An event reported by a CLI driver.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// A phase of the run started, e.g. `compile`.
+    PhaseStarted { phase: String },
+    /// A phase of the run finished, with the error that failed it, if any.
+    PhaseFinished {
+        phase: String,
+        success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+        elapsed_ms: u64,
+    },
+    /// A diagnostic of the compiled code.
+    Diagnostic {
+        severity: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code: Option<String>,
+        message: String,
+    },
+    /// An output file was written.
+    ArtifactWritten { path: String },
+    /// A message of the driver, not attributed to the compiled code.
+    Log { message: String },
+}
+impl ProgressEvent {
+    /// Returns the event of a diagnostic.
+    pub fn diagnostic(diagnostic: &FormattedDiagnosticEntry) -> Self {
+        Self::Diagnostic {
+            severity: diagnostic.severity().to_string(),
+            code: diagnostic.error_code().map(|code| code.to_string()),
+            message: diagnostic.message().trim_end().to_string(),
+        }
+    }
+}
+
+/// Reports the progress of a CLI driver, as JSON events if enabled, and otherwise as the regular
+/// human-readable output.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProgressReporter {
+    json: bool,
+}
+impl ProgressReporter {
+    /// Creates a reporter, emitting JSON events if `json` is set.
+    pub fn new(json: bool) -> Self {
+        Self { json }
+    }
+
+    /// Returns whether the progress is reported as JSON events.
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+
+    /// Emits an event, if the progress is reported as JSON events.
+    pub fn emit(&self, event: ProgressEvent) {
+        if self.json {
+            eprintln!("{}", serde_json::to_string(&event).expect("Events are serializable."));
+        }
+    }
+
+    /// Runs a phase of the driver, reporting its start and finish.
+    pub fn phase<T>(
+        &self,
+        phase: &str,
+        f: impl FnOnce() -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        self.emit(ProgressEvent::PhaseStarted { phase: phase.into() });
+        let start = Instant::now();
+        let result = f();
+        self.emit(ProgressEvent::PhaseFinished {
+            phase: phase.into(),
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|error| format!("{error:#}")),
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        });
+        result
+    }
+
+    /// Reports that an output file was written.
+    pub fn artifact_written(&self, path: impl AsRef<Path>) {
+        self.emit(ProgressEvent::ArtifactWritten { path: path.as_ref().display().to_string() });
+    }
+
+    /// Reports a message of the driver, printing it to stderr if the progress isn't reported as
+    /// JSON events.
+    pub fn log(&self, message: impl Into<String>) {
+        let message = message.into();
+        if self.json {
+            self.emit(ProgressEvent::Log { message });
+        } else {
+            eprintln!("{message}");
+        }
+    }
+
+    /// Returns a diagnostics reporter, reporting the diagnostics as events if the progress is
+    /// reported as JSON events, and printing them to stderr otherwise.
+    pub fn diagnostics_reporter(&self) -> DiagnosticsReporter<'static> {
+        if self.json {
+            let reporter = *self;
+            DiagnosticsReporter::callback(move |diagnostic| {
+                reporter.emit(ProgressEvent::diagnostic(&diagnostic))
+            })
+        } else {
+            DiagnosticsReporter::stderr()
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use cairo_lang_diagnostics::{error_code, FormattedDiagnosticEntry, Severity};
+use test_case::test_case;
+
+use super::ProgressEvent;
+
+#[test_case(
+    ProgressEvent::PhaseStarted { phase: "compile".into() },
+    r#"{"event":"phase_started","phase":"compile"}"#;
+    "phase started"
+)]
+#[test_case(
+    ProgressEvent::PhaseFinished {
+        phase: "compile".into(),
+        success: true,
+        error: None,
+        elapsed_ms: 12,
+    },
+    r#"{"event":"phase_finished","phase":"compile","success":true,"elapsed_ms":12}"#;
+    "phase finished"
+)]
+#[test_case(
+    ProgressEvent::PhaseFinished {
+        phase: "compile".into(),
+        success: false,
+        error: Some("Compilation failed.".into()),
+        elapsed_ms: 3,
+    },
+    r#"{"event":"phase_finished","phase":"compile","success":false,"error":"Compilation failed.","elapsed_ms":3}"#;
+    "phase failed"
+)]
+#[test_case(
+    ProgressEvent::ArtifactWritten { path: "out/a.sierra".into() },
+    r#"{"event":"artifact_written","path":"out/a.sierra"}"#;
+    "artifact written"
+)]
+#[test_case(
+    ProgressEvent::Log { message: "Done.".into() },
+    r#"{"event":"log","message":"Done."}"#;
+    "log"
+)]
+fn serialize_event(event: ProgressEvent, expected: &str) {
+    assert_eq!(serde_json::to_string(&event).unwrap(), expected);
+}
+
+#[test]
+fn diagnostic_event() {
+    let diagnostic = FormattedDiagnosticEntry::new(
+        Severity::Warning,
+        Some(error_code!(E0001)),
+        "Unused variable.".into(),
+    );
+    assert_eq!(
+        serde_json::to_string(&ProgressEvent::diagnostic(&diagnostic)).unwrap(),
+        r#"{"event":"diagnostic","severity":"warning","code":"E0001","message":"Unused variable."}"#
+    );
+    let diagnostic = FormattedDiagnosticEntry::new(Severity::Error, None, "Missing `;`.".into());
+    assert_eq!(
+        serde_json::to_string(&ProgressEvent::diagnostic(&diagnostic)).unwrap(),
+        r#"{"event":"diagnostic","severity":"error","message":"Missing `;`."}"#
+    );
+}
@@ -1,14 +1,22 @@
 use std::ffi::OsStr;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use cairo_lang_defs::ids::ModuleId;
-use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroupEx};
+use cairo_lang_filesystem::db::{
+    corelib_crate_settings, CrateConfiguration, FilesGroupEx, CORELIB_CRATE_NAME,
+};
 use cairo_lang_filesystem::ids::{CrateId, CrateLongId, Directory};
 pub use cairo_lang_project::*;
 use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use smol_str::SmolStr;
 
+#[cfg(test)]
+#[path = "project_test.rs"]
+mod test;
+
 #[derive(thiserror::Error, Debug)]
 pub enum ProjectError {
     #[error("Only files with .cairo extension can be compiled.")]
@@ -19,6 +27,10 @@ pub enum ProjectError {
     BadPath { path: String },
     #[error("Failed to load project config.")]
     LoadProjectError,
+    #[error("No Cairo projects found in {path}.")]
+    NoProjectsInWorkspace { path: String },
+    #[error("Crate `{crate_name}` is configured differently by the projects of the workspace.")]
+    ConflictingCrateConfig { crate_name: SmolStr },
 }
 
 /// Setup to 'db' to compile the file at the given path.
@@ -78,6 +90,23 @@ pub fn update_crate_roots_from_project_config(db: &mut dyn SemanticGroup, config
     }
 }
 
+/// Updates the corelib crate root from a ProjectConfig, if it specifies a patched corelib.
+/// The corelib keeps its default settings, unless the config overrides them explicitly.
+pub fn update_corelib_from_project_config(db: &mut dyn SemanticGroup, config: &ProjectConfig) {
+    let Some(corelib) = &config.corelib else {
+        return;
+    };
+    let crate_id = db.intern_crate(CrateLongId::Real(CORELIB_CRATE_NAME.into()));
+    let settings = config
+        .content
+        .crates_config
+        .override_map
+        .get(CORELIB_CRATE_NAME)
+        .cloned()
+        .unwrap_or_else(corelib_crate_settings);
+    db.set_crate_config(crate_id, Some(CrateConfiguration { root: corelib.clone(), settings }));
+}
+
 /// Updates a single crate root from a ProjectConfig.
 /// If the crate defines settings in the config, it will be used.
 /// Crate is identified by name and the root directory.
@@ -107,6 +136,7 @@ pub fn setup_project(
             Ok(config) => {
                 let main_crate_ids = get_main_crate_ids_from_project(db, &config);
                 update_crate_roots_from_project_config(db, &config);
+                update_corelib_from_project_config(db, &config);
                 Ok(main_crate_ids)
             }
             _ => Err(ProjectError::LoadProjectError),
@@ -143,3 +173,110 @@ pub fn get_main_crate_ids_from_project(
         .map(|crate_id| db.intern_crate(CrateLongId::Real(crate_id.clone())))
         .collect()
 }
+
+/// Setup the 'db' to compile the workspace in the given path - the Cairo projects in a directory
+/// and in its subdirectories, each with its own cairo project file and crate configurations.
+/// A path of a single project - a directory with a cairo project file or a .cairo file - is a
+/// workspace of that project.
+/// A crate may belong to several projects of the workspace - e.g. a library used by the other
+/// projects - as long as they configure it the same way.
+/// Returns the ids of the crates of the projects, without repetitions.
+pub fn setup_workspace(
+    db: &mut dyn SemanticGroup,
+    path: &Path,
+) -> Result<Vec<CrateId>, ProjectError> {
+    if !path.is_dir() || path.join(PROJECT_FILE_NAME).exists() {
+        return setup_project(db, path);
+    }
+    let mut project_paths = vec![];
+    find_workspace_projects(path, &mut project_paths);
+    if project_paths.is_empty() {
+        return Err(ProjectError::NoProjectsInWorkspace {
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+
+    let mut crate_configs = OrderedHashMap::<SmolStr, CrateConfiguration>::default();
+    let mut main_crate_names = vec![];
+    for project_path in project_paths {
+        let config = ProjectConfig::from_directory(&project_path)
+            .map_err(|_| ProjectError::LoadProjectError)?;
+        let crates = config.content.crate_roots.iter().map(|(crate_name, directory_path)| {
+            let settings = config.content.crates_config.get(crate_name).clone();
+            (crate_name.clone(), Directory::Real(config.base_path.join(directory_path)), settings)
+        });
+        let corelib = config.corelib.iter().map(|corelib| {
+            let settings = config
+                .content
+                .crates_config
+                .override_map
+                .get(CORELIB_CRATE_NAME)
+                .cloned()
+                .unwrap_or_else(corelib_crate_settings);
+            (CORELIB_CRATE_NAME.into(), corelib.clone(), settings)
+        });
+        for (crate_name, root, settings) in crates.chain(corelib) {
+            match crate_configs.get(&crate_name) {
+                Some(existing) => {
+                    if !is_same_directory(&existing.root, &root) || existing.settings != settings {
+                        return Err(ProjectError::ConflictingCrateConfig { crate_name });
+                    }
+                }
+                None => {
+                    if crate_name != CORELIB_CRATE_NAME {
+                        main_crate_names.push(crate_name.clone());
+                    }
+                    crate_configs.insert(crate_name, CrateConfiguration { root, settings });
+                }
+            }
+        }
+    }
+
+    for (crate_name, crate_config) in crate_configs {
+        let crate_id = db.intern_crate(CrateLongId::Real(crate_name));
+        db.set_crate_config(crate_id, Some(crate_config));
+    }
+    Ok(main_crate_names
+        .into_iter()
+        .map(|crate_name| db.intern_crate(CrateLongId::Real(crate_name)))
+        .collect())
+}
+
+/// Adds the directories of the Cairo projects in the given directory and its subdirectories to
+/// `project_paths`, in the order of their paths. The subdirectories of projects, and the hidden
+/// directories, are not searched.
+fn find_workspace_projects(directory: &Path, project_paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+    let mut subdirectories = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && !path
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|name| name.starts_with('.'))
+        })
+        .collect::<Vec<_>>();
+    subdirectories.sort();
+    for subdirectory in subdirectories {
+        if subdirectory.join(PROJECT_FILE_NAME).exists() {
+            project_paths.push(subdirectory);
+        } else {
+            find_workspace_projects(&subdirectory, project_paths);
+        }
+    }
+}
+
+/// Returns whether the directories are the same, comparing real directories by their canonical
+/// paths, as different projects may refer to the same directory by different paths.
+fn is_same_directory(a: &Directory, b: &Directory) -> bool {
+    match (a, b) {
+        (Directory::Real(a), Directory::Real(b)) => {
+            a == b || a.canonicalize().is_ok_and(|a| b.canonicalize().is_ok_and(|b| a == b))
+        }
+        _ => a == b,
+    }
+}
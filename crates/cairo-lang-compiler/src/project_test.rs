@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use test_case::test_case;
+
+use super::{setup_workspace, ProjectError};
+use crate::db::RootDatabase;
+use crate::diagnostics::DiagnosticsReporter;
+
+/// Returns the path of the given test data directory.
+fn test_data_path(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data").join(path)
+}
+
+#[test_case("workspace", &["app", "lib"]; "workspace")]
+#[test_case("workspace/lib", &["lib"]; "single project")]
+fn setup_workspace_crates(path: &str, expected_crate_names: &[&str]) {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_ids = setup_workspace(&mut db, &test_data_path(path)).unwrap();
+    let crate_names = crate_ids.iter().map(|crate_id| crate_id.name(&db)).collect::<Vec<_>>();
+    assert_eq!(crate_names, expected_crate_names);
+    assert!(!DiagnosticsReporter::stderr().with_crates(&crate_ids).check(&db));
+}
+
+#[test]
+fn setup_workspace_with_conflicting_crates() {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    assert!(matches!(
+        setup_workspace(&mut db, &test_data_path("conflicting_workspace")),
+        Err(ProjectError::ConflictingCrateConfig { crate_name }) if crate_name == "shared"
+    ));
+}
+
+#[test]
+fn setup_workspace_without_projects() {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    assert!(matches!(
+        setup_workspace(&mut db, &test_data_path("workspace/lib/src")),
+        Err(ProjectError::NoProjectsInWorkspace { .. })
+    ));
+}
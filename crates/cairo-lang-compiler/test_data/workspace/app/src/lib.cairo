@@ -0,0 +1,3 @@
+fn quadruple(x: felt252) -> felt252 {
+    lib::double(lib::double(x))
+}
@@ -9,3 +9,4 @@ pub mod plugin;
 pub mod plugin_utils;
 #[cfg(test)]
 mod test;
+pub mod token_stream;
@@ -2,7 +2,7 @@ use std::any::Any;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use cairo_lang_diagnostics::Severity;
+use cairo_lang_diagnostics::{ErrorCode, ErrorCodeRegistry, Severity};
 use cairo_lang_filesystem::cfg::CfgSet;
 use cairo_lang_filesystem::ids::CodeMapping;
 use cairo_lang_syntax::node::ast;
@@ -10,6 +10,8 @@ use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use smol_str::SmolStr;
 
+use crate::token_stream::{attribute_macro_input, AttributeMacroInput};
+
 /// A trait for arbitrary data that a macro generates along with a generated file.
 pub trait GeneratedFileAuxData: std::fmt::Debug + Sync + Send {
     fn as_any(&self) -> &dyn Any;
@@ -66,13 +68,21 @@ pub struct PluginDiagnostic {
     pub stable_ptr: SyntaxStablePtrId,
     pub message: String,
     pub severity: Severity,
+    /// The stable code of the diagnostic, documented by the plugin reporting it.
+    pub error_code: Option<ErrorCode>,
 }
 impl PluginDiagnostic {
     pub fn error(stable_ptr: SyntaxStablePtrId, message: String) -> PluginDiagnostic {
-        PluginDiagnostic { stable_ptr, message, severity: Severity::Error }
+        PluginDiagnostic { stable_ptr, message, severity: Severity::Error, error_code: None }
     }
     pub fn warning(stable_ptr: SyntaxStablePtrId, message: String) -> PluginDiagnostic {
-        PluginDiagnostic { stable_ptr, message, severity: Severity::Warning }
+        PluginDiagnostic { stable_ptr, message, severity: Severity::Warning, error_code: None }
+    }
+    /// Sets the error code of the diagnostic. The code should be registered by the plugin in its
+    /// `register_error_codes`.
+    pub fn with_error_code(mut self, error_code: ErrorCode) -> PluginDiagnostic {
+        self.error_code = Some(error_code);
+        self
     }
 }
 
@@ -102,6 +112,52 @@ pub trait MacroPlugin: std::fmt::Debug + Sync + Send {
     /// Note: They may not cause a diagnostic if some other plugin declares such attribute, but
     /// plugin writers should not rely on that.
     fn declared_attributes(&self) -> Vec<String>;
+
+    /// Registers the documentation of the error codes of the diagnostics this plugin reports.
+    fn register_error_codes(&self, _registry: &mut ErrorCodeRegistry) {}
+}
+
+/// A trait for an attribute macro plugin: a macro plugin generating code for the items with its
+/// attribute, getting the arguments of the attribute and the item as token streams.
+///
+/// Every attribute macro plugin is a [MacroPlugin] declaring its attribute.
+pub trait AttributeMacroPlugin: std::fmt::Debug + Sync + Send {
+    /// The name of the attribute of the macro.
+    fn attribute_name(&self) -> &'static str;
+
+    /// Generates code for an item with the attribute of the macro, given the input of the macro.
+    /// If no code should be generated returns None.
+    fn generate_code(
+        &self,
+        db: &dyn SyntaxGroup,
+        item_ast: ast::ModuleItem,
+        input: AttributeMacroInput,
+        metadata: &MacroPluginMetadata<'_>,
+    ) -> PluginResult;
+
+    /// Registers the documentation of the error codes of the diagnostics this plugin reports.
+    fn register_error_codes(&self, _registry: &mut ErrorCodeRegistry) {}
+}
+impl<T: AttributeMacroPlugin> MacroPlugin for T {
+    fn generate_code(
+        &self,
+        db: &dyn SyntaxGroup,
+        item_ast: ast::ModuleItem,
+        metadata: &MacroPluginMetadata<'_>,
+    ) -> PluginResult {
+        let Some(input) = attribute_macro_input(db, &item_ast, self.attribute_name()) else {
+            return PluginResult::default();
+        };
+        AttributeMacroPlugin::generate_code(self, db, item_ast, input, metadata)
+    }
+
+    fn declared_attributes(&self) -> Vec<String> {
+        vec![self.attribute_name().to_string()]
+    }
+
+    fn register_error_codes(&self, registry: &mut ErrorCodeRegistry) {
+        AttributeMacroPlugin::register_error_codes(self, registry)
+    }
 }
 
 /// Result of plugin code generation.
@@ -121,6 +177,9 @@ pub trait InlineMacroExprPlugin: std::fmt::Debug + Sync + Send {
         db: &dyn SyntaxGroup,
         item_ast: &ast::ExprInlineMacro,
     ) -> InlinePluginResult;
+
+    /// Registers the documentation of the error codes of the diagnostics this plugin reports.
+    fn register_error_codes(&self, _registry: &mut ErrorCodeRegistry) {}
 }
 
 /// A trait for easier addition of macro plugins.
@@ -0,0 +1,119 @@
+//! Token streams of the code, keeping the location of each token, for plugins processing their
+//! inputs as tokens rather than as syntax trees or text.
+
+use std::fmt::Display;
+
+use cairo_lang_filesystem::span::TextSpan;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::{ast, SyntaxNode, TypedSyntaxNode};
+use smol_str::SmolStr;
+
+use crate::patcher::RewriteNode;
+
+#[cfg(test)]
+#[path = "token_stream_test.rs"]
+mod test;
+
+/// A token of a [TokenStream], along with the location it was taken from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Token {
+    /// The text of the token, without its trivia.
+    pub text: SmolStr,
+    /// The span of the token in its file, without its trivia.
+    pub span: TextSpan,
+    /// The terminal node of the token, for reporting diagnostics on it.
+    pub stable_ptr: SyntaxStablePtrId,
+}
+
+/// A sequence of tokens taken from the code.
+///
+/// Unlike the text of the code, each token keeps its location, so plugins may report diagnostics
+/// on the exact tokens, and map the code they generate from the tokens back to them.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TokenStream {
+    pub tokens: Vec<Token>,
+}
+impl TokenStream {
+    /// Returns the tokens of `node`, in order. Missing tokens are skipped.
+    pub fn from_syntax_node(db: &dyn SyntaxGroup, node: &SyntaxNode) -> Self {
+        let tokens = node
+            .descendants(db)
+            .filter_map(|terminal| {
+                let token = terminal.get_terminal_token(db)?;
+                let text = token.text(db)?;
+                if text.is_empty() {
+                    return None;
+                }
+                Some(Token { text, span: token.span(db), stable_ptr: terminal.stable_ptr() })
+            })
+            .collect();
+        Self { tokens }
+    }
+
+    /// Returns whether the stream has no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns a rewrite node of the code of the tokens, mapping each token to its origin.
+    ///
+    /// The tokens are separated by a single space where they were separated in the code.
+    pub fn as_rewrite_node(&self) -> RewriteNode {
+        let mut children = vec![];
+        for (idx, token) in self.tokens.iter().enumerate() {
+            if self.is_separated(idx) {
+                children.push(RewriteNode::text(" "));
+            }
+            children.push(RewriteNode::mapped_text(&token.text, token.span));
+        }
+        RewriteNode::new_modified(children)
+    }
+
+    /// Returns whether the token at `idx` is separated from the previous token in the code.
+    fn is_separated(&self, idx: usize) -> bool {
+        idx > 0 && self.tokens[idx - 1].span.end != self.tokens[idx].span.start
+    }
+}
+impl Display for TokenStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, token) in self.tokens.iter().enumerate() {
+            if self.is_separated(idx) {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", token.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// The input of an attribute macro, as token streams.
+pub struct AttributeMacroInput {
+    /// The attribute of the macro.
+    pub attr: ast::Attribute,
+    /// The arguments of the attribute, without the surrounding parentheses.
+    pub args: TokenStream,
+    /// The item the attribute is applied to, without the attribute.
+    pub item: TokenStream,
+}
+
+/// Returns the input of the attribute macro `attr_name` applied to `item_ast`, or `None` if the
+/// item doesn't have the attribute.
+pub fn attribute_macro_input(
+    db: &dyn SyntaxGroup,
+    item_ast: &ast::ModuleItem,
+    attr_name: &str,
+) -> Option<AttributeMacroInput> {
+    let attr = item_ast.find_attr(db, attr_name)?;
+    let args = match attr.arguments(db) {
+        ast::OptionArgListParenthesized::Empty(_) => TokenStream::default(),
+        ast::OptionArgListParenthesized::ArgListParenthesized(args) => {
+            TokenStream::from_syntax_node(db, &args.arguments(db).as_syntax_node())
+        }
+    };
+    let attr_span = attr.as_syntax_node().span(db);
+    let mut item = TokenStream::from_syntax_node(db, &item_ast.as_syntax_node());
+    item.tokens.retain(|token| !attr_span.contains(token.span));
+    Some(AttributeMacroInput { attr, args, item })
+}
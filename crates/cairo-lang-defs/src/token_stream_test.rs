@@ -0,0 +1,55 @@
+use cairo_lang_filesystem::ids::CodeOrigin;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::node::TypedSyntaxNode;
+use indoc::indoc;
+
+use super::attribute_macro_input;
+use crate::db::DefsGroup;
+use crate::patcher::PatchBuilder;
+use crate::test::{setup_test_module, DatabaseForTesting};
+
+#[test]
+fn test_attribute_macro_input() {
+    let mut db_val = DatabaseForTesting::default();
+    let module_id = setup_test_module(
+        &mut db_val,
+        indoc! {"
+            #[inline]
+            #[my_macro(a, b: 1 +2)]
+            fn foo() -> felt252 { 5 }
+        "},
+    );
+    let db = &db_val;
+    let file_id = db.module_main_file(module_id).unwrap();
+    let item_ast = db.file_module_syntax(file_id).unwrap().items(db).elements(db).remove(0);
+
+    assert!(attribute_macro_input(db, &item_ast, "other_macro").is_none());
+    let input = attribute_macro_input(db, &item_ast, "my_macro").unwrap();
+    assert_eq!(input.attr.attr(db).as_syntax_node().get_text(db), "my_macro");
+    assert_eq!(input.args.to_string(), "a, b: 1 +2");
+    assert_eq!(input.item.to_string(), "#[inline] fn foo() -> felt252 { 5 }");
+
+    // Each token points at its exact location in the code.
+    let file_syntax = db.file_syntax(file_id).unwrap();
+    for token in input.args.tokens.iter().chain(&input.item.tokens) {
+        assert_eq!(file_syntax.clone().get_text_of_span(db, token.span), token.text);
+    }
+
+    // The code generated from the tokens is mapped back to them.
+    let mut builder = PatchBuilder::new(db);
+    builder.add_modified(input.args.as_rewrite_node());
+    assert_eq!(builder.code, "a, b: 1 +2");
+    assert_eq!(
+        builder
+            .code_mappings
+            .iter()
+            .map(|mapping| (mapping.span.take(&builder.code), mapping.origin.clone()))
+            .collect::<Vec<_>>(),
+        input
+            .args
+            .tokens
+            .iter()
+            .map(|token| (token.text.as_str(), CodeOrigin::Span(token.span)))
+            .collect::<Vec<_>>()
+    );
+}
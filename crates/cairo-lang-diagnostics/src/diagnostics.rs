@@ -1,4 +1,5 @@
 use std::fmt;
+use std::fmt::Write;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -11,6 +12,7 @@ use itertools::Itertools;
 
 use crate::error_code::{ErrorCode, OptionErrorCodeExt};
 use crate::location_marks::get_location_marks;
+use crate::render::{render_location, ColorTheme, RenderConfig};
 
 #[cfg(test)]
 #[path = "diagnostics_test.rs"]
@@ -202,11 +204,19 @@ pub struct FormattedDiagnosticEntry {
     severity: Severity,
     error_code: Option<ErrorCode>,
     message: String,
+    /// The colors used for displaying the severity.
+    theme: ColorTheme,
 }
 
 impl FormattedDiagnosticEntry {
     pub fn new(severity: Severity, error_code: Option<ErrorCode>, message: String) -> Self {
-        Self { severity, error_code, message }
+        Self { severity, error_code, message, theme: ColorTheme::NO_COLOR }
+    }
+
+    /// Sets the colors used for displaying the severity.
+    pub fn with_theme(mut self, theme: ColorTheme) -> Self {
+        self.theme = theme;
+        self
     }
 
     pub fn is_empty(&self) -> bool {
@@ -228,12 +238,12 @@ impl FormattedDiagnosticEntry {
 
 impl fmt::Display for FormattedDiagnosticEntry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = format!("{}{}", self.severity, self.error_code.display_bracketed());
         write!(
             f,
-            "{severity}{code}: {message}",
-            severity = self.severity,
+            "{severity}: {message}",
+            severity = self.theme.paint(self.theme.severity(self.severity), &severity),
             message = self.message,
-            code = self.error_code.display_bracketed()
         )
     }
 }
@@ -274,6 +284,41 @@ impl<TEntry: DiagnosticEntry> Diagnostics<TEntry> {
         res
     }
 
+    /// Format entries to pairs of severity and message, rendering their locations as source
+    /// snippets according to `config`.
+    pub fn format_with_config(
+        &self,
+        db: &TEntry::DbType,
+        config: &RenderConfig,
+    ) -> Vec<FormattedDiagnosticEntry> {
+        let files_db = db.upcast();
+        let theme = &config.theme;
+        let mut res: Vec<FormattedDiagnosticEntry> = Vec::new();
+        for entry in &self.0.leaves {
+            let severity_color = theme.severity(entry.severity());
+            let mut msg = format!(
+                "{}\n{}",
+                entry.format(db),
+                render_location(files_db, &entry.location(db), config, severity_color)
+            );
+            for note in entry.notes(db) {
+                writeln!(msg, "{}: {}", theme.paint(theme.note, "note"), note.text).unwrap();
+                if let Some(location) = &note.location {
+                    msg += &render_location(files_db, location, config, theme.note);
+                }
+            }
+            msg += "\n";
+            res.push(
+                FormattedDiagnosticEntry::new(entry.severity(), entry.error_code(), msg)
+                    .with_theme(config.theme),
+            );
+        }
+        res.extend(
+            self.0.subtrees.iter().flat_map(|subtree| subtree.format_with_config(db, config)),
+        );
+        res
+    }
+
     /// Format entries to a [`String`] with messages prefixed by severity.
     pub fn format(&self, db: &TEntry::DbType) -> String {
         self.format_with_severity(db).iter().map(ToString::to_string).join("")
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// The unique and never-changing identifier of an error or warning.
@@ -74,3 +75,47 @@ impl OptionErrorCodeExt for Option<ErrorCode> {
         self.map(ErrorCode::display_bracketed).unwrap_or_default()
     }
 }
+
+/// A registry of documented error codes, mapping each code to an explanation of what the
+/// diagnostic means and how to fix it.
+///
+/// The compiler and its plugins register the codes of the diagnostics they report, so that users
+/// can look them up, e.g. with the `--explain` flag of the CLI drivers.
+#[derive(Clone, Debug, Default)]
+pub struct ErrorCodeRegistry {
+    docs: BTreeMap<ErrorCode, &'static str>,
+}
+
+impl ErrorCodeRegistry {
+    /// Registers `doc` as the documentation of `code`.
+    ///
+    /// Panics if `code` is already registered with a different documentation, as error codes must
+    /// be unique across the compiler and all plugins.
+    pub fn register(&mut self, code: ErrorCode, doc: &'static str) -> &mut Self {
+        if let Some(existing) = self.docs.insert(code, doc) {
+            assert_eq!(existing, doc, "Error code `{code}` is registered more than once.");
+        }
+        self
+    }
+
+    /// Returns the documentation of the error code named `code`, if it is registered.
+    ///
+    /// ```
+    /// # use cairo_lang_diagnostics::{error_code, ErrorCodeRegistry};
+    /// let mut registry = ErrorCodeRegistry::default();
+    /// registry.register(error_code!(E0001), "Some explanation.");
+    /// assert_eq!(registry.explain("E0001"), Some("Some explanation."));
+    /// assert_eq!(registry.explain("e0001"), Some("Some explanation."));
+    /// assert_eq!(registry.explain("E0002"), None);
+    /// ```
+    pub fn explain(&self, code: &str) -> Option<&'static str> {
+        self.docs.iter().find_map(|(registered, doc)| {
+            registered.as_str().eq_ignore_ascii_case(code).then_some(*doc)
+        })
+    }
+
+    /// Returns the registered error codes and their documentation, ordered by code.
+    pub fn iter(&self) -> impl Iterator<Item = (ErrorCode, &'static str)> + '_ {
+        self.docs.iter().map(|(code, doc)| (*code, *doc))
+    }
+}
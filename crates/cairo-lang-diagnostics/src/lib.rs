@@ -6,9 +6,11 @@ pub use diagnostics::{
     DiagnosticNote, Diagnostics, DiagnosticsBuilder, FormattedDiagnosticEntry, Maybe, Severity,
     ToMaybe, ToOption,
 };
-pub use error_code::{ErrorCode, OptionErrorCodeExt};
+pub use error_code::{ErrorCode, ErrorCodeRegistry, OptionErrorCodeExt};
 pub use location_marks::get_location_marks;
+pub use render::{render_location, ColorTheme, RenderConfig};
 
 mod diagnostics;
 mod error_code;
 mod location_marks;
+mod render;
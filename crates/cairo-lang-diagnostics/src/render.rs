@@ -0,0 +1,127 @@
+//! Rendering of diagnostics with source snippets, for presenting them to users in a terminal.
+
+use std::fmt::Write;
+
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::span::{TextSpan, TextWidth};
+
+use crate::{DiagnosticLocation, Severity};
+
+#[cfg(test)]
+#[path = "render_test.rs"]
+mod test;
+
+/// The colors used for rendering diagnostics, as ANSI escape sequences.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ColorTheme {
+    /// The color of errors and of the marks of their locations.
+    pub error: &'static str,
+    /// The color of warnings and of the marks of their locations.
+    pub warning: &'static str,
+    /// The color of notes and of the marks of their locations.
+    pub note: &'static str,
+    /// The color of the line numbers and the location headers.
+    pub gutter: &'static str,
+}
+impl ColorTheme {
+    /// A theme without colors, for rendering into files or terminals not supporting colors.
+    pub const NO_COLOR: Self = Self { error: "", warning: "", note: "", gutter: "" };
+    /// A theme for terminals with a dark background.
+    pub const DARK: Self = Self {
+        error: "\x1b[1;31m",
+        warning: "\x1b[1;33m",
+        note: "\x1b[1;36m",
+        gutter: "\x1b[1;34m",
+    };
+    /// A theme for terminals with a light background.
+    pub const LIGHT: Self =
+        Self { error: "\x1b[31m", warning: "\x1b[35m", note: "\x1b[34m", gutter: "\x1b[36m" };
+
+    /// Returns the color of the given severity.
+    pub fn severity(&self, severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => self.error,
+            Severity::Warning => self.warning,
+        }
+    }
+
+    /// Returns `text` painted with `color`.
+    pub fn paint(&self, color: &str, text: &str) -> String {
+        if color.is_empty() { text.to_string() } else { format!("{color}{text}\x1b[0m") }
+    }
+}
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self::NO_COLOR
+    }
+}
+
+/// The configuration for rendering diagnostics with source snippets.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RenderConfig {
+    /// The number of source lines shown before and after the lines of a location.
+    pub context_lines: usize,
+    /// The colors used for rendering.
+    pub theme: ColorTheme,
+}
+
+/// Renders `location` as a header with its position in the file, followed by the source lines it
+/// spans with its span underlined in `color`, surrounded by the configured number of context
+/// lines.
+pub fn render_location(
+    db: &dyn FilesGroup,
+    location: &DiagnosticLocation,
+    config: &RenderConfig,
+    color: &str,
+) -> String {
+    let location = location.user_location(db);
+    let theme = &config.theme;
+    let file_path = location.file_id.full_path(db);
+    let (Some(content), Some(summary), Some(start), Some(end)) = (
+        db.file_content(location.file_id),
+        db.file_summary(location.file_id),
+        location.span.start.position_in_file(db, location.file_id),
+        location.span.end.position_in_file(db, location.file_id),
+    ) else {
+        return format!("{} {file_path}\n", theme.paint(theme.gutter, "-->"));
+    };
+
+    let first_line = start.line.saturating_sub(config.context_lines);
+    let last_line = (end.line + config.context_lines).min(summary.line_count() - 1);
+    let gutter_width = (last_line + 1).to_string().len();
+    let empty_gutter = theme.paint(theme.gutter, &format!("{:gutter_width$} |", ""));
+
+    let mut res = format!(
+        "{:gutter_width$}{} {file_path}:{}:{}\n{empty_gutter}\n",
+        "",
+        theme.paint(theme.gutter, "-->"),
+        start.line + 1,
+        start.col + 1
+    );
+    for line_idx in first_line..=last_line {
+        let line_start = summary.line_offsets[line_idx];
+        let line_end = match summary.line_offsets.get(line_idx + 1) {
+            Some(offset) => offset.sub_width(TextWidth::from_char('\n')),
+            None => summary.last_offset,
+        };
+        let line = TextSpan { start: line_start, end: line_end }.take(&content);
+        let gutter = theme.paint(theme.gutter, &format!("{:>gutter_width$} |", line_idx + 1));
+        writeln!(res, "{}", format!("{gutter} {line}").trim_end()).unwrap();
+        if line_idx < start.line || line_idx > end.line {
+            continue;
+        }
+        let marked_start = location.span.start.max(line_start);
+        let marked_end = location.span.end.min(line_end);
+        let col = TextSpan { start: line_start, end: marked_start }.n_chars(&content);
+        let marked_chars = TextSpan { start: marked_start, end: marked_end }.n_chars(&content);
+        // Empty spans are marked at their position, but empty ends of multiline spans are not.
+        if marked_chars == 0 && line_idx != start.line {
+            continue;
+        }
+        let marks = "^".repeat(marked_chars.max(1));
+        writeln!(res, "{empty_gutter} {:col$}{}", "", theme.paint(color, &marks)).unwrap();
+    }
+    res.push_str(&empty_gutter);
+    res.push('\n');
+    res
+}
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{FileId, FileKind, FileLongId, VirtualFile};
+use cairo_lang_filesystem::span::{TextSpan, TextWidth};
+use cairo_lang_filesystem::test_utils::FilesDatabaseForTesting;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_log::test;
+
+use super::{render_location, ColorTheme, RenderConfig};
+use crate::DiagnosticLocation;
+
+/// Returns the location of the span between the given offsets in the given line of `file`.
+fn location(
+    db: &FilesDatabaseForTesting,
+    file_id: FileId,
+    (start_line, start_col): (usize, u32),
+    (end_line, end_col): (usize, u32),
+) -> DiagnosticLocation {
+    let summary = db.file_summary(file_id).unwrap();
+    let offset =
+        |line: usize, col| summary.line_offsets[line].add_width(TextWidth::new_for_testing(col));
+    DiagnosticLocation {
+        file_id,
+        span: TextSpan { start: offset(start_line, start_col), end: offset(end_line, end_col) },
+    }
+}
+
+#[test]
+fn test_render_location() {
+    let db = FilesDatabaseForTesting::default();
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(
+            indoc! {"
+                fn foo() -> felt252 {
+                    let x = bar(1, 2);
+
+                    x + 1
+                }
+            "}
+            .into(),
+        ),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let no_context = RenderConfig::default();
+    let with_context = RenderConfig { context_lines: 1, ..RenderConfig::default() };
+
+    // A span within a single line.
+    let call = location(&db, file_id, (1, 12), (1, 21));
+    assert_eq!(
+        render_location(&db, &call, &no_context, ""),
+        indoc! {"
+             --> lib.cairo:2:13
+              |
+            2 |     let x = bar(1, 2);
+              |             ^^^^^^^^^
+              |
+        "}
+    );
+    assert_eq!(
+        render_location(&db, &call, &with_context, ""),
+        indoc! {"
+             --> lib.cairo:2:13
+              |
+            1 | fn foo() -> felt252 {
+            2 |     let x = bar(1, 2);
+              |             ^^^^^^^^^
+            3 |
+              |
+        "}
+    );
+
+    // An empty span.
+    let empty = location(&db, file_id, (3, 4), (3, 4));
+    assert_eq!(
+        render_location(&db, &empty, &no_context, ""),
+        indoc! {"
+             --> lib.cairo:4:5
+              |
+            4 |     x + 1
+              |     ^
+              |
+        "}
+    );
+
+    // A span over multiple lines.
+    let body = location(&db, file_id, (0, 20), (4, 1));
+    assert_eq!(
+        render_location(&db, &body, &with_context, ""),
+        indoc! {"
+             --> lib.cairo:1:21
+              |
+            1 | fn foo() -> felt252 {
+              |                     ^
+            2 |     let x = bar(1, 2);
+              | ^^^^^^^^^^^^^^^^^^^^^^
+            3 |
+            4 |     x + 1
+              | ^^^^^^^^^
+            5 | }
+              | ^
+            6 |
+              |
+        "}
+    );
+
+    // Colored marks and gutter.
+    let theme = ColorTheme { gutter: "<g>", ..ColorTheme::NO_COLOR };
+    assert_eq!(
+        render_location(&db, &empty, &RenderConfig { context_lines: 0, theme }, "<m>"),
+        indoc! {"
+             <g>-->\x1b[0m lib.cairo:4:5
+            <g>  |\x1b[0m
+            <g>4 |\x1b[0m     x + 1
+            <g>  |\x1b[0m     <m>^\x1b[0m
+            <g>  |\x1b[0m
+        "}
+    );
+}
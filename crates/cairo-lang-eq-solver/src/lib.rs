@@ -30,27 +30,8 @@ pub fn try_solve_equations<Var: Clone + Debug + PartialEq + Eq + Hash>(
             .iter()
             .map(|v| (v.clone(), *layer_solution.get(v).unwrap()))
             .collect::<OrderedHashMap<_, _>>();
-        equations = equations
-            .into_iter()
-            .filter_map(|eq| {
-                let const_term = eq
-                    .var_to_coef
-                    .iter()
-                    .filter_map(|(var, coef)| Some(target_vars_solution.get(var)? * coef))
-                    .sum::<i64>()
-                    + eq.const_term as i64;
-                let var_to_coef: OrderedHashMap<_, _> = eq
-                    .var_to_coef
-                    .into_iter()
-                    .filter(|(var, _coef)| !target_vars_solution.contains_key(var))
-                    .collect();
-                if var_to_coef.is_empty() {
-                    assert_eq!(const_term, 0, "Zeroed out equations should be zeroed out.");
-                    return None;
-                }
-                Some(Expr { var_to_coef, const_term: const_term.into_or_panic::<i32>() })
-            })
-            .collect();
+        equations = substitute_values(equations, &target_vars_solution)
+            .expect("Zeroed out equations should be zeroed out.");
         accumulated_solution.extend(target_vars_solution);
     }
     let final_layer_solution = try_solve_equations_iteration(&equations, final_iter)?;
@@ -58,6 +39,109 @@ pub fn try_solve_equations<Var: Clone + Debug + PartialEq + Eq + Hash>(
     Some(accumulated_solution)
 }
 
+/// Solving a set of equations as [try_solve_equations], warm-started from a previous solution of a
+/// similar set of equations.
+/// The previous values of the variables are kept if they are consistent with the equations - along
+/// with the values they determine - and only the rest of the variables are solved for. Otherwise,
+/// the equations are solved from scratch.
+/// # Arguments
+/// * `equations` - The equations to solve.
+/// * `minimization_vars` - Vars to minimize, as in [try_solve_equations].
+/// * `previous_solution` - The previous values of variables, possibly not appearing in the
+///   equations.
+/// # Returns
+/// * `Some(OrderedHashMap<Var, i64>)` - The solutions to the equations.
+/// * `None` - The equations are unsolvable.
+pub fn try_solve_equations_from_previous<Var: Clone + Debug + PartialEq + Eq + Hash>(
+    equations: Vec<Expr<Var>>,
+    minimization_vars: Vec<Vec<Var>>,
+    previous_solution: &OrderedHashMap<Var, i64>,
+) -> Option<OrderedHashMap<Var, i64>> {
+    if let Some(solution) =
+        try_solve_equations_with_fixed_values(&equations, &minimization_vars, previous_solution)
+    {
+        return Some(solution);
+    }
+    try_solve_equations(equations, minimization_vars)
+}
+
+/// Solving a set of equations, with the variables of `fixed_values` fixed to their values.
+/// Returns `None` if the equations are unsolvable with these values.
+fn try_solve_equations_with_fixed_values<Var: Clone + Debug + PartialEq + Eq + Hash>(
+    equations: &[Expr<Var>],
+    minimization_vars: &[Vec<Var>],
+    fixed_values: &OrderedHashMap<Var, i64>,
+) -> Option<OrderedHashMap<Var, i64>> {
+    let mut solution: OrderedHashMap<Var, i64> = equations
+        .iter()
+        .flat_map(|eq| eq.var_to_coef.keys())
+        .filter_map(|var| Some((var.clone(), *fixed_values.get(var)?)))
+        .collect();
+    if solution.is_empty() {
+        return None;
+    }
+    let mut equations = substitute_values(equations.to_vec(), &solution)?;
+    // Propagating the values determined by equations with a single variable.
+    loop {
+        let mut determined_values = OrderedHashMap::<Var, i64>::default();
+        for eq in &equations {
+            if eq.var_to_coef.len() != 1 {
+                continue;
+            }
+            let (var, coef) = eq.var_to_coef.iter().next().unwrap();
+            let const_term = eq.const_term as i64;
+            let value = -const_term / coef;
+            if value * coef != -const_term || value < 0 {
+                return None;
+            }
+            if *determined_values.entry(var.clone()).or_insert(value) != value {
+                return None;
+            }
+        }
+        if determined_values.is_empty() {
+            break;
+        }
+        equations = substitute_values(equations, &determined_values)?;
+        solution.extend(determined_values);
+    }
+    if !equations.is_empty() {
+        let minimization_vars = minimization_vars
+            .iter()
+            .map(|layer| layer.iter().filter(|var| !solution.contains_key(*var)).cloned().collect())
+            .collect();
+        solution.extend(try_solve_equations(equations, minimization_vars)?);
+    }
+    Some(solution)
+}
+
+/// Substitutes the given values of variables into the equations, removing the equations left with
+/// no variables.
+/// Returns `None` if any of the removed equations does not hold.
+fn substitute_values<Var: Clone + Debug + PartialEq + Eq + Hash>(
+    equations: Vec<Expr<Var>>,
+    values: &OrderedHashMap<Var, i64>,
+) -> Option<Vec<Expr<Var>>> {
+    let mut substituted = vec![];
+    for eq in equations {
+        let const_term = eq
+            .var_to_coef
+            .iter()
+            .filter_map(|(var, coef)| Some(values.get(var)? * coef))
+            .sum::<i64>()
+            + eq.const_term as i64;
+        let var_to_coef: OrderedHashMap<_, _> =
+            eq.var_to_coef.into_iter().filter(|(var, _coef)| !values.contains_key(var)).collect();
+        if var_to_coef.is_empty() {
+            if const_term != 0 {
+                return None;
+            }
+            continue;
+        }
+        substituted.push(Expr { var_to_coef, const_term: const_term.into_or_panic::<i32>() });
+    }
+    Some(substituted)
+}
+
 /// Solving a set of equations and returning the values of the symbols contained in them.
 /// # Arguments
 /// * `equations` - The equations to solve.
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -5,6 +6,7 @@ use std::sync::Arc;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::Upcast;
 use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
 
 use crate::cfg::CfgSet;
 use crate::flag::Flag;
@@ -41,6 +43,12 @@ pub struct CrateSettings {
 
     pub cfg_set: Option<CfgSet>,
 
+    /// Whether the crate is external to the project, e.g. a vendored dependency.
+    ///
+    /// Warnings originating in external crates are not reported, while errors still are.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external: bool,
+
     #[serde(default)]
     pub experimental_features: ExperimentalFeaturesConfig,
 }
@@ -98,6 +106,13 @@ pub struct ExperimentalFeaturesConfig {
     /// function before calling it.
     #[serde(default)]
     pub coupons: bool,
+    /// Features allowed for the entire crate, as if every item in the crate was annotated with
+    /// `#[feature("...")]`.
+    ///
+    /// Experimental syntax and semantics are gated behind such named features, so they can be
+    /// shipped without affecting crates that did not opt in.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub allowed_features: BTreeSet<SmolStr>,
 }
 
 // Salsa database interface.
@@ -159,18 +174,25 @@ pub fn init_dev_corelib(db: &mut (dyn FilesGroup + 'static), core_lib_dir: PathB
         core_crate,
         Some(CrateConfiguration {
             root: Directory::Real(core_lib_dir),
-            settings: CrateSettings {
-                edition: Edition::V2023_11,
-                cfg_set: Default::default(),
-                experimental_features: ExperimentalFeaturesConfig {
-                    negative_impls: true,
-                    coupons: true,
-                },
-            },
+            settings: corelib_crate_settings(),
         }),
     );
 }
 
+/// The settings the corelib is compiled with.
+pub fn corelib_crate_settings() -> CrateSettings {
+    CrateSettings {
+        edition: Edition::V2023_11,
+        cfg_set: Default::default(),
+        external: false,
+        experimental_features: ExperimentalFeaturesConfig {
+            negative_impls: true,
+            coupons: true,
+            allowed_features: Default::default(),
+        },
+    }
+}
+
 impl AsFilesGroupMut for dyn FilesGroup {
     fn as_files_group_mut(&mut self) -> &mut (dyn FilesGroup + 'static) {
         self
@@ -8,4 +8,18 @@ pub enum Flag {
     /// Default is false - automatically add.
     AddRedepositGas(bool),
     NumericMatchOptimizationMinArmsThreshold(usize),
+    /// Whether to encode the panic data with the revert-with-reason ABI, i.e. as the
+    /// `Error(string)` selector followed by the length-prefixed reason.
+    /// Default is false - the panic data is used as is.
+    RevertPanicAbi(bool),
+    /// Whether to explain ownership errors in depth - the chain of moves of a moved variable, the
+    /// impls considered for it, and the possible fixes.
+    /// Default is false - only the error and its direct cause are reported.
+    ExplainOwnership(bool),
+    /// Whether to optimize the program as a whole, after merging the lowered functions of all the
+    /// crates - inlining functions with a single call site in the program, and not keeping the
+    /// functions of crates only used as libraries of other crates unless they are called.
+    /// Default is false - every function of the compiled crates is kept, and only small functions
+    /// are inlined.
+    WholeProgram(bool),
 }
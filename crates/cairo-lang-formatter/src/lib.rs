@@ -1,8 +1,9 @@
 //! Cairo formatter.
 //!
-//! This crate is responsible for formatting Cairo code.
+//! This crate is responsible for formatting Cairo code, and migrating it between editions.
 pub mod cairo_formatter;
 pub mod formatter_impl;
+pub mod migration;
 pub mod node_properties;
 
 use std::sync::Arc;
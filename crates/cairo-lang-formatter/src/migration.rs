@@ -0,0 +1,410 @@
+//! Migration of Cairo code between editions.
+//!
+//! The migration applies a versioned set of syntactic rules, such as renamed corelib paths and
+//! changed attribute syntax, rewriting only the matching nodes so that the rest of the code,
+//! including its formatting and comments, is kept as is.
+
+use anyhow::{anyhow, Context};
+use cairo_lang_defs::patcher::{PatchBuilder, RewriteNode};
+use cairo_lang_filesystem::db::{Edition, FilesGroup};
+use cairo_lang_filesystem::span::TextSpan;
+use cairo_lang_parser::utils::{get_syntax_root_and_diagnostics, SimpleParserDatabase};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use itertools::{zip_eq, Itertools};
+use serde::de::value::{Error as DeError, StrDeserializer};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cairo_formatter::{FileDiff, FormattableInput, FormattingError};
+use crate::FormatOutcome;
+
+#[cfg(test)]
+#[path = "migration_test.rs"]
+mod test;
+
+/// The modules in the prelude of the `2023_01` edition that are no longer in the prelude of the
+/// `2023_10` edition, and are accessed through `core` instead.
+const MODULES_REMOVED_FROM_PRELUDE_2023_10: [&str; 25] = [
+    "array",
+    "box",
+    "byte_array",
+    "bytes_31",
+    "clone",
+    "cmp",
+    "debug",
+    "dict",
+    "ec",
+    "ecdsa",
+    "gas",
+    "hash",
+    "integer",
+    "internal",
+    "nullable",
+    "option",
+    "panics",
+    "pedersen",
+    "poseidon",
+    "result",
+    "serde",
+    "string",
+    "testing",
+    "traits",
+    "zeroable",
+];
+
+/// A syntactic rule of a migration.
+///
+/// Rules match the code syntactically, without resolving names, so code shadowing a migrated name
+/// (e.g. a local module named `array`) should be reviewed in the diff preview of the migration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MigrationRule {
+    /// Renames the paths starting with `from`, e.g. `array::ArrayTrait` to
+    /// `core::array::ArrayTrait` for `from = "array"` and `to = "core::array"`.
+    /// Expression and type paths of a single segment are not renamed, as they may be variables,
+    /// while `use` paths are renamed as well if equal to `from`.
+    RenamePath { from: String, to: String },
+    /// Replaces the attributes named `from` without arguments with `to`, e.g. `#[contract]` with
+    /// `#[starknet::contract]`, or `#[external]` with `#[external(v0)]`.
+    RenameAttribute { from: String, to: String },
+}
+
+/// The rules migrating code from an edition to the next one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationRuleSet {
+    pub from: Edition,
+    pub to: Edition,
+    pub rules: Vec<MigrationRule>,
+}
+
+/// Returns the rule sets migrating code between consecutive editions, from the oldest edition.
+pub fn edition_rule_sets() -> Vec<MigrationRuleSet> {
+    let attribute = |from: &str, to: &str| MigrationRule::RenameAttribute {
+        from: from.to_string(),
+        to: to.to_string(),
+    };
+    vec![
+        MigrationRuleSet {
+            from: Edition::V2023_01,
+            to: Edition::V2023_10,
+            rules: MODULES_REMOVED_FROM_PRELUDE_2023_10
+                .into_iter()
+                .map(|module| MigrationRule::RenamePath {
+                    from: module.to_string(),
+                    to: format!("core::{module}"),
+                })
+                .chain([
+                    attribute("contract", "starknet::contract"),
+                    attribute("abi", "starknet::interface"),
+                    attribute("external", "external(v0)"),
+                ])
+                .collect(),
+        },
+        // The `2023_11` edition only starts enforcing the visibility of items, which requires
+        // deciding what items are public.
+        MigrationRuleSet { from: Edition::V2023_10, to: Edition::V2023_11, rules: vec![] },
+    ]
+}
+
+/// Parses an edition by its name in the crate settings, e.g. `2023_10`.
+pub fn parse_edition(name: &str) -> Option<Edition> {
+    Edition::deserialize(StrDeserializer::<DeError>::new(name)).ok()
+}
+
+/// An error in setting up a migration.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MigrationError {
+    #[error("No migration from edition `{from:?}` to edition `{to:?}`.")]
+    NoMigrationPath { from: Edition, to: Edition },
+}
+
+/// Migrates Cairo code by applying a list of rules.
+#[derive(Clone, Debug, Default)]
+pub struct Migrator {
+    rules: Vec<MigrationRule>,
+}
+impl Migrator {
+    /// Creates a migrator applying the given rules, in order of precedence.
+    pub fn new(rules: Vec<MigrationRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Creates a migrator from edition `from` to edition `to`, chaining the rule sets of the
+    /// editions in between.
+    pub fn for_editions(from: Edition, to: Edition) -> Result<Self, MigrationError> {
+        Self::from_rule_sets(&edition_rule_sets(), from, to)
+    }
+
+    /// Creates a migrator from edition `from` to edition `to`, chaining the given rule sets.
+    pub fn from_rule_sets(
+        rule_sets: &[MigrationRuleSet],
+        from: Edition,
+        to: Edition,
+    ) -> Result<Self, MigrationError> {
+        let mut rules = vec![];
+        let mut edition = from;
+        while edition != to {
+            let Some(rule_set) = rule_sets.iter().find(|rule_set| rule_set.from == edition) else {
+                return Err(MigrationError::NoMigrationPath { from, to });
+            };
+            rules.extend(rule_set.rules.iter().cloned());
+            edition = rule_set.to;
+        }
+        Ok(Self { rules })
+    }
+
+    /// Returns the migrated code of the file with the given syntax root.
+    pub fn migrate_file(&self, db: &dyn SyntaxGroup, syntax_root: &SyntaxNode) -> String {
+        let Some(node) = self.rewrite(db, syntax_root) else {
+            return syntax_root.get_text(db);
+        };
+        let mut builder = PatchBuilder::new(db);
+        builder.add_modified(node);
+        builder.code
+    }
+
+    /// Migrates the input and returns the migrated code, along with a preview of the changes.
+    /// No changes are persisted.
+    pub fn migrate_to_string(
+        &self,
+        input: &dyn FormattableInput,
+    ) -> Result<FormatOutcome, FormattingError> {
+        let db = SimpleParserDatabase::default();
+        let file_id = input.to_file_id(&db).context("Unable to create virtual file.")?;
+        let original_text =
+            db.file_content(file_id).ok_or_else(|| anyhow!("Unable to read from input."))?;
+        let (syntax_root, diagnostics) =
+            get_syntax_root_and_diagnostics(&db, file_id, &original_text);
+        if diagnostics.check_error_free().is_err() {
+            return Err(FormattingError::ParsingError(
+                diagnostics.format_with_severity(&db).into(),
+            ));
+        }
+        let migrated_text = self.migrate_file(&db, &syntax_root);
+        if &migrated_text == original_text.as_ref() {
+            Ok(FormatOutcome::Identical(original_text.to_string()))
+        } else {
+            Ok(FormatOutcome::DiffFound(FileDiff {
+                original: original_text.to_string(),
+                formatted: migrated_text,
+            }))
+        }
+    }
+
+    /// Migrates the input in place, persisting the changes.
+    pub fn migrate_in_place(
+        &self,
+        input: &dyn FormattableInput,
+    ) -> Result<FormatOutcome, FormattingError> {
+        let outcome = self.migrate_to_string(input)?;
+        if let FormatOutcome::DiffFound(diff) = &outcome {
+            input.overwrite_content(diff.formatted.clone())?;
+        }
+        Ok(outcome)
+    }
+
+    /// Returns the rewritten `node`, or `None` if no rule applies to it or its descendants.
+    fn rewrite(&self, db: &dyn SyntaxGroup, node: &SyntaxNode) -> Option<RewriteNode> {
+        let rewritten = match node.kind(db) {
+            SyntaxKind::Attribute => {
+                self.rewrite_attribute(db, &ast::Attribute::from_syntax_node(db, node.clone()))
+            }
+            SyntaxKind::ExprPath => {
+                self.rewrite_expr_path(db, &ast::ExprPath::from_syntax_node(db, node.clone()))
+            }
+            SyntaxKind::ItemUse => {
+                let item_use = ast::ItemUse::from_syntax_node(db, node.clone());
+                let use_path = item_use.use_path(db).as_syntax_node();
+                let rewritten_use_path = self.rewrite_use_path(db, &item_use.use_path(db));
+                rewritten_use_path.map(|rewritten_use_path| {
+                    self.rewrite_children(db, node, |child| {
+                        if child == &use_path { Some(rewritten_use_path.clone()) } else { None }
+                    })
+                })
+            }
+            _ => None,
+        };
+        if rewritten.is_some() {
+            return rewritten;
+        }
+        let children = db.get_children(node.clone());
+        let rewritten_children = children.iter().map(|child| self.rewrite(db, child)).collect_vec();
+        if rewritten_children.iter().all(Option::is_none) {
+            return None;
+        }
+        Some(RewriteNode::new_modified(
+            zip_eq(children.iter(), rewritten_children)
+                .map(|(child, rewritten)| {
+                    rewritten.unwrap_or_else(|| RewriteNode::Copied(child.clone()))
+                })
+                .collect(),
+        ))
+    }
+
+    /// Returns `node` with its children rewritten, using `override_child` for the children it
+    /// returns a rewrite for.
+    fn rewrite_children(
+        &self,
+        db: &dyn SyntaxGroup,
+        node: &SyntaxNode,
+        override_child: impl Fn(&SyntaxNode) -> Option<RewriteNode>,
+    ) -> RewriteNode {
+        RewriteNode::new_modified(
+            db.get_children(node.clone())
+                .iter()
+                .map(|child| {
+                    override_child(child)
+                        .or_else(|| self.rewrite(db, child))
+                        .unwrap_or_else(|| RewriteNode::Copied(child.clone()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Rewrites an attribute matching a [MigrationRule::RenameAttribute] rule.
+    fn rewrite_attribute(
+        &self,
+        db: &dyn SyntaxGroup,
+        attribute: &ast::Attribute,
+    ) -> Option<RewriteNode> {
+        if !matches!(attribute.arguments(db), ast::OptionArgListParenthesized::Empty(_)) {
+            return None;
+        }
+        let attr = attribute.attr(db).as_syntax_node();
+        let name = attr.clone().get_text_without_trivia(db);
+        let to = self.rules.iter().find_map(|rule| match rule {
+            MigrationRule::RenameAttribute { from, to } if *from == name => Some(to),
+            _ => None,
+        })?;
+        Some(self.rewrite_children(db, &attribute.as_syntax_node(), |child| {
+            (child == &attr).then(|| replace_text(db, child, child, to))
+        }))
+    }
+
+    /// Rewrites an expression or type path matching a [MigrationRule::RenamePath] rule.
+    fn rewrite_expr_path(&self, db: &dyn SyntaxGroup, path: &ast::ExprPath) -> Option<RewriteNode> {
+        let segments = path.elements(db);
+        let (n_segments, to) = self.rules.iter().find_map(|rule| {
+            let MigrationRule::RenamePath { from, to } = rule else {
+                return None;
+            };
+            let from_segments = from.split("::").collect_vec();
+            let n_segments = from_segments.len();
+            // A path of a single segment may be a variable.
+            if segments.len() < n_segments || segments.len() == 1 {
+                return None;
+            }
+            let matches_from = segments.iter().zip(&from_segments).enumerate().all(
+                |(idx, (segment, from_segment))| {
+                    match segment {
+                        ast::PathSegment::Simple(segment) => {
+                            segment.ident(db).text(db) == *from_segment
+                        }
+                        // Only the generic arguments of the last renamed segment are kept.
+                        ast::PathSegment::WithGenericArgs(segment) => {
+                            idx + 1 == n_segments && segment.ident(db).text(db) == *from_segment
+                        }
+                    }
+                },
+            );
+            matches_from.then_some((n_segments, to))
+        })?;
+        // The children of a path are its segments, separated by `::` tokens.
+        let children = db.get_children(path.as_syntax_node());
+        let mut rewritten_children = match &segments[n_segments - 1] {
+            ast::PathSegment::Simple(_) => {
+                vec![replace_text(db, &children[0], &children[2 * n_segments - 2], to)]
+            }
+            ast::PathSegment::WithGenericArgs(segment) => vec![
+                replace_text(db, &children[0], &segment.ident(db).as_syntax_node(), to),
+                RewriteNode::Copied(segment.separator(db).as_syntax_node()),
+                self.rewrite(db, &segment.generic_args(db).as_syntax_node()).unwrap_or_else(|| {
+                    RewriteNode::Copied(segment.generic_args(db).as_syntax_node())
+                }),
+            ],
+        };
+        rewritten_children.extend(children[2 * n_segments - 1..].iter().map(|child| {
+            self.rewrite(db, child).unwrap_or_else(|| RewriteNode::Copied(child.clone()))
+        }));
+        Some(RewriteNode::new_modified(rewritten_children))
+    }
+
+    /// Rewrites the path of a `use` item matching a [MigrationRule::RenamePath] rule.
+    fn rewrite_use_path(
+        &self,
+        db: &dyn SyntaxGroup,
+        use_path: &ast::UsePath,
+    ) -> Option<RewriteNode> {
+        self.rules.iter().find_map(|rule| {
+            let MigrationRule::RenamePath { from, to } = rule else {
+                return None;
+            };
+            let mut from_segments = from.split("::").peekable();
+            let first_ident = use_path_ident(db, use_path)?;
+            let mut current = use_path.clone();
+            loop {
+                let ident = use_path_ident(db, &current)?;
+                if ident.as_syntax_node().get_text_without_trivia(db) != from_segments.next()? {
+                    return None;
+                }
+                if from_segments.peek().is_none() {
+                    let renamed = replace_text(
+                        db,
+                        &first_ident.as_syntax_node(),
+                        &ident.as_syntax_node(),
+                        to,
+                    );
+                    let rest = match &current {
+                        ast::UsePath::Leaf(leaf) => vec![leaf.alias_clause(db).as_syntax_node()],
+                        ast::UsePath::Single(single) => vec![
+                            single.colon_colon(db).as_syntax_node(),
+                            single.use_path(db).as_syntax_node(),
+                        ],
+                        ast::UsePath::Multi(_) => return None,
+                    };
+                    return Some(RewriteNode::new_modified(
+                        [renamed]
+                            .into_iter()
+                            .chain(rest.into_iter().map(RewriteNode::Copied))
+                            .collect(),
+                    ));
+                }
+                let ast::UsePath::Single(single) = current else {
+                    return None;
+                };
+                current = single.use_path(db);
+            }
+        })
+    }
+}
+
+/// Returns the first segment of a `use` path, if it is a simple segment.
+fn use_path_ident(db: &dyn SyntaxGroup, use_path: &ast::UsePath) -> Option<ast::PathSegment> {
+    let ident = match use_path {
+        ast::UsePath::Leaf(leaf) => leaf.ident(db),
+        ast::UsePath::Single(single) => single.ident(db),
+        ast::UsePath::Multi(_) => return None,
+    };
+    matches!(ident, ast::PathSegment::Simple(_)).then_some(ident)
+}
+
+/// Returns a node replacing the code from `first` to `last` with `text`, keeping the leading
+/// trivia of `first` and the trailing trivia of `last`.
+fn replace_text(
+    db: &dyn SyntaxGroup,
+    first: &SyntaxNode,
+    last: &SyntaxNode,
+    text: &str,
+) -> RewriteNode {
+    let leading_trivia = first.clone().get_text_of_span(
+        db,
+        TextSpan { start: first.span(db).start, end: first.span_start_without_trivia(db) },
+    );
+    let trailing_trivia = last.clone().get_text_of_span(
+        db,
+        TextSpan { start: last.span_end_without_trivia(db), end: last.span(db).end },
+    );
+    RewriteNode::Text(format!("{leading_trivia}{text}{trailing_trivia}"))
+}
@@ -0,0 +1,138 @@
+use cairo_lang_filesystem::db::Edition;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{parse_edition, MigrationError, MigrationRule, Migrator};
+use crate::FormatOutcome;
+
+/// Migrates `code` with `migrator`, expecting it to parse.
+fn migrate(migrator: &Migrator, code: &str) -> String {
+    migrator.migrate_to_string(&code.to_string()).unwrap().into_output_text()
+}
+
+#[test]
+fn migrate_to_latest_edition() {
+    let migrator = Migrator::for_editions(Edition::V2023_01, Edition::latest()).unwrap();
+    assert_eq!(
+        migrate(
+            &migrator,
+            indoc! {"
+                use array::ArrayTrait;
+                use option;
+                use core::traits::Into;
+                use starknet::ContractAddress;
+
+                #[contract]
+                mod counter {
+                    #[external]
+                    fn values(array: Array<felt252>) -> Array<u256> {
+                        // Renamed, with its comment: array::ArrayTrait::new().
+                        let mut values =
+                            array::ArrayTrait::<u256>::new();
+                        let _option: option::Option<integer::u8> = option::Option::None;
+                        values.append(traits::Into::<felt252, u256>::into(array.len().into()));
+                        values
+                    }
+
+                    #[external(v0)]
+                    fn untouched() {}
+                }
+            "}
+        ),
+        indoc! {"
+            use core::array::ArrayTrait;
+            use core::option;
+            use core::traits::Into;
+            use starknet::ContractAddress;
+
+            #[starknet::contract]
+            mod counter {
+                #[external(v0)]
+                fn values(array: Array<felt252>) -> Array<u256> {
+                    // Renamed, with its comment: array::ArrayTrait::new().
+                    let mut values =
+                        core::array::ArrayTrait::<u256>::new();
+                    let _option: core::option::Option<core::integer::u8> = core::option::Option::None;
+                    values.append(core::traits::Into::<felt252, u256>::into(array.len().into()));
+                    values
+                }
+
+                #[external(v0)]
+                fn untouched() {}
+            }
+        "}
+    );
+}
+
+#[test]
+fn migrate_with_custom_rules() {
+    let migrator = Migrator::new(vec![
+        MigrationRule::RenamePath {
+            from: "starknet::contract_address_const".into(),
+            to: "starknet::contract_address::contract_address_const".into(),
+        },
+        MigrationRule::RenameAttribute { from: "abi".into(), to: "starknet::interface".into() },
+    ]);
+    assert_eq!(
+        migrate(
+            &migrator,
+            indoc! {"
+                use starknet::contract_address_const;
+                use starknet::{contract_address_const as address};
+
+                #[abi]
+                trait IContract<T> {}
+
+                fn owner() -> starknet::ContractAddress {
+                    starknet::contract_address_const::<0x1>()
+                }
+            "}
+        ),
+        indoc! {"
+            use starknet::contract_address::contract_address_const;
+            use starknet::{contract_address_const as address};
+
+            #[starknet::interface]
+            trait IContract<T> {}
+
+            fn owner() -> starknet::ContractAddress {
+                starknet::contract_address::contract_address_const::<0x1>()
+            }
+        "}
+    );
+}
+
+#[test]
+fn migration_preview() {
+    let migrator = Migrator::for_editions(Edition::V2023_01, Edition::V2023_10).unwrap();
+    let FormatOutcome::DiffFound(diff) =
+        migrator.migrate_to_string(&"use array::ArrayTrait;\n".to_string()).unwrap()
+    else {
+        panic!("Expected the code to be migrated.");
+    };
+    assert_eq!(
+        diff.to_string(),
+        indoc! {"
+            --- original
+            +++ modified
+            @@ -1 +1 @@
+            -use array::ArrayTrait;
+            +use core::array::ArrayTrait;
+        "}
+    );
+    assert!(matches!(
+        migrator.migrate_to_string(&"use core::array::ArrayTrait;\n".to_string()).unwrap(),
+        FormatOutcome::Identical(_)
+    ));
+}
+
+#[test]
+fn migration_editions() {
+    assert_eq!(parse_edition("2023_10"), Some(Edition::V2023_10));
+    assert_eq!(parse_edition("2023_12"), None);
+    assert!(Migrator::for_editions(Edition::V2023_11, Edition::V2023_11).is_ok());
+    assert_eq!(
+        Migrator::for_editions(Edition::V2023_11, Edition::V2023_01).unwrap_err(),
+        MigrationError::NoMigrationPath { from: Edition::V2023_11, to: Edition::V2023_01 }
+    );
+}
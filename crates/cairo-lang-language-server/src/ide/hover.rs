@@ -1,6 +1,7 @@
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_defs::db::DefsGroup;
-use cairo_lang_defs::ids::LookupItemId;
+use cairo_lang_defs::ids::{LookupItemId, ModuleItemId, NamedLanguageElementId};
+use cairo_lang_sierra_to_casm::libfunc_docs::core_libfunc_doc;
 use cairo_lang_utils::Upcast;
 use tower_lsp::lsp_types::{Hover, HoverContents, HoverParams, MarkedString};
 
@@ -74,6 +75,15 @@ fn get_expr_hint(db: &dyn DefsGroup, lookup_item_id: LookupItemId) -> Option<Vec
         doc += "\n";
     }
     hints.push(MarkedString::from_markdown(doc));
+    // Extern functions are backed by the libfunc of the same name.
+    if let LookupItemId::ModuleItem(ModuleItemId::ExternFunction(extern_function_id)) =
+        lookup_item_id
+    {
+        if let Some(libfunc_doc) = core_libfunc_doc(&extern_function_id.name(db)) {
+            hints.push(MarkedString::String("\n---\n".to_string()));
+            hints.push(MarkedString::from_markdown(libfunc_doc.to_markdown()));
+        }
+    }
 
     Some(hints)
 }
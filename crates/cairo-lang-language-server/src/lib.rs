@@ -24,7 +24,7 @@ use cairo_lang_diagnostics::{Diagnostics, ToOption};
 use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_filesystem::db::{
     get_originating_location, init_dev_corelib, AsFilesGroupMut, CrateConfiguration, CrateSettings,
-    FilesGroup, FilesGroupEx, PrivRawFileContentQuery,
+    FilesGroup, FilesGroupEx, PrivRawFileContentQuery, CORELIB_CRATE_NAME,
 };
 use cairo_lang_filesystem::detect::detect_corelib;
 use cairo_lang_filesystem::ids::{CrateId, CrateLongId, Directory, FileId, FileLongId};
@@ -558,10 +558,17 @@ impl Backend {
         }
     }
 
-    /// Reload crate detection for all open files.
+    /// Reload crate detection for all open files, and the corelib files.
     #[tracing::instrument(level = "trace", skip_all)]
     pub async fn reload(&self) -> LSPResult<()> {
         let mut db = self.db_mut().await;
+        // Re-read the corelib, so that changes to it take effect without restarting the server.
+        let core_crate = db.intern_crate(CrateLongId::Real(CORELIB_CRATE_NAME.into()));
+        if let Some(CrateConfiguration { root: Directory::Real(root), .. }) =
+            db.crate_config(core_crate)
+        {
+            db.reload_corelib(root);
+        }
         for uri in self.state_mutex.lock().await.open_files.iter() {
             let file_id = db.file_for_url(uri);
             if let FileLongId::OnDisk(file_path) = db.lookup_intern_file(file_id) {
@@ -110,6 +110,7 @@ impl ScarbService {
                         CrateSettings {
                             edition,
                             cfg_set: Default::default(),
+                            external: false,
                             experimental_features: Default::default(),
                         },
                     ))
@@ -0,0 +1,189 @@
+//! In depth explanations of ownership errors, reported when the `explain_ownership` flag is set.
+
+use cairo_lang_defs::ids::LanguageElementId;
+use cairo_lang_diagnostics::DiagnosticNote;
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::FlagId;
+use cairo_lang_semantic::corelib::concrete_clone_trait;
+use cairo_lang_semantic::expr::inference::InferenceError;
+use cairo_lang_semantic::items::imp::ImplLookupContext;
+use cairo_lang_semantic::types::get_impl_at_context;
+use cairo_lang_semantic::{TypeId, TypeLongId};
+
+use crate::db::LoweringGroup;
+use crate::ids::{FunctionWithBodyId, LocationId};
+use crate::{Location, VariableId};
+
+/// Returns the value of the `explain_ownership` flag, or `false` if the flag is not set.
+pub fn flag_explain_ownership(db: &dyn LoweringGroup) -> bool {
+    db.get_flag(FlagId::new(db.upcast(), "explain_ownership"))
+        .is_some_and(|flag| *flag == Flag::ExplainOwnership(true))
+}
+
+/// A move of a non-copyable variable that is followed by another usage of it.
+pub struct VariableMove {
+    pub var_id: VariableId,
+    /// The location where the variable was moved.
+    pub position: LocationId,
+    /// The location of the next usage of the variable.
+    pub next_usage_position: LocationId,
+    /// The error of finding a `Copy` impl for the type of the variable.
+    pub inference_error: InferenceError,
+}
+
+/// A chain of consecutive moves of the same variable.
+pub struct MoveChain<'a> {
+    /// The last move of the chain, whose next usage is the one reported.
+    pub last: &'a VariableMove,
+    /// The locations of the moves of the chain, from the first to the last.
+    pub positions: Vec<LocationId>,
+}
+
+/// Collects the information required for explaining the ownership errors of a function.
+pub struct OwnershipExplainer {
+    /// The context for looking up the impls suggested as fixes.
+    lookup_context: ImplLookupContext,
+    /// The moves of non-copyable variables, in the order they were found.
+    pub moves: Vec<VariableMove>,
+}
+impl OwnershipExplainer {
+    pub fn new(db: &dyn LoweringGroup, function_id: FunctionWithBodyId) -> Self {
+        let semantic_function = function_id.base_semantic_function(db);
+        let generic_param_ids = db
+            .function_with_body_generic_params(semantic_function)
+            .map(|params| params.iter().map(|param| param.id()).collect())
+            .unwrap_or_default();
+        Self {
+            lookup_context: ImplLookupContext::new(
+                semantic_function.parent_module(db.upcast()),
+                generic_param_ids,
+            ),
+            moves: vec![],
+        }
+    }
+
+    /// Groups the collected moves into chains of consecutive moves of the same variable, so each
+    /// invalid usage is reported once, along with all the moves preceding it.
+    pub fn move_chains(&self) -> Vec<MoveChain<'_>> {
+        let mut chained = vec![false; self.moves.len()];
+        let mut chains = vec![];
+        // Moves that are not followed by another move of the same variable end a chain.
+        let is_chain_end = |mv: &VariableMove| {
+            !self
+                .moves
+                .iter()
+                .any(|other| other.var_id == mv.var_id && other.position == mv.next_usage_position)
+        };
+        for (idx, last) in self.moves.iter().enumerate() {
+            if !is_chain_end(last) {
+                continue;
+            }
+            chained[idx] = true;
+            let mut positions = vec![last.position];
+            while let Some(prev_idx) = self.moves.iter().enumerate().position(|(i, other)| {
+                !chained[i]
+                    && other.var_id == last.var_id
+                    && Some(&other.next_usage_position) == positions.last()
+            }) {
+                chained[prev_idx] = true;
+                positions.push(self.moves[prev_idx].position);
+            }
+            positions.reverse();
+            chains.push(MoveChain { last, positions });
+        }
+        // Moves not reachable from a chain end (which should not happen) are reported on their own.
+        for (idx, mv) in self.moves.iter().enumerate() {
+            if !chained[idx] {
+                chains.push(MoveChain { last: mv, positions: vec![mv.position] });
+            }
+        }
+        chains
+    }
+
+    /// Adds the notes explaining the usage of a variable after the given chain of moves.
+    pub fn explain_move(
+        &self,
+        db: &dyn LoweringGroup,
+        mut location: Location,
+        ty: TypeId,
+        chain: &MoveChain<'_>,
+    ) -> Location {
+        for (idx, position) in chain.positions.iter().enumerate() {
+            let text = if idx == 0 {
+                "variable was first moved here"
+            } else {
+                "variable was moved again here"
+            };
+            location = location.add_note_with_location(db, text, *position);
+        }
+        let ty_name = ty.format(db.upcast());
+        location = location
+            .with_note(DiagnosticNote::text_only(format!(
+                "the type `{ty_name}` is moved on use, since it is not copyable."
+            )))
+            .with_note(DiagnosticNote::text_only(format!(
+                "candidate `Copy` impl: {}",
+                chain.last.inference_error.format(db.upcast())
+            )));
+        if self.implements_clone(db, ty) {
+            location = location.with_note(DiagnosticNote::text_only(format!(
+                "help: `{ty_name}` implements `Clone` - consider moving a clone of the variable \
+                 instead, using `.clone()`."
+            )));
+        }
+        location.with_note(DiagnosticNote::text_only(
+            "help: if a move does not require ownership of the variable, consider passing a \
+             snapshot of it instead, using `@`."
+                .into(),
+        ))
+    }
+
+    /// Adds the notes explaining why a variable could not be dropped.
+    pub fn explain_not_dropped(
+        &self,
+        db: &dyn LoweringGroup,
+        location: Location,
+        ty: TypeId,
+        drop_err: &InferenceError,
+        destruct_err: &InferenceError,
+        panic_destruct_err: Option<&InferenceError>,
+    ) -> Location {
+        let ty_name = ty.format(db.upcast());
+        let is_generic_param = matches!(db.lookup_intern_type(ty), TypeLongId::GenericParameter(_));
+        let fix = if is_generic_param {
+            format!("adding a `+Drop<{ty_name}>` or `+Destruct<{ty_name}>` constraint")
+        } else {
+            format!(
+                "implementing `Drop` or `Destruct` for `{ty_name}`, e.g. using `#[derive(Drop)]`"
+            )
+        };
+        location
+            .with_note(DiagnosticNote::text_only(format!(
+                "the variable must be dropped, but its type `{ty_name}` is not droppable."
+            )))
+            .with_note(DiagnosticNote::text_only(format!(
+                "candidate `Drop` impl: {}",
+                drop_err.format(db.upcast())
+            )))
+            .with_note(DiagnosticNote::text_only(format!(
+                "candidate `Destruct` impl: {}",
+                destruct_err.format(db.upcast())
+            )))
+            .maybe_with_note(panic_destruct_err.map(|err| {
+                DiagnosticNote::text_only(format!(
+                    "candidate `PanicDestruct` impl: {}",
+                    err.format(db.upcast())
+                ))
+            }))
+            .with_note(DiagnosticNote::text_only(format!(
+                "help: consider {fix}, or consuming the variable on every path."
+            )))
+    }
+
+    /// Returns whether `ty` implements `Clone` in the context of the function.
+    fn implements_clone(&self, db: &dyn LoweringGroup, ty: TypeId) -> bool {
+        let concrete_trait_id = concrete_clone_trait(db.upcast(), ty);
+        get_impl_at_context(db.upcast(), self.lookup_context.clone(), concrete_trait_id, None)
+            .is_ok()
+    }
+}
@@ -2,7 +2,7 @@
 #[path = "test.rs"]
 mod test;
 
-use cairo_lang_defs::ids::{ModuleFileId, TraitFunctionId};
+use cairo_lang_defs::ids::{LanguageElementId, TraitFunctionId};
 use cairo_lang_diagnostics::{DiagnosticNote, Maybe};
 use cairo_lang_semantic::corelib::get_core_trait;
 use cairo_lang_semantic::items::functions::{GenericFunctionId, ImplGenericFunctionId};
@@ -12,16 +12,18 @@ use itertools::{zip_eq, Itertools};
 use self::analysis::{Analyzer, StatementLocation};
 pub use self::demand::Demand;
 use self::demand::{AuxCombine, DemandReporter};
+use self::explain::{flag_explain_ownership, OwnershipExplainer, VariableMove};
 use crate::blocks::Blocks;
 use crate::borrow_check::analysis::BackAnalysis;
 use crate::db::LoweringGroup;
 use crate::diagnostic::LoweringDiagnosticKind::*;
 use crate::diagnostic::LoweringDiagnostics;
-use crate::ids::{FunctionId, LocationId, SemanticFunctionIdEx};
+use crate::ids::{self, FunctionId, LocationId, SemanticFunctionIdEx};
 use crate::{BlockId, FlatLowered, MatchInfo, Statement, VarRemapping, VarUsage, VariableId};
 
 pub mod analysis;
 pub mod demand;
+pub mod explain;
 
 pub type BorrowCheckerDemand = Demand<VariableId, LocationId, PanicState>;
 pub struct BorrowChecker<'a> {
@@ -32,6 +34,8 @@ pub struct BorrowChecker<'a> {
     potential_destruct_calls: PotentialDestructCalls,
     destruct_fn: TraitFunctionId,
     panic_destruct_fn: TraitFunctionId,
+    /// Collects the information for explaining the ownership errors in depth, if requested.
+    explainer: Option<OwnershipExplainer>,
 }
 
 /// A state saved for each position in the back analysis.
@@ -135,21 +139,41 @@ impl<'a> DemandReporter<VariableId, PanicState> for BorrowChecker<'a> {
             location = location.with_note(drop_position.as_note(self.db));
         }
         let semantic_db = self.db.upcast();
-        self.success = Err(self.diagnostics.report_by_location(
-            location
+        let location = match &self.explainer {
+            Some(explainer) => explainer.explain_not_dropped(
+                self.db,
+                location,
+                var.ty,
+                &drop_err,
+                &destruct_err,
+                panic_destruct_err.as_ref(),
+            ),
+            None => location
                 .with_note(DiagnosticNote::text_only(drop_err.format(semantic_db)))
                 .with_note(DiagnosticNote::text_only(destruct_err.format(semantic_db)))
                 .maybe_with_note(
                     panic_destruct_err
                         .map(|err| DiagnosticNote::text_only(err.format(semantic_db))),
                 ),
-            VariableNotDropped { drop_err, destruct_err },
-        ));
+        };
+        self.success = Err(self
+            .diagnostics
+            .report_by_location(location, VariableNotDropped { drop_err, destruct_err }));
     }
 
     fn dup(&mut self, position: LocationId, var_id: VariableId, next_usage_position: LocationId) {
         let var = &self.lowered.variables[var_id];
         if let Err(inference_error) = var.copyable.clone() {
+            if let Some(explainer) = &mut self.explainer {
+                // Reported once the whole chain of moves of the variable is known.
+                explainer.moves.push(VariableMove {
+                    var_id,
+                    position,
+                    next_usage_position,
+                    inference_error,
+                });
+                return;
+            }
             self.success = Err(self.diagnostics.report_by_location(
                 next_usage_position
                     .get(self.db)
@@ -268,6 +292,28 @@ impl<'a> Analyzer<'_> for BorrowChecker<'a> {
     }
 }
 
+impl<'a> BorrowChecker<'a> {
+    /// Reports the moved variable usages collected by the explainer, once per chain of moves.
+    fn report_move_chains(&mut self) {
+        let Some(explainer) = self.explainer.take() else {
+            return;
+        };
+        for chain in explainer.move_chains() {
+            let VariableMove { var_id, next_usage_position, inference_error, .. } = chain.last;
+            let location = explainer.explain_move(
+                self.db,
+                next_usage_position.get(self.db),
+                self.lowered.variables[*var_id].ty,
+                &chain,
+            );
+            self.success = Err(self.diagnostics.report_by_location(
+                location,
+                VariableMoved { inference_error: inference_error.clone() },
+            ));
+        }
+    }
+}
+
 /// The possible destruct calls per block.
 pub type PotentialDestructCalls = UnorderedHashMap<BlockId, Vec<FunctionId>>;
 
@@ -275,12 +321,13 @@ pub type PotentialDestructCalls = UnorderedHashMap<BlockId, Vec<FunctionId>>;
 /// Returns the potential destruct function calls per block.
 pub fn borrow_check(
     db: &dyn LoweringGroup,
-    module_file_id: ModuleFileId,
+    function_id: ids::FunctionWithBodyId,
     lowered: &mut FlatLowered,
 ) -> PotentialDestructCalls {
     if lowered.blocks.has_root().is_err() {
         return Default::default();
     }
+    let module_file_id = function_id.base_semantic_function(db).module_file_id(db.upcast());
     let mut diagnostics = LoweringDiagnostics::new(module_file_id.file_id(db.upcast()).unwrap());
     diagnostics.diagnostics.extend(std::mem::take(&mut lowered.diagnostics));
     let destruct_trait_id = get_core_trait(db.upcast(), "Destruct".into());
@@ -299,6 +346,7 @@ pub fn borrow_check(
         potential_destruct_calls: Default::default(),
         destruct_fn,
         panic_destruct_fn,
+        explainer: flag_explain_ownership(db).then(|| OwnershipExplainer::new(db, function_id)),
     };
     let mut analysis = BackAnalysis::new(lowered, checker);
     let mut root_demand = analysis.get_root_info();
@@ -307,6 +355,7 @@ pub fn borrow_check(
         &lowered.parameters,
         (None, BlockId::root()),
     );
+    analysis.analyzer.report_move_chains();
     let block_extra_calls = analysis.analyzer.potential_destruct_calls;
     let success = analysis.analyzer.success;
     assert!(root_demand.finalize(), "Undefined variable should not happen at this stage");
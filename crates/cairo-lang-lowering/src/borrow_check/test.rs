@@ -1,7 +1,13 @@
+use std::sync::Arc;
+
 use cairo_lang_debug::DebugWithDb;
+use cairo_lang_filesystem::db::FilesGroupEx;
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::FlagId;
 use cairo_lang_semantic::test_utils::setup_test_function;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::UpcastMut;
 
 use crate::db::LoweringGroup;
 use crate::fmt::LoweredFormatter;
@@ -13,15 +19,24 @@ cairo_lang_test_utils::test_file_test!(
     "src/borrow_check/test_data",
     {
         borrow_check :"borrow_check",
+        explain_ownership :"explain_ownership",
     },
     test_borrow_check
 );
 
 fn test_borrow_check(
     inputs: &OrderedHashMap<String, String>,
-    _args: &OrderedHashMap<String, String>,
+    args: &OrderedHashMap<String, String>,
 ) -> TestRunnerResult {
-    let db = &mut LoweringDatabaseForTesting::default();
+    let db = &mut if args.get("explain_ownership").is_some_and(|value| value == "true") {
+        // The shared database is a snapshot, so a new one is required for setting the flag.
+        let mut db = LoweringDatabaseForTesting::new_empty();
+        let explain_ownership_flag_id = FlagId::new(db.upcast_mut(), "explain_ownership");
+        db.set_flag(explain_ownership_flag_id, Some(Arc::new(Flag::ExplainOwnership(true))));
+        db
+    } else {
+        LoweringDatabaseForTesting::default()
+    };
     let (test_function, semantic_diagnostics) = setup_test_function(
         db,
         inputs["function"].as_str(),
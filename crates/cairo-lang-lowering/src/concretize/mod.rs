@@ -21,6 +21,8 @@ fn concretize_function(
                 element,
             })
         }
+        // Specialized functions are created from concrete functions.
+        FunctionLongId::Specialized(specialized) => FunctionLongId::Specialized(specialized),
     };
     Ok(db.intern_lowering_function(long_id))
 }
@@ -28,6 +28,7 @@ use crate::inline::get_inline_diagnostics;
 use crate::lower::{lower_semantic_function, MultiLowering};
 use crate::optimizations::config::OptimizationConfig;
 use crate::optimizations::scrub_units::scrub_units;
+use crate::optimizations::specialization::specialized_function_lowered;
 use crate::optimizations::strategy::{OptimizationStrategy, OptimizationStrategyId};
 use crate::panic::lower_panics;
 use crate::{
@@ -50,6 +51,12 @@ pub trait LoweringGroup: SemanticGroup + Upcast<dyn SemanticGroup> {
         id: ids::FunctionWithBodyLongId,
     ) -> ids::FunctionWithBodyId;
 
+    #[salsa::interned]
+    fn intern_specialized_function(
+        &self,
+        id: ids::SpecializedFunction,
+    ) -> ids::SpecializedFunctionId;
+
     #[salsa::interned]
     fn intern_location(&self, id: Location) -> ids::LocationId;
 
@@ -113,6 +120,23 @@ pub trait LoweringGroup: SemanticGroup + Upcast<dyn SemanticGroup> {
         function_id: ids::ConcreteFunctionWithBodyId,
     ) -> Maybe<Arc<FlatLowered>>;
 
+    /// Returns the locations of the bounds checks elided from the final lowering of a function,
+    /// see [crate::optimizations::bounds_check_elision::elide_bounds_checks].
+    #[salsa::invoke(crate::optimizations::bounds_check_elision::elided_bounds_checks)]
+    fn elided_bounds_checks(
+        &self,
+        function_id: ids::ConcreteFunctionWithBodyId,
+    ) -> Maybe<Arc<Vec<ids::LocationId>>>;
+
+    /// Returns the locations of the creations of the dictionaries replaced by their single value in
+    /// the final lowering of a function, see
+    /// [crate::optimizations::single_key_dicts::replace_single_key_dicts].
+    #[salsa::invoke(crate::optimizations::single_key_dicts::single_key_dicts)]
+    fn single_key_dicts(
+        &self,
+        function_id: ids::ConcreteFunctionWithBodyId,
+    ) -> Maybe<Arc<Vec<ids::LocationId>>>;
+
     /// Returns the set of direct callees of a concrete function with a body after the inline phase.
     fn concrete_function_with_body_direct_callees(
         &self,
@@ -317,6 +341,11 @@ pub trait LoweringGroup: SemanticGroup + Upcast<dyn SemanticGroup> {
     #[salsa::invoke(crate::inline::priv_should_inline)]
     fn priv_should_inline(&self, function_id: ids::ConcreteFunctionWithBodyId) -> Maybe<bool>;
 
+    /// Returns the call graph of the program made of all the crates, used when optimizing the
+    /// program as a whole, see [crate::whole_program].
+    #[salsa::invoke(crate::whole_program::whole_program)]
+    fn whole_program(&self) -> Maybe<Arc<crate::whole_program::WholeProgram>>;
+
     /// Returns the configuration struct that controls the behavior of the optimization passes.
     #[salsa::input]
     fn optimization_config(&self) -> Arc<OptimizationConfig>;
@@ -388,8 +417,7 @@ fn function_with_body_lowering_with_borrow_check(
     function_id: ids::FunctionWithBodyId,
 ) -> Maybe<(Arc<FlatLowered>, Arc<PotentialDestructCalls>)> {
     let mut lowered = (*db.priv_function_with_body_lowering(function_id)?).clone();
-    let module_file_id = function_id.base_semantic_function(db).module_file_id(db.upcast());
-    let block_extra_calls = borrow_check(db, module_file_id, &mut lowered);
+    let block_extra_calls = borrow_check(db, function_id, &mut lowered);
     Ok((Arc::new(lowered), Arc::new(block_extra_calls)))
 }
 
@@ -401,10 +429,14 @@ fn function_with_body_lowering(
 }
 
 // * Concretizes lowered representation (monomorphization).
+// * Binds the constant arguments of specialized functions.
 fn priv_concrete_function_with_body_lowered_flat(
     db: &dyn LoweringGroup,
     function: ids::ConcreteFunctionWithBodyId,
 ) -> Maybe<Arc<FlatLowered>> {
+    if let ids::ConcreteFunctionWithBodyLongId::Specialized(specialized) = function.get(db) {
+        return Ok(Arc::new(specialized_function_lowered(db, specialized)?));
+    }
     let semantic_db = db.upcast();
     let mut lowered =
         (*db.function_with_body_lowering(function.function_with_body_id(db))?).clone();
@@ -2,7 +2,7 @@
 //! This is similar to the borrow checking algorithm, except we handle "undroppable drops" by adding
 //! destructor calls.
 
-use cairo_lang_defs::ids::LanguageElementId;
+use cairo_lang_defs::ids::{LanguageElementId, NamedLanguageElementId};
 use cairo_lang_semantic as semantic;
 use cairo_lang_semantic::corelib::{get_core_trait, unit_ty};
 use cairo_lang_semantic::items::functions::{GenericFunctionId, ImplGenericFunctionId};
@@ -11,7 +11,7 @@ use cairo_lang_semantic::ConcreteFunction;
 use cairo_lang_utils::extract_matches;
 use itertools::{chain, zip_eq, Itertools};
 use semantic::corelib::{core_module, get_ty_by_name};
-use semantic::{TypeId, TypeLongId};
+use semantic::{ConcreteTypeId, TypeId, TypeLongId};
 
 use crate::borrow_check::analysis::{Analyzer, BackAnalysis, StatementLocation};
 use crate::borrow_check::demand::{AuxCombine, DemandReporter};
@@ -19,6 +19,7 @@ use crate::borrow_check::Demand;
 use crate::db::LoweringGroup;
 use crate::ids::{ConcreteFunctionWithBodyId, SemanticFunctionIdEx};
 use crate::lower::context::{VarRequest, VariableAllocator};
+use crate::optimizations::config::DictSquashStrategy;
 use crate::{
     BlockId, FlatBlockEnd, FlatLowered, MatchInfo, Statement, StatementCall,
     StatementStructConstruct, StatementStructDestructure, VarRemapping, VarUsage, VariableId,
@@ -43,6 +44,7 @@ pub struct DestructAdder<'a> {
     lowered: &'a FlatLowered,
     destructions: Vec<DestructionEntry>,
     panic_ty: TypeId,
+    dict_squash_strategy: DictSquashStrategy,
 }
 
 /// A destructor call that needs to be added.
@@ -133,6 +135,15 @@ impl<'a> DemandReporter<VariableId, PanicState> for DestructAdder<'a> {
         };
         // If a non droppable variable gets out of scope, add a destruct call for it.
         if let Ok(impl_id) = var.destruct_impl.clone() {
+            // As the variable is unused after it gets out of scope, the squashing of a dictionary
+            // can be deferred to the end of the block.
+            let position = if self.dict_squash_strategy == DictSquashStrategy::Deferred
+                && is_felt252_dict(self.db, var.ty)
+            {
+                (position.0, self.lowered.blocks[position.0].statements.len())
+            } else {
+                position
+            };
             self.destructions.push(DestructionEntry::Plain(PlainDestructionEntry {
                 position,
                 var_id,
@@ -266,7 +277,20 @@ fn panic_ty(db: &dyn LoweringGroup) -> semantic::TypeId {
     get_ty_by_name(db.upcast(), core_module(db.upcast()), "Panic".into(), vec![])
 }
 
-/// Report borrow checking diagnostics.
+/// Returns whether the type is a `Felt252Dict`.
+fn is_felt252_dict(db: &dyn LoweringGroup, ty: TypeId) -> bool {
+    match db.lookup_intern_type(ty) {
+        TypeLongId::Concrete(ConcreteTypeId::Extern(extern_id)) => {
+            extern_id.extern_type_id(db.upcast()).name(db.upcast()) == "Felt252Dict"
+        }
+        _ => false,
+    }
+}
+
+/// Adds the destructor calls of the non droppable variables going out of scope.
+///
+/// The squashing of dictionaries is placed according to the configured
+/// [DictSquashStrategy].
 pub fn add_destructs(
     db: &dyn LoweringGroup,
     function_id: ConcreteFunctionWithBodyId,
@@ -275,7 +299,13 @@ pub fn add_destructs(
     if lowered.blocks.is_empty() {
         return;
     }
-    let checker = DestructAdder { db, lowered, destructions: vec![], panic_ty: panic_ty(db) };
+    let checker = DestructAdder {
+        db,
+        lowered,
+        destructions: vec![],
+        panic_ty: panic_ty(db),
+        dict_squash_strategy: db.optimization_config().dict_squash_strategy,
+    };
     let mut analysis = BackAnalysis::new(lowered, checker);
     let mut root_demand = analysis.get_root_info();
     root_demand.variables_introduced(
@@ -309,7 +339,7 @@ pub fn add_destructs(
 
     let location = variables.get_location(stable_ptr);
 
-    let DestructAdder { db: _, lowered: _, destructions, panic_ty } = analysis.analyzer;
+    let DestructAdder { destructions, panic_ty, .. } = analysis.analyzer;
 
     // We need to add the destructions in reverse order, so that they won't interfere with each
     // other.
@@ -6,6 +6,8 @@ use cairo_lang_syntax::node::{ast, TypedStablePtr};
 use cairo_lang_utils::{define_short_id, try_extract_matches};
 use defs::diagnostic_utils::StableLocation;
 use defs::ids::{ExternFunctionId, FreeFunctionId};
+use itertools::{zip_eq, Itertools};
+use semantic::items::constant::ConstValue;
 use semantic::items::functions::GenericFunctionId;
 use semantic::substitution::{GenericSubstitution, SubstitutionRewriter};
 use semantic::{ExprVar, Mutability};
@@ -84,6 +86,7 @@ impl SemanticFunctionWithBodyIdEx for cairo_lang_defs::ids::FunctionWithBodyId {
 pub enum ConcreteFunctionWithBodyLongId {
     Semantic(semantic::ConcreteFunctionWithBodyId),
     Generated(GeneratedFunction),
+    Specialized(SpecializedFunctionId),
 }
 define_short_id!(
     ConcreteFunctionWithBodyId,
@@ -109,6 +112,9 @@ impl ConcreteFunctionWithBodyLongId {
                     element,
                 }
             }
+            ConcreteFunctionWithBodyLongId::Specialized(specialized) => {
+                return specialized.base(db).function_with_body_id(db);
+            }
         };
         db.intern_lowering_function_with_body(long_id)
     }
@@ -119,6 +125,9 @@ impl ConcreteFunctionWithBodyLongId {
             ConcreteFunctionWithBodyLongId::Generated(GeneratedFunction { parent, .. }) => {
                 parent.substitution(semantic_db)
             }
+            ConcreteFunctionWithBodyLongId::Specialized(specialized) => {
+                specialized.base(db).substitution(db)
+            }
         }
     }
     pub fn function_id(&self, db: &dyn LoweringGroup) -> Maybe<FunctionId> {
@@ -130,22 +139,29 @@ impl ConcreteFunctionWithBodyLongId {
             ConcreteFunctionWithBodyLongId::Generated(generated) => {
                 FunctionLongId::Generated(*generated)
             }
+            ConcreteFunctionWithBodyLongId::Specialized(specialized) => {
+                FunctionLongId::Specialized(*specialized)
+            }
         };
         Ok(db.intern_lowering_function(long_id))
     }
     pub fn base_semantic_function(
         &self,
-        _db: &dyn LoweringGroup,
+        db: &dyn LoweringGroup,
     ) -> semantic::ConcreteFunctionWithBodyId {
         match *self {
             ConcreteFunctionWithBodyLongId::Semantic(id) => id,
             ConcreteFunctionWithBodyLongId::Generated(generated) => generated.parent,
+            ConcreteFunctionWithBodyLongId::Specialized(specialized) => {
+                specialized.base(db).base_semantic_function(db)
+            }
         }
     }
     pub fn name(&self, db: &dyn LoweringGroup) -> SmolStr {
         match self {
             ConcreteFunctionWithBodyLongId::Semantic(semantic) => semantic.name(db.upcast()),
             ConcreteFunctionWithBodyLongId::Generated(generated) => generated.name(db),
+            ConcreteFunctionWithBodyLongId::Specialized(specialized) => specialized.name(db),
         }
     }
 }
@@ -174,6 +190,9 @@ impl ConcreteFunctionWithBodyId {
         self.get(db).name(db)
     }
     pub fn signature(&self, db: &dyn LoweringGroup) -> Maybe<Signature> {
+        if let ConcreteFunctionWithBodyLongId::Specialized(specialized) = self.get(db) {
+            return specialized.signature(db);
+        }
         let generic_signature = self.function_with_body_id(db).signature(db)?;
         let substitution = self.substitution(db)?;
         SubstitutionRewriter { db: db.upcast(), substitution: &substitution }
@@ -207,6 +226,9 @@ impl ConcreteFunctionWithBodyId {
                     db.function_body(parent_id)?.exprs[generated.element].stable_ptr().untyped(),
                 )
             }
+            ConcreteFunctionWithBodyLongId::Specialized(specialized) => {
+                specialized.base(db).stable_location(db)?
+            }
         })
     }
 }
@@ -218,6 +240,8 @@ pub enum FunctionLongId {
     Semantic(semantic::FunctionId),
     /// A function generated by the compiler.
     Generated(GeneratedFunction),
+    /// A function specialized on constant arguments by the compiler.
+    Specialized(SpecializedFunctionId),
 }
 define_short_id!(FunctionId, FunctionLongId, LoweringGroup, lookup_intern_lowering_function);
 impl FunctionLongId {
@@ -231,6 +255,9 @@ impl FunctionLongId {
                 ConcreteFunctionWithBodyLongId::Semantic(body)
             }
             FunctionLongId::Generated(generated) => return Ok(Some(generated.body(db))),
+            FunctionLongId::Specialized(specialized) => {
+                ConcreteFunctionWithBodyLongId::Specialized(specialized)
+            }
         };
         Ok(Some(db.intern_lowering_concrete_function_with_body(long_id)))
     }
@@ -240,21 +267,30 @@ impl FunctionLongId {
                 Ok(Signature::from_semantic(db, db.concrete_function_signature(*semantic)?))
             }
             FunctionLongId::Generated(generated) => generated.body(db).signature(db),
+            FunctionLongId::Specialized(specialized) => specialized.signature(db),
         }
     }
     pub fn name(&self, db: &dyn LoweringGroup) -> SmolStr {
         match *self {
             FunctionLongId::Semantic(semantic) => semantic.name(db.upcast()),
             FunctionLongId::Generated(generated) => generated.name(db),
+            FunctionLongId::Specialized(specialized) => specialized.name(db),
         }
     }
     /// Returns the full path of the relevant semantic function:
     /// - If the function itself is semantic (non generated), its own full path.
     /// - If the function is generated, then its (semantic) parent's full path.
+    /// - If the function is specialized, then the full path of the function it specializes.
     pub fn semantic_full_path(&self, db: &dyn LoweringGroup) -> String {
         match self {
             FunctionLongId::Semantic(id) => id.full_name(db.upcast()),
             FunctionLongId::Generated(generated) => generated.parent.full_path(db.upcast()),
+            FunctionLongId::Specialized(specialized) => {
+                specialized.base(db).function_id(db).map_or_else(
+                    |_| specialized.base(db).name(db).into(),
+                    |function_id| function_id.semantic_full_path(db),
+                )
+            }
         }
     }
 }
@@ -299,6 +335,9 @@ impl<'a> DebugWithDb<dyn LoweringGroup + 'a> for FunctionLongId {
             FunctionLongId::Generated(generated) => {
                 write!(f, "{}", generated.name(db))
             }
+            FunctionLongId::Specialized(specialized) => {
+                write!(f, "{}", specialized.name(db))
+            }
         }
     }
 }
@@ -316,8 +355,86 @@ impl GeneratedFunction {
             ConcreteFunctionWithBodyLongId::Generated(GeneratedFunction { parent, element });
         db.intern_lowering_concrete_function_with_body(long_id)
     }
+    /// Returns a name for the generated function, stable across builds.
+    ///
+    /// The name consists of the full name of the parent, including its generic arguments, and the
+    /// index of the loop among the loops of the parent, e.g. `test::foo::<u32>[loop0]`.
+    pub fn name(&self, db: &dyn LoweringGroup) -> SmolStr {
+        let semantic_db = db.upcast();
+        let parent_name = match self.parent.concrete(semantic_db) {
+            Ok(concrete) => concrete.full_name(semantic_db),
+            Err(_) => self.parent.full_path(semantic_db),
+        };
+        match self.loop_index(db) {
+            Some(index) => format!("{parent_name}[loop{index}]"),
+            None => format!("{parent_name}[expr{}]", self.element.index()),
+        }
+        .into()
+    }
+
+    /// Returns the index of the generating loop among the loops of the parent function body.
+    fn loop_index(&self, db: &dyn LoweringGroup) -> Option<usize> {
+        let body = db.function_body(self.parent.function_with_body_id(db.upcast())).ok()?;
+        body.exprs
+            .iter()
+            .filter(|(_, expr)| matches!(expr, semantic::Expr::Loop(_) | semantic::Expr::While(_)))
+            .position(|(expr_id, _)| expr_id == self.element)
+    }
+}
+
+/// A function specialized on the constant values of some of its arguments, e.g. fixed lengths or
+/// configuration flags, so that the constants are folded through its body.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SpecializedFunction {
+    /// The function being specialized.
+    pub base: ConcreteFunctionWithBodyId,
+    /// The constant value of each parameter of the base function, or `None` for the parameters
+    /// which remain parameters of the specialized function.
+    pub args: Vec<Option<ConstValue>>,
+}
+define_short_id!(
+    SpecializedFunctionId,
+    SpecializedFunction,
+    LoweringGroup,
+    lookup_intern_specialized_function
+);
+impl SpecializedFunctionId {
+    pub fn lookup(&self, db: &dyn LoweringGroup) -> SpecializedFunction {
+        db.lookup_intern_specialized_function(*self)
+    }
+    pub fn base(&self, db: &dyn LoweringGroup) -> ConcreteFunctionWithBodyId {
+        self.lookup(db).base
+    }
+    pub fn body(&self, db: &dyn LoweringGroup) -> ConcreteFunctionWithBodyId {
+        db.intern_lowering_concrete_function_with_body(ConcreteFunctionWithBodyLongId::Specialized(
+            *self,
+        ))
+    }
+    /// Returns the signature of the base function, without the parameters bound to constants.
+    pub fn signature(&self, db: &dyn LoweringGroup) -> Maybe<Signature> {
+        let SpecializedFunction { base, args } = self.lookup(db);
+        let mut signature = base.signature(db)?;
+        signature.params = zip_eq(signature.params, args)
+            .filter_map(|(param, arg)| arg.is_none().then_some(param))
+            .collect();
+        Ok(signature)
+    }
+    /// Returns a name for the specialized function, consisting of the full name of the base
+    /// function and the constant arguments, e.g. `test::foo[specialized(_, 5)]`.
     pub fn name(&self, db: &dyn LoweringGroup) -> SmolStr {
-        format!("{}[expr{}]", self.parent.full_path(db.upcast()), self.element.index()).into()
+        let SpecializedFunction { base, args } = self.lookup(db);
+        let base_name = match base.function_id(db) {
+            Ok(function_id) => format!("{:?}", function_id.lookup(db).debug(db)),
+            Err(_) => base.name(db).into(),
+        };
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                Some(value) => format!("{:?}", value.debug(db)),
+                None => "_".into(),
+            })
+            .join(", ");
+        format!("{base_name}[specialized({args})]").into()
     }
 }
 
@@ -21,6 +21,7 @@ use crate::diagnostic::{LoweringDiagnostic, LoweringDiagnosticKind, LoweringDiag
 use crate::ids::{ConcreteFunctionWithBodyId, FunctionWithBodyId};
 use crate::lower::context::{VarRequest, VariableAllocator};
 use crate::utils::{Rebuilder, RebuilderEx};
+use crate::whole_program::flag_whole_program;
 use crate::{
     BlockId, FlatBlock, FlatBlockEnd, FlatLowered, Statement, StatementCall, VarRemapping,
     VariableId,
@@ -64,12 +65,17 @@ pub fn priv_should_inline(
         function_id.function_with_body_id(db).base_semantic_function(db),
     )?;
 
-    Ok(match config {
+    let should_inline = match config {
         InlineConfiguration::Never(_) => false,
         InlineConfiguration::Should(_) => true,
         InlineConfiguration::Always(_) => true,
-        InlineConfiguration::None => should_inline_lowered(db, function_id)?,
-    })
+        InlineConfiguration::None => {
+            should_inline_lowered(db, function_id)?
+                || (flag_whole_program(db) && db.whole_program()?.has_single_call_site(function_id))
+        }
+    };
+    log::debug!("Inlining decision for `{}`: {should_inline}.", function_id.name(db));
+    Ok(should_inline)
 }
 
 // A heuristic to decide if a function without an inline attribute should be inlined.
@@ -81,7 +87,7 @@ fn should_inline_lowered(
     // The inline heuristics optimization flag only applies to non-trivial small functions.
     // Functions which contains only a call or a literal are always inlined.
 
-    let weight_of_blocks = ApproxCasmInlineWeight::new(db, &lowered).lowered_weight(&lowered);
+    let weight_of_blocks = function_inline_weight(db, function_id)?;
 
     if weight_of_blocks < inline_small_functions_threshold(db).into_or_panic() {
         return Ok(true);
@@ -368,6 +374,16 @@ pub fn apply_inlining(
     Ok(())
 }
 
+/// Returns the approximate weight of the given function, after its own inlining, see
+/// [ApproxCasmInlineWeight].
+pub fn function_inline_weight(
+    db: &dyn LoweringGroup,
+    function_id: ConcreteFunctionWithBodyId,
+) -> Maybe<isize> {
+    let lowered = db.inlined_function_with_body_lowered(function_id)?;
+    Ok(ApproxCasmInlineWeight::new(db, &lowered).lowered_weight(&lowered))
+}
+
 /// Returns the threshold, in number of lowering statements, below which a function is marked as
 /// `should_inline`.
 fn inline_small_functions_threshold(db: &dyn LoweringGroup) -> usize {
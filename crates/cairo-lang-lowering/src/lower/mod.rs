@@ -3,6 +3,8 @@ use std::vec;
 use block_builder::BlockBuilder;
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_diagnostics::{Diagnostics, Maybe};
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::FlagId;
 use cairo_lang_semantic::corelib;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::TypedStablePtr;
@@ -14,8 +16,8 @@ use itertools::{chain, izip, zip_eq, Itertools};
 use num_bigint::{BigInt, Sign};
 use num_traits::ToPrimitive;
 use semantic::corelib::{
-    core_felt252_ty, core_submodule, get_core_function_id, get_core_ty_by_name, get_function_id,
-    never_ty, unit_ty,
+    core_array_felt252_ty, core_felt252_ty, core_submodule, get_core_function_id,
+    get_core_ty_by_name, get_function_id, never_ty, unit_ty,
 };
 use semantic::items::constant::{value_as_const_value, ConstValue};
 use semantic::items::structure::SemanticStructEx;
@@ -1079,7 +1081,25 @@ fn lower_expr_function_call(
 
     // If the function is panic(), do something special.
     if expr.function == get_core_function_id(ctx.db.upcast(), "panic".into(), vec![]) {
-        let [input] = <[_; 1]>::try_from(arg_inputs).ok().unwrap();
+        let [mut input] = <[_; 1]>::try_from(arg_inputs).ok().unwrap();
+        if flag_revert_panic_abi(ctx.db) {
+            let encode_revert_reason = get_function_id(
+                ctx.db.upcast(),
+                core_submodule(ctx.db.upcast(), "panics"),
+                "encode_revert_reason".into(),
+                vec![],
+            );
+            input = generators::Call {
+                function: encode_revert_reason.lowered(ctx.db),
+                inputs: vec![input],
+                coupon_input: None,
+                extra_ret_tys: vec![],
+                ret_tys: vec![core_array_felt252_ty(ctx.db.upcast())],
+                location,
+            }
+            .add(ctx, &mut builder.statements)
+            .returns[0];
+        }
         return Err(LoweringFlowError::Panic(input, location));
     }
 
@@ -1621,6 +1641,12 @@ fn lower_expr_assignment(
     Ok(LoweredExpr::Tuple { exprs: vec![], location })
 }
 
+/// Returns the value of the `revert_panic_abi` flag, or `false` if the flag is not set.
+fn flag_revert_panic_abi(db: &dyn LoweringGroup) -> bool {
+    db.get_flag(FlagId::new(db.upcast(), "revert_panic_abi"))
+        .is_some_and(|flag| *flag == Flag::RevertPanicAbi(true))
+}
+
 /// Allocates and empty block in `ctx`.
 fn alloc_empty_block(ctx: &mut LoweringContext<'_, '_>) -> BlockId {
     ctx.blocks.alloc_empty()
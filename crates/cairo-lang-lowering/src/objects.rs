@@ -170,6 +170,8 @@ pub struct FlatBlock {
     /// Note: Inner blocks might end with a `return`, which will exit the function in the middle.
     /// Note: Match is a possible statement, which means it has control flow logic inside, but
     /// after its execution is completed, the flow returns to the following statement of the block.
+    /// Note: Unlike blocks and variables, statements are owned by their block rather than
+    /// allocated in an arena of the function.
     pub statements: Vec<Statement>,
     /// Describes how this block ends: returns to the caller or exits the function.
     pub end: FlatBlockEnd,
@@ -274,6 +276,18 @@ impl Statement {
             Statement::Desnap(stmt) => std::slice::from_ref(&stmt.output),
         }
     }
+
+    pub fn outputs_mut(&mut self) -> &mut [VariableId] {
+        match self {
+            Statement::Const(stmt) => std::slice::from_mut(&mut stmt.output),
+            Statement::Call(stmt) => stmt.outputs.as_mut_slice(),
+            Statement::StructConstruct(stmt) => std::slice::from_mut(&mut stmt.output),
+            Statement::StructDestructure(stmt) => stmt.outputs.as_mut_slice(),
+            Statement::EnumConstruct(stmt) => std::slice::from_mut(&mut stmt.output),
+            Statement::Snapshot(stmt) => stmt.outputs.as_mut_slice(),
+            Statement::Desnap(stmt) => std::slice::from_mut(&mut stmt.output),
+        }
+    }
     pub fn location(&self) -> Option<LocationId> {
         // TODO(Gil): Add location to all statements.
         match &self {
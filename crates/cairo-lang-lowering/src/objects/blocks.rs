@@ -31,6 +31,11 @@ impl<T: Default> BlocksBuilder<T> {
     pub fn new() -> Self {
         Self(vec![])
     }
+    /// Creates a builder with room for `capacity` blocks, avoiding reallocations while they are
+    /// allocated.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
     pub fn alloc(&mut self, block: T) -> BlockId {
         let id = BlockId(self.0.len());
         self.0.push(block);
@@ -0,0 +1,241 @@
+#[cfg(test)]
+#[path = "bounds_check_elision_test.rs"]
+mod test;
+
+use std::sync::Arc;
+
+use cairo_lang_defs::ids::ModuleItemId;
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_semantic::items::constant::ConstValue;
+use cairo_lang_semantic::{corelib, TypeId};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::db::LoweringGroup;
+use crate::ids::{ConcreteFunctionWithBodyId, LocationId};
+use crate::{
+    BlockId, FlatBlockEnd, FlatLowered, MatchExternInfo, MatchInfo, Statement, StatementConst,
+    StatementDesnap, StatementStructDestructure, VarRemapping, VarUsage, VariableId,
+};
+
+/// A key identifying the value of a variable by the way it was computed.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+enum ValueKey {
+    /// A snapshot of the value of the variable.
+    Snapshot(VariableId),
+    /// The value of the variable with the snapshot removed.
+    Desnap(VariableId),
+    /// A member of the struct value of the variable.
+    Member(VariableId, usize),
+    /// A constant value of a type.
+    Const(TypeId, ConstValue),
+}
+
+/// Identifies variables holding the same value. As the lowering is in static single assignment
+/// form, the identification holds regardless of where the variables are defined.
+#[derive(Default)]
+struct ValueNumbering {
+    /// Maps variables to the first variable found with the same value.
+    equivalent_vars: UnorderedHashMap<VariableId, VariableId>,
+    /// The first variable found with each value.
+    vars: UnorderedHashMap<ValueKey, VariableId>,
+    /// The value of each of the variables in `vars`.
+    keys: UnorderedHashMap<VariableId, ValueKey>,
+}
+impl ValueNumbering {
+    /// Returns the first variable found with the same value as `var`.
+    fn canonical(&self, var: VariableId) -> VariableId {
+        *self.equivalent_vars.get(&var).unwrap_or(&var)
+    }
+
+    /// Records that `var` holds the same value as the canonical variable `canonical`.
+    fn record_equivalent(&mut self, var: VariableId, canonical: VariableId) {
+        if var != canonical {
+            self.equivalent_vars.insert(var, canonical);
+        }
+    }
+
+    /// Records that `var` holds the value identified by `key`.
+    fn record(&mut self, key: ValueKey, var: VariableId) {
+        if let Some(existing) = self.vars.get(&key) {
+            self.equivalent_vars.insert(var, *existing);
+        } else {
+            self.vars.insert(key.clone(), var);
+            self.keys.insert(var, key);
+        }
+    }
+
+    /// Records that `output` is the desnap of `input`.
+    fn record_desnap(&mut self, input: VariableId, output: VariableId) {
+        let input = self.canonical(input);
+        match self.keys.get(&input) {
+            // A desnap of a snapshot is the original value.
+            Some(ValueKey::Snapshot(original)) => self.record_equivalent(output, *original),
+            // A desnap of a member of a snapshot is the member of the original value.
+            Some(ValueKey::Member(snapshot, member)) => {
+                let key = match self.keys.get(snapshot) {
+                    Some(ValueKey::Snapshot(original)) => ValueKey::Member(*original, *member),
+                    _ => ValueKey::Desnap(input),
+                };
+                self.record(key, output);
+            }
+            _ => self.record(ValueKey::Desnap(input), output),
+        }
+    }
+}
+
+/// The successful bounds checks available at a point of the function, mapping the (canonical)
+/// array snapshot and index variables of an `array_get` call to the variable holding its result.
+type CheckedAccesses = OrderedHashMap<(VariableId, VariableId), VariableId>;
+
+/// Elides `array_get` bounds checks that are proven to succeed by a dominating `array_get` of the
+/// same array and index, reusing the result of the dominating call instead.
+///
+/// Variables are compared by value, so accesses through different snapshots of the same array, or
+/// through copies of the same span, are identified. As loops are lowered into separate functions,
+/// only checks within a single loop iteration are elided.
+///
+/// Returns the locations of the elided checks. The blocks of the failing branches of the elided
+/// checks are left unreachable.
+pub fn elide_bounds_checks(db: &dyn LoweringGroup, lowered: &mut FlatLowered) -> Vec<LocationId> {
+    if lowered.blocks.is_empty() {
+        return vec![];
+    }
+    let array_module = corelib::core_submodule(db.upcast(), "array");
+    let Ok(Some(ModuleItemId::ExternFunction(array_get))) =
+        db.module_item_by_name(array_module, "array_get".into())
+    else {
+        unreachable!("`core::array::array_get` not found");
+    };
+
+    let mut values = ValueNumbering::default();
+
+    // The successful checks available at the start of each block, as the intersection of the ones
+    // available at the ends of its visited predecessors.
+    let mut block_checks: Vec<Option<CheckedAccesses>> = vec![None; lowered.blocks.len()];
+    let mut elided = vec![];
+    for block_id in topological_order(lowered) {
+        let mut checks = block_checks[block_id.0].take().unwrap_or_default();
+        let block = &mut lowered.blocks[block_id];
+        for stmt in &block.statements {
+            match stmt {
+                Statement::Snapshot(stmt) => {
+                    let input = values.canonical(stmt.input.var_id);
+                    values.record_equivalent(stmt.original(), input);
+                    values.record(ValueKey::Snapshot(input), stmt.snapshot());
+                }
+                Statement::Desnap(StatementDesnap { input, output }) => {
+                    values.record_desnap(input.var_id, *output);
+                }
+                Statement::StructDestructure(StatementStructDestructure { input, outputs }) => {
+                    let input = values.canonical(input.var_id);
+                    for (member, output) in outputs.iter().enumerate() {
+                        values.record(ValueKey::Member(input, member), *output);
+                    }
+                }
+                Statement::Const(StatementConst { value, output }) => {
+                    values.record(
+                        ValueKey::Const(lowered.variables[*output].ty, value.clone()),
+                        *output,
+                    );
+                }
+                Statement::Call(_)
+                | Statement::StructConstruct(_)
+                | Statement::EnumConstruct(_) => {}
+            }
+        }
+
+        let mut successors = vec![];
+        let mut elided_end = None;
+        match &block.end {
+            FlatBlockEnd::Goto(target, _) => successors.push((*target, checks)),
+            FlatBlockEnd::Match {
+                info: MatchInfo::Extern(MatchExternInfo { function, inputs, arms, location }),
+            } if function.get_extern(db) == Some(array_get) => {
+                let key = (values.canonical(inputs[0].var_id), values.canonical(inputs[1].var_id));
+                // The arms are ordered as the variants of the returned `Option`, so the first arm
+                // is the successful one.
+                let success_arm = &arms[0];
+                let result_var = success_arm.var_ids[0];
+                match checks.get(&key).copied() {
+                    Some(checked_var) if lowered.variables[checked_var].copyable.is_ok() => {
+                        elided.push(*location);
+                        let usage = VarUsage { var_id: checked_var, location: *location };
+                        let target = success_arm.block_id;
+                        elided_end = Some(FlatBlockEnd::Goto(
+                            target,
+                            VarRemapping { remapping: [(result_var, usage)].into_iter().collect() },
+                        ));
+                        successors.push((target, checks));
+                    }
+                    _ => {
+                        for arm in &arms[1..] {
+                            successors.push((arm.block_id, checks.clone()));
+                        }
+                        checks.insert(key, result_var);
+                        successors.push((success_arm.block_id, checks));
+                    }
+                }
+            }
+            FlatBlockEnd::Match { info } => {
+                successors.extend(info.arms().iter().map(|arm| (arm.block_id, checks.clone())));
+            }
+            FlatBlockEnd::Return(..) | FlatBlockEnd::Panic(_) | FlatBlockEnd::NotSet => {}
+        }
+        if let Some(end) = elided_end {
+            block.end = end;
+        }
+        for (successor, checks) in successors {
+            let successor_checks = &mut block_checks[successor.0];
+            *successor_checks = Some(match successor_checks.take() {
+                Some(existing) => {
+                    existing.into_iter().filter(|(key, var)| checks.get(key) == Some(var)).collect()
+                }
+                None => checks,
+            });
+        }
+    }
+    elided
+}
+
+/// Returns the blocks reachable from the root in a topological order, so that each block is
+/// visited after all of its reachable predecessors.
+fn topological_order(lowered: &FlatLowered) -> Vec<BlockId> {
+    let mut visited = vec![false; lowered.blocks.len()];
+    let mut post_order = vec![];
+    // Blocks are pushed along with a flag stating whether their successors were already handled.
+    let mut stack = vec![(BlockId::root(), false)];
+    while let Some((block_id, successors_handled)) = stack.pop() {
+        if successors_handled {
+            post_order.push(block_id);
+            continue;
+        }
+        if visited[block_id.0] {
+            continue;
+        }
+        visited[block_id.0] = true;
+        stack.push((block_id, true));
+        match &lowered.blocks[block_id].end {
+            FlatBlockEnd::Goto(target, _) => stack.push((*target, false)),
+            FlatBlockEnd::Match { info } => {
+                stack.extend(info.arms().iter().map(|arm| (arm.block_id, false)));
+            }
+            FlatBlockEnd::Return(..) | FlatBlockEnd::Panic(_) | FlatBlockEnd::NotSet => {}
+        }
+    }
+    post_order.reverse();
+    post_order
+}
+
+/// Query implementation of [crate::db::LoweringGroup::elided_bounds_checks].
+pub fn elided_bounds_checks(
+    db: &dyn LoweringGroup,
+    function: ConcreteFunctionWithBodyId,
+) -> Maybe<Arc<Vec<LocationId>>> {
+    if !db.optimization_config().elide_bounds_checks {
+        return Ok(Default::default());
+    }
+    // The elision is the first phase applied on top of the inlined lowering.
+    let mut lowered = (*db.inlined_function_with_body_lowered(function)?).clone();
+    Ok(Arc::new(elide_bounds_checks(db, &mut lowered)))
+}
@@ -0,0 +1,65 @@
+use std::ops::Deref;
+
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_semantic::test_utils::setup_test_function;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+
+use super::elide_bounds_checks;
+use crate::db::LoweringGroup;
+use crate::fmt::LoweredFormatter;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::test_utils::LoweringDatabaseForTesting;
+
+cairo_lang_test_utils::test_file_test!(
+    bounds_check_elision,
+    "src/optimizations/test_data",
+    {
+        bounds_check_elision: "bounds_check_elision",
+    },
+    test_bounds_check_elision
+);
+
+fn test_bounds_check_elision(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &mut LoweringDatabaseForTesting::default();
+    let (test_function, semantic_diagnostics) = setup_test_function(
+        db,
+        inputs["function"].as_str(),
+        inputs["function_name"].as_str(),
+        inputs["module_code"].as_str(),
+    )
+    .split();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+
+    let before = db.inlined_function_with_body_lowered(function_id).unwrap().deref().clone();
+    let lowering_diagnostics = db.module_lowering_diagnostics(test_function.module_id).unwrap();
+
+    let mut after = before.clone();
+    let elided_checks = elide_bounds_checks(db, &mut after)
+        .into_iter()
+        .map(|location| {
+            // The checks are inlined from the corelib, so the outermost call site is reported.
+            let call_site = location.all_locations(db).pop().unwrap();
+            call_site.syntax_node(db).get_text_without_trivia(db)
+        })
+        .join("\n");
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        (
+            "before".into(),
+            format!("{:?}", before.debug(&LoweredFormatter::new(db, &before.variables))),
+        ),
+        (
+            "after".into(),
+            format!("{:?}", after.debug(&LoweredFormatter::new(db, &after.variables))),
+        ),
+        ("elided_checks".into(), elided_checks),
+        ("lowering_diagnostics".into(), lowering_diagnostics.format(db)),
+    ]))
+}
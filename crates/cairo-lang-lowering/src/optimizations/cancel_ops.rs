@@ -50,7 +50,7 @@ pub fn cancel_ops(lowered: &mut FlatLowered) {
 
     // Rebuild the blocks with the new variable names.
     for block in lowered.blocks.iter_mut() {
-        *block = var_remapper.rebuild_block(block);
+        var_remapper.rebuild_block_in_place(block);
     }
 }
 
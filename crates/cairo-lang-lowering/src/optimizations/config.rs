@@ -12,6 +12,18 @@ use crate::ids::{FunctionId, FunctionLongId};
 // TODO(Gil): Expose this as a configuration in the project toml.
 const DEFAULT_INLINE_SMALL_FUNCTIONS_THRESHOLD: usize = 24;
 
+/// When the squashing of dictionaries going out of scope is emitted.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DictSquashStrategy {
+    /// Right after the last use of the dictionary.
+    #[default]
+    Eager,
+    /// At the end of the block the dictionary goes out of scope in, right before the function
+    /// returns or the flow branches, keeping the squashing away from the code using the
+    /// dictionary.
+    Deferred,
+}
+
 /// A configuration struct that controls the behavior of the optimization passes.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct OptimizationConfig {
@@ -20,6 +32,21 @@ pub struct OptimizationConfig {
     /// The size of functions (in lowering statements) below which they are marked as
     /// `should_inline`.
     pub inline_small_functions_threshold: usize,
+    /// Whether to elide array bounds checks that are proven to succeed, see
+    /// [crate::optimizations::bounds_check_elision::elide_bounds_checks]. Should be disabled for
+    /// audit builds, where the lowering is expected to match the source code.
+    pub elide_bounds_checks: bool,
+    /// The weight of functions at or below which the calls passing them constant arguments are
+    /// replaced by calls to versions specialized on these constants, or `None` to disable the
+    /// specialization, see [crate::optimizations::specialization::specialize_calls].
+    pub specialization_threshold: Option<usize>,
+    /// When the squashing of dictionaries going out of scope is emitted, see
+    /// [crate::destructs::add_destructs].
+    pub dict_squash_strategy: DictSquashStrategy,
+    /// Whether to replace dictionaries only ever accessed with a single constant key by their
+    /// value, avoiding their squashing, see
+    /// [crate::optimizations::single_key_dicts::replace_single_key_dicts].
+    pub replace_single_key_dicts: bool,
 }
 
 impl OptimizationConfig {
@@ -40,6 +67,30 @@ impl OptimizationConfig {
         self.inline_small_functions_threshold = inline_small_functions_threshold;
         self
     }
+    /// Sets whether to elide bounds checks that are proven to succeed.
+    pub fn with_elide_bounds_checks(mut self, elide_bounds_checks: bool) -> Self {
+        self.elide_bounds_checks = elide_bounds_checks;
+        self
+    }
+    /// Sets the threshold for specializing functions on constant arguments.
+    pub fn with_specialization_threshold(
+        mut self,
+        specialization_threshold: Option<usize>,
+    ) -> Self {
+        self.specialization_threshold = specialization_threshold;
+        self
+    }
+    /// Sets when the squashing of dictionaries going out of scope is emitted.
+    pub fn with_dict_squash_strategy(mut self, dict_squash_strategy: DictSquashStrategy) -> Self {
+        self.dict_squash_strategy = dict_squash_strategy;
+        self
+    }
+    /// Sets whether to replace dictionaries only accessed with a single constant key by their
+    /// value.
+    pub fn with_replace_single_key_dicts(mut self, replace_single_key_dicts: bool) -> Self {
+        self.replace_single_key_dicts = replace_single_key_dicts;
+        self
+    }
 }
 
 impl Default for OptimizationConfig {
@@ -47,6 +98,10 @@ impl Default for OptimizationConfig {
         Self {
             moveable_functions: vec![],
             inline_small_functions_threshold: DEFAULT_INLINE_SMALL_FUNCTIONS_THRESHOLD,
+            elide_bounds_checks: false,
+            specialization_threshold: None,
+            dict_squash_strategy: DictSquashStrategy::Eager,
+            replace_single_key_dicts: false,
         }
     }
 }
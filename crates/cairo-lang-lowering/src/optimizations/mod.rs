@@ -1,3 +1,4 @@
+pub mod bounds_check_elision;
 pub mod branch_inversion;
 pub mod cancel_ops;
 pub mod config;
@@ -7,6 +8,8 @@ pub mod remappings;
 pub mod reorder_statements;
 pub mod return_optimization;
 pub mod scrub_units;
+pub mod single_key_dicts;
+pub mod specialization;
 pub mod split_structs;
 pub mod strategy;
 pub mod var_renamer;
@@ -142,6 +142,6 @@ pub fn optimize_remappings(lowered: &mut FlatLowered) {
 
     // Rebuild the blocks without unnecessary remappings.
     for block in lowered.blocks.iter_mut() {
-        *block = ctx.rebuild_block(block);
+        ctx.rebuild_block_in_place(block);
     }
 }
@@ -0,0 +1,300 @@
+#[cfg(test)]
+#[path = "single_key_dicts_test.rs"]
+mod test;
+
+use std::sync::Arc;
+
+use cairo_lang_defs::ids::{ExternFunctionId, ModuleItemId, TraitFunctionId};
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_semantic::items::constant::ConstValue;
+use cairo_lang_semantic::items::functions::{GenericFunctionId, ImplGenericFunctionId};
+use cairo_lang_semantic::{corelib, TypeId};
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use id_arena::Arena;
+use itertools::Itertools;
+use num_bigint::BigInt;
+
+use super::var_renamer::VarRenamer;
+use crate::db::LoweringGroup;
+use crate::ids::{ConcreteFunctionWithBodyId, FunctionId, FunctionLongId, LocationId};
+use crate::optimizations::strategy::OptimizationPhase;
+use crate::utils::RebuilderEx;
+use crate::{
+    FlatBlock, FlatBlockEnd, FlatLowered, Statement, StatementCall, StatementConst, VarUsage,
+    Variable, VariableId,
+};
+
+/// The functions operating on dictionaries that are replaced.
+struct DictFunctions {
+    new: FunctionIdMatcher,
+    entry_get: FunctionIdMatcher,
+    entry_finalize: FunctionIdMatcher,
+    squash: FunctionIdMatcher,
+    /// The value types of the dictionaries that may be replaced, the ones with a constant zero
+    /// default value.
+    value_types: UnorderedHashSet<TypeId>,
+}
+
+/// Matches calls to a generic function, with any generic arguments.
+enum FunctionIdMatcher {
+    Extern(ExternFunctionId),
+    /// A function of a trait, implemented by any impl.
+    TraitFunction(TraitFunctionId),
+}
+impl FunctionIdMatcher {
+    fn matches(&self, db: &dyn LoweringGroup, function: FunctionId) -> bool {
+        match self {
+            FunctionIdMatcher::Extern(extern_id) => function.get_extern(db) == Some(*extern_id),
+            FunctionIdMatcher::TraitFunction(trait_function) => {
+                let FunctionLongId::Semantic(semantic) = function.lookup(db) else {
+                    return false;
+                };
+                matches!(
+                    semantic.get_concrete(db.upcast()).generic_function,
+                    GenericFunctionId::Impl(ImplGenericFunctionId { function, .. })
+                        if function == *trait_function
+                )
+            }
+        }
+    }
+}
+
+impl DictFunctions {
+    fn new(db: &dyn LoweringGroup) -> Self {
+        let dict_module = corelib::core_submodule(db.upcast(), "dict");
+        let get_extern = |name: &str| {
+            let Ok(Some(ModuleItemId::ExternFunction(id))) =
+                db.module_item_by_name(dict_module, name.into())
+            else {
+                unreachable!("`core::dict::{name}` not found");
+            };
+            FunctionIdMatcher::Extern(id)
+        };
+        let dict_trait = corelib::get_core_trait(db.upcast(), "Felt252DictTrait".into());
+        let squash = db.trait_function_by_name(dict_trait, "squash".into()).unwrap().unwrap();
+        let value_types =
+            ["felt252", "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128"]
+                .into_iter()
+                .map(|name| corelib::get_core_ty_by_name(db.upcast(), name.into(), vec![]))
+                .collect();
+        Self {
+            new: get_extern("felt252_dict_new"),
+            entry_get: get_extern("felt252_dict_entry_get"),
+            entry_finalize: get_extern("felt252_dict_entry_finalize"),
+            squash: FunctionIdMatcher::TraitFunction(squash),
+            value_types,
+        }
+    }
+}
+
+/// The changes replacing a dictionary by its value.
+struct DictReplacement {
+    /// The location of the creation of the dictionary.
+    location: LocationId,
+    /// The statement reading the default value of the key, replaced by a zero constant into the
+    /// given variable, if the key is accessed.
+    zero_value: Option<(usize, VariableId)>,
+    /// The statements to remove.
+    removed: Vec<usize>,
+    /// The variables holding previous values, renamed to the variables finalizing them.
+    renamed: Vec<(VariableId, VariableId)>,
+}
+
+/// Replaces dictionaries that are only ever accessed with a single constant key by the value of
+/// that key, removing their creation, accesses and squashing.
+///
+/// A dictionary is replaced if all of its uses are within the block creating it, and it is squashed
+/// at its end with the squashed dictionary left unused - as when it is destructed. As a single key
+/// can't have inconsistent accesses, skipping the squashing is sound.
+///
+/// Returns the locations of the creations of the replaced dictionaries.
+pub fn replace_single_key_dicts(
+    db: &dyn LoweringGroup,
+    lowered: &mut FlatLowered,
+) -> Vec<LocationId> {
+    if lowered.blocks.is_empty() {
+        return vec![];
+    }
+    let functions = DictFunctions::new(db);
+    // Note that constants can be collected across blocks as the lowering is in static single
+    // assignment form.
+    let consts: UnorderedHashMap<VariableId, BigInt> = lowered
+        .blocks
+        .iter()
+        .flat_map(|(_, block)| &block.statements)
+        .filter_map(|stmt| match stmt {
+            Statement::Const(StatementConst { value: ConstValue::Int(value), output }) => {
+                Some((*output, value.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    let used_vars = used_vars(lowered);
+
+    let mut renamer = VarRenamer::default();
+    let mut locations = vec![];
+    for block in lowered.blocks.iter_mut() {
+        let replacements = block
+            .statements
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, stmt)| match stmt {
+                Statement::Call(call) if functions.new.matches(db, call.function) => {
+                    find_replacement(
+                        db,
+                        &functions,
+                        &consts,
+                        &used_vars,
+                        &lowered.variables,
+                        block,
+                        idx,
+                    )
+                }
+                _ => None,
+            })
+            .collect_vec();
+        let mut removed = vec![];
+        for replacement in replacements {
+            if let Some((idx, output)) = replacement.zero_value {
+                block.statements[idx] = Statement::Const(StatementConst {
+                    value: ConstValue::Int(BigInt::from(0)),
+                    output,
+                });
+            }
+            removed.extend(replacement.removed);
+            for (prev_value, value) in replacement.renamed {
+                renamer.renamed_vars.insert(prev_value, value);
+            }
+            locations.push(replacement.location);
+        }
+        for idx in removed.into_iter().sorted().rev() {
+            block.statements.remove(idx);
+        }
+    }
+    if !renamer.renamed_vars.is_empty() {
+        for block in lowered.blocks.iter_mut() {
+            renamer.rebuild_block_in_place(block);
+        }
+    }
+    locations
+}
+
+/// Follows the uses of the dictionary created by the statement at `new_idx` of the block, and
+/// returns how to replace it if it is only accessed with a single constant key.
+fn find_replacement(
+    db: &dyn LoweringGroup,
+    functions: &DictFunctions,
+    consts: &UnorderedHashMap<VariableId, BigInt>,
+    used_vars: &UnorderedHashSet<VariableId>,
+    variables: &Arena<Variable>,
+    block: &FlatBlock,
+    new_idx: usize,
+) -> Option<DictReplacement> {
+    let Statement::Call(StatementCall { outputs, location, .. }) = &block.statements[new_idx]
+    else {
+        unreachable!("Expected a call creating a dictionary.");
+    };
+    let mut replacement = DictReplacement {
+        location: *location,
+        zero_value: None,
+        removed: vec![new_idx],
+        renamed: vec![],
+    };
+    // The variable currently holding the dictionary, or its entry while it is accessed.
+    let mut dict = outputs[0];
+    let mut entry = None;
+    let mut key = None;
+    // The variable holding the current value of the key, `None` while it has the default value.
+    let mut value = None;
+    for (idx, stmt) in block.statements.iter().enumerate().skip(new_idx + 1) {
+        if !stmt.inputs().iter().any(|input| input.var_id == dict || Some(input.var_id) == entry) {
+            continue;
+        }
+        let Statement::Call(StatementCall { function, inputs, outputs, .. }) = stmt else {
+            return None;
+        };
+        let function = *function;
+        match (&inputs[..], &outputs[..]) {
+            (
+                [VarUsage { var_id: dict_var, .. }, VarUsage { var_id: key_var, .. }],
+                [entry_var, prev_var],
+            ) if *dict_var == dict && functions.entry_get.matches(db, function) => {
+                let key_value = consts.get(key_var)?;
+                if *key.get_or_insert(key_value) != key_value {
+                    return None;
+                }
+                match value.take() {
+                    Some(value) => {
+                        replacement.removed.push(idx);
+                        replacement.renamed.push((*prev_var, value));
+                    }
+                    None if functions.value_types.contains(&variables[*prev_var].ty) => {
+                        replacement.zero_value = Some((idx, *prev_var));
+                    }
+                    None => return None,
+                }
+                entry = Some(*entry_var);
+            }
+            (
+                [VarUsage { var_id: entry_var, .. }, VarUsage { var_id: new_value, .. }],
+                [dict_var],
+            ) if Some(*entry_var) == entry && functions.entry_finalize.matches(db, function) => {
+                replacement.removed.push(idx);
+                value = Some(*new_value);
+                dict = *dict_var;
+                entry = None;
+            }
+            ([VarUsage { var_id: dict_var, .. }], [squashed])
+                if *dict_var == dict
+                    && !used_vars.contains(squashed)
+                    && functions.squash.matches(db, function) =>
+            {
+                replacement.removed.push(idx);
+                return Some(replacement);
+            }
+            _ => return None,
+        }
+    }
+    // The dictionary outlives the block.
+    None
+}
+
+/// Returns the variables used anywhere in the lowering.
+fn used_vars(lowered: &FlatLowered) -> UnorderedHashSet<VariableId> {
+    let mut used = UnorderedHashSet::default();
+    for (_, block) in lowered.blocks.iter() {
+        for stmt in &block.statements {
+            used.extend(stmt.inputs().iter().map(|input| input.var_id));
+        }
+        match &block.end {
+            FlatBlockEnd::Goto(_, remapping) => {
+                used.extend(remapping.values().map(|src| src.var_id));
+            }
+            FlatBlockEnd::Return(vars, _) => used.extend(vars.iter().map(|var| var.var_id)),
+            FlatBlockEnd::Match { info } => {
+                used.extend(info.inputs().iter().map(|input| input.var_id));
+            }
+            FlatBlockEnd::Panic(var) => {
+                used.insert(var.var_id);
+            }
+            FlatBlockEnd::NotSet => {}
+        }
+    }
+    used
+}
+
+/// Query implementation of [crate::db::LoweringGroup::single_key_dicts].
+pub fn single_key_dicts(
+    db: &dyn LoweringGroup,
+    function: ConcreteFunctionWithBodyId,
+) -> Maybe<Arc<Vec<LocationId>>> {
+    if !db.optimization_config().replace_single_key_dicts {
+        return Ok(Default::default());
+    }
+    // The replacement directly follows the elision of bounds checks on top of the inlined
+    // lowering.
+    let mut lowered = (*db.inlined_function_with_body_lowered(function)?).clone();
+    OptimizationPhase::ElideBoundsChecks.apply(db, function, &mut lowered)?;
+    Ok(Arc::new(replace_single_key_dicts(db, &mut lowered)))
+}
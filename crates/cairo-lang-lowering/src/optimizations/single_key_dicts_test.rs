@@ -0,0 +1,72 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_semantic::test_utils::setup_test_function;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+
+use super::replace_single_key_dicts;
+use crate::db::LoweringGroup;
+use crate::fmt::LoweredFormatter;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::optimizations::config::OptimizationConfig;
+use crate::test_utils::LoweringDatabaseForTesting;
+
+cairo_lang_test_utils::test_file_test!(
+    single_key_dicts,
+    "src/optimizations/test_data",
+    {
+        single_key_dicts: "single_key_dicts",
+    },
+    test_single_key_dicts
+);
+
+fn test_single_key_dicts(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &mut LoweringDatabaseForTesting::new_empty();
+    db.set_optimization_config(Arc::new(
+        OptimizationConfig::default().with_replace_single_key_dicts(true),
+    ));
+    let (test_function, semantic_diagnostics) = setup_test_function(
+        db,
+        inputs["function"].as_str(),
+        inputs["function_name"].as_str(),
+        inputs["module_code"].as_str(),
+    )
+    .split();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+
+    let before = db.inlined_function_with_body_lowered(function_id).unwrap().deref().clone();
+    let lowering_diagnostics = db.module_lowering_diagnostics(test_function.module_id).unwrap();
+
+    let mut after = before.clone();
+    let replaced_dicts = replace_single_key_dicts(db, &mut after);
+    assert_eq!(*db.single_key_dicts(function_id).unwrap(), replaced_dicts);
+    let replaced_dicts = replaced_dicts
+        .into_iter()
+        .map(|location| {
+            // The dictionaries are created by the corelib, so the outermost call site is reported.
+            let call_site = location.all_locations(db).pop().unwrap();
+            call_site.syntax_node(db).get_text_without_trivia(db)
+        })
+        .join("\n");
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        (
+            "before".into(),
+            format!("{:?}", before.debug(&LoweredFormatter::new(db, &before.variables))),
+        ),
+        (
+            "after".into(),
+            format!("{:?}", after.debug(&LoweredFormatter::new(db, &after.variables))),
+        ),
+        ("replaced_dicts".into(), replaced_dicts),
+        ("lowering_diagnostics".into(), lowering_diagnostics.format(db)),
+    ]))
+}
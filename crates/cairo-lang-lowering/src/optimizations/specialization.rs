@@ -0,0 +1,152 @@
+#[cfg(test)]
+#[path = "specialization_test.rs"]
+mod test;
+
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_semantic::items::constant::ConstValue;
+use cairo_lang_utils::casts::IntoOrPanic;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::{zip_eq, Itertools};
+
+use crate::db::LoweringGroup;
+use crate::ids::{
+    ConcreteFunctionWithBodyId, ConcreteFunctionWithBodyLongId, FunctionLongId,
+    SpecializedFunction, SpecializedFunctionId,
+};
+use crate::inline::function_inline_weight;
+use crate::{
+    BlockId, DependencyType, FlatLowered, Statement, StatementCall, StatementConst, VariableId,
+};
+
+/// Replaces the calls passing constant arguments to small functions with calls to versions of the
+/// functions specialized on these constants, where the constants are folded through by the
+/// optimizations of the specialized functions.
+///
+/// A specialized function is shared by all the calls passing it the same constants, e.g. a helper
+/// frequently called with a fixed length or configuration flag is specialized once.
+/// Only functions whose weight is at most
+/// [crate::optimizations::config::OptimizationConfig::specialization_threshold] are specialized.
+/// The specializations are reported in the debug log, next to the inlining decisions.
+pub fn specialize_calls(
+    db: &dyn LoweringGroup,
+    function: ConcreteFunctionWithBodyId,
+    lowered: &mut FlatLowered,
+) -> Maybe<()> {
+    let Some(threshold) = db.optimization_config().specialization_threshold else {
+        return Ok(());
+    };
+    if lowered.blocks.is_empty() {
+        return Ok(());
+    }
+
+    // Note that the constants are valid in all the blocks, as the lowering is in static single
+    // assignment form.
+    let consts: UnorderedHashMap<VariableId, ConstValue> = lowered
+        .blocks
+        .iter()
+        .flat_map(|(_, block)| &block.statements)
+        .filter_map(|statement| match statement {
+            Statement::Const(StatementConst { value, output }) if value.is_fully_concrete() => {
+                Some((*output, value.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    if consts.is_empty() {
+        return Ok(());
+    }
+
+    for block_id in (0..lowered.blocks.len()).map(BlockId) {
+        for statement in lowered.blocks[block_id].statements.iter_mut() {
+            let Statement::Call(call) = statement else {
+                continue;
+            };
+            let Some(specialized) = specialized_callee(db, function, call, &consts, threshold)?
+            else {
+                continue;
+            };
+            call.inputs = zip_eq(std::mem::take(&mut call.inputs), specialized.lookup(db).args)
+                .filter_map(|(input, arg)| arg.is_none().then_some(input))
+                .collect();
+            log::debug!(
+                "Specialization decision for a call in `{}`: calling `{}`.",
+                function.name(db),
+                specialized.name(db)
+            );
+            call.function = db.intern_lowering_function(FunctionLongId::Specialized(specialized));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the version of the callee of the given call specialized on its constant arguments, or
+/// `None` if the callee should be called as is.
+fn specialized_callee(
+    db: &dyn LoweringGroup,
+    caller: ConcreteFunctionWithBodyId,
+    call: &StatementCall,
+    consts: &UnorderedHashMap<VariableId, ConstValue>,
+    threshold: usize,
+) -> Maybe<Option<SpecializedFunctionId>> {
+    if call.with_coupon {
+        return Ok(None);
+    }
+    let Some(callee) = call.function.body(db)? else {
+        return Ok(None);
+    };
+    if callee == caller || matches!(callee.get(db), ConcreteFunctionWithBodyLongId::Specialized(_))
+    {
+        return Ok(None);
+    }
+
+    let signature = callee.signature(db)?;
+    let args = zip_eq(&signature.params, &call.inputs)
+        .map(|(param, input)| {
+            // The values of reference parameters are returned, so they remain parameters.
+            if signature.extra_rets.contains(param) {
+                None
+            } else {
+                consts.get(&input.var_id).cloned()
+            }
+        })
+        .collect_vec();
+    if args.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+
+    // A specialized copy of a function in a cycle would add a cycle not broken by the
+    // `withdraw_gas` calls of the original cycle.
+    if db.in_cycle(callee.function_with_body_id(db), DependencyType::Call)? {
+        return Ok(None);
+    }
+    if function_inline_weight(db, callee)? > threshold.into_or_panic::<isize>() {
+        return Ok(None);
+    }
+    Ok(Some(db.intern_specialized_function(SpecializedFunction { base: callee, args })))
+}
+
+/// Returns the lowering of a specialized function - the lowering of its base function, with the
+/// parameters bound to constants replaced by constant statements at the start of its root block.
+pub fn specialized_function_lowered(
+    db: &dyn LoweringGroup,
+    specialized: SpecializedFunctionId,
+) -> Maybe<FlatLowered> {
+    let SpecializedFunction { base, args } = specialized.lookup(db);
+    let mut lowered = (*db.priv_concrete_function_with_body_lowered_flat(base)?).clone();
+    lowered.blocks.has_root()?;
+
+    let mut const_statements = vec![];
+    let mut parameters = vec![];
+    for (param, arg) in zip_eq(std::mem::take(&mut lowered.parameters), args) {
+        match arg {
+            Some(value) => {
+                const_statements.push(Statement::Const(StatementConst { value, output: param }))
+            }
+            None => parameters.push(param),
+        }
+    }
+    lowered.parameters = parameters;
+    lowered.signature = specialized.signature(db)?;
+    lowered.blocks[BlockId::root()].statements.splice(0..0, const_statements);
+    Ok(lowered)
+}
@@ -0,0 +1,90 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_semantic::test_utils::setup_test_function;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+
+use super::specialize_calls;
+use crate::db::LoweringGroup;
+use crate::fmt::LoweredFormatter;
+use crate::ids::{ConcreteFunctionWithBodyId, FunctionLongId};
+use crate::inline::apply_inlining;
+use crate::optimizations::config::OptimizationConfig;
+use crate::test_utils::LoweringDatabaseForTesting;
+use crate::Statement;
+
+cairo_lang_test_utils::test_file_test!(
+    specialization,
+    "src/optimizations/test_data",
+    {
+        specialization: "specialization",
+    },
+    test_specialization
+);
+
+fn test_specialization(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &mut LoweringDatabaseForTesting::new_empty();
+    let threshold = inputs["specialization_threshold"].parse().unwrap();
+    db.set_optimization_config(Arc::new(
+        OptimizationConfig::default().with_specialization_threshold(Some(threshold)),
+    ));
+    let (test_function, semantic_diagnostics) = setup_test_function(
+        db,
+        inputs["function"].as_str(),
+        inputs["function_name"].as_str(),
+        inputs["module_code"].as_str(),
+    )
+    .split();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+
+    let mut before =
+        db.concrete_function_with_body_postpanic_lowered(function_id).unwrap().deref().clone();
+    apply_inlining(db, function_id, &mut before).unwrap();
+    let lowering_diagnostics = db.module_lowering_diagnostics(test_function.module_id).unwrap();
+
+    let mut after = before.clone();
+    specialize_calls(db, function_id, &mut after).unwrap();
+
+    let specialized = after
+        .blocks
+        .iter()
+        .flat_map(|(_, block)| &block.statements)
+        .filter_map(|statement| match statement {
+            Statement::Call(call) => match call.function.lookup(db) {
+                FunctionLongId::Specialized(specialized) => Some(specialized),
+                _ => None,
+            },
+            _ => None,
+        })
+        .unique()
+        .map(|specialized| {
+            let lowered = db.inlined_function_with_body_lowered(specialized.body(db)).unwrap();
+            format!(
+                "{}:\n{:?}",
+                specialized.name(db),
+                lowered.debug(&LoweredFormatter::new(db, &lowered.variables))
+            )
+        })
+        .join("\n");
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        (
+            "before".into(),
+            format!("{:?}", before.debug(&LoweredFormatter::new(db, &before.variables))),
+        ),
+        (
+            "after".into(),
+            format!("{:?}", after.debug(&LoweredFormatter::new(db, &after.variables))),
+        ),
+        ("specialized".into(), specialized),
+        ("lowering_diagnostics".into(), lowering_diagnostics.format(db)),
+    ]))
+}
@@ -245,7 +245,7 @@ fn rebuild_blocks(lowered: &mut FlatLowered, split: SplitMapping) {
         }
 
         // Remap block variables.
-        *block = ctx.var_remapper.rebuild_block(block);
+        ctx.var_remapper.rebuild_block_in_place(block);
     }
 
     // Add all the end of block reconstructions.
@@ -5,6 +5,7 @@ use crate::db::LoweringGroup;
 use crate::ids::ConcreteFunctionWithBodyId;
 use crate::implicits::lower_implicits;
 use crate::inline::apply_inlining;
+use crate::optimizations::bounds_check_elision::elide_bounds_checks;
 use crate::optimizations::branch_inversion::branch_inversion;
 use crate::optimizations::cancel_ops::cancel_ops;
 use crate::optimizations::const_folding::const_folding;
@@ -12,6 +13,8 @@ use crate::optimizations::match_optimizer::optimize_matches;
 use crate::optimizations::remappings::optimize_remappings;
 use crate::optimizations::reorder_statements::reorder_statements;
 use crate::optimizations::return_optimization::return_optimization;
+use crate::optimizations::single_key_dicts::replace_single_key_dicts;
+use crate::optimizations::specialization::specialize_calls;
 use crate::optimizations::split_structs::split_structs;
 use crate::reorganize_blocks::reorganize_blocks;
 use crate::FlatLowered;
@@ -23,11 +26,14 @@ pub enum OptimizationPhase {
     BranchInversion,
     CancelOps,
     ConstFolding,
+    ElideBoundsChecks,
     OptimizeMatches,
     OptimizeRemappings,
     ReorderStatements,
     ReorganizeBlocks,
+    ReplaceSingleKeyDicts,
     ReturnOptimization,
+    SpecializeCalls,
     SplitStructs,
     /// The following is not really an optimization but we want to apply optimizations before and
     /// after it, so it is convenient to treat it as an optimization.
@@ -49,11 +55,22 @@ impl OptimizationPhase {
             OptimizationPhase::BranchInversion => branch_inversion(db, lowered),
             OptimizationPhase::CancelOps => cancel_ops(lowered),
             OptimizationPhase::ConstFolding => const_folding(db, lowered),
+            OptimizationPhase::ElideBoundsChecks => {
+                if db.optimization_config().elide_bounds_checks {
+                    elide_bounds_checks(db, lowered);
+                }
+            }
             OptimizationPhase::OptimizeMatches => optimize_matches(lowered),
             OptimizationPhase::OptimizeRemappings => optimize_remappings(lowered),
             OptimizationPhase::ReorderStatements => reorder_statements(db, lowered),
             OptimizationPhase::ReorganizeBlocks => reorganize_blocks(lowered),
+            OptimizationPhase::ReplaceSingleKeyDicts => {
+                if db.optimization_config().replace_single_key_dicts {
+                    replace_single_key_dicts(db, lowered);
+                }
+            }
             OptimizationPhase::ReturnOptimization => return_optimization(db, lowered),
+            OptimizationPhase::SpecializeCalls => specialize_calls(db, function, lowered)?,
             OptimizationPhase::SplitStructs => split_structs(lowered),
             OptimizationPhase::LowerImplicits => lower_implicits(db, function, lowered),
         }
@@ -94,6 +111,9 @@ impl OptimizationStrategyId {
 pub fn baseline_optimization_strategy(db: &dyn LoweringGroup) -> OptimizationStrategyId {
     db.intern_strategy(OptimizationStrategy(vec![
         OptimizationPhase::ApplyInlining,
+        // After the inlining, so that the constants passed to the inlined functions are
+        // propagated to their calls.
+        OptimizationPhase::SpecializeCalls,
         OptimizationPhase::ReturnOptimization,
         OptimizationPhase::ReorganizeBlocks,
         // The call to `reorder_statements` before and after `branch_inversion` is intentional.
@@ -118,6 +138,10 @@ pub fn baseline_optimization_strategy(db: &dyn LoweringGroup) -> OptimizationStr
 /// Query implementation of [crate::db::LoweringGroup::final_optimization_strategy].
 pub fn final_optimization_strategy(db: &dyn LoweringGroup) -> OptimizationStrategyId {
     db.intern_strategy(OptimizationStrategy(vec![
+        // Must remain the first phases, see `elided_bounds_checks` and `single_key_dicts`.
+        OptimizationPhase::ElideBoundsChecks,
+        OptimizationPhase::ReplaceSingleKeyDicts,
+        OptimizationPhase::ReorganizeBlocks,
         OptimizationPhase::LowerImplicits,
         OptimizationPhase::ReorganizeBlocks,
         OptimizationPhase::CancelOps,
@@ -39,9 +39,6 @@ pub fn reorganize_blocks(lowered: &mut FlatLowered) {
     analysis.get_root_info();
     let ctx = analysis.analyzer;
 
-    // Rebuild the blocks in the correct order.
-    let mut new_blocks = FlatBlocksBuilder::default();
-
     // Keep only blocks that can't be merged or have more than 1 incoming
     // goto.
     // Note that unreachable block were not added to `ctx.old_block_rev_order` during
@@ -57,6 +54,9 @@ pub fn reorganize_blocks(lowered: &mut FlatLowered) {
 
     let n_visited_blocks = old_block_rev_order.len();
 
+    // Rebuild the blocks in the correct order.
+    let mut new_blocks = FlatBlocksBuilder::with_capacity(n_visited_blocks);
+
     let mut rebuilder = RebuildContext {
         block_remapping: HashMap::from_iter(
             old_block_rev_order
@@ -72,14 +72,21 @@ pub fn reorganize_blocks(lowered: &mut FlatLowered) {
         *param = var_reassigner.map_var_id(*param);
     }
 
-    for block_id in old_block_rev_order.into_iter().rev() {
+    for mut block_id in old_block_rev_order.into_iter().rev() {
         let mut statements = vec![];
-
-        let mut block = &lowered.blocks[block_id];
         loop {
-            for stmt in &block.statements {
-                statements
-                    .push(var_reassigner.rebuild_statement(&rebuilder.rebuild_statement(stmt)));
+            // Each block is visited exactly once - either as the start of a new block, or merged
+            // into its single predecessor - so its statements can be moved rather than cloned.
+            let block = &mut lowered.blocks[block_id];
+            let first_new_statement = statements.len();
+            if statements.is_empty() {
+                statements = std::mem::take(&mut block.statements);
+            } else {
+                statements.append(&mut block.statements);
+            }
+            for stmt in &mut statements[first_new_statement..] {
+                rebuilder.rebuild_statement_in_place(stmt);
+                var_reassigner.rebuild_statement_in_place(stmt);
             }
             if let FlatBlockEnd::Goto(target_block_id, remappings) = &block.end {
                 if !rebuilder.block_remapping.contains_key(target_block_id) {
@@ -87,14 +94,14 @@ pub fn reorganize_blocks(lowered: &mut FlatLowered) {
                         rebuilder.rebuild_remapping(remappings).is_empty(),
                         "Remapping should be empty."
                     );
-                    block = &lowered.blocks[*target_block_id];
+                    block_id = *target_block_id;
                     continue;
                 }
             }
             break;
         }
 
-        let end = var_reassigner.rebuild_end(&rebuilder.rebuild_end(&block.end));
+        let end = var_reassigner.rebuild_end(&rebuilder.rebuild_end(&lowered.blocks[block_id].end));
         new_blocks.alloc(FlatBlock { statements, end });
     }
 
@@ -209,7 +216,11 @@ pub struct VarReassigner<'a> {
 
 impl<'a> VarReassigner<'a> {
     pub fn new(old_vars: &'a Arena<Variable>) -> Self {
-        Self { old_vars, new_vars: Default::default(), vars: UnorderedHashMap::default() }
+        Self {
+            old_vars,
+            new_vars: Arena::with_capacity(old_vars.len()),
+            vars: UnorderedHashMap::default(),
+        }
     }
 }
 
@@ -1,17 +1,21 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::diagnostic_utils::StableLocation;
 use cairo_lang_defs::ids::LanguageElementId;
 use cairo_lang_diagnostics::{DiagnosticNote, DiagnosticsBuilder};
+use cairo_lang_filesystem::db::FilesGroupEx;
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::FlagId;
 use cairo_lang_semantic as semantic;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::test_utils::{setup_test_expr, setup_test_function};
 use cairo_lang_syntax::node::{Terminal, TypedStablePtr};
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
-use cairo_lang_utils::{extract_matches, Upcast};
+use cairo_lang_utils::{extract_matches, Upcast, UpcastMut};
 use itertools::Itertools;
 use pretty_assertions::assert_eq;
 use semantic::test_utils::setup_test_module_ex;
@@ -20,6 +24,7 @@ use crate::db::LoweringGroup;
 use crate::diagnostic::{LoweringDiagnostic, LoweringDiagnosticKind};
 use crate::fmt::LoweredFormatter;
 use crate::ids::{ConcreteFunctionWithBodyId, LocationId};
+use crate::optimizations::config::{DictSquashStrategy, OptimizationConfig};
 use crate::test_utils::LoweringDatabaseForTesting;
 use crate::FlatLowered;
 
@@ -60,9 +65,18 @@ cairo_lang_test_utils::test_file_test!(
 
 fn test_function_lowering(
     inputs: &OrderedHashMap<String, String>,
-    _args: &OrderedHashMap<String, String>,
+    args: &OrderedHashMap<String, String>,
 ) -> TestRunnerResult {
-    let db = &mut LoweringDatabaseForTesting::default();
+    let db = &mut if args.get("dict_squash_strategy").is_some_and(|value| value == "deferred") {
+        // The shared database is a snapshot, so a new one is required for setting the config.
+        let mut db = LoweringDatabaseForTesting::new_empty();
+        db.set_optimization_config(Arc::new(
+            OptimizationConfig::default().with_dict_squash_strategy(DictSquashStrategy::Deferred),
+        ));
+        db
+    } else {
+        LoweringDatabaseForTesting::default()
+    };
     let (test_function, semantic_diagnostics) = setup_test_function(
         db,
         inputs["function"].as_str(),
@@ -210,3 +224,22 @@ fn test_sizes() {
         assert_eq!(size, expected_size, "Wrong size for type alias `{}`", ty.format(db.upcast()));
     }
 }
+
+#[test]
+fn test_revert_panic_abi() {
+    let db = &mut LoweringDatabaseForTesting::new_empty();
+    let revert_panic_abi_flag_id = FlagId::new(db.upcast_mut(), "revert_panic_abi");
+    db.set_flag(revert_panic_abi_flag_id, Some(Arc::new(Flag::RevertPanicAbi(true))));
+    let test_function =
+        setup_test_function(db, "fn foo() { core::panics::panic(array!['reason']) }", "foo", "")
+            .unwrap();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+    let lowered = db.priv_concrete_function_with_body_lowered_flat(function_id).unwrap();
+    let lowering_format = formatted_lowered(db, &lowered);
+    // The panic data is encoded before panicking.
+    assert!(
+        lowering_format.contains("core::panics::encode_revert_reason"),
+        "Unexpected lowering: {lowering_format}"
+    );
+}
@@ -66,6 +66,19 @@ pub trait RebuilderEx: Rebuilder {
         statement
     }
 
+    /// Renames the var ids of the statement in place, reusing its existing allocations.
+    /// Equivalent to [RebuilderEx::rebuild_statement], for when the original statement is no
+    /// longer needed.
+    fn rebuild_statement_in_place(&mut self, statement: &mut Statement) {
+        for input in statement.inputs_mut() {
+            *input = self.map_var_usage(*input);
+        }
+        for output in statement.outputs_mut() {
+            *output = self.map_var_id(*output);
+        }
+        self.transform_statement(statement);
+    }
+
     /// Apply map_var_id to all the variable in the `remapping`.
     fn rebuild_remapping(&mut self, remapping: &VarRemapping) -> VarRemapping {
         let mut remapping = VarRemapping {
@@ -163,6 +176,16 @@ pub trait RebuilderEx: Rebuilder {
         self.transform_block(&mut block);
         block
     }
+
+    /// Renames the var and block ids of the block in place, reusing the allocations of its
+    /// statements.
+    fn rebuild_block_in_place(&mut self, block: &mut FlatBlock) {
+        for stmt in block.statements.iter_mut() {
+            self.rebuild_statement_in_place(stmt);
+        }
+        block.end = self.rebuild_end(&block.end);
+        self.transform_block(block);
+    }
 }
 
 impl<T: Rebuilder> RebuilderEx for T {}
@@ -0,0 +1,134 @@
+//! Analysis of the program as a whole, after merging the lowered functions of all the crates,
+//! used when the `whole_program` flag is set.
+//!
+//! Without the flag, every non-generic free function of a compiled crate is an entry point of the
+//! program, and only small functions are inlined. With the flag, crates whose functions are called
+//! from other crates are considered libraries - their functions are kept only if called, and a
+//! function with a single call site in the whole program is inlined into it.
+
+use std::sync::Arc;
+
+use cairo_lang_defs::ids::LanguageElementId;
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::{CrateId, FlagId};
+use cairo_lang_semantic::corelib::core_crate;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+
+use crate::db::LoweringGroup;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::DependencyType;
+
+#[cfg(test)]
+#[path = "whole_program_test.rs"]
+mod test;
+
+/// Returns the value of the `whole_program` flag, or `false` if the flag is not set.
+pub fn flag_whole_program(db: &dyn LoweringGroup) -> bool {
+    db.get_flag(FlagId::new(db.upcast(), "whole_program"))
+        .is_some_and(|flag| *flag == Flag::WholeProgram(true))
+}
+
+/// The call graph of the program made of all the crates of the database, other than the corelib.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WholeProgram {
+    /// The crates with functions called from other crates.
+    pub library_crates: UnorderedHashSet<CrateId>,
+    /// The entry points of the program - the non-generic free functions of the crates which are
+    /// not libraries.
+    pub roots: UnorderedHashSet<ConcreteFunctionWithBodyId>,
+    /// The number of call sites of each function reachable from the roots, in the lowering of the
+    /// functions before inlining.
+    call_sites: UnorderedHashMap<ConcreteFunctionWithBodyId, usize>,
+}
+impl WholeProgram {
+    /// Returns whether the function is not an entry point, and is called from a single place in
+    /// the program - so that inlining it does not increase the size of the program.
+    pub fn has_single_call_site(&self, function_id: ConcreteFunctionWithBodyId) -> bool {
+        !self.roots.contains(&function_id) && self.call_sites.get(&function_id) == Some(&1)
+    }
+}
+
+/// Query implementation of [LoweringGroup::whole_program].
+pub fn whole_program(db: &dyn LoweringGroup) -> Maybe<Arc<WholeProgram>> {
+    let core_crate = core_crate(db.upcast());
+    let mut crate_roots = OrderedHashMap::<CrateId, Vec<_>>::default();
+    for crate_id in db.crates() {
+        if crate_id == core_crate {
+            continue;
+        }
+        let roots = crate_roots.entry(crate_id).or_default();
+        for module_id in db.crate_modules(crate_id).iter() {
+            for (free_function_id, _) in db.module_free_functions(*module_id)?.iter() {
+                if let Some(function) =
+                    ConcreteFunctionWithBodyId::from_no_generics_free(db, *free_function_id)
+                {
+                    roots.push(function);
+                }
+            }
+        }
+    }
+
+    let mut library_crates = UnorderedHashSet::default();
+    visit_calls(db, crate_roots.values().flatten().copied(), |caller, callee| {
+        let caller_crate = owning_crate(db, caller);
+        let callee_crate = owning_crate(db, callee);
+        if caller_crate != callee_crate
+            && crate_roots.contains_key(&caller_crate)
+            && crate_roots.contains_key(&callee_crate)
+        {
+            library_crates.insert(callee_crate);
+        }
+    })?;
+    // Crates calling each other leave no entry points, so none of them is considered a library.
+    if library_crates.len() == crate_roots.len() {
+        library_crates.clear();
+    }
+
+    let roots = crate_roots
+        .into_iter()
+        .filter(|(crate_id, _)| !library_crates.contains(crate_id))
+        .flat_map(|(_, roots)| roots)
+        .collect::<Vec<_>>();
+    let mut call_sites = UnorderedHashMap::<_, usize>::default();
+    visit_calls(db, roots.iter().copied(), |_, callee| {
+        *call_sites.entry(callee).or_default() += 1;
+    })?;
+
+    Ok(Arc::new(WholeProgram { library_crates, roots: roots.into_iter().collect(), call_sites }))
+}
+
+/// Calls `handle_call` for every call site in the functions reachable from `roots`, with the
+/// calling function and the called function.
+fn visit_calls(
+    db: &dyn LoweringGroup,
+    roots: impl Iterator<Item = ConcreteFunctionWithBodyId>,
+    mut handle_call: impl FnMut(ConcreteFunctionWithBodyId, ConcreteFunctionWithBodyId),
+) -> Maybe<()> {
+    let mut visited = UnorderedHashSet::<_>::default();
+    let mut stack: Vec<_> = roots.collect();
+    while let Some(function_id) = stack.pop() {
+        if !visited.insert(function_id) {
+            continue;
+        }
+        for callee in db.concrete_function_with_body_postpanic_direct_callees_with_body(
+            function_id,
+            DependencyType::Call,
+        )? {
+            handle_call(function_id, callee);
+            stack.push(callee);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the crate of the given function.
+fn owning_crate(db: &dyn LoweringGroup, function_id: ConcreteFunctionWithBodyId) -> CrateId {
+    function_id
+        .base_semantic_function(db)
+        .function_with_body_id(db.upcast())
+        .parent_module(db.upcast())
+        .owning_crate(db.upcast())
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{ModuleId, NamedLanguageElementId};
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{
+    CrateId, CrateLongId, Directory, FileKind, FileLongId, VirtualFile,
+};
+use cairo_lang_semantic::db::SemanticGroup;
+use indoc::indoc;
+
+use crate::db::LoweringGroup;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::test_utils::LoweringDatabaseForTesting;
+
+/// Adds a crate with the given name and lib file content to the database.
+fn add_crate(db: &mut LoweringDatabaseForTesting, name: &str, content: &str) -> CrateId {
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(content.into()),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let crate_id = db.intern_crate(CrateLongId::Real(name.into()));
+    db.set_crate_config(
+        crate_id,
+        Some(CrateConfiguration::default_for_root(Directory::Virtual {
+            files: [("lib.cairo".into(), file_id)].into(),
+            dirs: Default::default(),
+        })),
+    );
+    crate_id
+}
+
+/// Returns the function with the given name in the root module of the given crate.
+fn free_function(
+    db: &LoweringDatabaseForTesting,
+    crate_id: CrateId,
+    name: &str,
+) -> ConcreteFunctionWithBodyId {
+    let free_function_id = db
+        .module_free_functions_ids(ModuleId::CrateRoot(crate_id))
+        .unwrap()
+        .iter()
+        .copied()
+        .find(|id| id.name(db) == name)
+        .unwrap();
+    ConcreteFunctionWithBodyId::from_no_generics_free(db, free_function_id).unwrap()
+}
+
+#[test]
+fn test_whole_program() {
+    let mut db = LoweringDatabaseForTesting::new_empty();
+    let lib = add_crate(
+        &mut db,
+        "lib",
+        indoc! {"
+            pub fn once(x: felt252) -> felt252 {
+                x + 1
+            }
+            pub fn twice(x: felt252) -> felt252 {
+                x * 2
+            }
+            pub fn unused(x: felt252) -> felt252 {
+                x
+            }
+        "},
+    );
+    let app = add_crate(
+        &mut db,
+        "app",
+        indoc! {"
+            fn main(x: felt252) -> felt252 {
+                lib::twice(lib::once(x)) + lib::twice(x)
+            }
+        "},
+    );
+    for crate_id in [lib, app] {
+        for module_id in db.crate_modules(crate_id).iter() {
+            db.module_semantic_diagnostics(*module_id).unwrap().expect_with_db(&db, "");
+        }
+    }
+
+    let whole_program = db.whole_program().unwrap();
+    assert!(whole_program.library_crates.contains(&lib));
+    assert!(!whole_program.library_crates.contains(&app));
+
+    let main = free_function(&db, app, "main");
+    assert!(whole_program.roots.contains(&main));
+    assert!(!whole_program.has_single_call_site(main));
+    let once = free_function(&db, lib, "once");
+    assert!(!whole_program.roots.contains(&once));
+    assert!(whole_program.has_single_call_site(once));
+    assert!(!whole_program.has_single_call_site(free_function(&db, lib, "twice")));
+    assert!(!whole_program.has_single_call_site(free_function(&db, lib, "unused")));
+}
@@ -1,12 +1,11 @@
 use cairo_lang_defs::patcher::{PatchBuilder, RewriteNode};
 use cairo_lang_defs::plugin::{
-    MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
-};
-use cairo_lang_syntax::attribute::structured::{
-    Attribute, AttributeArg, AttributeArgVariant, AttributeStructurize,
+    AttributeMacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
 };
+use cairo_lang_defs::token_stream::{AttributeMacroInput, Token, TokenStream};
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::{ast, Terminal, TypedStablePtr, TypedSyntaxNode};
 use cairo_lang_utils::try_extract_matches;
 use indoc::formatdoc;
@@ -18,11 +17,16 @@ pub struct PanicablePlugin;
 
 const PANIC_WITH_ATTR: &str = "panic_with";
 
-impl MacroPlugin for PanicablePlugin {
+impl AttributeMacroPlugin for PanicablePlugin {
+    fn attribute_name(&self) -> &'static str {
+        PANIC_WITH_ATTR
+    }
+
     fn generate_code(
         &self,
         db: &dyn SyntaxGroup,
         item_ast: ast::ModuleItem,
+        input: AttributeMacroInput,
         _metadata: &MacroPluginMetadata<'_>,
     ) -> PluginResult {
         let (declaration, attributes, visibility) = match item_ast {
@@ -39,11 +43,7 @@ impl MacroPlugin for PanicablePlugin {
             _ => return PluginResult::default(),
         };
 
-        generate_panicable_code(db, declaration, attributes, visibility)
-    }
-
-    fn declared_attributes(&self) -> Vec<String> {
-        vec![PANIC_WITH_ATTR.to_string()]
+        generate_panicable_code(db, declaration, attributes, visibility, input)
     }
 }
 
@@ -53,11 +53,9 @@ fn generate_panicable_code(
     declaration: ast::FunctionDeclaration,
     attributes: ast::AttributeList,
     visibility: ast::Visibility,
+    input: AttributeMacroInput,
 ) -> PluginResult {
     let mut attrs = attributes.query_attr(db, PANIC_WITH_ATTR);
-    if attrs.is_empty() {
-        return PluginResult::default();
-    }
     let mut diagnostics = vec![];
     if attrs.len() > 1 {
         let extra_attr = attrs.swap_remove(1);
@@ -67,7 +65,6 @@ fn generate_panicable_code(
         ));
         return PluginResult { code: None, diagnostics, remove_original_item: false };
     }
-    let attr = attrs.swap_remove(0);
 
     let signature = declaration.signature(db);
     let Some((inner_ty, success_variant, failure_variant)) =
@@ -80,19 +77,21 @@ fn generate_panicable_code(
         return PluginResult { code: None, diagnostics, remove_original_item: false };
     };
 
-    let attr = attr.structurize(db);
-
-    let Some((err_value, panicable_name)) = parse_arguments(db, &attr) else {
-        diagnostics.push(PluginDiagnostic::error(
-            attr.stable_ptr.untyped(),
-            "Failed to extract panic data attribute".into(),
-        ));
-        return PluginResult { code: None, diagnostics, remove_original_item: false };
+    let (err_value, panicable_name) = match parse_arguments(db, &input.args) {
+        Ok(args) => args,
+        Err(token) => {
+            let stable_ptr = token.map_or(input.attr.stable_ptr().untyped(), |t| t.stable_ptr);
+            diagnostics.push(PluginDiagnostic::error(
+                stable_ptr,
+                "Failed to extract panic data attribute".into(),
+            ));
+            return PluginResult { code: None, diagnostics, remove_original_item: false };
+        }
     };
     let mut builder = PatchBuilder::new(db);
     builder.add_node(visibility.as_syntax_node());
     builder.add_node(declaration.function_kw(db).as_syntax_node());
-    builder.add_modified(RewriteNode::new_trimmed(panicable_name.as_syntax_node()));
+    builder.add_modified(RewriteNode::mapped_text(&panicable_name.text, panicable_name.span));
     builder.add_node(declaration.generic_params(db).as_syntax_node());
     builder.add_node(signature.lparen(db).as_syntax_node());
     builder.add_node(signature.parameters(db).as_syntax_node());
@@ -132,7 +131,7 @@ fn generate_panicable_code(
                 "function_name".to_string(),
                 RewriteNode::new_trimmed(declaration.name(db).as_syntax_node()),
             ),
-            ("err_value".to_string(), RewriteNode::new_trimmed(err_value.as_syntax_node())),
+            ("err_value".to_string(), RewriteNode::mapped_text(&err_value.text, err_value.span)),
         ]
         .into(),
     ));
@@ -180,29 +179,28 @@ fn extract_success_ty_and_variants(
     }
 }
 
-/// Parse `#[panic_with(...)]` attribute arguments and return a tuple with error value and
-/// panicable function name.
-fn parse_arguments(
+/// Parses the arguments of a `#[panic_with(...)]` attribute, and returns the tokens of the error
+/// value and of the panicable function name.
+/// On failure, returns the first unexpected token, or `None` if tokens are missing.
+fn parse_arguments<'a>(
     db: &dyn SyntaxGroup,
-    attr: &Attribute,
-) -> Option<(ast::TerminalShortString, ast::TerminalIdentifier)> {
-    let [
-        AttributeArg {
-            variant: AttributeArgVariant::Unnamed { value: ast::Expr::ShortString(err_value), .. },
-            ..
-        },
-        AttributeArg {
-            variant: AttributeArgVariant::Unnamed { value: ast::Expr::Path(name), .. },
-            ..
-        },
-    ] = &attr.args[..]
-    else {
-        return None;
-    };
-
-    let [ast::PathSegment::Simple(segment)] = &name.elements(db)[..] else {
-        return None;
-    };
-
-    Some((err_value.clone(), segment.ident(db)))
+    args: &'a TokenStream,
+) -> Result<(&'a Token, &'a Token), Option<&'a Token>> {
+    let mut tokens = args.tokens.iter();
+    let err_value = tokens.next().ok_or(None)?;
+    if err_value.stable_ptr.kind(db) != SyntaxKind::TerminalShortString {
+        return Err(Some(err_value));
+    }
+    let comma = tokens.next().ok_or(None)?;
+    if comma.stable_ptr.kind(db) != SyntaxKind::TerminalComma {
+        return Err(Some(comma));
+    }
+    let name = tokens.next().ok_or(None)?;
+    if name.stable_ptr.kind(db) != SyntaxKind::TerminalIdentifier {
+        return Err(Some(name));
+    }
+    if let Some(extra) = tokens.next() {
+        return Err(Some(extra));
+    }
+    Ok((err_value, name))
 }
@@ -19,7 +19,8 @@ pub enum DeserializationError {
     #[error("PathError")]
     PathError,
 }
-const PROJECT_FILE_NAME: &str = "cairo_project.toml";
+/// The name of the Cairo project file, at the root of a project directory.
+pub const PROJECT_FILE_NAME: &str = "cairo_project.toml";
 
 /// Cairo project config, including its file content and metadata about the file.
 /// This file is expected to be at a root of a crate and specify the crate name and location and
@@ -34,6 +35,12 @@ pub struct ProjectConfig {
 /// Contents of a Cairo project config file.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProjectConfigContent {
+    /// Path to a corelib to use instead of the detected one, relative to the project file.
+    /// Allows patching the corelib, e.g. during corelib development or migration periods.
+    /// The corelib is shared by all the crates of the project, as there is a single core crate in
+    /// a database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corelib: Option<PathBuf>,
     pub crate_roots: OrderedHashMap<SmolStr, PathBuf>,
     /// Additional configurations for the crates.
     #[serde(default)]
@@ -64,12 +71,15 @@ impl ProjectConfig {
         Self::from_file(&directory.join(PROJECT_FILE_NAME))
     }
     pub fn from_file(filename: &Path) -> Result<Self, DeserializationError> {
-        let base_path = filename
+        let base_path: PathBuf = filename
             .parent()
             .and_then(|p| p.to_str())
             .ok_or(DeserializationError::PathError)?
             .into();
-        let content = toml::from_str(&std::fs::read_to_string(filename)?)?;
-        Ok(ProjectConfig { base_path, content, corelib: None })
+        let content: ProjectConfigContent = toml::from_str(&std::fs::read_to_string(filename)?)?;
+        let corelib = content.corelib.as_ref().map(|path| {
+            Directory::Real(if path.is_relative() { base_path.join(path) } else { path.clone() })
+        });
+        Ok(ProjectConfig { base_path, content, corelib })
     }
 }
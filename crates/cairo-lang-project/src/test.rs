@@ -7,6 +7,7 @@ use crate::{AllCratesConfig, ProjectConfigContent};
 #[test]
 fn test_serde() {
     let config = ProjectConfigContent {
+        corelib: None,
         crate_roots: [
             ("crate1".into(), "dir1".into()),
             ("crate2".into(), "dir2".into()),
@@ -19,6 +20,7 @@ fn test_serde() {
                 edition: Default::default(),
                 experimental_features: ExperimentalFeaturesConfig::default(),
                 cfg_set: Default::default(),
+                external: false,
             },
             override_map: [
                 (
@@ -27,6 +29,7 @@ fn test_serde() {
                         edition: Edition::V2023_10,
                         experimental_features: ExperimentalFeaturesConfig::default(),
                         cfg_set: Default::default(),
+                        external: true,
                     },
                 ),
                 (
@@ -36,8 +39,10 @@ fn test_serde() {
                         experimental_features: ExperimentalFeaturesConfig {
                             negative_impls: true,
                             coupons: false,
+                            allowed_features: Default::default(),
                         },
                         cfg_set: Default::default(),
+                        external: false,
                     },
                 ),
             ]
@@ -63,6 +68,7 @@ fn test_serde() {
 
             [config.override.crate1]
             edition = "2023_10"
+            external = true
 
             [config.override.crate1.experimental_features]
             negative_impls = false
@@ -106,3 +112,17 @@ fn test_serde_defaults() {
     let config: ProjectConfigContent = toml::from_str(config_str).unwrap();
     assert_eq!(result, toml::to_string(&config).unwrap());
 }
+
+#[test]
+fn test_serde_corelib() {
+    let config_str = indoc! { r#"
+        corelib = "../corelib/src"
+
+        [crate_roots]
+        crate1 = "dir1"
+    "# };
+
+    let config: ProjectConfigContent = toml::from_str(config_str).unwrap();
+    assert_eq!(config.corelib, Some("../corelib/src".into()));
+    assert_eq!(config, toml::from_str(&toml::to_string(&config).unwrap()).unwrap());
+}
@@ -57,6 +57,11 @@ impl DictManagerExecScope {
             .expect("The given value does not point to a known dictionary.")
     }
 
+    /// Returns the number of dictionaries allocated so far.
+    pub fn n_dicts(&self) -> usize {
+        self.trackers.len()
+    }
+
     /// Returns the index of the dict tracker corresponding to a given pointer to a dict segment.
     pub fn get_dict_infos_index(&self, dict_end: Relocatable) -> usize {
         self.get_dict_tracker(dict_end).idx
@@ -2,6 +2,7 @@ use std::any::Any;
 use std::borrow::Cow;
 use std::collections::{HashMap, VecDeque};
 use std::ops::{Deref, Shl};
+use std::sync::Arc;
 use std::vec::IntoIter;
 
 use ark_ff::fields::{Fp256, MontBackend, MontConfig};
@@ -42,6 +43,7 @@ use {ark_secp256k1 as secp256k1, ark_secp256r1 as secp256r1};
 
 use self::contract_address::calculate_contract_address;
 use self::dict_manager::DictSquashExecScope;
+use crate::gas_budget::GasTracker;
 use crate::short_string::{as_cairo_short_string, as_cairo_short_string_ex};
 use crate::{Arg, RunResultValue, SierraCasmRunner, StarknetExecutionResources};
 
@@ -104,6 +106,10 @@ pub struct CairoHintProcessor<'a> {
     /// Resources used during syscalls - does not include resources used during the current VM run.
     /// At the end of the run - adding both would result in the actual expected resource usage.
     pub syscalls_used_resources: StarknetExecutionResources,
+    /// The output printed during the run, if captured. Printed directly to stdout if `None`.
+    pub captured_output: Option<String>,
+    /// Tracks the gas checks of the run, if requested.
+    pub gas_tracker: Option<GasTracker>,
 }
 
 pub fn cell_ref_to_relocatable(cell_ref: &CellRef, vm: &VirtualMachine) -> Relocatable {
@@ -128,6 +134,11 @@ type Log = (Vec<Felt252>, Vec<Felt252>);
 // L2 to L1 message type signature
 type L2ToL1Message = (Felt252, Vec<Felt252>);
 
+/// A provider of the data of an external segment, given the name of the accessor and its
+/// serialized arguments. Returns the serialized data, or an error message.
+pub type ExternalSegmentProvider =
+    Arc<dyn Fn(&str, &[Felt252]) -> Result<Vec<Felt252>, String> + Send + Sync>;
+
 /// Execution scope for starknet related data.
 /// All values will be 0 and by default if not setup by the test.
 #[derive(Clone, Default)]
@@ -142,8 +153,33 @@ pub struct StarknetState {
     exec_info: ExecutionInfo,
     /// A mock history, mapping block number to the class hash.
     block_hash: HashMap<u64, Felt252>,
+    /// The providers of the external segments, by segment name.
+    external_segments: HashMap<String, ExternalSegmentProvider>,
 }
 impl StarknetState {
+    /// Registers `provider` as filling the external segment `name`, as declared by a
+    /// `#[starknet::external_segment]` trait of the same name.
+    pub fn register_external_segment(
+        &mut self,
+        name: impl Into<String>,
+        provider: impl Fn(&str, &[Felt252]) -> Result<Vec<Felt252>, String> + Send + Sync + 'static,
+    ) {
+        self.external_segments.insert(name.into(), Arc::new(provider));
+    }
+
+    /// Returns the events emitted by each contract that were not popped yet, in emission order,
+    /// sorted by contract address.
+    pub fn events(&self) -> Vec<(Felt252, Vec<Log>)> {
+        self.logs
+            .iter()
+            .filter(|(_, contract_logs)| !contract_logs.events.is_empty())
+            .map(|(contract, contract_logs)| {
+                (contract.clone(), contract_logs.events.iter().cloned().collect())
+            })
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .collect()
+    }
+
     /// Replaces the addresses in the context.
     pub fn open_caller_context(
         &mut self,
@@ -353,11 +389,13 @@ mod gas_costs {
     pub const SECP256K1_GET_POINT_FROM_X: usize = 260 * STEP + 29 * RANGE_CHECK;
     pub const SECP256K1_GET_XY: usize = 24 * STEP + 9 * RANGE_CHECK;
     pub const SECP256K1_MUL: usize = 121810 * STEP + 10739 * RANGE_CHECK;
+    pub const SECP256K1_MUL_ADD: usize = 152263 * STEP + 13424 * RANGE_CHECK;
     pub const SECP256K1_NEW: usize = 340 * STEP + 36 * RANGE_CHECK;
     pub const SECP256R1_ADD: usize = 254 * STEP + 29 * RANGE_CHECK;
     pub const SECP256R1_GET_POINT_FROM_X: usize = 260 * STEP + 29 * RANGE_CHECK;
     pub const SECP256R1_GET_XY: usize = 24 * STEP + 9 * RANGE_CHECK;
     pub const SECP256R1_MUL: usize = 121810 * STEP + 10739 * RANGE_CHECK;
+    pub const SECP256R1_MUL_ADD: usize = 152263 * STEP + 13424 * RANGE_CHECK;
     pub const SECP256R1_NEW: usize = 340 * STEP + 36 * RANGE_CHECK;
     pub const SEND_MESSAGE_TO_L1: usize = 50 * STEP;
     pub const STORAGE_READ: usize = 50 * STEP;
@@ -414,6 +452,29 @@ impl HintProcessorLogic for CairoHintProcessor<'_> {
         _constants: &HashMap<String, Felt252>,
     ) -> Result<(), HintError> {
         let hint = hint_data.downcast_ref::<Hint>().unwrap();
+        if let (
+            Some(captured_output),
+            Hint::Core(cairo_lang_casm::hints::CoreHintBase::Core(CoreHint::DebugPrint {
+                start,
+                end,
+            })),
+        ) = (&mut self.captured_output, hint)
+        {
+            captured_output.push_str(&format_for_debug(read_felts(vm, start, end)?.into_iter()));
+            return Ok(());
+        }
+        if let (
+            Some(gas_tracker),
+            Some(runner),
+            Hint::Core(cairo_lang_casm::hints::CoreHintBase::Core(CoreHint::TestLessThanOrEqual {
+                lhs,
+                rhs,
+                ..
+            })),
+        ) = (&mut self.gas_tracker, self.runner, hint)
+        {
+            gas_tracker.track_gas_check(runner, vm, &get_val(vm, lhs)?, &get_val(vm, rhs)?);
+        }
         let hint = match hint {
             Hint::Core(core_hint_base) => {
                 return execute_core_hint_base(vm, exec_scopes, core_hint_base);
@@ -730,6 +791,16 @@ impl<'a> CairoHintProcessor<'a> {
                     exec_scopes,
                 )
             }),
+            "Secp256k1MulAdd" => execute_handle_helper(&mut |system_buffer, gas_counter| {
+                secp256k1_mul_add(
+                    gas_counter,
+                    system_buffer.next_usize()?,
+                    system_buffer.next_u256()?,
+                    system_buffer.next_usize()?,
+                    system_buffer.next_u256()?,
+                    exec_scopes,
+                )
+            }),
             "Secp256k1GetPointFromX" => execute_handle_helper(&mut |system_buffer, gas_counter| {
                 secp256k1_get_point_from_x(
                     gas_counter,
@@ -765,6 +836,16 @@ impl<'a> CairoHintProcessor<'a> {
                     exec_scopes,
                 )
             }),
+            "Secp256r1MulAdd" => execute_handle_helper(&mut |system_buffer, gas_counter| {
+                secp256r1_mul_add(
+                    gas_counter,
+                    system_buffer.next_usize()?,
+                    system_buffer.next_u256()?,
+                    system_buffer.next_usize()?,
+                    system_buffer.next_u256()?,
+                    exec_scopes,
+                )
+            }),
             "Secp256r1GetPointFromX" => execute_handle_helper(&mut |system_buffer, gas_counter| {
                 secp256r1_get_point_from_x(
                     gas_counter,
@@ -1144,6 +1225,11 @@ impl<'a> CairoHintProcessor<'a> {
             )
             .expect("Internal runner error.");
         self.syscalls_used_resources += res.used_resources;
+        if let (Some(captured_output), Some(output)) =
+            (&mut self.captured_output, &res.captured_output)
+        {
+            captured_output.push_str(output);
+        }
         *gas_counter = res.gas_counter.unwrap().to_usize().unwrap();
         match res.value {
             RunResultValue::Success(value) => {
@@ -1259,9 +1345,30 @@ impl<'a> CairoHintProcessor<'a> {
                     res_segment.write_data(payload.iter())?;
                 }
             }
-            _ => Err(HintError::CustomHint(Box::from(format!(
-                "Unknown cheatcode selector: {selector}"
-            ))))?,
+            _ => {
+                let Some(provider) = self.starknet_state.external_segments.get(selector) else {
+                    Err(HintError::CustomHint(Box::from(format!(
+                        "Unknown cheatcode selector: {selector}"
+                    ))))?
+                };
+                // The inputs of an external segment are the accessor name followed by its
+                // serialized arguments.
+                let Some((accessor, args)) = inputs
+                    .split_first()
+                    .and_then(|(accessor, args)| Some((as_cairo_short_string(accessor)?, args)))
+                else {
+                    Err(HintError::CustomHint(Box::from(format!(
+                        "`{selector}` external segment invalid args: pass span of an array \
+                         starting with the accessor name",
+                    ))))?
+                };
+                let data = provider(&accessor, args).map_err(|err| {
+                    HintError::CustomHint(Box::from(format!(
+                        "`{selector}` external segment `{accessor}` failed: {err}"
+                    )))
+                })?;
+                res_segment.write_data(data.iter())?;
+            }
         }
         let res_segment_end = res_segment.ptr;
         insert_value_to_cellref!(vm, output_start, res_segment_start)?;
@@ -1393,6 +1500,26 @@ fn secp256k1_mul(
     Ok(SyscallResult::Success(vec![id.into()]))
 }
 
+/// Executes the `secp256k1_mul_add_syscall` syscall.
+fn secp256k1_mul_add(
+    gas_counter: &mut usize,
+    p0_id: usize,
+    scalar0: BigUint,
+    p1_id: usize,
+    scalar1: BigUint,
+    exec_scopes: &mut ExecutionScopes,
+) -> Result<SyscallResult, HintError> {
+    deduct_gas!(gas_counter, SECP256K1_MUL_ADD);
+
+    let ec = get_secp256k1_exec_scope(exec_scopes)?;
+    let p0 = &ec.ec_points[p0_id];
+    let p1 = &ec.ec_points[p1_id];
+    let sum = *p0 * secp256k1::Fr::from(scalar0) + *p1 * secp256k1::Fr::from(scalar1);
+    let id = ec.ec_points.len();
+    ec.ec_points.push(sum.into());
+    Ok(SyscallResult::Success(vec![id.into()]))
+}
+
 /// Executes the `secp256k1_get_point_from_x_syscall` syscall.
 fn secp256k1_get_point_from_x(
     gas_counter: &mut usize,
@@ -1520,6 +1647,26 @@ fn secp256r1_mul(
     Ok(SyscallResult::Success(vec![id.into()]))
 }
 
+/// Executes the `secp256r1_mul_add_syscall` syscall.
+fn secp256r1_mul_add(
+    gas_counter: &mut usize,
+    p0_id: usize,
+    scalar0: BigUint,
+    p1_id: usize,
+    scalar1: BigUint,
+    exec_scopes: &mut ExecutionScopes,
+) -> Result<SyscallResult, HintError> {
+    deduct_gas!(gas_counter, SECP256R1_MUL_ADD);
+
+    let ec = get_secp256r1_exec_scope(exec_scopes)?;
+    let p0 = &ec.ec_points[p0_id];
+    let p1 = &ec.ec_points[p1_id];
+    let sum = *p0 * secp256r1::Fr::from(scalar0) + *p1 * secp256r1::Fr::from(scalar1);
+    let id = ec.ec_points.len();
+    ec.ec_points.push(sum.into());
+    Ok(SyscallResult::Success(vec![id.into()]))
+}
+
 /// Executes the `secp256r1_get_point_from_x_syscall` syscall.
 fn secp256r1_get_point_from_x(
     gas_counter: &mut usize,
@@ -2216,6 +2363,54 @@ pub struct RunFunctionResult {
     pub ap: usize,
     /// The used resources after the run.
     pub used_resources: ExecutionResources,
+    /// The statistics of the memory segments after the run.
+    pub segment_stats: SegmentStats,
+}
+
+/// Statistics of the memory segments used by a run, complementing [ExecutionResources] for
+/// estimating the resources required for proving the run.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct SegmentStats {
+    /// The number of memory cells used in the segment of each builtin, by builtin name.
+    pub builtin_segment_cells: HashMap<String, usize>,
+    /// The number of dictionary segments created during the run.
+    pub n_dict_segments: usize,
+    /// The maximal value of ap during the run, relative to its initial value.
+    pub max_ap: usize,
+}
+impl SegmentStats {
+    /// Collects the statistics of the segments of a run that has ended and was relocated.
+    fn collect(vm: &VirtualMachine, runner: &CairoRunner) -> Self {
+        let builtin_segment_cells = vm
+            .get_builtin_runners()
+            .iter()
+            .map(|builtin| {
+                (
+                    builtin.name().to_string(),
+                    vm.get_segment_used_size(builtin.base()).unwrap_or_default(),
+                )
+            })
+            .collect();
+        let n_dict_segments = runner
+            .exec_scopes
+            .get_ref::<DictManagerExecScope>("dict_manager_exec_scope")
+            .map_or(0, |dict_manager_exec_scope| dict_manager_exec_scope.n_dicts());
+        let trace = vm.get_relocated_trace().unwrap();
+        let initial_ap = trace.first().map_or(0, |entry| entry.ap);
+        let max_ap =
+            trace.iter().map(|entry| entry.ap.saturating_sub(initial_ap)).max().unwrap_or_default();
+        Self { builtin_segment_cells, n_dict_segments, max_ap }
+    }
+}
+impl std::fmt::Display for SegmentStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "builtin segment cells:")?;
+        for (name, n_cells) in self.builtin_segment_cells.iter().sorted() {
+            writeln!(f, "    {name}: {n_cells}")?;
+        }
+        writeln!(f, "dict segments: {}", self.n_dict_segments)?;
+        writeln!(f, "max ap: {}", self.max_ap)
+    }
 }
 
 /// Runs `bytecode` on layout with prime, and returns the matching [RunFunctionResult].
@@ -2239,10 +2434,12 @@ pub fn run_function<'a, 'b: 'a>(
     let used_resources = runner
         .get_execution_resources(vm)
         .expect("Failed to get execution resources, but the run was successful.");
+    let segment_stats = SegmentStats::collect(vm, &runner);
     Ok(RunFunctionResult {
         memory: runner.relocated_memory,
         ap: vm.get_relocated_trace().unwrap().last().unwrap().ap,
         used_resources,
+        segment_stats,
     })
 }
 
@@ -2341,3 +2538,19 @@ where
 
     Some(format!("{full_words_string}{pending_word_string}"))
 }
+
+/// The selector of panic data encoded with the revert-with-reason ABI, i.e. the selector of
+/// `Error(string)`.
+pub const REVERT_WITH_REASON_SELECTOR: u32 = 0x08c379a0;
+
+/// Decodes panic data encoded with the revert-with-reason ABI, i.e. the selector followed by the
+/// length-prefixed reason, and returns the reason. Returns None if the data is not encoded this
+/// way.
+pub fn decode_revert_reason(values: &[Felt252]) -> Option<&[Felt252]> {
+    let [selector, len, reason @ ..] = values else {
+        return None;
+    };
+    (*selector == Felt252::from(REVERT_WITH_REASON_SELECTOR)
+        && len.to_usize() == Some(reason.len()))
+    .then_some(reason)
+}
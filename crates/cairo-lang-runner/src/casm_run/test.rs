@@ -1,6 +1,8 @@
 use cairo_felt::{felt_str, Felt252};
+use cairo_lang_casm::hints::StarknetHint;
 use cairo_lang_casm::inline::CasmContext;
 use cairo_lang_casm::{casm, deref};
+use cairo_lang_utils::bigint::BigIntAsHex;
 use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
 use cairo_vm::vm::runners::cairo_runner::RunResources;
 use cairo_vm::vm::vm_core::VirtualMachine;
@@ -12,7 +14,7 @@ use test_case::test_case;
 
 use super::format_for_debug;
 use crate::casm_run::contract_address::calculate_contract_address;
-use crate::casm_run::{run_function, RunFunctionResult};
+use crate::casm_run::{run_function, RunFunctionResult, SegmentStats};
 use crate::short_string::{as_cairo_short_string, as_cairo_short_string_ex};
 use crate::{build_hints_dict, CairoHintProcessor, StarknetState};
 
@@ -117,6 +119,8 @@ fn test_runner(function: CasmContext, n_returns: usize, expected: &[i128]) {
         starknet_state: StarknetState::default(),
         run_resources: RunResources::default(),
         syscalls_used_resources: Default::default(),
+        captured_output: None,
+        gas_tracker: None,
     };
     let bytecode: Vec<BigInt> = function
         .instructions
@@ -156,6 +160,8 @@ fn test_allocate_segment() {
         starknet_state: StarknetState::default(),
         run_resources: RunResources::default(),
         syscalls_used_resources: Default::default(),
+        captured_output: None,
+        gas_tracker: None,
     };
     let bytecode: Vec<BigInt> =
         casm.instructions.iter().flat_map(|instruction| instruction.assemble().encode()).collect();
@@ -177,6 +183,110 @@ fn test_allocate_segment() {
     assert_eq!(memory[ptr], Some(Felt252::from(1337)));
 }
 
+#[test]
+fn test_segment_stats() {
+    let casm = casm! {
+        [ap] = 1, ap++;
+        ap += 3;
+        [ap] = 2, ap++;
+        ret;
+    };
+
+    let (hints_dict, string_to_hint) = build_hints_dict(casm.instructions.iter());
+    let mut hint_processor = CairoHintProcessor {
+        runner: None,
+        string_to_hint,
+        starknet_state: StarknetState::default(),
+        run_resources: RunResources::default(),
+        syscalls_used_resources: Default::default(),
+        captured_output: None,
+        gas_tracker: None,
+    };
+    let bytecode: Vec<BigInt> =
+        casm.instructions.iter().flat_map(|instruction| instruction.assemble().encode()).collect();
+
+    let RunFunctionResult { segment_stats, .. } = run_function(
+        &mut VirtualMachine::new(true),
+        bytecode.iter(),
+        vec![],
+        |_| Ok(()),
+        &mut hint_processor,
+        hints_dict,
+    )
+    .expect("Running code failed.");
+    assert_eq!(
+        segment_stats,
+        SegmentStats { builtin_segment_cells: Default::default(), n_dict_segments: 0, max_ap: 5 }
+    );
+}
+
+#[test]
+fn test_external_segment() {
+    let price = BigInt::from_bytes_be(num_bigint::Sign::Plus, b"price");
+    let mut casm = casm! {
+        // The accessor name and its argument.
+        [ap] = price, ap++;
+        [ap] = 5, ap++;
+        %{ memory[ap] = segments.add() %}
+        [ap - 2] = [[ap + 0]];
+        [ap - 1] = [[ap + 0] + 1];
+        [ap + 1] = [ap + 0] + 2, ap++;
+        ap += 3;
+        ret;
+    };
+    casm.instructions[5].hints.push(
+        StarknetHint::Cheatcode {
+            selector: BigIntAsHex {
+                value: BigInt::from_bytes_be(num_bigint::Sign::Plus, b"PriceOracle"),
+            },
+            input_start: deref!([ap - 1]).into(),
+            input_end: deref!([ap]).into(),
+            output_start: deref!([ap + 1]),
+            output_end: deref!([ap + 2]),
+        }
+        .into(),
+    );
+
+    let mut starknet_state = StarknetState::default();
+    starknet_state.register_external_segment("PriceOracle", |accessor, args| match accessor {
+        "price" => Ok(vec![args[0].clone() * Felt252::from(100), Felt252::from(0)]),
+        _ => Err(format!("unknown accessor `{accessor}`")),
+    });
+    let (hints_dict, string_to_hint) = build_hints_dict(casm.instructions.iter());
+    let mut hint_processor = CairoHintProcessor {
+        runner: None,
+        string_to_hint,
+        starknet_state,
+        run_resources: RunResources::default(),
+        syscalls_used_resources: Default::default(),
+        captured_output: None,
+        gas_tracker: None,
+    };
+    let bytecode: Vec<BigInt> =
+        casm.instructions.iter().flat_map(|instruction| instruction.assemble().encode()).collect();
+
+    let RunFunctionResult { memory, ap, .. } = run_function(
+        &mut VirtualMachine::new(true),
+        bytecode.iter(),
+        vec![],
+        |_| Ok(()),
+        &mut hint_processor,
+        hints_dict,
+    )
+    .expect("Running code failed.");
+    let [output_start, output_end] = [ap - 2, ap - 1].map(|address| {
+        memory[address]
+            .as_ref()
+            .expect("Uninitialized value.")
+            .to_usize()
+            .expect("Number not in index range.")
+    });
+    assert_eq!(
+        memory[output_start..output_end],
+        [Some(Felt252::from(500)), Some(Felt252::from(0))]
+    );
+}
+
 #[test]
 fn test_as_cairo_short_string() {
     // Simple short strings.
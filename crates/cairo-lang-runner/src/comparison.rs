@@ -0,0 +1,203 @@
+//! Comparison of the runs of the same entry points compiled by two compiler configurations or
+//! versions, for qualifying a compiler upgrade.
+
+use std::fmt::Display;
+
+use cairo_felt::Felt252;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+
+use crate::{Arg, RunResultStarknet, RunResultValue, SierraCasmRunner, StarknetState};
+
+#[cfg(test)]
+#[path = "comparison_test.rs"]
+mod test;
+
+/// A run of an entry point to compare.
+pub struct EntryPointRun {
+    /// The suffix of the name of the function to run, as in [SierraCasmRunner::find_function].
+    pub name: String,
+    /// The arguments of the run.
+    pub args: Vec<Arg>,
+    /// The gas available for the run, if the programs are gas-checked.
+    pub available_gas: Option<usize>,
+    /// The starknet state the run starts in.
+    pub starknet_state: StarknetState,
+}
+
+/// An event emitted by a run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmittedEvent {
+    pub contract_address: Felt252,
+    pub keys: Vec<Felt252>,
+    pub data: Vec<Felt252>,
+}
+
+/// A difference between the runs of an entry point by the two compilations.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RunDifference {
+    /// Only one of the runs failed, or both failed differently.
+    Error { a: Option<String>, b: Option<String> },
+    /// The runs returned different values.
+    Value { a: RunResultValue, b: RunResultValue },
+    /// The runs consumed different amounts of gas.
+    Gas { a: usize, b: usize },
+    /// The runs emitted different events.
+    Events { a: Vec<EmittedEvent>, b: Vec<EmittedEvent> },
+    /// The runs called a syscall a different number of times.
+    Syscall { selector: String, a: usize, b: usize },
+}
+
+/// The comparison of the runs of an entry point.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryPointComparison {
+    /// The name of the run entry point.
+    pub name: String,
+    /// The differences between the runs, empty if they match.
+    pub differences: Vec<RunDifference>,
+}
+
+/// The comparison of the runs of all the entry points.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ComparisonReport {
+    pub entry_points: Vec<EntryPointComparison>,
+}
+impl ComparisonReport {
+    /// Returns whether any of the entry points ran differently.
+    pub fn has_differences(&self) -> bool {
+        self.entry_points.iter().any(|entry_point| !entry_point.differences.is_empty())
+    }
+}
+
+impl Display for ComparisonReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry_point in &self.entry_points {
+            if entry_point.differences.is_empty() {
+                writeln!(f, "{}: match", entry_point.name)?;
+                continue;
+            }
+            writeln!(f, "{}: {} differences", entry_point.name, entry_point.differences.len())?;
+            for difference in &entry_point.differences {
+                match difference {
+                    RunDifference::Error { a, b } => {
+                        writeln!(f, "  error: {a:?} != {b:?}")?;
+                    }
+                    RunDifference::Value { a, b } => writeln!(f, "  value: {a:?} != {b:?}")?,
+                    RunDifference::Gas { a, b } => writeln!(f, "  gas: {a} != {b}")?,
+                    RunDifference::Events { a, b } => {
+                        writeln!(f, "  events: {} != {} events", a.len(), b.len())?;
+                        for i in 0..a.len().max(b.len()) {
+                            if a.get(i) != b.get(i) {
+                                writeln!(f, "    #{i}: {:?} != {:?}", a.get(i), b.get(i))?;
+                            }
+                        }
+                    }
+                    RunDifference::Syscall { selector, a, b } => {
+                        writeln!(f, "  syscall `{selector}`: {a} != {b} calls")?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs each of the given entry points with the runners `a` and `b`, of the same code compiled by
+/// two compilations, and reports the differences between the runs.
+///
+/// The entry points are found by the suffix of their names in each program, as the ids of the
+/// functions may differ between the compilations.
+pub fn compare_runs(
+    a: &SierraCasmRunner,
+    b: &SierraCasmRunner,
+    runs: Vec<EntryPointRun>,
+) -> ComparisonReport {
+    let entry_points = runs
+        .into_iter()
+        .map(|run| {
+            let result_a = run_entry_point(a, &run);
+            let result_b = run_entry_point(b, &run);
+            let differences = match (result_a, result_b) {
+                (Ok(result_a), Ok(result_b)) => diff_results(&run, result_a, result_b),
+                (Err(a), Err(b)) if a == b => vec![],
+                (result_a, result_b) => {
+                    vec![RunDifference::Error { a: result_a.err(), b: result_b.err() }]
+                }
+            };
+            EntryPointComparison { name: run.name, differences }
+        })
+        .collect();
+    ComparisonReport { entry_points }
+}
+
+/// Runs the entry point with the runner, returning the error message on failure.
+fn run_entry_point(
+    runner: &SierraCasmRunner,
+    run: &EntryPointRun,
+) -> Result<RunResultStarknet, String> {
+    let func = runner.find_function(&run.name).map_err(|err| err.to_string())?;
+    runner
+        .run_function_with_starknet_context(
+            func,
+            &run.args,
+            run.available_gas,
+            run.starknet_state.clone(),
+        )
+        .map_err(|err| err.to_string())
+}
+
+/// Returns the differences between the results of two successful runs of an entry point.
+fn diff_results(
+    run: &EntryPointRun,
+    a: RunResultStarknet,
+    b: RunResultStarknet,
+) -> Vec<RunDifference> {
+    let mut differences = vec![];
+    if a.value != b.value {
+        differences.push(RunDifference::Value { a: a.value.clone(), b: b.value.clone() });
+    }
+    if let Some(available_gas) = run.available_gas {
+        let consumed_gas = |result: &RunResultStarknet| {
+            let remaining_gas = result.gas_counter.as_ref().and_then(|gas| gas.to_usize());
+            available_gas - remaining_gas.unwrap_or_default()
+        };
+        let (gas_a, gas_b) = (consumed_gas(&a), consumed_gas(&b));
+        if gas_a != gas_b {
+            differences.push(RunDifference::Gas { a: gas_a, b: gas_b });
+        }
+    }
+    let (events_a, events_b) =
+        (emitted_events(&a.starknet_state), emitted_events(&b.starknet_state));
+    if events_a != events_b {
+        differences.push(RunDifference::Events { a: events_a, b: events_b });
+    }
+    let syscalls_a = &a.used_resources.syscalls;
+    let syscalls_b = &b.used_resources.syscalls;
+    for selector in syscalls_a.keys().chain(syscalls_b.keys()).unique().sorted() {
+        let calls_a = syscalls_a.get(selector).copied().unwrap_or_default();
+        let calls_b = syscalls_b.get(selector).copied().unwrap_or_default();
+        if calls_a != calls_b {
+            differences.push(RunDifference::Syscall {
+                selector: selector.clone(),
+                a: calls_a,
+                b: calls_b,
+            });
+        }
+    }
+    differences
+}
+
+/// Returns the events emitted into the given state.
+fn emitted_events(starknet_state: &StarknetState) -> Vec<EmittedEvent> {
+    starknet_state
+        .events()
+        .into_iter()
+        .flat_map(|(contract_address, events)| {
+            events.into_iter().map(move |(keys, data)| EmittedEvent {
+                contract_address: contract_address.clone(),
+                keys,
+                data,
+            })
+        })
+        .collect()
+}
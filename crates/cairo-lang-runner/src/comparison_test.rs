@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use cairo_felt::Felt252;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::optimizations::config::OptimizationConfig;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+
+use super::{compare_runs, EmittedEvent, EntryPointRun, RunDifference};
+use crate::{RunResultValue, SierraCasmRunner};
+
+/// Code emitting an event per iteration of a loop, with a small helper function to inline.
+const EMITTING_CODE: &str = indoc! {"
+    fn double(x: felt252) -> felt252 {
+        x + x
+    }
+    fn emit_doubles(n: felt252) -> felt252 {
+        let mut i = 0;
+        while i != n {
+            starknet::syscalls::emit_event_syscall(
+                array![i].span(), array![double(i)].span()
+            ).unwrap();
+            i += 1;
+        };
+        double(n)
+    }
+"};
+
+/// Creates a runner for the given code, optionally avoiding the inlining of small functions.
+fn runner(code: &str, inline_small_functions: bool) -> SierraCasmRunner {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    if !inline_small_functions {
+        db.set_optimization_config(Arc::new(
+            OptimizationConfig::default().with_inline_small_functions_threshold(0),
+        ));
+    }
+    let test_module = setup_test_module(&db, code).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let program = Arc::unwrap_or_clone(
+        db.get_sierra_program(vec![test_module.crate_id]).expect("`get_sierra_program` failed."),
+    )
+    .program;
+    let program = replace_sierra_ids_in_program(&db, &program);
+    SierraCasmRunner::new(program, Some(Default::default()), OrderedHashMap::default(), None)
+        .unwrap()
+}
+
+fn emit_doubles_run(n: usize) -> EntryPointRun {
+    EntryPointRun {
+        name: "::emit_doubles".into(),
+        args: vec![Felt252::from(n).into()],
+        available_gas: Some(u32::MAX as usize),
+        starknet_state: Default::default(),
+    }
+}
+
+#[test]
+fn compare_configurations() {
+    let report = compare_runs(
+        &runner(EMITTING_CODE, true),
+        &runner(EMITTING_CODE, false),
+        vec![emit_doubles_run(3)],
+    );
+    // Avoiding inlining only changes the gas consumption.
+    let [entry_point] = &report.entry_points[..] else { panic!("Expected a single entry point.") };
+    assert_eq!(entry_point.name, "::emit_doubles");
+    let [RunDifference::Gas { a, b }] = &entry_point.differences[..] else {
+        panic!("Expected only a gas difference, got {:?}.", entry_point.differences);
+    };
+    assert!(a < b, "Inlining should save gas: {a} >= {b}.");
+}
+
+#[test]
+fn compare_versions() {
+    let changed_code = EMITTING_CODE.replace("x + x", "x * 3");
+    let report = compare_runs(
+        &runner(EMITTING_CODE, true),
+        &runner(&changed_code, true),
+        vec![
+            emit_doubles_run(2),
+            emit_doubles_run(0),
+            EntryPointRun {
+                name: "::missing".into(),
+                args: vec![],
+                available_gas: None,
+                starknet_state: Default::default(),
+            },
+        ],
+    );
+    assert!(report.has_differences());
+    let event = |i: usize, data: usize| EmittedEvent {
+        contract_address: Felt252::from(0),
+        keys: vec![Felt252::from(i)],
+        data: vec![Felt252::from(data)],
+    };
+    assert!(report.entry_points[0].differences.contains(&RunDifference::Value {
+        a: RunResultValue::Success(vec![Felt252::from(4)]),
+        b: RunResultValue::Success(vec![Felt252::from(6)]),
+    }));
+    assert!(report.entry_points[0].differences.contains(&RunDifference::Events {
+        a: vec![event(0, 0), event(1, 2)],
+        b: vec![event(0, 0), event(1, 3)],
+    }));
+    // For `n = 0` no events are emitted and the same value is returned.
+    assert!(report.entry_points[1].differences.iter().all(|difference| {
+        !matches!(difference, RunDifference::Value { .. } | RunDifference::Events { .. })
+    }));
+    // Failing identically on both sides is not a difference.
+    assert!(report.entry_points[2].differences.is_empty());
+}
@@ -0,0 +1,170 @@
+//! Tracking of the gas of runs within a given budget, for embedders estimating the fees of
+//! executions.
+
+use cairo_felt::Felt252;
+use cairo_lang_sierra::extensions::core::CoreConcreteLibfunc;
+use cairo_lang_sierra::extensions::gas::GasConcreteLibfunc;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::{GenStatement, StatementIdx};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use cairo_vm::types::relocatable::Relocatable;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use num_traits::ToPrimitive;
+
+use crate::profiling::user_function_idx_by_sierra_statement_idx;
+use crate::{RunResultStarknet, SierraCasmRunner};
+
+#[cfg(test)]
+#[path = "gas_budget_test.rs"]
+mod test;
+
+/// The gas budget of a run.
+#[derive(Clone, Debug, Default)]
+pub struct GasBudget {
+    /// The gas available for the run, including the gas required for calling the function.
+    pub available_gas: usize,
+    /// The statements at which to report the available gas. As the gas counter is only checked
+    /// when withdrawing gas, only `withdraw_gas` statements are reported.
+    pub breakpoints: UnorderedHashSet<StatementIdx>,
+}
+
+/// A check of the gas counter against the gas requested by a statement.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GasCheck {
+    /// The statement requesting the gas.
+    pub statement_idx: StatementIdx,
+    /// The gas available before the statement.
+    pub available_gas: usize,
+    /// The gas requested by the statement.
+    pub requested_gas: usize,
+    /// The functions on the call stack, from the run function to the one containing the
+    /// statement.
+    pub call_stack: Vec<FunctionId>,
+}
+
+/// The outcome of a run in terms of gas.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GasOutcome {
+    /// The run completed within the budget, either successfully or with a panic.
+    Completed { remaining_gas: usize },
+    /// The run panicked due to a failed gas check.
+    OutOfGas(GasCheck),
+}
+
+/// The result of a run within a gas budget.
+pub struct GasBudgetRunResult {
+    /// The result of the run, or `None` if the budget does not cover calling the function.
+    pub run_result: Option<RunResultStarknet>,
+    /// The gas checks of the breakpoints reached by the run, in execution order.
+    pub breakpoints: Vec<GasCheck>,
+    /// The outcome of the run in terms of gas.
+    pub outcome: GasOutcome,
+}
+
+/// Tracks the gas checks of a run, as seen by the hint processor.
+pub struct GasTracker {
+    /// The size of the code preceding the program in the program segment.
+    code_offset: usize,
+    /// The statements at which to record the gas checks.
+    breakpoints: UnorderedHashSet<StatementIdx>,
+    /// The gas checks of the reached breakpoints.
+    pub breakpoint_checks: Vec<GasCheck>,
+    /// The last gas check that failed.
+    pub failed_check: Option<GasCheck>,
+}
+impl GasTracker {
+    pub fn new(code_offset: usize, breakpoints: UnorderedHashSet<StatementIdx>) -> Self {
+        Self { code_offset, breakpoints, breakpoint_checks: vec![], failed_check: None }
+    }
+
+    /// Tracks a comparison of `requested_gas` to `available_gas` at the current pc of `vm`, if it
+    /// is done by a statement withdrawing gas.
+    pub fn track_gas_check(
+        &mut self,
+        runner: &SierraCasmRunner,
+        vm: &VirtualMachine,
+        requested_gas: &Felt252,
+        available_gas: &Felt252,
+    ) {
+        let Some(statement_idx) = self.statement_idx_by_pc(runner, vm.get_pc()) else {
+            return;
+        };
+        let Some(GenStatement::Invocation(invocation)) =
+            runner.sierra_program.statements.get(statement_idx.0)
+        else {
+            return;
+        };
+        if !matches!(
+            runner.sierra_program_registry.get_libfunc(&invocation.libfunc_id),
+            Ok(CoreConcreteLibfunc::Gas(
+                GasConcreteLibfunc::WithdrawGas(_) | GasConcreteLibfunc::BuiltinWithdrawGas(_)
+            ))
+        ) {
+            return;
+        }
+        let failed = requested_gas > available_gas;
+        if !failed && !self.breakpoints.contains(&statement_idx) {
+            return;
+        }
+        let check = GasCheck {
+            statement_idx,
+            available_gas: available_gas.to_usize().unwrap(),
+            requested_gas: requested_gas.to_usize().unwrap(),
+            call_stack: self.call_stack(runner, vm, statement_idx),
+        };
+        if self.breakpoints.contains(&statement_idx) {
+            self.breakpoint_checks.push(check.clone());
+        }
+        if failed {
+            self.failed_check = Some(check);
+        }
+    }
+
+    /// Returns the functions on the call stack of `vm`, whose current statement is
+    /// `statement_idx`, by following the frames of the calls.
+    fn call_stack(
+        &self,
+        runner: &SierraCasmRunner,
+        vm: &VirtualMachine,
+        statement_idx: StatementIdx,
+    ) -> Vec<FunctionId> {
+        let function_id = |statement_idx| {
+            let function_idx =
+                user_function_idx_by_sierra_statement_idx(&runner.sierra_program, statement_idx);
+            runner.sierra_program.funcs[function_idx].id.clone()
+        };
+        let mut call_stack = vec![function_id(statement_idx)];
+        let mut fp = vm.get_fp();
+        // Each frame holds the fp of the caller at `fp - 2` and the return pc at `fp - 1`. The
+        // walk stops at the call from the entry code, whose return pc is outside the program.
+        while let Some(call_statement_idx) = (fp - 1)
+            .ok()
+            .and_then(|address| vm.get_relocatable(address).ok())
+            .and_then(|return_pc| (return_pc - 1).ok())
+            .and_then(|call_pc| self.statement_idx_by_pc(runner, call_pc))
+        {
+            call_stack.push(function_id(call_statement_idx));
+            let Some(caller_fp) =
+                (fp - 2).ok().and_then(|address| vm.get_relocatable(address).ok())
+            else {
+                break;
+            };
+            fp = caller_fp;
+        }
+        call_stack.reverse();
+        call_stack
+    }
+
+    /// Returns the statement of the program containing `pc`, or `None` if `pc` is outside the
+    /// program.
+    fn statement_idx_by_pc(
+        &self,
+        runner: &SierraCasmRunner,
+        pc: Relocatable,
+    ) -> Option<StatementIdx> {
+        let bytecode_len = runner.casm_program.debug_info.sierra_statement_info.last()?.end_offset;
+        let program_pc = pc.offset.checked_sub(self.code_offset)?;
+        (pc.segment_index == 0 && program_pc < bytecode_len)
+            .then(|| runner.sierra_statement_index_by_pc(program_pc))
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use cairo_felt::Felt252;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+
+use super::{GasBudget, GasOutcome};
+use crate::{Arg, RunResultValue, SierraCasmRunner};
+
+/// Creates a runner for a recursive function `fib`.
+fn fib_runner() -> SierraCasmRunner {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            fn fib(a: felt252, b: felt252, n: felt252) -> felt252 {
+                if n == 0 {
+                    a
+                } else {
+                    fib(b, a + b, n - 1)
+                }
+            }
+        "},
+    )
+    .unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+    let program = Arc::unwrap_or_clone(
+        db.get_sierra_program(vec![test_module.crate_id]).expect("`get_sierra_program` failed."),
+    )
+    .program;
+    let program = replace_sierra_ids_in_program(&db, &program);
+    SierraCasmRunner::new(program, Some(Default::default()), OrderedHashMap::default(), None)
+        .unwrap()
+}
+
+fn fib_args(n: usize) -> Vec<Arg> {
+    vec![Felt252::from(0).into(), Felt252::from(1).into(), Felt252::from(n).into()]
+}
+
+#[test]
+fn run_within_gas_budget() {
+    let runner = fib_runner();
+    let func = runner.find_function("::fib").unwrap();
+    let result = runner
+        .run_function_with_gas_budget(
+            func,
+            &fib_args(10),
+            GasBudget {
+                available_gas: u32::MAX as usize,
+                breakpoints: (0..runner.sierra_program.statements.len())
+                    .map(StatementIdx)
+                    .collect(),
+            },
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.run_result.unwrap().value, RunResultValue::Success(vec![Felt252::from(55)]));
+    let GasOutcome::Completed { remaining_gas } = result.outcome else {
+        panic!("Expected the run to complete, got {:?}.", result.outcome);
+    };
+    // Each recursive call withdraws gas, with a deeper call stack and less available gas.
+    assert!(!result.breakpoints.is_empty());
+    for (check, next_check) in result.breakpoints.iter().zip(result.breakpoints.iter().skip(1)) {
+        assert!(next_check.available_gas < check.available_gas);
+        assert!(next_check.call_stack.len() > check.call_stack.len());
+        assert!(next_check.call_stack.iter().all(|function_id| function_id == &func.id));
+    }
+    assert!(remaining_gas < result.breakpoints.last().unwrap().available_gas);
+}
+
+#[test]
+fn run_out_of_gas() {
+    let runner = fib_runner();
+    let func = runner.find_function("::fib").unwrap();
+    let required_gas = runner.initial_required_gas(func).unwrap();
+
+    // Not covering the call is reported at the entry point of the function.
+    let result = runner
+        .run_function_with_gas_budget(
+            func,
+            &fib_args(1000),
+            GasBudget { available_gas: required_gas - 1, ..Default::default() },
+            Default::default(),
+        )
+        .unwrap();
+    assert!(result.run_result.is_none());
+    let GasOutcome::OutOfGas(check) = result.outcome else {
+        panic!("Expected the run to run out of gas, got {:?}.", result.outcome);
+    };
+    assert_eq!(check.statement_idx, func.entry_point);
+    assert_eq!(check.call_stack, vec![func.id.clone()]);
+
+    // Running out of gas within the recursion reports the consuming call stack.
+    let result = runner
+        .run_function_with_gas_budget(
+            func,
+            &fib_args(1000),
+            GasBudget { available_gas: required_gas + 10000, ..Default::default() },
+            Default::default(),
+        )
+        .unwrap();
+    assert!(matches!(result.run_result.unwrap().value, RunResultValue::Panic(_)));
+    let GasOutcome::OutOfGas(check) = result.outcome else {
+        panic!("Expected the run to run out of gas, got {:?}.", result.outcome);
+    };
+    assert!(check.requested_gas > check.available_gas);
+    assert!(check.call_stack.len() > 1);
+    assert!(check.call_stack.iter().all(|function_id| function_id == &func.id));
+}
@@ -2,10 +2,10 @@
 use std::collections::HashMap;
 
 use cairo_felt::Felt252;
+use cairo_lang_casm::casm;
 use cairo_lang_casm::hints::Hint;
-use cairo_lang_casm::inline::CasmContext;
 use cairo_lang_casm::instructions::Instruction;
-use cairo_lang_casm::{casm, casm_extend};
+use cairo_lang_sierra::debug_info::SourceCodeLocation;
 use cairo_lang_sierra::extensions::bitwise::BitwiseType;
 use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
 use cairo_lang_sierra::extensions::ec::EcOpType;
@@ -22,6 +22,9 @@ use cairo_lang_sierra::program::{Function, GenStatement, GenericArg, StatementId
 use cairo_lang_sierra::program_registry::{ProgramRegistry, ProgramRegistryError};
 use cairo_lang_sierra_ap_change::ApChangeError;
 use cairo_lang_sierra_to_casm::compiler::{CairoProgram, CompilationError, SierraToCasmConfig};
+use cairo_lang_sierra_to_casm::cost_overlay::SourceCostOverlay;
+pub use cairo_lang_sierra_to_casm::entry_code::Arg;
+use cairo_lang_sierra_to_casm::entry_code::{self, EntryCodeError};
 use cairo_lang_sierra_to_casm::metadata::{
     calc_metadata, calc_metadata_ap_change_only, Metadata, MetadataComputationConfig, MetadataError,
 };
@@ -31,6 +34,7 @@ use cairo_lang_utils::casts::IntoOrPanic;
 use cairo_lang_utils::extract_matches;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use cairo_vm::hint_processor::hint_processor_definition::HintProcessor;
 use cairo_vm::serde::deserialize_program::{BuiltinName, HintParams};
 use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
@@ -44,12 +48,17 @@ use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 use profiling::{user_function_idx_by_sierra_statement_idx, ProfilingInfo};
 use thiserror::Error;
+use value_layout::ValueLayout;
 
-use crate::casm_run::{RunFunctionContext, RunFunctionResult};
+use crate::casm_run::{RunFunctionContext, RunFunctionResult, SegmentStats};
+use crate::gas_budget::{GasBudget, GasBudgetRunResult, GasCheck, GasOutcome, GasTracker};
 
 pub mod casm_run;
+pub mod comparison;
+pub mod gas_budget;
 pub mod profiling;
 pub mod short_string;
+pub mod value_layout;
 
 const MAX_STACK_TRACE_DEPTH_DEFAULT: usize = 100;
 
@@ -78,6 +87,18 @@ pub enum RunnerError {
     #[error(transparent)]
     CairoRunError(#[from] Box<CairoRunError>),
 }
+impl From<EntryCodeError> for RunnerError {
+    fn from(err: EntryCodeError) -> Self {
+        match err {
+            EntryCodeError::ArgumentUnaligned { param_index, arg_index } => {
+                RunnerError::ArgumentUnaligned { param_index, arg_index }
+            }
+            EntryCodeError::ArgumentsSizeMismatch { expected, actual } => {
+                RunnerError::ArgumentsSizeMismatch { expected, actual }
+            }
+        }
+    }
+}
 
 /// The full result of a run with Starknet state.
 pub struct RunResultStarknet {
@@ -86,8 +107,12 @@ pub struct RunResultStarknet {
     pub value: RunResultValue,
     pub starknet_state: StarknetState,
     pub used_resources: StarknetExecutionResources,
+    /// The statistics of the memory segments used by the run.
+    pub segment_stats: SegmentStats,
     /// The profiling info of the run, if requested.
     pub profiling_info: Option<ProfilingInfo>,
+    /// The output printed during the run, if captured.
+    pub captured_output: Option<String>,
 }
 
 /// The full result of a run.
@@ -97,6 +122,8 @@ pub struct RunResult {
     pub memory: Vec<Option<Felt252>>,
     pub value: RunResultValue,
     pub used_resources: ExecutionResources,
+    /// The statistics of the memory segments used by the run.
+    pub segment_stats: SegmentStats,
     /// The profiling info of the run, if requested.
     pub profiling_info: Option<ProfilingInfo>,
 }
@@ -144,16 +171,16 @@ pub fn token_gas_cost(token_type: CostTokenType) -> usize {
     }
 }
 
-/// An argument to a sierra function run,
-#[derive(Debug)]
-pub enum Arg {
-    Value(Felt252),
-    Array(Vec<Arg>),
-}
-impl From<Felt252> for Arg {
-    fn from(value: Felt252) -> Self {
-        Self::Value(value)
-    }
+/// The builtins in the formatting expected by the runner, in the order of
+/// [entry_code::ENTRY_CODE_BUILTINS].
+fn entry_code_builtins() -> Vec<BuiltinName> {
+    vec![
+        BuiltinName::pedersen,
+        BuiltinName::range_check,
+        BuiltinName::bitwise,
+        BuiltinName::ec_op,
+        BuiltinName::poseidon,
+    ]
 }
 
 /// Builds hints_dict required in cairo_vm::types::program::Program from instructions.
@@ -197,6 +224,8 @@ pub struct SierraCasmRunner {
     starknet_contracts_info: OrderedHashMap<Felt252, ContractInfo>,
     /// Whether to run the profiler when running using this runner.
     run_profiler: Option<ProfilingInfoCollectionConfig>,
+    /// Whether to capture the output printed by runs into their results, rather than printing it.
+    capture_output: bool,
 }
 impl SierraCasmRunner {
     pub fn new(
@@ -213,7 +242,7 @@ impl SierraCasmRunner {
         let casm_program = cairo_lang_sierra_to_casm::compiler::compile(
             &sierra_program,
             &metadata,
-            SierraToCasmConfig { gas_usage_check, max_bytecode_size: usize::MAX },
+            SierraToCasmConfig { gas_usage_check, ..Default::default() },
         )?;
 
         // Find all contracts.
@@ -225,9 +254,27 @@ impl SierraCasmRunner {
             casm_program,
             starknet_contracts_info,
             run_profiler,
+            capture_output: false,
         })
     }
 
+    /// Sets whether to capture the output printed by runs into their results, rather than
+    /// printing it.
+    pub fn with_output_capture(mut self, capture_output: bool) -> Self {
+        self.capture_output = capture_output;
+        self
+    }
+
+    /// Attaches the source code locations of the Sierra statements to the debug info of the
+    /// compiled program.
+    pub fn with_source_locations(
+        mut self,
+        locations: &UnorderedHashMap<StatementIdx, SourceCodeLocation>,
+    ) -> Self {
+        self.casm_program.debug_info.populate_source_locations(locations);
+        self
+    }
+
     /// Runs the vm starting from a function in the context of a given starknet state.
     pub fn run_function_with_starknet_context(
         &self,
@@ -237,11 +284,90 @@ impl SierraCasmRunner {
         starknet_state: StarknetState,
     ) -> Result<RunResultStarknet, RunnerError> {
         let initial_gas = self.get_initial_available_gas(func, available_gas)?;
+        let (run_result, _) = self.run_function_with_starknet_context_ex(
+            func,
+            args,
+            initial_gas,
+            starknet_state,
+            None,
+        )?;
+        Ok(run_result)
+    }
+
+    /// Runs the vm starting from a function in the context of a given starknet state, within the
+    /// given gas budget.
+    ///
+    /// Reports the gas available at the breakpoints of the budget, and whether the run ran out of
+    /// gas, along with the call stack of the failing gas check. Not covering the cost of calling
+    /// the function is reported as running out of gas at its entry point.
+    pub fn run_function_with_gas_budget(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        gas_budget: GasBudget,
+        starknet_state: StarknetState,
+    ) -> Result<GasBudgetRunResult, RunnerError> {
+        let initial_gas = match self.get_initial_available_gas(func, Some(gas_budget.available_gas))
+        {
+            Ok(initial_gas) => initial_gas,
+            Err(RunnerError::NotEnoughGasToCall) => {
+                return Ok(GasBudgetRunResult {
+                    run_result: None,
+                    breakpoints: vec![],
+                    outcome: GasOutcome::OutOfGas(GasCheck {
+                        statement_idx: func.entry_point,
+                        available_gas: gas_budget.available_gas,
+                        requested_gas: self.initial_required_gas(func).unwrap_or_default(),
+                        call_stack: vec![func.id.clone()],
+                    }),
+                });
+            }
+            Err(err) => return Err(err),
+        };
+        let (run_result, gas_tracker) = self.run_function_with_starknet_context_ex(
+            func,
+            args,
+            initial_gas,
+            starknet_state,
+            Some(gas_budget.breakpoints),
+        )?;
+        let gas_tracker = gas_tracker.expect("Gas tracker is set for runs with a gas budget.");
+        let outcome = match gas_tracker.failed_check {
+            Some(failed_check) if matches!(run_result.value, RunResultValue::Panic(_)) => {
+                GasOutcome::OutOfGas(failed_check)
+            }
+            _ => GasOutcome::Completed {
+                remaining_gas: run_result
+                    .gas_counter
+                    .as_ref()
+                    .and_then(|gas_counter| gas_counter.to_usize())
+                    .unwrap_or_default(),
+            },
+        };
+        Ok(GasBudgetRunResult {
+            run_result: Some(run_result),
+            breakpoints: gas_tracker.breakpoint_checks,
+            outcome,
+        })
+    }
+
+    /// Runs the vm starting from a function in the context of a given starknet state, with
+    /// `initial_gas` after paying for the call. Tracks the gas checks of the run if
+    /// `gas_breakpoints` is provided.
+    fn run_function_with_starknet_context_ex(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        initial_gas: usize,
+        starknet_state: StarknetState,
+        gas_breakpoints: Option<UnorderedHashSet<StatementIdx>>,
+    ) -> Result<(RunResultStarknet, Option<GasTracker>), RunnerError> {
         let (entry_code, builtins) = self.create_entry_code(func, args, initial_gas)?;
         let footer = Self::create_code_footer();
         let (hints_dict, string_to_hint) =
             build_hints_dict(chain!(&entry_code, &self.casm_program.instructions));
         let assembled_program = self.casm_program.clone().assemble_ex(&entry_code, &footer);
+        let code_offset = entry_code.iter().map(|instruction| instruction.body.op_size()).sum();
 
         let mut hint_processor = CairoHintProcessor {
             runner: Some(self),
@@ -249,9 +375,12 @@ impl SierraCasmRunner {
             string_to_hint,
             run_resources: RunResources::default(),
             syscalls_used_resources: Default::default(),
+            captured_output: self.capture_output.then(String::new),
+            gas_tracker: gas_breakpoints
+                .map(|breakpoints| GasTracker::new(code_offset, breakpoints)),
         };
-        let RunResult { gas_counter, memory, value, used_resources, profiling_info } = self
-            .run_function(
+        let RunResult { gas_counter, memory, value, used_resources, segment_stats, profiling_info } =
+            self.run_function(
                 func,
                 &mut hint_processor,
                 hints_dict,
@@ -260,14 +389,19 @@ impl SierraCasmRunner {
             )?;
         let mut all_used_resources = hint_processor.syscalls_used_resources;
         all_used_resources.basic_resources += &used_resources;
-        Ok(RunResultStarknet {
-            gas_counter,
-            memory,
-            value,
-            starknet_state: hint_processor.starknet_state,
-            used_resources: all_used_resources,
-            profiling_info,
-        })
+        Ok((
+            RunResultStarknet {
+                gas_counter,
+                memory,
+                value,
+                starknet_state: hint_processor.starknet_state,
+                used_resources: all_used_resources,
+                segment_stats,
+                profiling_info,
+                captured_output: hint_processor.captured_output,
+            },
+            hint_processor.gas_tracker,
+        ))
     }
 
     /// Runs the vm starting from a function with custom hint processor. Function may have
@@ -289,32 +423,36 @@ impl SierraCasmRunner {
     {
         let return_types = self.generic_id_and_size_from_concrete(&func.signature.ret_types);
 
-        let RunFunctionResult { memory, ap, used_resources } = casm_run::run_function(
-            vm,
-            bytecode,
-            builtins,
-            initialize_vm,
-            hint_processor,
-            hints_dict,
-        )?;
+        let RunFunctionResult { memory, ap, used_resources, segment_stats } =
+            casm_run::run_function(
+                vm,
+                bytecode,
+                builtins,
+                initialize_vm,
+                hint_processor,
+                hints_dict,
+            )?;
         let (results_data, gas_counter) = Self::get_results_data(&return_types, &memory, ap);
-        assert!(results_data.len() <= 1);
 
-        let value = if results_data.is_empty() {
+        let value = match &results_data[..] {
             // No result type - no panic.
-            RunResultValue::Success(vec![])
-        } else {
-            let (ty, values) = results_data[0].clone();
-            let inner_ty =
-                self.inner_type_from_panic_wrapper(&ty, func).map(|it| self.type_sizes[&it]);
-            Self::handle_main_return_value(inner_ty, values, &memory)
+            [] => RunResultValue::Success(vec![]),
+            [(ty, values)] => {
+                let inner_ty =
+                    self.inner_type_from_panic_wrapper(ty, func).map(|it| self.type_sizes[&it]);
+                Self::handle_main_return_value(inner_ty, values.clone(), &memory)
+            }
+            // Multiple result types - no panic wrapper, returning the values in order.
+            _ => RunResultValue::Success(
+                results_data.into_iter().rev().flat_map(|(_, values)| values).collect(),
+            ),
         };
 
         let profiling_info = self.run_profiler.as_ref().map(|config| {
             self.collect_profiling_info(vm.get_relocated_trace().unwrap(), config.clone())
         });
 
-        Ok(RunResult { gas_counter, memory, value, used_resources, profiling_info })
+        Ok(RunResult { gas_counter, memory, value, used_resources, segment_stats, profiling_info })
     }
 
     /// Collects profiling info of the current run using the trace.
@@ -536,25 +674,56 @@ impl SierraCasmRunner {
         // Handling implicits.
         let mut gas_counter = None;
         results_data.retain_mut(|(ty, values)| {
-            let generic_ty = ty;
-            if *generic_ty == GasBuiltinType::ID {
+            if *ty == GasBuiltinType::ID {
                 gas_counter = Some(values.remove(0));
                 assert!(values.is_empty());
-                false
-            } else {
-                *generic_ty != RangeCheckType::ID
-                    && *generic_ty != BitwiseType::ID
-                    && *generic_ty != EcOpType::ID
-                    && *generic_ty != PedersenType::ID
-                    && *generic_ty != PoseidonType::ID
-                    && *generic_ty != SystemType::ID
-                    && *generic_ty != SegmentArenaType::ID
             }
+            !is_implicit(ty)
         });
 
         (results_data, gas_counter)
     }
 
+    /// Returns the layout of the value returned by `func`, excluding the implicits and the panic
+    /// wrapper. Multiple returned values are laid out as a struct.
+    /// Returns `None` if `func` returns no value.
+    pub fn return_value_layout(&self, func: &Function) -> Option<ValueLayout> {
+        let value_types: Vec<_> = func
+            .signature
+            .ret_types
+            .iter()
+            .filter(|ty| !is_implicit(&self.get_info(ty).long_id.generic_id))
+            .collect();
+        let layout = |ty: &ConcreteTypeId| {
+            ValueLayout::new(&self.sierra_program_registry, &self.type_sizes, ty)
+        };
+        match value_types[..] {
+            [] => None,
+            [ty] => {
+                let generic_id = &self.get_info(ty).long_id.generic_id;
+                Some(match self.inner_type_from_panic_wrapper(generic_id, func) {
+                    Some(inner_ty) => layout(&inner_ty),
+                    None => layout(ty),
+                })
+            }
+            _ => Some(ValueLayout::Struct(value_types.into_iter().map(layout).collect())),
+        }
+    }
+
+    /// Returns the values of a successful run of `func`, flattened according to the layout of its
+    /// returned value, see [ValueLayout::flatten].
+    pub fn flatten_return_value(
+        &self,
+        func: &Function,
+        values: &[Felt252],
+        memory: &[Option<Felt252>],
+    ) -> Vec<Felt252> {
+        match self.return_value_layout(func) {
+            Some(layout) => layout.flatten(values, memory),
+            None => vec![],
+        }
+    }
+
     /// Finds first function ending with `name_suffix`.
     pub fn find_function(&self, name_suffix: &str) -> Result<&Function, RunnerError> {
         self.sierra_program
@@ -595,106 +764,9 @@ impl SierraCasmRunner {
         initial_gas: usize,
         code_offset: usize,
     ) -> Result<(Vec<Instruction>, Vec<BuiltinName>), RunnerError> {
-        let mut ctx = casm! {};
-        // The builtins in the formatting expected by the runner.
-        let builtins = vec![
-            BuiltinName::pedersen,
-            BuiltinName::range_check,
-            BuiltinName::bitwise,
-            BuiltinName::ec_op,
-            BuiltinName::poseidon,
-        ];
-        // The offset [fp - i] for each of this builtins in this configuration.
-        let builtin_offset: HashMap<GenericTypeId, i16> = HashMap::from([
-            (PedersenType::ID, 7),
-            (RangeCheckType::ID, 6),
-            (BitwiseType::ID, 5),
-            (EcOpType::ID, 4),
-            (PoseidonType::ID, 3),
-        ]);
-        let mut ap_offset: i16 = 0;
-        let mut array_args_data_iter = prep_array_args(&mut ctx, args, &mut ap_offset).into_iter();
-        let after_arrays_data_offset = ap_offset;
-        if param_types.iter().any(|(ty, _)| ty == &SegmentArenaType::ID) {
-            casm_extend! {ctx,
-                // SegmentArena segment.
-                %{ memory[ap + 0] = segments.add() %}
-                // Infos segment.
-                %{ memory[ap + 1] = segments.add() %}
-                ap += 2;
-                [ap + 0] = 0, ap++;
-                // Write Infos segment, n_constructed (0), and n_destructed (0) to the segment.
-                [ap - 2] = [[ap - 3]];
-                [ap - 1] = [[ap - 3] + 1];
-                [ap - 1] = [[ap - 3] + 2];
-            }
-            ap_offset += 3;
-        }
-        let mut expected_arguments_size = 0;
-        let mut param_index = 0;
-        let mut arg_iter = args.iter().enumerate();
-        for ty in param_types {
-            let (generic_ty, ty_size) = ty;
-            if let Some(offset) = builtin_offset.get(generic_ty) {
-                casm_extend! {ctx,
-                    [ap + 0] = [fp - offset], ap++;
-                }
-                ap_offset += 1;
-            } else if generic_ty == &SystemType::ID {
-                casm_extend! {ctx,
-                    %{ memory[ap + 0] = segments.add() %}
-                    ap += 1;
-                }
-                ap_offset += 1;
-            } else if generic_ty == &GasBuiltinType::ID {
-                casm_extend! {ctx,
-                    [ap + 0] = initial_gas, ap++;
-                }
-                ap_offset += 1;
-            } else if generic_ty == &SegmentArenaType::ID {
-                let offset = -ap_offset + after_arrays_data_offset;
-                casm_extend! {ctx,
-                    [ap + 0] = [ap + offset] + 3, ap++;
-                }
-                ap_offset += 1;
-            } else {
-                let arg_size = *ty_size;
-                let param_ap_offset_end = ap_offset + arg_size;
-                expected_arguments_size += arg_size.into_or_panic::<usize>();
-                while ap_offset < param_ap_offset_end {
-                    let Some((arg_index, arg)) = arg_iter.next() else {
-                        break;
-                    };
-                    add_arg_to_stack(&mut ctx, arg, &mut ap_offset, &mut array_args_data_iter);
-                    if ap_offset > param_ap_offset_end {
-                        return Err(RunnerError::ArgumentUnaligned { param_index, arg_index });
-                    }
-                }
-                param_index += 1;
-            };
-        }
-        let actual_args_size = args
-            .iter()
-            .map(|arg| match arg {
-                Arg::Value(_) => 1,
-                Arg::Array(_) => 2,
-            })
-            .sum::<usize>();
-        if expected_arguments_size != actual_args_size {
-            return Err(RunnerError::ArgumentsSizeMismatch {
-                expected: expected_arguments_size,
-                actual: actual_args_size,
-            });
-        }
-        let before_final_call = ctx.current_code_offset;
-        let final_call_size = 3;
-        let offset = final_call_size + code_offset;
-        casm_extend! {ctx,
-            call rel offset;
-            ret;
-        }
-        assert_eq!(before_final_call + final_call_size, ctx.current_code_offset);
-        Ok((ctx.instructions, builtins))
+        let instructions =
+            entry_code::create_entry_code_from_params(param_types, args, initial_gas, code_offset)?;
+        Ok((instructions, entry_code_builtins()))
     }
 
     /// Returns the instructions to add to the beginning of the code to successfully call the main
@@ -705,13 +777,15 @@ impl SierraCasmRunner {
         args: &[Arg],
         initial_gas: usize,
     ) -> Result<(Vec<Instruction>, Vec<BuiltinName>), RunnerError> {
-        let params = self.generic_id_and_size_from_concrete(&func.signature.param_types);
-
-        let entry_point = func.entry_point.0;
-        let code_offset =
-            self.casm_program.debug_info.sierra_statement_info[entry_point].start_offset;
-
-        Self::create_entry_code_from_params(&params, args, initial_gas, code_offset)
+        let instructions = entry_code::create_entry_code(
+            &self.sierra_program_registry,
+            &self.type_sizes,
+            &self.casm_program,
+            func,
+            args,
+            initial_gas,
+        )?;
+        Ok((instructions, entry_code_builtins()))
     }
 
     /// Returns the initial value for the gas counter.
@@ -760,6 +834,25 @@ impl SierraCasmRunner {
     pub fn get_casm_program(&self) -> &CairoProgram {
         &self.casm_program
     }
+
+    /// Returns the costs of the program accumulated per line of its Cairo source code.
+    /// Only the statements with source code locations are accounted for, see
+    /// [Self::with_source_locations].
+    pub fn get_source_cost_overlay(&self) -> Result<SourceCostOverlay, RunnerError> {
+        Ok(SourceCostOverlay::new(&self.sierra_program, &self.casm_program, &self.metadata)?)
+    }
+}
+
+/// Returns whether `generic_ty` is the type of an implicit, rather than of a returned value.
+fn is_implicit(generic_ty: &GenericTypeId) -> bool {
+    *generic_ty == GasBuiltinType::ID
+        || *generic_ty == RangeCheckType::ID
+        || *generic_ty == BitwiseType::ID
+        || *generic_ty == EcOpType::ID
+        || *generic_ty == PedersenType::ID
+        || *generic_ty == PoseidonType::ID
+        || *generic_ty == SystemType::ID
+        || *generic_ty == SegmentArenaType::ID
 }
 
 /// Configuration for the profiling info collection phase.
@@ -816,67 +909,6 @@ pub fn initialize_vm(context: RunFunctionContext<'_>) -> Result<(), Box<CairoRun
     Ok(())
 }
 
-/// The information on an array argument that was added to the stack.
-struct ArrayDataInfo {
-    /// The offset of the pointer to the array data in the stack.
-    ptr_offset: i16,
-    /// The size of the array data in the stack.
-    size: i16,
-}
-
-/// Adds an argument to the stack, updating the ap_offset and the array_data_iter.
-fn add_arg_to_stack(
-    ctx: &mut CasmContext,
-    arg: &Arg,
-    ap_offset: &mut i16,
-    array_data_iter: &mut impl Iterator<Item = ArrayDataInfo>,
-) {
-    match arg {
-        Arg::Value(value) => {
-            casm_extend! {ctx,
-                [ap + 0] = (value.to_bigint()), ap++;
-            }
-            *ap_offset += 1;
-        }
-        Arg::Array(_) => {
-            let info = array_data_iter.next().unwrap();
-            casm_extend! {ctx,
-                [ap + 0] = [ap + (info.ptr_offset - *ap_offset)], ap++;
-                [ap + 0] = [ap - 1] + (info.size), ap++;
-            }
-            *ap_offset += 2;
-        }
-    }
-}
-
-/// Prepares the array arguments for the stack, updating the ap_offset and returning the
-/// array_args_data.
-fn prep_array_args(ctx: &mut CasmContext, args: &[Arg], ap_offset: &mut i16) -> Vec<ArrayDataInfo> {
-    let mut array_args_data = vec![];
-    for arg in args {
-        let Arg::Array(values) = arg else { continue };
-        let mut inner_array_args_data = prep_array_args(ctx, values, ap_offset).into_iter();
-        casm_extend! {ctx,
-            %{ memory[ap + 0] = segments.add() %}
-            ap += 1;
-        }
-
-        let ptr_offset = *ap_offset;
-        *ap_offset += 1;
-        let data_offset = *ap_offset;
-        for arg in values {
-            add_arg_to_stack(ctx, arg, ap_offset, &mut inner_array_args_data);
-        }
-        let ptr = *ap_offset - ptr_offset;
-        let size = *ap_offset - data_offset;
-        for i in 0..size {
-            casm_extend! {ctx, [ap + (i - size)] = [[ap - ptr] + i]; }
-        }
-        array_args_data.push(ArrayDataInfo { ptr_offset, size });
-    }
-    array_args_data
-}
-
 /// Creates the metadata required for a Sierra program lowering to casm.
 fn create_metadata(
     sierra_program: &cairo_lang_sierra::program::Program,
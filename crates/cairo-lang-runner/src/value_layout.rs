@@ -0,0 +1,157 @@
+//! Layouts of Sierra values in memory, used for flattening returned values of arbitrary types.
+
+use cairo_felt::Felt252;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_to_casm::invocations::enm::get_variant_selector;
+use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_utils::casts::IntoOrPanic;
+use num_traits::ToPrimitive;
+
+#[cfg(test)]
+#[path = "value_layout_test.rs"]
+mod test;
+
+/// The layout of a Sierra value in memory.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ValueLayout {
+    /// A single cell holding the value.
+    Cell,
+    /// A struct, holding its members one after the other.
+    Struct(Vec<ValueLayout>),
+    /// An enum, holding its variant selector followed by the padded value of the active variant.
+    Enum { variants: Vec<ValueLayout>, size: usize },
+    /// An array or a span, holding pointers to the start and the end of its elements.
+    Array(Box<ValueLayout>),
+    /// A box, holding a pointer to its value.
+    Box(Box<ValueLayout>),
+    /// A nullable, holding a pointer to its value, or 0 if it is null.
+    Nullable(Box<ValueLayout>),
+    /// A value that is not interpreted, of the given size.
+    Opaque(usize),
+}
+impl ValueLayout {
+    /// Returns the layout of the values of type `ty`.
+    pub fn new(
+        registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+        type_sizes: &TypeSizeMap,
+        ty: &ConcreteTypeId,
+    ) -> Self {
+        let inner = |ty: &ConcreteTypeId| Box::new(Self::new(registry, type_sizes, ty));
+        match registry.get_type(ty).unwrap() {
+            CoreTypeConcrete::Struct(info) => Self::Struct(
+                info.members.iter().map(|member| Self::new(registry, type_sizes, member)).collect(),
+            ),
+            CoreTypeConcrete::Enum(info) => Self::Enum {
+                variants: info
+                    .variants
+                    .iter()
+                    .map(|variant| Self::new(registry, type_sizes, variant))
+                    .collect(),
+                size: type_sizes[ty].into_or_panic(),
+            },
+            // The length of arrays of zero sized elements cannot be deduced from their pointers.
+            CoreTypeConcrete::Array(info) | CoreTypeConcrete::Span(info)
+                if type_sizes[&info.ty] == 0 =>
+            {
+                Self::Opaque(2)
+            }
+            CoreTypeConcrete::Array(info) | CoreTypeConcrete::Span(info) => {
+                Self::Array(inner(&info.ty))
+            }
+            CoreTypeConcrete::Box(info) => Self::Box(inner(&info.ty)),
+            CoreTypeConcrete::Nullable(info) => Self::Nullable(inner(&info.ty)),
+            CoreTypeConcrete::Snapshot(info) => Self::new(registry, type_sizes, &info.ty),
+            _ => match type_sizes[ty] {
+                1 => Self::Cell,
+                size => Self::Opaque(size.into_or_panic()),
+            },
+        }
+    }
+
+    /// Returns the number of cells a value with this layout occupies.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Cell | Self::Box(_) | Self::Nullable(_) => 1,
+            Self::Struct(members) => members.iter().map(Self::size).sum(),
+            Self::Enum { size, .. } | Self::Opaque(size) => *size,
+            Self::Array(_) => 2,
+        }
+    }
+
+    /// Flattens the value held in `cells` with this layout, reading the data pointed to by the
+    /// value from `memory`.
+    ///
+    /// Pointed values are inlined - arrays are flattened into their length followed by their
+    /// flattened elements, nullables into 0 if null or 1 followed by their flattened value, and
+    /// enums into their variant index followed by the flattened value of the variant, without
+    /// padding.
+    pub fn flatten(&self, cells: &[Felt252], memory: &[Option<Felt252>]) -> Vec<Felt252> {
+        let mut flattened = vec![];
+        self.flatten_into(cells, memory, &mut flattened);
+        flattened
+    }
+
+    /// Flattens the value held in `cells` with this layout into `flattened`.
+    fn flatten_into(
+        &self,
+        cells: &[Felt252],
+        memory: &[Option<Felt252>],
+        flattened: &mut Vec<Felt252>,
+    ) {
+        assert_eq!(cells.len(), self.size(), "Value does not match its layout.");
+        match self {
+            Self::Cell | Self::Opaque(_) => flattened.extend(cells.iter().cloned()),
+            Self::Struct(members) => {
+                let mut offset = 0;
+                for member in members {
+                    let size = member.size();
+                    member.flatten_into(&cells[offset..offset + size], memory, flattened);
+                    offset += size;
+                }
+            }
+            Self::Enum { variants, size } => {
+                let selector = cells[0].to_usize().unwrap();
+                let index = (0..variants.len())
+                    .find(|index| {
+                        get_variant_selector(variants.len(), *index).ok() == Some(selector)
+                    })
+                    .expect("Invalid enum variant selector.");
+                let variant = &variants[index];
+                flattened.push(Felt252::from(index));
+                variant.flatten_into(&cells[size - variant.size()..], memory, flattened);
+            }
+            Self::Array(element) => {
+                let start = cells[0].to_usize().unwrap();
+                let end = cells[1].to_usize().unwrap();
+                let element_size = element.size();
+                flattened.push(Felt252::from((end - start) / element_size));
+                let data = read_cells(memory, start, end);
+                for element_cells in data.chunks(element_size) {
+                    element.flatten_into(element_cells, memory, flattened);
+                }
+            }
+            Self::Box(inner) => {
+                let ptr = cells[0].to_usize().unwrap();
+                let data = read_cells(memory, ptr, ptr + inner.size());
+                inner.flatten_into(&data, memory, flattened);
+            }
+            Self::Nullable(inner) => {
+                let ptr = cells[0].to_usize().unwrap();
+                if ptr == 0 {
+                    flattened.push(Felt252::from(0));
+                } else {
+                    flattened.push(Felt252::from(1));
+                    let data = read_cells(memory, ptr, ptr + inner.size());
+                    inner.flatten_into(&data, memory, flattened);
+                }
+            }
+        }
+    }
+}
+
+/// Reads the cells in the range `start..end` of `memory`, all of which must be initialized.
+fn read_cells(memory: &[Option<Felt252>], start: usize, end: usize) -> Vec<Felt252> {
+    memory[start..end].iter().cloned().map(Option::unwrap).collect()
+}
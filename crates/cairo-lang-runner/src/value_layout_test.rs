@@ -0,0 +1,44 @@
+use cairo_felt::Felt252;
+
+use super::ValueLayout;
+
+fn felts(values: &[usize]) -> Vec<Felt252> {
+    values.iter().map(|value| Felt252::from(*value)).collect()
+}
+
+#[test]
+fn flatten_nested_value() {
+    // `(Array<(felt252, felt252)>, Option<Box<felt252>>, Nullable<felt252>)`.
+    let layout = ValueLayout::Struct(vec![
+        ValueLayout::Array(Box::new(ValueLayout::Struct(vec![
+            ValueLayout::Cell,
+            ValueLayout::Cell,
+        ]))),
+        ValueLayout::Enum {
+            variants: vec![
+                ValueLayout::Box(Box::new(ValueLayout::Cell)),
+                ValueLayout::Struct(vec![]),
+            ],
+            size: 2,
+        },
+        ValueLayout::Nullable(Box::new(ValueLayout::Cell)),
+    ]);
+    let memory: Vec<_> = [None, Some(1), Some(2), Some(3), Some(4), Some(5)]
+        .into_iter()
+        .map(|cell| cell.map(Felt252::from))
+        .collect();
+    assert_eq!(layout.flatten(&felts(&[1, 5, 0, 5, 0]), &memory), felts(&[2, 1, 2, 3, 4, 0, 5, 0]));
+    assert_eq!(layout.flatten(&felts(&[3, 3, 1, 0, 3]), &memory), felts(&[0, 1, 1, 3]));
+}
+
+#[test]
+fn flatten_enum_with_jump_table_selector() {
+    let layout = ValueLayout::Enum {
+        variants: vec![ValueLayout::Cell, ValueLayout::Struct(vec![]), ValueLayout::Cell],
+        size: 2,
+    };
+    // The selectors of the variants are `5`, `3` and `1`.
+    assert_eq!(layout.flatten(&felts(&[5, 7]), &[]), felts(&[0, 7]));
+    assert_eq!(layout.flatten(&felts(&[3, 0]), &[]), felts(&[1]));
+    assert_eq!(layout.flatten(&felts(&[1, 9]), &[]), felts(&[2, 9]));
+}
@@ -21,6 +21,7 @@ use smol_str::SmolStr;
 
 use crate::diagnostic::SemanticDiagnosticKind;
 use crate::expr::inference::{self, ImplVar, ImplVarId};
+use crate::items::attribute::UsageAttributes;
 use crate::items::constant::{ConstValue, Constant};
 use crate::items::function_with_body::FunctionBody;
 use crate::items::functions::{ImplicitPrecedence, InlineConfiguration};
@@ -46,6 +47,11 @@ pub trait Elongate {
 // We differentiate between the declaration and the definition of each item:
 // Declarations and definitions must not depend on other definitions, only other declarations.
 // This prevents cycles where there shouldn't be any.
+// Queries used by other items should return values which don't hold syntax nodes (which change on
+// any edit of their file), such as usage attributes, so that their results stay the same when an
+// item's definition is edited. This does not firewall the users of an item yet: the ids of the
+// items of a file hold the green id of its root, so they change on any edit of the file, and the
+// queries on them are executed again.
 #[salsa::query_group(SemanticDatabase)]
 pub trait SemanticGroup:
     DefsGroup
@@ -188,6 +194,9 @@ pub trait SemanticGroup:
     /// Returns the attributes of a struct.
     #[salsa::invoke(items::structure::struct_attributes)]
     fn struct_attributes(&self, struct_id: StructId) -> Maybe<Vec<Attribute>>;
+    /// Returns the attributes of a struct which affect its usages.
+    #[salsa::invoke(items::structure::struct_usage_attributes)]
+    fn struct_usage_attributes(&self, struct_id: StructId) -> Maybe<UsageAttributes>;
     /// Returns the generic parameters of an enum.
     #[salsa::invoke(items::structure::struct_generic_params)]
     fn struct_generic_params(&self, struct_id: StructId) -> Maybe<Vec<GenericParam>>;
@@ -236,6 +245,9 @@ pub trait SemanticGroup:
     /// Returns the attributes attached to an enum.
     #[salsa::invoke(items::enm::enum_attributes)]
     fn enum_attributes(&self, enum_id: EnumId) -> Maybe<Vec<Attribute>>;
+    /// Returns the attributes of an enum which affect its usages.
+    #[salsa::invoke(items::enm::enum_usage_attributes)]
+    fn enum_usage_attributes(&self, enum_id: EnumId) -> Maybe<UsageAttributes>;
     /// Returns the resolution resolved_items of an enum declaration.
     #[salsa::invoke(items::enm::enum_declaration_resolver_data)]
     fn enum_declaration_resolver_data(&self, enum_id: EnumId) -> Maybe<Arc<ResolverData>>;
@@ -459,6 +471,12 @@ pub trait SemanticGroup:
         &self,
         trait_function_id: TraitFunctionId,
     ) -> Maybe<Vec<Attribute>>;
+    /// Returns the attributes of a trait function which affect its usages.
+    #[salsa::invoke(items::trt::trait_function_usage_attributes)]
+    fn trait_function_usage_attributes(
+        &self,
+        trait_function_id: TraitFunctionId,
+    ) -> Maybe<UsageAttributes>;
     /// Returns the resolution resolved_items of a trait function.
     #[salsa::invoke(items::trt::trait_function_resolver_data)]
     fn trait_function_resolver_data(
@@ -893,6 +911,12 @@ pub trait SemanticGroup:
         &self,
         function_id: FunctionWithBodyId,
     ) -> Maybe<Vec<Attribute>>;
+    /// Returns the attributes of a function with a body which affect its usages.
+    #[salsa::invoke(items::function_with_body::function_with_body_usage_attributes)]
+    fn function_with_body_usage_attributes(
+        &self,
+        function_id: FunctionWithBodyId,
+    ) -> Maybe<UsageAttributes>;
 
     /// Returns the semantic diagnostics of a body of a function (with a body).
     #[salsa::invoke(items::function_with_body::function_body_diagnostics)]
@@ -9,7 +9,7 @@ use cairo_lang_defs::ids::{
 use cairo_lang_defs::plugin::PluginDiagnostic;
 use cairo_lang_diagnostics::{
     error_code, DiagnosticAdded, DiagnosticEntry, DiagnosticLocation, Diagnostics,
-    DiagnosticsBuilder, ErrorCode, Severity,
+    DiagnosticsBuilder, ErrorCode, ErrorCodeRegistry, Severity,
 };
 use cairo_lang_filesystem::ids::FileId;
 use cairo_lang_syntax as syntax;
@@ -426,7 +426,7 @@ impl DiagnosticEntry for SemanticDiagnostic {
             }
             SemanticDiagnosticKind::UnstableFeature { feature_name } => {
                 format!(
-                    r#"Usage of unstable feature `{feature_name}` with no `#[feature({feature_name})]` attribute."#
+                    r#"Usage of unstable feature `"{feature_name}"` with no `#[feature("{feature_name}")]` attribute."#
                 )
             }
             SemanticDiagnosticKind::UnusedVariable => {
@@ -662,6 +662,13 @@ impl DiagnosticEntry for SemanticDiagnostic {
             SemanticDiagnosticKind::NegativeImplsOnlyOnImpls => {
                 "Negative impls supported only in impl definitions.".into()
             }
+            SemanticDiagnosticKind::NegativeImplOfImplementedTrait { trait_id } => {
+                format!(
+                    "A negative impl of the implemented trait `{}` is not allowed, as the impl \
+                     would exclude itself.",
+                    trait_id.name(db.upcast())
+                )
+            }
             SemanticDiagnosticKind::ImplicitPrecedenceAttrForExternFunctionNotAllowed => {
                 "`implicit_precedence` attribute is not allowed for extern functions.".into()
             }
@@ -674,6 +681,9 @@ impl DiagnosticEntry for SemanticDiagnostic {
             SemanticDiagnosticKind::UnsupportedFeatureAttrArguments => {
                 "`feature` attribute argument should be a single string.".into()
             }
+            SemanticDiagnosticKind::UnsupportedAllowAttrArguments => {
+                "`allow` attribute arguments should be lint names.".into()
+            }
             SemanticDiagnosticKind::UnsupportedPubArgument => "Unsupported `pub` argument.".into(),
             SemanticDiagnosticKind::UnknownStatementAttribute => {
                 "Unknown statement attribute.".into()
@@ -964,6 +974,9 @@ pub enum SemanticDiagnosticKind {
     ConstGenericParamNotSupported,
     NegativeImplsNotEnabled,
     NegativeImplsOnlyOnImpls,
+    NegativeImplOfImplementedTrait {
+        trait_id: TraitId,
+    },
     RefArgNotAVariable,
     RefArgNotMutable,
     RefArgNotExplicit,
@@ -1068,6 +1081,7 @@ pub enum SemanticDiagnosticKind {
     RedundantImplicitPrecedenceAttribute,
     UnsupportedImplicitPrecedenceArguments,
     UnsupportedFeatureAttrArguments,
+    UnsupportedAllowAttrArguments,
     UnsupportedPubArgument,
     UnknownStatementAttribute,
     InlineMacroNotFound {
@@ -1114,9 +1128,35 @@ impl SemanticDiagnosticKind {
     pub fn error_code(&self) -> Option<ErrorCode> {
         Some(match &self {
             Self::UnusedVariable => error_code!(E0001),
+            Self::PluginDiagnostic(diagnostic) => return diagnostic.error_code,
             _ => return None,
         })
     }
+
+    /// Registers the documentation of the error codes of the semantic diagnostics.
+    pub fn register_error_codes(registry: &mut ErrorCodeRegistry) {
+        registry.register(
+            error_code!(E0001),
+            "A variable is bound but never used.\n\nRemove the variable, or prefix its name with \
+             an underscore (e.g. `_x`) to mark it as intentionally unused.",
+        );
+    }
+}
+
+/// Returns the registry of the documented error codes of the compiler and of the plugins of `db`.
+pub fn error_code_registry(db: &dyn SemanticGroup) -> ErrorCodeRegistry {
+    let mut registry = ErrorCodeRegistry::default();
+    SemanticDiagnosticKind::register_error_codes(&mut registry);
+    for plugin in db.macro_plugins() {
+        plugin.register_error_codes(&mut registry);
+    }
+    for plugin in db.inline_macro_plugins().values() {
+        plugin.register_error_codes(&mut registry);
+    }
+    for plugin in db.analyzer_plugins() {
+        plugin.register_error_codes(&mut registry);
+    }
+    registry
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -6,6 +6,7 @@ use cairo_lang_defs::patcher::{PatchBuilder, RewriteNode};
 use cairo_lang_defs::plugin::{
     MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
 };
+use cairo_lang_diagnostics::{error_code, ErrorCodeRegistry};
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{ast, TypedStablePtr};
@@ -14,6 +15,7 @@ use pretty_assertions::assert_eq;
 use test_log::test;
 
 use crate::db::SemanticGroup;
+use crate::diagnostic::error_code_registry;
 use crate::items::us::SemanticUseEx;
 use crate::plugin::AnalyzerPlugin;
 use crate::resolve::ResolvedGenericItem;
@@ -292,3 +294,68 @@ fn test_analyzer_diagnostics() {
     "#},
     );
 }
+
+#[derive(Debug)]
+struct NoFeltReturnAnalyzerPlugin;
+impl AnalyzerPlugin for NoFeltReturnAnalyzerPlugin {
+    fn diagnostics(&self, db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<PluginDiagnostic> {
+        let Ok(free_functions) = db.module_free_functions_ids(module_id) else {
+            return vec![];
+        };
+        free_functions
+            .iter()
+            .filter(|function_id| {
+                db.free_function_signature(**function_id)
+                    .is_ok_and(|signature| signature.return_type == db.core_felt252_ty())
+            })
+            .map(|function_id| {
+                PluginDiagnostic::warning(
+                    function_id.stable_ptr(db.upcast()).untyped(),
+                    "Function returns a `felt252`.".to_string(),
+                )
+                .with_error_code(error_code!(E9001))
+            })
+            .collect()
+    }
+
+    fn register_error_codes(&self, registry: &mut ErrorCodeRegistry) {
+        registry.register(error_code!(E9001), "A function returns a `felt252`.");
+    }
+}
+
+#[test]
+fn test_plugin_error_codes() {
+    let mut db_val = SemanticDatabaseForTesting::new_empty();
+    let db = &mut db_val;
+    db.set_analyzer_plugins(vec![Arc::new(NoFeltReturnAnalyzerPlugin)]);
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            fn foo() -> felt252 {
+                let x = 5;
+                0
+            }
+       "},
+    );
+
+    assert_eq!(
+        get_crate_semantic_diagnostics(db, crate_id).format(db),
+        indoc! {r#"
+        warning[E9001]: Plugin diagnostic: Function returns a `felt252`.
+         --> lib.cairo:1:1
+        fn foo() -> felt252 {
+        ^*******************^
+
+        warning[E0001]: Unused variable. Consider ignoring by prefixing with `_`.
+         --> lib.cairo:2:9
+            let x = 5;
+                ^
+
+    "#},
+    );
+
+    let registry = error_code_registry(db);
+    assert_eq!(registry.explain("E9001"), Some("A function returns a `felt252`."));
+    assert!(registry.explain("E0001").is_some());
+    assert_eq!(registry.explain("E9002"), None);
+}
@@ -13,9 +13,9 @@ use cairo_lang_defs::ids::{
 };
 use cairo_lang_diagnostics::{DiagnosticAdded, Maybe, ToOption};
 use cairo_lang_filesystem::ids::{FileKind, FileLongId, VirtualFile};
-use cairo_lang_syntax::attribute::consts::FEATURE_ATTR;
+use cairo_lang_syntax::attribute::consts::{ALLOW_ATTR, FEATURE_ATTR};
 use cairo_lang_syntax::attribute::structured::{
-    Attribute, AttributeArg, AttributeArgVariant, AttributeStructurize,
+    AttributeArg, AttributeArgVariant, AttributeStructurize,
 };
 use cairo_lang_syntax::node::ast::{
     BlockOrIf, ExprPtr, PatternListOr, PatternStructParam, UnaryOperator,
@@ -128,6 +128,8 @@ pub struct ComputationContext<'ctx> {
     pub statements: Arena<semantic::Statement>,
     /// Definitions of semantic variables.
     pub semantic_defs: UnorderedHashMap<semantic::VarId, semantic::Variable>,
+    /// The variables defined where the `unused_variables` lint is allowed.
+    allowed_unused_variables: UnorderedHashSet<semantic::VarId>,
     loop_ctx: Option<LoopContext>,
 }
 impl<'ctx> ComputationContext<'ctx> {
@@ -152,6 +154,7 @@ impl<'ctx> ComputationContext<'ctx> {
             patterns: Arena::default(),
             statements: Arena::default(),
             semantic_defs,
+            allowed_unused_variables: Default::default(),
             loop_ctx: None,
         }
     }
@@ -182,7 +185,10 @@ impl<'ctx> ComputationContext<'ctx> {
 
     /// Adds warning for unused variables if required.
     fn add_unused_variable_warning(&mut self, var_name: &str, var: &Variable) {
-        if !self.environment.used_variables.contains(&var.id()) && !var_name.starts_with('_') {
+        if !self.environment.used_variables.contains(&var.id())
+            && !var_name.starts_with('_')
+            && !self.allowed_unused_variables.contains(&var.id())
+        {
             self.diagnostics.report_by_ptr(var.stable_ptr(self.db.upcast()), UnusedVariable);
         }
     }
@@ -202,12 +208,73 @@ impl<'ctx> ComputationContext<'ctx> {
             .report_by_ptr(stable_ptr, UnsupportedOutsideOfFunction { feature_name }))
     }
 
+    /// Returns whether `feature_name` is allowed in the current environment, either by a
+    /// `#[feature(...)]` attribute on an enclosing statement, item or module, or by the crate
+    /// configuration.
+    pub fn is_feature_allowed(&self, feature_name: &str) -> bool {
+        let mut env = &self.environment;
+        loop {
+            if env.allowed_features.contains(feature_name) {
+                return true;
+            }
+            match env.parent.as_ref() {
+                // Continue checking if the feature was allowed up the tree.
+                Some(parent) => env = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Returns whether the lint `lint_name` is allowed in the current environment, by an
+    /// `#[allow(...)]` attribute on an enclosing statement, item or module.
+    pub fn is_lint_allowed(&self, lint_name: &str) -> bool {
+        let mut env = &self.environment;
+        loop {
+            if env.allowed_lints.contains(lint_name) {
+                return true;
+            }
+            match env.parent.as_ref() {
+                Some(parent) => env = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Adds a local variable defined by a pattern to the current environment, returning the
+    /// variable it shadows in the environment, if any.
+    fn add_local_variable(&mut self, name: SmolStr, var_def: Variable) -> Option<Variable> {
+        if self.is_lint_allowed(UNUSED_VARIABLES_LINT) {
+            self.allowed_unused_variables.insert(var_def.id());
+        }
+        self.semantic_defs.insert(var_def.id(), var_def.clone());
+        self.environment.variables.insert(name, var_def)
+    }
+
+    /// Reports a diagnostic if `feature_name` is not allowed in the current environment.
+    /// Experimental syntax or semantics should call this when computed, to remain unusable in code
+    /// that did not opt in to the feature. Returns whether the feature is allowed.
+    pub fn validate_feature_gate(
+        &mut self,
+        feature_name: &str,
+        stable_ptr: SyntaxStablePtrId,
+    ) -> bool {
+        if self.is_feature_allowed(feature_name) {
+            return true;
+        }
+        self.diagnostics
+            .report_by_ptr(stable_ptr, UnstableFeature { feature_name: feature_name.into() });
+        false
+    }
+
     fn reduce_ty(&mut self, ty: TypeId) -> TypeId {
         // TODO(spapini): Propagate error to diagnostics.
         self.resolver.inference().rewrite(ty).unwrap()
     }
 }
 
+/// The lint of variables which are never used.
+pub const UNUSED_VARIABLES_LINT: &str = "unused_variables";
+
 // TODO(ilya): Change value to VarId.
 pub type EnvVariables = OrderedHashMap<SmolStr, Variable>;
 
@@ -220,6 +287,7 @@ pub struct Environment {
     variables: EnvVariables,
     used_variables: UnorderedHashSet<semantic::VarId>,
     allowed_features: UnorderedHashSet<SmolStr>,
+    allowed_lints: UnorderedHashSet<SmolStr>,
 }
 impl Environment {
     /// Adds a parameter to the environment.
@@ -249,6 +317,7 @@ impl Environment {
             variables: Default::default(),
             used_variables: Default::default(),
             allowed_features: Default::default(),
+            allowed_lints: Default::default(),
         }
     }
 
@@ -259,31 +328,27 @@ impl Environment {
     ) -> Self {
         let defs_db = db.upcast();
         let semantic_db = db.upcast();
-        let allowed_features = match lookup_item_id {
-            LookupItemId::ModuleItem(id) => extract_allowed_features(
-                semantic_db,
-                &id.stable_location(defs_db).syntax_node(defs_db),
-                diagnostics,
-            ),
-            LookupItemId::TraitItem(id) => extract_allowed_features(
-                semantic_db,
-                &id.stable_location(defs_db).syntax_node(defs_db),
-                diagnostics,
-            ),
-            LookupItemId::ImplItem(id) => extract_allowed_features(
-                semantic_db,
-                &id.stable_location(defs_db).syntax_node(defs_db),
-                diagnostics,
-            ),
+        let syntax = match lookup_item_id {
+            LookupItemId::ModuleItem(id) => id.stable_location(defs_db).syntax_node(defs_db),
+            LookupItemId::TraitItem(id) => id.stable_location(defs_db).syntax_node(defs_db),
+            LookupItemId::ImplItem(id) => id.stable_location(defs_db).syntax_node(defs_db),
         };
+        let allowed_features = extract_allowed_features(semantic_db, &syntax, diagnostics);
+        let allowed_lints = extract_allowed_lints(semantic_db, &syntax, diagnostics);
 
-        Self::from_element_id(db, lookup_item_id, allowed_features.into_iter().collect())
+        Self::from_element_id(
+            db,
+            lookup_item_id,
+            allowed_features.into_iter().collect(),
+            allowed_lints.into_iter().collect(),
+        )
     }
 
     fn from_element_id(
         db: &dyn SemanticGroup,
         element_id: impl LanguageElementId,
         mut allowed_features: UnorderedHashSet<SmolStr>,
+        mut allowed_lints: UnorderedHashSet<SmolStr>,
     ) -> Environment {
         let defs_db = db.upcast();
         let syntax_db = db.upcast();
@@ -293,7 +358,15 @@ impl Environment {
         let mut curr_module_id = element_id.parent_module(defs_db);
         loop {
             let submodule_id = match curr_module_id {
-                ModuleId::CrateRoot(_) => break,
+                ModuleId::CrateRoot(crate_id) => {
+                    // Features allowed at the crate level apply to all of its items.
+                    if let Some(config) = defs_db.crate_config(crate_id) {
+                        allowed_features.extend(
+                            config.settings.experimental_features.allowed_features.iter().cloned(),
+                        );
+                    }
+                    break;
+                }
                 ModuleId::Submodule(id) => id,
             };
             let parent = submodule_id.parent_module(defs_db);
@@ -303,6 +376,7 @@ impl Environment {
             {
                 allowed_features.insert(allowed_feature);
             }
+            allowed_lints.extend(extract_allowed_lints(syntax_db, module, ignored_diagnostics));
             curr_module_id = parent;
         }
         Self {
@@ -310,6 +384,7 @@ impl Environment {
             variables: Default::default(),
             used_variables: Default::default(),
             allowed_features,
+            allowed_lints,
         }
     }
 }
@@ -322,8 +397,8 @@ pub fn compute_expr_semantic(ctx: &mut ComputationContext<'_>, syntax: &ast::Exp
     let expr = wrap_maybe_with_missing(ctx, expr, syntax.stable_ptr());
     let id = ctx.exprs.alloc(expr.clone());
     if let TypeLongId::Concrete(concrete) = ctx.db.lookup_intern_type(expr.ty()) {
-        if let Ok(Some(attr)) = concrete.unstable_attr(ctx.db.upcast()) {
-            validate_unstable_feature_usage(ctx, attr, syntax.stable_ptr());
+        if let Ok(Some(feature_name)) = concrete.unstable_feature(ctx.db.upcast()) {
+            ctx.validate_feature_gate(&feature_name, syntax.stable_ptr().untyped());
         }
     }
     ExprAndId { expr, id }
@@ -1050,11 +1125,7 @@ fn compute_arm_semantic(
             }
 
             for v in variables {
-                let var_def = Variable::Local(v.var.clone());
-                // TODO(spapini): Wrap this in a function to couple with semantic_defs
-                // insertion.
-                new_ctx.environment.variables.insert(v.name.clone(), var_def.clone());
-                new_ctx.semantic_defs.insert(var_def.id(), var_def);
+                new_ctx.add_local_variable(v.name.clone(), Variable::Local(v.var.clone()));
             }
         }
         let arm_expr = if is_loop_arm {
@@ -2455,14 +2526,14 @@ fn expr_function_call(
     mut named_args: Vec<NamedArg>,
     stable_ptr: ast::ExprPtr,
 ) -> Maybe<Expr> {
-    if let Ok(Some(attr)) = ctx
+    if let Ok(Some(feature_name)) = ctx
         .db
         .lookup_intern_function(function_id)
         .function
         .generic_function
         .unstable_feature(ctx.db.upcast())
     {
-        validate_unstable_feature_usage(ctx, attr, stable_ptr);
+        ctx.validate_feature_gate(&feature_name, stable_ptr.untyped());
     }
     // TODO(spapini): Better location for these diagnostics after the refactor for generics resolve.
     // TODO(lior): Check whether concrete_function_signature should be `Option` instead of `Maybe`.
@@ -2638,8 +2709,6 @@ pub fn compute_statement_semantic(
 ) -> Maybe<StatementId> {
     let db = ctx.db;
     let syntax_db = db.upcast();
-    // As for now, statement attributes does not have any semantic affect, so we only validate they
-    // are allowed.
     validate_statement_attributes(ctx, &syntax);
     let mut features_to_remove = vec![];
     for feature_name in extract_allowed_features(syntax_db, &syntax, ctx.diagnostics) {
@@ -2647,6 +2716,12 @@ pub fn compute_statement_semantic(
             features_to_remove.push(feature_name);
         }
     }
+    let mut lints_to_remove = vec![];
+    for lint_name in extract_allowed_lints(syntax_db, &syntax, ctx.diagnostics) {
+        if ctx.environment.allowed_lints.insert(lint_name.clone()) {
+            lints_to_remove.push(lint_name);
+        }
+    }
     let statement = match &syntax {
         ast::Statement::Let(let_syntax) => {
             let expr = compute_expr_semantic(ctx, &let_syntax.rhs(syntax_db));
@@ -2688,13 +2763,11 @@ pub fn compute_statement_semantic(
             // TODO(yuval): allow unnamed variables. Add them here to
             // ctx.environment.unnamed_variables
             for v in variables {
-                let var_def = Variable::Local(v.var.clone());
                 if let Some(old_var) =
-                    ctx.environment.variables.insert(v.name.clone(), var_def.clone())
+                    ctx.add_local_variable(v.name.clone(), Variable::Local(v.var.clone()))
                 {
                     ctx.add_unused_variable_warning(&v.name, &old_var);
                 }
-                ctx.semantic_defs.insert(var_def.id(), var_def);
             }
             semantic::Statement::Let(semantic::StatementLet {
                 pattern: pattern.id,
@@ -2826,6 +2899,9 @@ pub fn compute_statement_semantic(
     for feature_name in features_to_remove {
         ctx.environment.allowed_features.remove(&feature_name);
     }
+    for lint_name in lints_to_remove {
+        ctx.environment.allowed_lints.remove(&lint_name);
+    }
     Ok(ctx.statements.alloc(statement))
 }
 
@@ -2844,16 +2920,50 @@ fn extract_allowed_features(
                     variant: AttributeArgVariant::Unnamed { value: ast::Expr::String(value), .. },
                     ..
                 },
-            ] => value.text(db),
+            ] => value.string_value(db),
+            _ => None,
+        };
+        let Some(feature_name) = feature_name else {
+            diagnostics
+                .report_by_ptr(attr.args_stable_ptr.untyped(), UnsupportedFeatureAttrArguments);
+            continue;
+        };
+        features.push(feature_name.into());
+    }
+    features
+}
+
+/// Returns the allowed lints of an object which supports attributes.
+fn extract_allowed_lints(
+    db: &dyn SyntaxGroup,
+    syntax: &impl QueryAttrs,
+    diagnostics: &mut SemanticDiagnostics,
+) -> Vec<SmolStr> {
+    let mut lints = vec![];
+    for attr_syntax in syntax.query_attr(db, ALLOW_ATTR) {
+        let attr = attr_syntax.structurize(db);
+        let lint_names: Option<Vec<_>> = attr
+            .args
+            .iter()
+            .map(|arg| match &arg.variant {
+                AttributeArgVariant::Unnamed { value: ast::Expr::Path(path), .. } => {
+                    match &path.elements(db)[..] {
+                        [ast::PathSegment::Simple(segment)] => Some(segment.ident(db).text(db)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+        match lint_names {
+            Some(lint_names) if !lint_names.is_empty() => lints.extend(lint_names),
             _ => {
                 diagnostics
-                    .report_by_ptr(attr.args_stable_ptr.untyped(), UnsupportedFeatureAttrArguments);
-                continue;
+                    .report_by_ptr(attr.args_stable_ptr.untyped(), UnsupportedAllowAttrArguments);
             }
-        };
-        features.push(feature_name);
+        }
     }
-    features
+    lints
 }
 
 /// Computes the semantic model of an expression and reports diagnostics if the expression does not
@@ -2914,37 +3024,3 @@ fn validate_statement_attributes(ctx: &mut ComputationContext<'_>, syntax: &ast:
         );
     }
 }
-
-/// Adds diagnostics if an expression using an unstable feature is not explicitly allowed to use the
-/// feature.
-fn validate_unstable_feature_usage(
-    ctx: &mut ComputationContext<'_>,
-    attr: Attribute,
-    stable_ptr: ExprPtr,
-) {
-    let Some(feature_name) = attr.args.iter().find_map(|arg| match &arg.variant {
-        AttributeArgVariant::Named { value: ast::Expr::String(value), name, .. }
-            if name == "feature" =>
-        {
-            Some(value.text(ctx.db.upcast()))
-        }
-        // TODO(orizi): Creates diagnostics for this case.
-        _ => None,
-    }) else {
-        return;
-    };
-    let mut env = &ctx.environment;
-    loop {
-        if env.allowed_features.contains(feature_name.as_str()) {
-            // The feature is allowed.
-            return;
-        }
-        if let Some(parent) = env.parent.as_ref() {
-            // Continue checking if the feature was allowed up the tree.
-            env = parent;
-        } else {
-            ctx.diagnostics.report_by_ptr(stable_ptr.untyped(), UnstableFeature { feature_name });
-            return;
-        }
-    }
-}
@@ -27,12 +27,14 @@ cairo_lang_test_utils::test_file_test!(
     expr_diagnostics,
     "src/expr/test_data",
     {
+        allow: "allow",
         assignment: "assignment",
         constant: "constant",
         constructor: "constructor",
         coupon: "coupon",
         enum_: "enum",
         error_propagate: "error_propagate",
+        feature: "feature",
         fixed_size_array: "fixed_size_array",
         function_call: "function_call",
         generics: "generics",
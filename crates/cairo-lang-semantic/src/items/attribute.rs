@@ -3,7 +3,10 @@ use cairo_lang_defs::ids::{
     ImplTypeDefId, ModuleId, StructId, SubmoduleId, TraitFunctionId, TraitId, TraitTypeId,
 };
 use cairo_lang_diagnostics::Maybe;
-use cairo_lang_syntax::attribute::structured::Attribute;
+use cairo_lang_syntax::attribute::consts::{MUST_USE_ATTR, UNSTABLE_ATTR};
+use cairo_lang_syntax::attribute::structured::{Attribute, AttributeArgVariant};
+use cairo_lang_syntax::node::ast;
+use smol_str::SmolStr;
 
 use crate::db::SemanticGroup;
 use crate::{ConcreteEnumId, ConcreteStructId};
@@ -31,6 +34,42 @@ impl AttributeTrait for Attribute {
     }
 }
 
+/// The attributes of an item which affect the semantics of its usages.
+///
+/// Unlike the attributes themselves, it holds no syntax, so it only changes when these attributes
+/// change, and not on edits of the item's body, or of the other items of its file. The semantic
+/// queries of the users of an item depend on it rather than on the item's attributes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsageAttributes {
+    /// Whether the item has the `#[must_use]` attribute.
+    pub must_use: bool,
+    /// The feature of the `#[unstable(feature: "some-string")]` attribute of the item, if any.
+    pub unstable_feature: Option<SmolStr>,
+}
+impl UsageAttributes {
+    /// Extracts the usage attributes out of the attributes of an item.
+    pub fn from_attributes(db: &dyn SemanticGroup, attributes: &[Attribute]) -> Self {
+        let unstable_feature = attributes
+            .iter()
+            .find(|attr| attr.id == UNSTABLE_ATTR)
+            .and_then(|attr| unstable_feature_name(db, attr));
+        Self { must_use: attributes.iter().any(|attr| attr.id == MUST_USE_ATTR), unstable_feature }
+    }
+}
+
+/// Returns the feature name of an `#[unstable(feature: "some-string")]` attribute.
+fn unstable_feature_name(db: &dyn SemanticGroup, attr: &Attribute) -> Option<SmolStr> {
+    attr.args.iter().find_map(|arg| match &arg.variant {
+        AttributeArgVariant::Named { value: ast::Expr::String(value), name, .. }
+            if name == "feature" =>
+        {
+            value.string_value(db.upcast()).map(SmolStr::from)
+        }
+        // TODO(orizi): Creates diagnostics for this case.
+        _ => None,
+    })
+}
+
 /// Trait for querying attributes of semantic items.
 pub trait SemanticQueryAttrs {
     /// Get the list of attributes attached to this node.
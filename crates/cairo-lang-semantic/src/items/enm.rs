@@ -12,6 +12,7 @@ use cairo_lang_utils::Upcast;
 use itertools::enumerate;
 use smol_str::SmolStr;
 
+use super::attribute::UsageAttributes;
 use super::generics::{semantic_generic_params, GenericParamsData};
 use crate::corelib::unit_ty;
 use crate::db::SemanticGroup;
@@ -127,6 +128,11 @@ pub fn enum_attributes(db: &dyn SemanticGroup, enum_id: EnumId) -> Maybe<Vec<Att
     Ok(db.priv_enum_declaration_data(enum_id)?.attributes)
 }
 
+/// Query implementation of [crate::db::SemanticGroup::enum_usage_attributes].
+pub fn enum_usage_attributes(db: &dyn SemanticGroup, enum_id: EnumId) -> Maybe<UsageAttributes> {
+    Ok(UsageAttributes::from_attributes(db, &db.enum_attributes(enum_id)?))
+}
+
 /// Query implementation of [crate::db::SemanticGroup::enum_declaration_resolver_data].
 pub fn enum_declaration_resolver_data(
     db: &dyn SemanticGroup,
@@ -189,11 +189,10 @@ pub fn priv_extern_function_declaration_data(
 
     match &inline_config {
         InlineConfiguration::None => {}
-        InlineConfiguration::Always(attr)
-        | InlineConfiguration::Never(attr)
-        | InlineConfiguration::Should(attr) => {
-            diagnostics
-                .report_by_ptr(attr.stable_ptr.untyped(), InlineAttrForExternFunctionNotAllowed);
+        InlineConfiguration::Always(attr_ptr)
+        | InlineConfiguration::Never(attr_ptr)
+        | InlineConfiguration::Should(attr_ptr) => {
+            diagnostics.report_by_ptr(attr_ptr.untyped(), InlineAttrForExternFunctionNotAllowed);
         }
     }
 
@@ -1,12 +1,19 @@
+use std::sync::Arc;
+
 use cairo_lang_debug::DebugWithDb;
-use cairo_lang_defs::ids::{FunctionWithBodyId, ModuleItemId};
+use cairo_lang_defs::ids::{FunctionWithBodyId, ModuleId, ModuleItemId};
+use cairo_lang_filesystem::db::{AsFilesGroupMut, CrateConfiguration, FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{CrateLongId, Directory, FileLongId};
 use cairo_lang_utils::extract_matches;
+use indoc::formatdoc;
 use pretty_assertions::assert_eq;
 use test_log::test;
 
 use crate::db::SemanticGroup;
 use crate::expr::fmt::ExprFormatter;
+use crate::items::attribute::UsageAttributes;
 use crate::items::function_with_body::SemanticExprLookup;
+use crate::items::functions::InlineConfiguration;
 use crate::test_utils::{setup_test_module, SemanticDatabaseForTesting};
 
 #[test]
@@ -93,3 +100,61 @@ fn test_expr_lookup() {
     let attributes = db.function_with_body_attributes(function_id).unwrap();
     assert_eq!(format!("{:?}", attributes.debug(db)), "[Attribute { id: \"inline\" }]");
 }
+
+#[test]
+fn test_usage_attributes_unchanged_by_body_edits() {
+    let mut db_val = SemanticDatabaseForTesting::new_empty();
+    let db = &mut db_val;
+    let crate_id = db.intern_crate(CrateLongId::Real("test".into()));
+    db.set_crate_config(
+        crate_id,
+        Some(CrateConfiguration::default_for_root(Directory::Real("src".into()))),
+    );
+    let file_id = db.intern_file(FileLongId::OnDisk("src/lib.cairo".into()));
+    let set_bodies = |db: &mut SemanticDatabaseForTesting, foo_body: &str, bar_body: &str| {
+        let content = formatdoc! {"
+            fn foo() -> felt252 {{
+                {foo_body}
+            }}
+
+            #[must_use]
+            #[inline(always)]
+            #[unstable(feature: \"bar-feature\")]
+            fn bar(a: felt252) -> felt252 {{
+                {bar_body}
+            }}
+        "};
+        db.as_files_group_mut().override_file_content(file_id, Some(Arc::new(content)));
+    };
+    // The usage attributes of `bar`, which its users depend on, along with its inline
+    // configuration.
+    let bar_usage_data = |db: &SemanticDatabaseForTesting| {
+        let bar = FunctionWithBodyId::Free(extract_matches!(
+            db.module_item_by_name(ModuleId::CrateRoot(crate_id), "bar".into()).unwrap().unwrap(),
+            ModuleItemId::FreeFunction
+        ));
+        (
+            db.function_with_body_usage_attributes(bar).unwrap(),
+            db.function_declaration_inline_config(bar).unwrap(),
+        )
+    };
+
+    set_bodies(db, "1", "a");
+    let (usage_attributes, inline_config) = bar_usage_data(db);
+    assert!(matches!(inline_config, InlineConfiguration::Always(_)));
+    assert_eq!(
+        usage_attributes,
+        UsageAttributes { must_use: true, unstable_feature: Some("bar-feature".into()) }
+    );
+
+    // Edits of the body of a preceding function, or of `bar` itself, keep them unchanged. Note that
+    // the queries are executed again, as the edits change the id of `bar`.
+    set_bodies(db, "let x = 1;\n    x + 2", "a");
+    let (edited_usage_attributes, edited_inline_config) = bar_usage_data(db);
+    assert_eq!(edited_usage_attributes, usage_attributes);
+    assert!(matches!(edited_inline_config, InlineConfiguration::Always(_)));
+    set_bodies(db, "let x = 1;\n    x + 2", "a + a * 2");
+    let (edited_usage_attributes, edited_inline_config) = bar_usage_data(db);
+    assert_eq!(edited_usage_attributes, usage_attributes);
+    assert!(matches!(edited_inline_config, InlineConfiguration::Always(_)));
+}
@@ -11,6 +11,7 @@ use cairo_lang_utils::Upcast;
 use id_arena::Arena;
 use itertools::Itertools;
 
+use super::attribute::UsageAttributes;
 use super::functions::InlineConfiguration;
 use crate::corelib::try_get_core_ty_by_name;
 use crate::db::SemanticGroup;
@@ -114,6 +115,14 @@ pub fn function_with_body_attributes(
     }
 }
 
+/// Query implementation of [crate::db::SemanticGroup::function_with_body_usage_attributes].
+pub fn function_with_body_usage_attributes(
+    db: &dyn SemanticGroup,
+    function_id: FunctionWithBodyId,
+) -> Maybe<UsageAttributes> {
+    Ok(UsageAttributes::from_attributes(db, &db.function_with_body_attributes(function_id)?))
+}
+
 // === Body ===
 
 #[derive(Clone, Debug, PartialEq, Eq, DebugWithDb)]
@@ -259,7 +268,7 @@ pub fn get_inline_config(
                     ..
                 },
             ] if &path.node.get_text(db.upcast()) == "always" => {
-                config = InlineConfiguration::Always(attr.clone());
+                config = InlineConfiguration::Always(attr.stable_ptr);
             }
             [
                 AttributeArg {
@@ -267,10 +276,10 @@ pub fn get_inline_config(
                     ..
                 },
             ] if &path.node.get_text(db.upcast()) == "never" => {
-                config = InlineConfiguration::Never(attr.clone());
+                config = InlineConfiguration::Never(attr.stable_ptr);
             }
             [] => {
-                config = InlineConfiguration::Should(attr.clone());
+                config = InlineConfiguration::Should(attr.stable_ptr);
             }
             _ => {
                 diagnostics.report_by_ptr(
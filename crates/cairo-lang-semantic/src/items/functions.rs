@@ -17,10 +17,9 @@ use cairo_lang_syntax::node::{ast, Terminal, TypedSyntaxNode};
 use cairo_lang_utils::{define_short_id, try_extract_matches, OptionFrom};
 use itertools::{chain, Itertools};
 use smol_str::SmolStr;
-use syntax::attribute::consts::{MUST_USE_ATTR, UNSTABLE_ATTR};
 use syntax::node::TypedStablePtr;
 
-use super::attribute::SemanticQueryAttrs;
+use super::attribute::UsageAttributes;
 use super::constant::ConstValue;
 use super::imp::ImplId;
 use super::modifiers;
@@ -177,21 +176,23 @@ impl GenericFunctionId {
             }
         }
     }
-    /// Returns whether the function has the `#[must_use]` attribute.
-    pub fn is_must_use(&self, db: &dyn SemanticGroup) -> Maybe<bool> {
+    /// Returns the attributes of the function which affect its usages.
+    pub fn usage_attributes(&self, db: &dyn SemanticGroup) -> Maybe<UsageAttributes> {
         match self {
-            GenericFunctionId::Free(id) => id.has_attr(db, MUST_USE_ATTR),
-            GenericFunctionId::Impl(id) => id.function.has_attr(db, MUST_USE_ATTR),
-            GenericFunctionId::Extern(_) => Ok(false),
+            GenericFunctionId::Free(id) => {
+                db.function_with_body_usage_attributes(FunctionWithBodyId::Free(*id))
+            }
+            GenericFunctionId::Impl(id) => db.trait_function_usage_attributes(id.function),
+            GenericFunctionId::Extern(_) => Ok(UsageAttributes::default()),
         }
     }
-    /// Returns the attribute if a function has the `#[unstable(feature: "some-string")]` attribute.
-    pub fn unstable_feature(&self, db: &dyn SemanticGroup) -> Maybe<Option<Attribute>> {
-        match self {
-            GenericFunctionId::Free(id) => id.find_attr(db, UNSTABLE_ATTR),
-            GenericFunctionId::Impl(id) => id.function.find_attr(db, UNSTABLE_ATTR),
-            GenericFunctionId::Extern(_) => Ok(None),
-        }
+    /// Returns whether the function has the `#[must_use]` attribute.
+    pub fn is_must_use(&self, db: &dyn SemanticGroup) -> Maybe<bool> {
+        Ok(self.usage_attributes(db)?.must_use)
+    }
+    /// Returns the feature if a function has the `#[unstable(feature: "some-string")]` attribute.
+    pub fn unstable_feature(&self, db: &dyn SemanticGroup) -> Maybe<Option<SmolStr>> {
+        Ok(self.usage_attributes(db)?.unstable_feature)
     }
 
     /// Returns true if the function does not depend on any generics.
@@ -842,9 +843,11 @@ pub struct FunctionDeclarationData {
 pub enum InlineConfiguration {
     /// The user did not specify any inlining preferences.
     None,
-    Always(Attribute),
-    Should(Attribute),
-    Never(Attribute),
+    /// Holds the pointer to the `#[inline]` attribute rather than the attribute itself, so that
+    /// the configuration doesn't change on edits of the function's file.
+    Always(ast::AttributePtr),
+    Should(ast::AttributePtr),
+    Never(ast::AttributePtr),
 }
 
 /// If a function with impl generic parameters is marked as '#[inline(always)]', raise a diagnostic.
@@ -855,9 +858,9 @@ pub fn forbid_inline_always_with_impl_generic_param(
 ) {
     let has_impl_generic_param = generic_params.iter().any(|p| matches!(p, GenericParam::Impl(_)));
     match &inline_config {
-        InlineConfiguration::Always(attr) if has_impl_generic_param => {
+        InlineConfiguration::Always(attr_ptr) if has_impl_generic_param => {
             diagnostics.report_by_ptr(
-                attr.stable_ptr.untyped(),
+                attr_ptr.untyped(),
                 SemanticDiagnosticKind::InlineAlwaysWithImplGenericArgNotAllowed,
             );
         }
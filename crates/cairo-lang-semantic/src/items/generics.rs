@@ -115,6 +115,8 @@ pub enum GenericParam {
     // TODO(spapini): Add expression.
     Const(GenericParamConst),
     Impl(GenericParamImpl),
+    /// A negative impl param (`-Trait<T>`), requiring the absence of an impl. This is the only way
+    /// to exclude impls, as there are no negative impl declarations (`impl !Trait for X;`).
     NegImpl(GenericParamImpl),
 }
 impl GenericParam {
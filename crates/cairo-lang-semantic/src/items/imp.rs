@@ -34,7 +34,9 @@ use super::function_with_body::{get_inline_config, FunctionBody, FunctionBodyDat
 use super::functions::{
     forbid_inline_always_with_impl_generic_param, FunctionDeclarationData, InlineConfiguration,
 };
-use super::generics::{semantic_generic_params, GenericArgumentHead, GenericParamsData};
+use super::generics::{
+    semantic_generic_params, GenericArgumentHead, GenericParamImpl, GenericParamsData,
+};
 use super::resolve_trait_path;
 use super::structure::SemanticStructEx;
 use super::trt::{ConcreteTraitGenericFunctionId, ConcreteTraitGenericFunctionLongId};
@@ -418,6 +420,23 @@ pub fn priv_impl_declaration_data_inner(
     let concrete_trait = inference.rewrite(concrete_trait).no_err();
     let generic_params = inference.rewrite(generic_params).no_err();
 
+    // An impl requiring the absence of the impl it defines can never be selected coherently.
+    if let Ok(concrete_trait) = concrete_trait {
+        for param in &generic_params {
+            if let GenericParam::NegImpl(GenericParamImpl {
+                concrete_trait: Ok(neg_trait), ..
+            }) = param
+            {
+                if *neg_trait == concrete_trait {
+                    diagnostics.report_by_ptr(
+                        param.stable_ptr(db.upcast()).untyped(),
+                        NegativeImplOfImplementedTrait { trait_id: concrete_trait.trait_id(db) },
+                    );
+                }
+            }
+        }
+    }
+
     let attributes = impl_ast.attributes(syntax_db).structurize(syntax_db);
     let mut resolver_data = resolver.data;
     resolver_data.trait_or_impl_ctx = TraitOrImplContext::Impl(ImplContext { impl_def_id });
@@ -11,6 +11,7 @@ use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::Upcast;
 use smol_str::SmolStr;
 
+use super::attribute::UsageAttributes;
 use super::generics::{semantic_generic_params, GenericParamsData};
 use super::visibility::Visibility;
 use crate::db::SemanticGroup;
@@ -133,6 +134,14 @@ pub fn struct_attributes(db: &dyn SemanticGroup, struct_id: StructId) -> Maybe<V
     Ok(db.priv_struct_declaration_data(struct_id)?.attributes)
 }
 
+/// Query implementation of [crate::db::SemanticGroup::struct_usage_attributes].
+pub fn struct_usage_attributes(
+    db: &dyn SemanticGroup,
+    struct_id: StructId,
+) -> Maybe<UsageAttributes> {
+    Ok(UsageAttributes::from_attributes(db, &db.struct_attributes(struct_id)?))
+}
+
 /// Query implementation of [crate::db::SemanticGroup::struct_declaration_resolver_data].
 pub fn struct_declaration_resolver_data(
     db: &dyn SemanticGroup,
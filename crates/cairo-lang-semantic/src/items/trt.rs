@@ -19,6 +19,7 @@ use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use itertools::chain;
 use smol_str::SmolStr;
 
+use super::attribute::UsageAttributes;
 use super::function_with_body::{get_implicit_precedence, get_inline_config, FunctionBodyData};
 use super::functions::{FunctionDeclarationData, ImplicitPrecedence, InlineConfiguration};
 use super::generics::{semantic_generic_params, GenericParamsData};
@@ -712,6 +713,14 @@ pub fn trait_function_attributes(
     Ok(db.priv_trait_function_declaration_data(trait_function_id)?.attributes)
 }
 
+/// Query implementation of [crate::db::SemanticGroup::trait_function_usage_attributes].
+pub fn trait_function_usage_attributes(
+    db: &dyn SemanticGroup,
+    trait_function_id: TraitFunctionId,
+) -> Maybe<UsageAttributes> {
+    Ok(UsageAttributes::from_attributes(db, &db.trait_function_attributes(trait_function_id)?))
+}
+
 /// Query implementation of [crate::db::SemanticGroup::trait_function_resolver_data].
 pub fn trait_function_resolver_data(
     db: &dyn SemanticGroup,
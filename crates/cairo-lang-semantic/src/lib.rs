@@ -14,6 +14,7 @@ pub mod lsp_helpers;
 pub mod plugin;
 pub mod resolve;
 pub mod substitution;
+pub mod typed_syntax;
 pub mod types;
 
 mod semantic;
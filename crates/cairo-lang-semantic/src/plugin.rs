@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use cairo_lang_defs::ids::ModuleId;
 use cairo_lang_defs::plugin::{InlineMacroExprPlugin, MacroPlugin, NamedPlugin, PluginDiagnostic};
+use cairo_lang_diagnostics::ErrorCodeRegistry;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
 use crate::db::SemanticGroup;
@@ -11,6 +12,9 @@ use crate::db::SemanticGroup;
 pub trait AnalyzerPlugin: std::fmt::Debug + Sync + Send {
     /// Runs the plugin on a module.
     fn diagnostics(&self, db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<PluginDiagnostic>;
+
+    /// Registers the documentation of the error codes of the diagnostics this plugin reports.
+    fn register_error_codes(&self, _registry: &mut ErrorCodeRegistry) {}
 }
 
 /// A suite of plugins.
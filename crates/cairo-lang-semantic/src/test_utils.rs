@@ -136,8 +136,10 @@ pub fn setup_test_crate_ex(
             experimental_features: ExperimentalFeaturesConfig {
                 negative_impls: true,
                 coupons: true,
+                allowed_features: Default::default(),
             },
             cfg_set: Default::default(),
+            external: false,
         }
     };
 
@@ -0,0 +1,107 @@
+//! A view of the expressions of a function body as syntax nodes annotated with their semantic
+//! information, for tools (e.g. linters, refactoring tools and documentation generators) that
+//! operate on the syntax of a function but need its resolved types and callees.
+
+use cairo_lang_defs::ids::FunctionWithBodyId;
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_syntax::node::ast;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::db::SemanticGroup;
+use crate::{Expr, ExprId, FunctionId, TypeId};
+
+#[cfg(test)]
+#[path = "typed_syntax_test.rs"]
+mod test;
+
+/// A conversion applied implicitly by the compiler to the value of an expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImplicitConversion {
+    /// The value is snapshotted, e.g. the receiver of a method taking `self` by snapshot, or the
+    /// operands of an operator implemented on snapshots.
+    Snapshot,
+    /// The value is desnapped.
+    Desnap,
+}
+
+/// An expression syntax node of a function body, annotated with its semantic information.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedExpr {
+    /// The semantic expression computed for the syntax node, before any implicit conversion.
+    pub expr_id: ExprId,
+    pub stable_ptr: ast::ExprPtr,
+    /// The resolved type of the expression, before any implicit conversion.
+    pub ty: TypeId,
+    /// The function called by the expression, if it is a function call. This includes the calls
+    /// the expression is desugared into, e.g. the trait function implementing an operator, or the
+    /// resolved impl function of a method call.
+    pub callee: Option<FunctionId>,
+    /// The implicit conversions applied to the value of the expression, in order of application.
+    pub conversions: Vec<ImplicitConversion>,
+    /// The type of the value of the expression after the implicit conversions.
+    pub converted_ty: TypeId,
+}
+
+/// The typed expressions of a function body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedSyntax {
+    /// The typed expressions, in the order of their computation.
+    pub exprs: Vec<TypedExpr>,
+    /// Maps the stable pointers of the expressions to their index in `exprs`. If several semantic
+    /// expressions were computed for the same syntax node, the last one is used.
+    lookup: UnorderedHashMap<ast::ExprPtr, usize>,
+}
+impl TypedSyntax {
+    /// Returns the typed expression of the syntax node pointed to by `stable_ptr`, if any.
+    pub fn expr_by_ptr(&self, stable_ptr: ast::ExprPtr) -> Option<&TypedExpr> {
+        self.lookup.get(&stable_ptr).map(|idx| &self.exprs[*idx])
+    }
+}
+
+/// Returns the expressions of the body of `function_id`, annotated with their semantic
+/// information.
+///
+/// The snapshots and desnaps added by the compiler on top of an expression share its syntax node,
+/// and are reported as its implicit conversions rather than as separate expressions.
+pub fn function_typed_syntax(
+    db: &dyn SemanticGroup,
+    function_id: FunctionWithBodyId,
+) -> Maybe<TypedSyntax> {
+    let body = db.function_body(function_id)?;
+    let mut exprs: Vec<TypedExpr> = vec![];
+    // Maps the semantic expressions to the index of their typed expression.
+    let mut expr_indices = UnorderedHashMap::<ExprId, usize>::default();
+    let mut lookup = UnorderedHashMap::default();
+    for (expr_id, expr) in body.exprs.iter() {
+        let stable_ptr = expr.stable_ptr();
+        let conversion = match expr {
+            Expr::Snapshot(snapshot) => Some((snapshot.inner, ImplicitConversion::Snapshot)),
+            Expr::Desnap(desnap) => Some((desnap.inner, ImplicitConversion::Desnap)),
+            _ => None,
+        }
+        .filter(|(inner, _)| body.exprs[*inner].stable_ptr() == stable_ptr);
+        if let Some((inner, conversion)) = conversion {
+            let idx = expr_indices[&inner];
+            let typed_expr = &mut exprs[idx];
+            typed_expr.conversions.push(conversion);
+            typed_expr.converted_ty = expr.ty();
+            expr_indices.insert(expr_id, idx);
+            continue;
+        }
+        let callee = match expr {
+            Expr::FunctionCall(call) => Some(call.function),
+            _ => None,
+        };
+        expr_indices.insert(expr_id, exprs.len());
+        lookup.insert(stable_ptr, exprs.len());
+        exprs.push(TypedExpr {
+            expr_id,
+            stable_ptr,
+            ty: expr.ty(),
+            callee,
+            conversions: vec![],
+            converted_ty: expr.ty(),
+        });
+    }
+    Ok(TypedSyntax { exprs, lookup })
+}
@@ -0,0 +1,59 @@
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use pretty_assertions::assert_eq;
+
+use super::function_typed_syntax;
+use crate::test_utils::{setup_test_function, SemanticDatabaseForTesting};
+
+#[test]
+fn test_function_typed_syntax() {
+    let db = &SemanticDatabaseForTesting::default();
+    let test_function = setup_test_function(
+        db,
+        indoc::indoc! {"
+            fn foo(a: felt252, b: felt252) -> bool {
+                let c = a + b;
+                c == a
+            }
+        "},
+        "foo",
+        "",
+    )
+    .unwrap();
+    let typed_syntax = function_typed_syntax(db, test_function.function_id).unwrap();
+    let typed_exprs = typed_syntax
+        .exprs
+        .iter()
+        .map(|typed_expr| {
+            let syntax = typed_expr.stable_ptr.lookup(db).as_syntax_node();
+            let mut line =
+                format!("{}: {}", syntax.get_text_without_trivia(db), typed_expr.ty.format(db));
+            if !typed_expr.conversions.is_empty() {
+                line += &format!(
+                    ", converted by {:?} to {}",
+                    typed_expr.conversions,
+                    typed_expr.converted_ty.format(db)
+                );
+            }
+            if let Some(callee) = typed_expr.callee {
+                line += &format!(", calls {}", callee.full_name(db));
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        typed_exprs,
+        [
+            "a: core::felt252",
+            "b: core::felt252",
+            "a + b: core::felt252, calls core::Felt252Add::add",
+            "c: core::felt252, converted by [Snapshot] to @core::felt252",
+            "a: core::felt252, converted by [Snapshot] to @core::felt252",
+            "c == a: core::bool, calls core::Felt252PartialEq::eq",
+            "{\n    let c = a + b;\n    c == a\n}: core::bool",
+        ]
+    );
+
+    // The syntax nodes are looked up with their conversions.
+    let c = &typed_syntax.exprs[3];
+    assert_eq!(typed_syntax.expr_by_ptr(c.stable_ptr), Some(c));
+}
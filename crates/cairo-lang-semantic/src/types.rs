@@ -5,8 +5,6 @@ use cairo_lang_defs::ids::{
 };
 use cairo_lang_diagnostics::{DiagnosticAdded, Maybe};
 use cairo_lang_proc_macros::SemanticObject;
-use cairo_lang_syntax::attribute::consts::{MUST_USE_ATTR, UNSTABLE_ATTR};
-use cairo_lang_syntax::attribute::structured::Attribute;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::{ast, TypedStablePtr, TypedSyntaxNode};
 use cairo_lang_utils::{define_short_id, try_extract_matches, OptionFrom};
@@ -25,7 +23,7 @@ use crate::diagnostic::{NotFoundItemType, SemanticDiagnostics};
 use crate::expr::compute::{compute_expr_semantic, ComputationContext, Environment};
 use crate::expr::inference::canonic::ResultNoErrEx;
 use crate::expr::inference::{InferenceData, InferenceError, InferenceId, TypeVar};
-use crate::items::attribute::SemanticQueryAttrs;
+use crate::items::attribute::UsageAttributes;
 use crate::items::constant::{resolve_const_expr_and_evaluate, ConstValue, ConstValueId};
 use crate::items::imp::{ImplId, ImplLookupContext};
 use crate::resolve::{ResolvedConcreteItem, Resolver};
@@ -220,21 +218,21 @@ impl ConcreteTypeId {
             )
         }
     }
-    /// Returns whether the type has the `#[must_use]` attribute.
-    pub fn is_must_use(&self, db: &dyn SemanticGroup) -> Maybe<bool> {
+    /// Returns the attributes of the type which affect its usages.
+    pub fn usage_attributes(&self, db: &dyn SemanticGroup) -> Maybe<UsageAttributes> {
         match self {
-            ConcreteTypeId::Struct(id) => id.has_attr(db, MUST_USE_ATTR),
-            ConcreteTypeId::Enum(id) => id.has_attr(db, MUST_USE_ATTR),
-            ConcreteTypeId::Extern(_) => Ok(false),
+            ConcreteTypeId::Struct(id) => db.struct_usage_attributes(id.struct_id(db)),
+            ConcreteTypeId::Enum(id) => db.enum_usage_attributes(id.enum_id(db)),
+            ConcreteTypeId::Extern(_) => Ok(UsageAttributes::default()),
         }
     }
-    /// Returns the attribute if a type has the `#[unstable(feature: "some-string")]` attribute.
-    pub fn unstable_attr(&self, db: &dyn SemanticGroup) -> Maybe<Option<Attribute>> {
-        match self {
-            ConcreteTypeId::Struct(id) => id.find_attr(db, UNSTABLE_ATTR),
-            ConcreteTypeId::Enum(id) => id.find_attr(db, UNSTABLE_ATTR),
-            ConcreteTypeId::Extern(_) => Ok(None),
-        }
+    /// Returns whether the type has the `#[must_use]` attribute.
+    pub fn is_must_use(&self, db: &dyn SemanticGroup) -> Maybe<bool> {
+        Ok(self.usage_attributes(db)?.must_use)
+    }
+    /// Returns the feature if a type has the `#[unstable(feature: "some-string")]` attribute.
+    pub fn unstable_feature(&self, db: &dyn SemanticGroup) -> Maybe<Option<SmolStr>> {
+        Ok(self.usage_attributes(db)?.unstable_feature)
     }
     /// Returns true if the type does not depend on any generics.
     pub fn is_fully_concrete(&self, db: &dyn SemanticGroup) -> bool {
@@ -0,0 +1,44 @@
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, FunctionId};
+use cairo_lang_sierra::program::StatementIdx;
+
+/// The statement introducing an unknown ap change.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnknownApChangeCause {
+    /// A libfunc with an unknown ap change is invoked at the statement, e.g. `revoke_ap_tracking`
+    /// or `felt252_dict_squash`.
+    Libfunc { idx: StatementIdx, libfunc_id: ConcreteLibfuncId },
+    /// A function already in the call chain is called again at the statement.
+    RecursiveCall { idx: StatementIdx, function: FunctionId },
+    /// The statement jumps back to a previous statement of the function.
+    Loop { idx: StatementIdx, target: StatementIdx },
+}
+
+/// The reason a function has an unknown ap change, so its callers need to allocate locals to
+/// access values from before the call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownApChangeBlame {
+    /// The calls leading from the function to the function of the cause, as the statements of the
+    /// calls along with the called functions.
+    pub call_chain: Vec<(StatementIdx, FunctionId)>,
+    /// The statement introducing the unknown ap change.
+    pub cause: UnknownApChangeCause,
+}
+
+impl std::fmt::Display for UnknownApChangeBlame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, function) in &self.call_chain {
+            write!(f, "calls `{function}` at #{idx}, which ")?;
+        }
+        match &self.cause {
+            UnknownApChangeCause::Libfunc { idx, libfunc_id } => {
+                write!(f, "invokes `{libfunc_id}` with an unknown ap change at #{idx}")
+            }
+            UnknownApChangeCause::RecursiveCall { idx, function } => {
+                write!(f, "recursively calls `{function}` at #{idx}")
+            }
+            UnknownApChangeCause::Loop { idx, target } => {
+                write!(f, "jumps back to #{target} at #{idx}")
+            }
+        }
+    }
+}
@@ -8,11 +8,17 @@ use cairo_lang_sierra_type_size::{get_type_size_map, TypeSizeMap};
 use cairo_lang_utils::casts::IntoOrPanic;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::{Entry, UnorderedHashMap};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 
+use crate::ap_change_blame::{UnknownApChangeBlame, UnknownApChangeCause};
 use crate::ap_change_info::ApChangeInfo;
 use crate::core_libfunc_ap_change::{self, InvocationApChangeInfoProvider};
 use crate::{ApChange, ApChangeError};
 
+#[cfg(test)]
+#[path = "compute_test.rs"]
+mod test;
+
 /// Helper to implement the `InvocationApChangeInfoProvider` for the equation generation.
 struct InvocationApChangeInfoProviderForEqGen<'a, TokenUsages: Fn(CostTokenType) -> usize> {
     /// Registry for providing the sizes of the types.
@@ -317,6 +323,69 @@ impl<'a, TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>
         }
     }
 
+    /// Returns the reason the ap change from the statement to the furthest return is unknown, or
+    /// `None` if it is known.
+    ///
+    /// Follows the unknown branches of the statements, preferring blaming a statement itself over
+    /// its continuations, and the calls into functions with unknown ap changes.
+    fn unknown_ap_change_blame(
+        &self,
+        mut idx: StatementIdx,
+    ) -> Result<Option<UnknownApChangeBlame>, ApChangeError> {
+        if self.known_ap_change_to_return.contains_key(&idx) {
+            return Ok(None);
+        }
+        let mut call_chain = vec![];
+        let mut visited = UnorderedHashSet::<StatementIdx>::default();
+        'statements: loop {
+            visited.insert(idx);
+            let branches = self.get_branches(idx)?;
+            for (ap_change, _) in &branches {
+                if self
+                    .branch_ap_change(idx, ap_change, |id| self.function_ap_change.get(id).cloned())
+                    .is_some()
+                {
+                    continue;
+                }
+                let cause = match ap_change {
+                    ApChange::FunctionCall(id) => {
+                        let entry_point = self.func_entry_point(id)?;
+                        if !visited.contains(&entry_point) {
+                            call_chain.push((idx, id.clone()));
+                            idx = entry_point;
+                            continue 'statements;
+                        }
+                        UnknownApChangeCause::RecursiveCall { idx, function: id.clone() }
+                    }
+                    _ => {
+                        let Some(Statement::Invocation(invocation)) =
+                            self.program.get_statement(&idx)
+                        else {
+                            unreachable!("Only invocations have branches.");
+                        };
+                        UnknownApChangeCause::Libfunc {
+                            idx,
+                            libfunc_id: invocation.libfunc_id.clone(),
+                        }
+                    }
+                };
+                return Ok(Some(UnknownApChangeBlame { call_chain, cause }));
+            }
+            let Some(target) = branches
+                .into_iter()
+                .map(|(_, target)| target)
+                .find(|target| !self.known_ap_change_to_return.contains_key(target))
+            else {
+                unreachable!("A statement with known branches has a known ap change.");
+            };
+            if visited.contains(&target) {
+                let cause = UnknownApChangeCause::Loop { idx, target };
+                return Ok(Some(UnknownApChangeBlame { call_chain, cause }));
+            }
+            idx = target;
+        }
+    }
+
     /// Returns the locals size for a statement.
     fn get_statement_locals(&self, idx: StatementIdx) -> usize {
         self.locals_size.get(&idx).cloned().unwrap_or_default()
@@ -378,3 +447,19 @@ pub fn calc_ap_changes<TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>(
         function_ap_change: helper.function_ap_change,
     })
 }
+
+/// Calculates the reasons of the functions of a program with unknown ap changes, by the functions.
+pub fn calc_unknown_ap_change_blames<TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>(
+    program: &Program,
+    token_usages: TokenUsages,
+) -> Result<OrderedHashMap<FunctionId, UnknownApChangeBlame>, ApChangeError> {
+    let mut helper = ApChangeCalcHelper::new(program, token_usages)?;
+    helper.calc_locals_and_function_ap_changes()?;
+    let mut blames = OrderedHashMap::default();
+    for f in &program.funcs {
+        if let Some(blame) = helper.unknown_ap_change_blame(f.entry_point)? {
+            blames.insert(f.id.clone(), blame);
+        }
+    }
+    Ok(blames)
+}
@@ -0,0 +1,91 @@
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, FunctionId};
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra::ProgramParser;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::calc_unknown_ap_change_blames;
+use crate::ap_change_blame::{UnknownApChangeBlame, UnknownApChangeCause};
+
+#[test]
+fn test_unknown_ap_change_blames() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            libfunc revoke_ap_tracking = revoke_ap_tracking;
+            libfunc jump = jump;
+            libfunc call_revoke = function_call<user@Revoke>;
+            libfunc call_rec = function_call<user@Rec>;
+
+            revoke_ap_tracking() -> ();
+            return();
+            call_revoke() -> ();
+            return();
+            call_rec() -> ();
+            return();
+            return();
+            jump() { 7() };
+
+            Revoke@0() -> ();
+            Main@2() -> ();
+            Rec@4() -> ();
+            Known@6() -> ();
+            Loop@7() -> ();
+        "})
+        .unwrap();
+    let blames = calc_unknown_ap_change_blames(&program, |_, _| 0).unwrap();
+    let revoke = UnknownApChangeCause::Libfunc {
+        idx: StatementIdx(0),
+        libfunc_id: ConcreteLibfuncId::from_string("revoke_ap_tracking"),
+    };
+    assert_eq!(
+        blames.into_iter().collect::<Vec<_>>(),
+        vec![
+            (
+                FunctionId::from_string("Revoke"),
+                UnknownApChangeBlame { call_chain: vec![], cause: revoke.clone() }
+            ),
+            (
+                FunctionId::from_string("Main"),
+                UnknownApChangeBlame {
+                    call_chain: vec![(StatementIdx(2), FunctionId::from_string("Revoke"))],
+                    cause: revoke,
+                }
+            ),
+            (
+                FunctionId::from_string("Rec"),
+                UnknownApChangeBlame {
+                    call_chain: vec![],
+                    cause: UnknownApChangeCause::RecursiveCall {
+                        idx: StatementIdx(4),
+                        function: FunctionId::from_string("Rec"),
+                    },
+                }
+            ),
+            (
+                FunctionId::from_string("Loop"),
+                UnknownApChangeBlame {
+                    call_chain: vec![],
+                    cause: UnknownApChangeCause::Loop {
+                        idx: StatementIdx(7),
+                        target: StatementIdx(7),
+                    },
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_ap_change_blame_display() {
+    let blame = UnknownApChangeBlame {
+        call_chain: vec![(StatementIdx(2), FunctionId::from_string("Revoke"))],
+        cause: UnknownApChangeCause::Libfunc {
+            idx: StatementIdx(0),
+            libfunc_id: ConcreteLibfuncId::from_string("revoke_ap_tracking"),
+        },
+    };
+    assert_eq!(
+        blame.to_string(),
+        "calls `Revoke` at #2, which invokes `revoke_ap_tracking` with an unknown ap change at #0"
+    );
+}
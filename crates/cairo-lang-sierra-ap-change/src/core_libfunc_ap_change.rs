@@ -1,5 +1,6 @@
 use cairo_lang_sierra::extensions::ap_tracking::ApTrackingConcreteLibfunc;
 use cairo_lang_sierra::extensions::array::ArrayConcreteLibfunc;
+use cairo_lang_sierra::extensions::blake::Blake2sConcreteLibfunc;
 use cairo_lang_sierra::extensions::boolean::BoolConcreteLibfunc;
 use cairo_lang_sierra::extensions::boxing::BoxConcreteLibfunc;
 use cairo_lang_sierra::extensions::bytes31::Bytes31ConcreteLibfunc;
@@ -222,9 +223,13 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
             Uint256Concrete::Divmod(_) => vec![ApChange::Known(19)],
             Uint256Concrete::SquareRoot(_) => vec![ApChange::Known(25)],
             Uint256Concrete::InvModN(_) => vec![ApChange::Known(46), ApChange::Known(14)],
+            Uint256Concrete::GuaranteeWideMul(_) => vec![ApChange::Known(22)],
+            Uint256Concrete::DivModU128(_) => vec![ApChange::Known(17)],
+            Uint256Concrete::GuaranteeMulModN(_) => vec![ApChange::Known(65)],
         },
         Uint512(libfunc) => match libfunc {
             Uint512Concrete::DivModU256(_) => vec![ApChange::Known(43)],
+            Uint512Concrete::Operation(_) => vec![ApChange::Known(19), ApChange::Known(19)],
         },
         Sint8(libfunc) => sint_ap_change(libfunc),
         Sint16(libfunc) => sint_ap_change(libfunc),
@@ -240,6 +245,7 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
             Sint128Concrete::Operation(_) => {
                 vec![ApChange::Known(3), ApChange::Known(4), ApChange::Known(4)]
             }
+            Sint128Concrete::BoundedOperation(_) => vec![ApChange::Known(5)],
             Sint128Concrete::Diff(_) => vec![ApChange::Known(2), ApChange::Known(3)],
         },
         Mem(libfunc) => match libfunc {
@@ -284,6 +290,11 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
         Poseidon(libfunc) => match libfunc {
             PoseidonConcreteLibfunc::HadesPermutation(_) => vec![ApChange::Known(0)],
         },
+        Blake2s(libfunc) => match libfunc {
+            Blake2sConcreteLibfunc::Compress(_) | Blake2sConcreteLibfunc::Finalize(_) => {
+                vec![ApChange::Known(4025)]
+            }
+        },
         StarkNet(libfunc) => match libfunc {
             StarkNetConcreteLibfunc::ClassHashConst(_)
             | StarkNetConcreteLibfunc::ContractAddressConst(_) => vec![ApChange::Known(0)],
@@ -347,7 +358,9 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
             }
         },
         Const(libfunc) => match libfunc {
-            ConstConcreteLibfunc::AsBox(_) => vec![ApChange::Known(3)],
+            ConstConcreteLibfunc::AsBox(libfunc) => {
+                vec![ApChange::Known(if libfunc.is_span { 5 } else { 3 })]
+            }
             ConstConcreteLibfunc::AsImmediate(_) => vec![ApChange::Known(0)],
         },
         Coupon(libfunc) => match libfunc {
@@ -394,6 +407,7 @@ fn sint_ap_change<TSintTraits: SintTraits + IntMulTraits + IsZeroTraits>(
         SintConcrete::Operation(_) => {
             vec![ApChange::Known(4), ApChange::Known(4), ApChange::Known(4)]
         }
+        SintConcrete::BoundedOperation(_) => vec![ApChange::Known(6)],
         SintConcrete::Diff(_) => vec![ApChange::Known(2), ApChange::Known(3)],
     }
 }
@@ -13,6 +13,7 @@ use generate_equations::{Effects, Var};
 use itertools::Itertools;
 use thiserror::Error;
 
+pub mod ap_change_blame;
 pub mod ap_change_info;
 /// Direct linear computation of AP-Changes instead of equation solver.
 pub mod compute;
@@ -1,6 +1,7 @@
 use std::iter;
 
 use cairo_lang_sierra::extensions::array::ArrayConcreteLibfunc;
+use cairo_lang_sierra::extensions::blake::Blake2sConcreteLibfunc;
 use cairo_lang_sierra::extensions::boolean::BoolConcreteLibfunc;
 use cairo_lang_sierra::extensions::boxing::BoxConcreteLibfunc;
 use cairo_lang_sierra::extensions::bytes31::Bytes31ConcreteLibfunc;
@@ -380,6 +381,14 @@ pub fn core_libfunc_cost(
                 pre_cost: PreCost::builtin(CostTokenType::Poseidon),
             }],
         },
+        Blake2s(libfunc) => match libfunc {
+            Blake2sConcreteLibfunc::Compress(_) | Blake2sConcreteLibfunc::Finalize(_) => {
+                vec![BranchCost::Regular {
+                    const_cost: ConstCost { steps: 5979, holes: 0, range_checks: 1920 },
+                    pre_cost: PreCost(OrderedHashMap::from_iter([(CostTokenType::Bitwise, 337)])),
+                }]
+            }
+        },
         StarkNet(libfunc) => {
             starknet_libfunc_cost_base(libfunc).into_iter().map(BranchCost::from).collect()
         }
@@ -409,7 +418,9 @@ pub fn core_libfunc_cost(
             ],
         },
         Const(libfunc) => match libfunc {
-            ConstConcreteLibfunc::AsBox(_) => vec![ConstCost::steps(3).into()],
+            ConstConcreteLibfunc::AsBox(libfunc) => {
+                vec![ConstCost::steps(if libfunc.is_span { 6 } else { 3 }).into()]
+            }
             ConstConcreteLibfunc::AsImmediate(_) => vec![ConstCost::steps(0).into()],
         },
         Coupon(libfunc) => match libfunc {
@@ -679,6 +690,13 @@ fn u256_libfunc_cost(libfunc: &Uint256Concrete) -> Vec<ConstCost> {
             ConstCost { steps: 40, holes: 0, range_checks: 9 },
             ConstCost { steps: 25, holes: 0, range_checks: 7 },
         ],
+        Uint256Concrete::GuaranteeWideMul(_) => {
+            vec![ConstCost { steps: 19, holes: 0, range_checks: 6 }]
+        }
+        Uint256Concrete::DivModU128(_) => vec![ConstCost { steps: 19, holes: 0, range_checks: 5 }],
+        Uint256Concrete::GuaranteeMulModN(_) => {
+            vec![ConstCost { steps: 66, holes: 0, range_checks: 18 }]
+        }
     }
 }
 
@@ -686,6 +704,10 @@ fn u256_libfunc_cost(libfunc: &Uint256Concrete) -> Vec<ConstCost> {
 fn u512_libfunc_cost(libfunc: &Uint512Concrete) -> Vec<ConstCost> {
     match libfunc {
         Uint512Concrete::DivModU256(_) => vec![ConstCost { steps: 47, holes: 0, range_checks: 12 }],
+        Uint512Concrete::Operation(_) => vec![
+            ConstCost { steps: 24, holes: 0, range_checks: 4 },
+            ConstCost { steps: 24, holes: 0, range_checks: 4 },
+        ],
     }
 }
 
@@ -712,6 +734,9 @@ fn sint_libfunc_cost<TSintTraits: SintTraits + IsZeroTraits + IntMulTraits>(
             ConstCost { steps: 6, holes: 0, range_checks: 1 }.into(),
             ConstCost { steps: 6, holes: 0, range_checks: 1 }.into(),
         ],
+        SintConcrete::BoundedOperation(_) => {
+            vec![ConstCost { steps: 9, holes: 0, range_checks: 2 }.into()]
+        }
         SintConcrete::Diff(_) => vec![
             (ConstCost { steps: 3, holes: 0, range_checks: 1 }).into(),
             (ConstCost { steps: 5, holes: 0, range_checks: 1 }).into(),
@@ -743,6 +768,9 @@ fn s128_libfunc_cost(libfunc: &Sint128Concrete) -> Vec<BranchCost> {
             ConstCost { steps: 6, holes: 0, range_checks: 1 }.into(),
             ConstCost { steps: 6, holes: 0, range_checks: 1 }.into(),
         ],
+        Sint128Concrete::BoundedOperation(_) => {
+            vec![ConstCost { steps: 7, holes: 0, range_checks: 1 }.into()]
+        }
         Sint128Concrete::Diff(_) => vec![
             ConstCost { steps: 3, holes: 0, range_checks: 1 }.into(),
             ConstCost { steps: 5, holes: 0, range_checks: 1 }.into(),
@@ -0,0 +1,124 @@
+//! Costs of libfuncs outside of the core libfuncs, supplied by the embedders of the gas computation
+//! (e.g. downstream forks adding experimental libfuncs), instead of the computation failing on the
+//! libfuncs missing from the core registry.
+
+use std::borrow::Cow;
+
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, GenericLibfuncId};
+use cairo_lang_sierra::program::{GenericArg, Program, Statement, StatementIdx};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::objects::{BranchCost, ConstCost, CostInfoProvider};
+use crate::CostError;
+
+#[cfg(test)]
+#[path = "custom_libfunc_cost_test.rs"]
+mod test;
+
+/// A function returning the cost of each branch of a custom libfunc, given the generic arguments
+/// it is specialized with.
+pub type CustomLibfuncCostFn =
+    dyn Fn(&[GenericArg], &dyn CostInfoProvider) -> Vec<ConstCost> + Send + Sync;
+
+/// The cost functions of custom libfunc families, by their generic libfunc ids.
+///
+/// The libfuncs of the registered families are left out of the core program registry, so they may
+/// only use core types, and their costs are only supported by the linear gas solver - see
+/// [crate::compute_precost_info_with_custom_costs] and
+/// [crate::compute_postcost_info_with_custom_costs].
+#[derive(Default)]
+pub struct CustomLibfuncCosts {
+    cost_fns: UnorderedHashMap<GenericLibfuncId, Box<CustomLibfuncCostFn>>,
+}
+impl CustomLibfuncCosts {
+    /// Registers the cost function of a custom libfunc family.
+    pub fn with_cost_fn<CostFn>(mut self, generic_id: GenericLibfuncId, cost_fn: CostFn) -> Self
+    where
+        CostFn: Fn(&[GenericArg], &dyn CostInfoProvider) -> Vec<ConstCost> + Send + Sync + 'static,
+    {
+        self.cost_fns.insert(generic_id, Box::new(cost_fn));
+        self
+    }
+
+    /// Returns the program without its custom libfuncs, for creating the registry of its core
+    /// libfuncs. The invocations of the custom libfuncs are replaced by `return`s, which the
+    /// registry doesn't validate.
+    pub(crate) fn core_program<'a>(&self, program: &'a Program) -> Cow<'a, Program> {
+        let custom_libfuncs = self.custom_libfuncs(program);
+        if custom_libfuncs.is_empty() {
+            return Cow::Borrowed(program);
+        }
+        Cow::Owned(Program {
+            type_declarations: program.type_declarations.clone(),
+            libfunc_declarations: program
+                .libfunc_declarations
+                .iter()
+                .filter(|declaration| !custom_libfuncs.contains_key(&declaration.id))
+                .cloned()
+                .collect(),
+            statements: program
+                .statements
+                .iter()
+                .map(|statement| match statement {
+                    Statement::Invocation(invocation)
+                        if custom_libfuncs.contains_key(&invocation.libfunc_id) =>
+                    {
+                        Statement::Return(vec![])
+                    }
+                    _ => statement.clone(),
+                })
+                .collect(),
+            funcs: program.funcs.clone(),
+        })
+    }
+
+    /// Returns the costs of the branches of the custom libfuncs of the program, by their concrete
+    /// ids, verifying that they match the branches of their invocations.
+    pub(crate) fn libfunc_costs(
+        &self,
+        program: &Program,
+        info_provider: &dyn CostInfoProvider,
+    ) -> Result<UnorderedHashMap<ConcreteLibfuncId, Vec<BranchCost>>, CostError> {
+        let libfunc_costs: UnorderedHashMap<_, Vec<BranchCost>> = self
+            .custom_libfuncs(program)
+            .into_iter()
+            .map(|(id, (cost_fn, generic_args))| {
+                (id, cost_fn(generic_args, info_provider).into_iter().map(Into::into).collect())
+            })
+            .collect();
+        for (idx, statement) in program.statements.iter().enumerate() {
+            let Statement::Invocation(invocation) = statement else {
+                continue;
+            };
+            if let Some(costs) = libfunc_costs.get(&invocation.libfunc_id) {
+                if costs.len() != invocation.branches.len() {
+                    return Err(CostError::CustomLibfuncBranchCountMismatch(
+                        invocation.libfunc_id.clone(),
+                        StatementIdx(idx),
+                    ));
+                }
+            }
+        }
+        Ok(libfunc_costs)
+    }
+
+    /// Returns the custom libfuncs declared by the program, along with their cost functions and
+    /// generic arguments.
+    fn custom_libfuncs<'a>(
+        &'a self,
+        program: &'a Program,
+    ) -> OrderedHashMap<ConcreteLibfuncId, (&'a CustomLibfuncCostFn, &'a [GenericArg])> {
+        program
+            .libfunc_declarations
+            .iter()
+            .filter_map(|declaration| {
+                let cost_fn = self.cost_fns.get(&declaration.long_id.generic_id)?;
+                Some((
+                    declaration.id.clone(),
+                    (cost_fn.as_ref(), declaration.long_id.generic_args.as_slice()),
+                ))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,77 @@
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::ids::ConcreteLibfuncId;
+use cairo_lang_sierra::program::{GenericArg, Program, StatementIdx};
+use cairo_lang_sierra::ProgramParser;
+use cairo_lang_utils::extract_matches;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+
+use super::CustomLibfuncCosts;
+use crate::objects::{ConstCost, CostInfoProvider};
+use crate::{
+    compute_postcost_info_with_custom_costs, compute_precost_info,
+    compute_precost_info_with_custom_costs, CostError,
+};
+
+/// Returns a program calling the custom libfunc `hash`.
+fn program() -> Program {
+    ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc hash_felt252 = hash<felt252>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            hash_felt252([0]) -> ([1]);
+            store_temp_felt252([1]) -> ([1]);
+            return([1]);
+
+            test::foo@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap()
+}
+
+/// Returns the custom costs of the `hash` libfunc, with the given number of branches.
+fn custom_costs(branches: usize) -> CustomLibfuncCosts {
+    CustomLibfuncCosts::default().with_cost_fn(
+        "hash".into(),
+        move |generic_args: &[GenericArg], info_provider: &dyn CostInfoProvider| {
+            let ty = extract_matches!(&generic_args[0], GenericArg::Type);
+            let steps = 3 * info_provider.type_size(ty) as i32;
+            vec![ConstCost { steps, holes: 0, range_checks: 1 }; branches]
+        },
+    )
+}
+
+#[test]
+fn test_custom_libfunc_costs() {
+    let program = program();
+    assert!(matches!(compute_precost_info(&program), Err(CostError::ProgramRegistryError(_))));
+
+    let custom_costs = custom_costs(1);
+    let precost_info = compute_precost_info_with_custom_costs(&program, &custom_costs).unwrap();
+    let postcost_info = compute_postcost_info_with_custom_costs::<i32>(
+        &program,
+        &|_| 0,
+        &precost_info,
+        &Default::default(),
+        &custom_costs,
+    )
+    .unwrap();
+    // The custom libfunc costs 3 steps and a range check, and the store costs a step.
+    assert_eq!(
+        postcost_info.function_costs.values().collect::<Vec<_>>(),
+        vec![&OrderedHashMap::from_iter([(CostTokenType::Const, 470)])]
+    );
+}
+
+#[test]
+fn test_custom_libfunc_branch_count_mismatch() {
+    assert_eq!(
+        compute_precost_info_with_custom_costs(&program(), &custom_costs(2)),
+        Err(CostError::CustomLibfuncBranchCountMismatch(
+            ConcreteLibfuncId::from_string("hash_felt252"),
+            StatementIdx(0)
+        ))
+    );
+}
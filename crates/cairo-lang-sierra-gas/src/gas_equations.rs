@@ -0,0 +1,175 @@
+//! The gas equations of a program along with their solution, as an inspectable artifact explaining
+//! the gas values of the statements of the program.
+
+use core::fmt;
+
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::cost_expr::{CostExpr, Var};
+
+/// A variable of the gas equations of a single cost token type.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GasVariable {
+    /// The gas supplied for the libfunc at the statement (e.g. the amount withdrawn by
+    /// `withdraw_gas`, or burnt by `branch_align`).
+    LibfuncImplicitGas(StatementIdx),
+    /// The future cost of the statement (of any route from it to a return).
+    StatementFuture(StatementIdx),
+}
+impl GasVariable {
+    /// Returns the statement of the variable.
+    pub fn statement_idx(&self) -> StatementIdx {
+        match self {
+            GasVariable::LibfuncImplicitGas(idx) | GasVariable::StatementFuture(idx) => *idx,
+        }
+    }
+}
+impl From<&Var> for GasVariable {
+    fn from(var: &Var) -> Self {
+        match var {
+            Var::LibfuncImplicitGasVariable(idx, _) => GasVariable::LibfuncImplicitGas(*idx),
+            Var::StatementFuture(idx, _) => GasVariable::StatementFuture(*idx),
+        }
+    }
+}
+impl fmt::Display for GasVariable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasVariable::LibfuncImplicitGas(idx) => write!(f, "libfunc#{}", idx.0),
+            GasVariable::StatementFuture(idx) => write!(f, "future#{}", idx.0),
+        }
+    }
+}
+
+/// A single term of a gas equation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasTerm {
+    pub coefficient: i64,
+    pub variable: GasVariable,
+}
+
+/// A linear gas equation, stating that the sum of its terms and its constant is zero.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasEquation {
+    pub terms: Vec<GasTerm>,
+    pub constant: i64,
+}
+impl GasEquation {
+    /// Returns whether the equation has a variable of the given statement.
+    pub fn involves(&self, idx: StatementIdx) -> bool {
+        self.terms.iter().any(|term| term.variable.statement_idx() == idx)
+    }
+}
+impl From<&CostExpr> for GasEquation {
+    fn from(expr: &CostExpr) -> Self {
+        Self {
+            terms: expr
+                .var_to_coef
+                .iter()
+                .filter(|(_, coefficient)| **coefficient != 0)
+                .map(|(var, coefficient)| GasTerm {
+                    coefficient: *coefficient,
+                    variable: var.into(),
+                })
+                .collect(),
+            constant: expr.const_term.into(),
+        }
+    }
+}
+impl fmt::Display for GasEquation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, GasTerm { coefficient, variable }) in self.terms.iter().enumerate() {
+            let sign = if *coefficient < 0 { "-" } else { "+" };
+            match (i, coefficient.abs()) {
+                (0, 1) if *coefficient < 0 => write!(f, "-{variable}")?,
+                (0, 1) => write!(f, "{variable}")?,
+                (0, _) => write!(f, "{coefficient}*{variable}")?,
+                (_, 1) => write!(f, " {sign} {variable}")?,
+                (_, abs) => write!(f, " {sign} {abs}*{variable}")?,
+            }
+        }
+        match (self.terms.is_empty(), self.constant) {
+            (true, constant) => write!(f, "{constant}")?,
+            (false, 0) => {}
+            (false, constant) if constant < 0 => write!(f, " - {}", -constant)?,
+            (false, constant) => write!(f, " + {constant}")?,
+        }
+        write!(f, " = 0")
+    }
+}
+
+/// The value assigned to a variable by the solver.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasAssignment {
+    pub variable: GasVariable,
+    pub value: i64,
+}
+
+/// The gas equations of a single cost token type along with their solution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenGasEquations {
+    pub token_type: CostTokenType,
+    pub equations: Vec<GasEquation>,
+    /// The values assigned to the variables of the equations, sorted by variable.
+    pub solution: Vec<GasAssignment>,
+}
+impl TokenGasEquations {
+    /// Creates the equations of a token type, sorting the solution by variable.
+    pub(crate) fn new(
+        token_type: CostTokenType,
+        equations: &[CostExpr],
+        solution: &OrderedHashMap<Var, i64>,
+    ) -> Self {
+        Self {
+            token_type,
+            equations: equations.iter().map(GasEquation::from).collect(),
+            solution: solution
+                .iter()
+                .map(|(var, value)| GasAssignment { variable: var.into(), value: *value })
+                .sorted_by(|a, b| a.variable.cmp(&b.variable))
+                .collect(),
+        }
+    }
+
+    /// Returns the value assigned to the variable by the solver, if it has any.
+    pub fn value(&self, variable: &GasVariable) -> Option<i64> {
+        self.solution
+            .binary_search_by(|assignment| assignment.variable.cmp(variable))
+            .ok()
+            .map(|i| self.solution[i].value)
+    }
+}
+
+/// The gas equations solved for a program, by cost token type, along with their solution.
+///
+/// Recorded while calculating the gas information of a program, for explaining why a statement
+/// (e.g. a `withdraw_gas`) got its value.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasEquations {
+    pub tokens: Vec<TokenGasEquations>,
+}
+impl GasEquations {
+    /// Returns the equations involving the variables of the given statement, by their token types.
+    pub fn explain(
+        &self,
+        idx: StatementIdx,
+    ) -> impl Iterator<Item = (CostTokenType, &GasEquation)> + '_ {
+        self.tokens.iter().flat_map(move |token| {
+            token
+                .equations
+                .iter()
+                .filter(move |eq| eq.involves(idx))
+                .map(|eq| (token.token_type, eq))
+        })
+    }
+
+    /// Returns the value assigned to the variable of the given token type by the solver, if it has
+    /// any.
+    pub fn value(&self, token_type: CostTokenType, variable: &GasVariable) -> Option<i64> {
+        self.tokens.iter().find(|token| token.token_type == token_type)?.value(variable)
+    }
+}
@@ -1,12 +1,18 @@
 use std::fmt::Display;
 
 use cairo_lang_sierra::extensions::branch_align::BranchAlignLibfunc;
-use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::extensions::function_call::{CouponCallLibfunc, FunctionCallLibfunc};
+use cairo_lang_sierra::extensions::gas::{
+    BuiltinCostWithdrawGasLibfunc, CostTokenType, RedepositGasLibfunc, WithdrawGasLibfunc,
+};
 use cairo_lang_sierra::extensions::NamedLibfunc;
-use cairo_lang_sierra::ids::FunctionId;
-use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
-use cairo_lang_utils::collection_arithmetics::sub_maps;
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, FunctionId};
+use cairo_lang_sierra::program::{
+    ConcreteLibfuncLongId, GenericArg, Program, Statement, StatementIdx,
+};
+use cairo_lang_utils::collection_arithmetics::{add_maps, sub_maps};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use itertools::{chain, Itertools};
 
 /// Gas information for a Sierra program.
@@ -17,7 +23,101 @@ pub struct GasInfo {
     /// The costs of calling the given function.
     pub function_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i64>>,
 }
+/// Gas withdrawn without being used, at a single statement of a solved program.
+#[derive(Debug, Eq, PartialEq)]
+pub struct GasSlack {
+    /// The `branch_align` or `redeposit_gas` statement accounting for the unused gas.
+    pub statement_idx: StatementIdx,
+    /// The token type of the unused gas.
+    pub token_type: CostTokenType,
+    /// The amount of unused gas.
+    pub amount: i64,
+}
+
+/// The worst-case gas consumption of a function of a solved program.
+#[derive(Debug, Eq, PartialEq)]
+pub enum WorstCaseGas {
+    /// The function consumes at most the given gas, by cost token type.
+    Bounded(OrderedHashMap<CostTokenType, i64>),
+    /// The function may withdraw gas in a loop or a recursion, so its consumption is bounded only
+    /// by the gas available to it.
+    Unbounded,
+}
+
 impl GasInfo {
+    /// Returns the worst-case gas consumption of each function of the program, in the order of the
+    /// program's functions.
+    ///
+    /// The consumption of a function is the gas required for calling it, which already covers its
+    /// most expensive branch up to its withdrawals of gas, and the gas it may withdraw on the way -
+    /// maximized over its branches, including the withdrawals of the functions it calls. The
+    /// maximum is taken for each token type separately, so the result is an upper bound even if
+    /// different branches are the most expensive for different token types.
+    pub fn worst_case_function_costs(
+        &self,
+        program: &Program,
+    ) -> OrderedHashMap<FunctionId, WorstCaseGas> {
+        let mut computer = WithdrawalsComputer {
+            program,
+            gas_info: self,
+            libfuncs: program
+                .libfunc_declarations
+                .iter()
+                .map(|declaration| (&declaration.id, &declaration.long_id))
+                .collect(),
+            withdrawals: Default::default(),
+        };
+        program
+            .funcs
+            .iter()
+            .map(|func| {
+                let cost = computer
+                    .statement_withdrawals(func.entry_point)
+                    .map(|withdrawals| {
+                        WorstCaseGas::Bounded(add_maps(
+                            self.function_costs.get(&func.id).cloned().unwrap_or_default(),
+                            withdrawals,
+                        ))
+                    })
+                    .unwrap_or(WorstCaseGas::Unbounded);
+                (func.id.clone(), cost)
+            })
+            .collect()
+    }
+
+    /// Returns the slack of the gas solution, sorted by statement index.
+    ///
+    /// Gas is withdrawn for the most expensive branch, so on cheaper branches the difference is
+    /// either redeposited by `redeposit_gas` or spent by `branch_align`. Large slack suggests
+    /// restructuring the code so that the branches cost about the same.
+    pub fn slack(&self, program: &Program) -> Vec<GasSlack> {
+        let slack_libfunc_ids: UnorderedHashSet<_> = program
+            .libfunc_declarations
+            .iter()
+            .filter(|fd| {
+                [BranchAlignLibfunc::STR_ID, RedepositGasLibfunc::STR_ID]
+                    .contains(&fd.long_id.generic_id.0.as_str())
+            })
+            .map(|fd| &fd.id)
+            .collect();
+        self.variable_values
+            .iter()
+            .filter(|((idx, _), amount)| {
+                **amount > 0
+                    && matches!(
+                        program.get_statement(idx),
+                        Some(Statement::Invocation(x)) if slack_libfunc_ids.contains(&x.libfunc_id)
+                    )
+            })
+            .map(|((statement_idx, token_type), amount)| GasSlack {
+                statement_idx: *statement_idx,
+                token_type: *token_type,
+                amount: *amount,
+            })
+            .sorted_by_key(|slack| slack.statement_idx.0)
+            .collect()
+    }
+
     pub fn combine(mut self, mut other: GasInfo) -> GasInfo {
         let variable_values = chain!(self.variable_values.keys(), other.variable_values.keys())
             .unique()
@@ -131,3 +231,76 @@ impl Display for GasInfo {
         Ok(())
     }
 }
+
+/// The gas withdrawn by the rest of the run starting at a statement, `None` if it is unbounded.
+type Withdrawals = Option<OrderedHashMap<CostTokenType, i64>>;
+
+/// Helper for computing the worst-case gas withdrawn from each statement of a program.
+struct WithdrawalsComputer<'a> {
+    program: &'a Program,
+    gas_info: &'a GasInfo,
+    libfuncs: OrderedHashMap<&'a ConcreteLibfuncId, &'a ConcreteLibfuncLongId>,
+    /// The withdrawals of the handled statements - `None` if the statement is still being handled.
+    withdrawals: OrderedHashMap<StatementIdx, Option<Withdrawals>>,
+}
+impl WithdrawalsComputer<'_> {
+    /// Returns the worst-case gas withdrawn from the given statement until the return of its
+    /// function.
+    fn statement_withdrawals(&mut self, idx: StatementIdx) -> Withdrawals {
+        match self.withdrawals.get(&idx) {
+            Some(Some(withdrawals)) => return withdrawals.clone(),
+            // A cycle - which must withdraw gas in every iteration.
+            Some(None) => return None,
+            None => {}
+        }
+        self.withdrawals.insert(idx, None);
+        let withdrawals = self.compute_statement_withdrawals(idx);
+        self.withdrawals.insert(idx, Some(withdrawals.clone()));
+        withdrawals
+    }
+
+    /// Computes the worst-case gas withdrawn from the given statement, assuming it is not
+    /// memoized.
+    fn compute_statement_withdrawals(&mut self, idx: StatementIdx) -> Withdrawals {
+        let Some(Statement::Invocation(invocation)) = self.program.get_statement(&idx) else {
+            return Some(Default::default());
+        };
+        let long_id = self.libfuncs[&invocation.libfunc_id];
+        let generic_id = long_id.generic_id.0.as_str();
+        let mut local = OrderedHashMap::<CostTokenType, i64>::default();
+        if [FunctionCallLibfunc::STR_ID, CouponCallLibfunc::STR_ID].contains(&generic_id) {
+            let [GenericArg::UserFunc(function_id)] = long_id.generic_args.as_slice() else {
+                unreachable!("Function calls have a single user function generic argument.");
+            };
+            let func = self.program.funcs.iter().find(|func| &func.id == function_id)?;
+            local = self.statement_withdrawals(func.entry_point)?;
+        }
+        let is_withdraw_gas = [WithdrawGasLibfunc::STR_ID, BuiltinCostWithdrawGasLibfunc::STR_ID]
+            .contains(&generic_id);
+        let mut result = OrderedHashMap::<CostTokenType, i64>::default();
+        for (branch_idx, branch) in invocation.branches.iter().enumerate() {
+            let mut branch_withdrawals =
+                add_maps(local.clone(), self.statement_withdrawals(idx.next(&branch.target))?);
+            // The first branch of gas withdrawal is the successful one.
+            if is_withdraw_gas && branch_idx == 0 {
+                for token_type in CostTokenType::iter_casm_tokens() {
+                    let withdrawn = self
+                        .gas_info
+                        .variable_values
+                        .get(&(idx, *token_type))
+                        .copied()
+                        .unwrap_or_default();
+                    *branch_withdrawals.entry(*token_type).or_default() += withdrawn;
+                }
+            }
+            for (token_type, value) in branch_withdrawals {
+                if value == 0 {
+                    continue;
+                }
+                let entry = result.entry(token_type).or_default();
+                *entry = (*entry).max(value);
+            }
+        }
+        Some(result)
+    }
+}
@@ -17,21 +17,27 @@ use compute_costs::PostCostTypeEx;
 use core_libfunc_cost_base::InvocationCostInfoProvider;
 use core_libfunc_cost_expr::CostExprMap;
 use cost_expr::Var;
+use custom_libfunc_cost::CustomLibfuncCosts;
+use gas_equations::{GasEquations, TokenGasEquations};
 use gas_info::GasInfo;
 use generate_equations::StatementFutureCost;
 use itertools::Itertools;
 use objects::CostInfoProvider;
 use thiserror::Error;
+use warm_start::PreviousGasSolution;
 
 pub mod compute_costs;
 pub mod core_libfunc_cost;
 mod core_libfunc_cost_base;
 mod core_libfunc_cost_expr;
 mod cost_expr;
+pub mod custom_libfunc_cost;
+pub mod gas_equations;
 pub mod gas_info;
 mod generate_equations;
 pub mod objects;
 mod starknet_libfunc_cost_base;
+pub mod warm_start;
 
 #[cfg(test)]
 mod test;
@@ -49,6 +55,8 @@ pub enum CostError {
     UnexpectedCycle,
     #[error("failed to enforce function cost")]
     EnforceWalletValueFailed(StatementIdx),
+    #[error("the custom costs of `{0}` don't match the branches of its invocation at #{1}")]
+    CustomLibfuncBranchCountMismatch(ConcreteLibfuncId, StatementIdx),
 }
 
 /// Helper to implement the `InvocationCostInfoProvider` for the equation generation.
@@ -94,6 +102,38 @@ impl CostInfoProvider for TypeSizeMap {
 pub fn calc_gas_precost_info(
     program: &Program,
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+) -> Result<GasInfo, CostError> {
+    calc_gas_precost_info_ex(program, function_set_costs, None, None)
+}
+
+/// Calculates gas pre-cost information for a given program, as [calc_gas_precost_info], along with
+/// the equations solved for it.
+pub fn calc_gas_precost_info_with_equations(
+    program: &Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+) -> Result<(GasInfo, GasEquations), CostError> {
+    let mut equations = GasEquations::default();
+    let info = calc_gas_precost_info_ex(program, function_set_costs, None, Some(&mut equations))?;
+    Ok((info, equations))
+}
+
+/// Calculates gas pre-cost information for a given program, as [calc_gas_precost_info], reusing the
+/// solution of a previous version of the program for its unchanged functions.
+pub fn calc_gas_precost_info_from_previous(
+    program: &Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    previous: &PreviousGasSolution<'_>,
+) -> Result<GasInfo, CostError> {
+    calc_gas_precost_info_ex(program, function_set_costs, Some(previous), None)
+}
+
+/// Calculates gas pre-cost information for a given program, optionally warm-started from a
+/// previous solution, and optionally recording the solved equations.
+fn calc_gas_precost_info_ex(
+    program: &Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    previous: Option<&PreviousGasSolution<'_>>,
+    equations: Option<&mut GasEquations>,
 ) -> Result<GasInfo, CostError> {
     let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
     let mut info = calc_gas_info_inner(
@@ -106,6 +146,8 @@ pub fn calc_gas_precost_info(
         },
         function_set_costs,
         &registry,
+        previous,
+        equations,
     )?;
     // Make `withdraw_gas` and `refund` libfuncs return 0 valued variables for all tokens.
     for (i, statement) in program.statements.iter().enumerate() {
@@ -131,12 +173,26 @@ pub fn calc_gas_precost_info(
 
 /// Calculates gas pre-cost information for a given program - the gas costs of non-step tokens.
 pub fn compute_precost_info(program: &Program) -> Result<GasInfo, CostError> {
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    compute_precost_info_with_custom_costs(program, &CustomLibfuncCosts::default())
+}
+
+/// Calculates gas pre-cost information for a given program, which may use custom libfuncs with the
+/// given costs - see [custom_libfunc_cost].
+pub fn compute_precost_info_with_custom_costs(
+    program: &Program,
+    custom_costs: &CustomLibfuncCosts,
+) -> Result<GasInfo, CostError> {
+    let core_program = custom_costs.core_program(program);
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&core_program)?;
     let type_sizes = get_type_size_map(program, &registry).unwrap();
+    let custom_libfunc_costs = custom_costs.libfunc_costs(program, &type_sizes)?;
 
     compute_costs::compute_costs(
         program,
         &(|libfunc_id| {
+            if let Some(costs) = custom_libfunc_costs.get(libfunc_id) {
+                return costs.clone();
+            }
             let core_libfunc = registry
                 .get_libfunc(libfunc_id)
                 .expect("Program registry creation would have already failed.");
@@ -154,6 +210,65 @@ pub fn calc_gas_postcost_info<ApChangeVarValue: Fn(StatementIdx) -> usize>(
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
     precost_gas_info: &GasInfo,
     ap_change_var_value: ApChangeVarValue,
+) -> Result<GasInfo, CostError> {
+    calc_gas_postcost_info_ex(
+        program,
+        function_set_costs,
+        precost_gas_info,
+        ap_change_var_value,
+        None,
+        None,
+    )
+}
+
+/// Calculates gas postcost information for a given program, as [calc_gas_postcost_info], along
+/// with the equations solved for it.
+pub fn calc_gas_postcost_info_with_equations<ApChangeVarValue: Fn(StatementIdx) -> usize>(
+    program: &Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    precost_gas_info: &GasInfo,
+    ap_change_var_value: ApChangeVarValue,
+) -> Result<(GasInfo, GasEquations), CostError> {
+    let mut equations = GasEquations::default();
+    let info = calc_gas_postcost_info_ex(
+        program,
+        function_set_costs,
+        precost_gas_info,
+        ap_change_var_value,
+        None,
+        Some(&mut equations),
+    )?;
+    Ok((info, equations))
+}
+
+/// Calculates gas postcost information for a given program, as [calc_gas_postcost_info], reusing
+/// the solution of a previous version of the program for its unchanged functions.
+pub fn calc_gas_postcost_info_from_previous<ApChangeVarValue: Fn(StatementIdx) -> usize>(
+    program: &Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    precost_gas_info: &GasInfo,
+    ap_change_var_value: ApChangeVarValue,
+    previous: &PreviousGasSolution<'_>,
+) -> Result<GasInfo, CostError> {
+    calc_gas_postcost_info_ex(
+        program,
+        function_set_costs,
+        precost_gas_info,
+        ap_change_var_value,
+        Some(previous),
+        None,
+    )
+}
+
+/// Calculates gas postcost information for a given program, optionally warm-started from a
+/// previous solution, and optionally recording the solved equations.
+fn calc_gas_postcost_info_ex<ApChangeVarValue: Fn(StatementIdx) -> usize>(
+    program: &Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    precost_gas_info: &GasInfo,
+    ap_change_var_value: ApChangeVarValue,
+    previous: Option<&PreviousGasSolution<'_>>,
+    equations: Option<&mut GasEquations>,
 ) -> Result<GasInfo, CostError> {
     let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
     let type_sizes = get_type_size_map(program, &registry).unwrap();
@@ -178,6 +293,8 @@ pub fn calc_gas_postcost_info<ApChangeVarValue: Fn(StatementIdx) -> usize>(
         },
         function_set_costs,
         &registry,
+        previous,
+        equations,
     )?;
     // Make `refund` libfuncs return 0 valued variables for all tokens.
     for (i, statement) in program.statements.iter().enumerate() {
@@ -201,6 +318,9 @@ pub fn calc_gas_postcost_info<ApChangeVarValue: Fn(StatementIdx) -> usize>(
 }
 
 /// Calculates gas information. Used for both precost and postcost.
+/// If `previous` is given, the solution of its unchanged functions is used for warm-starting the
+/// solver. If `recorded_equations` is given, the equations of each token type are added to it
+/// along with their solution.
 fn calc_gas_info_inner<
     GetCost: Fn(&mut dyn StatementFutureCost, &StatementIdx, &ConcreteLibfuncId) -> Vec<CostExprMap>,
 >(
@@ -208,6 +328,8 @@ fn calc_gas_info_inner<
     get_cost: GetCost,
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
     registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    previous: Option<&PreviousGasSolution<'_>>,
+    mut recorded_equations: Option<&mut GasEquations>,
 ) -> Result<GasInfo, CostError> {
     let mut equations = generate_equations::generate_equations(program, get_cost)?;
     let non_set_cost_func_entry_points: UnorderedHashSet<_> = program
@@ -227,6 +349,7 @@ fn calc_gas_info_inner<
         }
     }
 
+    let previous_values = previous.map(|previous| previous.unchanged_functions_values(program));
     let mut variable_values = OrderedHashMap::default();
     let mut function_costs = OrderedHashMap::default();
     for (token_type, token_equations) in equations {
@@ -277,9 +400,23 @@ fn calc_gas_info_inner<
             }]
             .push(v.clone())
         }
-        let solution =
+        let recorded_token_equations =
+            recorded_equations.is_some().then(|| token_equations.clone());
+        let solution = if let Some(previous_values) = &previous_values {
+            cairo_lang_eq_solver::try_solve_equations_from_previous(
+                token_equations,
+                minimization_vars,
+                previous_values,
+            )
+        } else {
             cairo_lang_eq_solver::try_solve_equations(token_equations, minimization_vars)
-                .ok_or(CostError::SolvingGasEquationFailed)?;
+        }
+        .ok_or(CostError::SolvingGasEquationFailed)?;
+        if let (Some(recorded), Some(token_equations)) =
+            (recorded_equations.as_deref_mut(), recorded_token_equations)
+        {
+            recorded.tokens.push(TokenGasEquations::new(token_type, &token_equations, &solution));
+        }
         for func in &program.funcs {
             let id = &func.id;
             if !function_costs.contains_key(id) {
@@ -318,13 +455,36 @@ pub fn compute_postcost_info<CostType: PostCostTypeEx>(
     precost_gas_info: &GasInfo,
     enforced_function_costs: &OrderedHashMap<FunctionId, CostType>,
 ) -> Result<GasInfo, CostError> {
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    compute_postcost_info_with_custom_costs(
+        program,
+        get_ap_change_fn,
+        precost_gas_info,
+        enforced_function_costs,
+        &CustomLibfuncCosts::default(),
+    )
+}
+
+/// Calculates gas postcost information for a given program, which may use custom libfuncs with the
+/// given costs - see [custom_libfunc_cost].
+pub fn compute_postcost_info_with_custom_costs<CostType: PostCostTypeEx>(
+    program: &Program,
+    get_ap_change_fn: &dyn Fn(&StatementIdx) -> usize,
+    precost_gas_info: &GasInfo,
+    enforced_function_costs: &OrderedHashMap<FunctionId, CostType>,
+    custom_costs: &CustomLibfuncCosts,
+) -> Result<GasInfo, CostError> {
+    let core_program = custom_costs.core_program(program);
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&core_program)?;
     let type_size_map = get_type_size_map(program, &registry).unwrap();
+    let custom_libfunc_costs = custom_costs.libfunc_costs(program, &type_size_map)?;
     let specific_cost_context =
         compute_costs::PostcostContext { get_ap_change_fn, precost_gas_info };
     compute_costs::compute_costs(
         program,
         &(|libfunc_id| {
+            if let Some(costs) = custom_libfunc_costs.get(libfunc_id) {
+                return costs.clone();
+            }
             let core_libfunc = registry
                 .get_libfunc(libfunc_id)
                 .expect("Program registry creation would have already failed.");
@@ -61,6 +61,7 @@ pub fn starknet_libfunc_cost_base(libfunc: &StarkNetConcreteLibfunc) -> Vec<Cons
                     Secp256OpConcreteLibfunc::Add(_) => syscall_cost(2),
                     Secp256OpConcreteLibfunc::Mul(_)
                     | Secp256OpConcreteLibfunc::GetPointFromX(_) => syscall_cost(3),
+                    Secp256OpConcreteLibfunc::MulAdd(_) => syscall_cost(6),
                     Secp256OpConcreteLibfunc::GetXy(_) => syscall_cost(1),
                 },
                 Secp256ConcreteLibfunc::R1(libfunc) => match libfunc {
@@ -68,6 +69,7 @@ pub fn starknet_libfunc_cost_base(libfunc: &StarkNetConcreteLibfunc) -> Vec<Cons
                     Secp256OpConcreteLibfunc::Add(_) => syscall_cost(2),
                     Secp256OpConcreteLibfunc::Mul(_)
                     | Secp256OpConcreteLibfunc::GetPointFromX(_) => syscall_cost(3),
+                    Secp256OpConcreteLibfunc::MulAdd(_) => syscall_cost(6),
                     Secp256OpConcreteLibfunc::GetXy(_) => syscall_cost(1),
                 },
             }
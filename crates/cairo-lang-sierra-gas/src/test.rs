@@ -1,11 +1,22 @@
 use std::fs;
 use std::path::PathBuf;
 
-use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
 
-use crate::{calc_gas_postcost_info, calc_gas_precost_info};
+use crate::cost_expr::Var;
+use crate::gas_equations::{GasEquations, GasVariable};
+use crate::gas_info::{GasInfo, WorstCaseGas};
+use crate::warm_start::PreviousGasSolution;
+use crate::{
+    calc_gas_postcost_info, calc_gas_postcost_info_from_previous,
+    calc_gas_postcost_info_with_equations, calc_gas_precost_info,
+    calc_gas_precost_info_from_previous,
+};
 
 cairo_lang_test_utils::test_file_test!(
     test_solve_gas,
@@ -36,8 +47,224 @@ fn test_solve_gas(
         calc_gas_postcost_info(&program, Default::default(), &gas_info0, |_| 0).unwrap();
     let gas_info = gas_info0.combine(gas_info1);
 
-    TestRunnerResult::success(OrderedHashMap::from([(
-        "gas_solution".into(),
-        format!("{gas_info}"),
-    )]))
+    let gas_slack = gas_info
+        .slack(&program)
+        .into_iter()
+        .map(|slack| format!("#{}: {:?}: {}", slack.statement_idx, slack.token_type, slack.amount))
+        .join("\n");
+    let worst_case_costs = gas_info
+        .worst_case_function_costs(&program)
+        .iter()
+        .map(|(function_id, cost)| format!("{function_id}: {cost:?}"))
+        .join("\n");
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("gas_solution".into(), format!("{gas_info}")),
+        ("gas_slack".into(), gas_slack),
+        ("worst_case_costs".into(), worst_case_costs),
+    ]))
+}
+
+#[test]
+fn test_worst_case_function_costs() {
+    let program = cairo_lang_sierra::ProgramParser::new()
+        .parse(indoc::indoc! {"
+            type RangeCheck = RangeCheck;
+            type GasBuiltin = GasBuiltin;
+
+            libfunc branch_align = branch_align;
+            libfunc withdraw_gas = withdraw_gas;
+            libfunc store_temp_rc = store_temp<RangeCheck>;
+            libfunc store_temp_gb = store_temp<GasBuiltin>;
+            libfunc call_withdraw = function_call<user@Withdraw>;
+
+            withdraw_gas(rc, gb) { fallthrough(rc, gb) 7(rc, gb) };
+            branch_align() -> ();
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            return(rc, gb);
+            branch_align() -> ();
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            return(rc, gb);
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            call_withdraw(rc, gb) -> (rc, gb);
+            call_withdraw(rc, gb) -> (rc, gb);
+            return(rc, gb);
+
+            Withdraw@0(rc: RangeCheck, gb: GasBuiltin) -> (RangeCheck, GasBuiltin);
+            CallTwice@11(rc: RangeCheck, gb: GasBuiltin) -> (RangeCheck, GasBuiltin);
+        "})
+        .unwrap();
+    let gas_info0 = calc_gas_precost_info(&program, Default::default()).unwrap();
+    let gas_info1 =
+        calc_gas_postcost_info(&program, Default::default(), &gas_info0, |_| 0).unwrap();
+    let gas_info = gas_info0.combine(gas_info1);
+
+    let costs = gas_info.worst_case_function_costs(&program);
+    let const_cost = |name: &str| match &costs[&FunctionId::from_string(name)] {
+        WorstCaseGas::Bounded(costs) => costs[&CostTokenType::Const],
+        WorstCaseGas::Unbounded => panic!("Expected a bounded cost for `{name}`."),
+    };
+    let withdrawn = gas_info.variable_values[&(StatementIdx(0), CostTokenType::Const)];
+    assert!(withdrawn > 0);
+    let withdraw_cost = const_cost("Withdraw");
+    assert_eq!(
+        withdraw_cost,
+        gas_info.function_costs[&FunctionId::from_string("Withdraw")][&CostTokenType::Const]
+            + withdrawn
+    );
+    // Calling twice costs the calls themselves, on top of two worst-case calls.
+    assert!(const_cost("CallTwice") > 2 * withdraw_cost);
+}
+
+/// Returns a program with a function doubling a felt252, a function checking if a felt252 is zero
+/// and a function calling both. If `extra_store` is true, the first function stores its result one
+/// more time, so that it costs more.
+fn double_and_check_program(extra_store: bool) -> Program {
+    let (extra_statements, shift) =
+        if extra_store { ("store_temp_felt252(x) -> (x);", 1) } else { ("", 0) };
+    cairo_lang_sierra::ProgramParser::new()
+        .parse(&format!(
+            "
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc branch_align = branch_align;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop_nz = drop<NonZeroFelt252>;
+            libfunc felt252_const_0 = felt252_const<0>;
+            libfunc felt252_const_1 = felt252_const<1>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc felt252_dup = dup<felt252>;
+            libfunc felt252_add = felt252_add;
+            libfunc call_scale = function_call<user@Scale>;
+            libfunc call_is_zero = function_call<user@IsZero>;
+
+            felt252_dup(x) -> (x, y);
+            {extra_statements}
+            felt252_add(x, y) -> (x);
+            store_temp_felt252(x) -> (x);
+            return(x);
+            felt252_is_zero(x) {{ fallthrough() NonZero(x) }};
+            branch_align() -> ();
+            felt252_const_1() -> (r);
+            store_temp_felt252(r) -> (r);
+            return(r);
+            NonZero:
+            branch_align() -> ();
+            drop_nz(x) -> ();
+            felt252_const_0() -> (r);
+            store_temp_felt252(r) -> (r);
+            store_temp_felt252(r) -> (r);
+            return(r);
+            call_scale(x) -> (x);
+            call_is_zero(x) -> (r);
+            return(r);
+
+            Scale@0(x: felt252) -> (felt252);
+            IsZero@{}(x: felt252) -> (felt252);
+            Main@{}(x: felt252) -> (felt252);
+            ",
+            4 + shift,
+            15 + shift,
+        ))
+        .unwrap()
+}
+
+/// Solves the gas of the program, optionally warm-started from a previous solution.
+fn solve_gas(program: &Program, previous: Option<&PreviousGasSolution<'_>>) -> GasInfo {
+    let (gas_info0, gas_info1) = if let Some(previous) = previous {
+        let gas_info0 =
+            calc_gas_precost_info_from_previous(program, Default::default(), previous).unwrap();
+        let gas_info1 = calc_gas_postcost_info_from_previous(
+            program,
+            Default::default(),
+            &gas_info0,
+            |_| 0,
+            previous,
+        )
+        .unwrap();
+        (gas_info0, gas_info1)
+    } else {
+        let gas_info0 = calc_gas_precost_info(program, Default::default()).unwrap();
+        let gas_info1 =
+            calc_gas_postcost_info(program, Default::default(), &gas_info0, |_| 0).unwrap();
+        (gas_info0, gas_info1)
+    };
+    gas_info0.combine(gas_info1)
+}
+
+#[test]
+fn test_solve_gas_from_previous() {
+    let previous_program = double_and_check_program(false);
+    let previous_gas_info = solve_gas(&previous_program, None);
+    let previous = PreviousGasSolution { program: &previous_program, gas_info: &previous_gas_info };
+
+    let program = double_and_check_program(true);
+    // Only `IsZero` is unchanged, as `Main` calls the changed `Scale`.
+    let previous_values = previous.unchanged_functions_values(&program);
+    assert!(!previous_values.is_empty());
+    for var in previous_values.keys() {
+        let (Var::LibfuncImplicitGasVariable(idx, _) | Var::StatementFuture(idx, _)) = var;
+        assert!((5..16).contains(&idx.0), "Unexpected reused variable `{var}`.");
+    }
+
+    let gas_info = solve_gas(&program, Some(&previous));
+    let expected_gas_info = solve_gas(&program, None);
+    gas_info.assert_eq_variables(&expected_gas_info, &program);
+    gas_info.assert_eq_functions(&expected_gas_info);
+    assert_ne!(
+        gas_info.function_costs[&FunctionId::from_string("Main")],
+        previous_gas_info.function_costs[&FunctionId::from_string("Main")]
+    );
+}
+
+#[test]
+fn test_gas_equations() {
+    let program = get_example_program("fib_jumps");
+    let gas_info0 = calc_gas_precost_info(&program, Default::default()).unwrap();
+    let (gas_info1, equations) =
+        calc_gas_postcost_info_with_equations(&program, Default::default(), &gas_info0, |_| 0)
+            .unwrap();
+
+    let withdraw_gas_idx = program
+        .statements
+        .iter()
+        .position(|statement| {
+            matches!(statement, Statement::Invocation(invocation)
+                if invocation.libfunc_id.to_string() == "withdraw_gas")
+        })
+        .map(StatementIdx)
+        .unwrap();
+    // The withdrawn amount is explained by the equations it appears in, and matches the solution.
+    let withdrawn = GasVariable::LibfuncImplicitGas(withdraw_gas_idx);
+    assert_eq!(
+        equations.value(CostTokenType::Const, &withdrawn),
+        Some(gas_info1.variable_values[&(withdraw_gas_idx, CostTokenType::Const)])
+    );
+    let explanation = equations.explain(withdraw_gas_idx).collect_vec();
+    assert!(!explanation.is_empty());
+    assert!(explanation.iter().any(|(token_type, eq)| {
+        *token_type == CostTokenType::Const
+            && eq.terms.iter().any(|term| term.variable == withdrawn)
+            && eq.to_string().contains(&format!("libfunc#{}", withdraw_gas_idx.0))
+    }));
+    // Every equation holds for the solution.
+    for token in &equations.tokens {
+        for eq in &token.equations {
+            let sum: i64 = eq.constant
+                + eq.terms
+                    .iter()
+                    .map(|term| term.coefficient * token.value(&term.variable).unwrap())
+                    .sum::<i64>();
+            assert_eq!(sum, 0, "Unsatisfied equation `{eq}`.");
+        }
+    }
+
+    let serialized = serde_json::to_string(&equations).unwrap();
+    assert_eq!(serde_json::from_str::<GasEquations>(&serialized).unwrap(), equations);
 }
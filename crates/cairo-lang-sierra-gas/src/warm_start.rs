@@ -0,0 +1,137 @@
+//! Warm-starting the solving of the gas equations of a program from the gas solution of a previous
+//! version of it, where only some of the functions changed.
+
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, FunctionId};
+use cairo_lang_sierra::program::{
+    ConcreteLibfuncLongId, Function, GenericArg, Program, Statement, StatementIdx,
+};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use itertools::Itertools;
+
+use crate::cost_expr::Var;
+use crate::gas_info::GasInfo;
+
+/// The gas solution of a previous version of a program.
+pub struct PreviousGasSolution<'a> {
+    /// The previous version of the program.
+    pub program: &'a Program,
+    /// The gas information solved for the previous version of the program.
+    pub gas_info: &'a GasInfo,
+}
+impl PreviousGasSolution<'_> {
+    /// Returns the previous values of the variables of the functions of `program` that are
+    /// unchanged from the previous version - along with all the functions they call - by their
+    /// statements in `program`.
+    ///
+    /// The values are only a hint for the solver, which validates them against the equations of
+    /// `program`.
+    pub(crate) fn unchanged_functions_values(&self, program: &Program) -> OrderedHashMap<Var, i64> {
+        let previous_functions: OrderedHashMap<_, _> = function_extents(self.program)
+            .map(|(func, extent)| (&func.id, (func, extent)))
+            .collect();
+        let previous_libfuncs = libfunc_long_ids(self.program);
+        let libfuncs = libfunc_long_ids(program);
+        // The functions with unchanged bodies, by their previous versions, and their callees.
+        let mut unchanged = OrderedHashMap::<&FunctionId, _>::default();
+        for (func, extent) in function_extents(program) {
+            let Some((previous_func, previous_extent)) = previous_functions.get(&func.id) else {
+                continue;
+            };
+            let offset = func.entry_point.0 as isize - previous_func.entry_point.0 as isize;
+            let mut callees = vec![];
+            let is_unchanged = previous_func.signature == func.signature
+                && previous_func.params == func.params
+                && previous_extent.len() == extent.len()
+                && previous_extent.clone().zip(extent.clone()).all(|(previous_idx, idx)| {
+                    let previous_statement = self.program.statements[previous_idx]
+                        .clone()
+                        .map(|target| StatementIdx((target.0 as isize + offset) as usize));
+                    let statement = &program.statements[idx];
+                    if &previous_statement != statement {
+                        return false;
+                    }
+                    let Statement::Invocation(invocation) = statement else {
+                        return true;
+                    };
+                    let long_id = libfuncs[&invocation.libfunc_id];
+                    callees.extend(long_id.generic_args.iter().filter_map(|arg| match arg {
+                        GenericArg::UserFunc(function_id) => Some(function_id),
+                        _ => None,
+                    }));
+                    previous_libfuncs.get(&invocation.libfunc_id) == Some(&long_id)
+                });
+            if is_unchanged {
+                unchanged.insert(&func.id, (*previous_func, extent, callees));
+            }
+        }
+        // Removing the functions calling changed functions, until a fixed point is reached.
+        loop {
+            let changed_callers: UnorderedHashSet<_> = unchanged
+                .iter()
+                .filter(|(_, (_, _, callees))| {
+                    callees.iter().any(|callee| !unchanged.contains_key(callee))
+                })
+                .map(|(function_id, _)| *function_id)
+                .collect();
+            if changed_callers.is_empty() {
+                break;
+            }
+            unchanged.retain(|function_id, _| !changed_callers.contains(function_id));
+        }
+
+        let mut values = OrderedHashMap::default();
+        for (function_id, (previous_func, extent, _)) in unchanged.iter() {
+            let previous_entry_point = previous_func.entry_point.0;
+            for token_type in CostTokenType::iter_casm_tokens() {
+                for idx in extent.clone() {
+                    let previous_idx = StatementIdx(idx - extent.start + previous_entry_point);
+                    if let Some(value) =
+                        self.gas_info.variable_values.get(&(previous_idx, *token_type))
+                    {
+                        values.insert(
+                            Var::LibfuncImplicitGasVariable(StatementIdx(idx), *token_type),
+                            *value,
+                        );
+                    }
+                }
+                let cost = self
+                    .gas_info
+                    .function_costs
+                    .get(*function_id)
+                    .and_then(|costs| costs.get(token_type))
+                    .copied()
+                    .unwrap_or_default();
+                values.insert(Var::StatementFuture(StatementIdx(extent.start), *token_type), cost);
+            }
+        }
+        values
+    }
+}
+
+/// Returns the functions of the program along with the ranges of their statements, assuming the
+/// statements of each function directly follow its entry point, up to the next entry point.
+fn function_extents(
+    program: &Program,
+) -> impl Iterator<Item = (&Function, std::ops::Range<usize>)> {
+    let sorted_funcs = program.funcs.iter().sorted_by_key(|func| func.entry_point.0).collect_vec();
+    let ends = sorted_funcs
+        .iter()
+        .skip(1)
+        .map(|func| func.entry_point.0)
+        .chain([program.statements.len()])
+        .collect_vec();
+    sorted_funcs.into_iter().zip(ends).map(|(func, end)| (func, func.entry_point.0..end))
+}
+
+/// Returns the long ids of the libfuncs of the program, by their ids.
+fn libfunc_long_ids(
+    program: &Program,
+) -> OrderedHashMap<&ConcreteLibfuncId, &ConcreteLibfuncLongId> {
+    program
+        .libfunc_declarations
+        .iter()
+        .map(|declaration| (&declaration.id, &declaration.long_id))
+        .collect()
+}
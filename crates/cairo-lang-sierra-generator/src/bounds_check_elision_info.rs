@@ -0,0 +1,67 @@
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_sierra::debug_info::{Annotations, SourceCodeLocation};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::db::SierraGenGroup;
+use crate::utils::outermost_source_code_location;
+
+#[cfg(test)]
+#[path = "bounds_check_elision_info_test.rs"]
+mod test;
+
+/// The namespace of the bounds check elision information in the annotations of the debug info of
+/// a program.
+pub const BOUNDS_CHECK_ELISION_ANNOTATION: &str =
+    "github.com/starkware-libs/cairo/bounds-check-elision/v1";
+
+/// The bounds checks of array accesses elided from a program, see
+/// [cairo_lang_lowering::optimizations::bounds_check_elision::elide_bounds_checks].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BoundsCheckElisionInfo {
+    /// The source locations of the array accesses whose bounds checks were elided, by the full
+    /// paths of the functions containing them.
+    pub elided_bounds_checks: OrderedHashMap<String, Vec<SourceCodeLocation>>,
+}
+impl BoundsCheckElisionInfo {
+    /// Collects the elided bounds checks of the given functions of a program.
+    pub fn collect(db: &dyn SierraGenGroup, functions: &[ConcreteFunctionWithBodyId]) -> Self {
+        let mut elided_bounds_checks = OrderedHashMap::<String, Vec<SourceCodeLocation>>::default();
+        for function in functions {
+            let Ok(locations) = db.elided_bounds_checks(*function) else {
+                continue;
+            };
+            let Ok(function_id) = function.function_id(db.upcast()) else {
+                continue;
+            };
+            // The accesses are implemented by the corelib, so the outermost call sites are
+            // reported.
+            let locations = locations
+                .iter()
+                .filter_map(|location| outermost_source_code_location(db, *location))
+                .collect_vec();
+            if !locations.is_empty() {
+                elided_bounds_checks
+                    .entry(function_id.semantic_full_path(db.upcast()))
+                    .or_default()
+                    .extend(locations);
+            }
+        }
+        Self { elided_bounds_checks }
+    }
+
+    /// Returns whether no bounds checks were elided.
+    pub fn is_empty(&self) -> bool {
+        self.elided_bounds_checks.is_empty()
+    }
+}
+
+impl From<&BoundsCheckElisionInfo> for Annotations {
+    fn from(value: &BoundsCheckElisionInfo) -> Self {
+        OrderedHashMap::from([(
+            BOUNDS_CHECK_ELISION_ANNOTATION.to_string(),
+            serde_json::to_value(value).unwrap(),
+        )])
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_lowering::optimizations::config::OptimizationConfig;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use cairo_lang_sierra::debug_info::{Annotations, SourceCodeLocation};
+use cairo_lang_utils::extract_matches;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::BOUNDS_CHECK_ELISION_ANNOTATION;
+use crate::db::SierraGenGroup;
+use crate::test_utils::SierraGenDatabaseForTesting;
+
+/// Returns the bounds check elision debug info of a program, compiled with or without eliding
+/// bounds checks.
+fn bounds_check_elision_annotation(elide_bounds_checks: bool) -> Option<serde_json::Value> {
+    let mut db = SierraGenDatabaseForTesting::new_empty();
+    db.set_optimization_config(Arc::new(
+        OptimizationConfig::default().with_elide_bounds_checks(elide_bounds_checks),
+    ));
+    let crate_id = setup_test_crate(
+        &db,
+        indoc! {"
+            fn foo(arr: @Array<felt252>, i: usize) -> felt252 {
+                *arr[i] + *arr[i]
+            }
+        "},
+    );
+    let item =
+        db.module_item_by_name(ModuleId::CrateRoot(crate_id), "foo".into()).unwrap().unwrap();
+    let foo = ConcreteFunctionWithBodyId::from_no_generics_free(
+        &db,
+        extract_matches!(item, ModuleItemId::FreeFunction),
+    )
+    .unwrap();
+    let program = db.get_sierra_program_for_functions(vec![foo]).unwrap();
+    let info = &program.debug_info.bounds_check_elision_info;
+    if info.is_empty() {
+        return None;
+    }
+    Some(serde_json::to_value(&Annotations::from(info)[BOUNDS_CHECK_ELISION_ANNOTATION]).unwrap())
+}
+
+#[test]
+fn test_no_elision() {
+    assert_eq!(bounds_check_elision_annotation(false), None);
+}
+
+#[test]
+fn test_elided_bounds_checks() {
+    // The second access reuses the checked result of the first.
+    let location = SourceCodeLocation { file: "lib.cairo".into(), line: 1, column: 15 };
+    assert_eq!(
+        bounds_check_elision_annotation(true),
+        Some(serde_json::json!({"elided_bounds_checks": {"test::foo": [location]}}))
+    );
+}
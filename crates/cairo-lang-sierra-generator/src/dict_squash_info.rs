@@ -0,0 +1,72 @@
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_lowering::optimizations::config::DictSquashStrategy;
+use cairo_lang_sierra::debug_info::{Annotations, SourceCodeLocation};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::db::SierraGenGroup;
+use crate::utils::outermost_source_code_location;
+
+#[cfg(test)]
+#[path = "dict_squash_info_test.rs"]
+mod test;
+
+/// The namespace of the dictionary squashing information in the annotations of the debug info of
+/// a program.
+pub const DICT_SQUASH_ANNOTATION: &str = "github.com/starkware-libs/cairo/dict-squash/v1";
+
+/// How the squashing of the dictionaries of a program was compiled, for attributing its costs.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DictSquashInfo {
+    /// When the squashing of dictionaries going out of scope is emitted, `eager` or `deferred`.
+    pub strategy: String,
+    /// The source locations of the creations of the dictionaries replaced by their single value,
+    /// and therefore never squashed, by the full paths of the functions containing them.
+    pub single_key_dicts: OrderedHashMap<String, Vec<SourceCodeLocation>>,
+}
+impl DictSquashInfo {
+    /// Collects the dictionary squashing information of the given functions of a program.
+    pub fn collect(db: &dyn SierraGenGroup, functions: &[ConcreteFunctionWithBodyId]) -> Self {
+        let strategy = match db.optimization_config().dict_squash_strategy {
+            DictSquashStrategy::Eager => "eager",
+            DictSquashStrategy::Deferred => "deferred",
+        };
+        let mut single_key_dicts = OrderedHashMap::<String, Vec<SourceCodeLocation>>::default();
+        for function in functions {
+            let Ok(locations) = db.single_key_dicts(*function) else {
+                continue;
+            };
+            let Ok(function_id) = function.function_id(db.upcast()) else {
+                continue;
+            };
+            // The dictionaries are created by the corelib, so the outermost call sites are
+            // reported.
+            let locations = locations
+                .iter()
+                .filter_map(|location| outermost_source_code_location(db, *location))
+                .collect_vec();
+            if !locations.is_empty() {
+                single_key_dicts
+                    .entry(function_id.semantic_full_path(db.upcast()))
+                    .or_default()
+                    .extend(locations);
+            }
+        }
+        Self { strategy: strategy.into(), single_key_dicts }
+    }
+
+    /// Returns whether the dictionaries were squashed as by default, with nothing to attribute.
+    pub fn is_default_squashing(&self) -> bool {
+        self.strategy == "eager" && self.single_key_dicts.is_empty()
+    }
+}
+
+impl From<&DictSquashInfo> for Annotations {
+    fn from(value: &DictSquashInfo) -> Self {
+        OrderedHashMap::from([(
+            DICT_SQUASH_ANNOTATION.to_string(),
+            serde_json::to_value(value).unwrap(),
+        )])
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_lowering::optimizations::config::{DictSquashStrategy, OptimizationConfig};
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use cairo_lang_sierra::debug_info::{Annotations, SourceCodeLocation};
+use cairo_lang_utils::extract_matches;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::DICT_SQUASH_ANNOTATION;
+use crate::db::SierraGenGroup;
+use crate::test_utils::SierraGenDatabaseForTesting;
+
+/// Returns the dictionary squashing debug info of a program, compiled with the given strategy and
+/// with or without replacing single key dictionaries.
+fn dict_squash_annotation(
+    strategy: DictSquashStrategy,
+    replace_single_key_dicts: bool,
+) -> Option<serde_json::Value> {
+    let mut db = SierraGenDatabaseForTesting::new_empty();
+    db.set_optimization_config(Arc::new(
+        OptimizationConfig::default()
+            .with_dict_squash_strategy(strategy)
+            .with_replace_single_key_dicts(replace_single_key_dicts),
+    ));
+    let crate_id = setup_test_crate(
+        &db,
+        indoc! {"
+            #[inline(never)]
+            fn sum_of_two(x: felt252) -> felt252 {
+                let mut dict: Felt252Dict<felt252> = Default::default();
+                dict.insert(1, x);
+                dict.insert(2, x);
+                dict.get(1) + dict.get(2)
+            }
+            fn foo(x: felt252) -> felt252 {
+                let mut dict: Felt252Dict<felt252> = Default::default();
+                dict.insert(1, x);
+                dict.get(1) + sum_of_two(x)
+            }
+        "},
+    );
+    let item =
+        db.module_item_by_name(ModuleId::CrateRoot(crate_id), "foo".into()).unwrap().unwrap();
+    let foo = ConcreteFunctionWithBodyId::from_no_generics_free(
+        &db,
+        extract_matches!(item, ModuleItemId::FreeFunction),
+    )
+    .unwrap();
+    let program = db.get_sierra_program_for_functions(vec![foo]).unwrap();
+    let info = &program.debug_info.dict_squash_info;
+    if info.is_default_squashing() {
+        return None;
+    }
+    Some(serde_json::to_value(&Annotations::from(info)[DICT_SQUASH_ANNOTATION]).unwrap())
+}
+
+#[test]
+fn test_default_squashing() {
+    assert_eq!(dict_squash_annotation(DictSquashStrategy::Eager, false), None);
+}
+
+#[test]
+fn test_deferred_squashing() {
+    assert_eq!(
+        dict_squash_annotation(DictSquashStrategy::Deferred, false),
+        Some(serde_json::json!({"strategy": "deferred", "single_key_dicts": {}}))
+    );
+}
+
+#[test]
+fn test_single_key_dicts() {
+    let location = SourceCodeLocation { file: "lib.cairo".into(), line: 8, column: 41 };
+    assert_eq!(
+        dict_squash_annotation(DictSquashStrategy::Eager, true),
+        Some(serde_json::json!({
+            "strategy": "eager",
+            "single_key_dicts": {"test::foo": [location]},
+        }))
+    );
+}
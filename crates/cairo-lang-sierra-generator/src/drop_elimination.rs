@@ -0,0 +1,290 @@
+//! Elimination of redundant drops in generated Sierra programs - variables that are created by
+//! duplicating, renaming or storing another variable, only to be dropped later on.
+//!
+//! Such a variable, along with its drop, is removed from the program when nothing between its
+//! creation and its drop depends on it, and its drop can't be reached from another statement:
+//! * A `dup` of a variable one of whose copies is dropped is replaced by a rename of the variable
+//!   to the kept copy, or removed if the copy is the variable itself.
+//! * A `rename` of a dropped variable is replaced by a drop of the renamed variable.
+//! * A `store_temp` of a dropped variable is replaced by a drop of the stored variable. Unless the
+//!   variable is zero-sized, the store changes `ap`, which the stack of the function must agree on
+//!   wherever its branches merge - so it is only removed when the code runs straight from it to a
+//!   `return`.
+//!
+//! The removed stores are removed from the compiled CASM code as well, along with the memory cells
+//! they use, as the ap changes and gas costs are computed from the optimized program. The other
+//! removed statements compile to no CASM instructions, but make the Sierra program smaller - e.g.
+//! the renames and drops left behind by [crate::inlining].
+
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, VarId};
+use cairo_lang_sierra::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, Function, GenericArg, Invocation,
+    LibfuncDeclaration, Program, Statement, StatementIdx,
+};
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+
+use crate::db::SierraGenGroup;
+use crate::program_generator::{SierraProgramDebugInfo, SierraProgramWithDebug};
+use crate::statements_locations::StatementsLocations;
+use crate::statements_remapping::StatementsRemapping;
+use crate::utils::{drop_libfunc_id, rename_libfunc_id};
+
+#[cfg(test)]
+#[path = "drop_elimination_test.rs"]
+mod test;
+
+/// Eliminates the redundant drops of the program, see the module docs.
+pub fn eliminate_redundant_drops(
+    db: &dyn SierraGenGroup,
+    program: &SierraProgramWithDebug,
+) -> SierraProgramWithDebug {
+    let mut eliminator = DropEliminator::new(db, &program.program);
+    // The statements of the program, where `None` marks a removed statement.
+    let mut statements = program.program.statements.iter().cloned().map(Some).collect::<Vec<_>>();
+    // Eliminating a drop may make the drop of the variable it was created from redundant, so this
+    // runs until no more drops are eliminated.
+    while eliminator.eliminate_drops(&mut statements) {}
+
+    let mut new_statements = vec![];
+    // The index of the statement each new statement originates from.
+    let mut origins = vec![];
+    // The index of the new statement replacing each original statement, or of the next one if it
+    // was removed.
+    let mut new_indices = vec![];
+    for (idx, statement) in statements.into_iter().enumerate() {
+        new_indices.push(new_statements.len());
+        if let Some(statement) = statement {
+            new_statements.push(statement);
+            origins.push(StatementIdx(idx));
+        }
+    }
+    new_indices.push(new_statements.len());
+
+    let statements_remapping =
+        StatementsRemapping::compose(program.debug_info.statements_remapping.as_ref(), &origins);
+    let remap = |idx: StatementIdx| StatementIdx(new_indices[idx.0]);
+    SierraProgramWithDebug {
+        program: Program {
+            type_declarations: program.program.type_declarations.clone(),
+            libfunc_declarations: eliminator.libfunc_declarations,
+            statements: new_statements.into_iter().map(|statement| statement.map(remap)).collect(),
+            funcs: program
+                .program
+                .funcs
+                .iter()
+                .map(|func| Function { entry_point: remap(func.entry_point), ..func.clone() })
+                .collect(),
+        },
+        debug_info: SierraProgramDebugInfo {
+            statements_locations: StatementsLocations {
+                locations: origins
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(idx, origin)| {
+                        let locations =
+                            program.debug_info.statements_locations.locations.get(&origin)?;
+                        Some((StatementIdx(idx), locations.clone()))
+                    })
+                    .collect(),
+            },
+            statements_remapping: Some(statements_remapping),
+            dict_squash_info: program.debug_info.dict_squash_info.clone(),
+            bounds_check_elision_info: program.debug_info.bounds_check_elision_info.clone(),
+        },
+    }
+}
+
+/// A variable created from another variable, which may be created only to be dropped.
+enum Creation {
+    /// `dup<T>(var) -> (copy0, copy1)`.
+    Dup { ty: ConcreteTypeId, var: VarId, copies: [VarId; 2] },
+    /// `rename<T>(var) -> (result)`, or `store_temp<T>(var) -> (result)` - where `changes_ap` is
+    /// whether it is a store of a non zero-sized `T`.
+    Move { ty: ConcreteTypeId, var: VarId, result: VarId, changes_ap: bool },
+}
+
+/// Helper for eliminating the redundant drops of a program.
+struct DropEliminator<'a> {
+    db: &'a dyn SierraGenGroup,
+    /// The libfunc declarations of the program, with the added `rename` and `drop` libfuncs.
+    libfunc_declarations: Vec<LibfuncDeclaration>,
+    /// The long ids of the declared libfuncs.
+    libfunc_long_ids: UnorderedHashMap<ConcreteLibfuncId, ConcreteLibfuncLongId>,
+    /// The statements that can be reached other than from their preceding statement - the entry
+    /// points of the functions and the targets of the branches.
+    jump_targets: UnorderedHashSet<usize>,
+}
+impl<'a> DropEliminator<'a> {
+    fn new(db: &'a dyn SierraGenGroup, program: &Program) -> Self {
+        let mut jump_targets =
+            program.funcs.iter().map(|func| func.entry_point.0).collect::<UnorderedHashSet<_>>();
+        for statement in &program.statements {
+            if let Statement::Invocation(invocation) = statement {
+                for branch in &invocation.branches {
+                    if let BranchTarget::Statement(target) = branch.target {
+                        jump_targets.insert(target.0);
+                    }
+                }
+            }
+        }
+        Self {
+            db,
+            libfunc_declarations: program.libfunc_declarations.clone(),
+            libfunc_long_ids: program
+                .libfunc_declarations
+                .iter()
+                .map(|declaration| (declaration.id.clone(), declaration.long_id.clone()))
+                .collect(),
+            jump_targets,
+        }
+    }
+
+    /// Eliminates the redundant drops of the variables created by the statements, returning
+    /// whether any drop was eliminated.
+    fn eliminate_drops(&mut self, statements: &mut [Option<Statement>]) -> bool {
+        let mut eliminated = false;
+        for idx in 0..statements.len() {
+            let Some(Statement::Invocation(invocation)) = &statements[idx] else {
+                continue;
+            };
+            let Some(creation) = self.creation(invocation) else {
+                continue;
+            };
+            let (replacement, drop_idx) = match creation {
+                Creation::Dup { ty, var, copies: [copy0, copy1] } => {
+                    let (kept, drop_idx) =
+                        if let Some(drop_idx) = self.find_drop(statements, idx, &copy1) {
+                            (copy0, drop_idx)
+                        } else if let Some(drop_idx) = self.find_drop(statements, idx, &copy0) {
+                            (copy1, drop_idx)
+                        } else {
+                            continue;
+                        };
+                    let replacement = (kept != var)
+                        .then(|| self.invocation(rename_libfunc_id(self.db, ty), var, vec![kept]));
+                    (replacement, drop_idx)
+                }
+                Creation::Move { ty, var, result, changes_ap } => {
+                    let Some(drop_idx) = self.find_drop(statements, idx, &result) else {
+                        continue;
+                    };
+                    if changes_ap && !self.runs_to_return(statements, drop_idx) {
+                        continue;
+                    }
+                    (Some(self.invocation(drop_libfunc_id(self.db, ty), var, vec![])), drop_idx)
+                }
+            };
+            statements[idx] = replacement;
+            statements[drop_idx] = None;
+            eliminated = true;
+        }
+        eliminated
+    }
+
+    /// Returns the variable created by the invocation, if it is one that may be created only to be
+    /// dropped.
+    fn creation(&self, invocation: &Invocation) -> Option<Creation> {
+        let long_id = self.libfunc_long_ids.get(&invocation.libfunc_id)?;
+        let [GenericArg::Type(ty)] = &long_id.generic_args[..] else {
+            return None;
+        };
+        let [var] = &invocation.args[..] else {
+            return None;
+        };
+        let [BranchInfo { target: BranchTarget::Fallthrough, results }] = &invocation.branches[..]
+        else {
+            return None;
+        };
+        let (ty, var) = (ty.clone(), var.clone());
+        match (long_id.generic_id.0.as_str(), &results[..]) {
+            ("dup", [copy0, copy1]) => {
+                Some(Creation::Dup { ty, var, copies: [copy0.clone(), copy1.clone()] })
+            }
+            ("rename", [result]) => {
+                Some(Creation::Move { ty, var, result: result.clone(), changes_ap: false })
+            }
+            ("store_temp", [result]) => {
+                let changes_ap =
+                    !self.db.get_type_info(ty.clone()).is_ok_and(|info| info.zero_sized);
+                Some(Creation::Move { ty, var, result: result.clone(), changes_ap })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the statement dropping the variable created by the statement at
+    /// `idx`, if the variable is not used before it, and it is only reachable from that
+    /// statement.
+    fn find_drop(
+        &self,
+        statements: &[Option<Statement>],
+        idx: usize,
+        var: &VarId,
+    ) -> Option<usize> {
+        for (drop_idx, statement) in statements.iter().enumerate().skip(idx + 1) {
+            if self.jump_targets.contains(&drop_idx) {
+                return None;
+            }
+            let Some(statement) = statement else {
+                continue;
+            };
+            let Statement::Invocation(invocation) = statement else {
+                return None;
+            };
+            if invocation.args.contains(var) {
+                let long_id = self.libfunc_long_ids.get(&invocation.libfunc_id)?;
+                return (long_id.generic_id.0 == "drop").then_some(drop_idx);
+            }
+            let [BranchInfo { target: BranchTarget::Fallthrough, results }] =
+                &invocation.branches[..]
+            else {
+                return None;
+            };
+            if results.contains(var) {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Returns whether the code runs straight from the statement at `idx` to a `return`, without
+    /// being reachable from other statements on the way.
+    fn runs_to_return(&self, statements: &[Option<Statement>], idx: usize) -> bool {
+        for (idx, statement) in statements.iter().enumerate().skip(idx + 1) {
+            if self.jump_targets.contains(&idx) {
+                return false;
+            }
+            let Some(statement) = statement else {
+                continue;
+            };
+            let Statement::Invocation(invocation) = statement else {
+                return true;
+            };
+            let [BranchInfo { target: BranchTarget::Fallthrough, .. }] = &invocation.branches[..]
+            else {
+                return false;
+            };
+        }
+        false
+    }
+
+    /// Returns a single branch invocation of the libfunc, declaring it if required.
+    fn invocation(
+        &mut self,
+        libfunc_id: ConcreteLibfuncId,
+        arg: VarId,
+        results: Vec<VarId>,
+    ) -> Statement {
+        if !self.libfunc_long_ids.contains_key(&libfunc_id) {
+            let long_id = self.db.lookup_intern_concrete_lib_func(libfunc_id.clone());
+            self.libfunc_long_ids.insert(libfunc_id.clone(), long_id.clone());
+            self.libfunc_declarations.push(LibfuncDeclaration { id: libfunc_id.clone(), long_id });
+        }
+        Statement::Invocation(Invocation {
+            libfunc_id,
+            args: vec![arg],
+            branches: vec![BranchInfo { target: BranchTarget::Fallthrough, results }],
+        })
+    }
+}
@@ -0,0 +1,116 @@
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_utils::extract_matches;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::eliminate_redundant_drops;
+use crate::db::SierraGenGroup;
+use crate::inlining::inline_small_functions;
+use crate::replace_ids::replace_sierra_ids_in_program;
+use crate::test_utils::SierraGenDatabaseForTesting;
+
+const CODE: &str = indoc! {"
+    #[inline(never)]
+    fn first(a: felt252, b: felt252) -> felt252 {
+        a
+    }
+    fn foo(a: felt252, b: felt252) -> felt252 {
+        first(a, b) + b
+    }
+    fn bar(a: felt252, b: felt252, c: bool) -> felt252 {
+        let x = first(a, b);
+        if c {
+            x + b
+        } else {
+            b
+        }
+    }
+"};
+
+/// Returns the id of the given function of [CODE], along with the database.
+fn setup(name: &str) -> (SierraGenDatabaseForTesting, ConcreteFunctionWithBodyId) {
+    let db = SierraGenDatabaseForTesting::new_empty();
+    let crate_id = setup_test_crate(&db, CODE);
+    let item = db.module_item_by_name(ModuleId::CrateRoot(crate_id), name.into()).unwrap().unwrap();
+    let function_id = ConcreteFunctionWithBodyId::from_no_generics_free(
+        &db,
+        extract_matches!(item, ModuleItemId::FreeFunction),
+    )
+    .unwrap();
+    (db, function_id)
+}
+
+#[test_case(
+    "foo",
+    None,
+    indoc! {"
+        store_temp<felt252>([0]) -> ([0]);
+        dup<felt252>([1]) -> ([1], [2]);
+        store_temp<felt252>([2]) -> ([2]);
+        function_call<user@test::first>([0], [2]) -> ([3]);
+        felt252_add([3], [1]) -> ([4]);
+        store_temp<felt252>([4]) -> ([4]);
+        return([4]);
+    "};
+    "not_inlined"
+)]
+#[test_case(
+    "foo",
+    Some(3),
+    indoc! {"
+        store_temp<felt252>([0]) -> ([0]);
+        store_temp<felt252>([0]) -> ([0]);
+        rename<felt252>([0]) -> ([3]);
+        felt252_add([3], [1]) -> ([4]);
+        store_temp<felt252>([4]) -> ([4]);
+        return([4]);
+    "};
+    "inlined"
+)]
+#[test_case(
+    "bar",
+    Some(3),
+    indoc! {"
+        store_temp<felt252>([0]) -> ([0]);
+        dup<felt252>([1]) -> ([1], [3]);
+        store_temp<felt252>([3]) -> ([3]);
+        drop<felt252>([3]) -> ();
+        store_temp<felt252>([0]) -> ([0]);
+        rename<felt252>([0]) -> ([4]);
+        enum_match<core::bool>([2]) { fallthrough([5]) 12([6]) };
+        branch_align() -> ();
+        drop<Unit>([5]) -> ();
+        drop<felt252>([4]) -> ();
+        store_temp<felt252>([1]) -> ([1]);
+        return([1]);
+        branch_align() -> ();
+        drop<Unit>([6]) -> ();
+        felt252_add([4], [1]) -> ([7]);
+        store_temp<felt252>([7]) -> ([7]);
+        return([7]);
+    "};
+    "inlined_before_branch"
+)]
+fn eliminate_drops(name: &str, inlining_threshold: Option<usize>, expected: &str) {
+    let (db, function_id) = setup(name);
+    let mut program =
+        db.get_sierra_program_for_functions(vec![function_id]).unwrap().as_ref().clone();
+    if let Some(max_statements) = inlining_threshold {
+        program = inline_small_functions(&db, &program, max_statements);
+    }
+    let optimized = eliminate_redundant_drops(&db, &program).program;
+    ProgramRegistry::<CoreType, CoreLibfunc>::new(&optimized).unwrap();
+    let optimized = replace_sierra_ids_in_program(&db, &optimized);
+    // The statements of the function, which precede those of the functions it calls.
+    let statements = &optimized.statements[..optimized.funcs[1].entry_point.0];
+    assert_eq!(
+        statements.iter().map(|statement| format!("{statement};\n")).collect::<String>(),
+        expected
+    );
+}
@@ -0,0 +1,317 @@
+//! Inlining of small functions in generated Sierra programs, removing the overhead of calling them
+//! (the `function_call` and `return` statements, and the loss of ap tracking across the call) from
+//! the code calling them, e.g. from hot loops.
+//!
+//! Only functions whose code runs straight to a single `return` are inlined, as such code behaves
+//! the same when spliced into its callers - as long as it doesn't depend on the frame of the
+//! function (e.g. by using local variables or by changing the ap tracking), nor invalidates the
+//! references to the arguments it gets from its callers (e.g. by an unknown ap change).
+
+use std::ops::Range;
+
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, VarId};
+use cairo_lang_sierra::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, Function, GenericArg, Invocation,
+    LibfuncDeclaration, Program, Statement, StatementIdx,
+};
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::db::SierraGenGroup;
+use crate::program_generator::{SierraProgramDebugInfo, SierraProgramWithDebug};
+use crate::statements_locations::StatementsLocations;
+use crate::statements_remapping::StatementsRemapping;
+use crate::utils::rename_libfunc_id;
+
+#[cfg(test)]
+#[path = "inlining_test.rs"]
+mod test;
+
+/// The generic libfuncs that prevent inlining the functions using them.
+const NON_INLINABLE_LIBFUNCS: [&str; 9] = [
+    // Calls may change ap by an unknown amount.
+    "function_call",
+    "coupon_call",
+    "felt252_dict_squash",
+    // Local variables and ap tracking are managed per function.
+    "alloc_local",
+    "finalize_locals",
+    "store_local",
+    "revoke_ap_tracking",
+    "enable_ap_tracking",
+    "disable_ap_tracking",
+];
+
+/// Inlines the calls to the functions of the program that have at most `max_statements`
+/// statements and can be inlined, see the module docs. The inlined functions are kept in the
+/// program, as they may still be called externally.
+pub fn inline_small_functions(
+    db: &dyn SierraGenGroup,
+    program: &SierraProgramWithDebug,
+    max_statements: usize,
+) -> SierraProgramWithDebug {
+    let mut inliner = Inliner::new(db, &program.program);
+    let inlinable = program
+        .program
+        .funcs
+        .iter()
+        .filter_map(|func| {
+            let statements = inliner.function_statements(func);
+            (statements.len() <= max_statements && inliner.is_inlinable(statements.clone()))
+                .then(|| (func.id.clone(), (func, statements)))
+        })
+        .collect::<UnorderedHashMap<_, _>>();
+
+    let statements = &program.program.statements;
+    let mut new_statements = vec![];
+    // The index of the statement each new statement originates from.
+    let mut origins = vec![];
+    // The index of the new statements replacing each original statement.
+    let mut new_indices = vec![];
+    for (idx, statement) in statements.iter().enumerate() {
+        new_indices.push(new_statements.len());
+        let callee = match statement {
+            Statement::Invocation(invocation) => inliner
+                .called_function(&invocation.libfunc_id)
+                .and_then(|function_id| inlinable.get(&function_id))
+                .map(|callee| (invocation, callee)),
+            Statement::Return(_) => None,
+        };
+        let Some((invocation, (callee, callee_statements))) = callee else {
+            new_statements.push(statement.clone());
+            origins.push(StatementIdx(idx));
+            continue;
+        };
+        for (statement, origin) in
+            inliner.inline_call(invocation, callee, callee_statements.clone())
+        {
+            new_statements.push(statement);
+            origins.push(origin);
+        }
+    }
+    new_indices.push(new_statements.len());
+
+    let statements_remapping =
+        StatementsRemapping::compose(program.debug_info.statements_remapping.as_ref(), &origins);
+    let remap = |idx: StatementIdx| StatementIdx(new_indices[idx.0]);
+    SierraProgramWithDebug {
+        program: Program {
+            type_declarations: program.program.type_declarations.clone(),
+            libfunc_declarations: inliner.libfunc_declarations,
+            statements: new_statements.into_iter().map(|statement| statement.map(remap)).collect(),
+            funcs: program
+                .program
+                .funcs
+                .iter()
+                .map(|func| Function { entry_point: remap(func.entry_point), ..func.clone() })
+                .collect(),
+        },
+        debug_info: SierraProgramDebugInfo {
+            statements_locations: StatementsLocations {
+                locations: origins
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(idx, origin)| {
+                        let locations =
+                            program.debug_info.statements_locations.locations.get(&origin)?;
+                        Some((StatementIdx(idx), locations.clone()))
+                    })
+                    .collect(),
+            },
+            statements_remapping: Some(statements_remapping),
+            dict_squash_info: program.debug_info.dict_squash_info.clone(),
+            bounds_check_elision_info: program.debug_info.bounds_check_elision_info.clone(),
+        },
+    }
+}
+
+/// Helper for inlining the functions of a program.
+struct Inliner<'a> {
+    db: &'a dyn SierraGenGroup,
+    program: &'a Program,
+    /// The libfunc declarations of the program, with the added `rename` libfuncs.
+    libfunc_declarations: Vec<LibfuncDeclaration>,
+    /// The long ids of the declared libfuncs.
+    libfunc_long_ids: UnorderedHashMap<ConcreteLibfuncId, ConcreteLibfuncLongId>,
+    /// The entry points of the functions, sorted.
+    entry_points: Vec<usize>,
+    next_var_id: u64,
+}
+impl<'a> Inliner<'a> {
+    fn new(db: &'a dyn SierraGenGroup, program: &'a Program) -> Self {
+        let mut entry_points =
+            program.funcs.iter().map(|func| func.entry_point.0).collect::<Vec<_>>();
+        entry_points.sort();
+        let max_var_id = program
+            .statements
+            .iter()
+            .flat_map(|statement| match statement {
+                Statement::Invocation(invocation) => invocation
+                    .args
+                    .iter()
+                    .chain(invocation.branches.iter().flat_map(|branch| &branch.results))
+                    .collect::<Vec<_>>(),
+                Statement::Return(vars) => vars.iter().collect(),
+            })
+            .chain(program.funcs.iter().flat_map(|func| func.params.iter().map(|param| &param.id)))
+            .map(|var_id| var_id.id)
+            .max();
+        Self {
+            db,
+            program,
+            libfunc_declarations: program.libfunc_declarations.clone(),
+            libfunc_long_ids: program
+                .libfunc_declarations
+                .iter()
+                .map(|declaration| (declaration.id.clone(), declaration.long_id.clone()))
+                .collect(),
+            entry_points,
+            next_var_id: max_var_id.map_or(0, |id| id + 1),
+        }
+    }
+
+    /// Returns the range of the statements of a function, up to the entry point of the next one.
+    fn function_statements(&self, func: &Function) -> Range<usize> {
+        let start = func.entry_point.0;
+        let end = self
+            .entry_points
+            .iter()
+            .find(|entry_point| **entry_point > start)
+            .copied()
+            .unwrap_or(self.program.statements.len());
+        start..end
+    }
+
+    /// Returns whether the given statements of a function run straight to a return at their end,
+    /// without using libfuncs preventing inlining.
+    fn is_inlinable(&self, statements: Range<usize>) -> bool {
+        let Some((Statement::Return(_), body)) = self.program.statements[statements].split_last()
+        else {
+            return false;
+        };
+        body.iter().all(|statement| {
+            let Statement::Invocation(invocation) = statement else {
+                return false;
+            };
+            let [BranchInfo { target: BranchTarget::Fallthrough, .. }] = &invocation.branches[..]
+            else {
+                return false;
+            };
+            self.libfunc_long_ids.get(&invocation.libfunc_id).is_some_and(|long_id| {
+                !NON_INLINABLE_LIBFUNCS.contains(&long_id.generic_id.0.as_str())
+            })
+        })
+    }
+
+    /// Returns the function called by the libfunc, if it is a `function_call`.
+    fn called_function(&self, libfunc_id: &ConcreteLibfuncId) -> Option<FunctionId> {
+        let long_id = self.libfunc_long_ids.get(libfunc_id)?;
+        if long_id.generic_id != "function_call".into() {
+            return None;
+        }
+        match &long_id.generic_args[..] {
+            [GenericArg::UserFunc(function_id)] => Some(function_id.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the statements replacing a call to an inlinable function, along with the index of
+    /// the statement each originates from.
+    ///
+    /// The variables of the callee are replaced by the arguments of the call and by new variables,
+    /// and its return is replaced by renames of the returned variables to the results of the call.
+    fn inline_call(
+        &mut self,
+        invocation: &Invocation,
+        callee: &Function,
+        callee_statements: Range<usize>,
+    ) -> Vec<(Statement, StatementIdx)> {
+        let mut var_mapping = callee
+            .params
+            .iter()
+            .map(|param| param.id.clone())
+            .zip(invocation.args.iter().cloned())
+            .collect::<UnorderedHashMap<_, _>>();
+        let mut inlined = vec![];
+        for idx in callee_statements {
+            let origin = StatementIdx(idx);
+            match &self.program.statements[idx] {
+                Statement::Invocation(callee_invocation) => {
+                    let args = callee_invocation
+                        .args
+                        .iter()
+                        .map(|var_id| self.map_var(&mut var_mapping, var_id))
+                        .collect();
+                    let branches = callee_invocation
+                        .branches
+                        .iter()
+                        .map(|branch| BranchInfo {
+                            target: branch.target.clone(),
+                            results: branch
+                                .results
+                                .iter()
+                                .map(|var_id| self.map_var(&mut var_mapping, var_id))
+                                .collect(),
+                        })
+                        .collect();
+                    inlined.push((
+                        Statement::Invocation(Invocation {
+                            libfunc_id: callee_invocation.libfunc_id.clone(),
+                            args,
+                            branches,
+                        }),
+                        origin,
+                    ));
+                }
+                Statement::Return(returned_vars) => {
+                    for ((var_id, result), ty) in returned_vars
+                        .iter()
+                        .zip(&invocation.branches[0].results)
+                        .zip(&callee.signature.ret_types)
+                    {
+                        let arg = self.map_var(&mut var_mapping, var_id);
+                        inlined.push((
+                            Statement::Invocation(Invocation {
+                                libfunc_id: self.rename_libfunc_id(ty),
+                                args: vec![arg],
+                                branches: vec![BranchInfo {
+                                    target: BranchTarget::Fallthrough,
+                                    results: vec![result.clone()],
+                                }],
+                            }),
+                            origin,
+                        ));
+                    }
+                }
+            }
+        }
+        inlined
+    }
+
+    /// Returns the variable of the caller replacing a variable of an inlined callee, allocating a
+    /// new one for the variables not replaced yet.
+    fn map_var(
+        &mut self,
+        var_mapping: &mut UnorderedHashMap<VarId, VarId>,
+        var_id: &VarId,
+    ) -> VarId {
+        var_mapping
+            .entry(var_id.clone())
+            .or_insert_with(|| {
+                let new_var_id = VarId::new(self.next_var_id);
+                self.next_var_id += 1;
+                new_var_id
+            })
+            .clone()
+    }
+
+    /// Returns the id of the `rename` libfunc of the given type, declaring it if required.
+    fn rename_libfunc_id(&mut self, ty: &ConcreteTypeId) -> ConcreteLibfuncId {
+        let id = rename_libfunc_id(self.db, ty.clone());
+        if !self.libfunc_long_ids.contains_key(&id) {
+            let long_id = self.db.lookup_intern_concrete_lib_func(id.clone());
+            self.libfunc_long_ids.insert(id.clone(), long_id.clone());
+            self.libfunc_declarations.push(LibfuncDeclaration { id: id.clone(), long_id });
+        }
+        id
+    }
+}
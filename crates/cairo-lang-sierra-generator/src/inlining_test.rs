@@ -0,0 +1,117 @@
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_utils::extract_matches;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::inline_small_functions;
+use crate::db::SierraGenGroup;
+use crate::replace_ids::replace_sierra_ids_in_program;
+use crate::test_utils::SierraGenDatabaseForTesting;
+
+const CODE: &str = indoc! {"
+    #[inline(never)]
+    fn add(a: felt252, b: felt252) -> felt252 {
+        a + b
+    }
+    #[inline(never)]
+    fn double(a: felt252) -> felt252 {
+        a + a
+    }
+    #[inline(never)]
+    fn is_zero(a: felt252) -> bool {
+        a == 0
+    }
+    fn foo(a: felt252, b: felt252) -> bool {
+        is_zero(double(add(a, b)))
+    }
+"};
+
+/// Returns the id of the `foo` function of [CODE], along with the database.
+fn setup() -> (SierraGenDatabaseForTesting, ConcreteFunctionWithBodyId) {
+    let db = SierraGenDatabaseForTesting::new_empty();
+    let crate_id = setup_test_crate(&db, CODE);
+    let item =
+        db.module_item_by_name(ModuleId::CrateRoot(crate_id), "foo".into()).unwrap().unwrap();
+    let foo = ConcreteFunctionWithBodyId::from_no_generics_free(
+        &db,
+        extract_matches!(item, ModuleItemId::FreeFunction),
+    )
+    .unwrap();
+    (db, foo)
+}
+
+#[test_case(
+    0,
+    indoc! {"
+        store_temp<felt252>([0]) -> ([0]);
+        store_temp<felt252>([1]) -> ([1]);
+        function_call<user@test::add>([0], [1]) -> ([2]);
+        function_call<user@test::double>([2]) -> ([3]);
+        function_call<user@test::is_zero>([3]) -> ([4]);
+        return([4]);
+    "};
+    "none"
+)]
+#[test_case(
+    3,
+    indoc! {"
+        store_temp<felt252>([0]) -> ([0]);
+        store_temp<felt252>([1]) -> ([1]);
+        felt252_add([0], [1]) -> ([6]);
+        store_temp<felt252>([6]) -> ([6]);
+        rename<felt252>([6]) -> ([2]);
+        function_call<user@test::double>([2]) -> ([3]);
+        function_call<user@test::is_zero>([3]) -> ([4]);
+        return([4]);
+    "};
+    "add"
+)]
+#[test_case(
+    100,
+    indoc! {"
+        store_temp<felt252>([0]) -> ([0]);
+        store_temp<felt252>([1]) -> ([1]);
+        felt252_add([0], [1]) -> ([6]);
+        store_temp<felt252>([6]) -> ([6]);
+        rename<felt252>([6]) -> ([2]);
+        dup<felt252>([2]) -> ([2], [7]);
+        felt252_add([7], [2]) -> ([8]);
+        store_temp<felt252>([8]) -> ([8]);
+        rename<felt252>([8]) -> ([3]);
+        function_call<user@test::is_zero>([3]) -> ([4]);
+        return([4]);
+    "};
+    "straight_line_functions"
+)]
+fn inline_functions(max_statements: usize, expected: &str) {
+    let (db, foo) = setup();
+    let program = db.get_sierra_program_for_functions(vec![foo]).unwrap();
+    let inlined = inline_small_functions(&db, &program, max_statements).program;
+    ProgramRegistry::<CoreType, CoreLibfunc>::new(&inlined).unwrap();
+    let inlined = replace_sierra_ids_in_program(&db, &inlined);
+    // The statements of `foo`, which precede those of the functions it calls.
+    let foo_statements = &inlined.statements[..inlined.funcs[1].entry_point.0];
+    assert_eq!(
+        foo_statements.iter().map(|statement| format!("{statement};\n")).collect::<String>(),
+        expected
+    );
+}
+
+#[test]
+fn inlined_statements_keep_their_locations() {
+    let (db, foo) = setup();
+    let program = db.get_sierra_program_for_functions(vec![foo]).unwrap();
+    let inlined = inline_small_functions(&db, &program, 3);
+    // The `felt252_add` of `add`, and its inlined copy in `foo`.
+    let locations = &program.debug_info.statements_locations.locations;
+    let inlined_locations = &inlined.debug_info.statements_locations.locations;
+    assert!(locations.get(&StatementIdx(6)).is_some());
+    assert_eq!(inlined_locations.get(&StatementIdx(2)), locations.get(&StatementIdx(6)));
+}
@@ -4,24 +4,30 @@
 mod ap_change;
 mod ap_tracking;
 mod block_generator;
+pub mod bounds_check_elision_info;
 pub mod canonical_id_replacer;
 pub mod db;
+pub mod dict_squash_info;
+pub mod drop_elimination;
 mod expr_generator_context;
 mod extra_sierra_info;
 mod function_generator;
 #[cfg(any(feature = "testing", test))]
 pub mod function_generator_test_utils;
 mod id_allocator;
+pub mod inlining;
 mod lifetime;
 mod local_variables;
 mod next_statement_index_fetch;
 pub mod pre_sierra;
+pub mod prelinked;
 pub mod program_generator;
 pub mod replace_ids;
 mod resolve_labels;
 mod specialization_context;
 pub mod statements_functions;
 pub mod statements_locations;
+pub mod statements_remapping;
 mod store_variables;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_filesystem::db::FilesGroupEx;
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::{CrateId, FlagId};
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use cairo_lang_utils::{extract_matches, UpcastMut};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{corelib_fingerprint, generate_prelinked_fragment};
+use crate::program_generator::get_sierra_program_for_functions_ex;
+use crate::replace_ids::replace_sierra_ids_in_program;
+use crate::test_utils::SierraGenDatabaseForTesting;
+
+const CODE: &str = indoc! {"
+    #[inline(never)]
+    fn helper(a: u128, b: u128) -> u128 {
+        a + b
+    }
+    fn foo(a: u128) -> u128 {
+        helper(a, 1)
+    }
+    fn bar(a: u128) -> u128 {
+        helper(a, 2) * a
+    }
+"};
+
+/// Returns the id of the free function `name` of the crate `crate_id`.
+fn function_id(
+    db: &SierraGenDatabaseForTesting,
+    crate_id: CrateId,
+    name: &str,
+) -> ConcreteFunctionWithBodyId {
+    let item = db.module_item_by_name(ModuleId::CrateRoot(crate_id), name.into()).unwrap().unwrap();
+    ConcreteFunctionWithBodyId::from_no_generics_free(
+        db,
+        extract_matches!(item, ModuleItemId::FreeFunction),
+    )
+    .unwrap()
+}
+
+#[test]
+fn splice_prelinked_fragment() {
+    // The fragment is generated by another database.
+    let fragment = {
+        let db = &SierraGenDatabaseForTesting::new_empty();
+        let crate_id = setup_test_crate(db, CODE);
+        generate_prelinked_fragment(db, vec![function_id(db, crate_id, "foo")]).unwrap()
+    };
+
+    let db = &SierraGenDatabaseForTesting::new_empty();
+    let crate_id = setup_test_crate(db, CODE);
+    let bar = function_id(db, crate_id, "bar");
+    let generated = get_sierra_program_for_functions_ex(db, vec![bar], None).unwrap();
+    let spliced = get_sierra_program_for_functions_ex(db, vec![bar], Some(&fragment)).unwrap();
+    assert_eq!(
+        replace_sierra_ids_in_program(db, &spliced.program).to_string(),
+        replace_sierra_ids_in_program(db, &generated.program).to_string()
+    );
+    // The code of `helper` and the functions it calls is taken from the fragment, and has no
+    // locations.
+    assert!(
+        spliced.debug_info.statements_locations.locations.len()
+            < generated.debug_info.statements_locations.locations.len()
+    );
+}
+
+#[test]
+fn ignore_prelinked_fragment_of_other_configuration() {
+    let fragment = {
+        let db = &SierraGenDatabaseForTesting::new_empty();
+        let crate_id = setup_test_crate(db, CODE);
+        generate_prelinked_fragment(db, vec![function_id(db, crate_id, "foo")]).unwrap()
+    };
+
+    // The fragment is generated with gas withdrawals, which change the code of the corelib.
+    let db = &mut SierraGenDatabaseForTesting::new_empty();
+    let add_withdraw_gas_flag_id = FlagId::new(db.upcast_mut(), "add_withdraw_gas");
+    db.set_flag(add_withdraw_gas_flag_id, Some(Arc::new(Flag::AddWithdrawGas(false))));
+    assert_ne!(fragment.fingerprint(), corelib_fingerprint(db));
+    let crate_id = setup_test_crate(db, CODE);
+    let bar = function_id(db, crate_id, "bar");
+    let generated = get_sierra_program_for_functions_ex(db, vec![bar], None).unwrap();
+    let spliced = get_sierra_program_for_functions_ex(db, vec![bar], Some(&fragment)).unwrap();
+    assert_eq!(spliced, generated);
+}
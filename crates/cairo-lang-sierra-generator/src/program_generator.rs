@@ -5,6 +5,7 @@ use cairo_lang_debug::DebugWithDb;
 use cairo_lang_diagnostics::{get_location_marks, Maybe};
 use cairo_lang_filesystem::ids::CrateId;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_lowering::whole_program::flag_whole_program;
 use cairo_lang_sierra::extensions::core::CoreLibfunc;
 use cairo_lang_sierra::extensions::GenericLibfuncEx;
 use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId};
@@ -14,13 +15,17 @@ use cairo_lang_utils::try_extract_matches;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use itertools::{chain, Itertools};
 
+use crate::bounds_check_elision_info::BoundsCheckElisionInfo;
 use crate::db::{sierra_concrete_long_id, SierraGenGroup};
+use crate::dict_squash_info::DictSquashInfo;
 use crate::extra_sierra_info::type_has_const_size;
 use crate::pre_sierra;
+use crate::prelinked::{corelib_fingerprint, spliced_function, PrelinkedFragment};
 use crate::replace_ids::{DebugReplacer, SierraIdReplacer};
 use crate::resolve_labels::{resolve_labels_and_extract_locations, LabelReplacer};
 use crate::specialization_context::SierraSignatureSpecializationContext;
 use crate::statements_locations::StatementsLocations;
+use crate::statements_remapping::StatementsRemapping;
 
 #[cfg(test)]
 #[path = "program_generator_test.rs"]
@@ -224,22 +229,48 @@ impl DebugWithDb<dyn SierraGenGroup> for SierraProgramWithDebug {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SierraProgramDebugInfo {
     pub statements_locations: StatementsLocations,
+    /// The remapping of the statements to the originally generated ones, or `None` if they were
+    /// not remapped by any Sierra-level optimization.
+    pub statements_remapping: Option<StatementsRemapping>,
+    /// How the squashing of the dictionaries of the program was compiled.
+    pub dict_squash_info: DictSquashInfo,
+    /// The bounds checks elided from the program.
+    pub bounds_check_elision_info: BoundsCheckElisionInfo,
 }
 
 pub fn get_sierra_program_for_functions(
     db: &dyn SierraGenGroup,
     requested_function_ids: Vec<ConcreteFunctionWithBodyId>,
 ) -> Maybe<Arc<SierraProgramWithDebug>> {
+    get_sierra_program_for_functions_ex(db, requested_function_ids, None)
+}
+
+/// Returns the Sierra program of the requested functions, similar to
+/// [SierraGenGroup::get_sierra_program_for_functions], taking the code of the functions found in
+/// `fragment` from it rather than generating it.
+///
+/// The statements taken from the fragment have no locations in the debug info. A fragment
+/// generated with a different corelib or compilation configuration than `db` is ignored.
+pub fn get_sierra_program_for_functions_ex(
+    db: &dyn SierraGenGroup,
+    requested_function_ids: Vec<ConcreteFunctionWithBodyId>,
+    fragment: Option<&PrelinkedFragment>,
+) -> Maybe<Arc<SierraProgramWithDebug>> {
+    let fragment = fragment.filter(|fragment| fragment.fingerprint() == corelib_fingerprint(db));
     let mut functions: Vec<Arc<pre_sierra::Function>> = vec![];
     let mut statements: Vec<pre_sierra::StatementWithLocation> = vec![];
-    let mut processed_function_ids = UnorderedHashSet::<ConcreteFunctionWithBodyId>::default();
+    let mut processed_function_ids = OrderedHashSet::<ConcreteFunctionWithBodyId>::default();
     let mut function_id_queue: VecDeque<ConcreteFunctionWithBodyId> =
         requested_function_ids.into_iter().collect();
     while let Some(function_id) = function_id_queue.pop_front() {
         if !processed_function_ids.insert(function_id) {
             continue;
         }
-        let function: Arc<pre_sierra::Function> = db.function_with_body_sierra(function_id)?;
+        let function: Arc<pre_sierra::Function> =
+            match fragment.and_then(|fragment| spliced_function(db, fragment, function_id)) {
+                Some(function) => Arc::new(function),
+                None => db.function_with_body_sierra(function_id)?,
+            };
         functions.push(function.clone());
         statements.extend_from_slice(&function.body);
 
@@ -250,6 +281,9 @@ pub fn get_sierra_program_for_functions(
         }
     }
 
+    let processed_function_ids = processed_function_ids.into_iter().collect_vec();
+    let dict_squash_info = DictSquashInfo::collect(db, &processed_function_ids);
+    let bounds_check_elision_info = BoundsCheckElisionInfo::collect(db, &processed_function_ids);
     let libfunc_declarations =
         generate_libfunc_declarations(db, collect_used_libfuncs(&statements).iter());
     let type_declarations =
@@ -280,6 +314,9 @@ pub fn get_sierra_program_for_functions(
         program,
         debug_info: SierraProgramDebugInfo {
             statements_locations: StatementsLocations::from_locations_vec(&statements_locations),
+            statements_remapping: None,
+            dict_squash_info,
+            bounds_check_elision_info,
         },
     }))
 }
@@ -328,7 +365,13 @@ pub fn get_sierra_program(
     requested_crate_ids: Vec<CrateId>,
 ) -> Maybe<Arc<SierraProgramWithDebug>> {
     let mut requested_function_ids = vec![];
+    let whole_program = flag_whole_program(db.upcast()).then(|| db.whole_program()).transpose()?;
     for crate_id in requested_crate_ids {
+        // The functions of library crates are included only if called by other crates.
+        if whole_program.as_ref().is_some_and(|program| program.library_crates.contains(&crate_id))
+        {
+            continue;
+        }
         for module_id in db.crate_modules(crate_id).iter() {
             for (free_func_id, _) in db.module_free_functions(*module_id)?.iter() {
                 // TODO(spapini): Search Impl functions.
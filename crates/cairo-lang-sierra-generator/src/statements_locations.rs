@@ -4,6 +4,7 @@ use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::diagnostic_utils::StableLocation;
 use cairo_lang_diagnostics::ToOption;
 use cairo_lang_filesystem::ids::{FileId, FileLongId, VirtualFile};
+use cairo_lang_sierra::debug_info::SourceCodeLocation;
 use cairo_lang_sierra::program::StatementIdx;
 use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
@@ -168,6 +169,30 @@ impl StatementsLocations {
         self.locations.map(|s| containing_function_identifier_for_tests(db, *s.first().unwrap()))
     }
 
+    /// Builds a map between each Sierra statement index and the start of the first Cairo source
+    /// code location it was generated from.
+    pub fn get_source_code_locations(
+        &self,
+        db: &dyn DefsGroup,
+    ) -> UnorderedHashMap<StatementIdx, SourceCodeLocation> {
+        self.locations
+            .iter_sorted()
+            .filter_map(|(statement_idx, stable_locations)| {
+                let location = stable_locations.first()?.diagnostic_location(db);
+                let position =
+                    location.span.start.position_in_file(db.upcast(), location.file_id)?;
+                Some((
+                    *statement_idx,
+                    SourceCodeLocation {
+                        file: location.file_id.full_path(db.upcast()),
+                        line: position.line,
+                        column: position.col,
+                    },
+                ))
+            })
+            .collect()
+    }
+
     /// Creates a new [StatementsFunctions] struct using [StatementsLocations] and [DefsGroup].
     pub fn extract_statements_functions(&self, db: &dyn DefsGroup) -> StatementsFunctions {
         StatementsFunctions {
@@ -0,0 +1,68 @@
+use cairo_lang_sierra::debug_info::Annotations;
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+#[path = "statements_remapping_test.rs"]
+mod test;
+
+/// The namespace of the remapping in the annotations of the debug info of a program.
+pub const STATEMENTS_REMAPPING_ANNOTATION: &str =
+    "github.com/starkware-libs/cairo/statements-remapping/v1";
+
+/// The mapping from the statements of a Sierra program optimized by Sierra-level passes (e.g.
+/// [crate::inlining] or [crate::drop_elimination]) to the statements of the program as originally
+/// generated, so that data keyed by the original statement indices remains usable.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StatementsRemapping {
+    /// The original statement each statement of the optimized program originates from.
+    pub origins: Vec<StatementIdx>,
+}
+impl StatementsRemapping {
+    /// Returns the remapping of a program after a pass, given the remapping of the program before
+    /// the pass (`None` if its statements are the original ones), and the statement of the program
+    /// before the pass each statement after the pass originates from.
+    pub fn compose(previous: Option<&Self>, origins: &[StatementIdx]) -> Self {
+        Self {
+            origins: match previous {
+                Some(previous) => origins.iter().map(|origin| previous.origins[origin.0]).collect(),
+                None => origins.to_vec(),
+            },
+        }
+    }
+
+    /// Returns the original statement the given statement of the optimized program originates
+    /// from.
+    pub fn original(&self, idx: StatementIdx) -> Option<StatementIdx> {
+        self.origins.get(idx.0).copied()
+    }
+
+    /// Returns the statements of the optimized program each original statement is remapped to,
+    /// sorted by the original statements. A removed statement is missing from the mapping, and a
+    /// statement of an inlined function is remapped to its copies in its callers as well.
+    pub fn remapped_statements(&self) -> OrderedHashMap<StatementIdx, Vec<StatementIdx>> {
+        let mut remapped = OrderedHashMap::<StatementIdx, Vec<StatementIdx>>::default();
+        for (origin, idx) in self
+            .origins
+            .iter()
+            .enumerate()
+            .map(|(idx, origin)| (*origin, StatementIdx(idx)))
+            .sorted()
+        {
+            remapped.entry(origin).or_default().push(idx);
+        }
+        remapped
+    }
+}
+
+impl From<&StatementsRemapping> for Annotations {
+    fn from(value: &StatementsRemapping) -> Self {
+        let mapping = serde_json::to_value(value.remapped_statements()).unwrap();
+        OrderedHashMap::from([(
+            STATEMENTS_REMAPPING_ANNOTATION.to_string(),
+            serde_json::Value::from_iter([("remapped_statements", mapping)]),
+        )])
+    }
+}
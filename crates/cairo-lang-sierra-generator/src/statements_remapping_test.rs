@@ -0,0 +1,103 @@
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use cairo_lang_sierra::debug_info::Annotations;
+use cairo_lang_sierra::program::{Statement, StatementIdx};
+use cairo_lang_utils::extract_matches;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{StatementsRemapping, STATEMENTS_REMAPPING_ANNOTATION};
+use crate::db::SierraGenGroup;
+use crate::drop_elimination::eliminate_redundant_drops;
+use crate::inlining::inline_small_functions;
+use crate::test_utils::SierraGenDatabaseForTesting;
+
+fn statements(indices: &[usize]) -> Vec<StatementIdx> {
+    indices.iter().copied().map(StatementIdx).collect()
+}
+
+#[test]
+fn test_compose() {
+    let first = StatementsRemapping::compose(None, &statements(&[0, 2, 3, 2, 4]));
+    assert_eq!(first.origins, statements(&[0, 2, 3, 2, 4]));
+    let second = StatementsRemapping::compose(Some(&first), &statements(&[0, 1, 3, 4]));
+    assert_eq!(second.origins, statements(&[0, 2, 2, 4]));
+    assert_eq!(second.original(StatementIdx(3)), Some(StatementIdx(4)));
+    assert_eq!(second.original(StatementIdx(4)), None);
+}
+
+#[test]
+fn test_remapped_statements() {
+    let remapping = StatementsRemapping { origins: statements(&[0, 2, 3, 2, 4]) };
+    assert_eq!(
+        remapping.remapped_statements(),
+        OrderedHashMap::from_iter([
+            (StatementIdx(0), statements(&[0])),
+            (StatementIdx(2), statements(&[1, 3])),
+            (StatementIdx(3), statements(&[2])),
+            (StatementIdx(4), statements(&[4])),
+        ])
+    );
+    assert_eq!(
+        serde_json::to_value(&Annotations::from(&remapping)[STATEMENTS_REMAPPING_ANNOTATION])
+            .unwrap(),
+        serde_json::json!({"remapped_statements": {"0": [0], "2": [1, 3], "3": [2], "4": [4]}})
+    );
+}
+
+/// Tests that the statements of a program optimized by the Sierra-level passes are remapped to the
+/// original statements they originate from.
+#[test]
+fn test_optimized_program_remapping() {
+    let db = SierraGenDatabaseForTesting::new_empty();
+    let crate_id = setup_test_crate(
+        &db,
+        indoc! {"
+            #[inline(never)]
+            fn first(a: felt252, b: felt252) -> felt252 {
+                a
+            }
+            fn foo(a: felt252, b: felt252) -> felt252 {
+                first(a, b) + first(b, a)
+            }
+        "},
+    );
+    let item =
+        db.module_item_by_name(ModuleId::CrateRoot(crate_id), "foo".into()).unwrap().unwrap();
+    let foo = ConcreteFunctionWithBodyId::from_no_generics_free(
+        &db,
+        extract_matches!(item, ModuleItemId::FreeFunction),
+    )
+    .unwrap();
+    let original = db.get_sierra_program_for_functions(vec![foo]).unwrap();
+    assert_eq!(original.debug_info.statements_remapping, None);
+
+    let optimized = eliminate_redundant_drops(&db, &inline_small_functions(&db, &original, 3));
+    let remapping = optimized.debug_info.statements_remapping.unwrap();
+    assert_eq!(remapping.origins.len(), optimized.program.statements.len());
+    assert!(optimized.program.statements.len() < original.program.statements.len());
+    for (statement, origin) in optimized.program.statements.iter().zip(&remapping.origins) {
+        let original_statement = &original.program.statements[origin.0];
+        match (statement, original_statement) {
+            (Statement::Return(_), Statement::Return(_)) => {}
+            (Statement::Invocation(invocation), _)
+                if ["rename", "drop"].contains(
+                    &db.lookup_intern_concrete_lib_func(invocation.libfunc_id.clone())
+                        .generic_id
+                        .0
+                        .as_str(),
+                ) =>
+            {
+                // Renames and drops may replace any statement removed by the passes - e.g. the
+                // returns of inlined functions.
+            }
+            (Statement::Invocation(invocation), Statement::Invocation(original_invocation)) => {
+                assert_eq!(invocation.libfunc_id, original_invocation.libfunc_id);
+            }
+            _ => panic!("Statement `{statement}` is remapped to `{original_statement}`."),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::ids::NamedLanguageElementId;
 use cairo_lang_diagnostics::Maybe;
+use cairo_lang_lowering::ids::LocationId;
+use cairo_lang_sierra::debug_info::SourceCodeLocation;
 use cairo_lang_sierra::extensions::const_type::{
     ConstAsBoxLibfunc, ConstAsImmediateLibfunc, ConstType,
 };
@@ -416,3 +418,18 @@ pub fn get_concrete_libfunc_id(
 
     (None, generic_libfunc_id(db, extern_id, generic_args))
 }
+
+/// Returns the source code location of the outermost call site of `location`, i.e. of the user
+/// code the location was inlined into.
+pub fn outermost_source_code_location(
+    db: &dyn SierraGenGroup,
+    location: LocationId,
+) -> Option<SourceCodeLocation> {
+    let location = location.all_locations(db.upcast()).pop()?.diagnostic_location(db.upcast());
+    let position = location.span.start.position_in_file(db.upcast(), location.file_id)?;
+    Some(SourceCodeLocation {
+        file: location.file_id.full_path(db.upcast()),
+        line: position.line,
+        column: position.col,
+    })
+}
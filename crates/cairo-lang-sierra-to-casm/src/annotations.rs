@@ -1,4 +1,5 @@
 use std::iter;
+use std::ops::Range;
 
 use cairo_lang_casm::ap_change::{ApChangeError, ApplyApChange};
 use cairo_lang_sierra::edit_state::{put_results, take_args};
@@ -126,51 +127,62 @@ pub struct StatementAnnotations {
 /// Annotations of the program statements.
 /// See StatementAnnotations.
 pub struct ProgramAnnotations {
-    /// Optional per statement annotation.
+    /// Optional per statement annotation, starting from `first_statement_idx`.
     per_statement_annotations: Vec<Option<StatementAnnotations>>,
+    /// The index of the first annotated statement.
+    first_statement_idx: usize,
     /// The indices of the statements that are the targets of backwards jumps.
     backwards_jump_indices: UnorderedHashSet<StatementIdx>,
 }
 impl ProgramAnnotations {
-    fn new(n_statements: usize, backwards_jump_indices: UnorderedHashSet<StatementIdx>) -> Self {
+    fn new(
+        statements: Range<usize>,
+        backwards_jump_indices: UnorderedHashSet<StatementIdx>,
+    ) -> Self {
         ProgramAnnotations {
-            per_statement_annotations: iter::repeat_with(|| None).take(n_statements).collect(),
+            per_statement_annotations: iter::repeat_with(|| None).take(statements.len()).collect(),
+            first_statement_idx: statements.start,
             backwards_jump_indices,
         }
     }
 
-    /// Creates a ProgramAnnotations object based on 'n_statements', a given functions list
-    /// and metadata for the program.
+    /// Creates a ProgramAnnotations object for the statements in `statements`, based on a given
+    /// functions list and metadata for the program.
+    /// All the given functions must have their entry points within `statements`.
     pub fn create(
-        n_statements: usize,
+        statements: Range<usize>,
         backwards_jump_indices: UnorderedHashSet<StatementIdx>,
         functions: &[Function],
         metadata: &Metadata,
         gas_usage_check: bool,
         type_sizes: &TypeSizeMap,
     ) -> Result<Self, AnnotationError> {
-        let mut annotations = ProgramAnnotations::new(n_statements, backwards_jump_indices);
+        let mut annotations = ProgramAnnotations::new(statements, backwards_jump_indices);
         for func in functions {
             annotations.set_or_assert(
                 func.entry_point,
-                StatementAnnotations {
-                    refs: build_function_parameters_refs(func, type_sizes).map_err(|error| {
-                        AnnotationError::ReferencesError { statement_idx: func.entry_point, error }
-                    })?,
-                    function_id: func.id.clone(),
-                    convergence_allowed: false,
-                    environment: Environment::new(if gas_usage_check {
-                        GasWallet::Value(metadata.gas_info.function_costs[&func.id].clone())
-                    } else {
-                        GasWallet::Disabled
-                    }),
-                },
+                function_entry_annotations(func, metadata, gas_usage_check, type_sizes)?,
             )?
         }
 
         Ok(annotations)
     }
 
+    /// Returns the annotations slot of the given statement, if it is in the annotated range.
+    fn slot(&self, statement_idx: StatementIdx) -> Option<&Option<StatementAnnotations>> {
+        self.per_statement_annotations.get(statement_idx.0.checked_sub(self.first_statement_idx)?)
+    }
+
+    /// Returns the mutable annotations slot of the given statement, if it is in the annotated
+    /// range.
+    fn slot_mut(
+        &mut self,
+        statement_idx: StatementIdx,
+    ) -> Option<&mut Option<StatementAnnotations>> {
+        self.per_statement_annotations
+            .get_mut(statement_idx.0.checked_sub(self.first_statement_idx)?)
+    }
+
     /// Sets the annotations at 'statement_idx' to 'annotations'
     /// If the annotations for this statement were set previously asserts that the previous
     /// assignment is consistent with the new assignment and verifies that convergence_allowed
@@ -180,9 +192,8 @@ impl ProgramAnnotations {
         statement_idx: StatementIdx,
         annotations: StatementAnnotations,
     ) -> Result<(), AnnotationError> {
-        let idx = statement_idx.0;
-        match self.per_statement_annotations.get(idx).ok_or(AnnotationError::InvalidStatementIdx)? {
-            None => self.per_statement_annotations[idx] = Some(annotations),
+        match self.slot(statement_idx).ok_or(AnnotationError::InvalidStatementIdx)? {
+            None => *self.slot_mut(statement_idx).unwrap() = Some(annotations),
             Some(expected_annotations) => {
                 if expected_annotations.function_id != annotations.function_id {
                     return Err(AnnotationError::InconsistentFunctionId { statement_idx });
@@ -265,10 +276,12 @@ impl ProgramAnnotations {
         statement_idx: StatementIdx,
         ref_ids: impl Iterator<Item = &'a VarId>,
     ) -> Result<(StatementAnnotations, Vec<ReferenceValue>), AnnotationError> {
-        let existing = self.per_statement_annotations[statement_idx.0]
-            .as_mut()
+        let is_backwards_jump_target = self.backwards_jump_indices.contains(&statement_idx);
+        let existing = self
+            .slot_mut(statement_idx)
+            .and_then(|slot| slot.as_mut())
             .ok_or(AnnotationError::MissingAnnotationsForStatement(statement_idx))?;
-        let mut updated = if self.backwards_jump_indices.contains(&statement_idx) {
+        let mut updated = if is_backwards_jump_target {
             existing.clone()
         } else {
             std::mem::replace(
@@ -304,7 +317,12 @@ impl ProgramAnnotations {
         branch_changes: BranchChanges,
         must_set: bool,
     ) -> Result<(), AnnotationError> {
-        if must_set && self.per_statement_annotations[destination_statement_idx.0].is_some() {
+        if must_set
+            && self
+                .slot(destination_statement_idx)
+                .ok_or(AnnotationError::InvalidStatementIdx)?
+                .is_some()
+        {
             return Err(AnnotationError::AnnotationAlreadySet {
                 source_statement_idx,
                 destination_statement_idx,
@@ -473,6 +491,27 @@ impl ProgramAnnotations {
     }
 }
 
+/// Returns the annotations at the entry point of the given function.
+fn function_entry_annotations(
+    func: &Function,
+    metadata: &Metadata,
+    gas_usage_check: bool,
+    type_sizes: &TypeSizeMap,
+) -> Result<StatementAnnotations, AnnotationError> {
+    Ok(StatementAnnotations {
+        refs: build_function_parameters_refs(func, type_sizes).map_err(|error| {
+            AnnotationError::ReferencesError { statement_idx: func.entry_point, error }
+        })?,
+        function_id: func.id.clone(),
+        convergence_allowed: false,
+        environment: Environment::new(if gas_usage_check {
+            GasWallet::Value(metadata.gas_info.function_costs[&func.id].clone())
+        } else {
+            GasWallet::Disabled
+        }),
+    })
+}
+
 /// Checks whether or not the references `actual` and `expected` are consistent and can be merged
 /// in a way that will be re-compilable.
 /// Returns an error representing the inconsistency.
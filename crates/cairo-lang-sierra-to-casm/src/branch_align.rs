@@ -0,0 +1,117 @@
+//! Repair of Sierra programs missing the `branch_align` statements required at the targets of
+//! branching libfuncs.
+
+use std::borrow::Cow;
+
+use cairo_lang_sierra::ids::ConcreteLibfuncId;
+use cairo_lang_sierra::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, Invocation, LibfuncDeclaration, Program,
+    Statement, StatementIdx,
+};
+
+#[cfg(test)]
+#[path = "branch_align_test.rs"]
+mod test;
+
+/// Returns `program` with a `branch_align` statement inserted before every target of a branching
+/// libfunc that does not start with one, as expected by the compiler.
+///
+/// All the branches and entry points leading to such a target are redirected to its inserted
+/// `branch_align`, which equalizes the ap changes of the branches once the metadata is computed
+/// for the returned program. The `branch_align` libfunc is declared if the program does not
+/// declare it already. Returns the program as is if no statement is missing.
+pub fn insert_branch_aligns(program: &Program) -> Cow<'_, Program> {
+    let branch_align_ids: Vec<&ConcreteLibfuncId> = program
+        .libfunc_declarations
+        .iter()
+        .filter(|declaration| is_branch_align(&declaration.long_id))
+        .map(|declaration| &declaration.id)
+        .collect();
+    let mut missing = vec![];
+    for (idx, statement) in program.statements.iter().enumerate() {
+        let Statement::Invocation(invocation) = statement else {
+            continue;
+        };
+        if invocation.branches.len() <= 1 {
+            continue;
+        }
+        for branch in &invocation.branches {
+            let target = StatementIdx(idx).next(&branch.target);
+            // Targets out of the program are left for the compiler to report.
+            let Some(target_statement) = program.statements.get(target.0) else {
+                continue;
+            };
+            if !matches!(
+                target_statement,
+                Statement::Invocation(target_invocation)
+                    if branch_align_ids.contains(&&target_invocation.libfunc_id)
+            ) {
+                missing.push(target.0);
+            }
+        }
+    }
+    if missing.is_empty() {
+        return Cow::Borrowed(program);
+    }
+    missing.sort_unstable();
+    missing.dedup();
+
+    let mut libfunc_declarations = program.libfunc_declarations.clone();
+    let branch_align_id = match branch_align_ids.first() {
+        Some(id) => (*id).clone(),
+        None => {
+            let id = ConcreteLibfuncId {
+                id: libfunc_declarations
+                    .iter()
+                    .map(|declaration| declaration.id.id + 1)
+                    .max()
+                    .unwrap_or(0),
+                debug_name: Some("branch_align".into()),
+            };
+            libfunc_declarations.push(LibfuncDeclaration {
+                id: id.clone(),
+                long_id: ConcreteLibfuncLongId {
+                    generic_id: "branch_align".into(),
+                    generic_args: vec![],
+                },
+            });
+            id
+        }
+    };
+
+    // Every reference to a statement is shifted by the number of statements inserted before it,
+    // so that references to a repaired target point to its inserted `branch_align`.
+    let remap = |idx: StatementIdx| StatementIdx(idx.0 + missing.partition_point(|m| *m < idx.0));
+    let mut statements = Vec::with_capacity(program.statements.len() + missing.len());
+    for (idx, statement) in program.statements.iter().enumerate() {
+        if missing.binary_search(&idx).is_ok() {
+            statements.push(Statement::Invocation(Invocation {
+                libfunc_id: branch_align_id.clone(),
+                args: vec![],
+                branches: vec![BranchInfo { target: BranchTarget::Fallthrough, results: vec![] }],
+            }));
+        }
+        statements.push(statement.clone().map(remap));
+    }
+    let funcs = program
+        .funcs
+        .iter()
+        .map(|function| {
+            let mut function = function.clone();
+            function.entry_point = remap(function.entry_point);
+            function
+        })
+        .collect();
+    Cow::Owned(Program {
+        type_declarations: program.type_declarations.clone(),
+        libfunc_declarations,
+        statements,
+        funcs,
+    })
+}
+
+/// Returns true if `long_id` is the `branch_align` libfunc, the only libfunc aligning the ap
+/// changes of branches.
+fn is_branch_align(long_id: &ConcreteLibfuncLongId) -> bool {
+    long_id.generic_id.0 == "branch_align" && long_id.generic_args.is_empty()
+}
@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra::program_registry::ProgramRegistryError;
+use cairo_lang_sierra::ProgramParser;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::insert_branch_aligns;
+use crate::compiler::{compile, CompilationError, SierraToCasmConfig};
+use crate::metadata::calc_metadata_ap_change_only;
+
+#[test]
+fn insert_missing_branch_aligns() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop_nz = drop<NonZeroFelt252>;
+            libfunc felt252_const_1 = felt252_const<1>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            felt252_is_zero([0]) { fallthrough() 4([1]) };
+            felt252_const_1() -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+            drop_nz([1]) -> ();
+            felt252_const_1() -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+
+            main@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    let repaired = insert_branch_aligns(&program);
+    assert_eq!(
+        repaired.to_string(),
+        indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop_nz = drop<NonZeroFelt252>;
+            libfunc felt252_const_1 = felt252_const<1>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc branch_align = branch_align;
+
+            felt252_is_zero([0]) { fallthrough() 5([1]) }; // 0
+            branch_align() -> (); // 1
+            felt252_const_1() -> ([2]); // 2
+            store_temp_felt252([2]) -> ([2]); // 3
+            return([2]); // 4
+            branch_align() -> (); // 5
+            drop_nz([1]) -> (); // 6
+            felt252_const_1() -> ([2]); // 7
+            store_temp_felt252([2]) -> ([2]); // 8
+            store_temp_felt252([2]) -> ([2]); // 9
+            return([2]); // 10
+
+            main@0([0]: felt252) -> (felt252);
+        "}
+    );
+    // The repair of a repaired program does nothing.
+    assert!(matches!(insert_branch_aligns(&repaired), Cow::Borrowed(_)));
+
+    // The original program fails to compile, while the repaired one compiles.
+    let metadata = calc_metadata_ap_change_only(&repaired).unwrap();
+    assert_eq!(
+        compile(&program, &metadata, config).unwrap_err(),
+        Box::new(CompilationError::ProgramRegistryError(Box::new(
+            ProgramRegistryError::BranchNotToBranchAlign {
+                src: StatementIdx(0),
+                dst: StatementIdx(1)
+            }
+        )))
+    );
+    let cairo_program = compile(&repaired, &metadata, config).unwrap();
+    assert_eq!(
+        cairo_program.to_string(),
+        indoc! {"
+            jmp rel 7 if [fp + -3] != 0;
+            ap += 1;
+            [ap + 0] = 1, ap++;
+            ret;
+            [ap + 0] = 1, ap++;
+            [ap + 0] = [ap + -1], ap++;
+            ret;
+        "}
+    );
+}
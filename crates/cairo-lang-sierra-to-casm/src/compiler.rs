@@ -1,38 +1,49 @@
 use std::fmt::Display;
+use std::ops::Range;
 
 use cairo_lang_casm::assembler::AssembledCairoProgram;
+use cairo_lang_casm::casm;
 use cairo_lang_casm::instructions::{Instruction, InstructionBody, RetInstruction};
+use cairo_lang_sierra::debug_info::SourceCodeLocation;
 use cairo_lang_sierra::extensions::const_type::ConstConcreteLibfunc;
 use cairo_lang_sierra::extensions::core::{
     CoreConcreteLibfunc, CoreLibfunc, CoreType, CoreTypeConcrete,
 };
 use cairo_lang_sierra::extensions::coupon::CouponConcreteLibfunc;
-use cairo_lang_sierra::extensions::gas::GasConcreteLibfunc;
+use cairo_lang_sierra::extensions::gas::{CostTokenType, GasConcreteLibfunc};
 use cairo_lang_sierra::extensions::lib_func::SierraApChange;
 use cairo_lang_sierra::extensions::ConcreteLibfunc;
-use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, VarId};
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, VarId};
 use cairo_lang_sierra::program::{
-    BranchTarget, GenericArg, Invocation, Program, Statement, StatementIdx,
+    BranchTarget, Function, GenericArg, Invocation, Program, Statement, StatementIdx,
 };
 use cairo_lang_sierra::program_registry::{ProgramRegistry, ProgramRegistryError};
+use cairo_lang_sierra_gas::gas_info::GasInfo;
 use cairo_lang_sierra_type_size::{get_type_size_map, TypeSizeMap};
 use cairo_lang_utils::casts::IntoOrPanic;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
-use itertools::{chain, zip_eq};
+use itertools::{chain, zip_eq, Itertools};
 use num_bigint::BigInt;
 use num_traits::{ToPrimitive, Zero};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use thiserror::Error;
 
 use crate::annotations::{AnnotationError, ProgramAnnotations, StatementAnnotations};
 use crate::invocations::enm::get_variant_selector;
 use crate::invocations::{
-    check_references_on_stack, compile_invocation, BranchChanges, InvocationError, ProgramInfo,
+    check_references_on_stack, compile_invocation, BranchChanges, CompiledInvocation,
+    InvocationError, ProgramInfo,
 };
 use crate::metadata::Metadata;
-use crate::references::{check_types_match, ReferenceValue, ReferencesError};
-use crate::relocations::{relocate_instructions, RelocationEntry};
+use crate::peephole;
+use crate::references::{
+    check_types_match, IntroductionPoint, OutputReferenceValue,
+    OutputReferenceValueIntroductionPoint, ReferenceValue, ReferencesError,
+};
+use crate::relocations::{relocate_instructions, CodeOffset, Relocation, RelocationEntry};
+use crate::segmentation::{compute_bytecode_segment_lengths, NestedIntList, SegmentationError};
 
 #[cfg(test)]
 #[path = "compiler_test.rs"]
@@ -69,6 +80,8 @@ pub enum CompilationError {
     ConstSegmentsOutOfOrder,
     #[error("Code size limit exceeded.")]
     CodeSizeLimitExceeded,
+    #[error("Function `{0}` not found in the program.")]
+    UnknownFunction(FunctionId),
     #[error("Unknown function id in metadata.")]
     MetadataUnknownFunctionId,
     #[error("Statement #{0} out of bounds in metadata.")]
@@ -88,14 +101,69 @@ pub struct SierraToCasmConfig {
     pub gas_usage_check: bool,
     /// CASM bytecode size limit.
     pub max_bytecode_size: usize,
+    /// Whether to apply peephole optimizations to the compiled instructions, see
+    /// [crate::peephole::optimize].
+    pub optimize: bool,
+    /// Whether to skip the compilation of statements unreachable from the entry points of the
+    /// functions of the program, instead of failing on their missing annotations.
+    pub eliminate_dead_code: bool,
+    /// Whether to coalesce `ap += <imm>` instructions across consecutive statements, see
+    /// [crate::peephole::coalesce_ap_updates].
+    pub coalesce_ap_updates: bool,
+    /// Whether to attach the gas costs pre-computed in the metadata to the debug information of
+    /// the statements, see [CairoProgramDebugInfo::populate_gas_costs].
+    pub annotate_gas_costs: bool,
+}
+impl Default for SierraToCasmConfig {
+    /// Returns the configuration of a plain compilation: checking the gas usage, with no bytecode
+    /// size limit and with all the optional passes disabled.
+    fn default() -> Self {
+        Self {
+            gas_usage_check: true,
+            max_bytecode_size: usize::MAX,
+            optimize: false,
+            eliminate_dead_code: false,
+            coalesce_ap_updates: false,
+            annotate_gas_costs: false,
+        }
+    }
+}
+
+/// Hooks into the compilation of a program, e.g. for collecting timings, instrumenting the code
+/// size or dumping intermediate state, see [compile_with_observer].
+///
+/// The functions of a program may be compiled in parallel, so the callbacks for statements of
+/// different functions may be invoked concurrently, while the callbacks for the statements of a
+/// single function are invoked in order.
+pub trait CompilationObserver: Sync {
+    /// Called before compiling the statement `statement_idx`.
+    fn on_statement_start(&self, _statement_idx: StatementIdx) {}
+
+    /// Called after compiling the statement `statement_idx`, with the size of its bytecode before
+    /// peephole optimizations.
+    fn on_statement_end(&self, _statement_idx: StatementIdx, _code_size: usize) {}
+
+    /// Called with the compilation of the invocation statement `statement_idx`.
+    fn on_invocation(&self, _statement_idx: StatementIdx, _invocation: &CompiledInvocation) {}
+
+    /// Called with each relocation applied to the instructions of the program, and the relocated
+    /// instruction.
+    fn on_relocation(&self, _entry: &RelocationEntry, _instruction: &Instruction) {}
 }
+/// An observer ignoring the compilation.
+impl CompilationObserver for () {}
 
 /// The casm program representation.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct CairoProgram {
     pub instructions: Vec<Instruction>,
     pub debug_info: CairoProgramDebugInfo,
     pub consts_info: ConstsInfo,
+    /// The relocations applied to the instructions, ordered by the relocated instructions. Used
+    /// for incremental recompilation, and for relocating the instructions into a different
+    /// layout, see [CairoProgram::unrelocated_instructions].
+    pub relocations: Vec<RelocationEntry>,
 }
 impl Display for CairoProgram {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -169,10 +237,97 @@ impl CairoProgram {
         }
         AssembledCairoProgram { bytecode, hints }
     }
+
+    /// Returns the offsets of the statements of the program within the bytecode, followed by the
+    /// offset of the end of the code, i.e. the layout the relocations of the program were applied
+    /// with.
+    pub fn statement_offsets(&self) -> Vec<CodeOffset> {
+        let statement_infos = &self.debug_info.sierra_statement_info;
+        chain!(
+            statement_infos.iter().map(|info| info.start_offset),
+            [statement_infos.last().map_or(0, |info| info.end_offset)]
+        )
+        .collect()
+    }
+
+    /// Returns the size of the bytecode of the program, including its const segments.
+    pub fn bytecode_len(&self) -> usize {
+        self.debug_info.sierra_statement_info.last().map_or(0, |info| info.end_offset)
+            + self.consts_info.total_segments_size
+    }
+
+    /// Returns the lengths of the segments of the bytecode of the program: a segment per function
+    /// of `program`, the Sierra program this program was compiled from, followed by a segment per
+    /// const segment.
+    ///
+    /// These are the lengths used for computing the hash of a segmented bytecode, e.g. the
+    /// `bytecode_segment_lengths` of a Starknet contract class.
+    pub fn bytecode_segment_lengths(
+        &self,
+        program: &Program,
+    ) -> Result<NestedIntList, SegmentationError> {
+        compute_bytecode_segment_lengths(program, self, self.bytecode_len())
+    }
+
+    /// Returns the instructions of the program before the application of its relocations.
+    ///
+    /// The relocations can then be applied to the returned instructions with a different layout
+    /// using [relocate_instructions], e.g. when linking the program with other programs.
+    pub fn unrelocated_instructions(&self) -> Vec<Instruction> {
+        let statement_offsets = self.statement_offsets();
+        let mut instructions = self.instructions.clone();
+        let instruction_offsets: Vec<CodeOffset> = instructions
+            .iter()
+            .scan(0, |offset, instruction| {
+                let instruction_offset = *offset;
+                *offset += instruction.body.op_size();
+                Some(instruction_offset)
+            })
+            .collect();
+        for entry in &self.relocations {
+            entry.relocation.revert(
+                instruction_offsets[entry.instruction_idx],
+                &statement_offsets,
+                &self.consts_info,
+                &mut instructions[entry.instruction_idx],
+            );
+        }
+        instructions
+    }
+
+    /// Returns the size of the bytecode of each of the functions of `program`, the Sierra program
+    /// this program was compiled from.
+    ///
+    /// Each statement is attributed to the function with the closest preceding entry point. The
+    /// const segments are not attributed to any function.
+    pub fn function_code_sizes(&self, program: &Program) -> OrderedHashMap<FunctionId, usize> {
+        let statement_infos = &self.debug_info.sierra_statement_info;
+        let entry_points: Vec<_> =
+            program.funcs.iter().map(|func| func.entry_point.0).sorted().dedup().collect();
+        program
+            .funcs
+            .iter()
+            .map(|func| {
+                let start = func.entry_point.0;
+                let end = entry_points
+                    .iter()
+                    .find(|entry_point| **entry_point > start)
+                    .copied()
+                    .unwrap_or(statement_infos.len());
+                let size = match &statement_infos[start..end] {
+                    [first, .., last] => last.end_offset - first.start_offset,
+                    [single] => single.end_offset - single.start_offset,
+                    [] => 0,
+                };
+                (func.id.clone(), size)
+            })
+            .collect()
+    }
 }
 
 /// The debug information of a compilation from Sierra to casm.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct SierraStatementDebugInfo {
     /// The start offset of the sierra statement within the bytecode.
     pub start_offset: usize,
@@ -182,11 +337,20 @@ pub struct SierraStatementDebugInfo {
     pub instruction_idx: usize,
     /// Statement-kind-dependent information.
     pub additional_kind_info: StatementKindDebugInfo,
+    /// The Cairo source code location the statement originated from, if known.
+    ///
+    /// Not filled by [compile], see [CairoProgramDebugInfo::populate_source_locations].
+    pub source_location: Option<SourceCodeLocation>,
+    /// The gas costs pre-computed for the statement, per cost token type.
+    ///
+    /// Only filled by [compile] if [SierraToCasmConfig::annotate_gas_costs] is set.
+    pub gas_costs: Option<OrderedHashMap<CostTokenType, i64>>,
 }
 
 /// Additional debug information for a Sierra statement, depending on its kind
 /// (invoke/return/dummy).
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum StatementKindDebugInfo {
     Return(ReturnStatementDebugInfo),
     Invoke(InvokeStatementDebugInfo),
@@ -194,6 +358,7 @@ pub enum StatementKindDebugInfo {
 
 /// Additional debug information for a return Sierra statement.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ReturnStatementDebugInfo {
     /// The references of a Sierra return statement.
     pub ref_values: Vec<ReferenceValue>,
@@ -201,6 +366,7 @@ pub struct ReturnStatementDebugInfo {
 
 /// Additional debug information for an invoke Sierra statement.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct InvokeStatementDebugInfo {
     /// The result branch changes of a Sierra invoke statement.
     pub result_branch_changes: Vec<BranchChanges>,
@@ -210,13 +376,42 @@ pub struct InvokeStatementDebugInfo {
 
 /// The debug information of a compilation from Sierra to casm.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct CairoProgramDebugInfo {
     /// The debug information per Sierra statement.
     pub sierra_statement_info: Vec<SierraStatementDebugInfo>,
 }
+impl CairoProgramDebugInfo {
+    /// Attaches the given Cairo source code locations to the matching Sierra statements.
+    /// Statements without a location in the map are left untouched.
+    pub fn populate_source_locations(
+        &mut self,
+        locations: &UnorderedHashMap<StatementIdx, SourceCodeLocation>,
+    ) {
+        for (idx, info) in self.sierra_statement_info.iter_mut().enumerate() {
+            if let Some(location) = locations.get(&StatementIdx(idx)) {
+                info.source_location = Some(location.clone());
+            }
+        }
+    }
+
+    /// Attaches the gas costs pre-computed in `gas_info` to the matching Sierra statements.
+    /// Statements without pre-computed gas costs are attached an empty map.
+    pub fn populate_gas_costs(&mut self, gas_info: &GasInfo) {
+        let mut gas_costs =
+            UnorderedHashMap::<StatementIdx, OrderedHashMap<CostTokenType, i64>>::default();
+        for ((idx, token_type), value) in gas_info.variable_values.iter() {
+            gas_costs.entry(*idx).or_default().insert(*token_type, *value);
+        }
+        for (idx, info) in self.sierra_statement_info.iter_mut().enumerate() {
+            info.gas_costs = Some(gas_costs.remove(&StatementIdx(idx)).unwrap_or_default());
+        }
+    }
+}
 
 /// The information about the constants used in the program.
 #[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ConstsInfo {
     pub segments: OrderedHashMap<u32, ConstSegment>,
     pub total_segments_size: usize,
@@ -231,16 +426,26 @@ impl ConstsInfo {
     ) -> Result<Self, CompilationError> {
         let mut segments_data_size = 0;
         let mut segments = OrderedHashMap::default();
+        // The offset of each distinct sequence of values already added to each of the segments, so
+        // that consts with identical values share their slot in the segment.
+        let mut data_offsets = UnorderedHashMap::<(u32, Vec<BigInt>), usize>::default();
         for id in libfunc_ids {
             if let CoreConcreteLibfunc::Const(ConstConcreteLibfunc::AsBox(as_box)) =
                 registry.get_libfunc(id).unwrap()
             {
                 let segment: &mut ConstSegment = segments.entry(as_box.segment_id).or_default();
-                let const_data =
-                    extract_const_value(registry, type_sizes, &as_box.const_type).unwrap();
-                segments_data_size += const_data.len();
-                segment.const_offset.insert(as_box.const_type.clone(), segment.values.len());
-                segment.values.extend(const_data);
+                let const_data = extract_const_value(registry, type_sizes, &as_box.const_type)?;
+                let data_key = (as_box.segment_id, const_data);
+                let data_offset = if let Some(offset) = data_offsets.get(&data_key) {
+                    *offset
+                } else {
+                    let offset = segment.values.len();
+                    segments_data_size += data_key.1.len();
+                    segment.values.extend(data_key.1.iter().cloned());
+                    data_offsets.insert(data_key, offset);
+                    offset
+                };
+                segment.const_offset.insert(as_box.const_type.clone(), data_offset);
                 if segments_data_size + segments.len() > const_segments_max_size {
                     return Err(CompilationError::CodeSizeLimitExceeded);
                 }
@@ -267,17 +472,28 @@ impl ConstsInfo {
 
 /// The data for a single segment.
 #[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ConstSegment {
     /// The values in the segment.
     pub values: Vec<BigInt>,
     /// The offset of each const within the segment.
-    pub const_offset: UnorderedHashMap<ConcreteTypeId, usize>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "cairo_lang_utils::ordered_hash_map::serialize_ordered_hashmap_vec",
+            deserialize_with = "cairo_lang_utils::ordered_hash_map::deserialize_ordered_hashmap_vec"
+        )
+    )]
+    pub const_offset: OrderedHashMap<ConcreteTypeId, usize>,
     /// The offset of the segment relative to the end of the code segment.
     pub segment_offset: usize,
 }
 
 /// Gets a concrete type, if it is a const type returns a vector of the values to be stored in the
 /// const segment.
+///
+/// For a const span, only the values of its elements are returned, as the segment can't contain
+/// pointers - so spans are only supported as the outermost const type.
 fn extract_const_value(
     registry: &ProgramRegistry<CoreType, CoreLibfunc>,
     type_sizes: &TypeSizeMap,
@@ -285,12 +501,23 @@ fn extract_const_value(
 ) -> Result<Vec<BigInt>, CompilationError> {
     let mut values = Vec::new();
     let mut types_stack = vec![ty.clone()];
+    let mut is_outermost = true;
     while let Some(ty) = types_stack.pop() {
         let CoreTypeConcrete::Const(const_type) = registry.get_type(&ty).unwrap() else {
             return Err(CompilationError::UnsupportedConstType);
         };
         let inner_type = registry.get_type(&const_type.inner_ty).unwrap();
         match inner_type {
+            CoreTypeConcrete::Span(_) if is_outermost => {
+                // Add the elements' types to the stack in reverse order.
+                for arg in const_type.inner_data.iter().rev() {
+                    match arg {
+                        GenericArg::Type(arg_ty) => types_stack.push(arg_ty.clone()),
+                        _ => return Err(CompilationError::ConstDataMismatch),
+                    }
+                }
+            }
+            CoreTypeConcrete::Span(_) => return Err(CompilationError::UnsupportedConstType),
             CoreTypeConcrete::Struct(_) => {
                 // Add the struct members' types to the stack in reverse order.
                 for arg in const_type.inner_data.iter().rev() {
@@ -336,6 +563,7 @@ fn extract_const_value(
                 _ => return Err(CompilationError::ConstDataMismatch),
             },
         };
+        is_outermost = false;
     }
     Ok(values)
 }
@@ -371,15 +599,103 @@ pub fn compile(
     metadata: &Metadata,
     config: SierraToCasmConfig,
 ) -> Result<CairoProgram, Box<CompilationError>> {
-    let mut instructions = Vec::new();
-    let mut relocations: Vec<RelocationEntry> = Vec::new();
+    compile_with_observer(program, metadata, config, &())
+}
 
-    // Maps statement_idx to its debug info.
-    // The last value (for statement_idx=number-of-statements)
-    // contains the final offset (the size of the program code segment).
-    let mut sierra_statement_info: Vec<SierraStatementDebugInfo> =
-        Vec::with_capacity(program.statements.len());
+/// Compiles `program` as done by [compile], reporting the progress of the compilation to
+/// `observer`.
+pub fn compile_with_observer(
+    program: &Program,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+    observer: &dyn CompilationObserver,
+) -> Result<CairoProgram, Box<CompilationError>> {
+    compile_ex(program, metadata, config, None, None, observer)
+}
+
+/// Compiles `program` from Sierra to CASM, reusing the compilation of `previous_program` given in
+/// `previous` for all the functions not in `changed_functions`.
+///
+/// The functions of both programs must be laid out contiguously by their entry points, as done by
+/// the Sierra generator. Functions whose compilation may be affected by the metadata of other
+/// functions (e.g. callers of changed functions whose costs changed) must be included in
+/// `changed_functions` as well. If the type or libfunc declarations of the programs differ, the
+/// program is fully recompiled.
+pub fn compile_incremental(
+    previous: &CairoProgram,
+    previous_program: &Program,
+    program: &Program,
+    changed_functions: &UnorderedHashSet<FunctionId>,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+) -> Result<CairoProgram, Box<CompilationError>> {
+    let reused = ReusedStatements::new(previous, previous_program, program, changed_functions);
+    compile_ex(program, metadata, config, reused.as_ref(), None, &())
+}
 
+/// Compiles only the function `function_id` of `program` and the functions it transitively calls,
+/// as done by [compile], into a standalone program.
+///
+/// The program starts with an entry header calling the function and returning its results, so
+/// that the program can be run directly with the arguments of the function on the stack. The
+/// statements of the other functions, and the statements unreachable from the compiled functions,
+/// are kept in the debug info without any instructions.
+pub fn compile_function(
+    program: &Program,
+    function_id: &FunctionId,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+) -> Result<CairoProgram, Box<CompilationError>> {
+    let func = program
+        .funcs
+        .iter()
+        .find(|func| &func.id == function_id)
+        .ok_or_else(|| Box::new(CompilationError::UnknownFunction(function_id.clone())))?;
+    let mut cairo_program = compile_ex(program, metadata, config, None, Some(func), &())?;
+
+    // The size of the `call rel` and `ret` instructions of the header.
+    let header_size = 3;
+    let offset = header_size
+        + cairo_program.debug_info.sierra_statement_info[func.entry_point.0].start_offset;
+    let header = casm! {
+        call rel offset;
+        ret;
+    }
+    .instructions;
+    for info in &mut cairo_program.debug_info.sierra_statement_info {
+        info.start_offset += header_size;
+        info.end_offset += header_size;
+        info.instruction_idx += header.len();
+    }
+    for entry in &mut cairo_program.relocations {
+        entry.instruction_idx += header.len();
+    }
+    cairo_program.relocations.insert(
+        0,
+        RelocationEntry {
+            instruction_idx: 0,
+            relocation: Relocation::RelativeStatementId(func.entry_point),
+        },
+    );
+    cairo_program.instructions.splice(0..0, header);
+    Ok(cairo_program)
+}
+
+/// Compiles `program` from Sierra to CASM as done by [compile], but instead of collecting the
+/// instructions of the program, passes the relocated instructions of each statement, in order, to
+/// `emit_statement`. Returns the information about the constants of the program, whose segments
+/// follow its instructions.
+///
+/// The program is compiled twice - once for computing the offsets of its statements, and once for
+/// emitting them - so that only the instructions of a single function are held in memory at any
+/// time. If the functions of the program cannot be compiled separately (e.g. due to jumps between
+/// functions), the program is compiled as a whole instead.
+pub fn compile_streaming(
+    program: &Program,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+    mut emit_statement: impl FnMut(StatementIdx, Vec<Instruction>),
+) -> Result<ConstsInfo, Box<CompilationError>> {
     let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_ap_change(
         program,
         metadata.ap_change_info.function_ap_change.clone(),
@@ -388,42 +704,621 @@ pub fn compile(
     validate_metadata(program, &registry, metadata)?;
     let type_sizes = get_type_size_map(program, &registry)
         .ok_or(CompilationError::FailedBuildingTypeInformation)?;
-    let mut backwards_jump_indices = UnorderedHashSet::<_>::default();
-    for (statement_id, statement) in program.statements.iter().enumerate() {
-        if let Statement::Invocation(invocation) = statement {
-            for branch in &invocation.branches {
-                if let BranchTarget::Statement(target) = branch.target {
-                    if target.0 < statement_id {
-                        backwards_jump_indices.insert(target);
+    let compiler = StatementsCompiler {
+        program,
+        registry: &registry,
+        type_sizes: &type_sizes,
+        metadata,
+        config,
+        reused: None,
+        included_functions: None,
+        emit_instructions: true,
+        observer: &(),
+    };
+    let chunks: Vec<(Range<usize>, &[Function])> = match function_chunks(program) {
+        Some(function_chunks) => function_chunks
+            .into_iter()
+            .map(|(statements, func)| (statements, std::slice::from_ref(func)))
+            .collect(),
+        None => vec![(0..program.statements.len(), &program.funcs[..])],
+    };
+    // The offsets of the statements, including the final offset.
+    let mut statement_offsets = vec![0];
+    for (statements, functions) in &chunks {
+        let chunk = compiler.compile_statements(statements.clone(), functions)?;
+        let chunk_offset = *statement_offsets.last().unwrap();
+        statement_offsets
+            .extend(chunk.sierra_statement_info.iter().map(|info| chunk_offset + info.end_offset));
+    }
+
+    let const_segments_max_size = config
+        .max_bytecode_size
+        .checked_sub(*statement_offsets.last().unwrap())
+        .ok_or_else(|| Box::new(CompilationError::CodeSizeLimitExceeded))?;
+    let consts_info = ConstsInfo::new(
+        &registry,
+        &type_sizes,
+        program.libfunc_declarations.iter().map(|ld| &ld.id),
+        const_segments_max_size,
+    )?;
+
+    for (statements, functions) in chunks {
+        let chunk = compiler.compile_statements(statements.clone(), functions)?;
+        let mut program_offset = statement_offsets[statements.start];
+        let mut relocations = chunk.relocations.into_iter().peekable();
+        let mut instructions = chunk.instructions.into_iter();
+        let instruction_indices = chunk
+            .sierra_statement_info
+            .iter()
+            .map(|info| info.instruction_idx)
+            .chain([instructions.len()])
+            .tuple_windows();
+        for (statement_id, (start, end)) in zip_eq(statements, instruction_indices) {
+            let mut statement_instructions = instructions.by_ref().take(end - start).collect_vec();
+            for (instruction_idx, instruction) in (start..end).zip(&mut statement_instructions) {
+                if let Some(entry) =
+                    relocations.next_if(|entry| entry.instruction_idx == instruction_idx)
+                {
+                    entry.relocation.apply(
+                        program_offset,
+                        &statement_offsets,
+                        &consts_info,
+                        instruction,
+                    );
+                }
+                program_offset += instruction.body.op_size();
+            }
+            emit_statement(StatementIdx(statement_id), statement_instructions);
+        }
+    }
+    Ok(consts_info)
+}
+
+/// The result of validating a program with [validate].
+#[derive(Debug, Eq, PartialEq)]
+pub struct ValidationReport {
+    /// The number of statements of the program.
+    pub n_statements: usize,
+    /// The number of functions of the program.
+    pub n_functions: usize,
+    /// The size of the bytecode of the statements of the program, before peephole optimizations
+    /// and excluding the const segments. Only computed if all the statements are valid.
+    pub code_size: Option<usize>,
+    /// The errors found in the program.
+    pub errors: Vec<ValidationError>,
+}
+impl ValidationReport {
+    /// Returns whether the program is valid, i.e. would compile successfully.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// An error found while validating a program.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ValidationError {
+    /// The function the error was found in, if it is not a program-wide error and the function
+    /// could be validated on its own.
+    pub function_id: Option<FunctionId>,
+    pub error: Box<CompilationError>,
+}
+
+/// Validates `program` with the checks done by [compile], without emitting or relocating its
+/// instructions.
+///
+/// When the functions of the program can be validated independently, the errors of all the
+/// invalid functions are reported. Otherwise, only the first error of the program is reported, as
+/// done by [compile].
+pub fn validate(
+    program: &Program,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+) -> ValidationReport {
+    let mut report = ValidationReport {
+        n_statements: program.statements.len(),
+        n_functions: program.funcs.len(),
+        code_size: None,
+        errors: vec![],
+    };
+    let program_error = |report: &mut ValidationReport, error: Box<CompilationError>| {
+        report.errors.push(ValidationError { function_id: None, error });
+    };
+    let registry = match ProgramRegistry::<CoreType, CoreLibfunc>::new_with_ap_change(
+        program,
+        metadata.ap_change_info.function_ap_change.clone(),
+    ) {
+        Ok(registry) => registry,
+        Err(err) => {
+            program_error(&mut report, Box::new(CompilationError::ProgramRegistryError(err)));
+            return report;
+        }
+    };
+    if let Err(err) = validate_metadata(program, &registry, metadata) {
+        program_error(&mut report, Box::new(err));
+        return report;
+    }
+    let Some(type_sizes) = get_type_size_map(program, &registry) else {
+        program_error(&mut report, Box::new(CompilationError::FailedBuildingTypeInformation));
+        return report;
+    };
+    let compiler = StatementsCompiler {
+        program,
+        registry: &registry,
+        type_sizes: &type_sizes,
+        metadata,
+        config,
+        reused: None,
+        included_functions: None,
+        emit_instructions: false,
+        observer: &(),
+    };
+    let code_size = match function_chunks(program) {
+        Some(function_chunks) => {
+            let results: Vec<_> = function_chunks
+                .into_par_iter()
+                .map(|(statements, func)| {
+                    (func, compiler.compile_statements(statements, std::slice::from_ref(func)))
+                })
+                .collect();
+            let mut code_size = 0;
+            let mut errors = vec![];
+            for (func, result) in results {
+                match result {
+                    Ok(chunk) => code_size += chunk.code_size,
+                    Err(error) => {
+                        errors.push(ValidationError { function_id: Some(func.id.clone()), error })
                     }
                 }
             }
+            if !errors.is_empty() {
+                report.errors = errors;
+                return report;
+            }
+            code_size
         }
+        None => match compiler.compile_statements(0..program.statements.len(), &program.funcs) {
+            Ok(chunk) => chunk.code_size,
+            Err(err) => {
+                program_error(&mut report, err);
+                return report;
+            }
+        },
+    };
+    report.code_size = Some(code_size);
+
+    let Some(const_segments_max_size) = config.max_bytecode_size.checked_sub(code_size) else {
+        program_error(&mut report, Box::new(CompilationError::CodeSizeLimitExceeded));
+        return report;
+    };
+    if let Err(err) = ConstsInfo::new(
+        &registry,
+        &type_sizes,
+        program.libfunc_declarations.iter().map(|ld| &ld.id),
+        const_segments_max_size,
+    ) {
+        program_error(&mut report, Box::new(err));
     }
-    let mut program_annotations = ProgramAnnotations::create(
-        program.statements.len(),
-        backwards_jump_indices,
+    report
+}
+
+/// An error found by [compile_collecting_errors].
+#[derive(Debug, Eq, PartialEq)]
+pub struct StatementCompilationError {
+    /// The statement the error was found at, or `None` for a program-wide error.
+    pub statement_idx: Option<StatementIdx>,
+    pub error: Box<CompilationError>,
+}
+
+/// Compiles `program` as done by [compile], but instead of failing on the first invalid statement,
+/// keeps compiling the following statements, and returns the errors of all the invalid statements,
+/// ordered by the statements.
+///
+/// The statements whose annotations are missing due to a previous error are skipped, so each
+/// problem is reported once. Errors which are not specific to a statement (e.g. an invalid
+/// program registry) are reported alone.
+pub fn compile_collecting_errors(
+    program: &Program,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+) -> Result<CairoProgram, Vec<StatementCompilationError>> {
+    let error = match compile_ex(program, metadata, config, None, None, &()) {
+        Ok(cairo_program) => return Ok(cairo_program),
+        Err(error) => error,
+    };
+    let errors = statement_errors(program, metadata, config);
+    Err(if errors.is_empty() {
+        vec![StatementCompilationError { statement_idx: None, error }]
+    } else {
+        errors
+    })
+}
+
+/// Returns the errors of all the invalid statements of `program`, found by compiling its statements
+/// sequentially, or an empty vector if the program can't be compiled at all.
+fn statement_errors(
+    program: &Program,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+) -> Vec<StatementCompilationError> {
+    let Ok(registry) = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_ap_change(
+        program,
+        metadata.ap_change_info.function_ap_change.clone(),
+    ) else {
+        return vec![];
+    };
+    if validate_metadata(program, &registry, metadata).is_err() {
+        return vec![];
+    }
+    let Some(type_sizes) = get_type_size_map(program, &registry) else {
+        return vec![];
+    };
+    let compiler = StatementsCompiler {
+        program,
+        registry: &registry,
+        type_sizes: &type_sizes,
+        metadata,
+        config,
+        reused: None,
+        included_functions: None,
+        emit_instructions: false,
+        observer: &(),
+    };
+    let mut errors = vec![];
+    if let Err(error) = compiler.compile_statements_ex(
+        0..program.statements.len(),
         &program.funcs,
+        Some(&mut errors),
+    ) {
+        errors.push(StatementCompilationError { statement_idx: None, error });
+    }
+    errors
+}
+
+/// Compiles `program`, copying the compilation of the statements in `reused` if provided. If
+/// `entry_function` is provided, only the statements reachable from it and from the functions it
+/// transitively calls are compiled. The progress of the compilation is reported to `observer`.
+///
+/// The functions of the program are compiled independently in parallel when each of them spans a
+/// contiguous range of statements that it does not jump out of, and are then stitched together by
+/// the final relocation pass.
+fn compile_ex(
+    program: &Program,
+    metadata: &Metadata,
+    config: SierraToCasmConfig,
+    reused: Option<&ReusedStatements<'_>>,
+    entry_function: Option<&Function>,
+    observer: &dyn CompilationObserver,
+) -> Result<CairoProgram, Box<CompilationError>> {
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_ap_change(
+        program,
+        metadata.ap_change_info.function_ap_change.clone(),
+    )
+    .map_err(CompilationError::ProgramRegistryError)?;
+    validate_metadata(program, &registry, metadata)?;
+    let included_functions = entry_function
+        .map(|entry_function| called_functions(program, &registry, entry_function))
+        .transpose()?;
+    let type_sizes = get_type_size_map(program, &registry)
+        .ok_or(CompilationError::FailedBuildingTypeInformation)?;
+    let compiler = StatementsCompiler {
+        program,
+        registry: &registry,
+        type_sizes: &type_sizes,
         metadata,
-        config.gas_usage_check,
+        config,
+        reused,
+        included_functions: included_functions.as_ref(),
+        emit_instructions: true,
+        observer,
+    };
+    let chunks = match function_chunks(program) {
+        Some(function_chunks) => function_chunks
+            .into_par_iter()
+            .map(|(statements, func)| {
+                compiler.compile_statements(statements, std::slice::from_ref(func))
+            })
+            .collect::<Vec<_>>()
+            // Collected before checking for errors, so that the reported error is the first in
+            // statement order, regardless of the order in which the functions were compiled.
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?,
+        // Jumps between functions are only detected (as statements belonging to two different
+        // functions) when compiling the program as a whole.
+        None => vec![compiler.compile_statements(0..program.statements.len(), &program.funcs)?],
+    };
+
+    let mut instructions = Vec::with_capacity(chunks.iter().map(|c| c.instructions.len()).sum());
+    let mut relocations: Vec<RelocationEntry> = Vec::new();
+    // Maps statement_idx to its debug info.
+    let mut sierra_statement_info: Vec<SierraStatementDebugInfo> =
+        Vec::with_capacity(program.statements.len());
+    let mut program_offset: usize = 0;
+    for chunk in chunks {
+        let instruction_offset = instructions.len();
+        relocations.extend(chunk.relocations.into_iter().map(|entry| RelocationEntry {
+            instruction_idx: instruction_offset + entry.instruction_idx,
+            relocation: entry.relocation,
+        }));
+        sierra_statement_info.extend(chunk.sierra_statement_info.into_iter().map(|info| {
+            SierraStatementDebugInfo {
+                start_offset: program_offset + info.start_offset,
+                end_offset: program_offset + info.end_offset,
+                instruction_idx: instruction_offset + info.instruction_idx,
+                ..info
+            }
+        }));
+        instructions.extend(chunk.instructions);
+        program_offset += chunk.code_size;
+    }
+
+    let statement_offsets: Vec<usize> = std::iter::once(0)
+        .chain(sierra_statement_info.iter().map(|s: &SierraStatementDebugInfo| s.end_offset))
+        .collect();
+
+    let const_segments_max_size = config
+        .max_bytecode_size
+        .checked_sub(program_offset)
+        .ok_or_else(|| Box::new(CompilationError::CodeSizeLimitExceeded))?;
+    let consts_info = ConstsInfo::new(
+        &registry,
         &type_sizes,
+        program.libfunc_declarations.iter().map(|ld| &ld.id),
+        const_segments_max_size,
+    )?;
+    relocate_instructions(&relocations, &statement_offsets, &consts_info, &mut instructions);
+    for entry in &relocations {
+        observer.on_relocation(entry, &instructions[entry.instruction_idx]);
+    }
+
+    let mut debug_info = CairoProgramDebugInfo { sierra_statement_info };
+    if config.annotate_gas_costs {
+        debug_info.populate_gas_costs(&metadata.gas_info);
+    }
+    Ok(CairoProgram { instructions, consts_info, debug_info, relocations })
+}
+
+/// Returns the statement range of each of the functions of `program`, ordered by their entry
+/// points. Returns `None` if the functions do not cover all the statements of the program, each
+/// starting at a distinct entry point, or if a statement of a function jumps out of its range.
+fn function_chunks(program: &Program) -> Option<Vec<(Range<usize>, &Function)>> {
+    let ranges = function_statement_ranges(program)?;
+    if ranges.values().next()?.start != 0
+        || !ranges.values().all(|statements| jumps_within_range(program, statements))
+    {
+        return None;
+    }
+    Some(
+        program
+            .funcs
+            .iter()
+            .map(|func| (ranges[&func.id].clone(), func))
+            .sorted_by_key(|(statements, _)| statements.start)
+            .collect(),
     )
-    .map_err(|err| Box::new(err.into()))?;
+}
 
-    let mut program_offset: usize = 0;
+/// Returns whether all the branches of the statements in `statements` target statements in it.
+fn jumps_within_range(program: &Program, statements: &Range<usize>) -> bool {
+    statements.clone().all(|statement_id| match &program.statements[statement_id] {
+        Statement::Return(_) => true,
+        Statement::Invocation(invocation) => invocation.branches.iter().all(|branch| {
+            statements.contains(&match branch.target {
+                BranchTarget::Fallthrough => statement_id + 1,
+                BranchTarget::Statement(target) => target.0,
+            })
+        }),
+    })
+}
+
+/// The compilation of a contiguous range of statements, with offsets and instruction indices
+/// relative to the start of the range.
+struct CompiledStatements {
+    instructions: Vec<Instruction>,
+    /// The relocations of the instructions, with statement targets in the full program.
+    relocations: Vec<RelocationEntry>,
+    sierra_statement_info: Vec<SierraStatementDebugInfo>,
+    /// The size of the bytecode of the instructions.
+    code_size: usize,
+}
+
+/// The program-wide information required for compiling ranges of statements.
+struct StatementsCompiler<'a> {
+    program: &'a Program,
+    registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    type_sizes: &'a TypeSizeMap,
+    metadata: &'a Metadata,
+    config: SierraToCasmConfig,
+    reused: Option<&'a ReusedStatements<'a>>,
+    /// The functions whose statements are compiled, or `None` for all the functions. The
+    /// statements unreachable from the entry points of these functions are skipped as dead code.
+    included_functions: Option<&'a UnorderedHashSet<FunctionId>>,
+    /// Whether to collect the compiled instructions, or only validate the statements and compute
+    /// their offsets.
+    emit_instructions: bool,
+    observer: &'a dyn CompilationObserver,
+}
+impl StatementsCompiler<'_> {
+    /// Compiles the statements in `statements`, which must be reachable only from the entry
+    /// points of `functions`.
+    fn compile_statements(
+        &self,
+        statements: Range<usize>,
+        functions: &[Function],
+    ) -> Result<CompiledStatements, Box<CompilationError>> {
+        self.compile_statements_ex(statements, functions, None)
+    }
 
-    for (statement_id, statement) in program.statements.iter().enumerate() {
-        let statement_idx = StatementIdx(statement_id);
+    /// Compiles the statements in `statements`, as done by [Self::compile_statements].
+    ///
+    /// If `errors` is provided, the errors of invalid statements are collected into it instead of
+    /// failing, and the compilation continues with the following statements. The statements whose
+    /// annotations are missing due to the errors are skipped, and the compiled statements are not
+    /// optimized.
+    fn compile_statements_ex(
+        &self,
+        statements: Range<usize>,
+        functions: &[Function],
+        mut errors: Option<&mut Vec<StatementCompilationError>>,
+    ) -> Result<CompiledStatements, Box<CompilationError>> {
+        let first_statement = statements.start;
+        let mut compiled = CompiledStatements {
+            instructions: vec![],
+            relocations: vec![],
+            sierra_statement_info: Vec::with_capacity(statements.len()),
+            code_size: 0,
+        };
 
-        if program_offset > config.max_bytecode_size {
-            return Err(Box::new(CompilationError::CodeSizeLimitExceeded));
+        let mut backwards_jump_indices = UnorderedHashSet::<_>::default();
+        for statement_id in statements.clone() {
+            if let Statement::Invocation(invocation) = &self.program.statements[statement_id] {
+                for branch in &invocation.branches {
+                    if let BranchTarget::Statement(target) = branch.target {
+                        if target.0 < statement_id {
+                            backwards_jump_indices.insert(target);
+                        }
+                    }
+                }
+            }
         }
-        match statement {
+        let mut program_annotations = ProgramAnnotations::create(
+            statements.clone(),
+            backwards_jump_indices,
+            functions,
+            self.metadata,
+            self.config.gas_usage_check,
+            self.type_sizes,
+        )
+        .map_err(|err| Box::new(err.into()))?;
+        let reachable = (self.config.eliminate_dead_code || self.included_functions.is_some())
+            .then(|| {
+                let entry_points = functions
+                    .iter()
+                    .filter(|func| match self.included_functions {
+                        Some(included_functions) => included_functions.contains(&func.id),
+                        None => true,
+                    })
+                    .map(|func| func.entry_point);
+                reachable_statements(self.program, &statements, entry_points)
+            });
+
+        let mut found_errors = false;
+        for statement_id in statements {
+            let statement_idx = StatementIdx(statement_id);
+            let statement = &self.program.statements[statement_id];
+
+            if compiled.code_size > self.config.max_bytecode_size {
+                return Err(Box::new(CompilationError::CodeSizeLimitExceeded));
+            }
+            if reachable.as_ref().is_some_and(|reachable| !reachable.contains(&statement_idx)) {
+                // Dead statements are kept in the debug info, without any instructions.
+                compiled.sierra_statement_info.push(SierraStatementDebugInfo {
+                    start_offset: compiled.code_size,
+                    end_offset: compiled.code_size,
+                    instruction_idx: compiled.instructions.len(),
+                    additional_kind_info: match statement {
+                        Statement::Return(_) => {
+                            StatementKindDebugInfo::Return(ReturnStatementDebugInfo {
+                                ref_values: vec![],
+                            })
+                        }
+                        Statement::Invocation(_) => {
+                            StatementKindDebugInfo::Invoke(InvokeStatementDebugInfo {
+                                result_branch_changes: vec![],
+                                ref_values: vec![],
+                            })
+                        }
+                    },
+                    source_location: None,
+                    gas_costs: None,
+                });
+                continue;
+            }
+            if let Some((reused, previous_idx)) = self.reused.and_then(|reused| {
+                reused
+                    .new_to_previous
+                    .get(&statement_idx)
+                    .map(|previous_idx| (reused, *previous_idx))
+            }) {
+                let statement_info = reused.copy_statement(
+                    previous_idx,
+                    compiled.code_size,
+                    &mut compiled.instructions,
+                    &mut compiled.relocations,
+                );
+                compiled.code_size = statement_info.end_offset;
+                compiled.sierra_statement_info.push(statement_info);
+                continue;
+            }
+            self.observer.on_statement_start(statement_idx);
+            let start_offset = compiled.code_size;
+            if let Err(error) =
+                self.compile_statement(statement_idx, &mut program_annotations, &mut compiled)
+            {
+                let Some(errors) = &mut errors else {
+                    return Err(error);
+                };
+                // Missing annotations following an error are most likely caused by it.
+                let caused_by_previous_error = found_errors
+                    && matches!(
+                        *error,
+                        CompilationError::AnnotationError(
+                            AnnotationError::MissingAnnotationsForStatement(_)
+                        )
+                    );
+                if !caused_by_previous_error {
+                    errors.push(StatementCompilationError {
+                        statement_idx: Some(statement_idx),
+                        error,
+                    });
+                }
+                found_errors = true;
+                continue;
+            }
+            self.observer.on_statement_end(statement_idx, compiled.code_size - start_offset);
+        }
+
+        if found_errors {
+            return Ok(compiled);
+        }
+        if self.config.optimize && self.emit_instructions {
+            peephole::optimize(
+                StatementIdx(first_statement),
+                &mut compiled.instructions,
+                &mut compiled.relocations,
+                &mut compiled.sierra_statement_info,
+            );
+            compiled.code_size =
+                compiled.sierra_statement_info.last().map_or(0, |info| info.end_offset);
+        }
+        if self.config.coalesce_ap_updates && self.emit_instructions {
+            peephole::coalesce_ap_updates(
+                StatementIdx(first_statement),
+                &mut compiled.instructions,
+                &mut compiled.relocations,
+                &mut compiled.sierra_statement_info,
+                &functions.iter().map(|function| function.entry_point).collect(),
+            );
+            compiled.code_size =
+                compiled.sierra_statement_info.last().map_or(0, |info| info.end_offset);
+        }
+
+        Ok(compiled)
+    }
+
+    /// Compiles the statement `statement_idx`, appending its compilation to `compiled`.
+    fn compile_statement(
+        &self,
+        statement_idx: StatementIdx,
+        program_annotations: &mut ProgramAnnotations,
+        compiled: &mut CompiledStatements,
+    ) -> Result<(), Box<CompilationError>> {
+        let start_offset = compiled.code_size;
+        match &self.program.statements[statement_idx.0] {
             Statement::Return(ref_ids) => {
                 let (annotations, return_refs) = program_annotations
                     .get_annotations_after_take_args(statement_idx, ref_ids.iter())
                     .map_err(|err| Box::new(err.into()))?;
-                return_refs.iter().for_each(|r| r.validate(&type_sizes));
+                return_refs.iter().for_each(|r| r.validate(self.type_sizes));
 
                 if let Some(var_id) = annotations.refs.keys().next() {
                     return Err(Box::new(CompilationError::DanglingReferences {
@@ -436,8 +1331,8 @@ pub fn compile(
                     .validate_final_annotations(
                         statement_idx,
                         &annotations,
-                        &program.funcs,
-                        metadata,
+                        &self.program.funcs,
+                        self.metadata,
                         &return_refs,
                     )
                     .map_err(|err| Box::new(err.into()))?;
@@ -448,28 +1343,33 @@ pub fn compile(
                     _ => CompilationError::InvocationError { statement_idx, error },
                 })?;
 
-                let start_offset = program_offset;
-
                 let ret_instruction = RetInstruction {};
-                program_offset += ret_instruction.op_size();
+                compiled.code_size += ret_instruction.op_size();
 
-                sierra_statement_info.push(SierraStatementDebugInfo {
+                compiled.sierra_statement_info.push(SierraStatementDebugInfo {
                     start_offset,
-                    end_offset: program_offset,
-                    instruction_idx: instructions.len(),
+                    end_offset: compiled.code_size,
+                    instruction_idx: compiled.instructions.len(),
                     additional_kind_info: StatementKindDebugInfo::Return(
                         ReturnStatementDebugInfo { ref_values: return_refs },
                     ),
+                    source_location: None,
+                    gas_costs: None,
                 });
 
-                instructions.push(Instruction::new(InstructionBody::Ret(ret_instruction), false));
+                if self.emit_instructions {
+                    compiled
+                        .instructions
+                        .push(Instruction::new(InstructionBody::Ret(ret_instruction), false));
+                }
             }
             Statement::Invocation(invocation) => {
                 let (annotations, invoke_refs) = program_annotations
                     .get_annotations_after_take_args(statement_idx, invocation.args.iter())
                     .map_err(|err| Box::new(err.into()))?;
 
-                let libfunc = registry
+                let libfunc = self
+                    .registry
                     .get_libfunc(&invocation.libfunc_id)
                     .map_err(CompilationError::ProgramRegistryError)?;
                 check_basic_structure(statement_idx, invocation, libfunc)?;
@@ -482,13 +1382,13 @@ pub fn compile(
                 check_types_match(&invoke_refs, &param_types).map_err(|error| {
                     Box::new(AnnotationError::ReferencesError { statement_idx, error }.into())
                 })?;
-                invoke_refs.iter().for_each(|r| r.validate(&type_sizes));
+                invoke_refs.iter().for_each(|r| r.validate(self.type_sizes));
                 let compiled_invocation = compile_invocation(
                     ProgramInfo {
-                        metadata,
-                        type_sizes: &type_sizes,
+                        metadata: self.metadata,
+                        type_sizes: self.type_sizes,
                         const_data_values: &|ty| {
-                            extract_const_value(&registry, &type_sizes, ty).unwrap()
+                            extract_const_value(self.registry, self.type_sizes, ty).unwrap()
                         },
                     },
                     invocation,
@@ -498,32 +1398,35 @@ pub fn compile(
                     annotations.environment,
                 )
                 .map_err(|error| CompilationError::InvocationError { statement_idx, error })?;
-
-                let start_offset = program_offset;
+                self.observer.on_invocation(statement_idx, &compiled_invocation);
 
                 for instruction in &compiled_invocation.instructions {
-                    program_offset += instruction.body.op_size();
+                    compiled.code_size += instruction.body.op_size();
                 }
 
-                sierra_statement_info.push(SierraStatementDebugInfo {
+                compiled.sierra_statement_info.push(SierraStatementDebugInfo {
                     start_offset,
-                    end_offset: program_offset,
-                    instruction_idx: instructions.len(),
+                    end_offset: compiled.code_size,
+                    instruction_idx: compiled.instructions.len(),
                     additional_kind_info: StatementKindDebugInfo::Invoke(
                         InvokeStatementDebugInfo {
                             result_branch_changes: compiled_invocation.results.clone(),
                             ref_values: invoke_refs,
                         },
                     ),
+                    source_location: None,
+                    gas_costs: None,
                 });
 
-                for entry in compiled_invocation.relocations {
-                    relocations.push(RelocationEntry {
-                        instruction_idx: instructions.len() + entry.instruction_idx,
-                        relocation: entry.relocation,
-                    });
+                if self.emit_instructions {
+                    for entry in compiled_invocation.relocations {
+                        compiled.relocations.push(RelocationEntry {
+                            instruction_idx: compiled.instructions.len() + entry.instruction_idx,
+                            relocation: entry.relocation,
+                        });
+                    }
+                    compiled.instructions.extend(compiled_invocation.instructions);
                 }
-                instructions.extend(compiled_invocation.instructions);
 
                 let branching_libfunc = compiled_invocation.results.len() > 1;
                 // Using a vector of annotations for the loop allows us to clone the annotations
@@ -543,8 +1446,8 @@ pub fn compile(
                     let destination_statement_idx = statement_idx.next(&branch_info.target);
                     if branching_libfunc
                         && !is_branch_align(
-                            &registry,
-                            &program.statements[destination_statement_idx.0],
+                            self.registry,
+                            &self.program.statements[destination_statement_idx.0],
                         )?
                     {
                         return Err(Box::new(CompilationError::ExpectedBranchAlign {
@@ -566,29 +1469,272 @@ pub fn compile(
                 }
             }
         }
+        Ok(())
     }
+}
 
-    let statement_offsets: Vec<usize> = std::iter::once(0)
-        .chain(sierra_statement_info.iter().map(|s: &SierraStatementDebugInfo| s.end_offset))
-        .collect();
+/// The statements of a previously compiled program that can be reused in a new compilation.
+struct ReusedStatements<'a> {
+    /// The previously compiled program.
+    previous: &'a CairoProgram,
+    /// The offsets of the statements in the previous program, including the final offset.
+    previous_statement_offsets: Vec<usize>,
+    /// Maps the reused statements of the new program to their index in the previous program.
+    new_to_previous: UnorderedHashMap<StatementIdx, StatementIdx>,
+    /// Maps the statements of the previous program that may be jumped to from reused statements to
+    /// their index in the new program.
+    previous_to_new: UnorderedHashMap<StatementIdx, StatementIdx>,
+}
+impl<'a> ReusedStatements<'a> {
+    /// Finds the statements of `program` that can be copied from `previous`. Returns `None` if no
+    /// statement can be reused.
+    fn new(
+        previous: &'a CairoProgram,
+        previous_program: &Program,
+        program: &Program,
+        changed_functions: &UnorderedHashSet<FunctionId>,
+    ) -> Option<Self> {
+        if previous_program.type_declarations != program.type_declarations
+            || previous_program.libfunc_declarations != program.libfunc_declarations
+            || previous.debug_info.sierra_statement_info.len() != previous_program.statements.len()
+        {
+            return None;
+        }
+        let previous_ranges = function_statement_ranges(previous_program)?;
+        let new_ranges = function_statement_ranges(program)?;
+        let mut new_to_previous = UnorderedHashMap::default();
+        let mut previous_to_new = UnorderedHashMap::default();
+        let mut reused_previous_statements = vec![];
+        for (function_id, new_range) in new_ranges.iter() {
+            let Some(previous_range) = previous_ranges.get(function_id) else {
+                continue;
+            };
+            previous_to_new
+                .insert(StatementIdx(previous_range.start), StatementIdx(new_range.start));
+            if changed_functions.contains(function_id) || previous_range.len() != new_range.len() {
+                continue;
+            }
+            for (previous_idx, new_idx) in zip_eq(previous_range.clone(), new_range.clone()) {
+                new_to_previous.insert(StatementIdx(new_idx), StatementIdx(previous_idx));
+                previous_to_new.insert(StatementIdx(previous_idx), StatementIdx(new_idx));
+                reused_previous_statements.push(StatementIdx(previous_idx));
+            }
+        }
+        let previous_statement_offsets = previous.statement_offsets();
+        let reused =
+            Self { previous, previous_statement_offsets, new_to_previous, previous_to_new };
+        // All the jumps of the reused statements must be to statements existing in the new
+        // program.
+        let all_targets_found = reused_previous_statements.iter().all(|previous_idx| {
+            reused.statement_relocations(*previous_idx).iter().all(|entry| {
+                match &entry.relocation {
+                    Relocation::RelativeStatementId(target) => {
+                        reused.previous_to_new.contains_key(target)
+                    }
+                    _ => true,
+                }
+            })
+        });
+        (all_targets_found && !reused_previous_statements.is_empty()).then_some(reused)
+    }
 
-    let const_segments_max_size = config
-        .max_bytecode_size
-        .checked_sub(program_offset)
-        .ok_or_else(|| Box::new(CompilationError::CodeSizeLimitExceeded))?;
-    let consts_info = ConstsInfo::new(
-        &registry,
-        &type_sizes,
-        program.libfunc_declarations.iter().map(|ld| &ld.id),
-        const_segments_max_size,
-    )?;
-    relocate_instructions(&relocations, &statement_offsets, &consts_info, &mut instructions);
+    /// Returns the range of the instructions of the given statement in the previous program.
+    fn statement_instructions(&self, previous_idx: StatementIdx) -> Range<usize> {
+        let infos = &self.previous.debug_info.sierra_statement_info;
+        let end = infos
+            .get(previous_idx.0 + 1)
+            .map_or(self.previous.instructions.len(), |next| next.instruction_idx);
+        infos[previous_idx.0].instruction_idx..end
+    }
 
-    Ok(CairoProgram {
-        instructions,
-        consts_info,
-        debug_info: CairoProgramDebugInfo { sierra_statement_info },
-    })
+    /// Returns the relocations of the instructions of the given statement in the previous program.
+    fn statement_relocations(&self, previous_idx: StatementIdx) -> &[RelocationEntry] {
+        let range = self.statement_instructions(previous_idx);
+        let relocations = &self.previous.relocations;
+        let start = relocations.partition_point(|entry| entry.instruction_idx < range.start);
+        let end = relocations.partition_point(|entry| entry.instruction_idx < range.end);
+        &relocations[start..end]
+    }
+
+    /// Appends the unrelocated instructions of the given previous statement, placed at
+    /// `program_offset`, along with their relocations for the new program. Returns the debug info
+    /// of the statement in the new program.
+    fn copy_statement(
+        &self,
+        previous_idx: StatementIdx,
+        program_offset: usize,
+        instructions: &mut Vec<Instruction>,
+        relocations: &mut Vec<RelocationEntry>,
+    ) -> SierraStatementDebugInfo {
+        let previous_info = &self.previous.debug_info.sierra_statement_info[previous_idx.0];
+        let range = self.statement_instructions(previous_idx);
+        let mut statement_relocations = self.statement_relocations(previous_idx).iter().peekable();
+        let instruction_idx = instructions.len();
+        let mut previous_offset = previous_info.start_offset;
+        for (previous_instruction_idx, instruction) in
+            zip_eq(range.clone(), &self.previous.instructions[range])
+        {
+            let mut instruction = instruction.clone();
+            if let Some(entry) = statement_relocations
+                .next_if(|entry| entry.instruction_idx == previous_instruction_idx)
+            {
+                entry.relocation.revert(
+                    previous_offset,
+                    &self.previous_statement_offsets,
+                    &self.previous.consts_info,
+                    &mut instruction,
+                );
+                let relocation = match &entry.relocation {
+                    Relocation::RelativeStatementId(target) => {
+                        Relocation::RelativeStatementId(self.previous_to_new[target])
+                    }
+                    relocation => relocation.clone(),
+                };
+                relocations
+                    .push(RelocationEntry { instruction_idx: instructions.len(), relocation });
+            }
+            previous_offset += instruction.body.op_size();
+            instructions.push(instruction);
+        }
+        SierraStatementDebugInfo {
+            start_offset: program_offset,
+            end_offset: program_offset + previous_info.end_offset - previous_info.start_offset,
+            instruction_idx,
+            additional_kind_info: self.remap_kind_info(&previous_info.additional_kind_info),
+            source_location: previous_info.source_location.clone(),
+            // Attached again for the whole program if requested.
+            gas_costs: None,
+        }
+    }
+
+    /// Returns the given statement-kind debug info, with the introduction points of the
+    /// references moved to the new statement indices.
+    fn remap_kind_info(&self, info: &StatementKindDebugInfo) -> StatementKindDebugInfo {
+        let remap_point = |point: &IntroductionPoint| IntroductionPoint {
+            source_statement_idx: point.source_statement_idx.map(|idx| self.previous_to_new[&idx]),
+            destination_statement_idx: self.previous_to_new[&point.destination_statement_idx],
+            output_idx: point.output_idx,
+        };
+        let remap_refs = |refs: &[ReferenceValue]| {
+            refs.iter()
+                .map(|r| ReferenceValue {
+                    introduction_point: remap_point(&r.introduction_point),
+                    ..r.clone()
+                })
+                .collect()
+        };
+        match info {
+            StatementKindDebugInfo::Return(info) => {
+                StatementKindDebugInfo::Return(ReturnStatementDebugInfo {
+                    ref_values: remap_refs(&info.ref_values),
+                })
+            }
+            StatementKindDebugInfo::Invoke(info) => {
+                StatementKindDebugInfo::Invoke(InvokeStatementDebugInfo {
+                    result_branch_changes: info
+                        .result_branch_changes
+                        .iter()
+                        .map(|changes| BranchChanges {
+                            refs: changes
+                                .refs
+                                .iter()
+                                .map(|r| OutputReferenceValue {
+                                    introduction_point: match &r.introduction_point {
+                                        OutputReferenceValueIntroductionPoint::Existing(point) => {
+                                            OutputReferenceValueIntroductionPoint::Existing(
+                                                remap_point(point),
+                                            )
+                                        }
+                                        point => point.clone(),
+                                    },
+                                    ..r.clone()
+                                })
+                                .collect(),
+                            ..changes.clone()
+                        })
+                        .collect(),
+                    ref_values: remap_refs(&info.ref_values),
+                })
+            }
+        }
+    }
+}
+
+/// Returns the statements in `statements` that are reachable from `entry_points`
+/// without leaving `statements`.
+fn reachable_statements(
+    program: &Program,
+    statements: &Range<usize>,
+    entry_points: impl IntoIterator<Item = StatementIdx>,
+) -> UnorderedHashSet<StatementIdx> {
+    let mut reachable = UnorderedHashSet::default();
+    let mut pending: Vec<StatementIdx> = entry_points.into_iter().collect();
+    while let Some(statement_idx) = pending.pop() {
+        if !statements.contains(&statement_idx.0) || !reachable.insert(statement_idx) {
+            continue;
+        }
+        if let Statement::Invocation(invocation) = &program.statements[statement_idx.0] {
+            pending.extend(
+                invocation.branches.iter().map(|branch| statement_idx.next(&branch.target)),
+            );
+        }
+    }
+    reachable
+}
+
+/// Returns the ids of `entry_function` and of the functions of `program` it transitively calls.
+fn called_functions(
+    program: &Program,
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    entry_function: &Function,
+) -> Result<UnorderedHashSet<FunctionId>, Box<CompilationError>> {
+    let mut functions = UnorderedHashSet::default();
+    functions.insert(entry_function.id.clone());
+    let mut visited = UnorderedHashSet::<_>::default();
+    let mut pending = vec![entry_function.entry_point];
+    while let Some(statement_idx) = pending.pop() {
+        if !visited.insert(statement_idx) {
+            continue;
+        }
+        let Some(Statement::Invocation(invocation)) = program.statements.get(statement_idx.0)
+        else {
+            continue;
+        };
+        if let CoreConcreteLibfunc::FunctionCall(libfunc)
+        | CoreConcreteLibfunc::CouponCall(libfunc) = registry
+            .get_libfunc(&invocation.libfunc_id)
+            .map_err(CompilationError::ProgramRegistryError)?
+        {
+            if functions.insert(libfunc.function.id.clone()) {
+                pending.push(libfunc.function.entry_point);
+            }
+        }
+        pending.extend(invocation.branches.iter().map(|branch| statement_idx.next(&branch.target)));
+    }
+    Ok(functions)
+}
+
+/// Returns the statement ranges of the functions of `program`, assuming each function spans from
+/// its entry point to the entry point of the next function. Returns `None` if two functions share
+/// an entry point.
+pub(crate) fn function_statement_ranges(
+    program: &Program,
+) -> Option<OrderedHashMap<FunctionId, Range<usize>>> {
+    let entry_points: Vec<_> = program
+        .funcs
+        .iter()
+        .map(|func| (func.entry_point.0, &func.id))
+        .sorted_by_key(|(entry_point, _)| *entry_point)
+        .collect();
+    let mut ranges = OrderedHashMap::default();
+    for (i, (start, function_id)) in entry_points.iter().enumerate() {
+        let end = entry_points.get(i + 1).map_or(program.statements.len(), |(end, _)| *end);
+        if end <= *start {
+            return None;
+        }
+        ranges.insert((*function_id).clone(), *start..end);
+    }
+    Some(ranges)
 }
 
 /// Runs basic validations on the given metadata.
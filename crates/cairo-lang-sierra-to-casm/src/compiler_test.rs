@@ -1,12 +1,25 @@
+use std::sync::Mutex;
+
+use cairo_lang_casm::instructions::Instruction;
+use cairo_lang_sierra::debug_info::SourceCodeLocation;
+use cairo_lang_sierra::program::{Program, StatementIdx};
 use cairo_lang_sierra::ProgramParser;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use indoc::indoc;
 use test_case::test_case;
 
 use super::SierraToCasmConfig;
-use crate::compiler::compile;
+use crate::compiler::{
+    compile, compile_collecting_errors, compile_function, compile_incremental, compile_streaming,
+    compile_with_observer, validate, CompilationError, CompilationObserver,
+    StatementCompilationError, ValidationError,
+};
+use crate::invocations::CompiledInvocation;
 use crate::metadata::{calc_metadata, calc_metadata_ap_change_only};
+use crate::relocations::{relocate_instructions, RelocationEntry};
 use crate::test_utils::{read_sierra_example_file, strip_comments_and_linebreaks};
 
 #[test_case(indoc! {"
@@ -481,6 +494,56 @@ use crate::test_utils::{read_sierra_example_file, strip_comments_and_linebreaks}
         dw 17;
     "};
     "Simple use of constants.")]
+#[test_case(indoc! {"
+        type felt252 = felt252;
+        type u128 = u128;
+        type Const<felt252, 5> = Const<felt252, 5>;
+        type Const<u128, 5> = Const<u128, 5>;
+        type Const<felt252, 17> = Const<felt252, 17>;
+        type Box<felt252> = Box<felt252>;
+        type Box<u128> = Box<u128>;
+
+        libfunc const_as_box<Const<felt252, 5>> = const_as_box<Const<felt252, 5>, 0>;
+        libfunc const_as_box<Const<felt252, 17>> = const_as_box<Const<felt252, 17>, 0>;
+        libfunc const_as_box<Const<u128, 5>> = const_as_box<Const<u128, 5>, 0>;
+        libfunc unbox<felt252> = unbox<felt252>;
+        libfunc unbox<u128> = unbox<u128>;
+        libfunc store_temp<felt252> = store_temp<felt252>;
+        libfunc store_temp<u128> = store_temp<u128>;
+        libfunc drop<felt252> = drop<felt252>;
+
+        const_as_box<Const<felt252, 5>>() -> ([1]);
+        const_as_box<Const<felt252, 17>>() -> ([2]);
+        const_as_box<Const<u128, 5>>() -> ([3]);
+        unbox<felt252>([1]) -> ([1]);
+        unbox<felt252>([2]) -> ([2]);
+        unbox<u128>([3]) -> ([3]);
+        store_temp<felt252>([1]) -> ([1]);
+        drop<felt252>([1]) -> ();
+        store_temp<felt252>([2]) -> ([2]);
+        drop<felt252>([2]) -> ();
+        store_temp<u128>([3]) -> ([3]);
+        return([3]);
+
+        test_program@0() -> (u128);
+    "},
+    false,
+    indoc! {"
+        call rel 16;
+        [ap + 0] = [ap + -1] + 15, ap++;
+        call rel 12;
+        [ap + 0] = [ap + -1] + 12, ap++;
+        call rel 8;
+        [ap + 0] = [ap + -1] + 7, ap++;
+        [ap + 0] = [[ap + -7] + 0], ap++;
+        [ap + 0] = [[ap + -5] + 0], ap++;
+        [ap + 0] = [[ap + -3] + 0], ap++;
+        ret;
+        ret;
+        dw 5;
+        dw 17;
+    "};
+    "Constants with identical values share their data.")]
 #[test_case(indoc! {"
     type felt252 = felt252;
     type Tuple<felt252, felt252> = Struct<ut@Tuple, felt252, felt252>;
@@ -699,6 +762,43 @@ indoc! {"
     "};
     "Constant enums.")]
 #[test_case(indoc! {"
+        type felt252 = felt252;
+        type Span<felt252> = Span<felt252>;
+        type Const<felt252, 5> = Const<felt252, 5>;
+        type Const<felt252, 17> = Const<felt252, 17>;
+        type Const<Span<felt252>, Const<felt252, 5>, Const<felt252, 17>> =
+            Const<Span<felt252>, Const<felt252, 5>, Const<felt252, 17>>;
+        type Box<Span<felt252>> = Box<Span<felt252>>;
+
+        libfunc const_as_box<Const<Span<felt252>, Const<felt252, 5>, Const<felt252, 17>>> =
+            const_as_box<Const<Span<felt252>, Const<felt252, 5>, Const<felt252, 17>>, 0>;
+        libfunc unbox<Span<felt252>> = unbox<Span<felt252>>;
+        libfunc store_temp<Span<felt252>> = store_temp<Span<felt252>>;
+
+        const_as_box<Const<Span<felt252>, Const<felt252, 5>, Const<felt252, 17>>>() -> ([1]);
+        unbox<Span<felt252>>([1]) -> ([1]);
+        store_temp<Span<felt252>>([1]) -> ([1]);
+        return([1]);
+
+        test_program@0() -> (Span<felt252>);
+    "},
+    false,
+    indoc! {"
+        call rel 11;
+        [ap + 0] = [ap + -1] + 10, ap++;
+        [ap + 0] = [ap + -1] + 2, ap++;
+        %{ memory[ap + 0] = segments.add() %}
+        [ap + -2] = [[ap + 0] + 0], ap++;
+        [ap + -2] = [[ap + -1] + 1];
+        [ap + 0] = [[ap + -1] + 0], ap++;
+        [ap + 0] = [[ap + -2] + 1], ap++;
+        ret;
+        ret;
+        dw 5;
+        dw 17;
+    "};
+    "Constant spans.")]
+#[test_case(indoc! {"
 
         type BuiltinCosts = BuiltinCosts;
 
@@ -770,7 +870,7 @@ fn sierra_to_casm(sierra_code: &str, gas_usage_check: bool, expected_casm: &str)
                 calc_metadata_ap_change_only(&program).unwrap_or_default()
             },
             // `max_bytecode_size` is a small value to ensure we can pass with small values.
-            SierraToCasmConfig { gas_usage_check, max_bytecode_size: 100 }
+            SierraToCasmConfig { gas_usage_check, max_bytecode_size: 100, ..Default::default() }
         )
         .expect("Compilation failed.")
         .to_string(),
@@ -778,6 +878,356 @@ fn sierra_to_casm(sierra_code: &str, gas_usage_check: bool, expected_casm: &str)
     );
 }
 
+#[test]
+fn populate_source_locations() {
+    let program = ProgramParser::new().parse(&read_sierra_example_file("fib_no_gas")).unwrap();
+    let mut debug_info = compile(
+        &program,
+        &calc_metadata_ap_change_only(&program).unwrap_or_default(),
+        SierraToCasmConfig { gas_usage_check: false, ..Default::default() },
+    )
+    .expect("Compilation failed.")
+    .debug_info;
+    let location = SourceCodeLocation { file: "lib.cairo".into(), line: 3, column: 4 };
+    debug_info.populate_source_locations(&UnorderedHashMap::from_iter([(
+        StatementIdx(1),
+        location.clone(),
+    )]));
+    let locations =
+        debug_info.sierra_statement_info.iter().map(|info| info.source_location.clone());
+    assert_eq!(locations.take(3).collect::<Vec<_>>(), vec![None, Some(location), None]);
+}
+
+#[test]
+fn compile_incremental_matches_full_compilation() {
+    let declarations = indoc! {"
+        type felt252 = felt252;
+
+        libfunc felt252_const<5> = felt252_const<5>;
+        libfunc felt252_add = felt252_add;
+        libfunc store_temp<felt252> = store_temp<felt252>;
+        libfunc function_call<user@bar> = function_call<user@bar>;
+    "};
+    let unchanged_functions = indoc! {"
+        store_temp<felt252>([0]) -> ([0]);
+        function_call<user@bar>([0]) -> ([1]);
+        return([1]);
+        store_temp<felt252>([0]) -> ([0]);
+        return([0]);
+    "};
+    let previous_program = ProgramParser::new()
+        .parse(&format!(
+            "{declarations}
+            store_temp<felt252>([0]) -> ([0]);
+            return([0]);
+            {unchanged_functions}
+            foo@0([0]: felt252) -> (felt252);
+            main@2([0]: felt252) -> (felt252);
+            bar@5([0]: felt252) -> (felt252);"
+        ))
+        .unwrap();
+    let program = ProgramParser::new()
+        .parse(&format!(
+            "{declarations}
+            felt252_const<5>() -> ([1]);
+            felt252_add([0], [1]) -> ([2]);
+            store_temp<felt252>([2]) -> ([2]);
+            return([2]);
+            {unchanged_functions}
+            foo@0([0]: felt252) -> (felt252);
+            main@4([0]: felt252) -> (felt252);
+            bar@7([0]: felt252) -> (felt252);"
+        ))
+        .unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    let previous = compile(
+        &previous_program,
+        &calc_metadata_ap_change_only(&previous_program).unwrap(),
+        config,
+    )
+    .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let changed_functions = UnorderedHashSet::from_iter(["foo".into()]);
+    pretty_assertions::assert_eq!(
+        compile_incremental(
+            &previous,
+            &previous_program,
+            &program,
+            &changed_functions,
+            &metadata,
+            config
+        )
+        .unwrap(),
+        compile(&program, &metadata, config).unwrap()
+    );
+}
+
+#[test_case("fib_no_gas", false, false; "fib_no_gas")]
+#[test_case("fib_jumps", true, false; "fib_jumps")]
+#[test_case("fib_jumps", true, true; "fib_jumps_optimized")]
+fn compile_streaming_matches_full_compilation(name: &str, gas_usage_check: bool, optimize: bool) {
+    let program = ProgramParser::new().parse(&read_sierra_example_file(name)).unwrap();
+    let metadata = if gas_usage_check {
+        calc_metadata(&program, Default::default()).unwrap_or_default()
+    } else {
+        calc_metadata_ap_change_only(&program).unwrap_or_default()
+    };
+    let config = SierraToCasmConfig { gas_usage_check, optimize, ..Default::default() };
+    let cairo_program = compile(&program, &metadata, config).unwrap();
+
+    let mut statement_ids = vec![];
+    let mut instructions = vec![];
+    let consts_info =
+        compile_streaming(&program, &metadata, config, |statement_idx, statement_instructions| {
+            statement_ids.push(statement_idx.0);
+            instructions.extend(statement_instructions);
+        })
+        .unwrap();
+    assert_eq!(statement_ids, (0..program.statements.len()).collect::<Vec<_>>());
+    pretty_assertions::assert_eq!(instructions, cairo_program.instructions);
+    assert_eq!(consts_info, cairo_program.consts_info);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type Const<felt252, 5> = Const<felt252, 5>;
+            type Box<felt252> = Box<felt252>;
+
+            libfunc const_as_box<Const<felt252, 5>> = const_as_box<Const<felt252, 5>, 0>;
+            libfunc unbox<felt252> = unbox<felt252>;
+            libfunc store_temp<felt252> = store_temp<felt252>;
+
+            const_as_box<Const<felt252, 5>>() -> ([0]);
+            unbox<felt252>([0]) -> ([0]);
+            store_temp<felt252>([0]) -> ([0]);
+            return([0]);
+
+            test_program@0() -> (felt252);
+        "})
+        .unwrap();
+    let cairo_program = compile(
+        &program,
+        &calc_metadata_ap_change_only(&program).unwrap(),
+        SierraToCasmConfig { gas_usage_check: false, ..Default::default() },
+    )
+    .unwrap();
+    let serialized = serde_json::to_string(&cairo_program).unwrap();
+    assert_eq!(serde_json::from_str::<super::CairoProgram>(&serialized).unwrap(), cairo_program);
+}
+
+#[test]
+fn function_code_sizes() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_add = felt252_add;
+            libfunc felt252_dup = dup<felt252>;
+            libfunc store_temp<felt252> = store_temp<felt252>;
+            libfunc call_double = function_call<user@double>;
+
+            store_temp<felt252>([0]) -> ([0]);
+            call_double([0]) -> ([1]);
+            return([1]);
+            felt252_dup([0]) -> ([0], [1]);
+            felt252_add([0], [1]) -> ([2]);
+            store_temp<felt252>([2]) -> ([2]);
+            return([2]);
+
+            main@0([0]: felt252) -> (felt252);
+            double@3([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let cairo_program = compile(
+        &program,
+        &calc_metadata_ap_change_only(&program).unwrap(),
+        SierraToCasmConfig { gas_usage_check: false, ..Default::default() },
+    )
+    .unwrap();
+    assert_eq!(
+        cairo_program
+            .function_code_sizes(&program)
+            .iter()
+            .map(|(id, size)| (id.to_string(), *size))
+            .collect::<Vec<_>>(),
+        vec![("main".into(), 4), ("double".into(), 2)]
+    );
+}
+
+#[test]
+fn compile_optimized() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc jump = jump;
+            libfunc store_temp<felt252> = store_temp<felt252>;
+
+            store_temp<felt252>([0]) -> ([0]);
+            jump() { 2() };
+            return([0]);
+
+            main@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        max_bytecode_size: 3,
+        optimize: true,
+        ..Default::default()
+    };
+    let cairo_program = compile(&program, &metadata, config).unwrap();
+    // The jump to the next statement is removed.
+    assert_eq!(
+        cairo_program.to_string(),
+        indoc! {"
+            [ap + 0] = [fp + -3], ap++;
+            ret;
+        "}
+    );
+    assert_eq!(
+        cairo_program
+            .debug_info
+            .sierra_statement_info
+            .iter()
+            .map(|info| (info.instruction_idx, info.start_offset, info.end_offset))
+            .collect::<Vec<_>>(),
+        vec![(0, 0, 1), (1, 1, 1), (1, 1, 2)]
+    );
+    // Without the optimization, the program exceeds the bytecode size limit.
+    assert!(
+        compile(&program, &metadata, SierraToCasmConfig { optimize: false, ..config }).is_err()
+    );
+}
+
+#[test]
+fn compile_eliminating_dead_code() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_add = felt252_add;
+            libfunc store_temp<felt252> = store_temp<felt252>;
+
+            store_temp<felt252>([0]) -> ([0]);
+            return([0]);
+            felt252_add([0], [0]) -> ([1]);
+            store_temp<felt252>([1]) -> ([1]);
+            return([1]);
+
+            main@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    // The metadata computation does not support unreachable statements, so it is done on the
+    // reachable ones only.
+    let metadata = calc_metadata_ap_change_only(&Program {
+        statements: program.statements[..2].to_vec(),
+        ..program.clone()
+    })
+    .unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        eliminate_dead_code: true,
+        ..Default::default()
+    };
+    let cairo_program = compile(&program, &metadata, config).unwrap();
+    assert_eq!(
+        cairo_program.to_string(),
+        indoc! {"
+            [ap + 0] = [fp + -3], ap++;
+            ret;
+        "}
+    );
+    // The unreachable statements have no instructions.
+    assert_eq!(
+        cairo_program
+            .debug_info
+            .sierra_statement_info
+            .iter()
+            .map(|info| (info.instruction_idx, info.start_offset, info.end_offset))
+            .collect::<Vec<_>>(),
+        vec![(0, 0, 1), (1, 1, 2), (2, 2, 2), (2, 2, 2), (2, 2, 2)]
+    );
+    // Without the elimination, the unreachable statements fail the compilation.
+    assert_eq!(
+        compile(&program, &metadata, SierraToCasmConfig { eliminate_dead_code: false, ..config })
+            .unwrap_err()
+            .to_string(),
+        "MissingAnnotationsForStatement"
+    );
+}
+
+#[test]
+fn validate_valid_program() {
+    let program = ProgramParser::new().parse(&read_sierra_example_file("fib_jumps")).unwrap();
+    let metadata = calc_metadata(&program, Default::default()).unwrap();
+    let config = SierraToCasmConfig { ..Default::default() };
+    let report = validate(&program, &metadata, config);
+    assert!(report.is_valid());
+    let cairo_program = compile(&program, &metadata, config).unwrap();
+    let code_size = cairo_program.debug_info.sierra_statement_info.last().unwrap().end_offset;
+    assert_eq!(report.code_size, Some(code_size));
+    assert_eq!(report.n_statements, program.statements.len());
+
+    // The size limit is checked as in the compilation.
+    let config = SierraToCasmConfig { max_bytecode_size: code_size - 1, ..config };
+    assert!(compile(&program, &metadata, config).is_err());
+    let report = validate(&program, &metadata, config);
+    assert_eq!(
+        report.errors,
+        vec![ValidationError {
+            function_id: None,
+            error: Box::new(CompilationError::CodeSizeLimitExceeded)
+        }]
+    );
+}
+
+#[test]
+fn validate_invalid_functions() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc store_temp<felt252> = store_temp<felt252>;
+
+            return([1]);
+            store_temp<felt252>([0]) -> ([0]);
+            return([0]);
+            return([2]);
+
+            foo@0([0]: felt252) -> (felt252);
+            bar@1([0]: felt252) -> (felt252);
+            baz@3([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    let report = validate(&program, &metadata, config);
+    // The errors of all the invalid functions are reported.
+    assert_eq!(
+        report
+            .errors
+            .iter()
+            .map(|err| (err.function_id.clone().unwrap().to_string(), err.error.to_string()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("foo".into(), "#0: [1] is undefined.".into()),
+            ("baz".into(), "#3: [2] is undefined.".into())
+        ]
+    );
+    assert_eq!(report.code_size, None);
+    // The compilation fails on the first error.
+    assert_eq!(
+        compile(&program, &metadata, config).unwrap_err().to_string(),
+        "#0: [1] is undefined."
+    );
+}
+
 cairo_lang_test_utils::test_file_test!(
     compiler_errors,
     "src/test_data",
@@ -815,7 +1265,7 @@ fn compiler_errors(
         Ok(metadata) => compile(
             &program,
             &metadata,
-            SierraToCasmConfig { gas_usage_check: false, max_bytecode_size },
+            SierraToCasmConfig { gas_usage_check: false, max_bytecode_size, ..Default::default() },
         )
         .expect_err("Compilation is expected to fail.")
         .to_string(),
@@ -823,3 +1273,363 @@ fn compiler_errors(
     };
     TestRunnerResult::success(OrderedHashMap::from([("error".into(), error_str)]))
 }
+
+#[test]
+fn compile_coalescing_ap_updates() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+            type Uninitialized<felt252> = Uninitialized<felt252>;
+
+            libfunc branch_align = branch_align;
+            libfunc dup<felt252> = dup<felt252>;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop<NonZeroFelt252> = drop<NonZeroFelt252>;
+            libfunc alloc_local<felt252> = alloc_local<felt252>;
+            libfunc finalize_locals = finalize_locals;
+            libfunc store_local<felt252> = store_local<felt252>;
+            libfunc store_temp<felt252> = store_temp<felt252>;
+
+            dup<felt252>([0]) -> ([0], [3]);
+            felt252_is_zero([3]) { fallthrough() 8([2]) };
+            branch_align() -> ();
+            alloc_local<felt252>() -> ([1]);
+            finalize_locals() -> ();
+            store_local<felt252>([1], [0]) -> ([0]);
+            store_temp<felt252>([0]) -> ([0]);
+            return([0]);
+            branch_align() -> ();
+            drop<NonZeroFelt252>([2]) -> ();
+            store_temp<felt252>([0]) -> ([0]);
+            store_temp<felt252>([0]) -> ([0]);
+            alloc_local<felt252>() -> ([1]);
+            finalize_locals() -> ();
+            store_local<felt252>([1], [0]) -> ([0]);
+            store_temp<felt252>([0]) -> ([0]);
+            return([0]);
+
+            main@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        coalesce_ap_updates: true,
+        ..Default::default()
+    };
+    let cairo_program = compile(&program, &metadata, config).unwrap();
+    // The `ap` update of the first `branch_align` is coalesced with the one of `finalize_locals`.
+    assert_eq!(
+        cairo_program.to_string(),
+        indoc! {"
+            jmp rel 7 if [fp + -3] != 0;
+            ap += 3;
+            [fp + 2] = [fp + -3];
+            [ap + 0] = [fp + 2], ap++;
+            ret;
+            [ap + 0] = [fp + -3], ap++;
+            [ap + 0] = [ap + -1], ap++;
+            ap += 1;
+            [fp + 2] = [ap + -2];
+            [ap + 0] = [fp + 2], ap++;
+            ret;
+        "}
+    );
+    assert_eq!(
+        cairo_program
+            .debug_info
+            .sierra_statement_info
+            .iter()
+            .map(|info| (info.instruction_idx, info.start_offset, info.end_offset))
+            .collect::<Vec<_>>()[..6],
+        [(0, 0, 0), (0, 0, 2), (1, 2, 4), (2, 4, 4), (2, 4, 4), (2, 4, 5)]
+    );
+    let uncoalesced =
+        compile(&program, &metadata, SierraToCasmConfig { coalesce_ap_updates: false, ..config })
+            .unwrap();
+    assert_eq!(uncoalesced.instructions.len(), cairo_program.instructions.len() + 1);
+}
+
+#[test]
+fn compile_annotating_gas_costs() {
+    let program = ProgramParser::new().parse(&read_sierra_example_file("fib_jumps")).unwrap();
+    let metadata = calc_metadata(&program, Default::default()).unwrap();
+    let config = SierraToCasmConfig { annotate_gas_costs: true, ..Default::default() };
+    let cairo_program = compile(&program, &metadata, config).unwrap();
+    let statement_info = &cairo_program.debug_info.sierra_statement_info;
+    let mut expected_gas_costs = vec![OrderedHashMap::default(); program.statements.len()];
+    for ((idx, token_type), value) in metadata.gas_info.variable_values.iter() {
+        expected_gas_costs[idx.0].insert(*token_type, *value);
+    }
+    assert!(expected_gas_costs.iter().any(|gas_costs| !gas_costs.is_empty()));
+    assert_eq!(
+        statement_info.iter().map(|info| info.gas_costs.clone()).collect::<Vec<_>>(),
+        expected_gas_costs.into_iter().map(Some).collect::<Vec<_>>()
+    );
+
+    // Without the annotation, the compilation is the same, without gas costs.
+    let unannotated =
+        compile(&program, &metadata, SierraToCasmConfig { annotate_gas_costs: false, ..config })
+            .unwrap();
+    assert_eq!(unannotated.instructions, cairo_program.instructions);
+    assert!(
+        unannotated.debug_info.sierra_statement_info.iter().all(|info| info.gas_costs.is_none())
+    );
+}
+
+#[test]
+fn compile_single_function() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_add = felt252_add;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc call_bar = function_call<user@bar>;
+            libfunc felt252_dup = dup<felt252>;
+
+            store_temp_felt252([0]) -> ([0]);
+            call_bar([0]) -> ([1]);
+            return([1]);
+            felt252_dup([0]) -> ([0], [1]);
+            felt252_add([0], [1]) -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+            felt252_add([0], [1]) -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+
+            foo@0([0]: felt252) -> (felt252);
+            bar@3([0]: felt252) -> (felt252);
+            baz@7([0]: felt252, [1]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    let foo = &program.funcs[0].id;
+    let cairo_program = compile_function(&program, foo, &metadata, config).unwrap();
+    // Only `foo` and `bar` are compiled, following the entry header.
+    assert_eq!(
+        cairo_program.to_string(),
+        indoc! {"
+            call rel 3;
+            ret;
+            [ap + 0] = [fp + -3], ap++;
+            call rel 3;
+            ret;
+            [ap + 0] = [fp + -3] + [fp + -3], ap++;
+            ret;
+        "}
+    );
+    assert_eq!(
+        cairo_program
+            .debug_info
+            .sierra_statement_info
+            .iter()
+            .map(|info| (info.instruction_idx, info.start_offset, info.end_offset))
+            .collect::<Vec<_>>(),
+        vec![
+            (2, 3, 4),
+            (3, 4, 6),
+            (4, 6, 7),
+            (5, 7, 7),
+            (5, 7, 7),
+            (5, 7, 8),
+            (6, 8, 9),
+            (7, 9, 9),
+            (7, 9, 9),
+            (7, 9, 9)
+        ]
+    );
+
+    // The header calls the requested function.
+    let bar = &program.funcs[1].id;
+    let cairo_program = compile_function(&program, bar, &metadata, config).unwrap();
+    assert_eq!(cairo_program.instructions[0].to_string(), "call rel 3");
+    assert_eq!(cairo_program.debug_info.sierra_statement_info[3].start_offset, 3);
+
+    assert_eq!(
+        compile_function(&program, &"qux".into(), &metadata, config).unwrap_err().to_string(),
+        "Function `qux` not found in the program."
+    );
+}
+
+#[test_case("fib_no_gas", false, false; "fib_no_gas")]
+#[test_case("fib_jumps", true, false; "fib_jumps")]
+#[test_case("fib_jumps", true, true; "fib_jumps_single_function")]
+fn relocate_unrelocated_instructions(name: &str, gas_usage_check: bool, single_function: bool) {
+    let program = ProgramParser::new().parse(&read_sierra_example_file(name)).unwrap();
+    let metadata = if gas_usage_check {
+        calc_metadata(&program, Default::default()).unwrap_or_default()
+    } else {
+        calc_metadata_ap_change_only(&program).unwrap_or_default()
+    };
+    let config = SierraToCasmConfig { gas_usage_check, ..Default::default() };
+    let cairo_program = if single_function {
+        compile_function(&program, &program.funcs[0].id, &metadata, config).unwrap()
+    } else {
+        compile(&program, &metadata, config).unwrap()
+    };
+    let mut instructions = cairo_program.unrelocated_instructions();
+    assert_ne!(instructions, cairo_program.instructions);
+    // Applying the relocations with the layout of the program restores its instructions.
+    relocate_instructions(
+        &cairo_program.relocations,
+        &cairo_program.statement_offsets(),
+        &cairo_program.consts_info,
+        &mut instructions,
+    );
+    assert_eq!(instructions, cairo_program.instructions);
+}
+
+/// An observer recording the compilation events.
+#[derive(Default)]
+struct RecordingObserver {
+    events: Mutex<Vec<String>>,
+}
+impl RecordingObserver {
+    fn record(&self, event: String) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+impl CompilationObserver for RecordingObserver {
+    fn on_statement_start(&self, statement_idx: StatementIdx) {
+        self.record(format!("start #{statement_idx}"));
+    }
+
+    fn on_statement_end(&self, statement_idx: StatementIdx, code_size: usize) {
+        self.record(format!("end #{statement_idx}: {code_size}"));
+    }
+
+    fn on_invocation(&self, statement_idx: StatementIdx, invocation: &CompiledInvocation) {
+        self.record(format!(
+            "invocation #{statement_idx}: {} instructions",
+            invocation.instructions.len()
+        ));
+    }
+
+    fn on_relocation(&self, entry: &RelocationEntry, instruction: &Instruction) {
+        self.record(format!("relocation of instruction {}: {instruction}", entry.instruction_idx));
+    }
+}
+
+#[test]
+fn compile_with_observer_test() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc store_temp<felt252> = store_temp<felt252>;
+            libfunc call_foo = function_call<user@foo>;
+
+            store_temp<felt252>([0]) -> ([0]);
+            call_foo([0]) -> ([0]);
+            return([0]);
+            store_temp<felt252>([0]) -> ([0]);
+            return([0]);
+
+            main@0([0]: felt252) -> (felt252);
+            foo@3([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    let observer = RecordingObserver::default();
+    let cairo_program = compile_with_observer(&program, &metadata, config, &observer).unwrap();
+    assert_eq!(cairo_program, compile(&program, &metadata, config).unwrap());
+    // The functions may be compiled in parallel, so the order of the events of the different
+    // functions is not deterministic.
+    let mut events = observer.events.into_inner().unwrap();
+    events.sort();
+    assert_eq!(
+        events,
+        vec![
+            "end #0: 1",
+            "end #1: 2",
+            "end #2: 1",
+            "end #3: 1",
+            "end #4: 1",
+            "invocation #0: 1 instructions",
+            "invocation #1: 1 instructions",
+            "invocation #3: 1 instructions",
+            "relocation of instruction 1: call rel 3",
+            "start #0",
+            "start #1",
+            "start #2",
+            "start #3",
+            "start #4",
+        ]
+    );
+}
+
+#[test]
+fn compile_error_with_observer_test() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_add = felt252_add;
+
+            felt252_add([0], [1]) -> ([2]);
+            return([2]);
+
+            main@0([0]: felt252, [1]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    let observer = RecordingObserver::default();
+    assert_eq!(
+        compile_with_observer(&program, &metadata, config, &observer).unwrap_err().to_string(),
+        "#1: Return arguments are not on the stack."
+    );
+    // The error of the function is returned as is, without compiling the program again, so each
+    // statement is observed once.
+    assert_eq!(
+        observer.events.into_inner().unwrap(),
+        vec!["start #0", "invocation #0: 0 instructions", "end #0: 0", "start #1"]
+    );
+}
+
+#[test]
+fn compile_collecting_errors_test() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_add = felt252_add;
+            libfunc store_temp<felt252> = store_temp<felt252>;
+
+            felt252_add([0], [1]) -> ([2]);
+            return([2]);
+            felt252_add([0], [5]) -> ([2]);
+            store_temp<felt252>([2]) -> ([2]);
+            return([2]);
+
+            main@0([0]: felt252, [1]: felt252) -> (felt252);
+            foo@2([0]: felt252, [1]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    assert_eq!(
+        compile(&program, &metadata, config).unwrap_err().to_string(),
+        "#1: Return arguments are not on the stack."
+    );
+    // The missing annotations of the statements following the second error are not reported.
+    assert_eq!(
+        compile_collecting_errors(&program, &metadata, config)
+            .unwrap_err()
+            .into_iter()
+            .map(|StatementCompilationError { statement_idx, error }| (
+                statement_idx,
+                error.to_string()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (Some(StatementIdx(1)), "#1: Return arguments are not on the stack.".to_string()),
+            (Some(StatementIdx(2)), "#2: [5] is undefined.".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,183 @@
+//! Accumulation of the costs of a compiled program per line of its Cairo source code.
+
+use std::fmt::Write;
+
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_gas::core_libfunc_cost::{core_libfunc_cost, InvocationCostInfoProvider};
+use cairo_lang_sierra_gas::gas_info::GasInfo;
+use cairo_lang_sierra_type_size::{get_type_size_map, TypeSizeMap};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use crate::compiler::{CairoProgram, CompilationError};
+use crate::metadata::Metadata;
+
+#[cfg(test)]
+#[path = "cost_overlay_test.rs"]
+mod test;
+
+/// The costs accumulated from the Sierra statements originating from a single source line.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct LineCosts {
+    /// The number of Sierra statements.
+    pub sierra_statements: usize,
+    /// The size of the CASM bytecode of the statements.
+    pub casm_bytecode_size: usize,
+    /// The static gas cost of the statements per token type, taking the most expensive branch of
+    /// each statement.
+    /// Does not include the costs of called functions, nor the gas withdrawn in advance for the
+    /// following statements.
+    pub gas: OrderedHashMap<CostTokenType, i64>,
+}
+
+/// The costs of a compiled program, accumulated per line of its Cairo source files.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct SourceCostOverlay {
+    /// Maps the full path of a source file to the costs of its lines, by their 0 based index.
+    pub files: OrderedHashMap<String, OrderedHashMap<usize, LineCosts>>,
+}
+impl SourceCostOverlay {
+    /// Accumulates the costs of the statements of `program`, compiled into `cairo_program` using
+    /// `metadata`, into the source lines they originated from.
+    ///
+    /// Only statements with a source location in the debug info of `cairo_program` are
+    /// accounted for, see [crate::compiler::CairoProgramDebugInfo::populate_source_locations].
+    pub fn new(
+        program: &Program,
+        cairo_program: &CairoProgram,
+        metadata: &Metadata,
+    ) -> Result<Self, Box<CompilationError>> {
+        let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_ap_change(
+            program,
+            metadata.ap_change_info.function_ap_change.clone(),
+        )
+        .map_err(CompilationError::ProgramRegistryError)?;
+        let type_sizes = get_type_size_map(program, &registry)
+            .ok_or(CompilationError::FailedBuildingTypeInformation)?;
+        // Gas info with all the variables and function costs zeroed, so that only the cost of the
+        // statements themselves is computed.
+        let gas_info = GasInfo {
+            variable_values: metadata
+                .gas_info
+                .variable_values
+                .keys()
+                .map(|key| (*key, 0))
+                .collect(),
+            function_costs: program
+                .funcs
+                .iter()
+                .map(|func| (func.id.clone(), OrderedHashMap::default()))
+                .collect(),
+        };
+
+        let mut overlay = Self::default();
+        for (statement_id, (statement, info)) in program
+            .statements
+            .iter()
+            .zip(&cairo_program.debug_info.sierra_statement_info)
+            .enumerate()
+        {
+            let Some(location) = &info.source_location else {
+                continue;
+            };
+            let line_costs = overlay
+                .files
+                .entry(location.file.clone())
+                .or_default()
+                .entry(location.line)
+                .or_default();
+            line_costs.sierra_statements += 1;
+            line_costs.casm_bytecode_size += info.end_offset - info.start_offset;
+            let Statement::Invocation(invocation) = statement else {
+                continue;
+            };
+            let libfunc = registry
+                .get_libfunc(&invocation.libfunc_id)
+                .map_err(CompilationError::ProgramRegistryError)?;
+            let statement_idx = StatementIdx(statement_id);
+            let info_provider =
+                StatementCostInfoProvider { type_sizes: &type_sizes, metadata, statement_idx };
+            let mut statement_gas = OrderedHashMap::<CostTokenType, i64>::default();
+            for branch_cost in core_libfunc_cost(&gas_info, &statement_idx, libfunc, &info_provider)
+                .into_iter()
+                .flatten()
+            {
+                for (token_type, cost) in branch_cost {
+                    let max_cost = statement_gas.entry(token_type).or_default();
+                    *max_cost = (*max_cost).max(cost);
+                }
+            }
+            for (token_type, cost) in statement_gas {
+                *line_costs.gas.entry(token_type).or_default() += cost;
+            }
+        }
+        Ok(overlay)
+    }
+
+    /// Returns the given content of the source file `file` with each line prefixed by its
+    /// accumulated costs - the number of Sierra statements, the CASM bytecode size and the `const`
+    /// gas cost. Builtin gas costs are added as a trailing comment.
+    /// Returns `None` if no costs were accumulated for the file.
+    pub fn annotate(&self, file: &str, content: &str) -> Option<String> {
+        let lines = self.files.get(file)?;
+        let mut annotated = format!("{:>8} {:>8} {:>8} |\n", "sierra", "casm", "gas");
+        for (line_idx, line) in content.lines().enumerate() {
+            let Some(costs) = lines.get(&line_idx) else {
+                writeln!(annotated, "{:>8} {:>8} {:>8} | {line}", "", "", "").unwrap();
+                continue;
+            };
+            write!(
+                annotated,
+                "{:>8} {:>8} {:>8} | {line}",
+                costs.sierra_statements,
+                costs.casm_bytecode_size,
+                costs.gas.get(&CostTokenType::Const).copied().unwrap_or_default(),
+            )
+            .unwrap();
+            let builtin_costs = costs
+                .gas
+                .iter()
+                .filter(|(token_type, cost)| **token_type != CostTokenType::Const && **cost != 0)
+                .map(|(token_type, cost)| format!("{}: {cost}", token_type.name()))
+                .collect::<Vec<_>>();
+            if !builtin_costs.is_empty() {
+                write!(annotated, " // {}", builtin_costs.join(", ")).unwrap();
+            }
+            annotated.push('\n');
+        }
+        Some(annotated)
+    }
+}
+
+/// Provides the information required for computing the cost of a single statement.
+struct StatementCostInfoProvider<'a> {
+    type_sizes: &'a TypeSizeMap,
+    metadata: &'a Metadata,
+    statement_idx: StatementIdx,
+}
+impl InvocationCostInfoProvider for StatementCostInfoProvider<'_> {
+    fn type_size(&self, ty: &ConcreteTypeId) -> usize {
+        self.type_sizes[ty] as usize
+    }
+
+    fn token_usages(&self, token_type: CostTokenType) -> usize {
+        self.metadata
+            .gas_info
+            .variable_values
+            .get(&(self.statement_idx, token_type))
+            .copied()
+            .unwrap_or(0) as usize
+    }
+
+    fn ap_change_var_value(&self) -> usize {
+        self.metadata
+            .ap_change_info
+            .variable_values
+            .get(&self.statement_idx)
+            .copied()
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,63 @@
+use cairo_lang_sierra::debug_info::SourceCodeLocation;
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra::ProgramParser;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use indoc::indoc;
+
+use super::SourceCostOverlay;
+use crate::compiler::{compile, SierraToCasmConfig};
+use crate::metadata::calc_metadata_ap_change_only;
+
+#[test]
+fn annotate_source() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type Pedersen = Pedersen;
+
+            libfunc pedersen = pedersen;
+            libfunc store_temp<felt252> = store_temp<felt252>;
+            libfunc store_temp<Pedersen> = store_temp<Pedersen>;
+
+            pedersen([0], [1], [2]) -> ([0], [3]);
+            store_temp<Pedersen>([0]) -> ([0]);
+            store_temp<felt252>([3]) -> ([3]);
+            return([0], [3]);
+
+            foo@0([0]: Pedersen, [1]: felt252, [2]: felt252) -> (Pedersen, felt252);
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let mut cairo_program = compile(
+        &program,
+        &metadata,
+        SierraToCasmConfig { gas_usage_check: false, ..Default::default() },
+    )
+    .unwrap();
+    let location = |line| SourceCodeLocation { file: "lib.cairo".into(), line, column: 4 };
+    cairo_program.debug_info.populate_source_locations(&UnorderedHashMap::from_iter([
+        (StatementIdx(0), location(1)),
+        (StatementIdx(1), location(2)),
+        (StatementIdx(2), location(2)),
+        (StatementIdx(3), location(2)),
+    ]));
+    let overlay = SourceCostOverlay::new(&program, &cairo_program, &metadata).unwrap();
+
+    let source = indoc! {"
+        fn foo(a: felt252, b: felt252) -> felt252 {
+            let c = pedersen(a, b);
+            c
+        }
+    "};
+    assert_eq!(overlay.annotate("other.cairo", source), None);
+    pretty_assertions::assert_eq!(
+        overlay.annotate("lib.cairo", source).unwrap(),
+        concat!(
+            "  sierra     casm      gas |\n",
+            "                           | fn foo(a: felt252, b: felt252) -> felt252 {\n",
+            "       1        2      200 |     let c = pedersen(a, b); // pedersen: 1\n",
+            "       3        4      200 |     c\n",
+            "                           | }\n",
+        )
+    );
+}
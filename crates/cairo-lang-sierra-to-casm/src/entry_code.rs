@@ -0,0 +1,239 @@
+//! Building of the entry code of a CASM program, that sets up the builtins and arguments of a
+//! Sierra function and calls it.
+
+use cairo_felt::Felt252;
+use cairo_lang_casm::inline::CasmContext;
+use cairo_lang_casm::instructions::Instruction;
+use cairo_lang_casm::{casm, casm_extend};
+use cairo_lang_sierra::extensions::bitwise::BitwiseType;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::ec::EcOpType;
+use cairo_lang_sierra::extensions::gas::GasBuiltinType;
+use cairo_lang_sierra::extensions::pedersen::PedersenType;
+use cairo_lang_sierra::extensions::poseidon::PoseidonType;
+use cairo_lang_sierra::extensions::range_check::RangeCheckType;
+use cairo_lang_sierra::extensions::segment_arena::SegmentArenaType;
+use cairo_lang_sierra::extensions::starknet::syscalls::SystemType;
+use cairo_lang_sierra::extensions::{ConcreteType, NamedType};
+use cairo_lang_sierra::ids::GenericTypeId;
+use cairo_lang_sierra::program::Function;
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_utils::casts::IntoOrPanic;
+use thiserror::Error;
+
+use crate::compiler::CairoProgram;
+
+#[cfg(test)]
+#[path = "entry_code_test.rs"]
+mod test;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum EntryCodeError {
+    #[error("Function param {param_index} only partially contains argument {arg_index}.")]
+    ArgumentUnaligned { param_index: usize, arg_index: usize },
+    #[error("Function expects arguments of size {expected} and received {actual} instead.")]
+    ArgumentsSizeMismatch { expected: usize, actual: usize },
+}
+
+/// An argument to a sierra function run,
+#[derive(Debug)]
+pub enum Arg {
+    Value(Felt252),
+    Array(Vec<Arg>),
+}
+impl From<Felt252> for Arg {
+    fn from(value: Felt252) -> Self {
+        Self::Value(value)
+    }
+}
+
+/// The builtins the entry code expects the caller to provide, in the order they are pushed before
+/// the call to the entry code - so the last one is at `[fp - 3]`.
+pub const ENTRY_CODE_BUILTINS: [GenericTypeId; 5] =
+    [PedersenType::ID, RangeCheckType::ID, BitwiseType::ID, EcOpType::ID, PoseidonType::ID];
+
+/// Returns the offset `i` of the builtin, given as `[fp - i]` in the entry code, if it is one of
+/// [ENTRY_CODE_BUILTINS].
+fn builtin_fp_offset(generic_ty: &GenericTypeId) -> Option<i16> {
+    let idx = ENTRY_CODE_BUILTINS.iter().position(|builtin| builtin == generic_ty)?;
+    Some((ENTRY_CODE_BUILTINS.len() + 2 - idx).into_or_panic())
+}
+
+/// Returns the instructions to add to the beginning of the code to successfully call a function
+/// with the given params, starting at `code_offset` of the code following the entry code.
+pub fn create_entry_code_from_params(
+    param_types: &[(GenericTypeId, i16)],
+    args: &[Arg],
+    initial_gas: usize,
+    code_offset: usize,
+) -> Result<Vec<Instruction>, EntryCodeError> {
+    let mut ctx = casm! {};
+    let mut ap_offset: i16 = 0;
+    let mut array_args_data_iter = prep_array_args(&mut ctx, args, &mut ap_offset).into_iter();
+    let after_arrays_data_offset = ap_offset;
+    if param_types.iter().any(|(ty, _)| ty == &SegmentArenaType::ID) {
+        casm_extend! {ctx,
+            // SegmentArena segment.
+            %{ memory[ap + 0] = segments.add() %}
+            // Infos segment.
+            %{ memory[ap + 1] = segments.add() %}
+            ap += 2;
+            [ap + 0] = 0, ap++;
+            // Write Infos segment, n_constructed (0), and n_destructed (0) to the segment.
+            [ap - 2] = [[ap - 3]];
+            [ap - 1] = [[ap - 3] + 1];
+            [ap - 1] = [[ap - 3] + 2];
+        }
+        ap_offset += 3;
+    }
+    let mut expected_arguments_size = 0;
+    let mut param_index = 0;
+    let mut arg_iter = args.iter().enumerate();
+    for ty in param_types {
+        let (generic_ty, ty_size) = ty;
+        if let Some(offset) = builtin_fp_offset(generic_ty) {
+            casm_extend! {ctx,
+                [ap + 0] = [fp - offset], ap++;
+            }
+            ap_offset += 1;
+        } else if generic_ty == &SystemType::ID {
+            casm_extend! {ctx,
+                %{ memory[ap + 0] = segments.add() %}
+                ap += 1;
+            }
+            ap_offset += 1;
+        } else if generic_ty == &GasBuiltinType::ID {
+            casm_extend! {ctx,
+                [ap + 0] = initial_gas, ap++;
+            }
+            ap_offset += 1;
+        } else if generic_ty == &SegmentArenaType::ID {
+            let offset = -ap_offset + after_arrays_data_offset;
+            casm_extend! {ctx,
+                [ap + 0] = [ap + offset] + 3, ap++;
+            }
+            ap_offset += 1;
+        } else {
+            let arg_size = *ty_size;
+            let param_ap_offset_end = ap_offset + arg_size;
+            expected_arguments_size += arg_size.into_or_panic::<usize>();
+            while ap_offset < param_ap_offset_end {
+                let Some((arg_index, arg)) = arg_iter.next() else {
+                    break;
+                };
+                add_arg_to_stack(&mut ctx, arg, &mut ap_offset, &mut array_args_data_iter);
+                if ap_offset > param_ap_offset_end {
+                    return Err(EntryCodeError::ArgumentUnaligned { param_index, arg_index });
+                }
+            }
+            param_index += 1;
+        };
+    }
+    let actual_args_size = args
+        .iter()
+        .map(|arg| match arg {
+            Arg::Value(_) => 1,
+            Arg::Array(_) => 2,
+        })
+        .sum::<usize>();
+    if expected_arguments_size != actual_args_size {
+        return Err(EntryCodeError::ArgumentsSizeMismatch {
+            expected: expected_arguments_size,
+            actual: actual_args_size,
+        });
+    }
+    let before_final_call = ctx.current_code_offset;
+    let final_call_size = 3;
+    let offset = final_call_size + code_offset;
+    casm_extend! {ctx,
+        call rel offset;
+        ret;
+    }
+    assert_eq!(before_final_call + final_call_size, ctx.current_code_offset);
+    Ok(ctx.instructions)
+}
+
+/// Returns the instructions to add to the beginning of `casm_program` to successfully call `func`.
+pub fn create_entry_code(
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    type_sizes: &TypeSizeMap,
+    casm_program: &CairoProgram,
+    func: &Function,
+    args: &[Arg],
+    initial_gas: usize,
+) -> Result<Vec<Instruction>, EntryCodeError> {
+    let params = func
+        .signature
+        .param_types
+        .iter()
+        .map(|ty| {
+            let info = registry.get_type(ty).unwrap().info();
+            (info.long_id.generic_id.clone(), type_sizes[ty])
+        })
+        .collect::<Vec<_>>();
+    let code_offset =
+        casm_program.debug_info.sierra_statement_info[func.entry_point.0].start_offset;
+    create_entry_code_from_params(&params, args, initial_gas, code_offset)
+}
+
+/// The information on an array argument that was added to the stack.
+struct ArrayDataInfo {
+    /// The offset of the pointer to the array data in the stack.
+    ptr_offset: i16,
+    /// The size of the array data in the stack.
+    size: i16,
+}
+
+/// Adds an argument to the stack, updating the ap_offset and the array_data_iter.
+fn add_arg_to_stack(
+    ctx: &mut CasmContext,
+    arg: &Arg,
+    ap_offset: &mut i16,
+    array_data_iter: &mut impl Iterator<Item = ArrayDataInfo>,
+) {
+    match arg {
+        Arg::Value(value) => {
+            casm_extend! {ctx,
+                [ap + 0] = (value.to_bigint()), ap++;
+            }
+            *ap_offset += 1;
+        }
+        Arg::Array(_) => {
+            let info = array_data_iter.next().unwrap();
+            casm_extend! {ctx,
+                [ap + 0] = [ap + (info.ptr_offset - *ap_offset)], ap++;
+                [ap + 0] = [ap - 1] + (info.size), ap++;
+            }
+            *ap_offset += 2;
+        }
+    }
+}
+
+/// Prepares the array arguments for the stack, updating the ap_offset and returning the
+/// array_args_data.
+fn prep_array_args(ctx: &mut CasmContext, args: &[Arg], ap_offset: &mut i16) -> Vec<ArrayDataInfo> {
+    let mut array_args_data = vec![];
+    for arg in args {
+        let Arg::Array(values) = arg else { continue };
+        let mut inner_array_args_data = prep_array_args(ctx, values, ap_offset).into_iter();
+        casm_extend! {ctx,
+            %{ memory[ap + 0] = segments.add() %}
+            ap += 1;
+        }
+
+        let ptr_offset = *ap_offset;
+        *ap_offset += 1;
+        let data_offset = *ap_offset;
+        for arg in values {
+            add_arg_to_stack(ctx, arg, ap_offset, &mut inner_array_args_data);
+        }
+        let ptr = *ap_offset - ptr_offset;
+        let size = *ap_offset - data_offset;
+        for i in 0..size {
+            casm_extend! {ctx, [ap + (i - size)] = [[ap - ptr] + i]; }
+        }
+        array_args_data.push(ArrayDataInfo { ptr_offset, size });
+    }
+    array_args_data
+}
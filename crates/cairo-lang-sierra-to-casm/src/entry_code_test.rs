@@ -0,0 +1,68 @@
+use cairo_felt::Felt252;
+use cairo_lang_casm::instructions::Instruction;
+use cairo_lang_sierra::extensions::array::ArrayType;
+use cairo_lang_sierra::extensions::felt252::Felt252Type;
+use cairo_lang_sierra::extensions::gas::GasBuiltinType;
+use cairo_lang_sierra::extensions::range_check::RangeCheckType;
+use cairo_lang_sierra::extensions::NamedType;
+use cairo_lang_sierra::ids::GenericTypeId;
+use indoc::indoc;
+use itertools::join;
+use pretty_assertions::assert_eq;
+
+use super::{create_entry_code_from_params, Arg, EntryCodeError};
+
+/// Returns the code of the given instructions, one instruction per line.
+fn code(instructions: &[Instruction]) -> String {
+    join(instructions.iter().map(|instruction| format!("{instruction};\n")), "")
+}
+
+/// The params of a function taking the range check and gas builtins, a felt252 and an array.
+fn params() -> Vec<(GenericTypeId, i16)> {
+    vec![(RangeCheckType::ID, 1), (GasBuiltinType::ID, 1), (Felt252Type::ID, 1), (ArrayType::ID, 2)]
+}
+
+#[test]
+fn entry_code_with_builtins_and_args() {
+    let instructions = create_entry_code_from_params(
+        &params(),
+        &[Arg::Value(Felt252::from(5)), Arg::Array(vec![Felt252::from(7).into()])],
+        1000,
+        20,
+    )
+    .unwrap();
+    assert_eq!(
+        code(&instructions),
+        indoc! {"
+        %{ memory[ap + 0] = segments.add() %}
+        ap += 1;
+        [ap + 0] = 7, ap++;
+        [ap + -1] = [[ap + -2] + 0];
+        [ap + 0] = [fp + -6], ap++;
+        [ap + 0] = 1000, ap++;
+        [ap + 0] = 5, ap++;
+        [ap + 0] = [ap + -5], ap++;
+        [ap + 0] = [ap + -1] + 1, ap++;
+        call rel 23;
+        ret;
+    "}
+    );
+}
+
+#[test]
+fn entry_code_argument_errors() {
+    let params = params();
+    assert_eq!(
+        create_entry_code_from_params(&params, &[Arg::Value(Felt252::from(5))], 0, 0),
+        Err(EntryCodeError::ArgumentsSizeMismatch { expected: 3, actual: 1 })
+    );
+    assert_eq!(
+        create_entry_code_from_params(
+            &params,
+            &[Arg::Array(vec![]), Arg::Value(Felt252::from(5))],
+            0,
+            0
+        ),
+        Err(EntryCodeError::ArgumentUnaligned { param_index: 0, arg_index: 0 })
+    );
+}
@@ -0,0 +1,219 @@
+use cairo_lang_casm::builder::{CasmBuilder, Var};
+use cairo_lang_casm::casm_build_extend;
+use cairo_lang_casm::cell_expression::CellExpression;
+use cairo_lang_sierra::extensions::blake::Blake2sConcreteLibfunc;
+use num_bigint::BigInt;
+
+use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+use crate::invocations::{add_input_variables, CostValidationInfo};
+
+/// The Blake2s initialization vector.
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The message word permutation of each of the Blake2s rounds.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The indices of the working vector words mixed by each of the `G` applications of a round.
+const MIX_INDICES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The number of `G` applications in the compression function.
+const MIX_COUNT: i16 = (SIGMA.len() * MIX_INDICES.len()) as i16;
+/// The number of range checks used by the compression function - 3 for each of the 4 additions
+/// and 4 rotations of every `G` application.
+const RANGE_CHECK_USAGE: i16 = MIX_COUNT * 8 * 3;
+/// The number of bitwise builtin usages of the compression function - the 4 XORs of every `G`
+/// application, one for mixing in the byte count and 2 for every word of the output state.
+const BITWISE_USAGE: i16 = MIX_COUNT * 4 + 1 + 2 * 8;
+/// The number of memory cells used by a single bitwise builtin usage.
+const BITWISE_CELLS: i16 = 5;
+
+/// Builds instructions for Sierra Blake2s operations.
+pub fn build(
+    libfunc: &Blake2sConcreteLibfunc,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    match libfunc {
+        Blake2sConcreteLibfunc::Compress(_) => build_blake2s_compress(builder, false),
+        Blake2sConcreteLibfunc::Finalize(_) => build_blake2s_compress(builder, true),
+    }
+}
+
+/// Handles instructions for applying the Blake2s compression function on a message block.
+/// `finalize` states whether the block is the last one of the message.
+fn build_blake2s_compress(
+    builder: CompiledInvocationBuilder<'_>,
+    finalize: bool,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, bitwise, state, byte_count, msg] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [bitwise] = bitwise.try_unpack()?;
+    let [byte_count] = byte_count.try_unpack()?;
+    let state = state.try_unpack::<8>()?;
+    let msg = msg.try_unpack::<16>()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(RANGE_CHECK_USAGE) range_check;
+        buffer(BITWISE_USAGE * BITWISE_CELLS) bitwise;
+        deref byte_count;
+    };
+    let mut add_deref_vars = |cells: &[CellExpression]| {
+        cells
+            .iter()
+            .map(|cell| {
+                Ok(casm_builder.add_var(CellExpression::Deref(
+                    cell.to_deref()
+                        .ok_or(InvocationError::InvalidReferenceExpressionForArgument)?,
+                )))
+            })
+            .collect::<Result<Vec<_>, InvocationError>>()
+    };
+    let state = add_deref_vars(state)?;
+    let msg = add_deref_vars(msg)?;
+
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    // The working vector is initialized with the state followed by the IV, where the low word of
+    // the byte count is mixed into its 12th word, and the 14th word is inverted for the final
+    // block. The high word of the byte count is always 0, so the 13th word is left untouched.
+    let mut v = state.clone();
+    for (i, iv) in IV.into_iter().enumerate() {
+        let value = if i == 6 && finalize { !iv } else { iv };
+        casm_build_extend! {casm_builder,
+            const value = value;
+            tempvar word = value;
+        };
+        v.push(word);
+    }
+    v[12] = xor(&mut casm_builder, bitwise, v[12], byte_count);
+
+    for sigma in SIGMA {
+        for (mix_idx, [a, b, c, d]) in MIX_INDICES.into_iter().enumerate() {
+            let x = msg[sigma[2 * mix_idx]];
+            let y = msg[sigma[2 * mix_idx + 1]];
+            v[a] = add_u32(&mut casm_builder, range_check, v[a], v[b], Some(x));
+            v[d] = xor_rotr(&mut casm_builder, range_check, bitwise, v[d], v[a], 16);
+            v[c] = add_u32(&mut casm_builder, range_check, v[c], v[d], None);
+            v[b] = xor_rotr(&mut casm_builder, range_check, bitwise, v[b], v[c], 12);
+            v[a] = add_u32(&mut casm_builder, range_check, v[a], v[b], Some(y));
+            v[d] = xor_rotr(&mut casm_builder, range_check, bitwise, v[d], v[a], 8);
+            v[c] = add_u32(&mut casm_builder, range_check, v[c], v[d], None);
+            v[b] = xor_rotr(&mut casm_builder, range_check, bitwise, v[b], v[c], 7);
+        }
+    }
+
+    let new_state = (0..8)
+        .map(|i| {
+            let partial = xor(&mut casm_builder, bitwise, state[i], v[i]);
+            xor(&mut casm_builder, bitwise, partial, v[i + 8])
+        })
+        .collect::<Vec<_>>();
+
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[range_check], &[bitwise], &new_state], None)],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Adds instructions calculating the sum of `a`, `b` and optionally `c` modulo 2**32.
+fn add_u32(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    a: Var,
+    b: Var,
+    c: Option<Var>,
+) -> Var {
+    casm_build_extend!(casm_builder, tempvar sum = a + b;);
+    let sum = match c {
+        Some(c) => {
+            casm_build_extend!(casm_builder, tempvar full_sum = sum + c;);
+            full_sum
+        }
+        None => sum,
+    };
+    casm_build_extend! {casm_builder,
+        const u32_limit = (BigInt::from(u32::MAX) + 1) as BigInt;
+        const u128_bound_minus_u32_limit = (BigInt::from(u128::MAX) - u32::MAX) as BigInt;
+        tempvar carry;
+        tempvar result;
+        hint DivMod { lhs: sum, rhs: u32_limit } into { quotient: carry, remainder: result };
+        // The result is in the range [0, 2**32).
+        assert result = *(range_check++);
+        tempvar shifted_result = result + u128_bound_minus_u32_limit;
+        assert shifted_result = *(range_check++);
+        // The carry is in range, so the decomposition of `sum` is unique.
+        assert carry = *(range_check++);
+        tempvar carry_shifted = carry * u32_limit;
+        assert sum = carry_shifted + result;
+    };
+    result
+}
+
+/// Adds instructions calculating the XOR of `a` and `b` using the bitwise builtin.
+fn xor(casm_builder: &mut CasmBuilder, bitwise: Var, a: Var, b: Var) -> Var {
+    casm_build_extend! {casm_builder,
+        assert a = *(bitwise++);
+        assert b = *(bitwise++);
+        let _and = *(bitwise++);
+        tempvar xor = *(bitwise++);
+        let _or = *(bitwise++);
+    };
+    xor
+}
+
+/// Adds instructions calculating the XOR of `a` and `b`, rotated right by `shift` bits.
+fn xor_rotr(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    bitwise: Var,
+    a: Var,
+    b: Var,
+    shift: u32,
+) -> Var {
+    let value = xor(casm_builder, bitwise, a, b);
+    casm_build_extend! {casm_builder,
+        const low_limit = BigInt::from(1) << shift;
+        const u128_bound_minus_low_limit =
+            ((BigInt::from(1) << 128) - (BigInt::from(1) << shift)) as BigInt;
+        const high_shift = BigInt::from(1) << (32 - shift);
+        tempvar high;
+        tempvar low;
+        hint DivMod { lhs: value, rhs: low_limit } into { quotient: high, remainder: low };
+        // The low part is in the range [0, 2**shift).
+        assert low = *(range_check++);
+        tempvar shifted_low = low + u128_bound_minus_low_limit;
+        assert shifted_low = *(range_check++);
+        // The high part is in range, so the decomposition of `value` is unique.
+        assert high = *(range_check++);
+        tempvar high_shifted = high * low_limit;
+        assert value = high_shifted + low;
+        tempvar low_rotated = low * high_shift;
+        tempvar rotated = low_rotated + high;
+    };
+    rotated
+}
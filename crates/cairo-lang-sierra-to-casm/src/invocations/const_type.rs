@@ -1,6 +1,6 @@
-use cairo_lang_casm::casm;
 use cairo_lang_casm::cell_expression::CellExpression;
 use cairo_lang_casm::operand::{CellRef, Register};
+use cairo_lang_casm::{casm, casm_extend};
 use cairo_lang_sierra::extensions::const_type::{
     ConstAsBoxConcreteLibfunc, ConstAsImmediateConcreteLibfunc, ConstConcreteLibfunc,
 };
@@ -25,7 +25,7 @@ fn build_as_box(
     libfunc: &ConstAsBoxConcreteLibfunc,
     builder: CompiledInvocationBuilder<'_>,
 ) -> Result<CompiledInvocation, InvocationError> {
-    let ctx = casm! {
+    let mut ctx = casm! {
         // The relocation will point the `call` to the `ret;` instruction that precedes the
         // relevant const.
         call rel 0;
@@ -34,6 +34,17 @@ fn build_as_box(
         // TODO(Gil): Support relocatable CellExpression and return an unstored "[ap - 1] + 1".
         [ap] = [ap - 1] + 1, ap++;
     };
+    if libfunc.is_span {
+        // Only the elements of a span are in the segment - so a box of the span is allocated,
+        // containing the start and end of the elements.
+        let size = (builder.program_info.const_data_values)(&libfunc.const_type).len();
+        casm_extend! {ctx,
+            [ap] = [ap - 1] + size, ap++;
+            %{ memory[ap + 0] = segments.add() %}
+            [ap - 2] = [[ap + 0]], ap++;
+            [ap - 2] = [[ap - 1] + 1];
+        }
+    }
     let relocations = vec![
         RelocationEntry {
             instruction_idx: 0,
@@ -1,6 +1,6 @@
 use cairo_lang_casm::builder::CasmBuilder;
 use cairo_lang_casm::casm_build_extend;
-use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SintTraits};
+use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SintOverflowHandling, SintTraits};
 use cairo_lang_sierra::extensions::int::{IntMulTraits, IntOperator};
 use cairo_lang_sierra::extensions::is_zero::IsZeroTraits;
 use cairo_lang_sierra::extensions::utils::Range;
@@ -123,6 +123,145 @@ pub fn build_sint_overflowing_operation(
     ))
 }
 
+/// Handles addition or subtraction of signed integers, saturating or wrapping on overflow.
+/// `[min_value, max_value]` is the range of the signed integer.
+/// Note: this function assumes that `min_value <= 0` and `max_value > 0` and that the range of
+/// possible values is smaller than 2**128.
+pub fn build_sint_bounded_operation(
+    builder: CompiledInvocationBuilder<'_>,
+    min_value: i128,
+    max_value: i128,
+    op: IntOperator,
+    handling: SintOverflowHandling,
+) -> Result<CompiledInvocation, InvocationError> {
+    assert!(min_value <= 0, "min_value must be non-positive");
+    assert!(max_value > 0, "max_value must be positive");
+    let is_full_range = min_value == i128::MIN && max_value == i128::MAX;
+    let [range_check, lhs, rhs] = builder.try_get_single_cells()?;
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(2) range_check;
+        deref lhs;
+        deref rhs;
+    };
+    casm_build_extend! {casm_builder,
+        let orig_range_check = range_check;
+        // Allocating the vars shared by all the paths in the beginning, for AP-Alignment between
+        // them.
+        tempvar value;
+        tempvar is_in_range;
+        tempvar is_above;
+        tempvar result;
+    }
+    match op {
+        IntOperator::OverflowingAdd => {
+            casm_build_extend! {casm_builder, assert value = lhs + rhs;};
+        }
+        IntOperator::OverflowingSub => {
+            casm_build_extend! {casm_builder, assert value = lhs - rhs;};
+        }
+    }
+    casm_build_extend! {casm_builder,
+        const positive_range_fixer = -BigInt::from(min_value);
+        const range_size = BigInt::from(max_value) - BigInt::from(min_value) + BigInt::from(1);
+        // Shift the valid range to [0, range_size).
+        let canonical_value = value + positive_range_fixer;
+        hint TestLessThan {lhs: canonical_value, rhs: range_size} into {dst: is_in_range};
+        jump IsInRange if is_in_range != 0;
+        // See `build_sint_overflowing_operation` for the bound.
+        const above_bound = BigInt::from(u128::MAX) + BigInt::from(1);
+        hint TestLessThan {lhs: value, rhs: above_bound} into {dst: is_above};
+        jump IsAbove if is_above != 0;
+        // Below range case.
+        // value + 2**128 - min_value < 2**128 ==> value < min_value
+        const min_value_fixer =
+            BigInt::from(u128::MAX) + BigInt::from(1) - BigInt::from(min_value);
+        tempvar rc_val = value + min_value_fixer;
+        assert rc_val = *(range_check++);
+    }
+    if !is_full_range {
+        // Smaller types require two range checks in range, so the value is bounded from the other
+        // side as well, for all the paths to use the same number of range checks.
+        casm_build_extend! {casm_builder,
+            // value - 2 * min_value >= 0 ==> value >= 2 * min_value
+            const lower_bound = BigInt::from(min_value) * BigInt::from(2);
+            tempvar rc_val = value - lower_bound;
+            assert rc_val = *(range_check++);
+        };
+    }
+    match handling {
+        SintOverflowHandling::Saturating => {
+            casm_build_extend! {casm_builder,
+                const min_value_imm = BigInt::from(min_value);
+                assert result = min_value_imm;
+            };
+        }
+        SintOverflowHandling::Wrapping => {
+            casm_build_extend! {casm_builder,
+                assert result = value + range_size;
+            };
+        }
+    }
+    casm_build_extend! {casm_builder,
+        jump Done;
+    IsAbove:
+        // value - (max_value + 1) >= 0 ==> value > max_value
+        const max_value_plus_one = BigInt::from(max_value) + BigInt::from(1);
+        tempvar rc_val = value - max_value_plus_one;
+        assert rc_val = *(range_check++);
+    }
+    if !is_full_range {
+        casm_build_extend! {casm_builder,
+            // value + 2**128 - 1 - (max_value - min_value) < 2**128
+            // ==> value <= max_value - min_value
+            const upper_bound_fixer = BigInt::from(u128::MAX) - BigInt::from(max_value)
+                + BigInt::from(min_value);
+            tempvar rc_val = value + upper_bound_fixer;
+            assert rc_val = *(range_check++);
+        };
+    }
+    match handling {
+        SintOverflowHandling::Saturating => {
+            casm_build_extend! {casm_builder,
+                const max_value_imm = BigInt::from(max_value);
+                assert result = max_value_imm;
+            };
+        }
+        SintOverflowHandling::Wrapping => {
+            casm_build_extend! {casm_builder,
+                assert result = value - range_size;
+            };
+        }
+    }
+    casm_build_extend! {casm_builder,
+        jump Done;
+    IsInRange:
+        tempvar rc_val = canonical_value;
+        assert rc_val = *(range_check++);
+    }
+    // For i128, the previous range check already made sure the value is in range.
+    if !is_full_range {
+        casm_build_extend! {casm_builder,
+            // value + 2**128 - max_value - 1 < 2**128 ==> value <= max_value
+            const fixer_limit = BigInt::from(u128::MAX) - BigInt::from(max_value);
+            tempvar rc_val = value + fixer_limit;
+            assert rc_val = *(range_check++);
+        };
+    }
+    casm_build_extend! {casm_builder,
+        assert result = value;
+    Done:
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[range_check], &[result]], None)],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
 /// Builds instructions for Sierra i8/i16/i32/i64 operations.
 pub fn build_sint<
     TSintTraits: SintTraits + IntMulTraits + IsZeroTraits,
@@ -142,6 +281,13 @@ pub fn build_sint<
         SintConcrete::Operation(libfunc) => {
             build_sint_overflowing_operation(builder, MIN_VALUE, MAX_VALUE, libfunc.operator)
         }
+        SintConcrete::BoundedOperation(libfunc) => build_sint_bounded_operation(
+            builder,
+            MIN_VALUE,
+            MAX_VALUE,
+            libfunc.operator,
+            libfunc.handling,
+        ),
         SintConcrete::Diff(_) => {
             build_small_diff(builder, BigInt::from(MAX_VALUE) + 1 - BigInt::from(MIN_VALUE))
         }
@@ -1,6 +1,8 @@
 use cairo_lang_sierra::extensions::int::signed128::Sint128Concrete;
 
-use super::signed::{build_sint_from_felt252, build_sint_overflowing_operation};
+use super::signed::{
+    build_sint_bounded_operation, build_sint_from_felt252, build_sint_overflowing_operation,
+};
 use super::{
     build_128bit_diff, build_const, CompiledInvocation, CompiledInvocationBuilder, InvocationError,
 };
@@ -20,6 +22,13 @@ pub fn build(
         Sint128Concrete::Operation(libfunc) => {
             build_sint_overflowing_operation(builder, i128::MIN, i128::MAX, libfunc.operator)
         }
+        Sint128Concrete::BoundedOperation(libfunc) => build_sint_bounded_operation(
+            builder,
+            i128::MIN,
+            i128::MAX,
+            libfunc.operator,
+            libfunc.handling,
+        ),
         Sint128Concrete::Diff(_) => build_128bit_diff(builder),
     }
 }
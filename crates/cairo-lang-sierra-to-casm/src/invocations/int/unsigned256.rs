@@ -1,8 +1,9 @@
-use cairo_lang_casm::builder::CasmBuilder;
+use cairo_lang_casm::builder::{CasmBuilder, Var};
 use cairo_lang_casm::casm_build_extend;
 use cairo_lang_sierra::extensions::int::unsigned256::Uint256Concrete;
 use num_bigint::BigInt;
 
+use super::unsigned512::add_u512_divmod_by_u256;
 use crate::invocations::{
     add_input_variables, get_non_fallthrough_statement_id, CompiledInvocation,
     CompiledInvocationBuilder, CostValidationInfo, InvocationError,
@@ -18,6 +19,9 @@ pub fn build(
         Uint256Concrete::Divmod(_) => build_u256_divmod(builder),
         Uint256Concrete::SquareRoot(_) => build_u256_sqrt(builder),
         Uint256Concrete::InvModN(_) => build_u256_inv_mod_n(builder),
+        Uint256Concrete::GuaranteeWideMul(_) => build_u256_guarantee_wide_mul(builder),
+        Uint256Concrete::DivModU128(_) => build_u256_divmod_by_u128(builder),
+        Uint256Concrete::GuaranteeMulModN(_) => build_u256_guarantee_mul_mod_n(builder),
     }
 }
 
@@ -591,3 +595,259 @@ fn build_u256_inv_mod_n(
         },
     ))
 }
+
+/// Generates casm instructions for `u256_guarantee_wide_mul()`.
+fn build_u256_guarantee_wide_mul(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, a, b] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [a0, a1] = a.try_unpack()?;
+    let [b0, b1] = b.try_unpack()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(5) range_check;
+        deref a0;
+        deref a1;
+        deref b0;
+        deref b1;
+    };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    let (product, [g0, g1, g2, g3]) =
+        add_u256_wide_mul(&mut casm_builder, range_check, [a0, a1], [b0, b1]);
+
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[range_check], &product, &g0, &g1, &g2, &g3], None)],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Generates casm instructions for `u256_guarantee_mul_mod_n()`.
+fn build_u256_guarantee_mul_mod_n(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, a, b, n] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [a0, a1] = a.try_unpack()?;
+    let [b0, b1] = b.try_unpack()?;
+    let [n0, n1] = n.try_unpack()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(18) range_check;
+        deref a0;
+        deref a1;
+        deref b0;
+        deref b1;
+        deref n0;
+        deref n1;
+    };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    // The product is validated by the guarantees of its calculation, so it is a valid u512
+    // dividend.
+    let (product, [g0, g1, g2, g3]) =
+        add_u256_wide_mul(&mut casm_builder, range_check, [a0, a1], [b0, b1]);
+    let (_, remainder, [g4, g5, g6, g7, g8]) =
+        add_u512_divmod_by_u256(&mut casm_builder, range_check, product, [n0, n1]);
+
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [(
+            "Fallthrough",
+            &[&[range_check], &remainder, &g0, &g1, &g2, &g3, &g4, &g5, &g6, &g7, &g8],
+            None,
+        )],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Adds instructions calculating the full 512-bit product of the u256 values `a` and `b`, given as
+/// their 128-bit limbs.
+/// Returns the limbs of the product, along with the `U128MulGuarantee`s of the products of the
+/// limbs, required for validating the calculation.
+pub(crate) fn add_u256_wide_mul(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    [a0, a1]: [Var; 2],
+    [b0, b1]: [Var; 2],
+) -> ([Var; 4], [[Var; 4]; 4]) {
+    casm_build_extend! {casm_builder,
+        const u128_bound_minus_3 = u128::MAX - 2;
+        const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+
+        // The limbs of the products, validated by the returned guarantees.
+        tempvar a0b0_low;
+        tempvar a0b0_high;
+        hint WideMul128 { lhs: a0, rhs: b0 } into { low: a0b0_low, high: a0b0_high };
+        tempvar a0b1_low;
+        tempvar a0b1_high;
+        hint WideMul128 { lhs: a0, rhs: b1 } into { low: a0b1_low, high: a0b1_high };
+        tempvar a1b0_low;
+        tempvar a1b0_high;
+        hint WideMul128 { lhs: a1, rhs: b0 } into { low: a1b0_low, high: a1b0_high };
+        tempvar a1b1_low;
+        tempvar a1b1_high;
+        hint WideMul128 { lhs: a1, rhs: b1 } into { low: a1b1_low, high: a1b1_high };
+
+        // Calculate limb1.
+        // Its sum is of 3 u128 values, so its carry is in range [0, 2].
+        tempvar part0 = a0b0_high + a0b1_low;
+        tempvar part1 = part0 + a1b0_low;
+        tempvar carry1;
+        tempvar limb1;
+        hint DivMod { lhs: part1, rhs: u128_limit } into { quotient: carry1, remainder: limb1 };
+        assert limb1 = *(range_check++);
+        assert carry1 = *(range_check++);
+        tempvar a = carry1 + u128_bound_minus_3;
+        assert a = *(range_check++);
+        tempvar carry1_shifted = carry1 * u128_limit;
+        assert part1 = carry1_shifted + limb1;
+        // Calculate limb2.
+        // Its sum is at most `2 + 3 * u128::MAX`, so its carry is in range [0, 2] as well.
+        tempvar part0 = carry1 + a0b1_high;
+        tempvar part1 = part0 + a1b0_high;
+        tempvar part2 = part1 + a1b1_low;
+        tempvar carry2;
+        tempvar limb2;
+        hint DivMod { lhs: part2, rhs: u128_limit } into { quotient: carry2, remainder: limb2 };
+        assert limb2 = *(range_check++);
+        assert carry2 = *(range_check++);
+        tempvar a = carry2 + u128_bound_minus_3;
+        assert a = *(range_check++);
+        tempvar carry2_shifted = carry2 * u128_limit;
+        assert part2 = carry2_shifted + limb2;
+        // Calculate limb3.
+        // The product is smaller than 2**512, so limb3 is in range as well.
+        tempvar limb3 = carry2 + a1b1_high;
+        // Allocate the remaining memory cells used by the hints.
+        ap += 4;
+    };
+
+    (
+        [a0b0_low, limb1, limb2, limb3],
+        [
+            [a0, b0, a0b0_high, a0b0_low],
+            [a0, b1, a0b1_high, a0b1_low],
+            [a1, b0, a1b0_high, a1b0_low],
+            [a1, b1, a1b1_high, a1b1_low],
+        ],
+    )
+}
+
+/// Generates casm instructions for `u256_safe_divmod_by_u128()`.
+fn build_u256_divmod_by_u128(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, dividend, divisor] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [dividend0, dividend1] = dividend.try_unpack()?;
+    let [divisor] = divisor.try_unpack()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(4) range_check;
+        deref dividend0;
+        deref dividend1;
+        deref divisor;
+    };
+
+    casm_build_extend! {casm_builder,
+        const zero = 0;
+        const one = 1;
+        const u128_bound_minus_u64_bound = u128::MAX - u64::MAX as u128;
+        const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+        let orig_range_check = range_check;
+
+        // Quotient 128-bit limbs.
+        tempvar quotient0;
+        tempvar quotient1;
+        tempvar remainder;
+        // The high limb of the remainder, which is always 0 for a 128-bit divisor.
+        tempvar remainder1;
+
+        // Divide in a hint.
+        hint Uint256DivMod {
+            dividend0: dividend0,
+            dividend1: dividend1,
+            divisor0: divisor,
+            divisor1: zero
+        } into {
+            quotient0: quotient0,
+            quotient1: quotient1,
+            remainder0: remainder,
+            remainder1: remainder1
+        };
+
+        // Verify the hint ranges.
+        assert quotient0 = *(range_check++);
+        assert quotient1 = *(range_check++);
+        assert remainder = *(range_check++);
+
+        // Assert remainder is less than divisor.
+        tempvar diff = divisor - remainder;
+        tempvar diff_min_1 = diff - one;
+        assert diff_min_1 = *(range_check++);
+
+        tempvar q0d_low;
+        tempvar q0d_high;
+        hint WideMul128 { lhs: quotient0, rhs: divisor } into { low: q0d_low, high: q0d_high };
+
+        // Validating `quotient * divisor + remainder - dividend = 0`.
+        // Validate limb0.
+        tempvar part0 = q0d_low + remainder;
+        tempvar part1 = part0 - dividend0;
+        // leftover is in range:
+        // [(0 * 2 - u128::MAX) / u128_limit, (u128::MAX * 2 - 0) / u128_limit] ==> [0, 1].
+        tempvar leftover = part1 / u128_limit;
+        assert leftover = leftover * leftover;
+        // Validate limb1.
+        // We know that quotient1 * divisor should be smaller than 2**128.
+        // Therefore the smaller of the two must be smaller than 2**64.
+        // So by checking this we can avoid wraparound on the prime.
+        tempvar qd1_small;
+        tempvar qd1_large;
+        tempvar quotient1_less_than_divisor;
+        hint TestLessThan { lhs: quotient1, rhs: divisor } into { dst: quotient1_less_than_divisor };
+        jump QUOTIENT1_LESS_THAN_DIVISOR if quotient1_less_than_divisor != 0;
+        assert qd1_small = divisor;
+        assert qd1_large = quotient1;
+        jump MERGE;
+    QUOTIENT1_LESS_THAN_DIVISOR:
+        assert qd1_small = quotient1;
+        assert qd1_large = divisor;
+        // Align the branches.
+        ap += 1;
+    MERGE:
+        tempvar qd1_small_fixed = qd1_small + u128_bound_minus_u64_bound;
+        assert qd1_small_fixed = *(range_check++);
+        tempvar qd1 = qd1_small * qd1_large;
+        tempvar part0 = leftover + q0d_high;
+        assert dividend1 = part0 + qd1;
+    };
+
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [(
+            "Fallthrough",
+            &[
+                &[range_check],
+                &[quotient0, quotient1],
+                &[remainder],
+                &[quotient0, divisor, q0d_high, q0d_low],
+            ],
+            None,
+        )],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
@@ -1,11 +1,12 @@
-use cairo_lang_casm::builder::CasmBuilder;
+use cairo_lang_casm::builder::{CasmBuilder, Var};
 use cairo_lang_casm::casm_build_extend;
 use cairo_lang_sierra::extensions::int::unsigned512::Uint512Concrete;
+use cairo_lang_sierra::extensions::int::IntOperator;
 use num_bigint::BigInt;
 
 use crate::invocations::{
-    add_input_variables, CompiledInvocation, CompiledInvocationBuilder, CostValidationInfo,
-    InvocationError,
+    add_input_variables, get_non_fallthrough_statement_id, CompiledInvocation,
+    CompiledInvocationBuilder, CostValidationInfo, InvocationError,
 };
 
 /// Builds instructions for Sierra u512 operations.
@@ -15,6 +16,7 @@ pub fn build(
 ) -> Result<CompiledInvocation, InvocationError> {
     match libfunc {
         Uint512Concrete::DivModU256(_) => build_u512_safe_divmod_by_u256(builder),
+        Uint512Concrete::Operation(libfunc) => build_u512_overflowing_op(builder, libfunc.operator),
     }
 }
 
@@ -38,13 +40,41 @@ fn build_u512_safe_divmod_by_u256(
         deref divisor1;
     };
 
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    let (quotient, remainder, [g0, g1, g2, g3, g4]) = add_u512_divmod_by_u256(
+        &mut casm_builder,
+        range_check,
+        [dividend0, dividend1, dividend2, dividend3],
+        [divisor0, divisor1],
+    );
+
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[range_check], &quotient, &remainder, &g0, &g1, &g2, &g3, &g4], None)],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Adds instructions calculating the division with remainder of a u512 `dividend` by a non-zero
+/// u256 `divisor`, given as their 128-bit limbs.
+/// Returns the limbs of the quotient and of the remainder, along with the `U128MulGuarantee`s of
+/// the products of the limbs of the quotient and the divisor, required for validating the
+/// calculation.
+pub(crate) fn add_u512_divmod_by_u256(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    [dividend0, dividend1, dividend2, dividend3]: [Var; 4],
+    [divisor0, divisor1]: [Var; 2],
+) -> ([Var; 4], [Var; 2], [[Var; 4]; 5]) {
     casm_build_extend! {casm_builder,
         const zero = 0;
         const one = 1;
         const u128_bound_minus_4 = u128::MAX - 3;
         const u128_bound_minus_u64_bound = u128::MAX - u64::MAX as u128;
         const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
-        let orig_range_check = range_check;
 
         // Quotient 128-bit limbs.
         tempvar quotient0;
@@ -192,25 +222,128 @@ fn build_u512_safe_divmod_by_u256(
         assert dividend3 = part1 + qd3;
     };
 
+    (
+        [quotient0, quotient1, quotient2, quotient3],
+        [remainder0, remainder1],
+        [
+            [quotient0, divisor0, q0d0_high, q0d0_low],
+            [quotient0, divisor1, q0d1_high, q0d1_low],
+            [quotient1, divisor0, q1d0_high, q1d0_low],
+            [quotient1, divisor1, q1d1_high, q1d1_low],
+            [quotient2, divisor0, q2d0_high, q2d0_low],
+        ],
+    )
+}
+
+/// Generates casm instructions for `u512_overflowing_add()` and `u512_overflowing_sub()`.
+fn build_u512_overflowing_op(
+    builder: CompiledInvocationBuilder<'_>,
+    operator: IntOperator,
+) -> Result<CompiledInvocation, InvocationError> {
+    let failure_handle_statement_id = get_non_fallthrough_statement_id(&builder);
+    let [range_check, a, b] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [a0, a1, a2, a3] = a.try_unpack()?;
+    let [b0, b1, b2, b3] = b.try_unpack()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(3) range_check;
+        deref a0;
+        deref a1;
+        deref a2;
+        deref a3;
+        deref b0;
+        deref b1;
+        deref b2;
+        deref b3;
+    };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    let build_limb = match operator {
+        IntOperator::OverflowingAdd => add_limb_with_carry,
+        IntOperator::OverflowingSub => sub_limb_with_borrow,
+    };
+    let (res0, carry) = build_limb(&mut casm_builder, range_check, a0, b0, None);
+    let (res1, carry) = build_limb(&mut casm_builder, range_check, a1, b1, Some(carry));
+    let (res2, carry) = build_limb(&mut casm_builder, range_check, a2, b2, Some(carry));
+    let (res3, carry) = build_limb(&mut casm_builder, range_check, a3, b3, Some(carry));
+    casm_build_extend!(casm_builder, jump Target if carry != 0;);
+
     Ok(builder.build_from_casm_builder(
         casm_builder,
-        [(
-            "Fallthrough",
-            &[
-                &[range_check],
-                &[quotient0, quotient1, quotient2, quotient3],
-                &[remainder0, remainder1],
-                &[quotient0, divisor0, q0d0_high, q0d0_low],
-                &[quotient0, divisor1, q0d1_high, q0d1_low],
-                &[quotient1, divisor0, q1d0_high, q1d0_low],
-                &[quotient1, divisor1, q1d1_high, q1d1_low],
-                &[quotient2, divisor0, q2d0_high, q2d0_low],
-            ],
-            None,
-        )],
+        [
+            ("Fallthrough", &[&[range_check], &[res0, res1, res2, res3]], None),
+            (
+                "Target",
+                &[&[range_check], &[res0, res1, res2, res3]],
+                Some(failure_handle_statement_id),
+            ),
+        ],
         CostValidationInfo {
             range_check_info: Some((orig_range_check, range_check)),
             extra_costs: None,
         },
     ))
 }
+
+/// Adds instructions calculating a limb of the sum of two u512 values, from the limbs `a` and `b`
+/// and the carry of the previous limb.
+/// Returns the limb of the result and its carry.
+fn add_limb_with_carry(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    a: Var,
+    b: Var,
+    prev_carry: Option<Var>,
+) -> (Var, Var) {
+    casm_build_extend!(casm_builder, tempvar sum = a + b;);
+    let sum = match prev_carry {
+        Some(prev_carry) => {
+            casm_build_extend!(casm_builder, tempvar sum_with_carry = sum + prev_carry;);
+            sum_with_carry
+        }
+        None => sum,
+    };
+    casm_build_extend! {casm_builder,
+        const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+        tempvar carry;
+        hint TestLessThanOrEqual { lhs: u128_limit, rhs: sum } into { dst: carry };
+        // The carry is a boolean, so the limb is in range only for the correct carry.
+        assert carry = carry * carry;
+        tempvar carry_shifted = carry * u128_limit;
+        tempvar limb = sum - carry_shifted;
+        assert limb = *(range_check++);
+    };
+    (limb, carry)
+}
+
+/// Adds instructions calculating a limb of the difference of two u512 values, from the limbs `a`
+/// and `b` and the borrow of the previous limb.
+/// Returns the limb of the result and its borrow.
+fn sub_limb_with_borrow(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    a: Var,
+    b: Var,
+    prev_borrow: Option<Var>,
+) -> (Var, Var) {
+    let subtrahend = match prev_borrow {
+        Some(prev_borrow) => {
+            casm_build_extend!(casm_builder, tempvar b_with_borrow = b + prev_borrow;);
+            b_with_borrow
+        }
+        None => b,
+    };
+    casm_build_extend! {casm_builder,
+        const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+        tempvar borrow;
+        hint TestLessThan { lhs: a, rhs: subtrahend } into { dst: borrow };
+        // The borrow is a boolean, so the limb is in range only for the correct borrow.
+        assert borrow = borrow * borrow;
+        tempvar diff = a - subtrahend;
+        tempvar borrow_shifted = borrow * u128_limit;
+        tempvar limb = diff + borrow_shifted;
+        assert limb = *(range_check++);
+    };
+    (limb, borrow)
+}
@@ -34,6 +34,7 @@ use crate::relocations::{InstructionsWithRelocations, Relocation, RelocationEntr
 
 mod array;
 mod bitwise;
+mod blake;
 mod boolean;
 mod boxing;
 mod bytes31;
@@ -56,6 +57,8 @@ mod range_reduction;
 mod starknet;
 mod structure;
 
+#[cfg(test)]
+mod semantics_test;
 #[cfg(test)]
 mod test_utils;
 
@@ -90,6 +93,7 @@ pub enum InvocationError {
 
 /// Describes a simple change in the ap tracking itself.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ApTrackingChange {
     /// Enables the tracking if not already enabled.
     Enable,
@@ -102,6 +106,7 @@ pub enum ApTrackingChange {
 /// Describes the changes to the set of references at a single branch target, as well as changes to
 /// the environment.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct BranchChanges {
     /// New references defined at a given branch.
     /// should correspond to BranchInfo.results.
@@ -674,6 +679,7 @@ pub fn compile_invocation(
         Felt252Dict(libfunc) => felt252_dict::build_dict(libfunc, builder),
         Pedersen(libfunc) => pedersen::build(libfunc, builder),
         Poseidon(libfunc) => poseidon::build(libfunc, builder),
+        Blake2s(libfunc) => blake::build(libfunc, builder),
         StarkNet(libfunc) => starknet::build(libfunc, builder),
         Nullable(libfunc) => nullable::build(libfunc, builder),
         Debug(libfunc) => debug::build(libfunc, builder),
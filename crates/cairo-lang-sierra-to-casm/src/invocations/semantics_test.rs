@@ -0,0 +1,243 @@
+//! Semantics tests of the libfunc CASM builders.
+//!
+//! Each libfunc is compiled on its own, and its CASM is run over input states generated from the
+//! types of its parameters. The branch taken and the outputs are checked against the Sierra
+//! simulation of the libfunc, and the outputs, the change to ap and the usage of the range check
+//! builtin are checked against the libfunc's signature.
+
+use cairo_felt::Felt252;
+use cairo_lang_casm::ap_change::ApChange;
+use cairo_lang_sierra::extensions::ConcreteLibfunc;
+use cairo_lang_sierra::simulation::core::simulate;
+use cairo_lang_sierra::simulation::value::CoreValue;
+use itertools::{izip, zip_eq, Itertools};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+use test_case::test_case;
+
+use crate::invocations::test_utils::{
+    compile_libfunc, run_compiled_invocation, specialize_libfunc, CasmState,
+};
+use crate::ref_expr;
+
+/// The value of fp in the runs, where the arguments of the libfunc are placed.
+const FP: usize = 1000;
+/// The value of ap at the start of the runs.
+const AP: usize = 2000;
+/// The start of the range check segment passed to libfuncs using the range check builtin.
+const RANGE_CHECK_BASE: usize = 10000;
+/// The size of the range check segment; cells beyond it are not checked.
+const RANGE_CHECK_SIZE: usize = 1000;
+
+/// Returns the value of `2**bits - 1`.
+fn max_value(bits: u32) -> BigInt {
+    (BigInt::one() << bits) - 1
+}
+
+/// Returns the number of bits of the given unsigned integer type, or `None` if the type is not an
+/// unsigned integer type.
+fn uint_bits(ty: &str) -> Option<u32> {
+    match ty {
+        "u8" => Some(8),
+        "u16" => Some(16),
+        "u32" => Some(32),
+        "u64" => Some(64),
+        "u128" => Some(128),
+        _ => None,
+    }
+}
+
+/// Returns the logical value of a cell holding `value` of the given type, as the Sierra simulation
+/// represents it.
+fn core_value(ty: &str, value: BigInt) -> CoreValue {
+    match ty {
+        "felt252" => CoreValue::Felt252(value),
+        "u8" => CoreValue::Uint8(value.try_into().unwrap()),
+        "u16" => CoreValue::Uint16(value.try_into().unwrap()),
+        "u32" => CoreValue::Uint32(value.try_into().unwrap()),
+        "u64" => CoreValue::Uint64(value.try_into().unwrap()),
+        "u128" => CoreValue::Uint128(value.try_into().unwrap()),
+        _ => match ty.strip_prefix("NonZero<").and_then(|inner| inner.strip_suffix('>')) {
+            Some(inner) => CoreValue::NonZero(Box::new(core_value(inner, value))),
+            None => panic!("Unsupported type `{ty}`."),
+        },
+    }
+}
+
+/// Returns the value of a cell holding the given logical value, or `None` for values not held in
+/// a single cell.
+fn cell_value(value: &CoreValue) -> Option<BigInt> {
+    match value {
+        CoreValue::Felt252(value) => Some(value.clone()),
+        CoreValue::Uint8(value) => Some((*value).into()),
+        CoreValue::Uint16(value) => Some((*value).into()),
+        CoreValue::Uint32(value) => Some((*value).into()),
+        CoreValue::Uint64(value) => Some((*value).into()),
+        CoreValue::Uint128(value) => Some((*value).into()),
+        CoreValue::NonZero(value) => cell_value(value),
+        _ => None,
+    }
+}
+
+/// Returns the sample values of a cell of the given type, with the bounds of the range of the
+/// type, and a few values inside it.
+fn sample_values(ty: &str) -> Vec<BigInt> {
+    if let Some(inner) = ty.strip_prefix("NonZero<").and_then(|inner| inner.strip_suffix('>')) {
+        return sample_values(inner).into_iter().filter(|value| !value.is_zero()).collect();
+    }
+    let max = match ty {
+        "felt252" => BigInt::from(Felt252::prime()) - 1,
+        _ => max_value(uint_bits(ty).unwrap_or_else(|| panic!("Unsupported type `{ty}`."))),
+    };
+    vec![BigInt::zero(), BigInt::one(), BigInt::from(7), &max / 2, &max - 1, max]
+}
+
+/// Asserts that the value of an output cell is in the range of its type.
+fn assert_in_range(ty: &str, value: &BigInt) {
+    if let Some(inner) = ty.strip_prefix("NonZero<").and_then(|inner| inner.strip_suffix('>')) {
+        assert!(!value.is_zero(), "Zero output of type `{ty}`.");
+        return assert_in_range(inner, value);
+    }
+    if let Some(bits) = uint_bits(ty) {
+        assert!(*value <= max_value(bits), "Output {value} out of the range of `{ty}`.");
+    }
+}
+
+/// Checks the semantics of the CASM of the given libfunc over all the combinations of the sample
+/// values of its parameters, see the module docs.
+///
+/// Supports libfuncs whose parameters and outputs are all single cells.
+fn check_libfunc_semantics(libfunc_name: &str) {
+    let libfunc = specialize_libfunc(libfunc_name);
+    let param_types = libfunc.param_signatures().iter().map(|param| param.ty.clone()).collect_vec();
+    let refs =
+        (0..param_types.len()).map(|i| ref_expr!([fp + (i16::try_from(i).unwrap())])).collect_vec();
+    let compiled = compile_libfunc(libfunc_name, refs);
+
+    let param_samples = param_types
+        .iter()
+        .map(|ty| match ty.to_string().as_str() {
+            "RangeCheck" => vec![None],
+            ty => sample_values(ty).into_iter().map(Some).collect(),
+        })
+        .multi_cartesian_product();
+    for args in param_samples {
+        let mut state = CasmState { ap: AP, fp: FP, ..Default::default() };
+        let mut inputs = vec![];
+        for (i, (ty, arg)) in zip_eq(&param_types, &args).enumerate() {
+            let (cell, input) = match arg {
+                Some(value) => (Felt252::from(value), core_value(&ty.to_string(), value.clone())),
+                None => (Felt252::from(RANGE_CHECK_BASE), CoreValue::RangeCheck),
+            };
+            state.memory.insert(FP + i, cell);
+            inputs.push(input);
+        }
+        let context = format!("`{libfunc_name}` with the arguments {args:?}");
+
+        let (expected_outputs, expected_branch) =
+            simulate(&libfunc, inputs, || None, |_, _| unreachable!("No user functions."))
+                .unwrap_or_else(|err| panic!("Simulation of {context} failed: {err:?}."));
+        let (branch, state) = run_compiled_invocation(&compiled, libfunc.fallthrough(), state)
+            .unwrap_or_else(|err| panic!("Run of {context} failed: {err}"));
+        assert_eq!(branch, expected_branch, "Wrong branch taken by {context}.");
+
+        let branch_changes = &compiled.results[branch];
+        if let ApChange::Known(ap_change) = branch_changes.ap_change {
+            assert_eq!(state.ap - AP, ap_change, "Wrong ap change of {context}.");
+        }
+        let output_types = &libfunc.branch_signatures()[branch].vars;
+        assert_eq!(
+            branch_changes.refs.len(),
+            expected_outputs.len(),
+            "Wrong number of outputs of {context}."
+        );
+        for (output, var, expected) in izip!(&branch_changes.refs, output_types, &expected_outputs)
+        {
+            let [cell] = &output.cells[..] else {
+                panic!("Output of {context} is not a single cell.");
+            };
+            let value = state
+                .eval_cell_expression(cell)
+                .unwrap_or_else(|err| panic!("Output of {context} failed: {err}"));
+            match var.ty.to_string().as_str() {
+                "RangeCheck" => check_range_check_usage(&state, &value, &context),
+                ty => {
+                    let value = BigInt::from(value.to_biguint());
+                    assert_in_range(ty, &value);
+                    assert_eq!(Some(value), cell_value(expected), "Wrong output of {context}.");
+                }
+            }
+        }
+    }
+}
+
+/// Checks that all the range check cells used up to the given range check pointer are assigned
+/// with values in the range `[0, 2**128)`, and that no cells beyond it are used.
+fn check_range_check_usage(state: &CasmState, ptr: &Felt252, context: &str) {
+    let end = BigUint::from(RANGE_CHECK_BASE + RANGE_CHECK_SIZE);
+    assert!(
+        BigUint::from(RANGE_CHECK_BASE) <= ptr.to_biguint() && ptr.to_biguint() <= end,
+        "Invalid range check pointer output by {context}."
+    );
+    let ptr: usize = ptr.to_biguint().try_into().unwrap();
+    for address in RANGE_CHECK_BASE..RANGE_CHECK_BASE + RANGE_CHECK_SIZE {
+        match state.memory.get(&address) {
+            Some(value) => {
+                assert!(
+                    address < ptr,
+                    "Range check cell {address} used beyond {ptr} by {context}."
+                );
+                assert!(
+                    value.to_biguint() < BigUint::one() << 128,
+                    "Range check of {value} failed in {context}."
+                );
+            }
+            None => assert!(address >= ptr, "Range check cell {address} skipped by {context}."),
+        }
+    }
+}
+
+#[test_case("felt252_add")]
+#[test_case("felt252_sub")]
+#[test_case("felt252_mul")]
+#[test_case("felt252_div")]
+#[test_case("felt252_add_const<5>")]
+#[test_case("felt252_mul_const<-3>")]
+#[test_case("felt252_const<-1>")]
+#[test_case("felt252_is_zero")]
+#[test_case("store_temp<felt252>")]
+#[test_case("rename<felt252>")]
+#[test_case("dup<felt252>")]
+#[test_case("drop<felt252>")]
+#[test_case("unwrap_non_zero<felt252>")]
+fn test_felt252_semantics(libfunc: &str) {
+    check_libfunc_semantics(libfunc);
+}
+
+#[test_case("u8")]
+#[test_case("u16")]
+#[test_case("u32")]
+#[test_case("u64")]
+#[test_case("u128")]
+fn test_uint_semantics(ty: &str) {
+    for libfunc in [
+        "overflowing_add",
+        "overflowing_sub",
+        "eq",
+        "is_zero",
+        "safe_divmod",
+        "sqrt",
+        "to_felt252",
+        "try_from_felt252",
+        "const<5>",
+    ] {
+        // `u128_try_from_felt252` splits values out of range rather than failing.
+        if ty == "u128" && libfunc == "try_from_felt252" {
+            continue;
+        }
+        check_libfunc_semantics(&format!("{ty}_{libfunc}"));
+    }
+    if ty != "u128" {
+        check_libfunc_semantics(&format!("{ty}_wide_mul"));
+    }
+}
@@ -20,6 +20,9 @@ pub fn build(
             Secp256OpConcreteLibfunc::Mul(_) => {
                 build_syscalls(builder, "Secp256k1Mul", [1, 2], [1])
             }
+            Secp256OpConcreteLibfunc::MulAdd(_) => {
+                build_syscalls(builder, "Secp256k1MulAdd", [1, 2, 1, 2], [1])
+            }
             Secp256OpConcreteLibfunc::GetPointFromX(_) => {
                 build_syscalls(builder, "Secp256k1GetPointFromX", [2, 1], [2])
             }
@@ -37,6 +40,9 @@ pub fn build(
             Secp256OpConcreteLibfunc::Mul(_) => {
                 build_syscalls(builder, "Secp256r1Mul", [1, 2], [1])
             }
+            Secp256OpConcreteLibfunc::MulAdd(_) => {
+                build_syscalls(builder, "Secp256r1MulAdd", [1, 2, 1, 2], [1])
+            }
             Secp256OpConcreteLibfunc::GetPointFromX(_) => {
                 build_syscalls(builder, "Secp256r1GetPointFromX", [2, 1], [2])
             }
@@ -1,6 +1,17 @@
+use std::collections::HashMap;
+
+use cairo_felt::Felt252;
 use cairo_lang_casm::ap_change::ApChange;
-use cairo_lang_casm::instructions::Instruction;
-use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_casm::cell_expression::{CellExpression, CellOperator};
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, Hint};
+use cairo_lang_casm::instructions::{
+    AddApInstruction, AssertEqInstruction, Instruction, InstructionBody, JnzInstruction,
+    JumpInstruction,
+};
+use cairo_lang_casm::operand::{
+    BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand,
+};
+use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
 use cairo_lang_sierra::extensions::lib_func::{
     SignatureSpecializationContext, SpecializationContext,
 };
@@ -14,14 +25,17 @@ use cairo_lang_sierra::program::{BranchInfo, BranchTarget, Invocation, Statement
 use cairo_lang_sierra_ap_change::ap_change_info::ApChangeInfo;
 use cairo_lang_sierra_gas::gas_info::GasInfo;
 use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_utils::bigint::BigIntAsHex;
 use itertools::{zip_eq, Itertools};
+use num_bigint::BigUint;
+use num_traits::{ToPrimitive, Zero};
 
 use super::{compile_invocation, CompiledInvocation, ProgramInfo};
 use crate::environment::gas_wallet::GasWallet;
 use crate::environment::Environment;
 use crate::metadata::Metadata;
 use crate::references::{IntroductionPoint, ReferenceExpression, ReferenceValue};
-use crate::relocations::RelocationEntry;
+use crate::relocations::{Relocation, RelocationEntry};
 
 /// Creates a Felt252BinaryOperator from a token operator.
 #[macro_export]
@@ -217,6 +231,18 @@ impl std::fmt::Debug for ReducedCompiledInvocation {
     }
 }
 
+/// Specializes a libfunc given by its full name, e.g. `u8_overflowing_add` or `felt252_const<5>`,
+/// without a program registry.
+pub fn specialize_libfunc(libfunc: &str) -> CoreConcreteLibfunc {
+    let long_id = cairo_lang_sierra::ConcreteLibfuncLongIdParser::new().parse(libfunc).unwrap();
+    CoreLibfunc::specialize_by_id(
+        &MockSpecializationContext {},
+        &long_id.generic_id,
+        &long_id.generic_args,
+    )
+    .unwrap()
+}
+
 /// Compiles a libfunc into a [ReducedCompiledInvocation].
 /// the arguments are auto-filled according to the signature
 /// I.e. the libfunc is invoked by:
@@ -231,13 +257,7 @@ impl std::fmt::Debug for ReducedCompiledInvocation {
 ///
 /// Currently, only works if all the libfunc's types (both inputs and output) are of size 1.
 pub fn compile_libfunc(libfunc: &str, refs: Vec<ReferenceExpression>) -> ReducedCompiledInvocation {
-    let long_id = cairo_lang_sierra::ConcreteLibfuncLongIdParser::new()
-        .parse(libfunc.to_string().as_str())
-        .unwrap();
-    let context = MockSpecializationContext {};
-    let libfunc =
-        CoreLibfunc::specialize_by_id(&context, &long_id.generic_id, &long_id.generic_args)
-            .unwrap();
+    let libfunc = specialize_libfunc(libfunc);
 
     let mut type_sizes: TypeSizeMap = Default::default();
     for param in libfunc.param_signatures() {
@@ -305,3 +325,264 @@ pub fn compile_libfunc(libfunc: &str, refs: Vec<ReferenceExpression>) -> Reduced
         .expect("Failed to compile invocation."),
     )
 }
+
+/// The state of the memory and registers in a run of the CASM of a single compiled libfunc.
+#[derive(Clone, Debug, Default)]
+pub struct CasmState {
+    /// The assigned memory cells, by address.
+    pub memory: HashMap<usize, Felt252>,
+    pub ap: usize,
+    pub fp: usize,
+}
+impl CasmState {
+    /// Returns the address of the given cell.
+    fn address(&self, cell: CellRef) -> Result<usize, String> {
+        let base = match cell.register {
+            Register::AP => self.ap,
+            Register::FP => self.fp,
+        };
+        base.checked_add_signed(cell.offset.into()).ok_or_else(|| format!("Invalid cell {cell}."))
+    }
+
+    /// Returns the value of the given cell, or `None` if it is not assigned.
+    fn get(&self, cell: CellRef) -> Result<Option<Felt252>, String> {
+        Ok(self.memory.get(&self.address(cell)?).cloned())
+    }
+
+    /// Returns the value of the given cell, which must be assigned.
+    fn get_assigned(&self, cell: CellRef) -> Result<Felt252, String> {
+        self.get(cell)?.ok_or_else(|| format!("Read of the unassigned cell {cell}."))
+    }
+
+    /// Returns the address pointed to by the given cell.
+    fn get_pointer(&self, cell: CellRef, offset: i16) -> Result<usize, String> {
+        self.get_assigned(cell)?
+            .to_usize()
+            .and_then(|ptr| ptr.checked_add_signed(offset.into()))
+            .ok_or_else(|| format!("Invalid pointer in {cell}."))
+    }
+
+    /// Assigns a value to the given address, which must be unassigned or hold the same value.
+    fn assign(&mut self, address: usize, value: Felt252) -> Result<(), String> {
+        match self.memory.insert(address, value.clone()) {
+            Some(prev) if prev != value => {
+                Err(format!("Assertion failed at address {address}: {prev} != {value}."))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the value of the given operand, or `None` if one of its cells is not assigned.
+    fn try_eval(&self, operand: &ResOperand) -> Result<Option<Felt252>, String> {
+        Ok(match operand {
+            ResOperand::Deref(cell) => self.get(*cell)?,
+            ResOperand::DoubleDeref(cell, offset) => {
+                self.memory.get(&self.get_pointer(*cell, *offset)?).cloned()
+            }
+            ResOperand::Immediate(value) => Some(Felt252::from(&value.value)),
+            ResOperand::BinOp(BinOpOperand { op, a, b }) => {
+                let (Some(a), Some(b)) = (self.get(*a)?, self.try_eval(&b.clone().into())?) else {
+                    return Ok(None);
+                };
+                Some(match op {
+                    Operation::Add => a + b,
+                    Operation::Mul => a * b,
+                })
+            }
+        })
+    }
+
+    /// Returns the value of the given operand, whose cells must be assigned.
+    fn eval(&self, operand: &ResOperand) -> Result<Felt252, String> {
+        self.try_eval(operand)?.ok_or_else(|| format!("Read of unassigned cells in {operand}."))
+    }
+
+    /// Returns the value of the given cell expression at the current state, e.g. of the result of
+    /// a libfunc.
+    pub fn eval_cell_expression(&self, expression: &CellExpression) -> Result<Felt252, String> {
+        Ok(match expression {
+            CellExpression::Deref(cell) => self.get_assigned(*cell)?,
+            CellExpression::DoubleDeref(cell, offset) => self
+                .memory
+                .get(&self.get_pointer(*cell, *offset)?)
+                .cloned()
+                .ok_or_else(|| format!("Read of an unassigned cell through {cell}."))?,
+            CellExpression::Immediate(value) => Felt252::from(value),
+            CellExpression::BinOp { op, a, b } => {
+                let a = self.get_assigned(*a)?;
+                let b = self.eval(&b.clone().into())?;
+                match op {
+                    CellOperator::Add => a + b,
+                    CellOperator::Sub => a - b,
+                    CellOperator::Mul => a * b,
+                    CellOperator::Div => a / b,
+                }
+            }
+        })
+    }
+
+    /// Runs an `assert_eq` instruction, deducing the single unassigned cell of the instruction if
+    /// there is one, as done by the Cairo VM.
+    fn assert_eq(&mut self, dst: CellRef, res: &ResOperand) -> Result<(), String> {
+        let dst_address = self.address(dst)?;
+        let dst_value = self.memory.get(&dst_address).cloned();
+        match (dst_value, self.try_eval(res)?) {
+            (_, Some(value)) => self.assign(dst_address, value),
+            (Some(value), None) => match res {
+                ResOperand::Deref(cell) => self.assign(self.address(*cell)?, value),
+                ResOperand::DoubleDeref(cell, offset) => {
+                    self.assign(self.get_pointer(*cell, *offset)?, value)
+                }
+                ResOperand::BinOp(BinOpOperand { op, a, b }) => {
+                    let (unknown, known) = match (self.get(*a)?, b) {
+                        (None, _) => (*a, self.eval(&b.clone().into())?),
+                        (Some(a_value), DerefOrImmediate::Deref(b)) => (*b, a_value),
+                        (Some(_), DerefOrImmediate::Immediate(_)) => unreachable!(),
+                    };
+                    let deduced = match op {
+                        Operation::Add => value - known,
+                        Operation::Mul => value / known,
+                    };
+                    self.assign(self.address(unknown)?, deduced)
+                }
+                ResOperand::Immediate(_) => unreachable!("Immediates are always known."),
+            },
+            (None, None) => Err(format!("Cannot deduce the cells of `{dst} = {res}`.")),
+        }
+    }
+
+    /// Runs a hint, assigning its outputs.
+    fn run_hint(&mut self, hint: &Hint) -> Result<(), String> {
+        let as_u128 = |value: Felt252| {
+            value.to_u128().ok_or_else(|| format!("Hint input {value} is not a u128."))
+        };
+        let Hint::Core(CoreHintBase::Core(hint)) = hint else {
+            return Err(format!("Unsupported hint: {hint:?}."));
+        };
+        let outputs: Vec<(CellRef, BigUint)> = match hint {
+            CoreHint::TestLessThan { lhs, rhs, dst } => {
+                let (lhs, rhs) = (self.eval(lhs)?.to_biguint(), self.eval(rhs)?.to_biguint());
+                vec![(*dst, BigUint::from(lhs < rhs))]
+            }
+            CoreHint::TestLessThanOrEqual { lhs, rhs, dst } => {
+                let (lhs, rhs) = (self.eval(lhs)?.to_biguint(), self.eval(rhs)?.to_biguint());
+                vec![(*dst, BigUint::from(lhs <= rhs))]
+            }
+            CoreHint::DivMod { lhs, rhs, quotient, remainder } => {
+                let (lhs, rhs) = (self.eval(lhs)?.to_biguint(), self.eval(rhs)?.to_biguint());
+                vec![(*quotient, &lhs / &rhs), (*remainder, lhs % rhs)]
+            }
+            CoreHint::SquareRoot { value, dst } => {
+                vec![(*dst, self.eval(value)?.to_biguint().sqrt())]
+            }
+            CoreHint::WideMul128 { lhs, rhs, high, low } => {
+                let product = BigUint::from(as_u128(self.eval(lhs)?)?)
+                    * BigUint::from(as_u128(self.eval(rhs)?)?);
+                vec![(*high, &product >> 128), (*low, product % (BigUint::from(1u8) << 128))]
+            }
+            CoreHint::LinearSplit { value, scalar, max_x, x, y } => {
+                let value = self.eval(value)?.to_biguint();
+                let scalar = self.eval(scalar)?.to_biguint();
+                let max_x = self.eval(max_x)?.to_biguint();
+                let x_value = (&value / &scalar).min(max_x);
+                let y_value = value - &x_value * scalar;
+                vec![(*x, x_value), (*y, y_value)]
+            }
+            _ => return Err(format!("Unsupported hint: {hint:?}.")),
+        };
+        for (cell, value) in outputs {
+            // Hints may overwrite already assigned cells.
+            self.memory.insert(self.address(cell)?, Felt252::from(value));
+        }
+        Ok(())
+    }
+}
+
+/// Runs the CASM of a compiled libfunc from the given state, until it exits to one of its branches.
+/// Returns the index of the branch taken and the final state.
+///
+/// The jumps to the branches are the jumps with statement relocations, see [compile_libfunc], and
+/// running past the last instruction exits to the `fallthrough` branch.
+pub fn run_compiled_invocation(
+    compiled: &ReducedCompiledInvocation,
+    fallthrough: Option<usize>,
+    mut state: CasmState,
+) -> Result<(usize, CasmState), String> {
+    /// A bound on the number of steps, as the CASM of a libfunc should not loop.
+    const MAX_STEPS: usize = 10000;
+
+    let instructions = &compiled.instructions;
+    let branch_jumps: HashMap<usize, usize> = compiled
+        .relocations
+        .iter()
+        .filter_map(|entry| match entry.relocation {
+            Relocation::RelativeStatementId(StatementIdx(branch)) => {
+                Some((entry.instruction_idx, branch))
+            }
+            _ => None,
+        })
+        .collect();
+    // The code offset of each instruction, and of the end of the code.
+    let offsets: Vec<usize> = std::iter::once(0)
+        .chain(instructions.iter().scan(0, |offset, instruction| {
+            *offset += instruction.body.op_size();
+            Some(*offset)
+        }))
+        .collect();
+    let jump_target = |pc: usize, offset: &BigIntAsHex| -> Result<usize, String> {
+        offset
+            .value
+            .to_isize()
+            .and_then(|offset| offsets[pc].checked_add_signed(offset))
+            .and_then(|target| offsets.iter().position(|o| *o == target))
+            .ok_or_else(|| format!("Invalid jump offset {} at instruction {pc}.", offset.value))
+    };
+    let exit = |state: CasmState| {
+        let branch = fallthrough.ok_or("Ran past the end of the code with no fallthrough.")?;
+        Ok((branch, state))
+    };
+
+    let mut pc = 0;
+    for _ in 0..MAX_STEPS {
+        let Some(instruction) = instructions.get(pc) else {
+            return exit(state);
+        };
+        for hint in &instruction.hints {
+            state.run_hint(hint)?;
+        }
+        let mut next_pc = pc + 1;
+        match &instruction.body {
+            InstructionBody::AssertEq(AssertEqInstruction { a, b }) => state.assert_eq(*a, b)?,
+            InstructionBody::AddAp(AddApInstruction { operand }) => {
+                state.ap += state
+                    .eval(operand)?
+                    .to_usize()
+                    .ok_or_else(|| format!("Invalid ap change at instruction {pc}."))?;
+            }
+            InstructionBody::Jump(JumpInstruction {
+                target: DerefOrImmediate::Immediate(offset),
+                relative: true,
+            }) => match branch_jumps.get(&pc) {
+                Some(branch) => return Ok((*branch, state)),
+                None => next_pc = jump_target(pc, offset)?,
+            },
+            InstructionBody::Jnz(JnzInstruction {
+                jump_offset: DerefOrImmediate::Immediate(offset),
+                condition,
+            }) => {
+                if !state.get_assigned(*condition)?.is_zero() {
+                    match branch_jumps.get(&pc) {
+                        Some(branch) => return Ok((*branch, state)),
+                        None => next_pc = jump_target(pc, offset)?,
+                    }
+                }
+            }
+            body => return Err(format!("Unsupported instruction: `{body}`.")),
+        }
+        if instruction.inc_ap {
+            state.ap += 1;
+        }
+        pc = next_pc;
+    }
+    Err("Exceeded the maximal number of steps.".into())
+}
@@ -1,13 +1,20 @@
 //! CASM backend. Compiles from Sierra down to CASM. See [cairo_lang_sierra] and [cairo_lang_casm]
 
 pub mod annotations;
+pub mod branch_align;
 // TODO(ilya): Reduce the size of CompilationError.
 #[allow(clippy::result_large_err)]
 pub mod compiler;
+pub mod cost_overlay;
+pub mod entry_code;
 pub mod environment;
 pub mod invocations;
+pub mod libfunc_docs;
+pub mod link;
 pub mod metadata;
+pub mod peephole;
 pub mod references;
 pub mod relocations;
+pub mod segmentation;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
@@ -0,0 +1,402 @@
+//! A registry documenting the core libfuncs, generated from their implementations in the Sierra
+//! extensions, the gas cost model and the CASM compiler.
+//!
+//! Generic libfuncs are documented through an example specialization, e.g. `array_append` is
+//! documented as `array_append<felt252>`.
+
+use cairo_lang_casm::ap_change::ApChange;
+use cairo_lang_casm::cell_expression::CellExpression;
+use cairo_lang_casm::operand::{CellRef, Register};
+use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::extensions::lib_func::{
+    SierraApChange, SignatureSpecializationContext, SpecializationContext,
+};
+use cairo_lang_sierra::extensions::type_specialization_context::TypeSpecializationContext;
+use cairo_lang_sierra::extensions::types::TypeInfo;
+use cairo_lang_sierra::extensions::{ConcreteLibfunc, ConcreteType, GenericLibfunc, GenericTypeEx};
+use cairo_lang_sierra::ids::{
+    ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericLibfuncId, GenericTypeId, VarId,
+};
+use cairo_lang_sierra::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, ConcreteTypeLongId, Function,
+    FunctionSignature, GenericArg, Invocation, LibfuncDeclaration, Program, StatementIdx,
+    TypeDeclaration,
+};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra::ConcreteTypeLongIdParser;
+use cairo_lang_sierra_gas::core_libfunc_cost::{core_libfunc_cost, InvocationCostInfoProvider};
+use cairo_lang_sierra_type_size::{get_type_size_map, TypeSizeMap};
+use cairo_lang_utils::casts::IntoOrPanic;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use num_bigint::BigInt;
+
+use crate::environment::gas_wallet::GasWallet;
+use crate::environment::Environment;
+use crate::invocations::{compile_invocation, ProgramInfo};
+use crate::metadata::Metadata;
+use crate::references::{IntroductionPoint, ReferenceExpression, ReferenceValue};
+
+#[cfg(test)]
+#[path = "libfunc_docs_test.rs"]
+mod test;
+
+/// The documentation of a core libfunc.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LibfuncDoc {
+    /// The generic id of the libfunc, which is also the name of the corelib extern function
+    /// backed by it.
+    pub id: String,
+    /// The example specialization described by the rest of the documentation, or `None` if the
+    /// libfunc could not be specialized with any of the example generic arguments.
+    pub specialization: Option<String>,
+    /// A summary of the semantics of the libfunc.
+    pub summary: String,
+    /// The types of the parameters of the libfunc.
+    pub params: Vec<String>,
+    /// The branches of the libfunc.
+    pub branches: Vec<LibfuncBranchDoc>,
+    /// The CASM instructions emitted for the libfunc, before relocation, or `None` if it cannot
+    /// be compiled outside of a program.
+    pub casm: Option<Vec<String>>,
+}
+
+/// The documentation of a branch of a core libfunc.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LibfuncBranchDoc {
+    /// The types of the outputs of the branch.
+    pub outputs: Vec<String>,
+    /// Whether the branch continues to the following statement.
+    pub fallthrough: bool,
+    /// The change of `ap` in the branch.
+    pub ap_change: String,
+    /// The gas cost of the branch per cost token, or `None` if it depends on the program (e.g.
+    /// the cost of a called function).
+    pub gas_cost: Option<OrderedHashMap<String, i64>>,
+}
+
+impl LibfuncDoc {
+    /// Returns the documentation as markdown, e.g. for presenting it in an editor.
+    pub fn to_markdown(&self) -> String {
+        let mut res = format!("Libfunc `{}`", self.specialization.as_ref().unwrap_or(&self.id));
+        res.push_str(&format!("\n\n{}\n", self.summary));
+        for (idx, branch) in self.branches.iter().enumerate() {
+            let fallthrough = if branch.fallthrough { " (fallthrough)" } else { "" };
+            let gas_cost = match &branch.gas_cost {
+                Some(gas_cost) => {
+                    gas_cost.iter().map(|(token, cost)| format!("{token}: {cost}")).join(", ")
+                }
+                None => "program dependent".into(),
+            };
+            res.push_str(&format!(
+                "\n* Branch {idx}{fallthrough}: `({})`, ap change: {}, gas: {gas_cost}.",
+                branch.outputs.join(", "),
+                branch.ap_change
+            ));
+        }
+        if let Some(casm) = &self.casm {
+            res.push_str(&format!("\n\n```\n{}\n```", casm.join("\n")));
+        }
+        res
+    }
+}
+
+/// The generic arguments libfuncs are tried to be specialized with, in order, for documenting
+/// them.
+fn example_generic_args() -> [Vec<GenericArg>; 5] {
+    let ty = |name: &str| GenericArg::Type(ConcreteTypeId::from_string(name));
+    [
+        vec![],
+        vec![ty("felt252")],
+        vec![GenericArg::Value(BigInt::from(1))],
+        vec![ty("u8"), ty("u128")],
+        vec![ty("u128"), ty("u8")],
+    ]
+}
+
+/// Returns the documentation of all the core libfuncs.
+pub fn core_libfunc_docs() -> Vec<LibfuncDoc> {
+    CoreLibfunc::supported_ids().iter().map(document_libfunc).collect()
+}
+
+/// Returns the documentation of the core libfunc with the given generic id.
+pub fn core_libfunc_doc(id: &str) -> Option<LibfuncDoc> {
+    let id = GenericLibfuncId::from_string(id);
+    CoreLibfunc::by_id(&id).map(|_| document_libfunc(&id))
+}
+
+/// Documents the libfunc with the given generic id.
+fn document_libfunc(id: &GenericLibfuncId) -> LibfuncDoc {
+    let mut doc = LibfuncDoc {
+        id: id.to_string(),
+        specialization: None,
+        summary: "The libfunc is only usable with program specific generic arguments.".into(),
+        params: vec![],
+        branches: vec![],
+        casm: None,
+    };
+    let Some((program, long_id)) = example_args_program(id) else {
+        return doc;
+    };
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&program)
+        .expect("Example specialization failed.");
+    let type_sizes = get_type_size_map(&program, &registry).unwrap_or_default();
+    let libfunc = registry.get_libfunc(&program.libfunc_declarations[0].id).unwrap();
+    let type_name = |ty: &ConcreteTypeId| ty.to_string();
+    let compiled = compile_example(libfunc, &type_sizes);
+    let gas_costs = core_libfunc_cost(
+        &Default::default(),
+        &StatementIdx(0),
+        libfunc,
+        &ExampleCostInfoProvider { type_sizes: &type_sizes },
+    );
+
+    doc.specialization = Some(long_id.to_string());
+    doc.params = libfunc.param_signatures().iter().map(|param| type_name(&param.ty)).collect();
+    doc.branches = libfunc
+        .branch_signatures()
+        .iter()
+        .zip(gas_costs)
+        .enumerate()
+        .map(|(idx, (branch, gas_cost))| LibfuncBranchDoc {
+            outputs: branch.vars.iter().map(|var| type_name(&var.ty)).collect(),
+            fallthrough: libfunc.fallthrough() == Some(idx),
+            ap_change: match compiled.as_ref().map(|(_, ap_changes)| &ap_changes[idx]) {
+                Some(ApChange::Known(change)) => change.to_string(),
+                Some(ApChange::Unknown) => "unknown".into(),
+                None => match branch.ap_change {
+                    SierraApChange::Known { .. } => "known".into(),
+                    SierraApChange::Unknown => "unknown".into(),
+                    SierraApChange::BranchAlign => "aligning the branches".into(),
+                },
+            },
+            gas_cost: gas_cost.map(|gas_cost| {
+                gas_cost
+                    .into_iter()
+                    .filter(|(_, cost)| *cost != 0)
+                    .map(|(token, cost)| (token.name(), cost))
+                    .collect()
+            }),
+        })
+        .collect();
+    doc.summary = summary(doc.params.len(), &doc.branches);
+    doc.casm = compiled.map(|(instructions, _)| instructions);
+    doc
+}
+
+/// Returns a summary of the semantics of a libfunc with the given number of parameters and
+/// branches.
+fn summary(n_params: usize, branches: &[LibfuncBranchDoc]) -> String {
+    let plural = |n: usize, word: &str| format!("{n} {word}{}", if n == 1 { "" } else { "s" });
+    let takes = format!("Takes {}", plural(n_params, "argument"));
+    match branches {
+        [] => format!("{takes} and does not return."),
+        [branch] => format!("{takes} and returns {}.", plural(branch.outputs.len(), "value")),
+        _ => {
+            let mut res = format!("{takes} and continues to one of {} branches", branches.len());
+            if let Some(idx) = branches.iter().position(|branch| branch.fallthrough) {
+                res.push_str(&format!(", falling through to branch {idx}"));
+            }
+            res.push('.');
+            res
+        }
+    }
+}
+
+/// Returns a program declaring the first specialization of the libfunc with the given generic
+/// id with the example generic arguments, along with the types it uses, and the long id of the
+/// specialization.
+fn example_args_program(id: &GenericLibfuncId) -> Option<(Program, ConcreteLibfuncLongId)> {
+    let context = ExampleSpecializationContext {};
+    example_generic_args().into_iter().find_map(|generic_args| {
+        let libfunc = CoreLibfunc::by_id(id)?;
+        let signature = libfunc.specialize_signature(&context, &generic_args).ok()?;
+        // Make sure the libfunc can be fully specialized as well.
+        libfunc.specialize(&context, &generic_args).ok()?;
+        let mut types = OrderedHashMap::default();
+        for arg in &generic_args {
+            if let GenericArg::Type(ty) = arg {
+                add_type_with_args(&mut types, ty)?;
+            }
+        }
+        for ty in signature.param_signatures.iter().map(|param| &param.ty).chain(
+            signature.branch_signatures.iter().flat_map(|branch| &branch.vars).map(|var| &var.ty),
+        ) {
+            add_type_with_args(&mut types, ty)?;
+        }
+        let long_id = ConcreteLibfuncLongId { generic_id: id.clone(), generic_args };
+        let program = Program {
+            type_declarations: types
+                .into_iter()
+                .map(|(id, long_id)| TypeDeclaration { id, long_id, declared_type_info: None })
+                .collect(),
+            libfunc_declarations: vec![LibfuncDeclaration {
+                id: ConcreteLibfuncId::from_string(long_id.to_string()),
+                long_id: long_id.clone(),
+            }],
+            statements: vec![],
+            funcs: vec![],
+        };
+        Some((program, long_id))
+    })
+}
+
+/// Adds the type `ty` to `types`, after the types of its generic arguments.
+fn add_type_with_args(
+    types: &mut OrderedHashMap<ConcreteTypeId, ConcreteTypeLongId>,
+    ty: &ConcreteTypeId,
+) -> Option<()> {
+    if types.contains_key(ty) {
+        return Some(());
+    }
+    let long_id = ConcreteTypeLongIdParser::new().parse(&ty.to_string()).ok()?;
+    for arg in &long_id.generic_args {
+        if let GenericArg::Type(arg) = arg {
+            add_type_with_args(types, arg)?;
+        }
+    }
+    types.insert(ty.clone(), long_id);
+    Some(())
+}
+
+/// Compiles the invocation of `libfunc` with its arguments on the stack, as the arguments of a
+/// function. Returns the emitted instructions and the ap change of each branch.
+fn compile_example(
+    libfunc: &CoreConcreteLibfunc,
+    type_sizes: &TypeSizeMap,
+) -> Option<(Vec<String>, Vec<ApChange>)> {
+    let sizes: Vec<i16> = libfunc
+        .param_signatures()
+        .iter()
+        .map(|param| type_sizes.get(&param.ty).copied())
+        .collect::<Option<_>>()?;
+    let mut offset = -3 - sizes.iter().sum::<i16>();
+    let refs: Vec<ReferenceValue> = libfunc
+        .param_signatures()
+        .iter()
+        .zip(sizes)
+        .map(|(param, size)| {
+            let expression = ReferenceExpression {
+                cells: (offset..offset + size)
+                    .map(|offset| CellExpression::Deref(CellRef { register: Register::FP, offset }))
+                    .collect(),
+            };
+            offset += size;
+            ReferenceValue {
+                expression,
+                ty: param.ty.clone(),
+                stack_idx: None,
+                introduction_point: IntroductionPoint {
+                    source_statement_idx: None,
+                    destination_statement_idx: StatementIdx(0),
+                    output_idx: 0,
+                },
+            }
+        })
+        .collect();
+    let invocation = Invocation {
+        libfunc_id: "".into(),
+        args: (0..refs.len().into_or_panic()).map(VarId::new).collect(),
+        branches: libfunc
+            .branch_signatures()
+            .iter()
+            .enumerate()
+            .map(|(idx, branch)| BranchInfo {
+                target: if libfunc.fallthrough() == Some(idx) {
+                    BranchTarget::Fallthrough
+                } else {
+                    BranchTarget::Statement(StatementIdx(idx + 1))
+                },
+                results: (0..branch.vars.len().into_or_panic()).map(VarId::new).collect(),
+            })
+            .collect(),
+    };
+    let compiled = compile_invocation(
+        ProgramInfo {
+            metadata: &Metadata::default(),
+            type_sizes,
+            // The example generic arguments never include const types.
+            const_data_values: &|_| vec![],
+        },
+        &invocation,
+        libfunc,
+        StatementIdx(0),
+        &refs,
+        Environment::new(GasWallet::Disabled),
+    )
+    .ok()?;
+    Some((
+        compiled.instructions.iter().map(|instruction| format!("{instruction};")).collect(),
+        compiled.results.into_iter().map(|branch| branch.ap_change).collect(),
+    ))
+}
+
+/// Provides the sizes of the types of the example specializations for computing gas costs.
+struct ExampleCostInfoProvider<'a> {
+    type_sizes: &'a TypeSizeMap,
+}
+impl InvocationCostInfoProvider for ExampleCostInfoProvider<'_> {
+    fn type_size(&self, ty: &ConcreteTypeId) -> usize {
+        self.type_sizes[ty].into_or_panic()
+    }
+
+    fn token_usages(&self, _token_type: CostTokenType) -> usize {
+        0
+    }
+
+    fn ap_change_var_value(&self) -> usize {
+        0
+    }
+}
+
+/// Specialization context for the example specializations, naming the concrete types by their
+/// long ids.
+struct ExampleSpecializationContext {}
+impl TypeSpecializationContext for ExampleSpecializationContext {
+    fn try_get_type_info(&self, id: ConcreteTypeId) -> Option<TypeInfo> {
+        let long_id = ConcreteTypeLongIdParser::new().parse(&id.to_string()).ok()?;
+        Some(
+            CoreType::specialize_by_id(self, &long_id.generic_id, &long_id.generic_args)
+                .ok()?
+                .info()
+                .clone(),
+        )
+    }
+}
+impl SignatureSpecializationContext for ExampleSpecializationContext {
+    fn try_get_concrete_type(
+        &self,
+        id: GenericTypeId,
+        generic_args: &[GenericArg],
+    ) -> Option<ConcreteTypeId> {
+        Some(if generic_args.is_empty() {
+            id.to_string().into()
+        } else {
+            format!("{id}<{}>", generic_args.iter().join(", ")).into()
+        })
+    }
+
+    fn try_get_function_signature(&self, _function_id: &FunctionId) -> Option<FunctionSignature> {
+        None
+    }
+
+    fn try_get_function_ap_change(&self, _function_id: &FunctionId) -> Option<SierraApChange> {
+        None
+    }
+
+    fn as_type_specialization_context(&self) -> &dyn TypeSpecializationContext {
+        self
+    }
+}
+impl SpecializationContext for ExampleSpecializationContext {
+    fn upcast(&self) -> &dyn SignatureSpecializationContext {
+        self
+    }
+
+    fn try_get_function(&self, _function_id: &FunctionId) -> Option<Function> {
+        None
+    }
+}
@@ -0,0 +1,58 @@
+use cairo_lang_sierra::extensions::core::CoreLibfunc;
+use cairo_lang_sierra::extensions::GenericLibfunc;
+use indoc::indoc;
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use super::{core_libfunc_doc, core_libfunc_docs};
+
+#[test]
+fn all_libfuncs_documented() {
+    let docs = core_libfunc_docs();
+    assert_eq!(
+        docs.iter().map(|doc| doc.id.clone()).collect_vec(),
+        CoreLibfunc::supported_ids().iter().map(|id| id.to_string()).collect_vec()
+    );
+    // Libfuncs requiring program specific generic arguments are documented by their id only.
+    let struct_construct = docs.iter().find(|doc| doc.id == "struct_construct").unwrap();
+    assert_eq!(struct_construct.specialization, None);
+    assert!(struct_construct.branches.is_empty());
+}
+
+#[test]
+fn libfunc_doc() {
+    assert_eq!(core_libfunc_doc("not_a_libfunc"), None);
+    assert_eq!(
+        core_libfunc_doc("u128_overflowing_add").unwrap().to_markdown(),
+        indoc! {"
+            Libfunc `u128_overflowing_add`
+
+            Takes 3 arguments and continues to one of 2 branches, falling through to branch 0.
+
+            * Branch 0 (fallthrough): `(RangeCheck, u128)`, ap change: 2, gas: const: 370.
+            * Branch 1: `(RangeCheck, u128)`, ap change: 3, gas: const: 570.
+
+            ```
+            [ap + 1] = [fp + -5] + [fp + -4], ap++;
+            %{ memory[ap + -1] = memory[ap + 0] < 340282366920938463463374607431768211456 %}
+            jmp rel 7 if [ap + -1] != 0, ap++;
+            [ap + -1] = [ap + 0] + 340282366920938463463374607431768211456, ap++;
+            [ap + -1] = [[fp + -6] + 0];
+            jmp rel 0;
+            [ap + -1] = [[fp + -6] + 0];
+            ```"}
+    );
+    assert_eq!(
+        core_libfunc_doc("array_append").unwrap().to_markdown(),
+        indoc! {"
+            Libfunc `array_append<felt252>`
+
+            Takes 2 arguments and returns 1 value.
+
+            * Branch 0 (fallthrough): `(Array<felt252>)`, ap change: 0, gas: const: 100.
+
+            ```
+            [fp + -4] = [[fp + -5] + 0];
+            ```"}
+    );
+}
@@ -0,0 +1,189 @@
+//! Linking of separately compiled CASM programs into a single program.
+
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::{Program, StatementIdx};
+use cairo_lang_utils::casts::IntoOrPanic;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::chain;
+use thiserror::Error;
+
+use crate::compiler::{
+    function_statement_ranges, CairoProgram, CairoProgramDebugInfo, ConstSegment, ConstsInfo,
+    SierraStatementDebugInfo,
+};
+use crate::relocations::{relocate_instructions, Relocation, RelocationEntry};
+
+#[cfg(test)]
+#[path = "link_test.rs"]
+mod test;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum LinkError {
+    #[error("Unit #{0} was not compiled from the given Sierra program.")]
+    ProgramMismatch(usize),
+    #[error(
+        "Unit #{unit_idx}: Jump into statement #{statement_idx} of a function defined in another \
+         unit."
+    )]
+    JumpIntoLinkedFunction { unit_idx: usize, statement_idx: StatementIdx },
+}
+
+/// A separately compiled unit to link.
+#[derive(Clone, Copy, Debug)]
+pub struct LinkUnit<'a> {
+    /// The Sierra program the unit was compiled from.
+    pub program: &'a Program,
+    /// The compiled program of the unit.
+    pub cairo_program: &'a CairoProgram,
+}
+
+/// Links the compiled `units` into a single program.
+///
+/// The statements of the linked program are the statements of the units, in order. A function
+/// defined in several units is linked to its definition in the first of them: the calls to the
+/// function from the other units are redirected to that definition, and the code of their own
+/// definitions is dropped, keeping their statements in the debug info without any instructions.
+/// The const segments of the units are merged, sharing the identical segments.
+pub fn link(units: &[LinkUnit<'_>]) -> Result<CairoProgram, LinkError> {
+    // The entry point in the linked program of the first definition of each function.
+    let mut function_entry_points = UnorderedHashMap::<FunctionId, StatementIdx>::default();
+    // The index of the first statement of each unit in the linked program.
+    let mut statement_bases = vec![];
+    let mut n_statements = 0;
+    for (unit_idx, unit) in units.iter().enumerate() {
+        if unit.cairo_program.debug_info.sierra_statement_info.len()
+            != unit.program.statements.len()
+        {
+            return Err(LinkError::ProgramMismatch(unit_idx));
+        }
+        statement_bases.push(n_statements);
+        for func in &unit.program.funcs {
+            function_entry_points
+                .entry(func.id.clone())
+                .or_insert(StatementIdx(n_statements + func.entry_point.0));
+        }
+        n_statements += unit.program.statements.len();
+    }
+
+    let (consts_info, segment_maps) = merge_consts(units);
+
+    let mut instructions = vec![];
+    let mut relocations: Vec<RelocationEntry> = vec![];
+    let mut sierra_statement_info: Vec<SierraStatementDebugInfo> = Vec::with_capacity(n_statements);
+    let mut code_offset = 0;
+    for (unit_idx, unit) in units.iter().enumerate() {
+        let base = statement_bases[unit_idx];
+        let statement_infos = &unit.cairo_program.debug_info.sierra_statement_info;
+        // The statements of the functions defined in an earlier unit, and the entry points of these
+        // functions in the linked program by their entry points in the unit.
+        let mut dropped = vec![false; statement_infos.len()];
+        let mut redirects = UnorderedHashMap::<StatementIdx, StatementIdx>::default();
+        for (function_id, statements) in function_statement_ranges(unit.program).unwrap_or_default()
+        {
+            let entry_point = function_entry_points[&function_id];
+            if entry_point.0 != base + statements.start {
+                redirects.insert(StatementIdx(statements.start), entry_point);
+                dropped[statements].fill(true);
+            }
+        }
+
+        let unit_instructions = unit.cairo_program.unrelocated_instructions();
+        let mut unit_relocations = unit.cairo_program.relocations.iter().peekable();
+        let instruction_ranges = chain!(
+            statement_infos.iter().map(|info| info.instruction_idx),
+            [unit_instructions.len()]
+        )
+        .scan(0, |start, end| Some(std::mem::replace(start, end)..end));
+        // The instructions preceding the first statement (e.g. an entry header) are attributed to
+        // no statement.
+        let statements = chain!([None], (0..statement_infos.len()).map(Some));
+        for (statement_id, instruction_range) in statements.zip(instruction_ranges) {
+            let is_dropped = statement_id.is_some_and(|statement_id| dropped[statement_id]);
+            let start_offset = code_offset;
+            let instruction_idx = instructions.len();
+            for unit_instruction_idx in instruction_range {
+                let entry =
+                    unit_relocations.next_if(|entry| entry.instruction_idx == unit_instruction_idx);
+                if is_dropped {
+                    continue;
+                }
+                if let Some(entry) = entry {
+                    let relocation = match &entry.relocation {
+                        Relocation::RelativeStatementId(target) => {
+                            Relocation::RelativeStatementId(if dropped[target.0] {
+                                *redirects.get(target).ok_or(LinkError::JumpIntoLinkedFunction {
+                                    unit_idx,
+                                    statement_idx: *target,
+                                })?
+                            } else {
+                                StatementIdx(base + target.0)
+                            })
+                        }
+                        Relocation::SegmentStart(segment_idx) => {
+                            Relocation::SegmentStart(segment_maps[unit_idx][segment_idx])
+                        }
+                        Relocation::ConstStart(segment_idx, ty) => {
+                            Relocation::ConstStart(segment_maps[unit_idx][segment_idx], ty.clone())
+                        }
+                        Relocation::EndOfProgram => Relocation::EndOfProgram,
+                    };
+                    relocations
+                        .push(RelocationEntry { instruction_idx: instructions.len(), relocation });
+                }
+                let instruction = &unit_instructions[unit_instruction_idx];
+                code_offset += instruction.body.op_size();
+                instructions.push(instruction.clone());
+            }
+            if let Some(statement_id) = statement_id {
+                sierra_statement_info.push(SierraStatementDebugInfo {
+                    start_offset,
+                    end_offset: code_offset,
+                    instruction_idx,
+                    ..statement_infos[statement_id].clone()
+                });
+            }
+        }
+    }
+
+    let statement_offsets: Vec<_> =
+        chain!(sierra_statement_info.iter().map(|info| info.start_offset), [code_offset]).collect();
+    relocate_instructions(&relocations, &statement_offsets, &consts_info, &mut instructions);
+    Ok(CairoProgram {
+        instructions,
+        debug_info: CairoProgramDebugInfo { sierra_statement_info },
+        consts_info,
+        relocations,
+    })
+}
+
+/// Merges the const segments of `units`, sharing the identical segments. Returns the merged
+/// segments, and the mapping from the segments of each unit to the merged segments.
+fn merge_consts(units: &[LinkUnit<'_>]) -> (ConstsInfo, Vec<UnorderedHashMap<u32, u32>>) {
+    let mut segments = OrderedHashMap::<u32, ConstSegment>::default();
+    let mut segment_maps = vec![];
+    for unit in units {
+        let mut segment_map = UnorderedHashMap::default();
+        for (unit_segment_idx, unit_segment) in unit.cairo_program.consts_info.segments.iter() {
+            let existing = segments.iter().find_map(|(segment_idx, segment)| {
+                (segment.values == unit_segment.values
+                    && segment.const_offset == unit_segment.const_offset)
+                    .then_some(*segment_idx)
+            });
+            let segment_idx = existing.unwrap_or_else(|| {
+                let segment_idx = segments.len().into_or_panic();
+                segments.insert(segment_idx, unit_segment.clone());
+                segment_idx
+            });
+            segment_map.insert(*unit_segment_idx, segment_idx);
+        }
+        segment_maps.push(segment_map);
+    }
+    let mut total_segments_size = 0;
+    for (_, segment) in segments.iter_mut() {
+        segment.segment_offset = total_segments_size;
+        // Add 1 for the `ret` instruction.
+        total_segments_size += 1 + segment.values.len();
+    }
+    (ConstsInfo { segments, total_segments_size }, segment_maps)
+}
@@ -0,0 +1,171 @@
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra::ProgramParser;
+use indoc::{formatdoc, indoc};
+use pretty_assertions::assert_eq;
+
+use super::{link, LinkError, LinkUnit};
+use crate::compiler::{compile, CairoProgram, SierraToCasmConfig};
+use crate::metadata::calc_metadata_ap_change_only;
+
+/// The declarations shared by the test programs.
+const DECLARATIONS: &str = indoc! {"
+    type felt252 = felt252;
+    type Const<felt252, 5> = Const<felt252, 5>;
+    type Const<felt252, 17> = Const<felt252, 17>;
+    type BoxFelt252 = Box<felt252>;
+
+    libfunc const_as_box<Const<felt252, 5>> = const_as_box<Const<felt252, 5>, 0>;
+    libfunc unbox<felt252> = unbox<felt252>;
+    libfunc store_temp_felt252 = store_temp<felt252>;
+    libfunc call_bar = function_call<user@bar>;
+"};
+
+/// Parses the program with the given additional libfuncs, statements and functions, and compiles
+/// it.
+fn compile_unit(libfuncs: &str, statements_and_functions: &str) -> (Program, CairoProgram) {
+    let program = ProgramParser::new()
+        .parse(&formatdoc! {"
+            {DECLARATIONS}{libfuncs}
+            {statements_and_functions}
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let config = SierraToCasmConfig { gas_usage_check: false, ..Default::default() };
+    let cairo_program = compile(&program, &metadata, config).unwrap();
+    (program, cairo_program)
+}
+
+const FOO: &str = indoc! {"
+    call_bar() -> ([0]);
+    return([0]);
+"};
+const BAR: &str = indoc! {"
+    const_as_box<Const<felt252, 5>>() -> ([0]);
+    unbox<felt252>([0]) -> ([0]);
+    store_temp_felt252([0]) -> ([0]);
+    return([0]);
+"};
+const BAZ: &str = indoc! {"
+    call_bar() -> ([0]);
+    return([0]);
+"};
+
+#[test]
+fn link_shared_function() {
+    let (program_a, cairo_program_a) = compile_unit(
+        "",
+        &formatdoc! {"
+        {FOO}{BAR}
+        foo@0() -> (felt252);
+        bar@2() -> (felt252);
+    "},
+    );
+    let (program_b, cairo_program_b) = compile_unit(
+        "",
+        &formatdoc! {"
+        {BAR}{BAZ}
+        bar@0() -> (felt252);
+        baz@4() -> (felt252);
+    "},
+    );
+    let linked = link(&[
+        LinkUnit { program: &program_a, cairo_program: &cairo_program_a },
+        LinkUnit { program: &program_b, cairo_program: &cairo_program_b },
+    ])
+    .unwrap();
+
+    // The second definition of `bar` is dropped, and `baz` calls the first one, as when compiling
+    // all the functions together.
+    let (_, combined) = compile_unit(
+        "",
+        &formatdoc! {"
+        {FOO}{BAR}{BAZ}
+        foo@0() -> (felt252);
+        bar@2() -> (felt252);
+        baz@6() -> (felt252);
+    "},
+    );
+    assert_eq!(linked.instructions, combined.instructions);
+    assert_eq!(linked.consts_info, combined.consts_info);
+    assert_eq!(linked.debug_info.sierra_statement_info.len(), 12);
+    let dropped_bar = &linked.debug_info.sierra_statement_info[6..10];
+    assert!(dropped_bar.iter().all(|info| info.start_offset == info.end_offset));
+}
+
+#[test]
+fn link_distinct_const_segments() {
+    let (program_a, cairo_program_a) = compile_unit(
+        "",
+        &formatdoc! {"
+        {BAR}
+        bar@0() -> (felt252);
+    "},
+    );
+    let libfuncs =
+        "libfunc const_as_box<Const<felt252, 17>> = const_as_box<Const<felt252, 17>, 0>;";
+    let (program_b, cairo_program_b) = compile_unit(
+        libfuncs,
+        &formatdoc! {"
+        {BAR}
+        const_as_box<Const<felt252, 17>>() -> ([0]);
+        unbox<felt252>([0]) -> ([0]);
+        store_temp_felt252([0]) -> ([0]);
+        return([0]);
+
+        bar@0() -> (felt252);
+        qux@4() -> (felt252);
+    "},
+    );
+    let linked = link(&[
+        LinkUnit { program: &program_a, cairo_program: &cairo_program_a },
+        LinkUnit { program: &program_b, cairo_program: &cairo_program_b },
+    ])
+    .unwrap();
+    // The segments of the units differ, so both are kept, and `qux` loads its const from the
+    // segment of its unit.
+    assert_eq!(
+        linked.to_string(),
+        indoc! {"
+            call rel 12;
+            [ap + 0] = [ap + -1] + 11, ap++;
+            [ap + 0] = [[ap + -1] + 0], ap++;
+            ret;
+            call rel 8;
+            [ap + 0] = [ap + -1] + 8, ap++;
+            [ap + 0] = [[ap + -1] + 0], ap++;
+            ret;
+            ret;
+            dw 5;
+            ret;
+            dw 5;
+            dw 17;
+        "}
+    );
+}
+
+#[test]
+fn link_mismatching_program() {
+    let (program, cairo_program) = compile_unit(
+        "",
+        &formatdoc! {"
+        {BAR}
+        bar@0() -> (felt252);
+    "},
+    );
+    let (other_program, _) = compile_unit(
+        "",
+        &formatdoc! {"
+        {BAR}{BAZ}
+        bar@0() -> (felt252);
+        baz@4() -> (felt252);
+    "},
+    );
+    assert_eq!(
+        link(&[
+            LinkUnit { program: &program, cairo_program: &cairo_program },
+            LinkUnit { program: &other_program, cairo_program: &cairo_program },
+        ])
+        .unwrap_err(),
+        LinkError::ProgramMismatch(1)
+    );
+}
@@ -0,0 +1,467 @@
+//! Peephole optimizations of the compiled CASM instructions of Sierra statements.
+
+use cairo_lang_casm::instructions::{
+    AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
+    JnzInstruction, JumpInstruction,
+};
+use cairo_lang_casm::operand::{BinOpOperand, CellRef, DerefOrImmediate, Register, ResOperand};
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_utils::bigint::BigIntAsHex;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::compiler::SierraStatementDebugInfo;
+use crate::relocations::{Relocation, RelocationEntry};
+
+#[cfg(test)]
+#[path = "peephole_test.rs"]
+mod test;
+
+/// Optimizes the unrelocated `instructions` compiled for the consecutive statements starting at
+/// `first_statement`, whose debug info is `sierra_statement_info`.
+///
+/// The following rewrites are performed:
+/// * Jumps to the next instruction are removed.
+/// * Consecutive `ap += <imm>` instructions are merged into a single instruction.
+/// * Consecutive identical assertions are folded into a single assertion.
+/// * An `ap += 1` instruction is merged into the `ap++` flag of the preceding assertion, or is
+///   moved after the following assertion or jump and merged into its `ap++` flag, adjusting its
+///   `ap` based operands.
+///
+/// Rewrites never merge instructions across statements or into jump targets, so that the ap
+/// changes of the statements are kept. The local jumps of the instructions (jumps without
+/// relocations), `relocations` and `sierra_statement_info` are updated to match the optimized
+/// instructions. The instructions are left unchanged if a local jump does not point to an
+/// instruction of the given statements.
+pub fn optimize(
+    first_statement: StatementIdx,
+    instructions: &mut Vec<Instruction>,
+    relocations: &mut Vec<RelocationEntry>,
+    sierra_statement_info: &mut [SierraStatementDebugInfo],
+) {
+    let n_instructions = instructions.len();
+    let pcs = instruction_pcs(instructions);
+    let mut instruction_relocations = vec![None; n_instructions];
+    for entry in relocations.drain(..) {
+        instruction_relocations[entry.instruction_idx] = Some(entry.relocation);
+    }
+    // The target instruction indices of the local jumps of the instructions.
+    let mut local_targets = vec![None; n_instructions];
+    for (idx, instruction) in instructions.iter().enumerate() {
+        if instruction_relocations[idx].is_some() {
+            continue;
+        }
+        let Some(offset) = relative_target(instruction) else {
+            continue;
+        };
+        let Some(target_pc) = usize::try_from(pcs[idx] as i128 + offset).ok() else {
+            return restore_relocations(relocations, instruction_relocations);
+        };
+        let Ok(target) = pcs.binary_search(&target_pc) else {
+            return restore_relocations(relocations, instruction_relocations);
+        };
+        local_targets[idx] = Some(target);
+    }
+    // Instructions that may be reached other than from the preceding instruction.
+    let mut is_boundary = vec![false; n_instructions + 1];
+    for info in sierra_statement_info.iter() {
+        is_boundary[info.instruction_idx] = true;
+    }
+    for target in local_targets.iter().flatten() {
+        is_boundary[*target] = true;
+    }
+    let statement_instruction = |statement_idx: &StatementIdx| {
+        let idx = statement_idx.0.checked_sub(first_statement.0)?;
+        match sierra_statement_info.get(idx) {
+            Some(info) => Some(info.instruction_idx),
+            None => (idx == sierra_statement_info.len()).then_some(n_instructions),
+        }
+    };
+
+    let mut optimized: Vec<Instruction> = Vec::with_capacity(n_instructions);
+    let mut optimized_relocations: Vec<RelocationEntry> = Vec::with_capacity(n_instructions);
+    // The local jumps of the optimized instructions, with their target in the original
+    // instructions.
+    let mut optimized_local_targets = vec![];
+    // Maps the original instruction indices to the index of the optimized instruction replacing
+    // them, or following them if they were removed.
+    let mut old_to_new = Vec::with_capacity(n_instructions + 1);
+    // Whether the last optimized instruction is executed right before the current instruction.
+    let mut follows_last = false;
+    for (idx, (instruction, relocation)) in
+        instructions.drain(..).zip(instruction_relocations).enumerate()
+    {
+        let target = match &relocation {
+            Some(Relocation::RelativeStatementId(statement_idx)) => {
+                statement_instruction(statement_idx)
+            }
+            Some(_) => None,
+            None => local_targets[idx],
+        };
+        if is_jump(&instruction) && target == Some(idx + 1) {
+            old_to_new.push(optimized.len());
+            follows_last = false;
+            continue;
+        }
+        let last_relocated = optimized_relocations
+            .last()
+            .is_some_and(|entry| entry.instruction_idx + 1 == optimized.len());
+        let can_combine =
+            follows_last && !is_boundary[idx] && instruction.hints.is_empty() && !last_relocated;
+        if can_combine {
+            if let Some(last) = optimized.last_mut() {
+                if relocation.is_none() && combine(last, &instruction) {
+                    old_to_new.push(optimized.len() - 1);
+                    continue;
+                }
+                // The relocations of instructions other than jumps may depend on their position.
+                let can_move = relocation.is_none()
+                    || matches!(
+                        instruction.body,
+                        InstructionBody::Jump(_) | InstructionBody::Jnz(_)
+                    );
+                let merged = if can_move { merge_ap_increment(last, &instruction) } else { None };
+                if let Some(merged) = merged {
+                    *last = merged;
+                    let merged_idx = optimized.len() - 1;
+                    old_to_new.push(merged_idx);
+                    if let Some(relocation) = relocation {
+                        optimized_relocations
+                            .push(RelocationEntry { instruction_idx: merged_idx, relocation });
+                    }
+                    if let Some(target) = local_targets[idx] {
+                        optimized_local_targets.push((merged_idx, target));
+                    }
+                    continue;
+                }
+            }
+        }
+        old_to_new.push(optimized.len());
+        if let Some(relocation) = relocation {
+            optimized_relocations
+                .push(RelocationEntry { instruction_idx: optimized.len(), relocation });
+        }
+        if let Some(target) = local_targets[idx] {
+            optimized_local_targets.push((optimized.len(), target));
+        }
+        optimized.push(instruction);
+        follows_last = true;
+    }
+    old_to_new.push(optimized.len());
+
+    let new_pcs = instruction_pcs(&optimized);
+    for (idx, target) in optimized_local_targets {
+        let offset = new_pcs[old_to_new[target]] as i128 - new_pcs[idx] as i128;
+        *relative_target_mut(&mut optimized[idx]).unwrap() = BigInt::from(offset);
+    }
+    let mut end_offset = *new_pcs.last().unwrap();
+    for info in sierra_statement_info.iter_mut().rev() {
+        info.instruction_idx = old_to_new[info.instruction_idx];
+        info.start_offset = new_pcs[info.instruction_idx];
+        info.end_offset = end_offset;
+        end_offset = info.start_offset;
+    }
+    *instructions = optimized;
+    *relocations = optimized_relocations;
+}
+
+/// Coalesces the `ap += <imm>` instructions ending a statement of the unrelocated `instructions`
+/// with the `ap += <imm>` instructions starting the following statements, given the same
+/// arguments as [optimize].
+///
+/// Statements in `entry_points`, statements jumped to by `relocations`, and instructions jumped to
+/// by local jumps are never merged into the preceding instructions, as they may be reached other
+/// than from them. Such jumps may only target the statements from within the given instructions,
+/// as is the case for all the statements of the functions, but their entry points.
+pub fn coalesce_ap_updates(
+    first_statement: StatementIdx,
+    instructions: &mut Vec<Instruction>,
+    relocations: &mut [RelocationEntry],
+    sierra_statement_info: &mut [SierraStatementDebugInfo],
+    entry_points: &UnorderedHashSet<StatementIdx>,
+) {
+    let n_instructions = instructions.len();
+    let pcs = instruction_pcs(instructions);
+    let mut is_relocated = vec![false; n_instructions];
+    let mut is_boundary = vec![false; n_instructions + 1];
+    for entry in relocations.iter() {
+        is_relocated[entry.instruction_idx] = true;
+        if let Relocation::RelativeStatementId(statement_idx) = entry.relocation {
+            let Some(info) = statement_idx
+                .0
+                .checked_sub(first_statement.0)
+                .and_then(|idx| sierra_statement_info.get(idx))
+            else {
+                continue;
+            };
+            is_boundary[info.instruction_idx] = true;
+        }
+    }
+    for (idx, info) in sierra_statement_info.iter().enumerate() {
+        if entry_points.contains(&StatementIdx(first_statement.0 + idx)) {
+            is_boundary[info.instruction_idx] = true;
+        }
+    }
+    // The target instruction indices of the local jumps of the instructions.
+    let mut local_targets = vec![None; n_instructions];
+    for (idx, instruction) in instructions.iter().enumerate() {
+        if is_relocated[idx] {
+            continue;
+        }
+        let Some(offset) = relative_target(instruction) else {
+            continue;
+        };
+        let Some(target) = usize::try_from(pcs[idx] as i128 + offset)
+            .ok()
+            .and_then(|target_pc| pcs.binary_search(&target_pc).ok())
+        else {
+            return;
+        };
+        is_boundary[target] = true;
+        local_targets[idx] = Some(target);
+    }
+
+    let mut optimized: Vec<Instruction> = Vec::with_capacity(n_instructions);
+    // The local jumps of the optimized instructions, with their target in the original
+    // instructions.
+    let mut optimized_local_targets = vec![];
+    // Maps the original instruction indices to the index of the optimized instruction replacing
+    // them.
+    let mut old_to_new = Vec::with_capacity(n_instructions + 1);
+    // Whether the original instructions were merged into the preceding instruction.
+    let mut is_merged = vec![false; n_instructions + 1];
+    for (idx, instruction) in instructions.drain(..).enumerate() {
+        if !is_boundary[idx] && !is_relocated[idx] {
+            if let Some(last) = optimized.last_mut() {
+                if is_ap_update(last) && is_ap_update(&instruction) && combine(last, &instruction) {
+                    old_to_new.push(optimized.len() - 1);
+                    is_merged[idx] = true;
+                    continue;
+                }
+            }
+        }
+        old_to_new.push(optimized.len());
+        if let Some(target) = local_targets[idx] {
+            optimized_local_targets.push((optimized.len(), target));
+        }
+        optimized.push(instruction);
+    }
+    old_to_new.push(optimized.len());
+
+    let new_pcs = instruction_pcs(&optimized);
+    for (idx, target) in optimized_local_targets {
+        let offset = new_pcs[old_to_new[target]] as i128 - new_pcs[idx] as i128;
+        *relative_target_mut(&mut optimized[idx]).unwrap() = BigInt::from(offset);
+    }
+    for entry in relocations.iter_mut() {
+        entry.instruction_idx = old_to_new[entry.instruction_idx];
+    }
+    // Statements starting with a merged instruction now start after it.
+    let mut end_offset = *new_pcs.last().unwrap();
+    for info in sierra_statement_info.iter_mut().rev() {
+        info.instruction_idx =
+            old_to_new[info.instruction_idx] + usize::from(is_merged[info.instruction_idx]);
+        info.start_offset = new_pcs[info.instruction_idx];
+        info.end_offset = end_offset;
+        end_offset = info.start_offset;
+    }
+    *instructions = optimized;
+}
+
+/// Puts back the relocations taken out of `relocations` into it.
+fn restore_relocations(
+    relocations: &mut Vec<RelocationEntry>,
+    instruction_relocations: Vec<Option<Relocation>>,
+) {
+    relocations.extend(instruction_relocations.into_iter().enumerate().filter_map(
+        |(instruction_idx, relocation)| {
+            Some(RelocationEntry { instruction_idx, relocation: relocation? })
+        },
+    ));
+}
+
+/// Returns the program offsets of the instructions, relative to the first instruction, followed by
+/// the offset of their end.
+fn instruction_pcs(instructions: &[Instruction]) -> Vec<usize> {
+    let mut pcs = Vec::with_capacity(instructions.len() + 1);
+    let mut pc = 0;
+    pcs.push(pc);
+    for instruction in instructions {
+        pc += instruction.body.op_size();
+        pcs.push(pc);
+    }
+    pcs
+}
+
+/// Returns whether `instruction` is a jump that has no other effect.
+fn is_jump(instruction: &Instruction) -> bool {
+    !instruction.inc_ap
+        && instruction.hints.is_empty()
+        && matches!(
+            instruction.body,
+            InstructionBody::Jump(JumpInstruction {
+                target: DerefOrImmediate::Immediate(_),
+                relative: true,
+            }) | InstructionBody::Jnz(JnzInstruction {
+                jump_offset: DerefOrImmediate::Immediate(_),
+                ..
+            })
+        )
+}
+
+/// Returns the relative offset of the target of `instruction`, if it is a relative jump or call.
+fn relative_target(instruction: &Instruction) -> Option<i128> {
+    match &instruction.body {
+        InstructionBody::Call(CallInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            relative: true,
+        })
+        | InstructionBody::Jump(JumpInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            relative: true,
+        })
+        | InstructionBody::Jnz(JnzInstruction {
+            jump_offset: DerefOrImmediate::Immediate(value),
+            ..
+        }) => value.value.to_i128(),
+        _ => None,
+    }
+}
+
+/// Returns the relative offset of the target of `instruction` for modification, if it is a
+/// relative jump or call.
+fn relative_target_mut(instruction: &mut Instruction) -> Option<&mut BigInt> {
+    match &mut instruction.body {
+        InstructionBody::Call(CallInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            relative: true,
+        })
+        | InstructionBody::Jump(JumpInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            relative: true,
+        })
+        | InstructionBody::Jnz(JnzInstruction {
+            jump_offset: DerefOrImmediate::Immediate(value),
+            ..
+        }) => Some(&mut value.value),
+        _ => None,
+    }
+}
+
+/// Combines `instruction` into the preceding instruction `last`, if the two can be replaced by
+/// the updated `last`. Returns whether `instruction` was combined.
+fn combine(last: &mut Instruction, instruction: &Instruction) -> bool {
+    match (&mut last.body, &instruction.body) {
+        (
+            InstructionBody::AddAp(AddApInstruction { operand: ResOperand::Immediate(total) }),
+            InstructionBody::AddAp(AddApInstruction { operand: ResOperand::Immediate(value) }),
+        ) if !last.inc_ap && !instruction.inc_ap => {
+            *total = BigIntAsHex { value: &total.value + &value.value };
+            true
+        }
+        (InstructionBody::AssertEq(last_body), InstructionBody::AssertEq(body)) => {
+            !last.inc_ap && !instruction.inc_ap && last_body == body
+        }
+        _ => false,
+    }
+}
+
+/// Merges the consecutive instructions `first` and `second`, one of them being `ap += 1`, into a
+/// single instruction with `ap++`, if they can be replaced by it.
+///
+/// `second` must have no hints, as they would be executed before `ap` is incremented.
+fn merge_ap_increment(first: &Instruction, second: &Instruction) -> Option<Instruction> {
+    if first.inc_ap || second.inc_ap {
+        return None;
+    }
+    match (&first.body, &second.body) {
+        (InstructionBody::AssertEq(_), body) if is_ap_increment(body) => {
+            Some(Instruction { inc_ap: true, ..first.clone() })
+        }
+        (body, InstructionBody::AssertEq(AssertEqInstruction { a, b }))
+            if is_ap_increment(body) =>
+        {
+            let body = InstructionBody::AssertEq(AssertEqInstruction {
+                a: shift_cell(a)?,
+                b: shift_res_operand(b)?,
+            });
+            Some(Instruction { body, inc_ap: true, hints: first.hints.clone() })
+        }
+        (body, InstructionBody::Jump(JumpInstruction { target, relative }))
+            if is_ap_increment(body) =>
+        {
+            let body = InstructionBody::Jump(JumpInstruction {
+                target: shift_deref_or_immediate(target)?,
+                relative: *relative,
+            });
+            Some(Instruction { body, inc_ap: true, hints: first.hints.clone() })
+        }
+        (body, InstructionBody::Jnz(JnzInstruction { jump_offset, condition }))
+            if is_ap_increment(body) =>
+        {
+            let body = InstructionBody::Jnz(JnzInstruction {
+                jump_offset: shift_deref_or_immediate(jump_offset)?,
+                condition: shift_cell(condition)?,
+            });
+            Some(Instruction { body, inc_ap: true, hints: first.hints.clone() })
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether `instruction` is an `ap += <imm>` instruction with no other effect.
+fn is_ap_update(instruction: &Instruction) -> bool {
+    !instruction.inc_ap
+        && instruction.hints.is_empty()
+        && matches!(
+            instruction.body,
+            InstructionBody::AddAp(AddApInstruction { operand: ResOperand::Immediate(_) })
+        )
+}
+
+/// Returns whether `body` is `ap += 1`.
+fn is_ap_increment(body: &InstructionBody) -> bool {
+    matches!(
+        body,
+        InstructionBody::AddAp(AddApInstruction { operand: ResOperand::Immediate(value) })
+            if value.value == BigInt::from(1)
+    )
+}
+
+/// Returns `cell` as referenced by an instruction executed before `ap` is incremented, instead of
+/// after it.
+fn shift_cell(cell: &CellRef) -> Option<CellRef> {
+    match cell.register {
+        Register::AP => {
+            Some(CellRef { register: Register::AP, offset: cell.offset.checked_add(1)? })
+        }
+        Register::FP => Some(*cell),
+    }
+}
+
+/// Returns `operand` as referenced by an instruction executed before `ap` is incremented, instead
+/// of after it.
+fn shift_deref_or_immediate(operand: &DerefOrImmediate) -> Option<DerefOrImmediate> {
+    match operand {
+        DerefOrImmediate::Deref(cell) => Some(DerefOrImmediate::Deref(shift_cell(cell)?)),
+        DerefOrImmediate::Immediate(_) => Some(operand.clone()),
+    }
+}
+
+/// Returns `operand` as referenced by an instruction executed before `ap` is incremented, instead
+/// of after it.
+fn shift_res_operand(operand: &ResOperand) -> Option<ResOperand> {
+    match operand {
+        ResOperand::Deref(cell) => Some(ResOperand::Deref(shift_cell(cell)?)),
+        ResOperand::DoubleDeref(cell, offset) => {
+            Some(ResOperand::DoubleDeref(shift_cell(cell)?, *offset))
+        }
+        ResOperand::Immediate(_) => Some(operand.clone()),
+        ResOperand::BinOp(BinOpOperand { op, a, b }) => Some(ResOperand::BinOp(BinOpOperand {
+            op: op.clone(),
+            a: shift_cell(a)?,
+            b: shift_deref_or_immediate(b)?,
+        })),
+    }
+}
@@ -0,0 +1,225 @@
+use cairo_lang_casm::casm;
+use cairo_lang_sierra::program::StatementIdx;
+use indoc::indoc;
+use itertools::join;
+use pretty_assertions::assert_eq;
+
+use super::{coalesce_ap_updates, optimize};
+use crate::compiler::{ReturnStatementDebugInfo, SierraStatementDebugInfo, StatementKindDebugInfo};
+use crate::relocations::{Relocation, RelocationEntry};
+
+/// Returns the debug info of a statement starting at the given instruction.
+fn statement_info(
+    instruction_idx: usize,
+    start_offset: usize,
+    end_offset: usize,
+) -> SierraStatementDebugInfo {
+    SierraStatementDebugInfo {
+        start_offset,
+        end_offset,
+        instruction_idx,
+        additional_kind_info: StatementKindDebugInfo::Return(ReturnStatementDebugInfo {
+            ref_values: vec![],
+        }),
+        source_location: None,
+        gas_costs: None,
+    }
+}
+
+#[test]
+fn optimize_instructions() {
+    let mut instructions = casm! {
+        // Statement #3.
+        ap += 1;
+        ap += 2;
+        [fp + 1] = [fp - 3];
+        [fp + 1] = [fp - 3];
+        jmp rel 4 if [fp + 1] != 0;
+        jmp rel 2;
+        [ap + 0] = [fp - 4], ap++;
+        ap += 3;
+        jmp rel 0;
+        // Statement #4.
+        ap += 1;
+        call rel 0;
+        ret;
+    }
+    .instructions;
+    let mut relocations = vec![
+        RelocationEntry {
+            instruction_idx: 8,
+            relocation: Relocation::RelativeStatementId(StatementIdx(4)),
+        },
+        RelocationEntry {
+            instruction_idx: 10,
+            relocation: Relocation::RelativeStatementId(StatementIdx(0)),
+        },
+    ];
+    let mut sierra_statement_info = vec![statement_info(0, 0, 13), statement_info(9, 13, 18)];
+
+    optimize(StatementIdx(3), &mut instructions, &mut relocations, &mut sierra_statement_info);
+
+    assert_eq!(
+        join(instructions.iter().map(|instruction| format!("{instruction};\n")), ""),
+        indoc! {"
+            ap += 3;
+            [fp + 1] = [fp + -3];
+            jmp rel 2 if [fp + 1] != 0;
+            [ap + 0] = [fp + -4], ap++;
+            ap += 3;
+            ap += 1;
+            call rel 0;
+            ret;
+        "}
+    );
+    assert_eq!(
+        relocations,
+        vec![RelocationEntry {
+            instruction_idx: 6,
+            relocation: Relocation::RelativeStatementId(StatementIdx(0)),
+        }]
+    );
+    assert_eq!(sierra_statement_info, vec![statement_info(0, 0, 8), statement_info(5, 8, 13)]);
+}
+
+#[test]
+fn optimize_keeps_jump_targets() {
+    let mut instructions = casm! {
+        ap += 2;
+        jmp rel 3 if [fp + 1] != 0;
+        [fp + 1] = [fp - 3];
+        [fp + 1] = [fp - 3];
+        ret;
+    }
+    .instructions;
+    let expected = instructions.clone();
+    let mut relocations = vec![];
+    let mut sierra_statement_info = vec![statement_info(0, 0, 7)];
+
+    optimize(StatementIdx(0), &mut instructions, &mut relocations, &mut sierra_statement_info);
+
+    // The second assertion may be jumped to, so it is not folded into the first one.
+    assert_eq!(instructions, expected);
+    assert_eq!(sierra_statement_info, vec![statement_info(0, 0, 7)]);
+}
+
+#[test]
+fn optimize_merges_ap_increments() {
+    let mut instructions = casm! {
+        // Statement #0.
+        [fp + 1] = [fp - 3];
+        ap += 1;
+        ap += 1;
+        [ap - 1] = [fp - 4] + [ap - 2];
+        ap += 1;
+        jmp rel 6 if [ap - 1] != 0;
+        ap += 1;
+        jmp rel 0;
+        // Statement #1.
+        ap += 1;
+        ret;
+    }
+    .instructions;
+    let mut relocations = vec![RelocationEntry {
+        instruction_idx: 7,
+        relocation: Relocation::RelativeStatementId(StatementIdx(0)),
+    }];
+    let mut sierra_statement_info = vec![statement_info(0, 0, 14), statement_info(8, 14, 17)];
+
+    optimize(StatementIdx(0), &mut instructions, &mut relocations, &mut sierra_statement_info);
+
+    assert_eq!(
+        join(instructions.iter().map(|instruction| format!("{instruction};\n")), ""),
+        indoc! {"
+            [fp + 1] = [fp + -3], ap++;
+            [ap + 0] = [fp + -4] + [ap + -1], ap++;
+            jmp rel 4 if [ap + 0] != 0, ap++;
+            jmp rel 0, ap++;
+            ap += 1;
+            ret;
+        "}
+    );
+    assert_eq!(
+        relocations,
+        vec![RelocationEntry {
+            instruction_idx: 3,
+            relocation: Relocation::RelativeStatementId(StatementIdx(0)),
+        }]
+    );
+    assert_eq!(sierra_statement_info, vec![statement_info(0, 0, 6), statement_info(4, 6, 9)]);
+}
+
+#[test]
+fn coalesce_ap_updates_across_statements() {
+    let mut instructions = casm! {
+        // Statement #0.
+        ap += 1;
+        // Statement #1.
+        ap += 2;
+        jmp rel 4 if [fp + 1] != 0;
+        // Statement #2.
+        ap += 3;
+        ap += 4;
+        // Statement #3.
+        ap += 5;
+        // Statement #4.
+        ap += 6;
+        jmp rel 0;
+        // Statement #5.
+        ret;
+    }
+    .instructions;
+    let mut relocations = vec![RelocationEntry {
+        instruction_idx: 7,
+        relocation: Relocation::RelativeStatementId(StatementIdx(2)),
+    }];
+    let mut sierra_statement_info = vec![
+        statement_info(0, 0, 2),
+        statement_info(1, 2, 6),
+        statement_info(3, 6, 10),
+        statement_info(5, 10, 12),
+        statement_info(6, 12, 16),
+        statement_info(8, 16, 17),
+    ];
+
+    coalesce_ap_updates(
+        StatementIdx(0),
+        &mut instructions,
+        &mut relocations,
+        &mut sierra_statement_info,
+        &[StatementIdx(0), StatementIdx(3)].into_iter().collect(),
+    );
+
+    // Statement #2 is jumped to by a relocation, the second instruction of statement #2 is jumped
+    // to by a local jump, and statement #3 is an entry point, so they are kept.
+    assert_eq!(
+        join(instructions.iter().map(|instruction| format!("{instruction};\n")), ""),
+        indoc! {"
+            ap += 3;
+            jmp rel 4 if [fp + 1] != 0;
+            ap += 3;
+            ap += 4;
+            ap += 11;
+            jmp rel 0;
+            ret;
+        "}
+    );
+    assert_eq!(
+        relocations,
+        vec![RelocationEntry {
+            instruction_idx: 5,
+            relocation: Relocation::RelativeStatementId(StatementIdx(2)),
+        }]
+    );
+    assert_eq!(
+        sierra_statement_info,
+        vec![
+            statement_info(0, 0, 2),
+            statement_info(1, 2, 4),
+            statement_info(2, 4, 8),
+            statement_info(4, 8, 10),
+            statement_info(5, 10, 12),
+            statement_info(6, 12, 13),
+        ]
+    );
+}
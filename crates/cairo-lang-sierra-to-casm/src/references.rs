@@ -28,6 +28,7 @@ pub type StatementRefs = OrderedHashMap<VarId, ReferenceValue>;
 /// A Sierra reference to a value.
 /// Corresponds to an argument or return value of a Sierra statement.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ReferenceValue {
     pub expression: ReferenceExpression,
     pub ty: ConcreteTypeId,
@@ -49,6 +50,7 @@ impl ReferenceValue {
 
 /// The location where a value was introduced.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct IntroductionPoint {
     /// The index of the statement creating the value, None if introduced as a function param.
     pub source_statement_idx: Option<StatementIdx>,
@@ -75,6 +77,7 @@ impl core::fmt::Display for IntroductionPoint {
 /// A Sierra reference to a value.
 /// Returned from a libfunc.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct OutputReferenceValue {
     pub expression: ReferenceExpression,
     pub ty: ConcreteTypeId,
@@ -87,6 +90,7 @@ pub struct OutputReferenceValue {
 
 /// The location where a value was introduced for output reference values.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum OutputReferenceValueIntroductionPoint {
     /// A new point introduced by a libfunc. The inner value is the output index.
     New(usize),
@@ -96,6 +100,7 @@ pub enum OutputReferenceValueIntroductionPoint {
 
 /// A collection of Cell Expression which represents one logical object.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ReferenceExpression {
     pub cells: Vec<CellExpression>,
 }
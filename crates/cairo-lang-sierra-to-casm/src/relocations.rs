@@ -11,7 +11,8 @@ use crate::compiler::ConstsInfo;
 
 pub type CodeOffset = usize;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Relocation {
     /// Adds program_offset(StatementIdx) and subtracts the program offset of the casm instruction
     /// that is being relocated.
@@ -33,7 +34,26 @@ impl Relocation {
         consts_info: &ConstsInfo,
         instruction: &mut Instruction,
     ) {
-        let target_pc = match self {
+        let target_pc = self.target_pc(statement_offsets, consts_info);
+        add_to_immediate(instruction, target_pc as i128 - instruction_offset as i128);
+    }
+
+    /// Reverts a previous application of the relocation, given the same layout it was applied
+    /// with.
+    pub fn revert(
+        &self,
+        instruction_offset: CodeOffset,
+        statement_offsets: &[CodeOffset],
+        consts_info: &ConstsInfo,
+        instruction: &mut Instruction,
+    ) {
+        let target_pc = self.target_pc(statement_offsets, consts_info);
+        add_to_immediate(instruction, instruction_offset as i128 - target_pc as i128);
+    }
+
+    /// Returns the program offset the relocation points to.
+    fn target_pc(&self, statement_offsets: &[CodeOffset], consts_info: &ConstsInfo) -> CodeOffset {
+        match self {
             Relocation::RelativeStatementId(statement_idx) => statement_offsets[statement_idx.0],
             Relocation::SegmentStart(segment_index) => {
                 let segment = consts_info.segments.get(segment_index).expect("Segment not found.");
@@ -48,53 +68,57 @@ impl Relocation {
             Relocation::EndOfProgram => {
                 *statement_offsets.last().unwrap() + consts_info.total_segments_size
             }
-        };
-        match instruction {
-            Instruction {
-                body:
-                    InstructionBody::Call(CallInstruction {
-                        target: DerefOrImmediate::Immediate(value),
-                        relative: true,
-                    }),
-                inc_ap: false,
-                ..
-            }
-            | Instruction {
-                body:
-                    InstructionBody::Jnz(JnzInstruction {
-                        jump_offset: DerefOrImmediate::Immediate(value),
-                        condition: _,
-                    }),
-                ..
-            }
-            | Instruction {
-                body:
-                    InstructionBody::Jump(JumpInstruction {
-                        target: DerefOrImmediate::Immediate(value),
-                        relative: true,
-                    }),
-                ..
-            }
-            | Instruction {
-                body:
-                    InstructionBody::AssertEq(AssertEqInstruction {
-                        b:
-                            ResOperand::BinOp(BinOpOperand {
-                                b: DerefOrImmediate::Immediate(value),
-                                ..
-                            }),
-                        ..
-                    }),
-                ..
-            } => {
-                value.value += target_pc as i128 - instruction_offset as i128;
-            }
-            _ => panic!("Bad relocation."),
         }
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+/// Adds `delta` to the relocatable immediate of `instruction`.
+fn add_to_immediate(instruction: &mut Instruction, delta: i128) {
+    match instruction {
+        Instruction {
+            body:
+                InstructionBody::Call(CallInstruction {
+                    target: DerefOrImmediate::Immediate(value),
+                    relative: true,
+                }),
+            inc_ap: false,
+            ..
+        }
+        | Instruction {
+            body:
+                InstructionBody::Jnz(JnzInstruction {
+                    jump_offset: DerefOrImmediate::Immediate(value),
+                    condition: _,
+                }),
+            ..
+        }
+        | Instruction {
+            body:
+                InstructionBody::Jump(JumpInstruction {
+                    target: DerefOrImmediate::Immediate(value),
+                    relative: true,
+                }),
+            ..
+        }
+        | Instruction {
+            body:
+                InstructionBody::AssertEq(AssertEqInstruction {
+                    b:
+                        ResOperand::BinOp(BinOpOperand {
+                            b: DerefOrImmediate::Immediate(value), ..
+                        }),
+                    ..
+                }),
+            ..
+        } => {
+            value.value += delta;
+        }
+        _ => panic!("Bad relocation."),
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct RelocationEntry {
     /// The index of the casm instruction that needs to be relocated.
     pub instruction_idx: CodeOffset,
@@ -1,12 +1,15 @@
-#[cfg(test)]
-#[path = "contract_segmentation_test.rs"]
-mod test;
+//! Segmentation of the bytecode of a program by its functions, e.g. for computing segmented class
+//! hashes.
 
 use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
-use cairo_lang_sierra_to_casm::compiler::CairoProgram;
-use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::compiler::CairoProgram;
+
+#[cfg(test)]
+#[path = "segmentation_test.rs"]
+mod test;
+
 /// NestedIntList is either a list of NestedIntList or an integer.
 /// E.g., `[0, [1, 2], [3, [4]]]`.
 ///
@@ -15,8 +18,8 @@ use thiserror::Error;
 /// For example, the contract may be segmented by functions, where each function is segmented by
 /// its branches. It is also possible to have the inner segmentation only for some of the functions,
 /// while others are kept as non-segmented leaves in the tree.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(untagged))]
 pub enum NestedIntList {
     Leaf(usize),
     Node(Vec<NestedIntList>),
@@ -30,8 +33,9 @@ pub enum SegmentationError {
     JumpOutsideFunction(StatementIdx),
 }
 
-/// Computes the bytecode_segment_length for the given contract.
-pub fn compute_bytecode_segment_lengths(
+/// Computes the bytecode_segment_length for the given program, see
+/// [CairoProgram::bytecode_segment_lengths].
+pub(crate) fn compute_bytecode_segment_lengths(
     program: &Program,
     cairo_program: &CairoProgram,
     bytecode_len: usize,
@@ -1,9 +1,10 @@
 use cairo_lang_sierra::ProgramParser;
-use cairo_lang_sierra_to_casm::compiler::SierraToCasmConfig;
 use cairo_lang_test_utils::parse_test_file::{TestFileRunner, TestRunnerResult};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
 use super::{compute_bytecode_segment_lengths, find_functions_segments, get_segment_lengths};
+use crate::compiler::{compile, SierraToCasmConfig};
+use crate::metadata::calc_metadata;
 
 /// Tests `bytecode_segment_length` and 'find_functions_segments' returns the correct result.
 #[derive(Default)]
@@ -21,24 +22,24 @@ impl TestFileRunner for BytecodeSegmentLengthTest {
         let function_segments_str = format!("{:?}", find_functions_segments(&sierra_program));
 
         let cairo_program =
-            cairo_lang_sierra_to_casm::metadata::calc_metadata(&sierra_program, Default::default())
+            calc_metadata(&sierra_program, Default::default()).ok().and_then(|metadata| {
+                compile(
+                    &sierra_program,
+                    &metadata,
+                    SierraToCasmConfig { gas_usage_check: false, ..Default::default() },
+                )
                 .ok()
-                .and_then(|metadata| {
-                    cairo_lang_sierra_to_casm::compiler::compile(
-                        &sierra_program,
-                        &metadata,
-                        SierraToCasmConfig {
-                            gas_usage_check: false,
-                            max_bytecode_size: usize::MAX,
-                        },
-                    )
-                    .ok()
-                });
+            });
         let (cairo_program_str, bytecode_lengths_str) = match cairo_program {
             Some(cairo_program) => {
                 let bytecode_len = cairo_program.assemble().bytecode.len();
                 let bytecode_lengths =
                     compute_bytecode_segment_lengths(&sierra_program, &cairo_program, bytecode_len);
+                assert_eq!(cairo_program.bytecode_len(), bytecode_len);
+                assert_eq!(
+                    cairo_program.bytecode_segment_lengths(&sierra_program),
+                    bytecode_lengths
+                );
                 (format!("{}", cairo_program), format!("{:?}", bytecode_lengths))
             }
             None => {
@@ -59,7 +60,7 @@ impl TestFileRunner for BytecodeSegmentLengthTest {
 
 cairo_lang_test_utils::test_file_test_with_runner!(
     test_bytecode_segment_lengths,
-    "src/contract_segmentation_test_data",
+    "src/segmentation_test_data",
     { find_segment: "find_segment" },
     BytecodeSegmentLengthTest
 );
@@ -0,0 +1,186 @@
+//! Auditing of the risky conversions of values in a Sierra program, so that reviewers can focus on
+//! them.
+
+use std::fmt;
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::debug_info::SourceCodeLocation;
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId};
+use crate::program::{GenericArg, Program, Statement, StatementIdx};
+
+#[cfg(test)]
+#[path = "audit_test.rs"]
+mod test;
+
+/// The libfuncs converting a `felt252` into a narrower type, failing if the value does not fit.
+const FELT252_DOWNCAST_LIBFUNCS: [&str; 14] = [
+    "u8_try_from_felt252",
+    "u16_try_from_felt252",
+    "u32_try_from_felt252",
+    "u64_try_from_felt252",
+    "u128_try_from_felt252",
+    "i8_try_from_felt252",
+    "i16_try_from_felt252",
+    "i32_try_from_felt252",
+    "i64_try_from_felt252",
+    "i128_try_from_felt252",
+    "bytes31_try_from_felt252",
+    "contract_address_try_from_felt252",
+    "class_hash_try_from_felt252",
+    "storage_address_try_from_felt252",
+];
+
+/// The libfuncs converting a value into another type without checking that it fits the type.
+const UNCHECKED_CAST_LIBFUNCS: [&str; 1] = ["storage_base_address_from_felt252"];
+
+/// The names of the functions that panic on failure instead of returning it to the caller.
+const PANICKING_SHORTCUT_FUNCTIONS: [&str; 1] = ["unwrap_syscall"];
+
+/// The kind of a risky conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RiskyConversionKind {
+    /// A conversion that does not check that the value fits the target type, e.g.
+    /// `storage_base_address_from_felt252`.
+    UncheckedCast,
+    /// A conversion of a `felt252` into a narrower type, e.g. `u8_try_from_felt252` or
+    /// `downcast<felt252, T>`.
+    Felt252Downcast,
+    /// A call to a function panicking on failure, e.g. `unwrap_syscall`.
+    PanickingShortcut,
+}
+impl fmt::Display for RiskyConversionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskyConversionKind::UncheckedCast => write!(f, "unchecked cast"),
+            RiskyConversionKind::Felt252Downcast => write!(f, "felt252 downcast"),
+            RiskyConversionKind::PanickingShortcut => write!(f, "panicking shortcut"),
+        }
+    }
+}
+
+/// A risky conversion found in a Sierra program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RiskyConversion {
+    pub kind: RiskyConversionKind,
+    /// The statement invoking the conversion.
+    pub statement_idx: StatementIdx,
+    /// The invoked libfunc, as declared in the program.
+    pub libfunc: String,
+    /// The function containing the statement.
+    pub function: Option<FunctionId>,
+    /// The Cairo source code location the statement was generated from, if known.
+    pub location: Option<SourceCodeLocation>,
+}
+
+/// The risky conversions found in a Sierra program, ordered by statement.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConversionAuditReport {
+    pub conversions: Vec<RiskyConversion>,
+}
+impl ConversionAuditReport {
+    /// Finds the risky conversions in `program`, attributing them to the source code locations in
+    /// `locations`.
+    pub fn new(
+        program: &Program,
+        locations: &UnorderedHashMap<StatementIdx, SourceCodeLocation>,
+    ) -> Self {
+        let felt252_types: Vec<&ConcreteTypeId> = program
+            .type_declarations
+            .iter()
+            .filter(|declaration| declaration.long_id.generic_id.0 == "felt252")
+            .map(|declaration| &declaration.id)
+            .collect();
+        let risky_libfuncs: UnorderedHashMap<&ConcreteLibfuncId, (RiskyConversionKind, String)> =
+            program
+                .libfunc_declarations
+                .iter()
+                .filter_map(|declaration| {
+                    let long_id = &declaration.long_id;
+                    let generic_id = long_id.generic_id.0.as_str();
+                    let kind = if UNCHECKED_CAST_LIBFUNCS.contains(&generic_id) {
+                        RiskyConversionKind::UncheckedCast
+                    } else if FELT252_DOWNCAST_LIBFUNCS.contains(&generic_id) {
+                        RiskyConversionKind::Felt252Downcast
+                    } else {
+                        match (generic_id, long_id.generic_args.as_slice()) {
+                            ("downcast", [GenericArg::Type(from_ty), _])
+                                if felt252_types.contains(&from_ty) =>
+                            {
+                                RiskyConversionKind::Felt252Downcast
+                            }
+                            ("function_call", [GenericArg::UserFunc(function)])
+                                if is_panicking_shortcut(function) =>
+                            {
+                                RiskyConversionKind::PanickingShortcut
+                            }
+                            _ => return None,
+                        }
+                    };
+                    Some((&declaration.id, (kind, long_id.to_string())))
+                })
+                .collect();
+
+        let mut entry_points: Vec<(StatementIdx, &FunctionId)> =
+            program.funcs.iter().map(|function| (function.entry_point, &function.id)).collect();
+        entry_points.sort_by_key(|(entry_point, _)| entry_point.0);
+        let conversions = program
+            .statements
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, statement)| {
+                let Statement::Invocation(invocation) = statement else {
+                    return None;
+                };
+                let (kind, libfunc) = risky_libfuncs.get(&invocation.libfunc_id)?;
+                let statement_idx = StatementIdx(idx);
+                // The containing function is the one with the last entry point before the
+                // statement.
+                let function = entry_points
+                    .partition_point(|(entry_point, _)| entry_point.0 <= idx)
+                    .checked_sub(1)
+                    .map(|function_idx| entry_points[function_idx].1.clone());
+                Some(RiskyConversion {
+                    kind: *kind,
+                    statement_idx,
+                    libfunc: libfunc.clone(),
+                    function,
+                    location: locations.get(&statement_idx).cloned(),
+                })
+            })
+            .collect();
+        Self { conversions }
+    }
+
+    /// Returns the number of conversions of the given kind.
+    pub fn count(&self, kind: RiskyConversionKind) -> usize {
+        self.conversions.iter().filter(|conversion| conversion.kind == kind).count()
+    }
+}
+impl fmt::Display for ConversionAuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for conversion in &self.conversions {
+            match &conversion.location {
+                Some(SourceCodeLocation { file, line, column }) => {
+                    write!(f, "{file}:{}:{}: ", line + 1, column + 1)?
+                }
+                None => write!(f, "statement #{}: ", conversion.statement_idx.0)?,
+            }
+            write!(f, "{} `{}`", conversion.kind, conversion.libfunc)?;
+            if let Some(function) = &conversion.function {
+                write!(f, " in `{function}`")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if `function` is one of [PANICKING_SHORTCUT_FUNCTIONS], identified by the last
+/// segment of its debug name.
+fn is_panicking_shortcut(function: &FunctionId) -> bool {
+    let Some(name) = &function.debug_name else {
+        return false;
+    };
+    PANICKING_SHORTCUT_FUNCTIONS.iter().any(|shortcut| name.rsplit("::").next() == Some(shortcut))
+}
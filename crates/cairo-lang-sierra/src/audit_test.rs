@@ -0,0 +1,62 @@
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{ConversionAuditReport, RiskyConversionKind};
+use crate::debug_info::SourceCodeLocation;
+use crate::program::StatementIdx;
+use crate::ProgramParser;
+
+#[test]
+fn audit_risky_conversions() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            type felt252 = felt252;
+            type u8 = u8;
+            type u16 = u16;
+            type StorageBaseAddress = StorageBaseAddress;
+
+            libfunc storage_base_address_from_felt252 = storage_base_address_from_felt252;
+            libfunc u8_try_from_felt252 = u8_try_from_felt252;
+            libfunc downcast<felt252, u8> = downcast<felt252, u8>;
+            libfunc downcast<u16, u8> = downcast<u16, u8>;
+            libfunc function_call<user@core::starknet::SyscallResultTraitImpl::<core::felt252>::unwrap_syscall> = function_call<user@core::starknet::SyscallResultTraitImpl::<core::felt252>::unwrap_syscall>;
+
+            storage_base_address_from_felt252([0], [1]) -> ([0], [2]);
+            return([0], [2]);
+            u8_try_from_felt252([0], [1]) { fallthrough([0], [2]) 4([0]) };
+            downcast<felt252, u8>([0], [1]) { fallthrough([0], [2]) 4([0]) };
+            downcast<u16, u8>([0], [3]) { fallthrough([0], [2]) 5([0]) };
+            return([0], [2]);
+            function_call<user@core::starknet::SyscallResultTraitImpl::<core::felt252>::unwrap_syscall>([1]) -> ([2]);
+            return([2]);
+
+            test::address@0([0]: RangeCheck, [1]: felt252) -> (RangeCheck, StorageBaseAddress);
+            test::narrow@2([0]: RangeCheck, [1]: felt252, [3]: u16) -> (RangeCheck, u8);
+            test::call@6([1]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let location = |line| SourceCodeLocation { file: "lib.cairo".into(), line, column: 4 };
+    let report = ConversionAuditReport::new(
+        &program,
+        &UnorderedHashMap::from_iter([
+            (StatementIdx(0), location(1)),
+            (StatementIdx(2), location(5)),
+            (StatementIdx(3), location(6)),
+        ]),
+    );
+
+    assert_eq!(report.count(RiskyConversionKind::UncheckedCast), 1);
+    assert_eq!(report.count(RiskyConversionKind::Felt252Downcast), 2);
+    assert_eq!(report.count(RiskyConversionKind::PanickingShortcut), 1);
+    assert_eq!(
+        report.to_string(),
+        indoc! {"
+            lib.cairo:2:5: unchecked cast `storage_base_address_from_felt252` in `test::address`
+            lib.cairo:6:5: felt252 downcast `u8_try_from_felt252` in `test::narrow`
+            lib.cairo:7:5: felt252 downcast `downcast<felt252, u8>` in `test::narrow`
+            statement #6: panicking shortcut `function_call<user@core::starknet::SyscallResultTraitImpl::<core::felt252>::unwrap_syscall>` in `test::call`
+        "}
+    );
+}
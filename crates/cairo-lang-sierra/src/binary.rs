@@ -0,0 +1,518 @@
+//! A compact binary encoding of Sierra programs.
+//!
+//! The encoding starts with a header of the [MAGIC] bytes and the [FORMAT_VERSION], followed by a
+//! table of all the strings of the program (debug names and generic ids), and then the program
+//! itself, referring to the strings by their index in the table. Integers are encoded as LEB128
+//! varints, and ids whose numeric value is derived from their debug name are encoded by the name
+//! alone.
+
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use const_fnv1a_hash::fnv1a_hash_str_64;
+use num_bigint::{BigInt, BigUint, Sign};
+use smol_str::SmolStr;
+use thiserror::Error;
+
+use crate::ids::{
+    ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericLibfuncId, GenericTypeId, UserTypeId,
+    VarId,
+};
+use crate::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, ConcreteTypeLongId, DeclaredTypeInfo,
+    Function, FunctionSignature, GenericArg, Invocation, LibfuncDeclaration, Param, Program,
+    Statement, StatementIdx, TypeDeclaration,
+};
+
+#[cfg(test)]
+#[path = "binary_test.rs"]
+mod test;
+
+/// The bytes every binary encoded program starts with.
+pub const MAGIC: [u8; 4] = *b"SRAB";
+/// The version of the binary encoding, following the magic bytes.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Error decoding a binary encoded program.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum BinaryDecodeError {
+    #[error("Input is not a binary encoded Sierra program.")]
+    InvalidMagic,
+    #[error("Unsupported binary Sierra format version {0}, expected {FORMAT_VERSION}.")]
+    UnsupportedVersion(u8),
+    #[error("Unexpected end of input.")]
+    UnexpectedEnd,
+    #[error("Integer out of range.")]
+    IntegerOutOfRange,
+    #[error("Invalid {kind} tag {tag}.")]
+    InvalidTag { kind: &'static str, tag: u8 },
+    #[error("String index {0} is out of the strings table.")]
+    InvalidStringIndex(usize),
+    #[error("Invalid UTF-8 string.")]
+    InvalidUtf8,
+    #[error("Unexpected data after the end of the program.")]
+    TrailingData,
+}
+
+impl Program {
+    /// Encodes the program into its binary form.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut program_encoder = Encoder::default();
+        program_encoder.program(self);
+        let mut result = MAGIC.to_vec();
+        result.push(FORMAT_VERSION);
+        let mut strings_encoder = Encoder::default();
+        strings_encoder.usize(program_encoder.strings.len());
+        for s in program_encoder.strings.keys() {
+            strings_encoder.bytes(s.as_bytes());
+        }
+        result.extend(strings_encoder.data);
+        result.extend(program_encoder.data);
+        result
+    }
+
+    /// Decodes a program from its binary form, as returned by [Program::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryDecodeError> {
+        let Some(rest) = bytes.strip_prefix(&MAGIC) else {
+            return Err(BinaryDecodeError::InvalidMagic);
+        };
+        let mut decoder = Decoder { data: rest, strings: vec![] };
+        let version = decoder.u8()?;
+        if version != FORMAT_VERSION {
+            return Err(BinaryDecodeError::UnsupportedVersion(version));
+        }
+        decoder.strings = decoder.vec(|decoder| {
+            let bytes = decoder.bytes()?;
+            Ok(SmolStr::new(
+                std::str::from_utf8(bytes).map_err(|_| BinaryDecodeError::InvalidUtf8)?,
+            ))
+        })?;
+        let program = decoder.program()?;
+        if !decoder.data.is_empty() {
+            return Err(BinaryDecodeError::TrailingData);
+        }
+        Ok(program)
+    }
+}
+
+/// The encoding of an id: its numeric value, its debug name, or both.
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum IdTag {
+    /// Only the numeric value.
+    Numeric = 0,
+    /// Only the debug name, from which the numeric value is derived.
+    Named = 1,
+    /// Both the numeric value and the debug name.
+    NumericAndNamed = 2,
+}
+
+/// The tags of the variants of [GenericArg].
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum GenericArgTag {
+    UserType = 0,
+    Type = 1,
+    Value = 2,
+    UserFunc = 3,
+    Libfunc = 4,
+}
+
+/// Encodes the parts of a program into bytes, collecting the used strings.
+#[derive(Default)]
+struct Encoder {
+    data: Vec<u8>,
+    /// The strings used by the program, mapped to their index in the strings table.
+    strings: OrderedHashMap<SmolStr, usize>,
+}
+impl Encoder {
+    fn program(&mut self, program: &Program) {
+        self.slice(&program.type_declarations, Self::type_declaration);
+        self.slice(&program.libfunc_declarations, Self::libfunc_declaration);
+        self.slice(&program.statements, Self::statement);
+        self.slice(&program.funcs, Self::function);
+    }
+
+    fn type_declaration(&mut self, declaration: &TypeDeclaration) {
+        let TypeDeclaration {
+            id, long_id: ConcreteTypeLongId { generic_id, generic_args }, ..
+        } = declaration;
+        self.id(id.id, &id.debug_name);
+        self.string(&generic_id.0);
+        self.slice(generic_args, Self::generic_arg);
+        match &declaration.declared_type_info {
+            None => self.u8(0),
+            Some(DeclaredTypeInfo { storable, droppable, duplicatable, zero_sized }) => {
+                self.u8(1);
+                self.u8(*storable as u8
+                    | (*droppable as u8) << 1
+                    | (*duplicatable as u8) << 2
+                    | (*zero_sized as u8) << 3);
+            }
+        }
+    }
+
+    fn libfunc_declaration(&mut self, declaration: &LibfuncDeclaration) {
+        let LibfuncDeclaration { id, long_id: ConcreteLibfuncLongId { generic_id, generic_args } } =
+            declaration;
+        self.libfunc_id(id);
+        self.string(&generic_id.0);
+        self.slice(generic_args, Self::generic_arg);
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Invocation(Invocation { libfunc_id, args, branches }) => {
+                self.u8(0);
+                self.libfunc_id(libfunc_id);
+                self.slice(args, Self::var_id);
+                self.slice(branches, |encoder, BranchInfo { target, results }| {
+                    // Statement targets are shifted by one to make room for the fallthrough.
+                    encoder.usize(match target {
+                        BranchTarget::Fallthrough => 0,
+                        BranchTarget::Statement(StatementIdx(idx)) => idx + 1,
+                    });
+                    encoder.slice(results, Self::var_id);
+                });
+            }
+            Statement::Return(vars) => {
+                self.u8(1);
+                self.slice(vars, Self::var_id);
+            }
+        }
+    }
+
+    fn function(&mut self, function: &Function) {
+        let Function {
+            id, signature: FunctionSignature { param_types, ret_types }, params, ..
+        } = function;
+        self.function_id(id);
+        self.slice(param_types, Self::type_id);
+        self.slice(ret_types, Self::type_id);
+        self.slice(params, |encoder, Param { id, ty }| {
+            encoder.var_id(id);
+            encoder.type_id(ty);
+        });
+        self.usize(function.entry_point.0);
+    }
+
+    fn generic_arg(&mut self, arg: &GenericArg) {
+        match arg {
+            GenericArg::UserType(id) => {
+                self.u8(GenericArgTag::UserType as u8);
+                self.user_type_id(id);
+            }
+            GenericArg::Type(id) => {
+                self.u8(GenericArgTag::Type as u8);
+                self.type_id(id);
+            }
+            GenericArg::Value(value) => {
+                self.u8(GenericArgTag::Value as u8);
+                let (sign, magnitude) = value.to_bytes_le();
+                self.u8(match sign {
+                    Sign::Minus => 0,
+                    Sign::NoSign => 1,
+                    Sign::Plus => 2,
+                });
+                self.bytes(&magnitude);
+            }
+            GenericArg::UserFunc(id) => {
+                self.u8(GenericArgTag::UserFunc as u8);
+                self.function_id(id);
+            }
+            GenericArg::Libfunc(id) => {
+                self.u8(GenericArgTag::Libfunc as u8);
+                self.libfunc_id(id);
+            }
+        }
+    }
+
+    fn type_id(&mut self, id: &ConcreteTypeId) {
+        self.id(id.id, &id.debug_name);
+    }
+
+    fn libfunc_id(&mut self, id: &ConcreteLibfuncId) {
+        self.id(id.id, &id.debug_name);
+    }
+
+    fn function_id(&mut self, id: &FunctionId) {
+        self.id(id.id, &id.debug_name);
+    }
+
+    fn var_id(&mut self, id: &VarId) {
+        self.id(id.id, &id.debug_name);
+    }
+
+    /// Encodes an id with a numeric value.
+    fn id(&mut self, id: u64, debug_name: &Option<SmolStr>) {
+        let Some(name) = debug_name else {
+            self.u8(IdTag::Numeric as u8);
+            self.u64(id);
+            return;
+        };
+        if fnv1a_hash_str_64(name) == id {
+            self.u8(IdTag::Named as u8);
+        } else {
+            self.u8(IdTag::NumericAndNamed as u8);
+            self.u64(id);
+        }
+        self.string(name);
+    }
+
+    fn user_type_id(&mut self, id: &UserTypeId) {
+        let Some(name) = &id.debug_name else {
+            self.u8(IdTag::Numeric as u8);
+            self.bytes(&id.id.to_bytes_le());
+            return;
+        };
+        if UserTypeId::from_string(name.clone()).id == id.id {
+            self.u8(IdTag::Named as u8);
+        } else {
+            self.u8(IdTag::NumericAndNamed as u8);
+            self.bytes(&id.id.to_bytes_le());
+        }
+        self.string(name);
+    }
+
+    fn string(&mut self, s: &SmolStr) {
+        let next_idx = self.strings.len();
+        let idx = *self.strings.entry(s.clone()).or_insert(next_idx);
+        self.usize(idx);
+    }
+
+    fn slice<T>(&mut self, items: &[T], mut encode_item: impl FnMut(&mut Self, &T)) {
+        self.usize(items.len());
+        for item in items {
+            encode_item(self, item);
+        }
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.usize(bytes.len());
+        self.data.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    /// Encodes `value` as an LEB128 varint.
+    fn u64(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.data.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.data.push(value as u8);
+    }
+}
+
+/// Decodes the parts of a program from bytes.
+struct Decoder<'a> {
+    /// The data left to decode.
+    data: &'a [u8],
+    /// The strings table of the program.
+    strings: Vec<SmolStr>,
+}
+impl Decoder<'_> {
+    fn program(&mut self) -> Result<Program, BinaryDecodeError> {
+        Ok(Program {
+            type_declarations: self.vec(Self::type_declaration)?,
+            libfunc_declarations: self.vec(Self::libfunc_declaration)?,
+            statements: self.vec(Self::statement)?,
+            funcs: self.vec(Self::function)?,
+        })
+    }
+
+    fn type_declaration(&mut self) -> Result<TypeDeclaration, BinaryDecodeError> {
+        let id = self.type_id()?;
+        let generic_id = GenericTypeId(self.string()?);
+        let generic_args = self.vec(Self::generic_arg)?;
+        let declared_type_info = match self.u8()? {
+            0 => None,
+            1 => {
+                let flags = self.u8()?;
+                Some(DeclaredTypeInfo {
+                    storable: flags & 1 != 0,
+                    droppable: flags & (1 << 1) != 0,
+                    duplicatable: flags & (1 << 2) != 0,
+                    zero_sized: flags & (1 << 3) != 0,
+                })
+            }
+            tag => return Err(BinaryDecodeError::InvalidTag { kind: "type info", tag }),
+        };
+        Ok(TypeDeclaration {
+            id,
+            long_id: ConcreteTypeLongId { generic_id, generic_args },
+            declared_type_info,
+        })
+    }
+
+    fn libfunc_declaration(&mut self) -> Result<LibfuncDeclaration, BinaryDecodeError> {
+        let id = self.libfunc_id()?;
+        let generic_id = GenericLibfuncId(self.string()?);
+        let generic_args = self.vec(Self::generic_arg)?;
+        Ok(LibfuncDeclaration { id, long_id: ConcreteLibfuncLongId { generic_id, generic_args } })
+    }
+
+    fn statement(&mut self) -> Result<Statement, BinaryDecodeError> {
+        match self.u8()? {
+            0 => {
+                let libfunc_id = self.libfunc_id()?;
+                let args = self.vec(Self::var_id)?;
+                let branches = self.vec(|decoder| {
+                    let target = match decoder.usize()? {
+                        0 => BranchTarget::Fallthrough,
+                        idx => BranchTarget::Statement(StatementIdx(idx - 1)),
+                    };
+                    Ok(BranchInfo { target, results: decoder.vec(Self::var_id)? })
+                })?;
+                Ok(Statement::Invocation(Invocation { libfunc_id, args, branches }))
+            }
+            1 => Ok(Statement::Return(self.vec(Self::var_id)?)),
+            tag => Err(BinaryDecodeError::InvalidTag { kind: "statement", tag }),
+        }
+    }
+
+    fn function(&mut self) -> Result<Function, BinaryDecodeError> {
+        let id = self.function_id()?;
+        let param_types = self.vec(Self::type_id)?;
+        let ret_types = self.vec(Self::type_id)?;
+        let params =
+            self.vec(|decoder| Ok(Param { id: decoder.var_id()?, ty: decoder.type_id()? }))?;
+        let entry_point = StatementIdx(self.usize()?);
+        Ok(Function {
+            id,
+            signature: FunctionSignature { param_types, ret_types },
+            params,
+            entry_point,
+        })
+    }
+
+    fn generic_arg(&mut self) -> Result<GenericArg, BinaryDecodeError> {
+        let tag = self.u8()?;
+        Ok(match tag {
+            tag if tag == GenericArgTag::UserType as u8 => {
+                GenericArg::UserType(self.user_type_id()?)
+            }
+            tag if tag == GenericArgTag::Type as u8 => GenericArg::Type(self.type_id()?),
+            tag if tag == GenericArgTag::Value as u8 => {
+                let sign = match self.u8()? {
+                    0 => Sign::Minus,
+                    1 => Sign::NoSign,
+                    2 => Sign::Plus,
+                    tag => return Err(BinaryDecodeError::InvalidTag { kind: "sign", tag }),
+                };
+                GenericArg::Value(BigInt::from_bytes_le(sign, self.bytes()?))
+            }
+            tag if tag == GenericArgTag::UserFunc as u8 => {
+                GenericArg::UserFunc(self.function_id()?)
+            }
+            tag if tag == GenericArgTag::Libfunc as u8 => GenericArg::Libfunc(self.libfunc_id()?),
+            tag => return Err(BinaryDecodeError::InvalidTag { kind: "generic argument", tag }),
+        })
+    }
+
+    fn type_id(&mut self) -> Result<ConcreteTypeId, BinaryDecodeError> {
+        let (id, debug_name) = self.id()?;
+        Ok(ConcreteTypeId { id, debug_name })
+    }
+
+    fn libfunc_id(&mut self) -> Result<ConcreteLibfuncId, BinaryDecodeError> {
+        let (id, debug_name) = self.id()?;
+        Ok(ConcreteLibfuncId { id, debug_name })
+    }
+
+    fn function_id(&mut self) -> Result<FunctionId, BinaryDecodeError> {
+        let (id, debug_name) = self.id()?;
+        Ok(FunctionId { id, debug_name })
+    }
+
+    fn var_id(&mut self) -> Result<VarId, BinaryDecodeError> {
+        let (id, debug_name) = self.id()?;
+        Ok(VarId { id, debug_name })
+    }
+
+    /// Decodes the numeric value and the debug name of an id.
+    fn id(&mut self) -> Result<(u64, Option<SmolStr>), BinaryDecodeError> {
+        match self.u8()? {
+            tag if tag == IdTag::Numeric as u8 => Ok((self.u64()?, None)),
+            tag if tag == IdTag::Named as u8 => {
+                let name = self.string()?;
+                Ok((fnv1a_hash_str_64(&name), Some(name)))
+            }
+            tag if tag == IdTag::NumericAndNamed as u8 => Ok((self.u64()?, Some(self.string()?))),
+            tag => Err(BinaryDecodeError::InvalidTag { kind: "id", tag }),
+        }
+    }
+
+    fn user_type_id(&mut self) -> Result<UserTypeId, BinaryDecodeError> {
+        match self.u8()? {
+            tag if tag == IdTag::Numeric as u8 => {
+                Ok(UserTypeId { id: BigUint::from_bytes_le(self.bytes()?), debug_name: None })
+            }
+            tag if tag == IdTag::Named as u8 => Ok(UserTypeId::from_string(self.string()?)),
+            tag if tag == IdTag::NumericAndNamed as u8 => {
+                let id = BigUint::from_bytes_le(self.bytes()?);
+                Ok(UserTypeId { id, debug_name: Some(self.string()?) })
+            }
+            tag => Err(BinaryDecodeError::InvalidTag { kind: "user type id", tag }),
+        }
+    }
+
+    fn string(&mut self) -> Result<SmolStr, BinaryDecodeError> {
+        let idx = self.usize()?;
+        self.strings.get(idx).cloned().ok_or(BinaryDecodeError::InvalidStringIndex(idx))
+    }
+
+    fn vec<T>(
+        &mut self,
+        mut decode_item: impl FnMut(&mut Self) -> Result<T, BinaryDecodeError>,
+    ) -> Result<Vec<T>, BinaryDecodeError> {
+        let len = self.usize()?;
+        // Not preallocating `len` items, as it is not validated against the size of the input.
+        let mut items = vec![];
+        for _ in 0..len {
+            items.push(decode_item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn bytes(&mut self) -> Result<&[u8], BinaryDecodeError> {
+        let len = self.usize()?;
+        if len > self.data.len() {
+            return Err(BinaryDecodeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, BinaryDecodeError> {
+        let (value, rest) = self.data.split_first().ok_or(BinaryDecodeError::UnexpectedEnd)?;
+        self.data = rest;
+        Ok(*value)
+    }
+
+    fn usize(&mut self) -> Result<usize, BinaryDecodeError> {
+        self.u64()?.try_into().map_err(|_| BinaryDecodeError::IntegerOutOfRange)
+    }
+
+    /// Decodes an LEB128 varint.
+    fn u64(&mut self) -> Result<u64, BinaryDecodeError> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = (byte & 0x7f) as u64;
+            if bits << shift >> shift != bits {
+                return Err(BinaryDecodeError::IntegerOutOfRange);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BinaryDecodeError::IntegerOutOfRange)
+    }
+}
@@ -0,0 +1,88 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::{BinaryDecodeError, FORMAT_VERSION, MAGIC};
+use crate::ids::{ConcreteTypeId, VarId};
+use crate::program::{GenericArg, Program, Statement};
+use crate::ProgramParser;
+
+fn example_program() -> Program {
+    ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252> [storable: true, drop: true, dup: true, zero_sized: false];
+            type [7] = Struct<ut@Tuple, felt252>;
+            type MyEnum = Enum<ut@test::MyEnum, felt252, [7]>;
+
+            libfunc felt252_const<-5> = felt252_const<-5>;
+            libfunc felt252_const<0> = felt252_const<0>;
+            libfunc felt252_const<123456789012345678901234567890> = felt252_const<123456789012345678901234567890>;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc branch_align = branch_align;
+            libfunc drop<NonZeroFelt252> = drop<NonZeroFelt252>;
+            libfunc call_foo = function_call<user@test::foo>;
+
+            felt252_is_zero([0]) { fallthrough() 3([1]) };
+            branch_align() -> ();
+            return([0]);
+            branch_align() -> ();
+            drop<NonZeroFelt252>([1]) -> ();
+            call_foo([0]) -> ([2]);
+            return([2]);
+
+            test::foo@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let program = example_program();
+    let decoded = Program::from_bytes(&program.to_bytes()).unwrap();
+    assert_eq!(decoded, program);
+    // Debug names are ignored by the comparison of ids, so comparing the textual forms as well.
+    assert_eq!(decoded.to_string(), program.to_string());
+}
+
+#[test]
+fn round_trip_with_non_derived_ids() {
+    let mut program = example_program();
+    // An id whose numeric value does not match its debug name.
+    program.type_declarations[0].id = ConcreteTypeId { id: 7, debug_name: Some("felt252".into()) };
+    let Statement::Return(vars) = &mut program.statements[2] else { panic!("Expected a return.") };
+    vars[0] = VarId { id: u64::MAX, debug_name: Some("x".into()) };
+    let decoded = Program::from_bytes(&program.to_bytes()).unwrap();
+    assert_eq!(decoded, program);
+    assert_eq!(decoded.to_string(), program.to_string());
+    let GenericArg::UserType(user_type) = &decoded.type_declarations[2].long_id.generic_args[0]
+    else {
+        panic!("Expected a user type.")
+    };
+    assert_eq!(user_type.debug_name, Some("Tuple".into()));
+}
+
+#[test]
+fn header() {
+    let bytes = example_program().to_bytes();
+    assert_eq!(bytes[..MAGIC.len()], MAGIC);
+    assert_eq!(bytes[MAGIC.len()], FORMAT_VERSION);
+}
+
+#[test_case(b"", BinaryDecodeError::InvalidMagic; "empty")]
+#[test_case(b"{\"version\": 1}", BinaryDecodeError::InvalidMagic; "json")]
+#[test_case(b"SRAB", BinaryDecodeError::UnexpectedEnd; "no version")]
+#[test_case(b"SRAB\x02", BinaryDecodeError::UnsupportedVersion(2); "future version")]
+#[test_case(b"SRAB\x01\x01\x02a", BinaryDecodeError::UnexpectedEnd; "truncated string")]
+#[test_case(b"SRAB\x01\x01\x01\xff", BinaryDecodeError::InvalidUtf8; "invalid utf8")]
+#[test_case(b"SRAB\x01\x00\x01\x01\x00", BinaryDecodeError::InvalidStringIndex(0); "invalid string")]
+#[test_case(
+    b"SRAB\x01\x00\x01\x00\xff\xff\xff\xff\xff\xff\xff\xff\xff\x7f",
+    BinaryDecodeError::IntegerOutOfRange;
+    "varint overflow"
+)]
+#[test_case(b"SRAB\x01\x00\x01\x07", BinaryDecodeError::InvalidTag { kind: "id", tag: 7 }; "bad tag")]
+#[test_case(b"SRAB\x01\x00\x00\x00\x00\x00\x00", BinaryDecodeError::TrailingData; "trailing data")]
+fn invalid_input(bytes: &[u8], expected: BinaryDecodeError) {
+    assert_eq!(Program::from_bytes(bytes), Err(expected));
+}
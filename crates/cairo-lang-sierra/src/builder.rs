@@ -0,0 +1,291 @@
+//! A builder of Sierra programs, for constructing programs in code - e.g. by testing tools and
+//! alternative frontends - instead of generating and parsing their text.
+//!
+//! The ids of the declared items and of the variables are allocated by the builder, and the items
+//! are named by their declarations, so the built program prints as if written by hand.
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use thiserror::Error;
+
+use crate::ids::{
+    ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericLibfuncId, GenericTypeId, VarId,
+};
+use crate::program::{
+    ConcreteLibfuncLongId, ConcreteTypeLongId, Function, GenBranchInfo, GenBranchTarget,
+    GenInvocation, GenStatement, GenericArg, LibfuncDeclaration, Param, Program, StatementIdx,
+    TypeDeclaration,
+};
+
+#[cfg(test)]
+#[path = "builder_test.rs"]
+mod test;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ProgramBuilderError {
+    #[error("#{statement_idx}: Variable {var_id} is undefined.")]
+    UndefinedVariable { statement_idx: StatementIdx, var_id: VarId },
+    #[error("#{0}: Statement added outside of a function.")]
+    StatementOutsideFunction(StatementIdx),
+    #[error("Label #{0} is not placed.")]
+    UnplacedLabel(usize),
+    #[error("Label #{0} is placed twice.")]
+    LabelPlacedTwice(usize),
+    #[error("Function `{0}` has no body.")]
+    MissingFunctionBody(FunctionId),
+    #[error("The body of function `{0}` is already started.")]
+    FunctionBodyStartedTwice(FunctionId),
+}
+
+/// A position in the statements of a program under construction, used as a branch target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Label(usize);
+
+/// A branch target of a statement under construction.
+pub type LabelTarget = GenBranchTarget<Label>;
+
+/// A function declared in a [ProgramBuilder].
+#[derive(Debug)]
+struct FunctionDeclaration {
+    id: FunctionId,
+    param_types: Vec<ConcreteTypeId>,
+    ret_types: Vec<ConcreteTypeId>,
+    /// The function, with its entry point, if its body was started.
+    function: Option<Function>,
+}
+
+/// Builder of a Sierra program.
+///
+/// Statements are added to the body of the last started function, and may branch to labels placed
+/// before or after them.
+#[derive(Debug, Default)]
+pub struct ProgramBuilder {
+    type_declarations: Vec<TypeDeclaration>,
+    libfunc_declarations: Vec<LibfuncDeclaration>,
+    statements: Vec<GenStatement<Label>>,
+    funcs: Vec<FunctionDeclaration>,
+    /// The declared types and libfuncs, for reusing their declarations.
+    type_ids: UnorderedHashMap<ConcreteTypeLongId, ConcreteTypeId>,
+    libfunc_ids: UnorderedHashMap<ConcreteLibfuncLongId, ConcreteLibfuncId>,
+    /// The statement each label is placed before.
+    labels: Vec<Option<StatementIdx>>,
+    /// The variables defined in the body of the current function, if a body was started.
+    defined_vars: Option<UnorderedHashSet<VarId>>,
+    next_var_id: u64,
+    /// The first error found while building, reported when the program is built.
+    error: Option<ProgramBuilderError>,
+}
+impl ProgramBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a concrete type, or returns the id of its existing declaration.
+    pub fn declare_type(
+        &mut self,
+        generic_id: impl Into<GenericTypeId>,
+        generic_args: impl IntoIterator<Item = GenericArg>,
+    ) -> ConcreteTypeId {
+        let long_id = ConcreteTypeLongId {
+            generic_id: generic_id.into(),
+            generic_args: generic_args.into_iter().collect(),
+        };
+        if let Some(id) = self.type_ids.get(&long_id) {
+            return id.clone();
+        }
+        let id = ConcreteTypeId {
+            id: self.type_declarations.len() as u64,
+            debug_name: Some(long_id.to_string().into()),
+        };
+        self.type_ids.insert(long_id.clone(), id.clone());
+        self.type_declarations.push(TypeDeclaration {
+            id: id.clone(),
+            long_id,
+            declared_type_info: None,
+        });
+        id
+    }
+
+    /// Declares a concrete libfunc, or returns the id of its existing declaration.
+    pub fn declare_libfunc(
+        &mut self,
+        generic_id: impl Into<GenericLibfuncId>,
+        generic_args: impl IntoIterator<Item = GenericArg>,
+    ) -> ConcreteLibfuncId {
+        let long_id = ConcreteLibfuncLongId {
+            generic_id: generic_id.into(),
+            generic_args: generic_args.into_iter().collect(),
+        };
+        if let Some(id) = self.libfunc_ids.get(&long_id) {
+            return id.clone();
+        }
+        let id = ConcreteLibfuncId {
+            id: self.libfunc_declarations.len() as u64,
+            debug_name: Some(long_id.to_string().into()),
+        };
+        self.libfunc_ids.insert(long_id.clone(), id.clone());
+        self.libfunc_declarations.push(LibfuncDeclaration { id: id.clone(), long_id });
+        id
+    }
+
+    /// Declares a function with the given signature, to be defined by [Self::begin_function].
+    /// Functions may be declared before their bodies, so that they can be called earlier.
+    pub fn declare_function(
+        &mut self,
+        name: &str,
+        param_types: Vec<ConcreteTypeId>,
+        ret_types: Vec<ConcreteTypeId>,
+    ) -> FunctionId {
+        let id = FunctionId { id: self.funcs.len() as u64, debug_name: Some(name.into()) };
+        self.funcs.push(FunctionDeclaration {
+            id: id.clone(),
+            param_types,
+            ret_types,
+            function: None,
+        });
+        id
+    }
+
+    /// Starts the body of a declared function at the next statement, and returns the variables of
+    /// its parameters. The following statements are added to this body.
+    pub fn begin_function(&mut self, function_id: &FunctionId) -> Vec<VarId> {
+        let entry_point = StatementIdx(self.statements.len());
+        let FunctionDeclaration { param_types, ret_types, function, .. } =
+            &mut self.funcs[function_id.id as usize];
+        let params = param_types
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| Param { id: VarId::new(idx as u64), ty: ty.clone() })
+            .collect::<Vec<_>>();
+        let vars = params.iter().map(|param| param.id.clone()).collect::<Vec<_>>();
+        if function.is_some() {
+            self.set_error(ProgramBuilderError::FunctionBodyStartedTwice(function_id.clone()));
+        } else {
+            *function =
+                Some(Function::new(function_id.clone(), params, ret_types.clone(), entry_point));
+        }
+        self.next_var_id = vars.len() as u64;
+        self.defined_vars = Some(vars.iter().cloned().collect());
+        vars
+    }
+
+    /// Returns a new label, to be placed by [Self::place_label].
+    pub fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+        Label(self.labels.len() - 1)
+    }
+
+    /// Places a label before the next statement.
+    pub fn place_label(&mut self, label: Label) {
+        let statement_idx = StatementIdx(self.statements.len());
+        if self.labels[label.0].replace(statement_idx).is_some() {
+            self.set_error(ProgramBuilderError::LabelPlacedTwice(label.0));
+        }
+    }
+
+    /// Adds an invocation of a libfunc continuing to the next statement, and returns the
+    /// `n_results` variables of its results.
+    pub fn invoke(
+        &mut self,
+        libfunc_id: &ConcreteLibfuncId,
+        args: &[VarId],
+        n_results: usize,
+    ) -> Vec<VarId> {
+        self.branch(libfunc_id, args, &[(LabelTarget::Fallthrough, n_results)]).pop().unwrap()
+    }
+
+    /// Adds an invocation of a branching libfunc, given the target and the number of results of
+    /// each of its branches, and returns the variables of the results of each branch.
+    pub fn branch(
+        &mut self,
+        libfunc_id: &ConcreteLibfuncId,
+        args: &[VarId],
+        branches: &[(LabelTarget, usize)],
+    ) -> Vec<Vec<VarId>> {
+        self.use_vars(args);
+        let results = branches
+            .iter()
+            .map(|(_, n_results)| (0..*n_results).map(|_| self.new_var()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        self.statements.push(GenStatement::Invocation(GenInvocation {
+            libfunc_id: libfunc_id.clone(),
+            args: args.to_vec(),
+            branches: branches
+                .iter()
+                .zip(&results)
+                .map(|((target, _), results)| GenBranchInfo {
+                    target: target.clone(),
+                    results: results.clone(),
+                })
+                .collect(),
+        }));
+        results
+    }
+
+    /// Adds a return of the given variables.
+    pub fn ret(&mut self, vars: &[VarId]) {
+        self.use_vars(vars);
+        self.statements.push(GenStatement::Return(vars.to_vec()));
+    }
+
+    /// Builds the program, resolving the labels.
+    pub fn build(self) -> Result<Program, ProgramBuilderError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let labels = self
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(idx, statement_idx)| {
+                statement_idx.ok_or(ProgramBuilderError::UnplacedLabel(idx))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let funcs = self
+            .funcs
+            .into_iter()
+            .map(|declaration| {
+                declaration.function.ok_or(ProgramBuilderError::MissingFunctionBody(declaration.id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Program {
+            type_declarations: self.type_declarations,
+            libfunc_declarations: self.libfunc_declarations,
+            statements: self
+                .statements
+                .into_iter()
+                .map(|statement| statement.map(|label| labels[label.0]))
+                .collect(),
+            funcs,
+        })
+    }
+
+    /// Marks the variables as used by the next statement, checking that they are defined in the
+    /// current function.
+    fn use_vars(&mut self, vars: &[VarId]) {
+        let statement_idx = StatementIdx(self.statements.len());
+        let Some(defined_vars) = &self.defined_vars else {
+            self.set_error(ProgramBuilderError::StatementOutsideFunction(statement_idx));
+            return;
+        };
+        if let Some(var_id) = vars.iter().find(|var_id| !defined_vars.contains(*var_id)) {
+            let var_id = var_id.clone();
+            self.set_error(ProgramBuilderError::UndefinedVariable { statement_idx, var_id });
+        }
+    }
+
+    /// Returns a new variable of the current function.
+    fn new_var(&mut self) -> VarId {
+        let var_id = VarId::new(self.next_var_id);
+        self.next_var_id += 1;
+        if let Some(defined_vars) = &mut self.defined_vars {
+            defined_vars.insert(var_id.clone());
+        }
+        var_id
+    }
+
+    /// Records an error, unless an earlier one was recorded.
+    fn set_error(&mut self, error: ProgramBuilderError) {
+        self.error.get_or_insert(error);
+    }
+}
@@ -0,0 +1,113 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{LabelTarget, ProgramBuilder, ProgramBuilderError};
+use crate::extensions::core::{CoreLibfunc, CoreType};
+use crate::ids::VarId;
+use crate::program::{GenericArg, StatementIdx};
+use crate::program_registry::ProgramRegistry;
+use crate::validate::validate_program;
+
+#[test]
+fn build_program() {
+    let mut builder = ProgramBuilder::new();
+    let felt252 = builder.declare_type("felt252", []);
+    let non_zero = builder.declare_type("NonZero", [GenericArg::Type(felt252.clone())]);
+    assert_eq!(builder.declare_type("felt252", []), felt252);
+    let is_zero = builder.declare_libfunc("felt252_is_zero", []);
+    let branch_align = builder.declare_libfunc("branch_align", []);
+    let drop = builder.declare_libfunc("drop", [GenericArg::Type(non_zero)]);
+    let foo = builder.declare_function("test::foo", vec![felt252.clone()], vec![felt252]);
+    let call_foo = builder.declare_libfunc("function_call", [GenericArg::UserFunc(foo.clone())]);
+
+    let params = builder.begin_function(&foo);
+    let non_zero_label = builder.new_label();
+    let branches = builder.branch(
+        &is_zero,
+        &params,
+        &[(LabelTarget::Fallthrough, 0), (LabelTarget::Statement(non_zero_label), 1)],
+    );
+    builder.invoke(&branch_align, &[], 0);
+    builder.ret(&params);
+    builder.place_label(non_zero_label);
+    builder.invoke(&branch_align, &[], 0);
+    builder.invoke(&drop, &branches[1], 0);
+    let results = builder.invoke(&call_foo, &params, 1);
+    builder.ret(&results);
+
+    let program = builder.build().unwrap();
+    assert_eq!(
+        program.to_string(),
+        indoc! {"
+            type felt252 = felt252;
+            type NonZero<felt252> = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc branch_align = branch_align;
+            libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+            libfunc function_call<user@test::foo> = function_call<user@test::foo>;
+
+            felt252_is_zero([0]) { fallthrough() 3([1]) }; // 0
+            branch_align() -> (); // 1
+            return([0]); // 2
+            branch_align() -> (); // 3
+            drop<NonZero<felt252>>([1]) -> (); // 4
+            function_call<user@test::foo>([0]) -> ([2]); // 5
+            return([2]); // 6
+
+            test::foo@0([0]: felt252) -> (felt252);
+        "}
+    );
+    assert_eq!(validate_program(&program), Ok(()));
+    ProgramRegistry::<CoreType, CoreLibfunc>::new(&program).unwrap();
+}
+
+#[test]
+fn build_program_with_undefined_variable() {
+    let mut builder = ProgramBuilder::new();
+    let felt252 = builder.declare_type("felt252", []);
+    let foo = builder.declare_function("test::foo", vec![], vec![felt252]);
+    builder.begin_function(&foo);
+    builder.ret(&[VarId::new(0)]);
+    assert_eq!(
+        builder.build(),
+        Err(ProgramBuilderError::UndefinedVariable {
+            statement_idx: StatementIdx(0),
+            var_id: VarId::new(0)
+        })
+    );
+}
+
+#[test]
+fn build_program_with_unplaced_label() {
+    let mut builder = ProgramBuilder::new();
+    let felt252 = builder.declare_type("felt252", []);
+    let is_zero = builder.declare_libfunc("felt252_is_zero", []);
+    let foo = builder.declare_function("test::foo", vec![felt252.clone()], vec![felt252]);
+    let params = builder.begin_function(&foo);
+    let label = builder.new_label();
+    builder.branch(
+        &is_zero,
+        &params,
+        &[(LabelTarget::Fallthrough, 0), (LabelTarget::Statement(label), 1)],
+    );
+    builder.ret(&params);
+    assert_eq!(builder.build(), Err(ProgramBuilderError::UnplacedLabel(0)));
+}
+
+#[test]
+fn build_program_with_missing_function_body() {
+    let mut builder = ProgramBuilder::new();
+    let foo = builder.declare_function("test::foo", vec![], vec![]);
+    assert_eq!(builder.build(), Err(ProgramBuilderError::MissingFunctionBody(foo)));
+}
+
+#[test]
+fn build_program_with_statement_outside_function() {
+    let mut builder = ProgramBuilder::new();
+    builder.ret(&[]);
+    assert_eq!(
+        builder.build(),
+        Err(ProgramBuilderError::StatementOutsideFunction(StatementIdx(0)))
+    );
+}
@@ -38,6 +38,17 @@ pub struct DebugInfo {
     pub annotations: Annotations,
 }
 
+/// A position in the Cairo source code from which a Sierra statement was generated.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceCodeLocation {
+    /// The full path of the originating file.
+    pub file: String,
+    /// Line index, 0 based.
+    pub line: usize,
+    /// Character index inside the line, 0 based.
+    pub column: usize,
+}
+
 /// Store for non-crucial information about the program, for use by external libraries and tool.
 ///
 /// Keys represent tool namespaces, and values are tool-specific annotations themselves.
@@ -0,0 +1,305 @@
+//! Structural differences between two Sierra programs, e.g. two versions of a contract, for
+//! auditing what changed between them.
+//!
+//! Declarations and functions are matched by their ids, so the programs should use ids derived
+//! from the names of the items (as done when the debug names replace the numeric ids), rather than
+//! ids allocated by order of appearance.
+
+use std::fmt;
+use std::hash::Hash;
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::program::{
+    Function, LibfuncDeclaration, Program, Statement, StatementIdx, TypeDeclaration,
+};
+
+#[cfg(test)]
+#[path = "diff_test.rs"]
+mod test;
+
+/// The maximal size of the table used for diffing the statements of a function. Larger changes
+/// are reported as the removal of all the old statements and the addition of all the new ones.
+const MAX_STATEMENTS_DIFF_TABLE_SIZE: usize = 1 << 24;
+
+/// An item present in both programs, with different definitions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Changed<T> {
+    pub old: T,
+    pub new: T,
+}
+
+/// The differences between the items of a single kind in two programs, each ordered as in the
+/// program containing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemsDiff<T, TChanged = Changed<T>> {
+    /// The items only in the new program.
+    pub added: Vec<T>,
+    /// The items only in the old program.
+    pub removed: Vec<T>,
+    /// The items in both programs, with different definitions.
+    pub changed: Vec<TChanged>,
+}
+impl<T, TChanged> Default for ItemsDiff<T, TChanged> {
+    fn default() -> Self {
+        Self { added: vec![], removed: vec![], changed: vec![] }
+    }
+}
+impl<T, TChanged> ItemsDiff<T, TChanged> {
+    /// Returns true if the items are the same in both programs.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A statement in the diff of the statements of a function.
+/// The branch targets of the statements are relative to the entry point of the function, so that
+/// moving a function does not change its statements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StatementDiff {
+    Unchanged(Statement),
+    Removed(Statement),
+    Added(Statement),
+}
+
+/// A function present in both programs, with a different signature or different statements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionDiff {
+    pub old: Function,
+    pub new: Function,
+    /// The statements of the function in both programs, in order, with the unchanged statements
+    /// between the removed and added ones.
+    pub statements: Vec<StatementDiff>,
+}
+impl FunctionDiff {
+    /// Returns true if the parameters or the return types of the function changed.
+    pub fn signature_changed(&self) -> bool {
+        self.old.params != self.new.params || self.old.signature != self.new.signature
+    }
+
+    /// Returns true if any of the statements of the function changed.
+    pub fn statements_changed(&self) -> bool {
+        self.statements.iter().any(|statement| !matches!(statement, StatementDiff::Unchanged(_)))
+    }
+}
+
+/// The structural differences between two Sierra programs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgramDiff {
+    pub types: ItemsDiff<TypeDeclaration>,
+    pub libfuncs: ItemsDiff<LibfuncDeclaration>,
+    pub functions: ItemsDiff<Function, FunctionDiff>,
+}
+impl ProgramDiff {
+    /// Computes the differences between the `old` and the `new` versions of a program.
+    pub fn new(old: &Program, new: &Program) -> Self {
+        let types = diff_items(
+            &old.type_declarations,
+            &new.type_declarations,
+            |declaration| &declaration.id,
+            |old, new| (old != new).then(|| Changed { old: old.clone(), new: new.clone() }),
+        );
+        let libfuncs = diff_items(
+            &old.libfunc_declarations,
+            &new.libfunc_declarations,
+            |declaration| &declaration.id,
+            |old, new| (old != new).then(|| Changed { old: old.clone(), new: new.clone() }),
+        );
+        let old_statements = function_statements(old);
+        let new_statements = function_statements(new);
+        let functions = diff_items(
+            &old.funcs,
+            &new.funcs,
+            |function| &function.id,
+            |old_function, new_function| {
+                let diff = FunctionDiff {
+                    old: old_function.clone(),
+                    new: new_function.clone(),
+                    statements: diff_statements(
+                        &old_statements[&old_function.entry_point],
+                        &new_statements[&new_function.entry_point],
+                    ),
+                };
+                (diff.signature_changed() || diff.statements_changed()).then_some(diff)
+            },
+        );
+        Self { types, libfuncs, functions }
+    }
+
+    /// Returns true if the programs are structurally the same.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.libfuncs.is_empty() && self.functions.is_empty()
+    }
+}
+impl fmt::Display for ProgramDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_items_diff(f, "types", &self.types)?;
+        write_items_diff(f, "libfuncs", &self.libfuncs)?;
+        if !self.functions.added.is_empty() || !self.functions.removed.is_empty() {
+            writeln!(f, "functions:")?;
+            for function in &self.functions.added {
+                writeln!(f, "+ {function};")?;
+            }
+            for function in &self.functions.removed {
+                writeln!(f, "- {function};")?;
+            }
+        }
+        for FunctionDiff { old, new, statements } in &self.functions.changed {
+            writeln!(f, "function {}:", old.id)?;
+            if old.params != new.params || old.signature != new.signature {
+                writeln!(f, "- {old};")?;
+                writeln!(f, "+ {new};")?;
+            }
+            let (mut old_idx, mut new_idx) = (0, 0);
+            for statement in statements {
+                match statement {
+                    StatementDiff::Unchanged(_) => {
+                        old_idx += 1;
+                        new_idx += 1;
+                    }
+                    StatementDiff::Removed(statement) => {
+                        writeln!(f, "- {old_idx}: {statement};")?;
+                        old_idx += 1;
+                    }
+                    StatementDiff::Added(statement) => {
+                        writeln!(f, "+ {new_idx}: {statement};")?;
+                        new_idx += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the added, removed and changed declarations of a single kind, under the given title.
+fn write_items_diff<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    diff: &ItemsDiff<T>,
+) -> fmt::Result {
+    if diff.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "{title}:")?;
+    for item in &diff.added {
+        writeln!(f, "+ {item};")?;
+    }
+    for item in &diff.removed {
+        writeln!(f, "- {item};")?;
+    }
+    for Changed { old, new } in &diff.changed {
+        writeln!(f, "- {old};")?;
+        writeln!(f, "+ {new};")?;
+    }
+    Ok(())
+}
+
+/// Matches the items of the two programs by their keys, and collects the unmatched items, and the
+/// matched items for which `diff_matched` returns a change.
+fn diff_items<'a, T: Clone, TKey: Eq + Hash + 'a, TChanged>(
+    old: &'a [T],
+    new: &'a [T],
+    key: impl Fn(&'a T) -> &'a TKey,
+    diff_matched: impl Fn(&'a T, &'a T) -> Option<TChanged>,
+) -> ItemsDiff<T, TChanged> {
+    let old_by_key: UnorderedHashMap<_, _> = old.iter().map(|item| (key(item), item)).collect();
+    let new_by_key: UnorderedHashMap<_, _> = new.iter().map(|item| (key(item), item)).collect();
+    ItemsDiff {
+        added: new.iter().filter(|item| !old_by_key.contains_key(key(*item))).cloned().collect(),
+        removed: old.iter().filter(|item| !new_by_key.contains_key(key(*item))).cloned().collect(),
+        changed: new
+            .iter()
+            .filter_map(|new_item| diff_matched(old_by_key.get(key(new_item))?, new_item))
+            .collect(),
+    }
+}
+
+/// Returns the statements of each function of the program by its entry point, with the branch
+/// targets relative to the entry point.
+/// The statements of a function are the ones from its entry point up to the next entry point.
+fn function_statements(program: &Program) -> UnorderedHashMap<StatementIdx, Vec<Statement>> {
+    let mut entry_points: Vec<StatementIdx> =
+        program.funcs.iter().map(|function| function.entry_point).collect();
+    entry_points.sort();
+    entry_points.dedup();
+    let ends = entry_points.iter().skip(1).map(|end| end.0).chain([program.statements.len()]);
+    entry_points
+        .iter()
+        .zip(ends)
+        .map(|(entry_point, end)| {
+            let statements = program.statements[entry_point.0.min(end)..end]
+                .iter()
+                .map(|statement| {
+                    statement
+                        .clone()
+                        .map(|target| StatementIdx(target.0.saturating_sub(entry_point.0)))
+                })
+                .collect();
+            (*entry_point, statements)
+        })
+        .collect()
+}
+
+/// Diffs two lists of statements, by their longest common subsequence.
+fn diff_statements(old: &[Statement], new: &[Statement]) -> Vec<StatementDiff> {
+    let prefix_len = old.iter().zip(new).take_while(|(old, new)| old == new).count();
+    let (old_rest, new_rest) = (&old[prefix_len..], &new[prefix_len..]);
+    let suffix_len = old_rest
+        .iter()
+        .rev()
+        .zip(new_rest.iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old_middle = &old_rest[..old_rest.len() - suffix_len];
+    let new_middle = &new_rest[..new_rest.len() - suffix_len];
+
+    let mut diff: Vec<_> =
+        old[..prefix_len].iter().cloned().map(StatementDiff::Unchanged).collect();
+    if (old_middle.len() + 1) * (new_middle.len() + 1) > MAX_STATEMENTS_DIFF_TABLE_SIZE {
+        diff.extend(old_middle.iter().cloned().map(StatementDiff::Removed));
+        diff.extend(new_middle.iter().cloned().map(StatementDiff::Added));
+    } else {
+        diff.extend(diff_by_common_subsequence(old_middle, new_middle));
+    }
+    diff.extend(
+        old_rest[old_rest.len() - suffix_len..].iter().cloned().map(StatementDiff::Unchanged),
+    );
+    diff
+}
+
+/// Diffs two lists of statements by computing the table of the lengths of the longest common
+/// subsequences of their suffixes.
+fn diff_by_common_subsequence(old: &[Statement], new: &[Statement]) -> Vec<StatementDiff> {
+    let width = new.len() + 1;
+    // `lengths[i * width + j]` is the length of the longest common subsequence of `old[i..]` and
+    // `new[j..]`.
+    let mut lengths = vec![0; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+    let mut diff = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(StatementDiff::Unchanged(old[i].clone()));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            diff.push(StatementDiff::Removed(old[i].clone()));
+            i += 1;
+        } else {
+            diff.push(StatementDiff::Added(new[j].clone()));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().cloned().map(StatementDiff::Removed));
+    diff.extend(new[j..].iter().cloned().map(StatementDiff::Added));
+    diff
+}
@@ -0,0 +1,133 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{ProgramDiff, StatementDiff};
+use crate::program::Program;
+use crate::ProgramParser;
+
+fn parse(code: &str) -> Program {
+    ProgramParser::new().parse(code).unwrap()
+}
+
+const OLD_PROGRAM: &str = indoc! {"
+    type felt252 = felt252;
+    type u8 = u8;
+
+    libfunc felt252_add = felt252_add;
+    libfunc store_temp<felt252> = store_temp<felt252>;
+    libfunc function_call<user@test::helper> = function_call<user@test::helper>;
+
+    felt252_add([0], [1]) -> ([2]);
+    store_temp<felt252>([2]) -> ([2]);
+    return([2]);
+    function_call<user@test::helper>([0], [0]) -> ([1]);
+    return([1]);
+    return([0]);
+
+    test::helper@0([0]: felt252, [1]: felt252) -> (felt252);
+    test::main@3([0]: felt252) -> (felt252);
+    test::removed@5([0]: u8) -> (u8);
+"};
+
+#[test]
+fn diff_identical_programs() {
+    let program = parse(OLD_PROGRAM);
+    let diff = ProgramDiff::new(&program, &program);
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn diff_changed_program() {
+    // `helper` is moved after `main`, gets a new statement, and `removed` is replaced by `added`.
+    let new = parse(indoc! {"
+        type felt252 = felt252;
+        type u16 = u16;
+
+        libfunc felt252_add = felt252_add;
+        libfunc felt252_mul = felt252_mul;
+        libfunc store_temp<felt252> = store_temp<felt252>;
+        libfunc function_call<user@test::helper> = function_call<user@test::helper>;
+
+        function_call<user@test::helper>([0], [0]) -> ([1]);
+        return([1]);
+        felt252_mul([0], [1]) -> ([0]);
+        felt252_add([0], [1]) -> ([2]);
+        store_temp<felt252>([2]) -> ([2]);
+        return([2]);
+        return([0]);
+
+        test::main@0([0]: felt252) -> (felt252);
+        test::helper@2([0]: felt252, [1]: felt252) -> (felt252);
+        test::added@6([0]: u16) -> (u16);
+    "});
+    let diff = ProgramDiff::new(&parse(OLD_PROGRAM), &new);
+
+    let [helper_diff] = &diff.functions.changed[..] else {
+        panic!("Expected only `helper` to change.");
+    };
+    assert!(!helper_diff.signature_changed());
+    assert!(matches!(helper_diff.statements[0], StatementDiff::Added(_)));
+    assert!(matches!(helper_diff.statements[1], StatementDiff::Unchanged(_)));
+    assert_eq!(
+        diff.to_string(),
+        indoc! {"
+            types:
+            + type u16 = u16;
+            - type u8 = u8;
+            libfuncs:
+            + libfunc felt252_mul = felt252_mul;
+            functions:
+            + test::added@6([0]: u16) -> (u16);
+            - test::removed@5([0]: u8) -> (u8);
+            function test::helper:
+            + 0: felt252_mul([0], [1]) -> ([0]);
+        "}
+    );
+}
+
+#[test]
+fn diff_changed_signature_and_branches() {
+    let old = parse(indoc! {"
+        type felt252 = felt252;
+        type NonZeroFelt252 = NonZero<felt252>;
+
+        libfunc felt252_is_zero = felt252_is_zero;
+        libfunc drop<NonZeroFelt252> = drop<NonZeroFelt252>;
+
+        felt252_is_zero([0]) { fallthrough() 2([1]) };
+        return([0]);
+        drop<NonZeroFelt252>([1]) -> ();
+        return([0]);
+
+        test::foo@0([0]: felt252) -> (felt252);
+    "});
+    let new = parse(indoc! {"
+        type felt252 = felt252;
+        type NonZeroFelt252 = NonZero<felt252>;
+
+        libfunc felt252_is_zero = felt252_is_zero;
+        libfunc drop<NonZeroFelt252> = drop<NonZeroFelt252>;
+
+        felt252_is_zero([1]) { fallthrough() 2([2]) };
+        return([1]);
+        drop<NonZeroFelt252>([2]) -> ();
+        return([0]);
+
+        test::foo@0([0]: felt252, [1]: felt252) -> (felt252);
+    "});
+    assert_eq!(
+        ProgramDiff::new(&old, &new).to_string(),
+        indoc! {"
+            function test::foo:
+            - test::foo@0([0]: felt252) -> (felt252);
+            + test::foo@0([0]: felt252, [1]: felt252) -> (felt252);
+            - 0: felt252_is_zero([0]) { fallthrough() 2([1]) };
+            - 1: return([0]);
+            - 2: drop<NonZeroFelt252>([1]) -> ();
+            + 0: felt252_is_zero([1]) { fallthrough() 2([2]) };
+            + 1: return([1]);
+            + 2: drop<NonZeroFelt252>([2]) -> ();
+        "}
+    );
+}
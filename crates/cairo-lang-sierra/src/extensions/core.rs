@@ -1,6 +1,7 @@
 use super::ap_tracking::ApTrackingLibfunc;
 use super::array::{ArrayLibfunc, ArrayType};
 use super::bitwise::BitwiseType;
+use super::blake::Blake2sLibfunc;
 use super::boolean::BoolLibfunc;
 use super::bounded_int::BoundedIntType;
 use super::branch_align::BranchAlignLibfunc;
@@ -133,6 +134,7 @@ define_libfunc_hierarchy! {
         Felt252DictEntry(Felt252DictEntryLibfunc),
         Pedersen(PedersenLibfunc),
         Poseidon(PoseidonLibfunc),
+        Blake2s(Blake2sLibfunc),
         StarkNet(StarkNetLibfunc),
         Debug(DebugLibfunc),
         SnapshotTake(SnapshotTakeLibfunc),
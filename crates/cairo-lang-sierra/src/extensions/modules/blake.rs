@@ -0,0 +1,96 @@
+use super::bitwise::BitwiseType;
+use super::int::unsigned::Uint32Type;
+use super::range_check::RangeCheckType;
+use super::structure::StructType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibfuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
+    SignatureSpecializationContext,
+};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericLibfunc, OutputVarReferenceInfo, SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, UserTypeId};
+use crate::program::GenericArg;
+
+/// The number of `u32` words in a Blake2s state.
+pub const BLAKE2S_STATE_WORDS: usize = 8;
+/// The number of `u32` words in a Blake2s message block.
+pub const BLAKE2S_MSG_WORDS: usize = 16;
+
+define_libfunc_hierarchy! {
+    pub enum Blake2sLibfunc {
+        Compress(Blake2sCompressLibfunc),
+        Finalize(Blake2sFinalizeLibfunc),
+    }, Blake2sConcreteLibfunc
+}
+
+/// Libfunc for applying the Blake2s compression function on a non-final message block.
+/// Gets the current state, the number of bytes hashed so far (including the given block) and the
+/// block, and returns the updated state.
+#[derive(Default)]
+pub struct Blake2sCompressLibfunc {}
+impl NoGenericArgsGenericLibfunc for Blake2sCompressLibfunc {
+    const STR_ID: &'static str = "blake2s_compress";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        blake2s_compress_signature(context)
+    }
+}
+
+/// Libfunc for applying the Blake2s compression function on the final message block.
+/// Same as `blake2s_compress`, but with the finalization flag set.
+#[derive(Default)]
+pub struct Blake2sFinalizeLibfunc {}
+impl NoGenericArgsGenericLibfunc for Blake2sFinalizeLibfunc {
+    const STR_ID: &'static str = "blake2s_finalize";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        blake2s_compress_signature(context)
+    }
+}
+
+/// Returns the signature shared by the Blake2s compression libfuncs.
+fn blake2s_compress_signature(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<LibfuncSignature, SpecializationError> {
+    let range_check_ty = context.get_concrete_type(RangeCheckType::id(), &[])?;
+    let bitwise_ty = context.get_concrete_type(BitwiseType::id(), &[])?;
+    let u32_ty = context.get_concrete_type(Uint32Type::id(), &[])?;
+    let state_ty = u32_fixed_array_ty(context, BLAKE2S_STATE_WORDS)?;
+    Ok(LibfuncSignature::new_non_branch_ex(
+        vec![
+            ParamSignature::new(range_check_ty.clone()).with_allow_add_const(),
+            ParamSignature::new(bitwise_ty.clone()).with_allow_add_const(),
+            ParamSignature::new(state_ty.clone()),
+            ParamSignature::new(u32_ty),
+            ParamSignature::new(u32_fixed_array_ty(context, BLAKE2S_MSG_WORDS)?),
+        ],
+        vec![
+            OutputVarInfo::new_builtin(range_check_ty, 0),
+            OutputVarInfo::new_builtin(bitwise_ty, 1),
+            OutputVarInfo {
+                ty: state_ty,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            },
+        ],
+        SierraApChange::Known { new_vars_only: false },
+    ))
+}
+
+/// Returns the type of a fixed size array of `size` `u32`s - `[u32; size]`.
+fn u32_fixed_array_ty(
+    context: &dyn SignatureSpecializationContext,
+    size: usize,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    let u32_ty = context.get_concrete_type(Uint32Type::id(), &[])?;
+    let mut args = vec![GenericArg::UserType(UserTypeId::from_string("Tuple"))];
+    args.extend((0..size).map(|_| GenericArg::Type(u32_ty.clone())));
+    context.get_concrete_type(StructType::id(), &args)
+}
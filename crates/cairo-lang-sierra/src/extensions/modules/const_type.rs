@@ -6,6 +6,7 @@ use super::boxing::box_ty;
 use super::enm::EnumType;
 use super::int::unsigned128::Uint128Type;
 use super::non_zero::NonZeroType;
+use super::span::SpanType;
 use super::structure::StructType;
 use super::utils::Range;
 use crate::define_libfunc_hierarchy;
@@ -57,6 +58,8 @@ pub struct ConstConcreteType {
     ///   the same as the corresponding struct member type.
     /// - A selector (a single value) followed by a const type, if the inner type is an enum. The
     ///   type of the const type must be the same as the corresponding enum variant type.
+    /// - A list of const types, if the inner type is a span. The type of each const type must be
+    ///   the same as the span element type.
     pub inner_data: Vec<GenericArg>,
 }
 
@@ -73,6 +76,8 @@ fn validate_const_data(
         validate_const_enum_data(context, &inner_type_info, inner_data)?;
     } else if inner_type_info.long_id.generic_id == NonZeroType::ID {
         validate_const_nz_data(context, &inner_type_info, inner_data)?;
+    } else if inner_type_info.long_id.generic_id == SpanType::ID {
+        validate_const_span_data(context, &inner_type_info, inner_data)?;
     } else {
         let type_range = Range::from_type_info(&inner_type_info)?;
         let [GenericArg::Value(value)] = inner_data else {
@@ -196,6 +201,33 @@ fn validate_const_nz_data(
     if is_non_zero { Ok(()) } else { Err(SpecializationError::UnsupportedGenericArg) }
 }
 
+/// Given a const type representing a span, validates that the inner data are const types of the
+/// span element type.
+fn validate_const_span_data(
+    context: &dyn TypeSpecializationContext,
+    inner_type_info: &TypeInfo,
+    inner_data: &[GenericArg],
+) -> Result<(), SpecializationError> {
+    let element_ty = args_as_single_type(&inner_type_info.long_id.generic_args)?;
+    for const_arg in inner_data {
+        let GenericArg::Type(const_arg_ty) = const_arg else {
+            return Err(SpecializationError::UnsupportedGenericArg);
+        };
+        if extract_const_info(context, const_arg_ty)?.0 != element_ty {
+            return Err(SpecializationError::UnsupportedGenericArg);
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether `ty` is a span type.
+fn is_span_type(
+    context: &dyn TypeSpecializationContext,
+    ty: &ConcreteTypeId,
+) -> Result<bool, SpecializationError> {
+    Ok(context.get_type_info(ty.clone())?.long_id.generic_id == SpanType::ID)
+}
+
 /// Validates the type is a `Const` type, and extracts the inner type and the rest of the generic
 /// args of `const_ty`.
 fn extract_const_info(
@@ -256,6 +288,9 @@ pub struct ConstAsBoxConcreteLibfunc {
     pub const_type: ConcreteTypeId,
     /// The segment to have the constant in.
     pub segment_id: u32,
+    /// Whether the constant is a span. Only the elements of a span are kept in the segment, so its
+    /// box is allocated when the libfunc is invoked.
+    pub is_span: bool,
     pub signature: LibfuncSignature,
 }
 
@@ -281,11 +316,13 @@ impl ConstAsBoxLibfunc {
         let segment_id = segment_id.to_u32().ok_or(SpecializationError::UnsupportedGenericArg)?;
         let (inner_ty, _) =
             extract_const_info(context.as_type_specialization_context(), const_type)?;
+        let is_span = is_span_type(context.as_type_specialization_context(), &inner_ty)?;
         let boxed_inner_ty = box_ty(context, inner_ty)?;
 
         Ok(ConstAsBoxConcreteLibfunc {
             const_type: const_type.clone(),
             segment_id,
+            is_span,
             signature: LibfuncSignature::new_non_branch(
                 vec![],
                 vec![OutputVarInfo {
@@ -342,6 +379,10 @@ impl ConstAsImmediateLibfunc {
     ) -> Result<ConstAsImmediateConcreteLibfunc, SpecializationError> {
         let const_type = args_as_single_type(args)?;
         let (ty, _) = extract_const_info(context.as_type_specialization_context(), &const_type)?;
+        // The elements of a span must be in memory, so it can't be an immediate.
+        if is_span_type(context.as_type_specialization_context(), &ty)? {
+            return Err(SpecializationError::UnsupportedGenericArg);
+        }
         Ok(ConstAsImmediateConcreteLibfunc {
             const_type,
             signature: LibfuncSignature::new_non_branch(
@@ -16,7 +16,7 @@ use crate::extensions::lib_func::{
 use crate::extensions::range_check::RangeCheckType;
 use crate::extensions::{
     GenericLibfunc, NamedType, NoGenericArgsGenericLibfunc, OutputVarReferenceInfo,
-    SpecializationError,
+    SignatureBasedConcreteLibfunc, SpecializationError,
 };
 use crate::ids::{GenericLibfuncId, GenericTypeId};
 use crate::program::GenericArg;
@@ -30,6 +30,14 @@ pub trait SintTraits: IntTraits {
     /// The generic libfunc id for difference of signed integers, logically equivalent to
     /// subtraction of unsigned integers.
     const DIFF: &'static str;
+    /// The generic libfunc id for saturating addition.
+    const SATURATING_ADD: &'static str;
+    /// The generic libfunc id for saturating subtraction.
+    const SATURATING_SUB: &'static str;
+    /// The generic libfunc id for wrapping addition.
+    const WRAPPING_ADD: &'static str;
+    /// The generic libfunc id for wrapping subtraction.
+    const WRAPPING_SUB: &'static str;
     /// The generic type id of the equivalent unsigned integer type.
     const UNSIGNED_INT_TYPE: GenericTypeId;
 }
@@ -41,6 +49,7 @@ define_libfunc_hierarchy! {
         ToFelt252(IntToFelt252Libfunc<TSintTraits>),
         FromFelt252(IntFromFelt252Libfunc<TSintTraits>),
         Operation(SintOperationLibfunc<TSintTraits>),
+        BoundedOperation(SintBoundedOperationLibfunc<TSintTraits>),
         Diff(SintDiffLibfunc<TSintTraits>),
         IsZero(IsZeroLibfunc<TSintTraits>),
         WideMul(IntWideMulLibfunc<TSintTraits>),
@@ -136,6 +145,110 @@ impl<TSintTraits: SintTraits> GenericLibfunc for SintOperationLibfunc<TSintTrait
     }
 }
 
+/// The handling of results of signed integer operations that are out of the range of the type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SintOverflowHandling {
+    /// The result is clamped to the closest bound of the range.
+    Saturating,
+    /// The result wraps around the range.
+    Wrapping,
+}
+
+pub struct SintBoundedOperationConcreteLibfunc {
+    pub operator: IntOperator,
+    pub handling: SintOverflowHandling,
+    pub signature: LibfuncSignature,
+}
+impl SignatureBasedConcreteLibfunc for SintBoundedOperationConcreteLibfunc {
+    fn signature(&self) -> &LibfuncSignature {
+        &self.signature
+    }
+}
+
+/// Libfunc for integer operations that do not branch on overflow, but saturate or wrap instead.
+pub struct SintBoundedOperationLibfunc<TSintTraits: SintTraits> {
+    pub operator: IntOperator,
+    pub handling: SintOverflowHandling,
+    _phantom: PhantomData<TSintTraits>,
+}
+impl<TSintTraits: SintTraits> SintBoundedOperationLibfunc<TSintTraits> {
+    const SATURATING_ADD: &'static str = TSintTraits::SATURATING_ADD;
+    const SATURATING_SUB: &'static str = TSintTraits::SATURATING_SUB;
+    const WRAPPING_ADD: &'static str = TSintTraits::WRAPPING_ADD;
+    const WRAPPING_SUB: &'static str = TSintTraits::WRAPPING_SUB;
+    fn new(operator: IntOperator, handling: SintOverflowHandling) -> Option<Self> {
+        Some(Self { operator, handling, _phantom: PhantomData })
+    }
+}
+impl<TSintTraits: SintTraits> GenericLibfunc for SintBoundedOperationLibfunc<TSintTraits> {
+    type Concrete = SintBoundedOperationConcreteLibfunc;
+
+    fn supported_ids() -> Vec<GenericLibfuncId> {
+        vec![
+            GenericLibfuncId::from(Self::SATURATING_ADD),
+            GenericLibfuncId::from(Self::SATURATING_SUB),
+            GenericLibfuncId::from(Self::WRAPPING_ADD),
+            GenericLibfuncId::from(Self::WRAPPING_SUB),
+        ]
+    }
+
+    fn by_id(id: &GenericLibfuncId) -> Option<Self> {
+        match id.0.as_str() {
+            id if id == Self::SATURATING_ADD => {
+                Self::new(IntOperator::OverflowingAdd, SintOverflowHandling::Saturating)
+            }
+            id if id == Self::SATURATING_SUB => {
+                Self::new(IntOperator::OverflowingSub, SintOverflowHandling::Saturating)
+            }
+            id if id == Self::WRAPPING_ADD => {
+                Self::new(IntOperator::OverflowingAdd, SintOverflowHandling::Wrapping)
+            }
+            id if id == Self::WRAPPING_SUB => {
+                Self::new(IntOperator::OverflowingSub, SintOverflowHandling::Wrapping)
+            }
+            _ => None,
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::WrongNumberOfGenericArgs);
+        }
+        let ty = context.get_concrete_type(TSintTraits::GENERIC_TYPE_ID, &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+
+        let ty_param = ParamSignature::new(ty.clone());
+        Ok(LibfuncSignature::new_non_branch_ex(
+            vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ty_param.clone(),
+                ty_param,
+            ],
+            vec![
+                OutputVarInfo::new_builtin(range_check_type, 0),
+                OutputVarInfo { ty, ref_info: OutputVarReferenceInfo::SimpleDerefs },
+            ],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(SintBoundedOperationConcreteLibfunc {
+            operator: self.operator,
+            handling: self.handling,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
 /// Libfunc for integer difference calculation.
 #[derive(Default)]
 pub struct SintDiffLibfunc<TSintTraits: SintTraits> {
@@ -198,6 +311,10 @@ impl SintTraits for Sint8Traits {
     const OVERFLOWING_ADD: &'static str = "i8_overflowing_add_impl";
     const OVERFLOWING_SUB: &'static str = "i8_overflowing_sub_impl";
     const DIFF: &'static str = "i8_diff";
+    const SATURATING_ADD: &'static str = "i8_saturating_add";
+    const SATURATING_SUB: &'static str = "i8_saturating_sub";
+    const WRAPPING_ADD: &'static str = "i8_wrapping_add";
+    const WRAPPING_SUB: &'static str = "i8_wrapping_sub";
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint8Type as NamedType>::ID;
 }
 
@@ -233,6 +350,10 @@ impl SintTraits for Sint16Traits {
     const OVERFLOWING_ADD: &'static str = "i16_overflowing_add_impl";
     const OVERFLOWING_SUB: &'static str = "i16_overflowing_sub_impl";
     const DIFF: &'static str = "i16_diff";
+    const SATURATING_ADD: &'static str = "i16_saturating_add";
+    const SATURATING_SUB: &'static str = "i16_saturating_sub";
+    const WRAPPING_ADD: &'static str = "i16_wrapping_add";
+    const WRAPPING_SUB: &'static str = "i16_wrapping_sub";
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint16Type as NamedType>::ID;
 }
 
@@ -268,6 +389,10 @@ impl SintTraits for Sint32Traits {
     const OVERFLOWING_ADD: &'static str = "i32_overflowing_add_impl";
     const OVERFLOWING_SUB: &'static str = "i32_overflowing_sub_impl";
     const DIFF: &'static str = "i32_diff";
+    const SATURATING_ADD: &'static str = "i32_saturating_add";
+    const SATURATING_SUB: &'static str = "i32_saturating_sub";
+    const WRAPPING_ADD: &'static str = "i32_wrapping_add";
+    const WRAPPING_SUB: &'static str = "i32_wrapping_sub";
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint32Type as NamedType>::ID;
 }
 
@@ -303,6 +428,10 @@ impl SintTraits for Sint64Traits {
     const OVERFLOWING_ADD: &'static str = "i64_overflowing_add_impl";
     const OVERFLOWING_SUB: &'static str = "i64_overflowing_sub_impl";
     const DIFF: &'static str = "i64_diff";
+    const SATURATING_ADD: &'static str = "i64_saturating_add";
+    const SATURATING_SUB: &'static str = "i64_saturating_sub";
+    const WRAPPING_ADD: &'static str = "i64_wrapping_add";
+    const WRAPPING_SUB: &'static str = "i64_wrapping_sub";
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint64Type as NamedType>::ID;
 }
 
@@ -1,4 +1,6 @@
-use super::signed::{SintDiffLibfunc, SintOperationLibfunc, SintTraits};
+use super::signed::{
+    SintBoundedOperationLibfunc, SintDiffLibfunc, SintOperationLibfunc, SintTraits,
+};
 use super::unsigned128::Uint128Type;
 use super::{
     IntConstLibfunc, IntEqualLibfunc, IntFromFelt252Libfunc, IntToFelt252Libfunc, IntTraits,
@@ -19,6 +21,7 @@ define_libfunc_hierarchy! {
         ToFelt252(IntToFelt252Libfunc<Sint128Traits>),
         FromFelt252(IntFromFelt252Libfunc<Sint128Traits>),
         Operation(SintOperationLibfunc<Sint128Traits>),
+        BoundedOperation(SintBoundedOperationLibfunc<Sint128Traits>),
         Diff(SintDiffLibfunc<Sint128Traits>),
         IsZero(IsZeroLibfunc<Sint128Traits>),
     }, Sint128Concrete
@@ -31,6 +34,10 @@ impl SintTraits for Sint128Traits {
     const OVERFLOWING_ADD: &'static str = "i128_overflowing_add_impl";
     const OVERFLOWING_SUB: &'static str = "i128_overflowing_sub_impl";
     const DIFF: &'static str = "i128_diff";
+    const SATURATING_ADD: &'static str = "i128_saturating_add";
+    const SATURATING_SUB: &'static str = "i128_saturating_sub";
+    const WRAPPING_ADD: &'static str = "i128_wrapping_add";
+    const WRAPPING_SUB: &'static str = "i128_wrapping_sub";
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint128Type as NamedType>::ID;
 }
 
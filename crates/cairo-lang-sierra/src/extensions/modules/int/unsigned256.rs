@@ -4,7 +4,7 @@ use crate::extensions::lib_func::{
     BranchSignature, LibfuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
     SignatureSpecializationContext,
 };
-use crate::extensions::modules::get_u256_type;
+use crate::extensions::modules::{get_u256_type, get_u512_type};
 use crate::extensions::non_zero::nonzero_ty;
 use crate::extensions::range_check::RangeCheckType;
 use crate::extensions::{
@@ -17,6 +17,9 @@ define_libfunc_hierarchy! {
         Divmod(Uint256DivmodLibfunc),
         SquareRoot(Uint256SquareRootLibfunc),
         InvModN(Uint256InvModNLibfunc),
+        GuaranteeWideMul(Uint256GuaranteeWideMulLibfunc),
+        DivModU128(Uint256DivmodU128Libfunc),
+        GuaranteeMulModN(Uint256GuaranteeMulModNLibfunc),
     }, Uint256Concrete
 }
 
@@ -172,3 +175,114 @@ impl NoGenericArgsGenericLibfunc for Uint256InvModNLibfunc {
         })
     }
 }
+
+/// Libfunc for the full multiplication of two u256 values into a u512.
+/// Additionally returns the `U128MulGuarantee`s of the multiplications of the limbs, that are
+/// required for validating the calculation.
+#[derive(Default)]
+pub struct Uint256GuaranteeWideMulLibfunc;
+impl NoGenericArgsGenericLibfunc for Uint256GuaranteeWideMulLibfunc {
+    const STR_ID: &'static str = "u256_guarantee_wide_mul";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let u256_ty = get_u256_type(context)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let guarantee_output_info = OutputVarInfo {
+            ty: context.get_concrete_type(U128MulGuaranteeType::id(), &[])?,
+            ref_info: OutputVarReferenceInfo::SimpleDerefs,
+        };
+        Ok(LibfuncSignature::new_non_branch_ex(
+            vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ParamSignature::new(u256_ty.clone()),
+                ParamSignature::new(u256_ty),
+            ],
+            vec![
+                OutputVarInfo::new_builtin(range_check_type, 0),
+                OutputVarInfo {
+                    ty: get_u512_type(context)?,
+                    ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                },
+                guarantee_output_info.clone(),
+                guarantee_output_info.clone(),
+                guarantee_output_info.clone(),
+                guarantee_output_info,
+            ],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+}
+
+// Divmod by u128.
+#[derive(Default)]
+pub struct Uint256DivmodU128Libfunc;
+impl NoGenericArgsGenericLibfunc for Uint256DivmodU128Libfunc {
+    const STR_ID: &'static str = "u256_safe_divmod_by_u128";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let u256_ty = get_u256_type(context)?;
+        let u128_ty = context.get_concrete_type(Uint128Type::id(), &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        Ok(LibfuncSignature::new_non_branch_ex(
+            vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ParamSignature::new(u256_ty.clone()),
+                ParamSignature::new(nonzero_ty(context, &u128_ty)?),
+            ],
+            vec![
+                OutputVarInfo::new_builtin(range_check_type, 0),
+                OutputVarInfo { ty: u256_ty, ref_info: OutputVarReferenceInfo::SimpleDerefs },
+                OutputVarInfo { ty: u128_ty, ref_info: OutputVarReferenceInfo::SimpleDerefs },
+                OutputVarInfo {
+                    ty: context.get_concrete_type(U128MulGuaranteeType::id(), &[])?,
+                    ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                },
+            ],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+}
+
+/// Libfunc for the multiplication of two u256 values modulo a non-zero u256.
+/// Additionally returns the `U128MulGuarantee`s of the multiplications of the limbs, that are
+/// required for validating the calculation: 4 for the product of the values, followed by 5 for the
+/// product of its quotient by the modulus.
+#[derive(Default)]
+pub struct Uint256GuaranteeMulModNLibfunc;
+impl NoGenericArgsGenericLibfunc for Uint256GuaranteeMulModNLibfunc {
+    const STR_ID: &'static str = "u256_guarantee_mul_mod_n";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let u256_ty = get_u256_type(context)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let guarantee_output_info = OutputVarInfo {
+            ty: context.get_concrete_type(U128MulGuaranteeType::id(), &[])?,
+            ref_info: OutputVarReferenceInfo::SimpleDerefs,
+        };
+        Ok(LibfuncSignature::new_non_branch_ex(
+            vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ParamSignature::new(u256_ty.clone()),
+                ParamSignature::new(u256_ty.clone()),
+                ParamSignature::new(nonzero_ty(context, &u256_ty)?),
+            ],
+            [
+                OutputVarInfo::new_builtin(range_check_type, 0),
+                OutputVarInfo { ty: u256_ty, ref_info: OutputVarReferenceInfo::SimpleDerefs },
+            ]
+            .into_iter()
+            .chain(itertools::repeat_n(guarantee_output_info, 9))
+            .collect(),
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+}
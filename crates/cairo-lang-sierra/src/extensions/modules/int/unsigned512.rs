@@ -1,21 +1,24 @@
-use super::unsigned128::{U128MulGuaranteeType, Uint128Type};
+use super::unsigned128::U128MulGuaranteeType;
+use super::{IntOperationConcreteLibfunc, IntOperator};
 use crate::define_libfunc_hierarchy;
 use crate::extensions::lib_func::{
-    LibfuncSignature, OutputVarInfo, ParamSignature, SierraApChange, SignatureSpecializationContext,
+    BranchSignature, LibfuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
+    SignatureSpecializationContext, SpecializationContext,
 };
-use crate::extensions::modules::get_u256_type;
+use crate::extensions::modules::{get_u256_type, get_u512_type};
 use crate::extensions::non_zero::nonzero_ty;
 use crate::extensions::range_check::RangeCheckType;
-use crate::extensions::structure::StructType;
 use crate::extensions::{
-    NamedType, NoGenericArgsGenericLibfunc, OutputVarReferenceInfo, SpecializationError,
+    GenericLibfunc, NamedType, NoGenericArgsGenericLibfunc, OutputVarReferenceInfo,
+    SpecializationError,
 };
-use crate::ids::{ConcreteTypeId, UserTypeId};
+use crate::ids::GenericLibfuncId;
 use crate::program::GenericArg;
 
 define_libfunc_hierarchy! {
     pub enum Uint512Libfunc {
         DivModU256(Uint512DivmodU256Libfunc),
+        Operation(Uint512OperationLibfunc),
     }, Uint512Concrete
 }
 
@@ -56,19 +59,75 @@ impl NoGenericArgsGenericLibfunc for Uint512DivmodU256Libfunc {
     }
 }
 
-/// Helper for u512 type def.
-fn get_u512_type(
-    context: &dyn SignatureSpecializationContext,
-) -> Result<ConcreteTypeId, SpecializationError> {
-    let u128_ty = context.get_concrete_type(Uint128Type::id(), &[])?;
-    context.get_concrete_type(
-        StructType::id(),
-        &[
-            GenericArg::UserType(UserTypeId::from_string("core::integer::u512")),
-            GenericArg::Type(u128_ty.clone()),
-            GenericArg::Type(u128_ty.clone()),
-            GenericArg::Type(u128_ty.clone()),
-            GenericArg::Type(u128_ty),
-        ],
-    )
+/// Libfunc for u512 overflowing operations.
+pub struct Uint512OperationLibfunc {
+    pub operator: IntOperator,
+}
+impl Uint512OperationLibfunc {
+    const OVERFLOWING_ADD: &'static str = "u512_overflowing_add";
+    const OVERFLOWING_SUB: &'static str = "u512_overflowing_sub";
+    fn new(operator: IntOperator) -> Option<Self> {
+        Some(Self { operator })
+    }
+}
+impl GenericLibfunc for Uint512OperationLibfunc {
+    type Concrete = IntOperationConcreteLibfunc;
+
+    fn supported_ids() -> Vec<GenericLibfuncId> {
+        vec![
+            GenericLibfuncId::from(Self::OVERFLOWING_ADD),
+            GenericLibfuncId::from(Self::OVERFLOWING_SUB),
+        ]
+    }
+
+    fn by_id(id: &GenericLibfuncId) -> Option<Self> {
+        match id.0.as_str() {
+            id if id == Self::OVERFLOWING_ADD => Self::new(IntOperator::OverflowingAdd),
+            id if id == Self::OVERFLOWING_SUB => Self::new(IntOperator::OverflowingSub),
+            _ => None,
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::WrongNumberOfGenericArgs);
+        }
+        let u512_ty = get_u512_type(context)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        // Both branches return the wrapping result.
+        let branch_signature = || BranchSignature {
+            vars: vec![
+                OutputVarInfo::new_builtin(range_check_type.clone(), 0),
+                OutputVarInfo {
+                    ty: u512_ty.clone(),
+                    ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                },
+            ],
+            ap_change: SierraApChange::Known { new_vars_only: false },
+        };
+        Ok(LibfuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ParamSignature::new(u512_ty.clone()),
+                ParamSignature::new(u512_ty.clone()),
+            ],
+            branch_signatures: vec![branch_signature(), branch_signature()],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(IntOperationConcreteLibfunc {
+            operator: self.operator,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
 }
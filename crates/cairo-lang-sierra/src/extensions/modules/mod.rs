@@ -9,6 +9,7 @@ use crate::program::GenericArg;
 pub mod ap_tracking;
 pub mod array;
 pub mod bitwise;
+pub mod blake;
 pub mod boolean;
 pub mod bounded_int;
 pub mod boxing;
@@ -85,3 +86,20 @@ fn get_u256_type(
         ],
     )
 }
+
+/// Helper for u512 type def.
+fn get_u512_type(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    let u128_ty = context.get_concrete_type(Uint128Type::id(), &[])?;
+    context.get_concrete_type(
+        StructType::id(),
+        &[
+            GenericArg::UserType(UserTypeId::from_string("core::integer::u512")),
+            GenericArg::Type(u128_ty.clone()),
+            GenericArg::Type(u128_ty.clone()),
+            GenericArg::Type(u128_ty.clone()),
+            GenericArg::Type(u128_ty),
+        ],
+    )
+}
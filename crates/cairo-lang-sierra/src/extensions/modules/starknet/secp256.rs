@@ -22,6 +22,7 @@ pub trait Secp256Trait: Default {
     const STR_ID_NEW: &'static str;
     const STR_ID_ADD: &'static str;
     const STR_ID_MUL: &'static str;
+    const STR_ID_MUL_ADD: &'static str;
     const STR_ID_GET_POINT_FROM_X: &'static str;
     const STR_ID_GET_XY: &'static str;
     const TYPE_ID: GenericTypeId;
@@ -38,6 +39,7 @@ define_libfunc_hierarchy! {
         New(Secp256NewLibfunc<T>),
         Add(Secp256AddLibfunc<T>),
         Mul(Secp256MulLibfunc<T>),
+        MulAdd(Secp256MulAddLibfunc<T>),
         GetPointFromX(Secp256GetPointFromXLibfunc<T>),
         GetXy(Secp256GetXyLibfunc<T>),
     }, Secp256OpConcreteLibfunc
@@ -116,6 +118,33 @@ impl<T: Secp256Trait> SyscallGenericLibfunc for Secp256MulLibfunc<T> {
     }
 }
 
+/// Libfunc for a secp256 elliptic curve multi-scalar multiplication system call, computing
+/// `p0 * scalar0 + p1 * scalar1`.
+///
+/// Not implemented by the Starknet OS yet, so it is only in the experimental allowed libfuncs list.
+#[derive(Default)]
+pub struct Secp256MulAddLibfunc<T: Secp256Trait> {
+    _phantom: PhantomData<T>,
+}
+impl<T: Secp256Trait> SyscallGenericLibfunc for Secp256MulAddLibfunc<T> {
+    const STR_ID: &'static str = T::STR_ID_MUL_ADD;
+
+    fn input_tys(
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<Vec<crate::ids::ConcreteTypeId>, SpecializationError> {
+        let secp256_ec_point_type = context.get_concrete_type(T::TYPE_ID, &[])?;
+        let u256_ty = get_u256_type(context)?;
+        // Point `p0`, scalar `scalar0`, point `p1`, scalar `scalar1`.
+        Ok(vec![secp256_ec_point_type.clone(), u256_ty.clone(), secp256_ec_point_type, u256_ty])
+    }
+
+    fn success_output_tys(
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<Vec<crate::ids::ConcreteTypeId>, SpecializationError> {
+        Ok(vec![context.get_concrete_type(T::TYPE_ID, &[])?])
+    }
+}
+
 /// System call libfunc for getting a point on the secp256 elliptic curve, according to the given
 /// `x` coordinate and the parity of the relevant y coordinate.
 #[derive(Default)]
@@ -18,6 +18,7 @@ impl Secp256Trait for Secp256k1 {
     const STR_ID_NEW: &'static str = "secp256k1_new_syscall";
     const STR_ID_ADD: &'static str = "secp256k1_add_syscall";
     const STR_ID_MUL: &'static str = "secp256k1_mul_syscall";
+    const STR_ID_MUL_ADD: &'static str = "secp256k1_mul_add_syscall";
     const STR_ID_GET_POINT_FROM_X: &'static str = "secp256k1_get_point_from_x_syscall";
     const STR_ID_GET_XY: &'static str = "secp256k1_get_xy_syscall";
     const TYPE_ID: GenericTypeId = Secp256k1PointType::ID;
@@ -12,17 +12,24 @@
 use lalrpop_util::lalrpop_mod;
 
 pub mod algorithm;
+pub mod audit;
+pub mod binary;
+pub mod builder;
 pub mod debug_info;
+pub mod diff;
 pub mod edit_state;
 pub mod extensions;
 pub mod fmt;
 pub mod ids;
 mod pre_statement;
+pub mod pretty_print;
 pub mod program;
 pub mod program_registry;
 pub mod simulation;
+pub mod ssa;
 #[cfg(test)]
 mod test_utils;
+pub mod validate;
 
 lalrpop_mod!(
     #[allow(clippy::all, unused_extern_crates)]
@@ -0,0 +1,301 @@
+//! Printing of Sierra programs in a human readable form, with configurable verbosity.
+//! Unlike the plain `Display` of a program, the output is not necessarily parsable.
+
+use std::fmt;
+
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::Itertools;
+
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId};
+use crate::program::{
+    Function, GenBranchTarget, GenericArg, Program, Statement, StatementIdx, TypeDeclaration,
+};
+
+#[cfg(test)]
+#[path = "pretty_print_test.rs"]
+mod test;
+
+/// The configuration of the printing of a program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrettyPrintConfig {
+    /// Whether to shorten the names of types and libfuncs by dropping the module paths in them,
+    /// e.g. `core::array::Array::<core::felt252>` is printed as `Array<felt252>`.
+    pub collapse_type_names: bool,
+    /// Whether to print each type declaration just before the first libfunc declaration using it,
+    /// instead of printing all of them before the libfunc declarations.
+    pub inline_type_declarations: bool,
+    /// Whether to add the index of each statement as a comment.
+    pub statement_indices: bool,
+    /// Whether to add a comment with the signature of each function before its entry point.
+    pub function_boundaries: bool,
+}
+impl Default for PrettyPrintConfig {
+    /// Returns the configuration printing the same as the `Display` of the program.
+    fn default() -> Self {
+        Self {
+            collapse_type_names: false,
+            inline_type_declarations: false,
+            statement_indices: true,
+            function_boundaries: false,
+        }
+    }
+}
+impl PrettyPrintConfig {
+    /// Returns the configuration for the most readable printing.
+    pub fn readable() -> Self {
+        Self {
+            collapse_type_names: true,
+            inline_type_declarations: true,
+            statement_indices: true,
+            function_boundaries: true,
+        }
+    }
+}
+
+/// A program printed according to a configuration.
+pub struct PrettyProgram<'a> {
+    program: &'a Program,
+    config: &'a PrettyPrintConfig,
+}
+impl Program {
+    /// Returns a wrapper of the program, displaying it according to `config`.
+    pub fn pretty<'a>(&'a self, config: &'a PrettyPrintConfig) -> PrettyProgram<'a> {
+        PrettyProgram { program: self, config }
+    }
+}
+
+impl fmt::Display for PrettyProgram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let program = self.program;
+        let type_declarations: UnorderedHashMap<_, _> = program
+            .type_declarations
+            .iter()
+            .map(|declaration| (&declaration.id, declaration))
+            .collect();
+        let mut printed_types = OrderedHashSet::default();
+        if !self.config.inline_type_declarations {
+            for declaration in &program.type_declarations {
+                self.write_type_declaration(f, declaration)?;
+            }
+            writeln!(f)?;
+        }
+        for declaration in &program.libfunc_declarations {
+            if self.config.inline_type_declarations {
+                for arg in &declaration.long_id.generic_args {
+                    if let GenericArg::Type(ty) = arg {
+                        self.write_type_with_dependencies(
+                            f,
+                            &type_declarations,
+                            &mut printed_types,
+                            ty,
+                        )?;
+                    }
+                }
+            }
+            writeln!(
+                f,
+                "libfunc {} = {}{};",
+                self.libfunc_name(&declaration.id),
+                declaration.long_id.generic_id,
+                self.generic_args(&declaration.long_id.generic_args)
+            )?;
+        }
+        if self.config.inline_type_declarations {
+            // Types not used by any libfunc, e.g. only used in function signatures.
+            for declaration in &program.type_declarations {
+                self.write_type_with_dependencies(
+                    f,
+                    &type_declarations,
+                    &mut printed_types,
+                    &declaration.id,
+                )?;
+            }
+        }
+        writeln!(f)?;
+        let functions_by_entry_point: UnorderedHashMap<_, Vec<_>> = program
+            .funcs
+            .iter()
+            .map(|func| (func.entry_point, func))
+            .into_group_map()
+            .into_iter()
+            .collect();
+        for (i, statement) in program.statements.iter().enumerate() {
+            if self.config.function_boundaries {
+                for func in functions_by_entry_point.get(&StatementIdx(i)).into_iter().flatten() {
+                    if i != 0 {
+                        writeln!(f)?;
+                    }
+                    writeln!(f, "// {}", self.function_signature(func))?;
+                }
+            }
+            self.write_statement(f, statement)?;
+            if self.config.statement_indices {
+                writeln!(f, "; // {i}")?;
+            } else {
+                writeln!(f, ";")?;
+            }
+        }
+        writeln!(f)?;
+        for func in &program.funcs {
+            writeln!(f, "{};", self.function_signature(func))?;
+        }
+        Ok(())
+    }
+}
+
+impl PrettyProgram<'_> {
+    /// Writes the declaration of a type, after the declarations of the types it depends on, unless
+    /// already written.
+    fn write_type_with_dependencies<'a>(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        type_declarations: &UnorderedHashMap<&'a ConcreteTypeId, &'a TypeDeclaration>,
+        printed_types: &mut OrderedHashSet<&'a ConcreteTypeId>,
+        ty: &ConcreteTypeId,
+    ) -> fmt::Result {
+        // Types without declarations are left for the validation of the program to report.
+        let Some(declaration) = type_declarations.get(ty).copied() else {
+            return Ok(());
+        };
+        if !printed_types.insert(&declaration.id) {
+            return Ok(());
+        }
+        for arg in &declaration.long_id.generic_args {
+            if let GenericArg::Type(dependency) = arg {
+                self.write_type_with_dependencies(f, type_declarations, printed_types, dependency)?;
+            }
+        }
+        self.write_type_declaration(f, declaration)
+    }
+
+    /// Writes the declaration of a type.
+    fn write_type_declaration(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        declaration: &TypeDeclaration,
+    ) -> fmt::Result {
+        let TypeDeclaration { id, long_id, declared_type_info } = declaration;
+        write!(
+            f,
+            "type {} = {}{}",
+            self.type_name(id),
+            long_id.generic_id,
+            self.generic_args(&long_id.generic_args)
+        )?;
+        if let Some(info) = declared_type_info {
+            write!(
+                f,
+                " [storable: {:?}, drop: {:?}, dup: {:?}, zero_sized: {:?}]",
+                info.storable, info.droppable, info.duplicatable, info.zero_sized
+            )?;
+        }
+        writeln!(f, ";")
+    }
+
+    /// Writes a statement, without the terminating `;`.
+    fn write_statement(&self, f: &mut fmt::Formatter<'_>, statement: &Statement) -> fmt::Result {
+        let invocation = match statement {
+            Statement::Invocation(invocation) => invocation,
+            Statement::Return(ids) => {
+                return write!(f, "return({})", ids.iter().join(", "));
+            }
+        };
+        write!(
+            f,
+            "{}({})",
+            self.libfunc_name(&invocation.libfunc_id),
+            invocation.args.iter().join(", ")
+        )?;
+        if let [branch] = &invocation.branches[..] {
+            if branch.target == GenBranchTarget::Fallthrough {
+                return write!(f, " -> ({})", branch.results.iter().join(", "));
+            }
+        }
+        write!(f, " {{ ")?;
+        for branch in &invocation.branches {
+            write!(f, "{}({}) ", branch.target, branch.results.iter().join(", "))?;
+        }
+        write!(f, "}}")
+    }
+
+    /// Returns the signature of a function, as printed in the functions section.
+    fn function_signature(&self, func: &Function) -> String {
+        format!(
+            "{}@{}({}) -> ({})",
+            self.function_name(&func.id),
+            func.entry_point,
+            func.params
+                .iter()
+                .map(|param| format!("{}: {}", param.id, self.type_name(&param.ty)))
+                .join(", "),
+            func.signature.ret_types.iter().map(|ty| self.type_name(ty)).join(", ")
+        )
+    }
+
+    /// Returns the printed generic arguments, including the surrounding `<>` if there are any.
+    fn generic_args(&self, args: &[GenericArg]) -> String {
+        if args.is_empty() {
+            return String::new();
+        }
+        let args = args.iter().map(|arg| match arg {
+            GenericArg::Type(id) => self.type_name(id),
+            GenericArg::UserType(id) => format!("ut@{}", self.collapse(id.to_string())),
+            GenericArg::Value(value) => value.to_string(),
+            GenericArg::UserFunc(id) => format!("user@{}", self.function_name(id)),
+            GenericArg::Libfunc(id) => format!("lib@{}", self.libfunc_name(id)),
+        });
+        format!("<{}>", args.format(", "))
+    }
+
+    fn type_name(&self, id: &ConcreteTypeId) -> String {
+        self.collapse(id.to_string())
+    }
+
+    fn libfunc_name(&self, id: &ConcreteLibfuncId) -> String {
+        self.collapse(id.to_string())
+    }
+
+    /// Function names are never collapsed, as functions of different modules commonly share names.
+    fn function_name(&self, id: &FunctionId) -> String {
+        id.to_string()
+    }
+
+    /// Collapses the module paths in `name` if configured to.
+    fn collapse(&self, name: String) -> String {
+        if self.config.collapse_type_names { collapse_paths(&name) } else { name }
+    }
+}
+
+/// Drops the module paths from the paths in `name`, and the `::` before generic arguments.
+/// For example, `core::array::Array::<core::felt252>` is collapsed into `Array<felt252>`.
+fn collapse_paths(name: &str) -> String {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(c) = rest.chars().next() {
+        if !is_ident_char(c) {
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let ident_len = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+        let (ident, after_ident) = rest.split_at(ident_len);
+        match after_ident.strip_prefix("::") {
+            // A generic arguments list - only the separator is dropped.
+            Some(after_separator) if after_separator.starts_with('<') => {
+                result.push_str(ident);
+                rest = after_separator;
+            }
+            // A module in the path - dropped along with the separator.
+            Some(after_separator) if after_separator.starts_with(is_ident_char) => {
+                rest = after_separator;
+            }
+            _ => {
+                result.push_str(ident);
+                rest = after_ident;
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,94 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::{collapse_paths, PrettyPrintConfig};
+use crate::program::Program;
+use crate::ProgramParser;
+
+fn example_program() -> Program {
+    ProgramParser::new()
+        .parse(indoc! {"
+            type core::felt252 = felt252;
+            type core::array::Array::<core::felt252> = Array<core::felt252>;
+            type core::option::Option::<core::array::Array::<core::felt252>> = Enum<ut@core::option::Option::<core::array::Array::<core::felt252>>, core::array::Array::<core::felt252>, Unit>;
+            type Unit = Struct<ut@Tuple>;
+
+            libfunc array_new<core::felt252> = array_new<core::felt252>;
+            libfunc array_append<core::felt252> = array_append<core::felt252>;
+            libfunc enum_init<core::option::Option::<core::array::Array::<core::felt252>>, 0> = enum_init<core::option::Option::<core::array::Array::<core::felt252>>, 0>;
+            libfunc function_call<user@test::wrap> = function_call<user@test::wrap>;
+
+            array_new<core::felt252>() -> ([1]);
+            array_append<core::felt252>([1], [0]) -> ([1]);
+            function_call<user@test::wrap>([1]) -> ([2]);
+            return([2]);
+            enum_init<core::option::Option::<core::array::Array::<core::felt252>>, 0>([0]) -> ([1]);
+            return([1]);
+
+            test::single@0([0]: core::felt252) -> (core::option::Option::<core::array::Array::<core::felt252>>);
+            test::wrap@4([0]: core::array::Array::<core::felt252>) -> (core::option::Option::<core::array::Array::<core::felt252>>);
+        "})
+        .unwrap()
+}
+
+#[test]
+fn default_config_matches_display() {
+    let program = example_program();
+    assert_eq!(program.pretty(&PrettyPrintConfig::default()).to_string(), program.to_string());
+}
+
+#[test]
+fn readable_config() {
+    let program = example_program();
+    assert_eq!(
+        program.pretty(&PrettyPrintConfig::readable()).to_string(),
+        indoc! {"
+            type felt252 = felt252;
+            libfunc array_new<felt252> = array_new<felt252>;
+            libfunc array_append<felt252> = array_append<felt252>;
+            type Array<felt252> = Array<felt252>;
+            type Unit = Struct<ut@Tuple>;
+            type Option<Array<felt252>> = Enum<ut@Option<Array<felt252>>, Array<felt252>, Unit>;
+            libfunc enum_init<Option<Array<felt252>>, 0> = enum_init<Option<Array<felt252>>, 0>;
+            libfunc function_call<user@wrap> = function_call<user@test::wrap>;
+
+            // test::single@0([0]: felt252) -> (Option<Array<felt252>>)
+            array_new<felt252>() -> ([1]); // 0
+            array_append<felt252>([1], [0]) -> ([1]); // 1
+            function_call<user@wrap>([1]) -> ([2]); // 2
+            return([2]); // 3
+
+            // test::wrap@4([0]: Array<felt252>) -> (Option<Array<felt252>>)
+            enum_init<Option<Array<felt252>>, 0>([0]) -> ([1]); // 4
+            return([1]); // 5
+
+            test::single@0([0]: felt252) -> (Option<Array<felt252>>);
+            test::wrap@4([0]: Array<felt252>) -> (Option<Array<felt252>>);
+        "}
+    );
+}
+
+#[test]
+fn no_statement_indices() {
+    let program = example_program();
+    let config = PrettyPrintConfig { statement_indices: false, ..PrettyPrintConfig::default() };
+    let printed = program.pretty(&config).to_string();
+    assert!(printed.contains("\nreturn([2]);\n"));
+    assert!(!printed.contains("// "));
+}
+
+#[test_case("felt252", "felt252"; "no path")]
+#[test_case("core::felt252", "felt252"; "simple path")]
+#[test_case("core::array::Array::<core::felt252>", "Array<felt252>"; "generic path")]
+#[test_case("Tuple<core::integer::u8, core::bool>", "Tuple<u8, bool>"; "multiple args")]
+#[test_case(
+    "core::result::Result::<core::integer::u8, core::array::Array::<core::felt252>>",
+    "Result<u8, Array<felt252>>";
+    "nested args"
+)]
+#[test_case("test::Impl::<core::felt252>::foo", "Impl<felt252>::foo"; "item of generic")]
+#[test_case("[5]", "[5]"; "no debug name")]
+fn collapse(name: &str, expected: &str) {
+    assert_eq!(collapse_paths(name), expected);
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use cairo_felt::Felt252 as Felt252Value;
 use cairo_lang_utils::extract_matches;
 use num_bigint::{BigInt, ToBigInt};
 use num_traits::{ToPrimitive, Zero};
@@ -10,10 +11,11 @@ use super::LibfuncSimulationError;
 use crate::extensions::array::ArrayConcreteLibfunc;
 use crate::extensions::boolean::BoolConcreteLibfunc;
 use crate::extensions::core::CoreConcreteLibfunc::{
-    self, ApTracking, Array, Bool, BranchAlign, Bytes31, Cast, Const, Coupon, CouponCall, Debug,
-    Drop, Dup, Ec, Enum, Felt252, Felt252Dict, Felt252DictEntry, FunctionCall, Gas, Mem, Nullable,
-    Pedersen, Poseidon, Sint128, Sint16, Sint32, Sint64, Sint8, SnapshotTake, StarkNet, Struct,
-    Uint128, Uint16, Uint256, Uint32, Uint512, Uint64, Uint8, UnconditionalJump, UnwrapNonZero,
+    self, ApTracking, Array, Blake2s, Bool, BranchAlign, Bytes31, Cast, Const, Coupon, CouponCall,
+    Debug, Drop, Dup, Ec, Enum, Felt252, Felt252Dict, Felt252DictEntry, FunctionCall, Gas, Mem,
+    Nullable, Pedersen, Poseidon, Sint128, Sint16, Sint32, Sint64, Sint8, SnapshotTake, StarkNet,
+    Struct, Uint128, Uint16, Uint256, Uint32, Uint512, Uint64, Uint8, UnconditionalJump,
+    UnwrapNonZero,
 };
 use crate::extensions::ec::EcConcreteLibfunc;
 use crate::extensions::enm::{EnumConcreteLibfunc, EnumInitConcreteLibfunc};
@@ -34,6 +36,7 @@ use crate::extensions::int::{IntConstConcreteLibfunc, IntOperator};
 use crate::extensions::mem::MemConcreteLibfunc::{
     AllocLocal, FinalizeLocals, Rename, StoreLocal, StoreTemp,
 };
+use crate::extensions::nullable::NullableConcreteLibfunc;
 use crate::extensions::structure::StructConcreteLibfunc;
 use crate::ids::FunctionId;
 
@@ -88,7 +91,7 @@ pub fn simulate<
                 [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
                 _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
             },
-            _ => unimplemented!(),
+            _ => Err(LibfuncSimulationError::UnsupportedLibfunc),
         },
         FunctionCall(SignatureAndFunctionConcreteLibfunc { function, .. })
         | CouponCall(SignatureAndFunctionConcreteLibfunc { function, .. }) => {
@@ -132,7 +135,7 @@ pub fn simulate<
             ))
         }
         Gas(BuiltinWithdrawGas(_) | GetBuiltinCosts(_)) => {
-            unimplemented!("Simulation of the builtin cost functionality is not implemented yet.")
+            Err(LibfuncSimulationError::UnsupportedLibfunc)
         }
         BranchAlign(_) => {
             get_statement_gas_info().ok_or(LibfuncSimulationError::UnresolvedStatementGasInfo)?;
@@ -145,7 +148,15 @@ pub fn simulate<
                 Err(LibfuncSimulationError::WrongNumberOfArgs)
             }
         }
-        Array(ArrayConcreteLibfunc::SpanFromTuple(_)) => todo!(),
+        Array(ArrayConcreteLibfunc::SpanFromTuple(_)) => match &inputs[..] {
+            [CoreValue::Struct(_)] => {
+                let members =
+                    extract_matches!(inputs.into_iter().next().unwrap(), CoreValue::Struct);
+                Ok((vec![CoreValue::Array(members)], 0))
+            }
+            [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Array(ArrayConcreteLibfunc::Append(_)) => match &inputs[..] {
             [CoreValue::Array(_), _] => {
                 let mut iter = inputs.into_iter();
@@ -221,15 +232,33 @@ pub fn simulate<
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
-        Array(ArrayConcreteLibfunc::SnapshotPopFront(_)) => todo!(),
-        Array(ArrayConcreteLibfunc::SnapshotPopBack(_)) => todo!(),
+        Array(
+            ArrayConcreteLibfunc::SnapshotPopFront(_) | ArrayConcreteLibfunc::SnapshotPopBack(_),
+        ) => match &inputs[..] {
+            [CoreValue::Array(_)] => {
+                let mut arr =
+                    extract_matches!(inputs.into_iter().next().unwrap(), CoreValue::Array);
+                let element = if matches!(libfunc, Array(ArrayConcreteLibfunc::SnapshotPopFront(_)))
+                {
+                    (!arr.is_empty()).then(|| arr.remove(0))
+                } else {
+                    arr.pop()
+                };
+                match element {
+                    Some(element) => Ok((vec![CoreValue::Array(arr), element], 0)),
+                    None => Ok((vec![CoreValue::Array(arr)], 1)),
+                }
+            }
+            [_] => Err(LibfuncSimulationError::WrongArgType),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint8(libfunc) => simulate_u8_libfunc(libfunc, &inputs),
         Uint16(libfunc) => simulate_u16_libfunc(libfunc, &inputs),
         Uint32(libfunc) => simulate_u32_libfunc(libfunc, &inputs),
         Uint64(libfunc) => simulate_u64_libfunc(libfunc, &inputs),
         Uint128(libfunc) => simulate_u128_libfunc(libfunc, &inputs),
         Sint8(_) | Sint16(_) | Sint32(_) | Sint64(_) | Sint128(_) => {
-            unimplemented!("Simulation of signed integer libfuncs is not implemented yet.")
+            Err(LibfuncSimulationError::UnsupportedLibfunc)
         }
         Bool(libfunc) => simulate_bool_libfunc(libfunc, &inputs),
         Felt252(libfunc) => simulate_felt252_libfunc(libfunc, &inputs),
@@ -280,7 +309,9 @@ pub fn simulate<
                 _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
             }
         }
-        Enum(EnumConcreteLibfunc::FromBoundedInt(_)) => todo!(),
+        Enum(EnumConcreteLibfunc::FromBoundedInt(_)) => {
+            Err(LibfuncSimulationError::UnsupportedLibfunc)
+        }
         Struct(StructConcreteLibfunc::Construct(_)) => Ok((vec![CoreValue::Struct(inputs)], 0)),
         Struct(
             StructConcreteLibfunc::Deconstruct(_) | StructConcreteLibfunc::SnapshotDeconstruct(_),
@@ -312,18 +343,31 @@ pub fn simulate<
                 _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
             }
         }
-        Pedersen(_) => {
-            unimplemented!("Simulation of the Pedersen hash function is not implemented yet.");
-        }
-        Poseidon(_) => {
-            unimplemented!("Simulation of the Poseidon hash function is not implemented yet.");
-        }
-        StarkNet(_) => {
-            unimplemented!("Simulation of the StarkNet functionalities is not implemented yet.")
+        Pedersen(_) | Poseidon(_) | Blake2s(_) | StarkNet(_) => {
+            Err(LibfuncSimulationError::UnsupportedLibfunc)
         }
-        Nullable(_) => {
-            unimplemented!("Simulation of nullable is not implemented yet.")
+        Nullable(NullableConcreteLibfunc::Null(_)) => {
+            if inputs.is_empty() {
+                Ok((vec![CoreValue::Nullable(None)], 0))
+            } else {
+                Err(LibfuncSimulationError::WrongNumberOfArgs)
+            }
         }
+        Nullable(NullableConcreteLibfunc::NullableFromBox(_)) => match &inputs[..] {
+            [value] => Ok((vec![CoreValue::Nullable(Some(Box::new(value.clone())))], 0)),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Nullable(NullableConcreteLibfunc::MatchNullable(_)) => match &inputs[..] {
+            [CoreValue::Nullable(None)] => Ok((vec![], 0)),
+            [CoreValue::Nullable(Some(value))] => Ok((vec![*value.clone()], 1)),
+            [_] => Err(LibfuncSimulationError::WrongArgType),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Nullable(NullableConcreteLibfunc::ForwardSnapshot(_)) => match &inputs[..] {
+            [CoreValue::Nullable(_)] => Ok((inputs, 0)),
+            [_] => Err(LibfuncSimulationError::WrongArgType),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Debug(_) => {
             if inputs.len() == 1 {
                 let arr = extract_matches!(&inputs[0], CoreValue::Array);
@@ -349,13 +393,8 @@ pub fn simulate<
             [value] => Ok((vec![value.clone(), value.clone()], 0)),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
-        Cast(_) => unimplemented!(),
-        Felt252DictEntry(_) => unimplemented!(),
-        Uint256(_) => unimplemented!(),
-        Uint512(_) => unimplemented!(),
-        Bytes31(_) => unimplemented!(),
-        Const(_) => unimplemented!(),
-        Coupon(_) => unimplemented!(),
+        Cast(_) | Felt252DictEntry(_) | Uint256(_) | Uint512(_) | Bytes31(_) | Const(_)
+        | Coupon(_) => Err(LibfuncSimulationError::UnsupportedLibfunc),
     }
 }
 
@@ -455,7 +494,7 @@ fn simulate_u128_libfunc(
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
         Uint128Concrete::ToFelt252(_) => match inputs {
-            [CoreValue::RangeCheck, CoreValue::Uint128(value)] => {
+            [CoreValue::Uint128(value)] => {
                 Ok((vec![CoreValue::Felt252(value.to_bigint().unwrap())], 0))
             }
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
@@ -491,7 +530,7 @@ fn simulate_u128_libfunc(
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
         Uint128Concrete::GuaranteeMul(_) | Uint128Concrete::MulGuaranteeVerify(_) => {
-            unimplemented!()
+            Err(LibfuncSimulationError::UnsupportedLibfunc)
         }
         Uint128Concrete::IsZero(_) => {
             match inputs {
@@ -525,7 +564,7 @@ fn simulate_u128_libfunc(
             [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
-        Uint128Concrete::ByteReverse(_) => todo!("ByteReverse"),
+        Uint128Concrete::ByteReverse(_) => Err(LibfuncSimulationError::UnsupportedLibfunc),
         Uint128Concrete::Bitwise(_) => match inputs {
             [CoreValue::Uint128(a), CoreValue::Uint128(b)] => Ok((
                 vec![
@@ -583,7 +622,7 @@ fn simulate_u8_libfunc(
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
         Uint8Concrete::ToFelt252(_) => match inputs {
-            [CoreValue::RangeCheck, CoreValue::Uint8(value)] => {
+            [CoreValue::Uint8(value)] => {
                 Ok((vec![CoreValue::Felt252(value.to_bigint().unwrap())], 0))
             }
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
@@ -597,9 +636,43 @@ fn simulate_u8_libfunc(
             [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
-        Uint8Concrete::IsZero(_) => unimplemented!(),
-        Uint8Concrete::Divmod(_) => unimplemented!(),
-        Uint8Concrete::Bitwise(_) => unimplemented!(),
+        Uint8Concrete::IsZero(_) => match inputs {
+            // Zero - jumping to the failure branch.
+            [CoreValue::Uint8(0)] => Ok((vec![], 0)),
+            // Non-zero - jumping to the success branch and providing a NonZero wrap to the given
+            // value.
+            [CoreValue::Uint8(value)] => {
+                Ok((vec![CoreValue::NonZero(Box::new(CoreValue::Uint8(*value)))], 1))
+            }
+            [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint8Concrete::Divmod(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint8(lhs), CoreValue::NonZero(non_zero)] => {
+                if let CoreValue::Uint8(rhs) = **non_zero {
+                    Ok((
+                        vec![
+                            CoreValue::RangeCheck,
+                            CoreValue::Uint8(lhs / rhs),
+                            CoreValue::Uint8(lhs % rhs),
+                        ],
+                        0,
+                    ))
+                } else {
+                    Err(LibfuncSimulationError::MemoryLayoutMismatch)
+                }
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint8Concrete::Bitwise(_) => match inputs {
+            [CoreValue::Uint8(a), CoreValue::Uint8(b)] => Ok((
+                vec![CoreValue::Uint8(a & b), CoreValue::Uint8(a | b), CoreValue::Uint8(a ^ b)],
+                0,
+            )),
+            [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint8Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)] => {
                 Ok((vec![CoreValue::Uint16(u16::from(*lhs) * u16::from(*rhs))], 0))
@@ -652,7 +725,7 @@ fn simulate_u16_libfunc(
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
         Uint16Concrete::ToFelt252(_) => match inputs {
-            [CoreValue::RangeCheck, CoreValue::Uint16(value)] => {
+            [CoreValue::Uint16(value)] => {
                 Ok((vec![CoreValue::Felt252(value.to_bigint().unwrap())], 0))
             }
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
@@ -666,9 +739,43 @@ fn simulate_u16_libfunc(
             [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
-        Uint16Concrete::IsZero(_) => unimplemented!(),
-        Uint16Concrete::Divmod(_) => unimplemented!(),
-        Uint16Concrete::Bitwise(_) => unimplemented!(),
+        Uint16Concrete::IsZero(_) => match inputs {
+            // Zero - jumping to the failure branch.
+            [CoreValue::Uint16(0)] => Ok((vec![], 0)),
+            // Non-zero - jumping to the success branch and providing a NonZero wrap to the given
+            // value.
+            [CoreValue::Uint16(value)] => {
+                Ok((vec![CoreValue::NonZero(Box::new(CoreValue::Uint16(*value)))], 1))
+            }
+            [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint16Concrete::Divmod(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint16(lhs), CoreValue::NonZero(non_zero)] => {
+                if let CoreValue::Uint16(rhs) = **non_zero {
+                    Ok((
+                        vec![
+                            CoreValue::RangeCheck,
+                            CoreValue::Uint16(lhs / rhs),
+                            CoreValue::Uint16(lhs % rhs),
+                        ],
+                        0,
+                    ))
+                } else {
+                    Err(LibfuncSimulationError::MemoryLayoutMismatch)
+                }
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint16Concrete::Bitwise(_) => match inputs {
+            [CoreValue::Uint16(a), CoreValue::Uint16(b)] => Ok((
+                vec![CoreValue::Uint16(a & b), CoreValue::Uint16(a | b), CoreValue::Uint16(a ^ b)],
+                0,
+            )),
+            [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint16Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)] => {
                 Ok((vec![CoreValue::Uint32(u32::from(*lhs) * u32::from(*rhs))], 0))
@@ -721,7 +828,7 @@ fn simulate_u32_libfunc(
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
         Uint32Concrete::ToFelt252(_) => match inputs {
-            [CoreValue::RangeCheck, CoreValue::Uint32(value)] => {
+            [CoreValue::Uint32(value)] => {
                 Ok((vec![CoreValue::Felt252(value.to_bigint().unwrap())], 0))
             }
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
@@ -735,9 +842,43 @@ fn simulate_u32_libfunc(
             [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
-        Uint32Concrete::IsZero(_) => unimplemented!(),
-        Uint32Concrete::Divmod(_) => unimplemented!(),
-        Uint32Concrete::Bitwise(_) => unimplemented!(),
+        Uint32Concrete::IsZero(_) => match inputs {
+            // Zero - jumping to the failure branch.
+            [CoreValue::Uint32(0)] => Ok((vec![], 0)),
+            // Non-zero - jumping to the success branch and providing a NonZero wrap to the given
+            // value.
+            [CoreValue::Uint32(value)] => {
+                Ok((vec![CoreValue::NonZero(Box::new(CoreValue::Uint32(*value)))], 1))
+            }
+            [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint32Concrete::Divmod(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint32(lhs), CoreValue::NonZero(non_zero)] => {
+                if let CoreValue::Uint32(rhs) = **non_zero {
+                    Ok((
+                        vec![
+                            CoreValue::RangeCheck,
+                            CoreValue::Uint32(lhs / rhs),
+                            CoreValue::Uint32(lhs % rhs),
+                        ],
+                        0,
+                    ))
+                } else {
+                    Err(LibfuncSimulationError::MemoryLayoutMismatch)
+                }
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint32Concrete::Bitwise(_) => match inputs {
+            [CoreValue::Uint32(a), CoreValue::Uint32(b)] => Ok((
+                vec![CoreValue::Uint32(a & b), CoreValue::Uint32(a | b), CoreValue::Uint32(a ^ b)],
+                0,
+            )),
+            [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint32Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)] => {
                 Ok((vec![CoreValue::Uint64(u64::from(*lhs) * u64::from(*rhs))], 0))
@@ -790,7 +931,7 @@ fn simulate_u64_libfunc(
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
         Uint64Concrete::ToFelt252(_) => match inputs {
-            [CoreValue::RangeCheck, CoreValue::Uint64(value)] => {
+            [CoreValue::Uint64(value)] => {
                 Ok((vec![CoreValue::Felt252(value.to_bigint().unwrap())], 0))
             }
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
@@ -804,9 +945,43 @@ fn simulate_u64_libfunc(
             [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
-        Uint64Concrete::IsZero(_) => unimplemented!(),
-        Uint64Concrete::Divmod(_) => unimplemented!(),
-        Uint64Concrete::Bitwise(_) => unimplemented!(),
+        Uint64Concrete::IsZero(_) => match inputs {
+            // Zero - jumping to the failure branch.
+            [CoreValue::Uint64(0)] => Ok((vec![], 0)),
+            // Non-zero - jumping to the success branch and providing a NonZero wrap to the given
+            // value.
+            [CoreValue::Uint64(value)] => {
+                Ok((vec![CoreValue::NonZero(Box::new(CoreValue::Uint64(*value)))], 1))
+            }
+            [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint64Concrete::Divmod(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint64(lhs), CoreValue::NonZero(non_zero)] => {
+                if let CoreValue::Uint64(rhs) = **non_zero {
+                    Ok((
+                        vec![
+                            CoreValue::RangeCheck,
+                            CoreValue::Uint64(lhs / rhs),
+                            CoreValue::Uint64(lhs % rhs),
+                        ],
+                        0,
+                    ))
+                } else {
+                    Err(LibfuncSimulationError::MemoryLayoutMismatch)
+                }
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint64Concrete::Bitwise(_) => match inputs {
+            [CoreValue::Uint64(a), CoreValue::Uint64(b)] => Ok((
+                vec![CoreValue::Uint64(a & b), CoreValue::Uint64(a | b), CoreValue::Uint64(a ^ b)],
+                0,
+            )),
+            [_, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint64Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)] => {
                 Ok((vec![CoreValue::Uint128(u128::from(*lhs) * u128::from(*rhs))], 0))
@@ -817,6 +992,25 @@ fn simulate_u64_libfunc(
     }
 }
 
+/// Returns the canonical representation of a felt252 value, in the range `[0, PRIME)`.
+fn felt252_canonical(value: &BigInt) -> BigInt {
+    Felt252Value::from(value.clone()).to_biguint().into()
+}
+
+/// Returns the canonical result of a felt252 binary operation.
+/// The divisor of a division is expected to be non-zero.
+fn felt252_operation(operator: Felt252BinaryOperator, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    let lhs = Felt252Value::from(lhs.clone());
+    let rhs = Felt252Value::from(rhs.clone());
+    let result = match operator {
+        Felt252BinaryOperator::Add => lhs + rhs,
+        Felt252BinaryOperator::Sub => lhs - rhs,
+        Felt252BinaryOperator::Mul => lhs * rhs,
+        Felt252BinaryOperator::Div => lhs / rhs,
+    };
+    result.to_biguint().into()
+}
+
 /// Simulate felt252 library functions.
 fn simulate_felt252_libfunc(
     libfunc: &Felt252Concrete,
@@ -825,7 +1019,7 @@ fn simulate_felt252_libfunc(
     match libfunc {
         Felt252Concrete::Const(Felt252ConstConcreteLibfunc { c, .. }) => {
             if inputs.is_empty() {
-                Ok((vec![CoreValue::Felt252(c.to_bigint().unwrap())], 0))
+                Ok((vec![CoreValue::Felt252(felt252_canonical(c))], 0))
             } else {
                 Err(LibfuncSimulationError::WrongNumberOfArgs)
             }
@@ -838,21 +1032,13 @@ fn simulate_felt252_libfunc(
                 Felt252BinaryOperator::Add
                 | Felt252BinaryOperator::Sub
                 | Felt252BinaryOperator::Mul,
-            ) => Ok((
-                vec![CoreValue::Felt252(match operator {
-                    Felt252BinaryOperator::Add => lhs + rhs,
-                    Felt252BinaryOperator::Sub => lhs - rhs,
-                    Felt252BinaryOperator::Mul => lhs * rhs,
-                    _ => unreachable!("Arm only handles these cases."),
-                })],
-                0,
-            )),
+            ) => Ok((vec![CoreValue::Felt252(felt252_operation(*operator, lhs, rhs))], 0)),
             (
-                [CoreValue::Felt252(_lhs), CoreValue::NonZero(non_zero)],
+                [CoreValue::Felt252(lhs), CoreValue::NonZero(non_zero)],
                 Felt252BinaryOperator::Div,
             ) => {
-                if let CoreValue::Felt252(_rhs) = *non_zero.clone() {
-                    todo!("Support felt252_div operation.")
+                if let CoreValue::Felt252(rhs) = &**non_zero {
+                    Ok((vec![CoreValue::Felt252(felt252_operation(*operator, lhs, rhs))], 0))
                 } else {
                     Err(LibfuncSimulationError::MemoryLayoutMismatch)
                 }
@@ -863,15 +1049,9 @@ fn simulate_felt252_libfunc(
         Felt252Concrete::BinaryOperation(Felt252BinaryOperationConcrete::WithConst(
             Felt252OperationWithConstConcreteLibfunc { operator, c, .. },
         )) => match inputs {
-            [CoreValue::Felt252(value)] => Ok((
-                vec![CoreValue::Felt252(match operator {
-                    Felt252BinaryOperator::Add => value + c.clone(),
-                    Felt252BinaryOperator::Sub => value - c.clone(),
-                    Felt252BinaryOperator::Mul => value * c.clone(),
-                    Felt252BinaryOperator::Div => todo!("Support full felt252 operations."),
-                })],
-                0,
-            )),
+            [CoreValue::Felt252(value)] => {
+                Ok((vec![CoreValue::Felt252(felt252_operation(*operator, value, c))], 0))
+            }
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
@@ -1,3 +1,10 @@
+//! Simulation of Sierra programs - a reference interpreter, running the functions of a program over
+//! a logical model of their values, without compiling them to CASM.
+//!
+//! The simulation is useful for fast execution in unit tests, and as an oracle in differential
+//! testing of the compilation to CASM. Libfuncs the simulation does not support are reported as
+//! [LibfuncSimulationError::UnsupportedLibfunc] errors.
+
 use std::collections::HashMap;
 
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
@@ -27,6 +34,8 @@ pub enum LibfuncSimulationError {
     MemoryLayoutMismatch,
     #[error("Could not resolve requested symbol value")]
     UnresolvedStatementGasInfo,
+    #[error("Simulation of the libfunc is not supported")]
+    UnsupportedLibfunc,
     #[error("Error occurred during user function call")]
     FunctionSimulationError(FunctionId, Box<SimulationError>),
 }
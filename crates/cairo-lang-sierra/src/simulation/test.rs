@@ -1,12 +1,13 @@
 use bimap::BiMap;
+use cairo_felt::Felt252 as Felt252Value;
 use num_bigint::BigInt;
 use test_case::test_case;
 
 use super::value::CoreValue::{
-    self, Array, GasBuiltin, NonZero, RangeCheck, Uint128, Uint64, Uninitialized,
+    self, Array, Felt252, GasBuiltin, NonZero, Nullable, RangeCheck, Uint128, Uint64, Uninitialized,
 };
 use super::LibfuncSimulationError::{
-    self, FunctionSimulationError, MemoryLayoutMismatch, WrongNumberOfArgs,
+    self, FunctionSimulationError, MemoryLayoutMismatch, UnsupportedLibfunc, WrongNumberOfArgs,
 };
 use super::{core, SimulationError};
 use crate::extensions::core::CoreLibfunc;
@@ -32,6 +33,11 @@ fn user_func_arg(name: &str) -> GenericArg {
     GenericArg::UserFunc(name.into())
 }
 
+/// Returns the felt252 value of `v` modulo the prime.
+fn felt252(v: i64) -> CoreValue {
+    Felt252(Felt252Value::from(v).to_biguint().into())
+}
+
 struct MockSpecializationContext {
     mapping: BiMap<ConcreteTypeId, ConcreteTypeLongId>,
 }
@@ -157,6 +163,23 @@ fn simulate(
 #[test_case("u128_overflowing_sub", vec![], vec![RangeCheck, Uint128(3), Uint128(5)]
              => Ok((vec![RangeCheck, Uint128(u128::MAX - 1)], 1));
             "u128_overflowing_sub(3, 5)")]
+#[test_case("u64_is_zero", vec![], vec![Uint64(2)]
+             => Ok((vec![NonZero(Box::new(Uint64(2)))], 1)); "u64_is_zero(2)")]
+#[test_case("u64_is_zero", vec![], vec![Uint64(0)] => Ok((vec![], 0)); "u64_is_zero(0)")]
+#[test_case("array_snapshot_pop_front", vec![type_arg("u128")],
+            vec![Array(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![Array(vec![Uint128(2)]), Uint128(1)], 0));
+            "array_snapshot_pop_front([1, 2])")]
+#[test_case("array_snapshot_pop_front", vec![type_arg("u128")], vec![Array(vec![])]
+             => Ok((vec![Array(vec![])], 1)); "array_snapshot_pop_front([])")]
+#[test_case("array_snapshot_pop_back", vec![type_arg("u128")],
+            vec![Array(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![Array(vec![Uint128(1)]), Uint128(2)], 0));
+            "array_snapshot_pop_back([1, 2])")]
+#[test_case("match_nullable", vec![type_arg("u128")], vec![Nullable(None)]
+             => Ok((vec![], 0)); "match_nullable(null)")]
+#[test_case("match_nullable", vec![type_arg("u128")], vec![Nullable(Some(Box::new(Uint128(3))))]
+             => Ok((vec![Uint128(3)], 1)); "match_nullable(3)")]
 fn simulate_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -194,6 +217,24 @@ fn simulate_branch(
              => Ok(vec![]); "function_call<drop_all_inputs>()")]
 #[test_case("function_call", vec![user_func_arg("identity")], vec![Uint128(3), Uint128(5)]
              => Ok(vec![Uint128(3), Uint128(5)]); "function_call<identity>()")]
+#[test_case("felt252_add", vec![], vec![felt252(-1), felt252(2)] => Ok(vec![felt252(1)]);
+            "felt252_add(-1, 2)")]
+#[test_case("felt252_sub", vec![], vec![felt252(1), felt252(2)] => Ok(vec![felt252(-1)]);
+            "felt252_sub(1, 2)")]
+#[test_case("felt252_mul", vec![], vec![felt252(-1), felt252(3)] => Ok(vec![felt252(-3)]);
+            "felt252_mul(-1, 3)")]
+#[test_case("felt252_div", vec![], vec![felt252(-6), NonZero(Box::new(felt252(3)))]
+             => Ok(vec![felt252(-2)]); "felt252_div(-6, 3)")]
+#[test_case("felt252_div_const", vec![value_arg(2)], vec![felt252(1)]
+             => Ok(vec![Felt252((Felt252Value::from(1) / Felt252Value::from(2)).to_biguint().into())]);
+            "felt252_div_const<2>(1)")]
+#[test_case("felt252_const", vec![value_arg(-1)], vec![] => Ok(vec![felt252(-1)]);
+            "felt252_const<-1>()")]
+#[test_case("u64_safe_divmod", vec![], vec![RangeCheck, Uint64(32), NonZero(Box::new(Uint64(5)))]
+             => Ok(vec![RangeCheck, Uint64(6), Uint64(2)]); "u64_safe_divmod(32, 5)")]
+#[test_case("null", vec![type_arg("u128")], vec![] => Ok(vec![Nullable(None)]); "null<u128>()")]
+#[test_case("nullable_from_box", vec![type_arg("u128")], vec![Uint128(3)]
+             => Ok(vec![Nullable(Some(Box::new(Uint128(3))))]); "nullable_from_box<u128>(3)")]
 fn simulate_none_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -236,6 +277,8 @@ fn simulate_none_branch(
                 "unimplemented".into(),
                 Box::new(SimulationError::StatementOutOfBounds(StatementIdx(0))));
             "function_call<unimplemented>()")]
+#[test_case("u128_byte_reverse", vec![], vec![Uint128(1)] => UnsupportedLibfunc;
+            "u128_byte_reverse(1)")]
 fn simulate_error(
     id: &str,
     generic_args: Vec<GenericArg>,
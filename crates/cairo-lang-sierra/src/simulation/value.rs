@@ -16,6 +16,8 @@ pub enum CoreValue {
     Uint64(u64),
     Uint128(u128),
     NonZero(Box<CoreValue>),
+    /// A nullable value, which is either null or a boxed value.
+    Nullable(Option<Box<CoreValue>>),
     Ref(Box<CoreValue>),
     Array(Vec<CoreValue>),
     Dict(HashMap<BigInt, CoreValue>),
@@ -0,0 +1,265 @@
+//! Exporting of Sierra functions in a readable SSA form, for analysis tools.
+//!
+//! Each definition of a Sierra variable `[n]` is a distinct typed value `%n.k`, where `k` counts
+//! the previous definitions of the variable. The statements are grouped into basic blocks, named by
+//! their first statement, whose parameters are the variables available when entering them, so the
+//! branches explicitly pass values to their targets instead of using phi nodes.
+
+use std::fmt::Write;
+
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::extensions::core::{CoreLibfunc, CoreType};
+use crate::extensions::ConcreteLibfunc;
+use crate::ids::{ConcreteTypeId, FunctionId, VarId};
+use crate::program::{BranchTarget, Function, Invocation, Program, Statement, StatementIdx};
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+
+#[cfg(test)]
+#[path = "ssa_test.rs"]
+mod test;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum SsaExportError {
+    #[error(transparent)]
+    ProgramRegistryError(#[from] Box<ProgramRegistryError>),
+    #[error("#{0}: Statement out of the program.")]
+    StatementOutOfBounds(StatementIdx),
+    #[error("#{statement_idx}: Variable {var_id} is undefined.")]
+    UndefinedVariable { statement_idx: StatementIdx, var_id: VarId },
+}
+
+/// The variables available at a point of a function, with their types.
+type Environment = OrderedHashMap<VarId, ConcreteTypeId>;
+
+/// Returns the function `function_id` of `program` in SSA form.
+pub fn function_to_ssa(
+    program: &Program,
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    function_id: &FunctionId,
+) -> Result<String, SsaExportError> {
+    let func = registry.get_function(function_id)?;
+    let leaders = block_leaders(program, func)?;
+    let blocks = block_params(program, registry, func, &leaders)?;
+    let mut writer = SsaWriter {
+        program,
+        registry,
+        leaders: &leaders,
+        blocks: &blocks,
+        versions: UnorderedHashMap::default(),
+        out: String::new(),
+    };
+    writer.write_function(func)?;
+    Ok(writer.out)
+}
+
+/// Returns the statement of `program` at `idx`.
+fn statement(program: &Program, idx: StatementIdx) -> Result<&Statement, SsaExportError> {
+    program.statements.get(idx.0).ok_or(SsaExportError::StatementOutOfBounds(idx))
+}
+
+/// Returns whether the invocation continues to the next statement without branching.
+fn is_plain(invocation: &Invocation) -> bool {
+    matches!(&invocation.branches[..], [branch] if branch.target == BranchTarget::Fallthrough)
+}
+
+/// Returns the first statements of the basic blocks of `func`: its entry point and the targets of
+/// its branches.
+fn block_leaders(
+    program: &Program,
+    func: &Function,
+) -> Result<UnorderedHashSet<StatementIdx>, SsaExportError> {
+    let mut leaders = UnorderedHashSet::<_>::from_iter([func.entry_point]);
+    let mut visited = UnorderedHashSet::<StatementIdx>::default();
+    let mut stack = vec![func.entry_point];
+    while let Some(idx) = stack.pop() {
+        if !visited.insert(idx) {
+            continue;
+        }
+        let Statement::Invocation(invocation) = statement(program, idx)? else {
+            continue;
+        };
+        for branch in &invocation.branches {
+            let target = idx.next(&branch.target);
+            if !is_plain(invocation) {
+                leaders.insert(target);
+            }
+            stack.push(target);
+        }
+    }
+    Ok(leaders)
+}
+
+/// Returns the parameters of the basic blocks of `func` by their first statements, ordered by
+/// them.
+fn block_params(
+    program: &Program,
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    func: &Function,
+    leaders: &UnorderedHashSet<StatementIdx>,
+) -> Result<OrderedHashMap<StatementIdx, Environment>, SsaExportError> {
+    let entry_env: Environment =
+        func.params.iter().map(|param| (param.id.clone(), param.ty.clone())).collect();
+    let mut blocks = UnorderedHashMap::<_, _>::from_iter([(func.entry_point, entry_env.clone())]);
+    let mut stack = vec![(func.entry_point, entry_env)];
+    while let Some((mut idx, mut env)) = stack.pop() {
+        while let Statement::Invocation(invocation) = statement(program, idx)? {
+            for var_id in &invocation.args {
+                env.swap_remove(var_id).ok_or_else(|| SsaExportError::UndefinedVariable {
+                    statement_idx: idx,
+                    var_id: var_id.clone(),
+                })?;
+            }
+            let output_types = registry.get_libfunc(&invocation.libfunc_id)?.output_types();
+            let mut successors = vec![];
+            for (branch, types) in invocation.branches.iter().zip(output_types) {
+                let mut branch_env = env.clone();
+                branch_env.extend(branch.results.iter().cloned().zip(types));
+                successors.push((idx.next(&branch.target), branch_env));
+            }
+            if is_plain(invocation) && !leaders.contains(&successors[0].0) {
+                (idx, env) = successors.pop().unwrap();
+                continue;
+            }
+            for (target, branch_env) in successors {
+                if !blocks.contains_key(&target) {
+                    blocks.insert(target, branch_env.clone());
+                    stack.push((target, branch_env));
+                }
+            }
+            break;
+        }
+    }
+    Ok(blocks.into_iter_sorted_by_key(|(idx, _)| idx.0).collect())
+}
+
+/// Writes a function in SSA form.
+struct SsaWriter<'a> {
+    program: &'a Program,
+    registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    leaders: &'a UnorderedHashSet<StatementIdx>,
+    blocks: &'a OrderedHashMap<StatementIdx, Environment>,
+    /// The number of definitions of each variable so far.
+    versions: UnorderedHashMap<VarId, usize>,
+    out: String,
+}
+impl SsaWriter<'_> {
+    fn write_function(&mut self, func: &Function) -> Result<(), SsaExportError> {
+        writeln!(
+            self.out,
+            "fn {}({}) -> ({}):",
+            func.id,
+            func.signature.param_types.iter().join(", "),
+            func.signature.ret_types.iter().join(", ")
+        )
+        .unwrap();
+        for (start, params) in self.blocks.iter() {
+            self.write_block(*start, params)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the block starting at `start` with the parameters `params`.
+    fn write_block(
+        &mut self,
+        start: StatementIdx,
+        params: &Environment,
+    ) -> Result<(), SsaExportError> {
+        let mut names = UnorderedHashMap::<VarId, String>::default();
+        let params =
+            params.iter().map(|(var_id, ty)| self.define(&mut names, var_id, ty)).join(", ");
+        writeln!(self.out, "bb{start}({params}):").unwrap();
+        let mut idx = start;
+        loop {
+            let invocation = match statement(self.program, idx)? {
+                Statement::Return(var_ids) => {
+                    let values = self.uses(&names, idx, var_ids)?;
+                    writeln!(self.out, "    return({values})").unwrap();
+                    return Ok(());
+                }
+                Statement::Invocation(invocation) => invocation,
+            };
+            let args = self.uses(&names, idx, &invocation.args)?;
+            let output_types = self.registry.get_libfunc(&invocation.libfunc_id)?.output_types();
+            if is_plain(invocation) {
+                let results = invocation.branches[0]
+                    .results
+                    .iter()
+                    .zip(&output_types[0])
+                    .map(|(var_id, ty)| self.define(&mut names, var_id, ty))
+                    .join(", ");
+                let assignment = if results.is_empty() { results } else { results + " = " };
+                writeln!(self.out, "    {assignment}{}({args})", invocation.libfunc_id).unwrap();
+                idx = idx.next(&BranchTarget::Fallthrough);
+                if self.leaders.contains(&idx) {
+                    let jump = self.jump(&names, idx)?;
+                    writeln!(self.out, "    br {jump}").unwrap();
+                    return Ok(());
+                }
+                continue;
+            }
+            writeln!(self.out, "    {}({args}) -> [", invocation.libfunc_id).unwrap();
+            for (branch, types) in invocation.branches.iter().zip(&output_types) {
+                let mut branch_names = names.clone();
+                let results = branch
+                    .results
+                    .iter()
+                    .zip(types)
+                    .map(|(var_id, ty)| self.define(&mut branch_names, var_id, ty))
+                    .join(", ");
+                let jump = self.jump(&branch_names, idx.next(&branch.target))?;
+                writeln!(self.out, "        ({results}) => {jump},").unwrap();
+            }
+            writeln!(self.out, "    ]").unwrap();
+            return Ok(());
+        }
+    }
+
+    /// Defines a new value for `var_id`, and returns its typed name.
+    fn define(
+        &mut self,
+        names: &mut UnorderedHashMap<VarId, String>,
+        var_id: &VarId,
+        ty: &ConcreteTypeId,
+    ) -> String {
+        let version = self.versions.entry(var_id.clone()).or_default();
+        let name = format!("%{}.{version}", var_id.id);
+        *version += 1;
+        names.insert(var_id.clone(), name.clone());
+        format!("{name}: {ty}")
+    }
+
+    /// Returns the names of the current values of `var_ids`.
+    fn uses(
+        &self,
+        names: &UnorderedHashMap<VarId, String>,
+        statement_idx: StatementIdx,
+        var_ids: &[VarId],
+    ) -> Result<String, SsaExportError> {
+        Ok(var_ids
+            .iter()
+            .map(|var_id| {
+                names.get(var_id).cloned().ok_or_else(|| SsaExportError::UndefinedVariable {
+                    statement_idx,
+                    var_id: var_id.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join(", "))
+    }
+
+    /// Returns a jump to the block starting at `target`, passing the current values of its
+    /// parameters.
+    fn jump(
+        &self,
+        names: &UnorderedHashMap<VarId, String>,
+        target: StatementIdx,
+    ) -> Result<String, SsaExportError> {
+        let params = self.blocks[&target].keys().cloned().collect_vec();
+        Ok(format!("bb{target}({})", self.uses(names, target, &params)?))
+    }
+}
@@ -0,0 +1,92 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{function_to_ssa, SsaExportError};
+use crate::ids::{FunctionId, VarId};
+use crate::program::StatementIdx;
+use crate::program_registry::ProgramRegistry;
+use crate::ProgramParser;
+
+#[test]
+fn export_function_to_ssa() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_add = felt252_add;
+            libfunc felt252_dup = dup<felt252>;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc branch_align = branch_align;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc jump = jump;
+
+            felt252_add([0], [1]) -> ([2]);
+            felt252_dup([2]) -> ([2], [3]);
+            felt252_is_zero([3]) { fallthrough() 5([4]) };
+            branch_align() -> ();
+            jump() { 9() };
+            branch_align() -> ();
+            drop_nz_felt252([4]) -> ();
+            felt252_dup([2]) -> ([2], [3]);
+            felt252_add([2], [3]) -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+
+            test::foo@0([0]: felt252, [1]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::new(&program).unwrap();
+    assert_eq!(
+        function_to_ssa(&program, &registry, &"test::foo".into()).unwrap(),
+        indoc! {"
+            fn test::foo(felt252, felt252) -> (felt252):
+            bb0(%0.0: felt252, %1.0: felt252):
+                %2.0: felt252 = felt252_add(%0.0, %1.0)
+                %2.1: felt252, %3.0: felt252 = felt252_dup(%2.0)
+                felt252_is_zero(%3.0) -> [
+                    () => bb3(%2.1),
+                    (%4.0: NonZeroFelt252) => bb5(%2.1, %4.0),
+                ]
+            bb3(%2.2: felt252):
+                branch_align()
+                jump() -> [
+                    () => bb9(%2.2),
+                ]
+            bb5(%2.3: felt252, %4.1: NonZeroFelt252):
+                branch_align()
+                drop_nz_felt252(%4.1)
+                %2.4: felt252, %3.1: felt252 = felt252_dup(%2.3)
+                %2.5: felt252 = felt252_add(%2.4, %3.1)
+                br bb9(%2.5)
+            bb9(%2.6: felt252):
+                %2.7: felt252 = store_temp_felt252(%2.6)
+                return(%2.7)
+        "}
+    );
+}
+
+#[test]
+fn export_invalid_function_to_ssa() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            store_temp_felt252([1]) -> ([1]);
+            return([1]);
+
+            test::foo@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::new(&program).unwrap();
+    assert_eq!(
+        function_to_ssa(&program, &registry, &FunctionId::from_string("test::foo")),
+        Err(SsaExportError::UndefinedVariable {
+            statement_idx: StatementIdx(0),
+            var_id: VarId::new(1)
+        })
+    );
+}
@@ -23,6 +23,7 @@ pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
         .insert("Option".into(), as_named_type_long_id("Enum", "Option", &["felt252", "Tuple<>"]));
     elements.insert("NonZeroFelt252".into(), as_type_long_id("NonZero", &["felt252"]));
     elements.insert("NonZeroU128".into(), as_type_long_id("NonZero", &["u128"]));
+    elements.insert("NonZeroU64".into(), as_type_long_id("NonZero", &["u64"]));
     elements.insert("ArrayFelt252".into(), as_type_long_id("Array", &["felt252"]));
     elements.insert("ArrayFelt252".into(), as_type_long_id("Array", &["felt252"]));
     elements.insert(
@@ -47,10 +48,12 @@ pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
     );
     elements.insert("ArrayU128".into(), as_type_long_id("Array", &["u128"]));
     elements.insert("BoxU128".into(), as_type_long_id("Box", &["u128"]));
+    elements.insert("NullableU128".into(), as_type_long_id("Nullable", &["u128"]));
     elements.insert("UninitializedFelt252".into(), as_type_long_id("Uninitialized", &["felt252"]));
     elements.insert("Uninitializedu128".into(), as_type_long_id("Uninitialized", &["u128"]));
     elements.insert("GasBuiltin".into(), as_type_long_id("GasBuiltin", &[]));
     elements.insert("RangeCheck".into(), as_type_long_id("RangeCheck", &[]));
+    elements.insert("Bitwise".into(), as_type_long_id("Bitwise", &[]));
     elements.insert("System".into(), as_type_long_id("System", &[]));
     elements.insert("StorageBaseAddress".into(), as_type_long_id("StorageBaseAddress", &[]));
     elements.insert("StorageAddress".into(), as_type_long_id("StorageAddress", &[]));
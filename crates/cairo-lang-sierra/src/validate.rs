@@ -0,0 +1,271 @@
+//! Structural validation of a Sierra program - checking that its ids are unique, that its
+//! references resolve, and that its branch targets and function signatures are consistent.
+//!
+//! The validation does not specialize the types and libfuncs of the program, so it is cheaper than
+//! building a [crate::program_registry::ProgramRegistry], and reports all the errors found, each
+//! with its location in the program.
+
+use std::fmt;
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use thiserror::Error;
+
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, VarId};
+use crate::program::{BranchTarget, GenericArg, Program, Statement, StatementIdx};
+
+#[cfg(test)]
+#[path = "validate_test.rs"]
+mod test;
+
+/// The location of a validation error in a Sierra program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationLocation {
+    /// The type declaration with the given index.
+    TypeDeclaration(usize),
+    /// The libfunc declaration with the given index.
+    LibfuncDeclaration(usize),
+    Statement(StatementIdx),
+    /// The declaration of the function with the given index.
+    Function(usize),
+}
+impl fmt::Display for ValidationLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationLocation::TypeDeclaration(idx) => write!(f, "type declaration #{idx}"),
+            ValidationLocation::LibfuncDeclaration(idx) => write!(f, "libfunc declaration #{idx}"),
+            ValidationLocation::Statement(idx) => write!(f, "statement #{idx}"),
+            ValidationLocation::Function(idx) => write!(f, "function declaration #{idx}"),
+        }
+    }
+}
+
+/// The kind of a validation error.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    #[error("Type `{0}` is already declared in type declaration #{1}.")]
+    DuplicateTypeId(ConcreteTypeId, usize),
+    #[error("The same type is already declared in type declaration #{0}.")]
+    DuplicateTypeDeclaration(usize),
+    #[error("Libfunc `{0}` is already declared in libfunc declaration #{1}.")]
+    DuplicateLibfuncId(ConcreteLibfuncId, usize),
+    #[error("Function `{0}` is already declared in function declaration #{1}.")]
+    DuplicateFunctionId(FunctionId, usize),
+    #[error("Undeclared type `{0}`.")]
+    MissingType(ConcreteTypeId),
+    #[error("Undeclared libfunc `{0}`.")]
+    MissingLibfunc(ConcreteLibfuncId),
+    #[error("Undeclared function `{0}`.")]
+    MissingFunction(FunctionId),
+    #[error("Branch #{branch} targets statement #{target}, out of the program.")]
+    BranchTargetOutOfRange { branch: usize, target: StatementIdx },
+    #[error("Branch #{0} falls through past the last statement.")]
+    FallthroughOutOfRange(usize),
+    #[error("Entry point #{0} is out of the program.")]
+    EntryPointOutOfRange(StatementIdx),
+    #[error("{params} parameters for {signature_params} parameter types in the signature.")]
+    ParamCountMismatch { params: usize, signature_params: usize },
+    #[error("Parameter `{var}` is of type `{ty}`, but the signature type is `{signature_ty}`.")]
+    ParamTypeMismatch { var: VarId, ty: ConcreteTypeId, signature_ty: ConcreteTypeId },
+    #[error("Parameter `{0}` is declared twice.")]
+    DuplicateParam(VarId),
+}
+
+/// A structural error in a Sierra program.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{location}: {kind}")]
+pub struct ValidationError {
+    pub location: ValidationLocation,
+    pub kind: ValidationErrorKind,
+}
+
+/// Validates the structure of a Sierra program, returning all the errors found, ordered by their
+/// location.
+pub fn validate_program(program: &Program) -> Result<(), Vec<ValidationError>> {
+    let mut validator = Validator { program, errors: vec![] };
+    validator.validate();
+    if validator.errors.is_empty() { Ok(()) } else { Err(validator.errors) }
+}
+
+/// Helper for collecting the validation errors of a program.
+struct Validator<'a> {
+    program: &'a Program,
+    errors: Vec<ValidationError>,
+}
+impl<'a> Validator<'a> {
+    fn validate(&mut self) {
+        let program = self.program;
+        let type_ids = first_indices(program.type_declarations.iter().map(|decl| &decl.id));
+        let type_long_ids =
+            first_indices(program.type_declarations.iter().map(|decl| &decl.long_id));
+        let libfunc_ids = first_indices(program.libfunc_declarations.iter().map(|decl| &decl.id));
+        let function_ids = first_indices(program.funcs.iter().map(|function| &function.id));
+        let refs =
+            References { types: &type_ids, libfuncs: &libfunc_ids, functions: &function_ids };
+
+        for (idx, declaration) in program.type_declarations.iter().enumerate() {
+            let location = ValidationLocation::TypeDeclaration(idx);
+            let first = type_ids[&declaration.id];
+            if first != idx {
+                self.add(
+                    location.clone(),
+                    ValidationErrorKind::DuplicateTypeId(declaration.id.clone(), first),
+                );
+            }
+            let first = type_long_ids[&declaration.long_id];
+            if first != idx {
+                self.add(location.clone(), ValidationErrorKind::DuplicateTypeDeclaration(first));
+            }
+            self.validate_generic_args(&location, &declaration.long_id.generic_args, &refs);
+        }
+        for (idx, declaration) in program.libfunc_declarations.iter().enumerate() {
+            let location = ValidationLocation::LibfuncDeclaration(idx);
+            let first = libfunc_ids[&declaration.id];
+            if first != idx {
+                self.add(
+                    location.clone(),
+                    ValidationErrorKind::DuplicateLibfuncId(declaration.id.clone(), first),
+                );
+            }
+            self.validate_generic_args(&location, &declaration.long_id.generic_args, &refs);
+        }
+        for (idx, statement) in program.statements.iter().enumerate() {
+            let Statement::Invocation(invocation) = statement else {
+                continue;
+            };
+            let location = ValidationLocation::Statement(StatementIdx(idx));
+            if !libfunc_ids.contains_key(&invocation.libfunc_id) {
+                self.add(
+                    location.clone(),
+                    ValidationErrorKind::MissingLibfunc(invocation.libfunc_id.clone()),
+                );
+            }
+            for (branch, branch_info) in invocation.branches.iter().enumerate() {
+                match branch_info.target {
+                    BranchTarget::Fallthrough if idx + 1 >= program.statements.len() => {
+                        self.add(
+                            location.clone(),
+                            ValidationErrorKind::FallthroughOutOfRange(branch),
+                        );
+                    }
+                    BranchTarget::Statement(target) if target.0 >= program.statements.len() => {
+                        self.add(
+                            location.clone(),
+                            ValidationErrorKind::BranchTargetOutOfRange { branch, target },
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+        for (idx, function) in program.funcs.iter().enumerate() {
+            let location = ValidationLocation::Function(idx);
+            let first = function_ids[&function.id];
+            if first != idx {
+                self.add(
+                    location.clone(),
+                    ValidationErrorKind::DuplicateFunctionId(function.id.clone(), first),
+                );
+            }
+            if function.entry_point.0 >= program.statements.len() {
+                self.add(
+                    location.clone(),
+                    ValidationErrorKind::EntryPointOutOfRange(function.entry_point),
+                );
+            }
+            let signature = &function.signature;
+            for ty in signature.param_types.iter().chain(&signature.ret_types) {
+                self.validate_type(&location, ty, &refs);
+            }
+            if function.params.len() != signature.param_types.len() {
+                self.add(
+                    location.clone(),
+                    ValidationErrorKind::ParamCountMismatch {
+                        params: function.params.len(),
+                        signature_params: signature.param_types.len(),
+                    },
+                );
+            }
+            let mut vars = UnorderedHashSet::<&VarId>::default();
+            for (param, signature_ty) in function.params.iter().zip(&signature.param_types) {
+                if !vars.insert(&param.id) {
+                    self.add(
+                        location.clone(),
+                        ValidationErrorKind::DuplicateParam(param.id.clone()),
+                    );
+                }
+                if param.ty != *signature_ty {
+                    self.add(
+                        location.clone(),
+                        ValidationErrorKind::ParamTypeMismatch {
+                            var: param.id.clone(),
+                            ty: param.ty.clone(),
+                            signature_ty: signature_ty.clone(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    /// Validates that the ids referenced by the generic arguments of a declaration are declared.
+    fn validate_generic_args(
+        &mut self,
+        location: &ValidationLocation,
+        generic_args: &[GenericArg],
+        refs: &References<'_>,
+    ) {
+        for arg in generic_args {
+            match arg {
+                GenericArg::Type(ty) => self.validate_type(location, ty, refs),
+                GenericArg::Libfunc(libfunc_id) if !refs.libfuncs.contains_key(libfunc_id) => {
+                    self.add(
+                        location.clone(),
+                        ValidationErrorKind::MissingLibfunc(libfunc_id.clone()),
+                    );
+                }
+                GenericArg::UserFunc(function_id) if !refs.functions.contains_key(function_id) => {
+                    self.add(
+                        location.clone(),
+                        ValidationErrorKind::MissingFunction(function_id.clone()),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Validates that a referenced type is declared.
+    fn validate_type(
+        &mut self,
+        location: &ValidationLocation,
+        ty: &ConcreteTypeId,
+        refs: &References<'_>,
+    ) {
+        if !refs.types.contains_key(ty) {
+            self.add(location.clone(), ValidationErrorKind::MissingType(ty.clone()));
+        }
+    }
+
+    fn add(&mut self, location: ValidationLocation, kind: ValidationErrorKind) {
+        self.errors.push(ValidationError { location, kind });
+    }
+}
+
+/// The declared ids of a program, mapped to the index of their first declaration.
+struct References<'a> {
+    types: &'a UnorderedHashMap<&'a ConcreteTypeId, usize>,
+    libfuncs: &'a UnorderedHashMap<&'a ConcreteLibfuncId, usize>,
+    functions: &'a UnorderedHashMap<&'a FunctionId, usize>,
+}
+
+/// Maps each of the given keys to the index of its first occurrence.
+fn first_indices<'a, T: Eq + std::hash::Hash>(
+    keys: impl Iterator<Item = &'a T>,
+) -> UnorderedHashMap<&'a T, usize> {
+    let mut indices = UnorderedHashMap::default();
+    for (idx, key) in keys.enumerate() {
+        indices.entry(key).or_insert(idx);
+    }
+    indices
+}
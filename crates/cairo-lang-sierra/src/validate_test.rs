@@ -0,0 +1,78 @@
+use indoc::indoc;
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use super::validate_program;
+use crate::ProgramParser;
+
+#[test]
+fn validate_valid_program() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop<NonZeroFelt252> = drop<NonZeroFelt252>;
+            libfunc function_call<user@test::foo> = function_call<user@test::foo>;
+
+            felt252_is_zero([0]) { fallthrough() 2([1]) };
+            return([0]);
+            drop<NonZeroFelt252>([1]) -> ();
+            function_call<user@test::foo>([0]) -> ([0]);
+            return([0]);
+
+            test::foo@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    assert_eq!(validate_program(&program), Ok(()));
+}
+
+#[test]
+fn validate_invalid_program() {
+    let mut program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type felt252 = u8;
+            type Felt = felt252;
+            type Box = Box<u16>;
+
+            libfunc drop<u16> = drop<u16>;
+            libfunc drop<u16> = drop<u16>;
+            libfunc function_call<user@test::missing> = function_call<user@test::missing>;
+
+            drop<u16>([0]) -> ();
+            store_temp<felt252>([0]) -> ([0]);
+            jump() { 7() };
+            return([0]);
+            drop<u16>([0]) { fallthrough() };
+
+            test::foo@0([0]: felt252, [0]: felt252) -> (u32);
+            test::foo@3([0]: felt252) -> (felt252);
+            test::bar@5([0]: felt252, [1]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    program.funcs[2].params.pop();
+    program.funcs[2].params[0].ty = "u8".into();
+    assert_eq!(
+        validate_program(&program).unwrap_err().iter().join("\n"),
+        indoc! {"
+            type declaration #1: Type `felt252` is already declared in type declaration #0.
+            type declaration #2: The same type is already declared in type declaration #0.
+            type declaration #3: Undeclared type `u16`.
+            libfunc declaration #0: Undeclared type `u16`.
+            libfunc declaration #1: Libfunc `drop<u16>` is already declared in libfunc declaration #0.
+            libfunc declaration #1: Undeclared type `u16`.
+            libfunc declaration #2: Undeclared function `test::missing`.
+            statement #1: Undeclared libfunc `store_temp<felt252>`.
+            statement #2: Undeclared libfunc `jump`.
+            statement #2: Branch #0 targets statement #7, out of the program.
+            statement #4: Branch #0 falls through past the last statement.
+            function declaration #0: Undeclared type `u32`.
+            function declaration #0: Parameter `[0]` is declared twice.
+            function declaration #1: Function `test::foo` is already declared in function declaration #0.
+            function declaration #2: Entry point #5 is out of the program.
+            function declaration #2: 1 parameters for 2 parameter types in the signature.
+            function declaration #2: Parameter `[0]` is of type `u8`, but the signature type is `felt252`."}
+    );
+}
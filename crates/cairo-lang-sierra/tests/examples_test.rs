@@ -2,11 +2,13 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use cairo_felt::Felt252;
 use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
 use cairo_lang_sierra::program::{Program, StatementIdx};
 use cairo_lang_sierra::program_registry::ProgramRegistry;
 use cairo_lang_sierra::simulation::value::CoreValue;
 use cairo_lang_sierra::simulation::{self};
+use cairo_lang_sierra::validate::validate_program;
 use num_bigint::ToBigInt;
 use pretty_assertions::assert_eq;
 use test_case::test_case;
@@ -30,6 +32,12 @@ fn create_registry(name: &str) {
     ProgramRegistry::<CoreType, CoreLibfunc>::new(&get_example_program(name)).unwrap();
 }
 
+#[test_case("fib_jumps")]
+#[test_case("fib_no_gas")]
+fn validate(name: &str) {
+    validate_program(&get_example_program(name)).unwrap();
+}
+
 #[test_case((1000, 0), (1000, 1); "0 => 1")]
 #[test_case((1000, 1), (989, 1); "1 => 1")]
 #[test_case((1000, 2), (978, 2); "2 => 2")]
@@ -66,7 +74,8 @@ fn simulate_fib_jumps((gb, n): (i64, i128), (new_gb, fib): (i64, i128)) {
         Ok(vec![
             CoreValue::RangeCheck,
             CoreValue::GasBuiltin(new_gb),
-            CoreValue::Felt252(fib.to_bigint().unwrap())
+            // The simulated felt252 values are in the range `[0, PRIME)`.
+            CoreValue::Felt252(Felt252::from(fib).to_biguint().into())
         ])
     );
 }
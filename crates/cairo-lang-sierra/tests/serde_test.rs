@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use cairo_lang_sierra::program::VersionedProgram;
+use cairo_lang_sierra::program::{Program, VersionedProgram};
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use test_case::test_case;
@@ -54,3 +54,17 @@ fn serde_json_from_parser_test(example_name: &str) {
         "Could not serialize and deserialize VersionedProgram."
     );
 }
+
+// Parse code, encode it into binary, and then decode it, ensuring the original parsed code is
+// retained, and that the binary form is smaller than the textual and json forms.
+#[test_case("fib_jumps")]
+#[test_case("fib_no_gas")]
+fn binary_from_parser_test(example_name: &str) {
+    let program = get_test_program_from_sierra(example_name).into_v1().unwrap().program;
+    let bytes = program.to_bytes();
+    let decoded = Program::from_bytes(&bytes).expect("Could not decode binary Sierra program.");
+    assert_eq!(decoded, program);
+    assert_eq!(decoded.to_string(), program.to_string());
+    assert!(bytes.len() < program.to_string().len());
+    assert!(bytes.len() < serde_json::to_string(&program).unwrap().len());
+}
@@ -13,6 +13,11 @@ impl Contract {
         Self { items }
     }
 
+    /// Returns an iterator over the items of the ABI.
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter()
+    }
+
     pub fn json(&self) -> String {
         serde_json::to_string_pretty(&self).unwrap()
     }
@@ -202,6 +207,32 @@ pub struct Output {
 pub struct Struct {
     pub name: String,
     pub members: Vec<StructMember>,
+    /// The storage layout of the struct, if its `starknet::Store` derive packs its members into
+    /// shared storage slots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_layout: Option<Vec<StorageLayoutItem>>,
+}
+
+/// An item of the storage layout of a struct, in storage order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[serde(tag = "kind")]
+pub enum StorageLayoutItem {
+    /// A member stored using the `Store` implementation of its type.
+    #[serde(rename = "member")]
+    Member { name: String },
+    /// A single storage slot holding multiple members.
+    #[serde(rename = "packed_slot")]
+    PackedSlot { members: Vec<PackedStructMember> },
+}
+
+/// A struct member packed into a shared storage slot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct PackedStructMember {
+    pub name: String,
+    /// The index of the lowest bit of the member in the slot.
+    pub shift: usize,
+    /// The number of bits of the member.
+    pub bits: usize,
 }
 
 /// Struct member.
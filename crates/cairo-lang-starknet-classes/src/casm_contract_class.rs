@@ -18,11 +18,12 @@ use cairo_lang_sierra::extensions::starknet::syscalls::SystemType;
 use cairo_lang_sierra::extensions::structure::StructType;
 use cairo_lang_sierra::extensions::NamedType;
 use cairo_lang_sierra::ids::{ConcreteTypeId, GenericTypeId};
-use cairo_lang_sierra::program::{ConcreteTypeLongId, GenericArg, TypeDeclaration};
+use cairo_lang_sierra::program::{ConcreteTypeLongId, GenericArg, Program, TypeDeclaration};
 use cairo_lang_sierra_to_casm::compiler::{CompilationError, SierraToCasmConfig};
 use cairo_lang_sierra_to_casm::metadata::{
     calc_metadata, MetadataComputationConfig, MetadataError,
 };
+use cairo_lang_sierra_to_casm::segmentation::{NestedIntList, SegmentationError};
 use cairo_lang_utils::bigint::{deserialize_big_uint, serialize_big_uint, BigUintAsHex};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
@@ -43,9 +44,6 @@ use crate::compiler_version::{
     CONTRACT_SEGMENTATION_MINOR_VERSION,
 };
 use crate::contract_class::{ContractClass, ContractEntryPoint};
-use crate::contract_segmentation::{
-    compute_bytecode_segment_lengths, NestedIntList, SegmentationError,
-};
 use crate::felt252_serde::{sierra_from_felt252s, Felt252SerdeError};
 use crate::keccak::starknet_keccak;
 
@@ -340,22 +338,7 @@ impl CasmContractClass {
         max_bytecode_size: usize,
     ) -> Result<Self, StarknetSierraCompilationError> {
         let prime = Felt252::prime();
-        for felt252 in &contract_class.sierra_program {
-            if felt252.value >= prime {
-                return Err(StarknetSierraCompilationError::ValueOutOfRange);
-            }
-        }
-
-        let (sierra_version, _, program) = sierra_from_felt252s(&contract_class.sierra_program)?;
-        let current_sierra_version = current_sierra_version_id();
-        if !(sierra_version.major == current_sierra_version.major
-            && sierra_version.minor <= current_sierra_version.minor)
-        {
-            return Err(StarknetSierraCompilationError::UnsupportedSierraVersion {
-                version_in_contract: sierra_version,
-                version_of_compiler: current_sierra_version,
-            });
-        }
+        let (sierra_version, program) = decode_sierra_program(&contract_class)?;
 
         match &contract_class.entry_points_by_type.constructor.as_slice() {
             [] => {}
@@ -423,7 +406,7 @@ impl CasmContractClass {
         let cairo_program = cairo_lang_sierra_to_casm::compiler::compile(
             &program,
             &metadata,
-            SierraToCasmConfig { gas_usage_check: true, max_bytecode_size },
+            SierraToCasmConfig { max_bytecode_size, ..Default::default() },
         )?;
 
         let AssembledCairoProgram { bytecode, hints } = cairo_program.assemble();
@@ -439,7 +422,7 @@ impl CasmContractClass {
 
         let bytecode_segment_lengths =
             if sierra_version.minor >= CONTRACT_SEGMENTATION_MINOR_VERSION {
-                Some(compute_bytecode_segment_lengths(&program, &cairo_program, bytecode.len())?)
+                Some(cairo_program.bytecode_segment_lengths(&program)?)
             } else {
                 None
             };
@@ -566,6 +549,32 @@ impl CasmContractClass {
     }
 }
 
+/// Decodes the Sierra program of a contract class, checking that it is supported by the current
+/// compiler.
+#[allow(clippy::result_large_err)]
+pub(crate) fn decode_sierra_program(
+    contract_class: &ContractClass,
+) -> Result<(VersionId, Program), StarknetSierraCompilationError> {
+    let prime = Felt252::prime();
+    for felt252 in &contract_class.sierra_program {
+        if felt252.value >= prime {
+            return Err(StarknetSierraCompilationError::ValueOutOfRange);
+        }
+    }
+
+    let (sierra_version, _, program) = sierra_from_felt252s(&contract_class.sierra_program)?;
+    let current_sierra_version = current_sierra_version_id();
+    if !(sierra_version.major == current_sierra_version.major
+        && sierra_version.minor <= current_sierra_version.minor)
+    {
+        return Err(StarknetSierraCompilationError::UnsupportedSierraVersion {
+            version_in_contract: sierra_version,
+            version_of_compiler: current_sierra_version,
+        });
+    }
+    Ok((sierra_version, program))
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CasmContractEntryPoint {
     /// A field element that encodes the signature of the called function.
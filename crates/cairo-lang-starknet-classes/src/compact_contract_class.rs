@@ -0,0 +1,181 @@
+use cairo_felt::Felt252;
+use cairo_lang_sierra as sierra;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::abi::Contract;
+use crate::contract_class::{ContractClass, ContractEntryPoints};
+
+#[cfg(test)]
+#[path = "compact_contract_class_test.rs"]
+mod test;
+
+/// The largest felt252 encoded as a JSON number, as larger JSON numbers are not exact in common
+/// JSON parsers.
+const MAX_NUMBER_FELT252: u64 = (1 << 53) - 1;
+
+/// The alphabet of the base64 encoding of large felt252s.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum CompactContractClassError {
+    #[error("Invalid encoded felt252: `{0}`.")]
+    InvalidFelt252(String),
+    #[error("Encoded value is not a valid felt252: `{0}`.")]
+    ValueOutOfRange(BigUint),
+    #[error("Sierra program refers to {0}, which is not an entry of the repeated felt252s.")]
+    InvalidReference(i64),
+}
+
+/// A felt252 of the Sierra program of a [CompactContractClass].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CompactFelt252 {
+    /// A value of at most [MAX_NUMBER_FELT252], as a JSON number.
+    Number(u64),
+    /// A reference to an entry of the repeated felt252s, as a negative JSON number: `-1` refers
+    /// to the first entry, `-2` to the second and so on.
+    Reference(i64),
+    /// A larger value, as the unpadded base64 of its minimal big-endian bytes.
+    Base64(String),
+}
+
+/// A compact encoding of a [ContractClass], remaining plain JSON.
+///
+/// Small felt252s of the Sierra program are emitted as JSON numbers, and large felt252s as base64
+/// strings, so each value takes only as many characters as it needs. Large felt252s appearing more
+/// than once are emitted once into a table, and referred to from the program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactContractClass {
+    /// The large felt252s appearing more than once in the Sierra program, as base64.
+    pub repeated_felt252s: Vec<String>,
+    /// The Sierra program.
+    pub sierra_program: Vec<CompactFelt252>,
+    pub sierra_program_debug_info: Option<sierra::debug_info::DebugInfo>,
+    pub contract_class_version: String,
+    pub entry_points_by_type: ContractEntryPoints,
+    pub abi: Option<Contract>,
+}
+
+impl From<&ContractClass> for CompactContractClass {
+    fn from(contract_class: &ContractClass) -> Self {
+        let mut counts = OrderedHashMap::<&BigUint, usize>::default();
+        for felt252 in &contract_class.sierra_program {
+            *counts.entry(&felt252.value).or_default() += 1;
+        }
+        let repeated: OrderedHashMap<&BigUint, i64> = counts
+            .into_iter()
+            .filter(|(value, count)| *count > 1 && as_number(value).is_none())
+            .enumerate()
+            .map(|(idx, (value, _))| (value, -(idx as i64) - 1))
+            .collect();
+        let sierra_program = contract_class
+            .sierra_program
+            .iter()
+            .map(|felt252| {
+                if let Some(reference) = repeated.get(&felt252.value) {
+                    CompactFelt252::Reference(*reference)
+                } else if let Some(number) = as_number(&felt252.value) {
+                    CompactFelt252::Number(number)
+                } else {
+                    CompactFelt252::Base64(encode_base64(&felt252.value.to_bytes_be()))
+                }
+            })
+            .collect();
+        Self {
+            repeated_felt252s: repeated
+                .keys()
+                .map(|value| encode_base64(&value.to_bytes_be()))
+                .collect(),
+            sierra_program,
+            sierra_program_debug_info: contract_class.sierra_program_debug_info.clone(),
+            contract_class_version: contract_class.contract_class_version.clone(),
+            entry_points_by_type: contract_class.entry_points_by_type.clone(),
+            abi: contract_class.abi.clone(),
+        }
+    }
+}
+
+impl TryFrom<CompactContractClass> for ContractClass {
+    type Error = CompactContractClassError;
+
+    fn try_from(compact: CompactContractClass) -> Result<Self, Self::Error> {
+        let prime = Felt252::prime();
+        let decode = |encoded: String| {
+            let value = decode_base64(&encoded)
+                .map(|bytes| BigUint::from_bytes_be(&bytes))
+                .ok_or(CompactContractClassError::InvalidFelt252(encoded))?;
+            if value >= prime {
+                return Err(CompactContractClassError::ValueOutOfRange(value));
+            }
+            Ok(value)
+        };
+        let repeated =
+            compact.repeated_felt252s.into_iter().map(decode).collect::<Result<Vec<_>, _>>()?;
+        let sierra_program = compact
+            .sierra_program
+            .into_iter()
+            .map(|felt252| {
+                let value = match felt252 {
+                    CompactFelt252::Number(number) => BigUint::from(number),
+                    CompactFelt252::Reference(reference) => usize::try_from(-1 - reference)
+                        .ok()
+                        .and_then(|idx| repeated.get(idx))
+                        .ok_or(CompactContractClassError::InvalidReference(reference))?
+                        .clone(),
+                    CompactFelt252::Base64(encoded) => decode(encoded)?,
+                };
+                Ok(BigUintAsHex { value })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            sierra_program,
+            sierra_program_debug_info: compact.sierra_program_debug_info,
+            contract_class_version: compact.contract_class_version,
+            entry_points_by_type: compact.entry_points_by_type,
+            abi: compact.abi,
+        })
+    }
+}
+
+/// Returns the value as a number if it is small enough to be encoded as a JSON number.
+fn as_number(value: &BigUint) -> Option<u64> {
+    value.to_u64().filter(|number| *number <= MAX_NUMBER_FELT252)
+}
+
+/// Encodes the bytes as unpadded base64.
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().fold(0_u32, |bits, byte| bits << 8 | u32::from(*byte))
+            << (8 * (3 - chunk.len()));
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64_ALPHABET[(bits >> (18 - 6 * i)) as usize & 0x3f] as char);
+        }
+    }
+    encoded
+}
+
+/// Decodes unpadded base64 into bytes, or returns None if it is not valid unpadded base64.
+fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut bits = 0_u32;
+        for c in chunk {
+            bits = bits << 6 | BASE64_ALPHABET.iter().position(|a| a == c)? as u32;
+        }
+        bits <<= 6 * (4 - chunk.len());
+        for i in 0..chunk.len() - 1 {
+            bytes.push((bits >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,107 @@
+use std::io::BufReader;
+
+use cairo_felt::Felt252;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use num_bigint::BigUint;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use crate::compact_contract_class::{
+    CompactContractClass, CompactContractClassError, CompactFelt252,
+};
+use crate::contract_class::{ContractClass, ContractEntryPoints};
+use crate::test_utils::get_example_file_path;
+
+#[test]
+fn test_compact_felt252s() {
+    let large = BigUint::from(1_u8) << 200_u32;
+    let contract = ContractClass {
+        sierra_program: [
+            BigUint::from(5_u8),
+            large.clone(),
+            BigUint::from(5_u8),
+            BigUint::from(1_u64 << 53),
+            large,
+        ]
+        .into_iter()
+        .map(|value| BigUintAsHex { value })
+        .collect(),
+        sierra_program_debug_info: None,
+        contract_class_version: "0.1.0".into(),
+        entry_points_by_type: ContractEntryPoints::default(),
+        abi: None,
+    };
+    let compact = CompactContractClass::from(&contract);
+    // Only repeated large values are in the table, and small values are numbers.
+    assert_eq!(compact.repeated_felt252s, vec!["AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string()]);
+    assert_eq!(
+        compact.sierra_program,
+        vec![
+            CompactFelt252::Number(5),
+            CompactFelt252::Reference(-1),
+            CompactFelt252::Number(5),
+            CompactFelt252::Base64("IAAAAAAAAA".into()),
+            CompactFelt252::Reference(-1),
+        ]
+    );
+    assert_eq!(
+        serde_json::to_string(&compact.sierra_program).unwrap(),
+        r#"[5,-1,5,"IAAAAAAAAA",-1]"#
+    );
+    assert_eq!(ContractClass::try_from(compact), Ok(contract));
+}
+
+#[test]
+fn test_invalid_compact_contract_class() {
+    let compact = CompactContractClass {
+        repeated_felt252s: vec![],
+        sierra_program: vec![CompactFelt252::Base64("A".into())],
+        sierra_program_debug_info: None,
+        contract_class_version: "0.1.0".into(),
+        entry_points_by_type: ContractEntryPoints::default(),
+        abi: None,
+    };
+    assert_eq!(
+        ContractClass::try_from(compact.clone()),
+        Err(CompactContractClassError::InvalidFelt252("A".into()))
+    );
+    let compact = CompactContractClass {
+        repeated_felt252s: vec!["AQ".into()],
+        sierra_program: vec![CompactFelt252::Reference(-2)],
+        ..compact
+    };
+    assert_eq!(
+        ContractClass::try_from(compact.clone()),
+        Err(CompactContractClassError::InvalidReference(-2))
+    );
+    // The prime itself is not a valid felt252.
+    let prime = Felt252::prime();
+    let compact = CompactContractClass {
+        repeated_felt252s: vec![],
+        sierra_program: vec![CompactFelt252::Base64(
+            "CAAAAAAAABEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAE".into(),
+        )],
+        ..compact
+    };
+    assert_eq!(
+        ContractClass::try_from(compact),
+        Err(CompactContractClassError::ValueOutOfRange(prime))
+    );
+}
+
+// Tests the conversion of a contract to the compact form and back.
+#[test_case("test_contract__test_contract")]
+#[test_case("hello_starknet__hello_starknet")]
+#[test_case("erc20__erc_20")]
+#[test_case("multi_component__contract_with_4_components")]
+fn test_compact_contract_class_round_trip(name: &str) {
+    let contract_path = get_example_file_path(&format!("{name}.contract_class.json"));
+    let contract: ContractClass =
+        serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap()))
+            .unwrap();
+    let compact = CompactContractClass::from(&contract);
+    let serialized = serde_json::to_string(&compact).unwrap();
+    assert!(serialized.len() < serde_json::to_string(&contract).unwrap().len());
+    let deserialized: CompactContractClass = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(ContractClass::try_from(deserialized), Ok(contract));
+}
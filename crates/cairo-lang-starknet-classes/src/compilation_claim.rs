@@ -0,0 +1,155 @@
+//! Compilation claims - artifacts binding the sources of a contract to the compiler and the
+//! configuration used to compile them, and to the resulting classes, so that source-verification
+//! services can check the compilation offline.
+
+use std::fmt;
+
+use cairo_lang_utils::bigint::BigUintAsHex;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use starknet_crypto::{poseidon_hash_many, FieldElement};
+
+use crate::casm_contract_class::{CasmContractClass, StarknetSierraCompilationError};
+use crate::compiler_version::current_compiler_version_id;
+use crate::contract_class::ContractClass;
+use crate::keccak::starknet_keccak;
+
+#[cfg(test)]
+#[path = "compilation_claim_test.rs"]
+mod test;
+
+/// The configuration of the compilation affecting the resulting classes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimConfig {
+    /// Whether the panic data is encoded with the revert-with-reason ABI.
+    pub revert_panic_abi: bool,
+    /// Whether the program is optimized as a whole.
+    pub whole_program: bool,
+    /// Whether the pythonic hints are added to the compiled class.
+    pub add_pythonic_hints: bool,
+    /// The maximal size of the bytecode of the compiled class.
+    pub max_bytecode_size: usize,
+}
+
+/// A claim that a contract class and its compiled class were compiled from sources with a given
+/// digest, by a given version of the compiler with a given configuration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompilationClaim {
+    pub compiler_version: String,
+    pub config: ClaimConfig,
+    /// The digest of the sources, see [source_digest].
+    pub source_digest: BigUintAsHex,
+    /// The hash of the Sierra program of the contract class, see [sierra_program_hash].
+    pub sierra_program_hash: BigUintAsHex,
+    /// The hash of the compiled class, see [CasmContractClass::compiled_class_hash].
+    pub compiled_class_hash: BigUintAsHex,
+}
+impl CompilationClaim {
+    /// Creates the claim of compiling `contract_class` with the current compiler, from sources with
+    /// the digest `source_digest`. The contract class is compiled into its compiled class for
+    /// computing the compiled class hash.
+    #[allow(clippy::result_large_err)]
+    pub fn new(
+        source_digest: BigUint,
+        config: ClaimConfig,
+        contract_class: &ContractClass,
+    ) -> Result<Self, StarknetSierraCompilationError> {
+        let casm_contract_class = CasmContractClass::from_contract_class(
+            contract_class.clone(),
+            config.add_pythonic_hints,
+            config.max_bytecode_size,
+        )?;
+        Ok(Self {
+            compiler_version: current_compiler_version_id().to_string(),
+            config,
+            source_digest: BigUintAsHex { value: source_digest },
+            sierra_program_hash: BigUintAsHex { value: sierra_program_hash(contract_class) },
+            compiled_class_hash: BigUintAsHex {
+                value: casm_contract_class.compiled_class_hash().to_biguint(),
+            },
+        })
+    }
+
+    /// Returns the claimed fields that differ from the ones of `actual`, e.g. the claim of
+    /// recompiling the sources.
+    pub fn mismatches(&self, actual: &CompilationClaim) -> Vec<ClaimMismatch> {
+        let hex = |value: &BigUintAsHex| format!("{:#x}", value.value);
+        let json = |config: &ClaimConfig| serde_json::to_string(config).unwrap();
+        [
+            ("compiler_version", self.compiler_version.clone(), actual.compiler_version.clone()),
+            ("config", json(&self.config), json(&actual.config)),
+            ("source_digest", hex(&self.source_digest), hex(&actual.source_digest)),
+            (
+                "sierra_program_hash",
+                hex(&self.sierra_program_hash),
+                hex(&actual.sierra_program_hash),
+            ),
+            (
+                "compiled_class_hash",
+                hex(&self.compiled_class_hash),
+                hex(&actual.compiled_class_hash),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, claimed, actual)| claimed != actual)
+        .map(|(field, claimed, actual)| ClaimMismatch { field, claimed, actual })
+        .collect()
+    }
+
+    /// Verifies the claim offline against a contract class, without its sources - checks that the
+    /// claim is of the current compiler, and that the contract class and the compiled class
+    /// compiled from it with the claimed configuration match the claimed hashes.
+    ///
+    /// Returns the mismatching fields.
+    #[allow(clippy::result_large_err)]
+    pub fn verify_contract_class(
+        &self,
+        contract_class: &ContractClass,
+    ) -> Result<Vec<ClaimMismatch>, StarknetSierraCompilationError> {
+        let actual = CompilationClaim::new(
+            self.source_digest.value.clone(),
+            self.config.clone(),
+            contract_class,
+        )?;
+        Ok(self.mismatches(&actual))
+    }
+}
+
+/// A field of a compilation claim that differs from the actual compilation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimMismatch {
+    pub field: &'static str,
+    pub claimed: String,
+    pub actual: String,
+}
+impl fmt::Display for ClaimMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Claimed {} `{}`, got `{}`.", self.field, self.claimed, self.actual)
+    }
+}
+
+/// Returns the digest of source files, given as pairs of their paths and contents.
+/// The digest does not depend on the order of the files, so the paths should be relative to a
+/// stable root, e.g. the root of the crate of the file.
+pub fn source_digest<'a>(files: impl IntoIterator<Item = (&'a str, &'a str)>) -> BigUint {
+    let mut data = vec![];
+    for (path, content) in files.into_iter().sorted() {
+        // The lengths are added to make the encoding of the files unambiguous.
+        for part in [path, content] {
+            data.extend((part.len() as u64).to_be_bytes());
+            data.extend(part.as_bytes());
+        }
+    }
+    starknet_keccak(&data)
+}
+
+/// Returns the Poseidon hash of the Sierra program of a contract class, as encoded in the class.
+pub fn sierra_program_hash(contract_class: &ContractClass) -> BigUint {
+    let felts = contract_class
+        .sierra_program
+        .iter()
+        .map(|felt| FieldElement::from_byte_slice_be(&felt.value.to_bytes_be()).unwrap())
+        .collect_vec();
+    BigUint::from_bytes_be(&poseidon_hash_many(&felts).to_bytes_be())
+}
@@ -0,0 +1,83 @@
+use std::io::BufReader;
+
+use num_bigint::BigUint;
+use pretty_assertions::assert_eq;
+
+use crate::compilation_claim::{source_digest, ClaimConfig, ClaimMismatch, CompilationClaim};
+use crate::contract_class::ContractClass;
+use crate::test_utils::get_example_file_path;
+
+fn example_contract_class(name: &str) -> ContractClass {
+    serde_json::from_reader(BufReader::new(
+        std::fs::File::open(get_example_file_path(&format!("{name}.contract_class.json"))).unwrap(),
+    ))
+    .unwrap()
+}
+
+fn example_config() -> ClaimConfig {
+    ClaimConfig {
+        revert_panic_abi: false,
+        whole_program: false,
+        add_pythonic_hints: false,
+        max_bytecode_size: usize::MAX,
+    }
+}
+
+#[test]
+fn test_source_digest() {
+    let digest = source_digest([("lib.cairo", "mod a;"), ("a.cairo", "fn foo() {}")]);
+    // The digest does not depend on the order of the files.
+    assert_eq!(source_digest([("a.cairo", "fn foo() {}"), ("lib.cairo", "mod a;")]), digest);
+    // Moving content between the path and the content of a file changes the digest.
+    assert_ne!(source_digest([("lib.cairo", "mod a;"), ("a.cairo ", "fn foo() {}")]), digest);
+    assert_ne!(source_digest([("lib.cairo", "mod a;"), ("a.cairo", "fn foo() { }")]), digest);
+}
+
+#[test]
+fn test_verify_contract_class() {
+    let contract_class = example_contract_class("minimal_contract__minimal_contract");
+    let claim =
+        CompilationClaim::new(BigUint::from(7u32), example_config(), &contract_class).unwrap();
+    assert_eq!(claim.verify_contract_class(&contract_class), Ok(vec![]));
+
+    // A claim survives serialization.
+    let serialized = serde_json::to_string(&claim).unwrap();
+    assert_eq!(serde_json::from_str::<CompilationClaim>(&serialized).unwrap(), claim);
+
+    let other_class = example_contract_class("hello_starknet__hello_starknet");
+    let mismatches = claim.verify_contract_class(&other_class).unwrap();
+    assert_eq!(
+        mismatches.iter().map(|mismatch| mismatch.field).collect::<Vec<_>>(),
+        vec!["sierra_program_hash", "compiled_class_hash"]
+    );
+}
+
+#[test]
+fn test_mismatches() {
+    let contract_class = example_contract_class("minimal_contract__minimal_contract");
+    let claim =
+        CompilationClaim::new(BigUint::from(7u32), example_config(), &contract_class).unwrap();
+    let mut actual = claim.clone();
+    actual.compiler_version = "0.0.1".into();
+    actual.config.revert_panic_abi = true;
+    let mismatches = claim.mismatches(&actual);
+    assert_eq!(
+        mismatches,
+        vec![
+            ClaimMismatch {
+                field: "compiler_version",
+                claimed: claim.compiler_version.clone(),
+                actual: "0.0.1".into()
+            },
+            ClaimMismatch {
+                field: "config",
+                claimed: r#"{"revert_panic_abi":false,"whole_program":false,"add_pythonic_hints":false,"max_bytecode_size":18446744073709551615}"#.into(),
+                actual: r#"{"revert_panic_abi":true,"whole_program":false,"add_pythonic_hints":false,"max_bytecode_size":18446744073709551615}"#.into(),
+            },
+        ]
+    );
+    assert_eq!(
+        mismatches[0].to_string(),
+        format!("Claimed compiler_version `{}`, got `0.0.1`.", claim.compiler_version)
+    );
+}
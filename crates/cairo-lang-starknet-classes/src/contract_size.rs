@@ -0,0 +1,130 @@
+//! Size accounting of contract classes, for checking them against the size limits of a network.
+
+use crate::casm_contract_class::CasmContractClass;
+use crate::contract_class::ContractClass;
+use crate::NestedIntList;
+
+#[cfg(test)]
+#[path = "contract_size_test.rs"]
+mod test;
+
+/// Limits on the size of declared contract classes.
+/// The limits are set by each network, and are not known to the compiler, so they are provided by
+/// the user.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractSizeLimits {
+    /// The maximal number of felt252s in the compiled bytecode.
+    pub max_bytecode_size: usize,
+    /// The maximal size in bytes of the declared contract class object.
+    pub max_contract_class_object_size: usize,
+}
+
+/// A size of a contract class, compared to its limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SizeUsage {
+    pub size: usize,
+    pub limit: usize,
+}
+impl SizeUsage {
+    /// Returns whether the size fits the limit.
+    pub fn fits(&self) -> bool {
+        self.size <= self.limit
+    }
+
+    /// Returns the distance from the limit, negative if the limit is exceeded.
+    pub fn remaining(&self) -> i64 {
+        self.limit as i64 - self.size as i64
+    }
+}
+
+/// The sizes of a contract class that are limited when declaring it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractSizeInfo {
+    /// The number of felt252s in the Sierra program.
+    pub sierra_program_length: usize,
+    /// The number of felt252s in the compiled bytecode.
+    pub bytecode_length: usize,
+    /// The number of segments the bytecode is split into.
+    pub bytecode_segment_count: usize,
+    /// The length in bytes of the serialized ABI.
+    pub abi_length: usize,
+    /// The size in bytes of the declared class object, i.e. the serialized contract class
+    /// without its debug info and ABI.
+    pub class_object_size: usize,
+}
+impl ContractSizeInfo {
+    pub fn new(contract_class: &ContractClass, casm_contract_class: &CasmContractClass) -> Self {
+        let abi_length = contract_class
+            .abi
+            .as_ref()
+            .map(|abi| serde_json::to_string(abi).unwrap().len())
+            .unwrap_or_default();
+        let declared_class =
+            ContractClass { sierra_program_debug_info: None, abi: None, ..contract_class.clone() };
+        Self {
+            sierra_program_length: contract_class.sierra_program.len(),
+            bytecode_length: casm_contract_class.bytecode.len(),
+            bytecode_segment_count: segment_count(
+                &casm_contract_class.get_bytecode_segment_lengths(),
+            ),
+            abi_length,
+            class_object_size: serde_json::to_string(&declared_class).unwrap().len(),
+        }
+    }
+
+    /// Returns the bytecode size compared to its limit.
+    pub fn bytecode_usage(&self, limits: &ContractSizeLimits) -> SizeUsage {
+        SizeUsage { size: self.bytecode_length, limit: limits.max_bytecode_size }
+    }
+
+    /// Returns the declared class object size, including the ABI, compared to its limit.
+    pub fn class_object_usage(&self, limits: &ContractSizeLimits) -> SizeUsage {
+        SizeUsage {
+            size: self.class_object_size + self.abi_length,
+            limit: limits.max_contract_class_object_size,
+        }
+    }
+
+    /// Returns warnings for every limit the contract class exceeds.
+    pub fn limit_warnings(&self, limits: &ContractSizeLimits) -> Vec<String> {
+        let mut warnings = vec![];
+        let bytecode_usage = self.bytecode_usage(limits);
+        if !bytecode_usage.fits() {
+            warnings.push(format!(
+                "The bytecode size ({} felts) exceeds the limit ({} felts).",
+                bytecode_usage.size, bytecode_usage.limit
+            ));
+        }
+        let class_object_usage = self.class_object_usage(limits);
+        if !class_object_usage.fits() {
+            warnings.push(format!(
+                "The contract class size ({} bytes) exceeds the limit ({} bytes).",
+                class_object_usage.size, class_object_usage.limit
+            ));
+        }
+        warnings
+    }
+}
+
+/// The size report of a contract class, e.g. for comparing the costs of contract options such as
+/// `strict_calldata`.
+impl std::fmt::Display for ContractSizeInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Sierra program length: {} felts", self.sierra_program_length)?;
+        writeln!(
+            f,
+            "Bytecode length: {} felts, in {} segments",
+            self.bytecode_length, self.bytecode_segment_count
+        )?;
+        writeln!(f, "ABI length: {} bytes", self.abi_length)?;
+        writeln!(f, "Contract class object size: {} bytes", self.class_object_size)
+    }
+}
+
+/// Returns the number of segments in the given bytecode segment lengths.
+fn segment_count(segment_lengths: &NestedIntList) -> usize {
+    match segment_lengths {
+        NestedIntList::Leaf(_) => 1,
+        NestedIntList::Node(children) => children.iter().map(segment_count).sum(),
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::BufReader;
+
+use test_case::test_case;
+
+use crate::casm_contract_class::CasmContractClass;
+use crate::contract_class::ContractClass;
+use crate::contract_size::{ContractSizeInfo, ContractSizeLimits, SizeUsage};
+use crate::test_utils::get_example_file_path;
+
+#[test_case("hello_starknet__hello_starknet")]
+#[test_case("erc20__erc_20")]
+fn test_contract_size_info(name: &str) {
+    let contract_path = get_example_file_path(&format!("{name}.contract_class.json"));
+    let contract_class: ContractClass =
+        serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap()))
+            .unwrap();
+    let casm_contract_class =
+        CasmContractClass::from_contract_class(contract_class.clone(), false, usize::MAX).unwrap();
+    let size_info = ContractSizeInfo::new(&contract_class, &casm_contract_class);
+
+    assert_eq!(size_info.sierra_program_length, contract_class.sierra_program.len());
+    assert_eq!(size_info.bytecode_length, casm_contract_class.bytecode.len());
+    assert!(size_info.abi_length > 0);
+    let exact_limits = ContractSizeLimits {
+        max_bytecode_size: size_info.bytecode_length,
+        max_contract_class_object_size: size_info.class_object_size + size_info.abi_length,
+    };
+    assert!(size_info.limit_warnings(&exact_limits).is_empty());
+
+    let tight_limits = ContractSizeLimits {
+        max_bytecode_size: size_info.bytecode_length - 1,
+        max_contract_class_object_size: usize::MAX,
+    };
+    assert_eq!(size_info.bytecode_usage(&tight_limits).remaining(), -1);
+    assert_eq!(size_info.limit_warnings(&tight_limits).len(), 1);
+}
+
+#[test]
+fn test_size_usage() {
+    assert!(SizeUsage { size: 10, limit: 10 }.fits());
+    assert_eq!(SizeUsage { size: 7, limit: 10 }.remaining(), 3);
+    assert!(!SizeUsage { size: 11, limit: 10 }.fits());
+}
+
+#[test]
+fn test_size_report() {
+    let size_info = ContractSizeInfo {
+        sierra_program_length: 100,
+        bytecode_length: 200,
+        bytecode_segment_count: 3,
+        abi_length: 40,
+        class_object_size: 5000,
+    };
+    assert_eq!(
+        size_info.to_string(),
+        "Sierra program length: 100 felts\nBytecode length: 200 felts, in 3 segments\nABI length: \
+         40 bytes\nContract class object size: 5000 bytes\n"
+    );
+}
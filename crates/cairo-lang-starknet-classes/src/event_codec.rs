@@ -0,0 +1,381 @@
+//! Decoding and encoding of Starknet events according to a contract ABI.
+
+use cairo_felt::Felt252;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use num_traits::ToPrimitive;
+use thiserror::Error;
+
+use crate::abi::{Contract, Enum, Event, EventField, EventFieldKind, EventKind, Item, Struct};
+use crate::keccak::starknet_keccak;
+
+#[cfg(test)]
+#[path = "event_codec_test.rs"]
+mod test;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum EventCodingError {
+    #[error("Event `{0}` not found in the ABI.")]
+    EventNotFound(String),
+    #[error("Missing felts for decoding a value of type `{0}`.")]
+    MissingFelts(String),
+    #[error("Unexpected felts left after decoding the event.")]
+    TrailingFelts,
+    #[error("Invalid variant index for enum `{0}`.")]
+    InvalidVariantIndex(String),
+    #[error("Invalid length for a value of type `{0}`.")]
+    InvalidLength(String),
+    #[error("No variant of event `{0}` matches the keys.")]
+    NoMatchingVariant(String),
+    #[error("Value does not match type `{0}`.")]
+    ValueMismatch(String),
+}
+
+/// A value of a type described by an ABI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AbiValue {
+    /// A value serialized into a single felt252, e.g. a `felt252`, a `u32` or a
+    /// `ContractAddress`.
+    Felt252(Felt252),
+    /// A struct or a struct event, with its members in order.
+    Struct(Vec<(String, AbiValue)>),
+    /// A variant of an enum or of an enum event.
+    Enum(String, Box<AbiValue>),
+    /// An `Array`, or a fixed size array.
+    Array(Vec<AbiValue>),
+    /// A tuple.
+    Tuple(Vec<AbiValue>),
+}
+
+/// Decodes and encodes event keys and data according to the event, struct and enum items of an
+/// ABI.
+pub struct EventCodec<'a> {
+    events: UnorderedHashMap<&'a str, &'a Event>,
+    structs: UnorderedHashMap<&'a str, &'a Struct>,
+    enums: UnorderedHashMap<&'a str, &'a Enum>,
+}
+
+impl<'a> EventCodec<'a> {
+    pub fn new(abi: &'a Contract) -> Self {
+        let mut events = UnorderedHashMap::default();
+        let mut structs = UnorderedHashMap::default();
+        let mut enums = UnorderedHashMap::default();
+        for item in abi.items() {
+            match item {
+                Item::Event(event) => {
+                    events.insert(event.name.as_str(), event);
+                }
+                Item::Struct(item) => {
+                    structs.insert(item.name.as_str(), item);
+                }
+                Item::Enum(item) => {
+                    enums.insert(item.name.as_str(), item);
+                }
+                _ => {}
+            }
+        }
+        Self { events, structs, enums }
+    }
+
+    /// Decodes the keys and data of an event of the given type, usually the `Event` enum of the
+    /// contract.
+    pub fn decode_event(
+        &self,
+        event_name: &str,
+        keys: &[Felt252],
+        data: &[Felt252],
+    ) -> Result<AbiValue, EventCodingError> {
+        let (mut keys, mut data) = (keys, data);
+        let value = self.decode_event_inner(event_name, &mut keys, &mut data)?;
+        if !keys.is_empty() || !data.is_empty() {
+            return Err(EventCodingError::TrailingFelts);
+        }
+        Ok(value)
+    }
+
+    /// Encodes an event of the given type into its keys and data.
+    pub fn encode_event(
+        &self,
+        event_name: &str,
+        value: &AbiValue,
+    ) -> Result<(Vec<Felt252>, Vec<Felt252>), EventCodingError> {
+        let (mut keys, mut data) = (vec![], vec![]);
+        self.encode_event_inner(event_name, value, &mut keys, &mut data)?;
+        Ok((keys, data))
+    }
+
+    /// Decodes a value of the given type, as serialized by `Serde`.
+    pub fn decode_value(
+        &self,
+        ty: &str,
+        felts: &mut &[Felt252],
+    ) -> Result<AbiValue, EventCodingError> {
+        let ty = ty.strip_prefix('@').unwrap_or(ty);
+        if let Some(element_types) = tuple_element_types(ty) {
+            return Ok(AbiValue::Tuple(
+                element_types
+                    .into_iter()
+                    .map(|ty| self.decode_value(ty, felts))
+                    .collect::<Result<_, _>>()?,
+            ));
+        }
+        let (element_ty, len) = if let Some((element_ty, len)) = fixed_size_array_info(ty) {
+            (element_ty, len)
+        } else if let Some(element_ty) = array_element_type(ty) {
+            let len = pop_felt(ty, felts)?
+                .to_usize()
+                .ok_or_else(|| EventCodingError::InvalidLength(ty.into()))?;
+            (element_ty, len)
+        } else if let Some(item) = self.structs.get(ty) {
+            return Ok(AbiValue::Struct(
+                item.members
+                    .iter()
+                    .map(|member| Ok((member.name.clone(), self.decode_value(&member.ty, felts)?)))
+                    .collect::<Result<_, _>>()?,
+            ));
+        } else if let Some(item) = self.enums.get(ty) {
+            let variant = pop_felt(ty, felts)?
+                .to_usize()
+                .and_then(|index| item.variants.get(index))
+                .ok_or_else(|| EventCodingError::InvalidVariantIndex(ty.into()))?;
+            let value = self.decode_value(&variant.ty, felts)?;
+            return Ok(AbiValue::Enum(variant.name.clone(), Box::new(value)));
+        } else {
+            return Ok(AbiValue::Felt252(pop_felt(ty, felts)?));
+        };
+        // Avoid preallocating according to an untrusted length.
+        let mut elements = vec![];
+        for _ in 0..len {
+            elements.push(self.decode_value(element_ty, felts)?);
+        }
+        Ok(AbiValue::Array(elements))
+    }
+
+    /// Encodes a value of the given type, as serialized by `Serde`.
+    pub fn encode_value(
+        &self,
+        ty: &str,
+        value: &AbiValue,
+        felts: &mut Vec<Felt252>,
+    ) -> Result<(), EventCodingError> {
+        let ty = ty.strip_prefix('@').unwrap_or(ty);
+        let mismatch = || EventCodingError::ValueMismatch(ty.into());
+        if let Some(element_types) = tuple_element_types(ty) {
+            let AbiValue::Tuple(elements) = value else {
+                return Err(mismatch());
+            };
+            if elements.len() != element_types.len() {
+                return Err(mismatch());
+            }
+            for (ty, element) in element_types.into_iter().zip(elements) {
+                self.encode_value(ty, element, felts)?;
+            }
+        } else if let Some((element_ty, len)) = fixed_size_array_info(ty) {
+            let AbiValue::Array(elements) = value else {
+                return Err(mismatch());
+            };
+            if elements.len() != len {
+                return Err(mismatch());
+            }
+            for element in elements {
+                self.encode_value(element_ty, element, felts)?;
+            }
+        } else if let Some(element_ty) = array_element_type(ty) {
+            let AbiValue::Array(elements) = value else {
+                return Err(mismatch());
+            };
+            felts.push(Felt252::from(elements.len()));
+            for element in elements {
+                self.encode_value(element_ty, element, felts)?;
+            }
+        } else if let Some(item) = self.structs.get(ty) {
+            let AbiValue::Struct(members) = value else {
+                return Err(mismatch());
+            };
+            if members.len() != item.members.len() {
+                return Err(mismatch());
+            }
+            for (member, (name, value)) in item.members.iter().zip(members) {
+                if &member.name != name {
+                    return Err(mismatch());
+                }
+                self.encode_value(&member.ty, value, felts)?;
+            }
+        } else if let Some(item) = self.enums.get(ty) {
+            let AbiValue::Enum(name, value) = value else {
+                return Err(mismatch());
+            };
+            let (index, variant) = item
+                .variants
+                .iter()
+                .enumerate()
+                .find(|(_, variant)| &variant.name == name)
+                .ok_or_else(mismatch)?;
+            felts.push(Felt252::from(index));
+            self.encode_value(&variant.ty, value, felts)?;
+        } else {
+            let AbiValue::Felt252(felt) = value else {
+                return Err(mismatch());
+            };
+            felts.push(felt.clone());
+        }
+        Ok(())
+    }
+
+    fn decode_event_inner(
+        &self,
+        event_name: &str,
+        keys: &mut &[Felt252],
+        data: &mut &[Felt252],
+    ) -> Result<AbiValue, EventCodingError> {
+        let event = self
+            .events
+            .get(event_name)
+            .ok_or_else(|| EventCodingError::EventNotFound(event_name.into()))?;
+        match &event.kind {
+            EventKind::Struct { members } => Ok(AbiValue::Struct(
+                members
+                    .iter()
+                    .map(|member| Ok((member.name.clone(), self.decode_field(member, keys, data)?)))
+                    .collect::<Result<_, _>>()?,
+            )),
+            EventKind::Enum { variants } => {
+                // Flat variants have no selector key, so they are tried first, in order.
+                for variant in
+                    variants.iter().filter(|variant| variant.kind == EventFieldKind::Flat)
+                {
+                    let (mut variant_keys, mut variant_data) = (*keys, *data);
+                    if let Ok(value) =
+                        self.decode_field(variant, &mut variant_keys, &mut variant_data)
+                    {
+                        (*keys, *data) = (variant_keys, variant_data);
+                        return Ok(AbiValue::Enum(variant.name.clone(), Box::new(value)));
+                    }
+                }
+                let selector = pop_felt(event_name, keys)?;
+                let variant = variants
+                    .iter()
+                    .filter(|variant| variant.kind != EventFieldKind::Flat)
+                    .find(|variant| variant_selector(&variant.name) == selector)
+                    .ok_or_else(|| EventCodingError::NoMatchingVariant(event_name.into()))?;
+                let value = self.decode_field(variant, keys, data)?;
+                Ok(AbiValue::Enum(variant.name.clone(), Box::new(value)))
+            }
+        }
+    }
+
+    fn decode_field(
+        &self,
+        field: &EventField,
+        keys: &mut &[Felt252],
+        data: &mut &[Felt252],
+    ) -> Result<AbiValue, EventCodingError> {
+        match field.kind {
+            EventFieldKind::KeySerde => self.decode_value(&field.ty, keys),
+            EventFieldKind::DataSerde => self.decode_value(&field.ty, data),
+            EventFieldKind::Nested | EventFieldKind::Flat => {
+                self.decode_event_inner(&field.ty, keys, data)
+            }
+        }
+    }
+
+    fn encode_event_inner(
+        &self,
+        event_name: &str,
+        value: &AbiValue,
+        keys: &mut Vec<Felt252>,
+        data: &mut Vec<Felt252>,
+    ) -> Result<(), EventCodingError> {
+        let event = self
+            .events
+            .get(event_name)
+            .ok_or_else(|| EventCodingError::EventNotFound(event_name.into()))?;
+        let mismatch = || EventCodingError::ValueMismatch(event_name.into());
+        match (&event.kind, value) {
+            (EventKind::Struct { members }, AbiValue::Struct(values)) => {
+                if members.len() != values.len() {
+                    return Err(mismatch());
+                }
+                for (member, (name, value)) in members.iter().zip(values) {
+                    if &member.name != name {
+                        return Err(mismatch());
+                    }
+                    self.encode_field(member, value, keys, data)?;
+                }
+            }
+            (EventKind::Enum { variants }, AbiValue::Enum(name, value)) => {
+                let variant =
+                    variants.iter().find(|variant| &variant.name == name).ok_or_else(mismatch)?;
+                if variant.kind != EventFieldKind::Flat {
+                    keys.push(variant_selector(&variant.name));
+                }
+                self.encode_field(variant, value, keys, data)?;
+            }
+            _ => return Err(mismatch()),
+        }
+        Ok(())
+    }
+
+    fn encode_field(
+        &self,
+        field: &EventField,
+        value: &AbiValue,
+        keys: &mut Vec<Felt252>,
+        data: &mut Vec<Felt252>,
+    ) -> Result<(), EventCodingError> {
+        match field.kind {
+            EventFieldKind::KeySerde => self.encode_value(&field.ty, value, keys),
+            EventFieldKind::DataSerde => self.encode_value(&field.ty, value, data),
+            EventFieldKind::Nested | EventFieldKind::Flat => {
+                self.encode_event_inner(&field.ty, value, keys, data)
+            }
+        }
+    }
+}
+
+/// Returns the selector key of an event enum variant.
+fn variant_selector(variant_name: &str) -> Felt252 {
+    Felt252::from_bytes_be(&starknet_keccak(variant_name.as_bytes()).to_bytes_be())
+}
+
+/// Pops the first felt, for decoding a value of type `ty`.
+fn pop_felt(ty: &str, felts: &mut &[Felt252]) -> Result<Felt252, EventCodingError> {
+    let (first, rest) =
+        felts.split_first().ok_or_else(|| EventCodingError::MissingFelts(ty.into()))?;
+    *felts = rest;
+    Ok(first.clone())
+}
+
+/// Returns the element type of an `Array` type.
+fn array_element_type(ty: &str) -> Option<&str> {
+    ty.strip_prefix("core::array::Array::<")?.strip_suffix('>')
+}
+
+/// Returns the element type and size of a fixed size array type, e.g. `[core::felt252; 3]`.
+fn fixed_size_array_info(ty: &str) -> Option<(&str, usize)> {
+    let (element_ty, size) = ty.strip_prefix('[')?.strip_suffix(']')?.rsplit_once(';')?;
+    Some((element_ty.trim(), size.trim().parse().ok()?))
+}
+
+/// Returns the element types of a tuple type, e.g. `(core::felt252, core::integer::u32)`.
+fn tuple_element_types(ty: &str) -> Option<Vec<&str>> {
+    let inner = ty.strip_prefix('(')?.strip_suffix(')')?;
+    let mut element_types = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '<' | '[' => depth += 1,
+            ')' | '>' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                element_types.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() {
+        element_types.push(last);
+    }
+    Some(element_types)
+}
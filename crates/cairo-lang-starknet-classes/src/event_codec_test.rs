@@ -0,0 +1,140 @@
+use cairo_felt::Felt252;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use crate::abi::Contract;
+use crate::event_codec::{variant_selector, AbiValue, EventCodec, EventCodingError};
+
+/// An ABI of a contract with nested, flat, key and data event fields.
+const ABI: &str = indoc! {r#"
+    [
+      {
+        "type": "struct",
+        "name": "core::integer::u256",
+        "members": [
+          {"name": "low", "type": "core::integer::u128"},
+          {"name": "high", "type": "core::integer::u128"}
+        ]
+      },
+      {
+        "type": "enum",
+        "name": "core::bool",
+        "variants": [{"name": "False", "type": "()"}, {"name": "True", "type": "()"}]
+      },
+      {
+        "type": "event",
+        "name": "test::Transfer",
+        "kind": "struct",
+        "members": [
+          {"name": "from", "type": "core::felt252", "kind": "key"},
+          {"name": "value", "type": "core::integer::u256", "kind": "data"},
+          {"name": "memo", "type": "core::array::Array::<core::felt252>", "kind": "data"}
+        ]
+      },
+      {
+        "type": "event",
+        "name": "test::Flagged",
+        "kind": "struct",
+        "members": [
+          {"name": "flag", "type": "core::bool", "kind": "data"},
+          {"name": "pair", "type": "(core::felt252, [core::felt252; 2])", "kind": "data"}
+        ]
+      },
+      {
+        "type": "event",
+        "name": "test::Inner",
+        "kind": "enum",
+        "variants": [{"name": "Flagged", "type": "test::Flagged", "kind": "nested"}]
+      },
+      {
+        "type": "event",
+        "name": "test::Event",
+        "kind": "enum",
+        "variants": [
+          {"name": "Transfer", "type": "test::Transfer", "kind": "nested"},
+          {"name": "Inner", "type": "test::Inner", "kind": "flat"}
+        ]
+      }
+    ]
+"#};
+
+fn felts(values: &[u128]) -> Vec<Felt252> {
+    values.iter().map(|value| Felt252::from(*value)).collect()
+}
+
+fn felt_value(value: u128) -> AbiValue {
+    AbiValue::Felt252(Felt252::from(value))
+}
+
+#[test]
+fn test_nested_event() {
+    let abi: Contract = serde_json::from_str(ABI).unwrap();
+    let codec = EventCodec::new(&abi);
+    let value = AbiValue::Enum(
+        "Transfer".into(),
+        Box::new(AbiValue::Struct(vec![
+            ("from".into(), felt_value(7)),
+            (
+                "value".into(),
+                AbiValue::Struct(vec![
+                    ("low".into(), felt_value(5)),
+                    ("high".into(), felt_value(0)),
+                ]),
+            ),
+            ("memo".into(), AbiValue::Array(vec![felt_value(1), felt_value(2)])),
+        ])),
+    );
+    let (keys, data) = codec.encode_event("test::Event", &value).unwrap();
+    assert_eq!(keys, vec![variant_selector("Transfer"), Felt252::from(7)]);
+    assert_eq!(data, felts(&[5, 0, 2, 1, 2]));
+    assert_eq!(codec.decode_event("test::Event", &keys, &data), Ok(value));
+}
+
+#[test]
+fn test_flat_event() {
+    let abi: Contract = serde_json::from_str(ABI).unwrap();
+    let codec = EventCodec::new(&abi);
+    let value = AbiValue::Enum(
+        "Inner".into(),
+        Box::new(AbiValue::Enum(
+            "Flagged".into(),
+            Box::new(AbiValue::Struct(vec![
+                ("flag".into(), AbiValue::Enum("True".into(), Box::new(AbiValue::Tuple(vec![])))),
+                (
+                    "pair".into(),
+                    AbiValue::Tuple(vec![
+                        felt_value(3),
+                        AbiValue::Array(vec![felt_value(4), felt_value(5)]),
+                    ]),
+                ),
+            ])),
+        )),
+    );
+    let (keys, data) = codec.encode_event("test::Event", &value).unwrap();
+    assert_eq!(keys, vec![variant_selector("Flagged")]);
+    assert_eq!(data, felts(&[1, 3, 4, 5]));
+    assert_eq!(codec.decode_event("test::Event", &keys, &data), Ok(value));
+}
+
+#[test]
+fn test_decoding_errors() {
+    let abi: Contract = serde_json::from_str(ABI).unwrap();
+    let codec = EventCodec::new(&abi);
+    let transfer_key = variant_selector("Transfer");
+    assert_eq!(
+        codec.decode_event("test::Event", &[Felt252::from(1)], &[]),
+        Err(EventCodingError::NoMatchingVariant("test::Event".into()))
+    );
+    assert_eq!(
+        codec.decode_event("test::Event", &[transfer_key.clone(), Felt252::from(7)], &felts(&[5])),
+        Err(EventCodingError::MissingFelts("core::integer::u128".into()))
+    );
+    assert_eq!(
+        codec.decode_event("test::Event", &[transfer_key, Felt252::from(7)], &felts(&[5, 0, 0, 9])),
+        Err(EventCodingError::TrailingFelts)
+    );
+    assert_eq!(
+        codec.decode_event("test::Missing", &[], &[]),
+        Err(EventCodingError::EventNotFound("test::Missing".into()))
+    );
+}
@@ -0,0 +1,152 @@
+//! Gas estimation of contract classes - solving the gas costs of the entry points of a contract
+//! class without compiling it into its compiled class, for services only interested in the costs.
+//!
+//! The estimate may also be embedded into the contract class itself, as an annotation of its debug
+//! info, so that it can be read back without solving the costs again.
+
+use cairo_lang_sierra::debug_info::DebugInfo;
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_to_casm::metadata::{calc_metadata, Metadata, MetadataComputationConfig};
+use cairo_lang_utils::bigint::{deserialize_big_uint, serialize_big_uint};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::casm_contract_class::{
+    decode_sierra_program, StarknetSierraCompilationError, ENTRY_POINT_COST,
+};
+use crate::contract_class::{ContractClass, ContractEntryPoint};
+
+#[cfg(test)]
+#[path = "gas_estimation_test.rs"]
+mod test;
+
+/// The namespace of the gas estimate embedded into the annotations of the debug info of a contract
+/// class, see [ContractGasEstimate::embed].
+pub const GAS_ESTIMATE_ANNOTATION: &str = "github.com/starkware-libs/cairo/gas-estimate/v1";
+
+/// The gas cost of an entry point of a contract class.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPointGasCost {
+    #[serde(serialize_with = "serialize_big_uint", deserialize_with = "deserialize_big_uint")]
+    pub selector: BigUint,
+    /// The index of the function of the entry point in the Sierra program.
+    pub function_idx: usize,
+    /// The gas required for calling the entry point, up to its withdrawals of gas, by cost token
+    /// type.
+    pub costs: OrderedHashMap<CostTokenType, i64>,
+}
+impl EntryPointGasCost {
+    /// Returns whether the constant cost of the entry point fits the cost charged for calling an
+    /// entry point, as required for compiling the contract class.
+    pub fn fits_entry_point_cost(&self) -> bool {
+        self.costs.get(&CostTokenType::Const).copied().unwrap_or_default()
+            <= ENTRY_POINT_COST as i64
+    }
+}
+
+/// The gas costs of the entry points of a contract class, as solved for compiling it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractGasEstimate {
+    #[serde(rename = "EXTERNAL")]
+    pub external: Vec<EntryPointGasCost>,
+    #[serde(rename = "L1_HANDLER")]
+    pub l1_handler: Vec<EntryPointGasCost>,
+    #[serde(rename = "CONSTRUCTOR")]
+    pub constructor: Vec<EntryPointGasCost>,
+}
+impl ContractGasEstimate {
+    /// Solves the gas costs of the entry points of a contract class, stopping before the
+    /// generation of the code of the compiled class.
+    /// If `compute_runtime_costs` is true, the costs include the runtime cost token types (steps,
+    /// holes and range-checks) as well.
+    #[allow(clippy::result_large_err)]
+    pub fn new(
+        contract_class: &ContractClass,
+        compute_runtime_costs: bool,
+    ) -> Result<Self, StarknetSierraCompilationError> {
+        let (sierra_version, program) = decode_sierra_program(contract_class)?;
+        // The costs of the entry points are not enforced, so that they are solved.
+        let no_eq_solver = sierra_version.minor >= 4;
+        let metadata = calc_metadata(
+            &program,
+            MetadataComputationConfig {
+                function_set_costs: Default::default(),
+                linear_gas_solver: no_eq_solver,
+                linear_ap_change_solver: no_eq_solver,
+                skip_non_linear_solver_comparisons: false,
+                compute_runtime_costs,
+            },
+        )?;
+        let entry_points = &contract_class.entry_points_by_type;
+        Ok(Self {
+            external: entry_points_costs(&program, &metadata, &entry_points.external)?,
+            l1_handler: entry_points_costs(&program, &metadata, &entry_points.l1_handler)?,
+            constructor: entry_points_costs(&program, &metadata, &entry_points.constructor)?,
+        })
+    }
+
+    /// Returns all the entry points costs, of all types.
+    pub fn all(&self) -> impl Iterator<Item = &EntryPointGasCost> {
+        self.external.iter().chain(&self.l1_handler).chain(&self.constructor)
+    }
+
+    /// Embeds the estimate into the contract class, as the [GAS_ESTIMATE_ANNOTATION] annotation of
+    /// its debug info - which is not a part of the class hash, so the declared class is unchanged.
+    pub fn embed(&self, contract_class: &mut ContractClass) {
+        let debug_info =
+            contract_class.sierra_program_debug_info.get_or_insert_with(|| DebugInfo {
+                type_names: Default::default(),
+                libfunc_names: Default::default(),
+                user_func_names: Default::default(),
+                annotations: Default::default(),
+            });
+        debug_info.annotations.insert(
+            GAS_ESTIMATE_ANNOTATION.into(),
+            serde_json::to_value(self).expect("Gas estimate serialization should not fail."),
+        );
+    }
+
+    /// Returns the estimate embedded into the contract class by [Self::embed], or `None` if there
+    /// is none.
+    pub fn embedded(contract_class: &ContractClass) -> Result<Option<Self>, serde_json::Error> {
+        let Some(value) = contract_class
+            .sierra_program_debug_info
+            .as_ref()
+            .and_then(|debug_info| debug_info.annotations.get(GAS_ESTIMATE_ANNOTATION))
+        else {
+            return Ok(None);
+        };
+        Self::deserialize(value).map(Some)
+    }
+}
+
+/// Returns the solved costs of the given entry points.
+#[allow(clippy::result_large_err)]
+fn entry_points_costs(
+    program: &Program,
+    metadata: &Metadata,
+    entry_points: &[ContractEntryPoint],
+) -> Result<Vec<EntryPointGasCost>, StarknetSierraCompilationError> {
+    entry_points
+        .iter()
+        .map(|entry_point| {
+            let function = program
+                .funcs
+                .get(entry_point.function_idx)
+                .ok_or(StarknetSierraCompilationError::EntryPointError)?;
+            let costs = metadata
+                .gas_info
+                .function_costs
+                .get(&function.id)
+                .cloned()
+                .ok_or(StarknetSierraCompilationError::EntryPointError)?;
+            Ok(EntryPointGasCost {
+                selector: entry_point.selector.clone(),
+                function_idx: entry_point.function_idx,
+                costs,
+            })
+        })
+        .collect()
+}
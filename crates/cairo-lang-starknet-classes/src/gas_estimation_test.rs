@@ -0,0 +1,95 @@
+use std::io::BufReader;
+
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use test_case::test_case;
+
+use crate::casm_contract_class::ENTRY_POINT_COST;
+use crate::contract_class::ContractClass;
+use crate::gas_estimation::{ContractGasEstimate, EntryPointGasCost, GAS_ESTIMATE_ANNOTATION};
+use crate::test_utils::get_example_file_path;
+
+fn example_contract_class(name: &str) -> ContractClass {
+    let contract_path = get_example_file_path(&format!("{name}.contract_class.json"));
+    serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap())).unwrap()
+}
+
+#[test_case("hello_starknet__hello_starknet")]
+#[test_case("erc20__erc_20")]
+#[test_case("account__account")]
+fn test_gas_estimate(name: &str) {
+    let contract_class = example_contract_class(name);
+    let estimate = ContractGasEstimate::new(&contract_class, false).unwrap();
+    let entry_points = &contract_class.entry_points_by_type;
+    assert_eq!(estimate.external.len(), entry_points.external.len());
+    assert_eq!(estimate.l1_handler.len(), entry_points.l1_handler.len());
+    assert_eq!(estimate.constructor.len(), entry_points.constructor.len());
+    for (cost, entry_point) in estimate.all().zip(
+        entry_points
+            .external
+            .iter()
+            .chain(&entry_points.l1_handler)
+            .chain(&entry_points.constructor),
+    ) {
+        assert_eq!(cost.selector, entry_point.selector);
+        assert_eq!(cost.function_idx, entry_point.function_idx);
+        assert!(cost.fits_entry_point_cost());
+    }
+
+    let with_runtime_costs = ContractGasEstimate::new(&contract_class, true).unwrap();
+    for (cost, cost_with_runtime) in estimate.all().zip(with_runtime_costs.all()) {
+        assert_eq!(
+            cost.costs[&CostTokenType::Const],
+            cost_with_runtime.costs[&CostTokenType::Const]
+        );
+        assert!(cost_with_runtime.costs[&CostTokenType::Step] > 0);
+    }
+}
+
+#[test]
+fn test_gas_estimate_serialization() {
+    let estimate =
+        ContractGasEstimate::new(&example_contract_class("hello_starknet__hello_starknet"), false)
+            .unwrap();
+    let serialized = serde_json::to_string(&estimate).unwrap();
+    assert_eq!(serde_json::from_str::<ContractGasEstimate>(&serialized).unwrap(), estimate);
+}
+
+#[test]
+fn test_embedded_gas_estimate() {
+    let mut contract_class = example_contract_class("hello_starknet__hello_starknet");
+    assert_eq!(ContractGasEstimate::embedded(&contract_class).unwrap(), None);
+    let estimate = ContractGasEstimate::new(&contract_class, false).unwrap();
+    estimate.embed(&mut contract_class);
+    assert_eq!(ContractGasEstimate::embedded(&contract_class).unwrap(), Some(estimate.clone()));
+
+    // Embedding into a class without debug info, after a serialization round trip.
+    contract_class.sierra_program_debug_info = None;
+    estimate.embed(&mut contract_class);
+    let serialized = serde_json::to_string(&contract_class).unwrap();
+    let contract_class = serde_json::from_str::<ContractClass>(&serialized).unwrap();
+    assert_eq!(ContractGasEstimate::embedded(&contract_class).unwrap(), Some(estimate));
+}
+
+#[test]
+fn test_invalid_embedded_gas_estimate() {
+    let mut contract_class = example_contract_class("hello_starknet__hello_starknet");
+    contract_class
+        .sierra_program_debug_info
+        .as_mut()
+        .unwrap()
+        .annotations
+        .insert(GAS_ESTIMATE_ANNOTATION.into(), serde_json::json!({"EXTERNAL": 1}));
+    assert!(ContractGasEstimate::embedded(&contract_class).is_err());
+}
+
+#[test]
+fn test_fits_entry_point_cost() {
+    let cost = |value: i64| EntryPointGasCost {
+        selector: 0u32.into(),
+        function_idx: 0,
+        costs: OrderedHashMap::from_iter([(CostTokenType::Const, value)]),
+    };
+    assert!(cost(ENTRY_POINT_COST as i64).fits_entry_point_cost());
+    assert!(!cost(ENTRY_POINT_COST as i64 + 1).fits_entry_point_cost());
+}
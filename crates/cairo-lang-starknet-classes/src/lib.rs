@@ -8,14 +8,19 @@
 pub mod abi;
 pub mod allowed_libfuncs;
 pub mod casm_contract_class;
+pub mod compact_contract_class;
+pub mod compilation_claim;
 pub mod compiler_version;
 pub mod contract_class;
-mod contract_segmentation;
+pub mod contract_size;
+pub mod event_codec;
 mod felt252_serde;
 mod felt252_vec_compression;
+pub mod gas_estimation;
 pub mod keccak;
+pub mod recompilation;
 
-pub use contract_segmentation::NestedIntList;
+pub use cairo_lang_sierra_to_casm::segmentation::NestedIntList;
 
 #[cfg(test)]
 mod test_utils;
@@ -0,0 +1,310 @@
+//! Checking that recompiling a contract class with the current compiler reproduces its compiled
+//! class hash, as required before upgrading the compiler used by the network.
+
+use std::fmt;
+use std::ops::Range;
+
+use cairo_lang_sierra::program::Program;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use itertools::{EitherOrBoth, Itertools};
+use num_bigint::BigUint;
+
+use crate::casm_contract_class::{
+    CasmContractClass, CasmContractEntryPoint, StarknetSierraCompilationError,
+};
+use crate::contract_class::ContractClass;
+use crate::NestedIntList;
+
+#[cfg(test)]
+#[path = "recompilation_test.rs"]
+mod test;
+
+/// A recompilation of a contract class that did not reproduce its expected compiled class hash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecompilationMismatch {
+    /// The expected compiled class hash.
+    pub expected_hash: BigUint,
+    /// The compiled class hash of the recompiled class.
+    pub actual_hash: BigUint,
+    /// The differences from the expected compiled class, if it was provided.
+    pub diff: Option<CasmContractClassDiff>,
+}
+impl fmt::Display for RecompilationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Compiled class hash mismatch: expected {:#x}, got {:#x}.",
+            self.expected_hash, self.actual_hash
+        )?;
+        let Some(diff) = &self.diff else {
+            return Ok(());
+        };
+        for entry_point in &diff.entry_points {
+            let describe = |entry_point: &Option<CasmContractEntryPoint>| match entry_point {
+                Some(entry_point) => format!(
+                    "offset {} with builtins [{}]",
+                    entry_point.offset,
+                    entry_point.builtins.join(", ")
+                ),
+                None => "missing".into(),
+            };
+            writeln!(
+                f,
+                "{} entry point {:#x}: expected {}, got {}.",
+                entry_point.entry_point_type,
+                entry_point.selector,
+                describe(&entry_point.expected),
+                describe(&entry_point.actual)
+            )?;
+        }
+        for segment in &diff.segments {
+            writeln!(
+                f,
+                "{}: expected {} felts, got {} felts, first difference at offset {}.",
+                segment.kind,
+                segment.expected_length,
+                segment.actual_length,
+                segment.first_difference
+            )?;
+        }
+        if diff.segment_lengths_differ {
+            writeln!(f, "The bytecode segment lengths differ.")?;
+        }
+        Ok(())
+    }
+}
+
+/// The differences between two compiled classes of the same contract class, in the parts covered
+/// by the compiled class hash.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CasmContractClassDiff {
+    /// The entry points that differ, or exist in only one of the classes.
+    pub entry_points: Vec<EntryPointDiff>,
+    /// The bytecode segments whose content differs.
+    pub segments: Vec<SegmentDiff>,
+    /// Whether the segmentation of the bytecode differs, even if its content does not.
+    pub segment_lengths_differ: bool,
+}
+
+/// An entry point that differs between the expected and the actual compiled classes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryPointDiff {
+    /// The type of the entry point, as named in the compiled class.
+    pub entry_point_type: &'static str,
+    pub selector: BigUint,
+    pub expected: Option<CasmContractEntryPoint>,
+    pub actual: Option<CasmContractEntryPoint>,
+}
+
+/// The part of the bytecode a segment was compiled from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SegmentKind {
+    /// The code of a Sierra function, identified by its debug name if available.
+    Function(String),
+    /// The const segments following the code of the functions.
+    Consts,
+    /// The entire bytecode, for classes that are not segmented by functions.
+    Bytecode,
+}
+impl fmt::Display for SegmentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SegmentKind::Function(name) => write!(f, "function `{name}`"),
+            SegmentKind::Consts => write!(f, "consts"),
+            SegmentKind::Bytecode => write!(f, "bytecode"),
+        }
+    }
+}
+
+/// A bytecode segment whose content differs between the expected and the actual compiled classes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SegmentDiff {
+    pub kind: SegmentKind,
+    /// The length of the segment in the expected class.
+    pub expected_length: usize,
+    /// The length of the segment in the actual class.
+    pub actual_length: usize,
+    /// The offset, relative to the segment start, of the first differing felt252.
+    pub first_difference: usize,
+}
+
+/// Recompiles `contract_class` with the current compiler and checks that its compiled class hash
+/// is `expected_hash`.
+///
+/// Returns the mismatch if the hashes differ, with the differences from
+/// `expected_casm_contract_class` if it is provided.
+#[allow(clippy::result_large_err)]
+pub fn check_recompilation(
+    contract_class: ContractClass,
+    expected_hash: &BigUint,
+    expected_casm_contract_class: Option<&CasmContractClass>,
+    max_bytecode_size: usize,
+) -> Result<Option<RecompilationMismatch>, StarknetSierraCompilationError> {
+    let program = contract_class.extract_sierra_program()?;
+    let casm_contract_class =
+        CasmContractClass::from_contract_class(contract_class, false, max_bytecode_size)?;
+    let actual_hash = casm_contract_class.compiled_class_hash().to_biguint();
+    if &actual_hash == expected_hash {
+        return Ok(None);
+    }
+    Ok(Some(RecompilationMismatch {
+        expected_hash: expected_hash.clone(),
+        actual_hash,
+        diff: expected_casm_contract_class
+            .map(|expected| diff_casm_contract_classes(&program, expected, &casm_contract_class)),
+    }))
+}
+
+/// Returns the differences between two compiled classes of the contract class of `program`.
+///
+/// If both classes are segmented by the functions of `program`, the bytecode is compared per
+/// function. Otherwise, it is compared as a whole.
+pub fn diff_casm_contract_classes(
+    program: &Program,
+    expected: &CasmContractClass,
+    actual: &CasmContractClass,
+) -> CasmContractClassDiff {
+    let entry_points = [
+        (
+            "EXTERNAL",
+            &expected.entry_points_by_type.external,
+            &actual.entry_points_by_type.external,
+        ),
+        (
+            "L1_HANDLER",
+            &expected.entry_points_by_type.l1_handler,
+            &actual.entry_points_by_type.l1_handler,
+        ),
+        (
+            "CONSTRUCTOR",
+            &expected.entry_points_by_type.constructor,
+            &actual.entry_points_by_type.constructor,
+        ),
+    ]
+    .into_iter()
+    .flat_map(|(entry_point_type, expected, actual)| {
+        diff_entry_points(entry_point_type, expected, actual)
+    })
+    .collect();
+
+    let expected_segments = function_segments(program, expected);
+    let actual_segments = function_segments(program, actual);
+    let segments = match (expected_segments, actual_segments) {
+        (Some(expected_segments), Some(actual_segments)) => expected_segments
+            .into_iter()
+            .zip(actual_segments)
+            .filter_map(|((kind, expected_range), (_, actual_range))| {
+                diff_segment(
+                    kind,
+                    &expected.bytecode[expected_range],
+                    &actual.bytecode[actual_range],
+                )
+            })
+            .collect(),
+        _ => diff_segment(SegmentKind::Bytecode, &expected.bytecode, &actual.bytecode)
+            .into_iter()
+            .collect(),
+    };
+
+    CasmContractClassDiff {
+        entry_points,
+        segments,
+        segment_lengths_differ: expected.get_bytecode_segment_lengths()
+            != actual.get_bytecode_segment_lengths(),
+    }
+}
+
+/// Returns the differing entry points of the given type, where both lists are sorted by selector.
+fn diff_entry_points(
+    entry_point_type: &'static str,
+    expected: &[CasmContractEntryPoint],
+    actual: &[CasmContractEntryPoint],
+) -> Vec<EntryPointDiff> {
+    expected
+        .iter()
+        .merge_join_by(actual, |expected, actual| expected.selector.cmp(&actual.selector))
+        .filter_map(|entry| {
+            let (expected, actual) = match entry {
+                EitherOrBoth::Both(expected, actual) if expected == actual => return None,
+                EitherOrBoth::Both(expected, actual) => (Some(expected), Some(actual)),
+                EitherOrBoth::Left(expected) => (Some(expected), None),
+                EitherOrBoth::Right(actual) => (None, Some(actual)),
+            };
+            Some(EntryPointDiff {
+                entry_point_type,
+                selector: expected.or(actual).unwrap().selector.clone(),
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the bytecode ranges of the functions of `program` in `casm_contract_class`, ordered by
+/// their entry points, followed by the range of the consts.
+///
+/// Returns `None` if the class is not segmented by the functions of `program`.
+fn function_segments(
+    program: &Program,
+    casm_contract_class: &CasmContractClass,
+) -> Option<Vec<(SegmentKind, Range<usize>)>> {
+    let NestedIntList::Node(segments) = casm_contract_class.bytecode_segment_lengths.as_ref()?
+    else {
+        return None;
+    };
+    // Every function compiles to at least one instruction, so functions sharing an entry point
+    // share a segment.
+    let functions = program
+        .funcs
+        .iter()
+        .sorted_by_key(|function| function.entry_point.0)
+        .dedup_by(|a, b| a.entry_point == b.entry_point)
+        .collect_vec();
+    if segments.len() < functions.len() {
+        return None;
+    }
+    let mut offset = 0;
+    let mut ranges = vec![];
+    for (idx, segment) in segments.iter().enumerate() {
+        let length = nested_length(segment);
+        let kind = match functions.get(idx) {
+            Some(function) => SegmentKind::Function(function.id.to_string()),
+            None => SegmentKind::Consts,
+        };
+        ranges.push((kind, offset..offset + length));
+        offset += length;
+    }
+    if offset != casm_contract_class.bytecode.len() {
+        return None;
+    }
+    // Merge the const segments, which may be split differently in the two classes.
+    let consts_start = ranges.get(functions.len()).map_or(offset, |(_, range)| range.start);
+    ranges.truncate(functions.len());
+    ranges.push((SegmentKind::Consts, consts_start..offset));
+    Some(ranges)
+}
+
+/// Returns the total length of the segments in `segment_lengths`.
+fn nested_length(segment_lengths: &NestedIntList) -> usize {
+    match segment_lengths {
+        NestedIntList::Leaf(length) => *length,
+        NestedIntList::Node(children) => children.iter().map(nested_length).sum(),
+    }
+}
+
+/// Returns the difference between the expected and actual content of a segment, if any.
+fn diff_segment(
+    kind: SegmentKind,
+    expected: &[BigUintAsHex],
+    actual: &[BigUintAsHex],
+) -> Option<SegmentDiff> {
+    let first_difference = expected.iter().zip_longest(actual).position(
+        |entry| !matches!(entry, EitherOrBoth::Both(expected, actual) if expected == actual),
+    )?;
+    Some(SegmentDiff {
+        kind,
+        expected_length: expected.len(),
+        actual_length: actual.len(),
+        first_difference,
+    })
+}
@@ -0,0 +1,76 @@
+use std::io::BufReader;
+
+use cairo_lang_utils::bigint::BigUintAsHex;
+use num_bigint::BigUint;
+use pretty_assertions::assert_eq;
+
+use crate::casm_contract_class::CasmContractClass;
+use crate::contract_class::ContractClass;
+use crate::recompilation::{check_recompilation, SegmentDiff, SegmentKind};
+use crate::test_utils::get_example_file_path;
+
+/// Returns the contract class and the expected compiled class of the example contract `name`.
+fn example_classes(name: &str) -> (ContractClass, CasmContractClass) {
+    let read = |suffix: &str| {
+        BufReader::new(
+            std::fs::File::open(get_example_file_path(&format!("{name}.{suffix}.json"))).unwrap(),
+        )
+    };
+    (
+        serde_json::from_reader(read("contract_class")).unwrap(),
+        serde_json::from_reader(read("compiled_contract_class")).unwrap(),
+    )
+}
+
+#[test]
+fn test_recompilation_match() {
+    let (contract_class, expected) = example_classes("erc20__erc_20");
+    let expected_hash = expected.compiled_class_hash().to_biguint();
+
+    assert_eq!(
+        check_recompilation(contract_class, &expected_hash, Some(&expected), usize::MAX),
+        Ok(None)
+    );
+}
+
+#[test]
+fn test_recompilation_mismatch() {
+    let (contract_class, mut expected) = example_classes("erc20__erc_20");
+    // Modify the second function and move the first external entry point.
+    expected.bytecode[116 + 5] = BigUintAsHex { value: BigUint::from(7u32) };
+    expected.entry_points_by_type.external[0].offset += 1;
+    let expected_hash = expected.compiled_class_hash().to_biguint();
+
+    let mismatch = check_recompilation(contract_class, &expected_hash, Some(&expected), usize::MAX)
+        .unwrap()
+        .unwrap();
+    assert_eq!(mismatch.expected_hash, expected_hash);
+    let diff = mismatch.diff.as_ref().unwrap();
+    assert_eq!(diff.entry_points.len(), 1);
+    assert_eq!(diff.entry_points[0].expected.as_ref().unwrap().offset, 1216);
+    assert_eq!(diff.entry_points[0].actual.as_ref().unwrap().offset, 1215);
+    assert_eq!(
+        diff.segments,
+        vec![SegmentDiff {
+            kind: SegmentKind::Function(
+                "cairo_level_tests::contracts::erc20::erc_20::__wrapper__IERC20Impl__get_symbol"
+                    .into()
+            ),
+            expected_length: 116,
+            actual_length: 116,
+            first_difference: 5,
+        }]
+    );
+    assert!(!diff.segment_lengths_differ);
+}
+
+#[test]
+fn test_recompilation_mismatch_without_expected_class() {
+    let (contract_class, _) = example_classes("erc20__erc_20");
+
+    let mismatch = check_recompilation(contract_class, &BigUint::from(1u32), None, usize::MAX)
+        .unwrap()
+        .unwrap();
+    assert_eq!(mismatch.diff, None);
+    assert!(mismatch.to_string().starts_with("Compiled class hash mismatch: expected 0x1, got 0x"));
+}
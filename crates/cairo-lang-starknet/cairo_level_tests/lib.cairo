@@ -21,5 +21,7 @@ mod replace_class_test;
 #[cfg(test)]
 mod storage_access;
 #[cfg(test)]
+mod storage_paths;
+#[cfg(test)]
 mod contract_address_test;
 mod utils;
@@ -80,15 +80,27 @@ struct NonZeros {
     value_felt252: NonZero<felt252>,
 }
 
+#[derive(Copy, Drop, Debug, Serde, PartialEq, starknet::Store)]
+#[packed]
+struct Packed {
+    enabled: bool,
+    fee: u16,
+    owner: ContractAddress,
+    limit: u128,
+    period: u64,
+    threshold: u128,
+}
+
 #[starknet::contract]
 mod test_contract {
-    use super::{AbcEtc, ByteArrays, NonZeros};
+    use super::{AbcEtc, ByteArrays, NonZeros, Packed};
 
     #[storage]
     struct Storage {
         data: AbcEtc,
         byte_arrays: ByteArrays,
         non_zeros: NonZeros,
+        packed: Packed,
     }
 
     #[external(v0)]
@@ -120,6 +132,16 @@ mod test_contract {
     pub fn get_non_zeros(self: @ContractState) -> NonZeros {
         self.non_zeros.read()
     }
+
+    #[external(v0)]
+    pub fn set_packed(ref self: ContractState, value: Packed) {
+        self.packed.write(value);
+    }
+
+    #[external(v0)]
+    pub fn get_packed(self: @ContractState) -> Packed {
+        self.packed.read()
+    }
 }
 
 #[test]
@@ -206,3 +228,29 @@ fn test_read_write_non_zero() {
     assert!(test_contract::__external::set_non_zeros(serialized(x.clone())).is_empty());
     assert_eq!(deserialized(test_contract::__external::get_non_zeros(serialized(()))), x);
 }
+
+#[test]
+fn write_read_packed_struct() {
+    let x = Packed {
+        enabled: true,
+        fee: BoundedInt::max(),
+        owner: 123_felt252.try_into().unwrap(),
+        limit: BoundedInt::max(),
+        period: 4_u64,
+        threshold: BoundedInt::max(),
+    };
+
+    assert!(test_contract::__external::set_packed(serialized(x)).is_empty());
+    assert_eq!(deserialized(test_contract::__external::get_packed(serialized(()))), x);
+    // `enabled`, `fee`, `limit` and `period` share a slot, and `threshold` opens another one.
+    assert_eq!(starknet::Store::<Packed>::size(), 3);
+}
+
+#[test]
+fn read_packed_struct_with_unused_bits_set() {
+    let base = starknet::storage_access::storage_base_address_const::<0>();
+    // The first slot of `Packed` uses its low 209 bits.
+    let value = 0x20000000000000000000000000000000000000000000000000000_felt252;
+    starknet::Store::write(0, base, value).unwrap();
+    assert!(starknet::Store::<Packed>::read(0, base).is_err());
+}
@@ -0,0 +1,151 @@
+use starknet::storage::{
+    StoragePath, StoragePathTrait, StorageMapPath, StorageMapPathTrait, ReadOnlyStoragePath,
+    ReadOnlyStoragePathTrait, ReadOnlyStorageMapPath, ReadOnlyStorageMapPathTrait
+};
+use super::utils::{deserialized, serialized};
+
+/// Increments the counter at the given path - usable for any `u64` storage location.
+fn increment(counter: StoragePath<u64>) -> u64 {
+    let value = counter.read() + 1;
+    counter.write(value);
+    value
+}
+
+/// Moves `amount` from the entry of `from` to the entry of `to` of the given balances mapping.
+fn transfer(balances: StorageMapPath<felt252, u128>, from: felt252, to: felt252, amount: u128) {
+    balances.write(from, balances.read(from) - amount);
+    let to_balance = balances.entry(to);
+    to_balance.write(to_balance.read() + amount);
+}
+
+/// Returns the sum of the entries of `a` and `b` of the given balances mapping - usable with a
+/// snapshot of the contract's state.
+fn total_balance(balances: ReadOnlyStorageMapPath<felt252, u128>, a: felt252, b: felt252) -> u128 {
+    balances.read(a) + balances.entry(b).read()
+}
+
+/// Returns the value at the given read-only path.
+fn get(counter: ReadOnlyStoragePath<u64>) -> u64 {
+    counter.read()
+}
+
+#[starknet::component]
+mod counter_component {
+    #[storage]
+    struct Storage {
+        component_counter: u64,
+        component_balances: LegacyMap<felt252, u128>,
+    }
+
+    #[generate_trait]
+    pub impl CounterHelperImpl<
+        TContractState, impl X: HasComponent<TContractState>
+    > of CounterHelperTrait<TContractState, X> {
+        fn increment(ref self: ComponentState<TContractState>) -> u64 {
+            super::increment(self.component_counter.path_mut())
+        }
+        fn transfer(
+            ref self: ComponentState<TContractState>, from: felt252, to: felt252, amount: u128
+        ) {
+            super::transfer(self.component_balances.path_mut(), from, to, amount);
+        }
+        fn get(self: @ComponentState<TContractState>) -> u64 {
+            super::get(self.component_counter.path())
+        }
+    }
+}
+
+#[starknet::contract]
+mod test_contract {
+    use super::counter_component;
+
+    #[storage]
+    struct Storage {
+        counter: u64,
+        balances: LegacyMap<felt252, u128>,
+        #[substorage(v0)]
+        counter_storage: counter_component::Storage,
+    }
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        CounterEvent: counter_component::Event,
+    }
+
+    component!(path: counter_component, storage: counter_storage, event: CounterEvent);
+
+    impl CounterHelper = counter_component::CounterHelperImpl<ContractState>;
+
+    #[external(v0)]
+    pub fn increment(ref self: ContractState) -> u64 {
+        super::increment(self.counter.path_mut())
+    }
+
+    #[external(v0)]
+    pub fn increment_component(ref self: ContractState) -> u64 {
+        self.counter_storage.increment()
+    }
+
+    #[external(v0)]
+    pub fn set_balance(ref self: ContractState, owner: felt252, balance: u128) {
+        self.balances.write(owner, balance);
+    }
+
+    #[external(v0)]
+    pub fn transfer(ref self: ContractState, from: felt252, to: felt252, amount: u128) {
+        super::transfer(self.balances.path_mut(), from, to, amount);
+    }
+
+    #[external(v0)]
+    pub fn get_balance(self: @ContractState, owner: felt252) -> u128 {
+        self.balances.path().read(owner)
+    }
+
+    #[external(v0)]
+    pub fn get_total_balance(self: @ContractState, a: felt252, b: felt252) -> u128 {
+        super::total_balance(self.balances.path(), a, b)
+    }
+
+    #[external(v0)]
+    pub fn get_counters(self: @ContractState) -> (u64, u64) {
+        (super::get(self.counter.path()), self.counter_storage.get())
+    }
+}
+
+#[test]
+fn test_increment_through_path() {
+    assert_eq!(deserialized(test_contract::__external::increment(serialized(()))), 1_u64);
+    assert_eq!(deserialized(test_contract::__external::increment(serialized(()))), 2_u64);
+}
+
+#[test]
+fn test_component_increment_through_path() {
+    assert_eq!(deserialized(test_contract::__external::increment_component(serialized(()))), 1_u64);
+    // The counter of the component is stored separately from the counter of the contract.
+    assert_eq!(deserialized(test_contract::__external::increment(serialized(()))), 1_u64);
+    assert_eq!(deserialized(test_contract::__external::increment_component(serialized(()))), 2_u64);
+    assert_eq!(
+        deserialized(test_contract::__external::get_counters(serialized(()))), (1_u64, 2_u64)
+    );
+}
+
+#[test]
+fn test_transfer_through_map_path() {
+    assert!(test_contract::__external::set_balance(serialized((1, 100_u128))).is_empty());
+    assert!(test_contract::__external::transfer(serialized((1, 2, 30_u128))).is_empty());
+    assert_eq!(deserialized(test_contract::__external::get_balance(serialized(1))), 70_u128);
+    assert_eq!(deserialized(test_contract::__external::get_balance(serialized(2))), 30_u128);
+    assert_eq!(
+        deserialized(test_contract::__external::get_total_balance(serialized((1, 2)))), 100_u128
+    );
+}
+
+#[test]
+fn test_read_only_path_from_address() {
+    let path: ReadOnlyStoragePath<u64> = ReadOnlyStoragePathTrait::new(
+        starknet::storage_base_address_from_felt252(selector!("counter"))
+    );
+    assert_eq!(deserialized(test_contract::__external::increment(serialized(()))), 1_u64);
+    assert_eq!(path.read(), 1);
+}
@@ -30,14 +30,15 @@ use itertools::zip_eq;
 use smol_str::SmolStr;
 use thiserror::Error;
 
-use crate::plugin::aux_data::StarkNetEventAuxData;
+use crate::plugin::aux_data::StarkNetDeriveAuxData;
 use crate::plugin::consts::{
     ABI_ATTR, ABI_ATTR_EMBED_V0_ARG, ABI_ATTR_PER_ITEM_ARG, ACCOUNT_CONTRACT_ENTRY_POINT_SELECTORS,
-    CONSTRUCTOR_ATTR, CONTRACT_ATTR, CONTRACT_ATTR_ACCOUNT_ARG, CONTRACT_STATE_NAME,
-    EMBEDDABLE_ATTR, EVENT_ATTR, EVENT_TYPE_NAME, EXTERNAL_ATTR, FLAT_ATTR, INTERFACE_ATTR,
-    L1_HANDLER_ATTR, VALIDATE_DEPLOY_ENTRY_POINT_SELECTOR,
+    CONSTRUCTOR_ATTR, CONTRACT_ATTR, CONTRACT_ATTR_ACCOUNT_ARG, CONTRACT_ATTR_STRICT_CALLDATA_ARG,
+    CONTRACT_STATE_NAME, EMBEDDABLE_ATTR, EVENT_ATTR, EVENT_TYPE_NAME, EXTERNAL_ATTR, FLAT_ATTR,
+    INTERFACE_ATTR, L1_HANDLER_ATTR, VALIDATE_DEPLOY_ENTRY_POINT_SELECTOR,
 };
 use crate::plugin::events::EventData;
+use crate::plugin::storage_packing::StoragePacking;
 
 #[cfg(test)]
 #[path = "abi_test.rs"]
@@ -138,11 +139,14 @@ impl<'a> AbiBuilder<'a> {
         let attrs = submodule_id.query_attr(self.db, CONTRACT_ATTR)?;
         let mut is_account_contract = false;
         for attr in attrs {
-            if attr.is_single_unnamed_arg(self.db.upcast(), CONTRACT_ATTR_ACCOUNT_ARG) {
-                is_account_contract = true;
-            } else if !attr.args.is_empty() {
-                self.errors.push(ABIError::IllegalContractAttrArgs);
-                return Ok(());
+            for arg in &attr.args {
+                let arg_text = arg.arg.as_syntax_node().get_text_without_trivia(self.db.upcast());
+                if arg_text == CONTRACT_ATTR_ACCOUNT_ARG {
+                    is_account_contract = true;
+                } else if arg_text != CONTRACT_ATTR_STRICT_CALLDATA_ARG {
+                    self.errors.push(ABIError::IllegalContractAttrArgs);
+                    return Ok(());
+                }
             }
         }
         if is_account_contract {
@@ -695,7 +699,13 @@ impl<'a> AbiBuilder<'a> {
         match concrete {
             ConcreteTypeId::Struct(id) => {
                 let members = self.add_and_get_struct_members(id)?;
-                let struct_item = Item::Struct(Struct { name: concrete.format(self.db), members });
+                let name = concrete.format(self.db);
+                let storage_layout = fetch_storage_packing(
+                    self.db,
+                    self.db.intern_type(TypeLongId::Concrete(concrete)),
+                )
+                .map(|storage_packing| storage_packing.abi_layout());
+                let struct_item = Item::Struct(Struct { name, members, storage_layout });
                 self.add_abi_item(struct_item, true, Source::Struct(id))?;
             }
             ConcreteTypeId::Enum(id) => {
@@ -818,34 +828,51 @@ fn is_impl_abi_per_item(db: &dyn SemanticGroup, imp: ImplDefId) -> Maybe<bool> {
 /// Fetch the event data for the given type. Returns None if the given event type doesn't derive
 /// `starknet::Event` by using the `derive` attribute.
 fn fetch_event_data(db: &dyn SemanticGroup, event_type_id: TypeId) -> Option<EventData> {
+    // `starknet::event::Event`.
+    fetch_derive_aux_data(db, "event", "Event", event_type_id)?.event_data
+}
+
+/// Fetch the storage packing of the given type. Returns None if the given type doesn't derive
+/// `starknet::Store` by using the `derive` attribute, or isn't a struct annotated with `#[packed]`.
+fn fetch_storage_packing(db: &dyn SemanticGroup, type_id: TypeId) -> Option<StoragePacking> {
+    // `starknet::storage_access::Store`.
+    fetch_derive_aux_data(db, "storage_access", "Store", type_id)?.storage_packing
+}
+
+/// Fetch the auxiliary data of the generation of the impl of the trait `trait_name` of the
+/// `starknet` submodule `module_name` for the given type, generated by a Starknet derive.
+fn fetch_derive_aux_data(
+    db: &dyn SemanticGroup,
+    module_name: &str,
+    trait_name: &str,
+    type_id: TypeId,
+) -> Option<StarkNetDeriveAuxData> {
     let starknet_module = core_submodule(db, "starknet");
-    // `starknet::event`.
-    let event_module = try_extract_matches!(
-        db.module_item_by_name(starknet_module, "event".into()).unwrap().unwrap(),
+    let module = try_extract_matches!(
+        db.module_item_by_name(starknet_module, module_name.into()).unwrap().unwrap(),
         ModuleItemId::Submodule
     )?;
-    // `starknet::event::Event`.
-    let event_trait_id = try_extract_matches!(
-        db.module_item_by_name(ModuleId::Submodule(event_module), "Event".into()).unwrap().unwrap(),
+    let trait_id = try_extract_matches!(
+        db.module_item_by_name(ModuleId::Submodule(module), trait_name.into()).unwrap().unwrap(),
         ModuleItemId::Trait
     )?;
-    // `starknet::event::Event<ThisEvent>`.
+    // The trait for the given type, e.g. `starknet::event::Event<ThisEvent>`.
     let concrete_trait_id = db.intern_concrete_trait(ConcreteTraitLongId {
-        trait_id: event_trait_id,
-        generic_args: vec![GenericArgumentId::Type(event_type_id)],
+        trait_id,
+        generic_args: vec![GenericArgumentId::Type(type_id)],
     });
-    // The impl of `starknet::event::Event<ThisEvent>`.
-    let event_impl =
+    // The impl of the trait for the given type.
+    let imp =
         get_impl_at_context(db.upcast(), ImplLookupContext::default(), concrete_trait_id, None)
             .ok()?;
-    let concrete_event_impl = try_extract_matches!(event_impl, ImplId::Concrete)?;
-    let impl_def_id = concrete_event_impl.impl_def_id(db);
+    let concrete_impl = try_extract_matches!(imp, ImplId::Concrete)?;
+    let impl_def_id = concrete_impl.impl_def_id(db);
 
-    // Attempt to extract the event data from the aux data from the impl generation.
+    // Attempt to extract the aux data from the impl generation.
     let module_file = impl_def_id.module_file_id(db.upcast());
     let file_infos = db.module_generated_file_infos(module_file.0).ok()?;
     let aux_data = file_infos.get(module_file.1.0)?.as_ref()?.aux_data.as_ref()?;
-    Some(aux_data.0.as_any().downcast_ref::<StarkNetEventAuxData>()?.event_data.clone())
+    aux_data.0.as_any().downcast_ref::<StarkNetDeriveAuxData>().cloned()
 }
 
 #[derive(Error, Debug)]
@@ -2,9 +2,12 @@ use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
 use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_starknet_classes::abi::{Item, PackedStructMember, StorageLayoutItem};
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_test_utils::{get_direct_or_file_content, verify_diagnostics_expectation};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
 
 use super::BuilderConfig;
 use crate::abi::AbiBuilder;
@@ -57,3 +60,67 @@ cairo_lang_test_utils::test_file_test!(
   },
   test_abi_failure
 );
+
+#[test]
+fn test_struct_storage_layout() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(starknet_plugin_suite())
+        .build()
+        .unwrap();
+    let (module, diagnostics) = setup_test_module(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod test_contract {
+                #[storage]
+                struct Storage {}
+
+                #[derive(Drop, Serde, starknet::Store)]
+                #[packed]
+                struct Config {
+                    enabled: bool,
+                    owner: felt252,
+                    fee: u16,
+                }
+
+                #[external(v0)]
+                fn set_config(ref self: ContractState, config: Config) {}
+            }
+        "},
+    )
+    .split();
+    assert_eq!(diagnostics, "");
+
+    let contract_submodule = db.module_submodules_ids(module.module_id).unwrap()[0];
+    let abi = AbiBuilder::from_submodule(
+        db,
+        contract_submodule,
+        BuilderConfig { account_contract_validations: true },
+    )
+    .unwrap()
+    .finalize()
+    .unwrap();
+    let storage_layouts = abi
+        .items()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some((item.name.as_str(), item.storage_layout.clone())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        storage_layouts,
+        vec![(
+            "test::test_contract::Config",
+            Some(vec![
+                StorageLayoutItem::PackedSlot {
+                    members: vec![
+                        PackedStructMember { name: "enabled".into(), shift: 0, bits: 1 },
+                        PackedStructMember { name: "fee".into(), shift: 1, bits: 16 },
+                    ]
+                },
+                StorageLayoutItem::Member { name: "owner".into() },
+            ])
+        )]
+    );
+}
@@ -1,15 +1,17 @@
 use cairo_lang_defs::ids::ModuleId;
 use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_semantic::corelib::get_core_ty_by_name;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
 use cairo_lang_semantic::plugin::AnalyzerPlugin;
 use cairo_lang_syntax::attribute::consts::STARKNET_INTERFACE_ATTR;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
-use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use cairo_lang_syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode};
 
 use crate::abi::{ABIError, AbiBuilder, BuilderConfig};
 use crate::contract::module_contract;
-use crate::plugin::consts::EMBEDDABLE_ATTR;
+use crate::plugin::consts::{EMBEDDABLE_ATTR, PACKED_ATTR};
+use crate::plugin::storage_packing::{packed_bits, PACKABLE_TYPES};
 
 /// Plugin to add diagnostics for contracts for bad ABI generation.
 #[derive(Default, Debug)]
@@ -20,6 +22,7 @@ impl AnalyzerPlugin for ABIAnalyzer {
         let mut diagnostics = vec![];
         add_non_starknet_interface_embeddable_diagnostics(db, module_id, &mut diagnostics);
         add_abi_diagnostics(db, module_id, &mut diagnostics);
+        add_storage_packing_diagnostics(db, module_id, &mut diagnostics);
         diagnostics
     }
 }
@@ -74,3 +77,52 @@ fn add_abi_diagnostics(
         }
     }
 }
+
+/// Add diagnostics for members of structs annotated with `#[packed]` whose resolved types don't
+/// match the way the `starknet::Store` derive packs them, as it recognizes the types by their
+/// names.
+fn add_storage_packing_diagnostics(
+    db: &dyn SemanticGroup,
+    module_id: ModuleId,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+) {
+    let Ok(structs) = db.module_structs(module_id) else {
+        return;
+    };
+    let syntax_db = db.upcast();
+    for (id, item) in structs.iter() {
+        if !item.has_attr(syntax_db, PACKED_ATTR) {
+            continue;
+        }
+        let Ok(members) = db.struct_members(*id) else { continue };
+        for member_ast in item.members(syntax_db).elements(syntax_db) {
+            let name = member_ast.name(syntax_db).text(syntax_db);
+            let Some(member) = members.get(&name) else { continue };
+            let type_ast = member_ast.type_clause(syntax_db).ty(syntax_db);
+            let written_ty = type_ast.as_syntax_node().get_text_without_trivia(syntax_db);
+            let resolved_ty = PACKABLE_TYPES
+                .iter()
+                .map(|(name, _)| *name)
+                .find(|name| get_core_ty_by_name(db, (*name).into(), vec![]) == member.ty);
+            match (packed_bits(&written_ty), resolved_ty) {
+                (Some(_), Some(resolved_ty)) if resolved_ty == written_ty => {}
+                (Some(_), _) => diagnostics.push(PluginDiagnostic::error(
+                    type_ast.stable_ptr().untyped(),
+                    format!(
+                        "Packed member `{name}` must be of the core type `{written_ty}`, found \
+                         `{}`.",
+                        member.ty.format(db)
+                    ),
+                )),
+                (None, Some(resolved_ty)) => diagnostics.push(PluginDiagnostic::warning(
+                    type_ast.stable_ptr().untyped(),
+                    format!(
+                        "Member `{name}` is not packed, as its type is not written as \
+                         `{resolved_ty}`."
+                    ),
+                )),
+                (None, None) => {}
+            }
+        }
+    }
+}
@@ -5,9 +5,11 @@ use anyhow::{Context, Result};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::project::setup_project;
 use cairo_lang_compiler::CompilerConfig;
+use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::TopLevelLanguageElementId;
 use cairo_lang_diagnostics::ToOption;
-use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup};
+use cairo_lang_filesystem::ids::{CrateId, Directory, FileLongId};
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
 use cairo_lang_sierra::debug_info::Annotations;
@@ -16,9 +18,13 @@ use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
 use cairo_lang_sierra_generator::replace_ids::{replace_sierra_ids_in_program, SierraIdReplacer};
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
+use cairo_lang_starknet_classes::compilation_claim::{
+    source_digest, ClaimConfig, ClaimMismatch, CompilationClaim,
+};
 use cairo_lang_starknet_classes::contract_class::{
     ContractClass, ContractEntryPoint, ContractEntryPoints,
 };
+use cairo_lang_starknet_classes::gas_estimation::ContractGasEstimate;
 use itertools::{chain, Itertools};
 
 use crate::abi::AbiBuilder;
@@ -28,7 +34,7 @@ use crate::contract::{
     ContractDeclaration,
 };
 use crate::plugin::consts::{CONSTRUCTOR_MODULE, EXTERNAL_MODULE, L1_HANDLER_MODULE};
-use crate::starknet_plugin_suite;
+use crate::{reentrancy_analysis_plugin_suite, starknet_plugin_suite};
 
 #[cfg(test)]
 #[path = "compile_test.rs"]
@@ -41,14 +47,133 @@ pub fn compile_path(
     contract_path: Option<&str>,
     compiler_config: CompilerConfig<'_>,
 ) -> Result<ContractClass> {
-    let mut db = RootDatabase::builder()
+    let (db, main_crate_ids) = setup_compilation_db(path, &compiler_config)?;
+    compile_contract_in_prepared_db(&db, contract_path, main_crate_ids, compiler_config)
+}
+
+/// Compile the contract given by path, along with the [CompilationClaim] binding the sources of
+/// the main crates and the compilation configuration to the resulting classes.
+/// `add_pythonic_hints` and `max_bytecode_size` are the configuration of the compilation of the
+/// contract class into its compiled class.
+/// Errors if there is ambiguity.
+pub fn compile_path_with_claim(
+    path: &Path,
+    contract_path: Option<&str>,
+    compiler_config: CompilerConfig<'_>,
+    add_pythonic_hints: bool,
+    max_bytecode_size: usize,
+) -> Result<(ContractClass, CompilationClaim)> {
+    let config = ClaimConfig {
+        revert_panic_abi: compiler_config.revert_panic_abi,
+        whole_program: compiler_config.whole_program,
+        add_pythonic_hints,
+        max_bytecode_size,
+    };
+    let (db, main_crate_ids) = setup_compilation_db(path, &compiler_config)?;
+    let sources = main_crates_sources(&db, &main_crate_ids);
+    let contract_class =
+        compile_contract_in_prepared_db(&db, contract_path, main_crate_ids, compiler_config)?;
+    let source_digest =
+        source_digest(sources.iter().map(|(path, content)| (path.as_str(), content.as_str())));
+    let claim = CompilationClaim::new(source_digest, config, &contract_class)?;
+    Ok((contract_class, claim))
+}
+
+/// Verifies a [CompilationClaim] by recompiling the contract given by path with the claimed
+/// configuration.
+///
+/// Returns the claimed fields that differ from the ones of the recompilation.
+pub fn verify_compilation_claim(
+    path: &Path,
+    contract_path: Option<&str>,
+    claim: &CompilationClaim,
+) -> Result<Vec<ClaimMismatch>> {
+    let (_, actual) = compile_path_with_claim(
+        path,
+        contract_path,
+        CompilerConfig {
+            revert_panic_abi: claim.config.revert_panic_abi,
+            whole_program: claim.config.whole_program,
+            ..CompilerConfig::default()
+        },
+        claim.config.add_pythonic_hints,
+        claim.config.max_bytecode_size,
+    )?;
+    Ok(claim.mismatches(&actual))
+}
+
+/// Compile the contract given by path, and solve the gas costs of its entry points, without
+/// compiling it into its compiled class.
+/// If `compute_runtime_costs` is true, the costs include the runtime cost token types as well.
+/// Errors if there is ambiguity.
+pub fn estimate_path_gas(
+    path: &Path,
+    contract_path: Option<&str>,
+    compiler_config: CompilerConfig<'_>,
+    compute_runtime_costs: bool,
+) -> Result<ContractGasEstimate> {
+    let contract_class = compile_path(path, contract_path, compiler_config)?;
+    Ok(ContractGasEstimate::new(&contract_class, compute_runtime_costs)?)
+}
+
+/// Builds a database for compiling Starknet contracts with the given configuration, and sets up
+/// the project at the given path in it.
+fn setup_compilation_db(
+    path: &Path,
+    compiler_config: &CompilerConfig<'_>,
+) -> Result<(RootDatabase, Vec<CrateId>)> {
+    let mut builder = RootDatabase::builder();
+    builder
         .detect_corelib()
         .with_plugin_suite(starknet_plugin_suite())
-        .build()?;
+        .with_revert_panic_abi(compiler_config.revert_panic_abi)
+        .with_explain_ownership(compiler_config.explain_ownership)
+        .with_whole_program(compiler_config.whole_program);
+    if compiler_config.reentrancy_analysis {
+        builder.with_plugin_suite(reentrancy_analysis_plugin_suite());
+    }
+    let mut db = builder.build()?;
 
     let main_crate_ids = setup_project(&mut db, Path::new(&path))?;
+    Ok((db, main_crate_ids))
+}
 
-    compile_contract_in_prepared_db(&db, contract_path, main_crate_ids, compiler_config)
+/// Returns the source files of the modules of the main crates, as pairs of their paths and
+/// contents. The paths are relative to the root of the crate, prefixed by the name of the crate,
+/// so they do not depend on the location of the project.
+fn main_crates_sources(
+    db: &RootDatabase,
+    main_crate_ids: &[CrateId],
+) -> Vec<(String, Arc<String>)> {
+    let mut sources = vec![];
+    for crate_id in main_crate_ids {
+        let crate_root = match db.crate_config(*crate_id) {
+            Some(CrateConfiguration { root: Directory::Real(root), .. }) => Some(root),
+            _ => None,
+        };
+        for module_id in db.crate_modules(*crate_id).iter() {
+            let Ok(files) = db.module_files(*module_id) else {
+                continue;
+            };
+            for file_id in files.iter() {
+                // Files generated by plugins are derived from the on-disk files.
+                let FileLongId::OnDisk(path) = db.lookup_intern_file(*file_id) else {
+                    continue;
+                };
+                let Some(content) = db.file_content(*file_id) else {
+                    continue;
+                };
+                let relative_path = crate_root
+                    .as_ref()
+                    .and_then(|root| path.strip_prefix(root).ok())
+                    .unwrap_or(&path);
+                let relative_path =
+                    relative_path.components().map(|c| c.as_os_str().to_string_lossy()).join("/");
+                sources.push((format!("{}/{relative_path}", crate_id.name(db)), content));
+            }
+        }
+    }
+    sources
 }
 
 /// Runs StarkNet contract compiler on the specified contract.
@@ -155,7 +280,7 @@ fn compile_contract_with_prepared_and_checked_db(
         Default::default()
     };
 
-    let contract_class = ContractClass::new(
+    let mut contract_class = ContractClass::new(
         &sierra_program,
         entry_points_by_type,
         Some(
@@ -168,6 +293,9 @@ fn compile_contract_with_prepared_and_checked_db(
         annotations,
     )?;
     contract_class.sanity_check();
+    if compiler_config.embed_gas_estimate {
+        ContractGasEstimate::new(&contract_class, false)?.embed(&mut contract_class);
+    }
     Ok(contract_class)
 }
 
@@ -1,7 +1,21 @@
+use std::path::PathBuf;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::CompilerConfig;
+use cairo_lang_semantic::test_utils::setup_test_crate;
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_size::ContractSizeInfo;
+use cairo_lang_starknet_classes::gas_estimation::ContractGasEstimate;
 use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+use indoc::formatdoc;
 use test_case::test_case;
 
+use crate::compile::{
+    compile_contract_in_prepared_db, compile_path, compile_path_with_claim, estimate_path_gas,
+    verify_compilation_claim,
+};
+use crate::starknet_plugin_suite;
 use crate::test_utils::{get_example_file_path, get_test_contract};
 
 /// Tests that the sierra compiled from a contract in the contracts crate is the same as in
@@ -41,3 +55,108 @@ fn test_compile_path_from_contracts_crate(example_contract_path: &str) {
         sierra_program.to_string(),
     );
 }
+
+/// Tests that the claim of compiling a contract is verified by recompiling its sources, and by the
+/// compiled contract class.
+#[test]
+fn test_compilation_claim() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("cairo_level_tests");
+    let contract_path = Some("cairo_level_tests::contracts::minimal_contract::minimal_contract");
+    let (contract, claim) =
+        compile_path_with_claim(&path, contract_path, CompilerConfig::default(), false, usize::MAX)
+            .unwrap();
+    assert_eq!(claim.verify_contract_class(&contract), Ok(vec![]));
+    assert_eq!(verify_compilation_claim(&path, contract_path, &claim).unwrap(), vec![]);
+
+    let mut other_sources_claim = claim.clone();
+    other_sources_claim.source_digest.value += 1u32;
+    let mismatches = verify_compilation_claim(&path, contract_path, &other_sources_claim).unwrap();
+    assert_eq!(
+        mismatches.iter().map(|mismatch| mismatch.field).collect::<Vec<_>>(),
+        vec!["source_digest"]
+    );
+}
+
+/// Tests that estimating the gas of a contract solves the same costs as for its contract class.
+#[test]
+fn test_estimate_path_gas() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("cairo_level_tests");
+    let contract_path = "cairo_level_tests::contracts::hello_starknet::hello_starknet";
+    let estimate =
+        estimate_path_gas(&path, Some(contract_path), CompilerConfig::default(), true).unwrap();
+    let contract = get_test_contract(contract_path);
+    assert_eq!(estimate, ContractGasEstimate::new(&contract, true).unwrap());
+    assert_eq!(estimate.external.len(), contract.entry_points_by_type.external.len());
+}
+
+/// Tests that the gas estimate embedded into a compiled contract class is its solved estimate, and
+/// that embedding it doesn't change the Sierra program.
+#[test]
+fn test_embed_gas_estimate() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("cairo_level_tests");
+    let contract_path = "cairo_level_tests::contracts::hello_starknet::hello_starknet";
+    let contract = compile_path(
+        &path,
+        Some(contract_path),
+        CompilerConfig { embed_gas_estimate: true, ..CompilerConfig::default() },
+    )
+    .unwrap();
+    assert_eq!(
+        ContractGasEstimate::embedded(&contract).unwrap(),
+        Some(ContractGasEstimate::new(&contract, false).unwrap())
+    );
+    let without_estimate =
+        compile_path(&path, Some(contract_path), CompilerConfig::default()).unwrap();
+    assert_eq!(ContractGasEstimate::embedded(&without_estimate).unwrap(), None);
+    assert_eq!(contract.sierra_program, without_estimate.sierra_program);
+}
+
+/// Tests that strict calldata validation costs bytecode size, as shown by the size report.
+#[test]
+fn test_strict_calldata_size() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(starknet_plugin_suite())
+        .build()
+        .unwrap();
+    let contract_code = |contract_attr: &str, name: &str| {
+        formatdoc! {"
+            {contract_attr}
+            mod {name} {{
+                #[storage]
+                struct Storage {{
+                    value: u128,
+                }}
+
+                #[external(v0)]
+                fn set(ref self: ContractState, a: u128, b: u128) {{
+                    self.value.write(a + b);
+                }}
+            }}
+        "}
+    };
+    let crate_id = setup_test_crate(
+        db,
+        &format!(
+            "{}\n{}",
+            contract_code("#[starknet::contract]", "permissive"),
+            contract_code("#[starknet::contract(strict_calldata)]", "strict")
+        ),
+    );
+    let contract_size_info = |contract_path: &str| {
+        let contract = compile_contract_in_prepared_db(
+            db,
+            Some(contract_path),
+            vec![crate_id],
+            CompilerConfig::default(),
+        )
+        .unwrap();
+        let casm_contract =
+            CasmContractClass::from_contract_class(contract.clone(), false, usize::MAX).unwrap();
+        ContractSizeInfo::new(&contract, &casm_contract)
+    };
+    let permissive = contract_size_info("test::permissive");
+    let strict = contract_size_info("test::strict");
+    assert!(strict.sierra_program_length > permissive.sierra_program_length);
+    assert!(strict.bytecode_length > permissive.bytecode_length);
+}
@@ -14,6 +14,7 @@ pub mod compile;
 pub mod contract;
 pub mod inline_macros;
 pub mod plugin;
+mod reentrancy;
 
 /// Get the suite of plugins for compilation with StarkNet.
 pub fn starknet_plugin_suite() -> PluginSuite {
@@ -27,5 +28,14 @@ pub fn starknet_plugin_suite() -> PluginSuite {
     suite
 }
 
+/// Get the suite of the opt-in re-entrancy analysis of contracts, to add on top of
+/// [starknet_plugin_suite]. It warns about storage writes following external calls in the entry
+/// points of contracts.
+pub fn reentrancy_analysis_plugin_suite() -> PluginSuite {
+    let mut suite = PluginSuite::default();
+    suite.add_analyzer_plugin::<reentrancy::ReentrancyAnalyzer>();
+    suite
+}
+
 #[cfg(test)]
 mod test_utils;
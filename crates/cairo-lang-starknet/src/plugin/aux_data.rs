@@ -1,6 +1,7 @@
 use cairo_lang_defs::plugin::GeneratedFileAuxData;
 
 use super::events::EventData;
+use super::storage_packing::StoragePacking;
 
 /// Contract related auxiliary data of the Starknet plugin.
 #[derive(Debug, PartialEq, Eq)]
@@ -16,12 +17,16 @@ impl GeneratedFileAuxData for StarkNetContractAuxData {
         if let Some(other) = other.as_any().downcast_ref::<Self>() { self == other } else { false }
     }
 }
-/// Contract related auxiliary data of the Starknet plugin.
-#[derive(Debug, PartialEq, Eq)]
-pub struct StarkNetEventAuxData {
-    pub event_data: EventData,
+/// Auxiliary data of the Starknet derives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StarkNetDeriveAuxData {
+    /// The data of a `#[derive(starknet::Event)]`.
+    pub event_data: Option<EventData>,
+    /// The storage layout of a `#[derive(starknet::Store)]` on a struct annotated with
+    /// `#[packed]`.
+    pub storage_packing: Option<StoragePacking>,
 }
-impl GeneratedFileAuxData for StarkNetEventAuxData {
+impl GeneratedFileAuxData for StarkNetDeriveAuxData {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
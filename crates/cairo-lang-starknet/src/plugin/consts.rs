@@ -4,6 +4,7 @@ pub const EXTERNAL_MODULE: &str = "__external";
 pub const L1_HANDLER_MODULE: &str = "__l1_handler";
 pub const CONSTRUCTOR_MODULE: &str = "__constructor";
 pub const WRAPPER_PREFIX: &str = "__wrapper__";
+pub const DEFAULT_IMPL_PREFIX: &str = "__default__";
 pub const STORAGE_STRUCT_NAME: &str = "Storage";
 pub const EVENT_TYPE_NAME: &str = "Event";
 pub const LEGACY_STORAGE_MAPPING: &str = "LegacyMap";
@@ -25,9 +26,11 @@ pub const INTERFACE_ATTR: &str = "starknet::interface";
 pub(super) const DEPRECATED_CONTRACT_ATTR: &str = "contract";
 pub const CONTRACT_ATTR: &str = "starknet::contract";
 pub const CONTRACT_ATTR_ACCOUNT_ARG: &str = "account";
+pub const CONTRACT_ATTR_STRICT_CALLDATA_ARG: &str = "strict_calldata";
 pub(super) const COMPONENT_ATTR: &str = "starknet::component";
 pub const STORAGE_ATTR: &str = "storage";
 pub const EXTERNAL_ATTR: &str = "external";
+pub const EXTERNAL_SEGMENT_ATTR: &str = "starknet::external_segment";
 pub const EMBEDDABLE_ATTR: &str = "starknet::embeddable";
 pub const L1_HANDLER_ATTR: &str = "l1_handler";
 pub const CONSTRUCTOR_ATTR: &str = "constructor";
@@ -40,6 +43,7 @@ pub const SUBSTORAGE_ATTR: &str = "substorage";
 pub const NESTED_ATTR: &str = "nested";
 pub const FLAT_ATTR: &str = "flat";
 pub const KEY_ATTR: &str = "key";
+pub const PACKED_ATTR: &str = "packed";
 pub const SERDE_ATTR: &str = "serde";
 
 pub const VALIDATE_ENTRY_POINT_SELECTOR: &str = "__validate__";
@@ -6,7 +6,6 @@ use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{ast, Terminal, TypedStablePtr, TypedSyntaxNode};
 use indoc::{formatdoc, indoc};
 
-use crate::plugin::aux_data::StarkNetEventAuxData;
 use crate::plugin::consts::{
     EVENT_TRAIT, EVENT_TYPE_NAME, FLAT_ATTR, KEY_ATTR, NESTED_ATTR, SERDE_ATTR,
 };
@@ -17,7 +16,7 @@ pub fn handle_event_derive(
     db: &dyn SyntaxGroup,
     item_ast: &ast::ModuleItem,
     diagnostics: &mut Vec<PluginDiagnostic>,
-) -> Option<(RewriteNode, StarkNetEventAuxData)> {
+) -> Option<(RewriteNode, EventData)> {
     match item_ast {
         ast::ModuleItem::Struct(struct_ast) => handle_struct(db, struct_ast, diagnostics),
         ast::ModuleItem::Enum(enum_ast) => handle_enum(db, enum_ast, diagnostics),
@@ -31,7 +30,7 @@ fn handle_struct(
     db: &dyn SyntaxGroup,
     struct_ast: &ast::ItemStruct,
     diagnostics: &mut Vec<PluginDiagnostic>,
-) -> Option<(RewriteNode, StarkNetEventAuxData)> {
+) -> Option<(RewriteNode, EventData)> {
     // TODO(spapini): Support generics.
     let generic_params = struct_ast.generic_params(db);
     let ast::OptionWrappedGenericParamList::Empty(_) = generic_params else {
@@ -98,7 +97,7 @@ fn handle_struct(
         ]
         .into(),
     );
-    Some((event_impl, StarkNetEventAuxData { event_data }))
+    Some((event_impl, event_data))
 }
 
 /// Retrieves the field kind for a given struct member,
@@ -180,7 +179,7 @@ fn handle_enum(
     db: &dyn SyntaxGroup,
     enum_ast: &ast::ItemEnum,
     diagnostics: &mut Vec<PluginDiagnostic>,
-) -> Option<(RewriteNode, StarkNetEventAuxData)> {
+) -> Option<(RewriteNode, EventData)> {
     const SELECTOR: &str = "__selector__";
     let enum_name = RewriteNode::new_trimmed(enum_ast.name(db).as_syntax_node());
 
@@ -335,7 +334,7 @@ fn handle_enum(
         .into(),
     );
 
-    Some((event_impl, StarkNetEventAuxData { event_data }))
+    Some((event_impl, event_data))
 }
 
 /// Generates code to emit an event for a field
@@ -7,6 +7,7 @@ use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{ast, TypedSyntaxNode};
 
+use super::aux_data::StarkNetDeriveAuxData;
 use super::consts::{EVENT_TRAIT, STORE_TRAIT};
 
 mod event;
@@ -41,20 +42,23 @@ fn has_derive<T: QueryAttrs>(with_attrs: &T, db: &dyn SyntaxGroup, derived_type:
 pub fn handle_derive(db: &dyn SyntaxGroup, item_ast: ast::ModuleItem) -> PluginResult {
     let mut builder = PatchBuilder::new(db);
     let mut diagnostics = vec![];
-    let mut aux_data = None;
+    let mut event_data = None;
+    let mut storage_packing = None;
     if has_derive(&item_ast, db, EVENT_TRAIT) {
-        if let Some((node, starknet_aux_data)) =
-            event::handle_event_derive(db, &item_ast, &mut diagnostics)
-        {
+        if let Some((node, data)) = event::handle_event_derive(db, &item_ast, &mut diagnostics) {
             builder.add_modified(node);
-            aux_data = Some(DynGeneratedFileAuxData::new(starknet_aux_data));
+            event_data = Some(data);
         }
     }
     if has_derive(&item_ast, db, STORE_TRAIT) {
-        if let Some(node) = store::handle_store_derive(db, &item_ast, &mut diagnostics) {
+        if let Some((node, packing)) = store::handle_store_derive(db, &item_ast, &mut diagnostics) {
             builder.add_modified(node);
+            storage_packing = packing;
         }
     }
+    let aux_data = (event_data.is_some() || storage_packing.is_some()).then(|| {
+        DynGeneratedFileAuxData::new(StarkNetDeriveAuxData { event_data, storage_packing })
+    });
 
     PluginResult {
         code: if builder.code.is_empty() {
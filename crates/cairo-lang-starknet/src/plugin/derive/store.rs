@@ -5,51 +5,108 @@ use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{ast, Terminal, TypedStablePtr, TypedSyntaxNode};
 use indent::indent_by;
 use indoc::formatdoc;
+use itertools::Itertools;
+use smol_str::SmolStr;
 
-use crate::plugin::consts::STORE_TRAIT;
+use crate::plugin::consts::{PACKED_ATTR, STORE_TRAIT};
+use crate::plugin::storage_packing::{
+    compute_storage_packing, PackedField, StorageItem, StoragePacking,
+};
 
-/// Returns the rewrite node for the `#[derive(starknet::Store)]` attribute.
+/// Returns the rewrite node for the `#[derive(starknet::Store)]` attribute, and the storage layout
+/// of packed structs.
 pub fn handle_store_derive(
     db: &dyn SyntaxGroup,
     item_ast: &ast::ModuleItem,
     diagnostics: &mut Vec<PluginDiagnostic>,
-) -> Option<RewriteNode> {
+) -> Option<(RewriteNode, Option<StoragePacking>)> {
     match item_ast {
         ast::ModuleItem::Struct(struct_ast) => handle_struct(db, struct_ast),
-        ast::ModuleItem::Enum(enum_ast) => handle_enum(db, enum_ast, diagnostics),
+        ast::ModuleItem::Enum(enum_ast) => Some((handle_enum(db, enum_ast, diagnostics)?, None)),
         _ => None,
     }
 }
 
 /// Derive the `Store` trait for structs annotated with `derive(starknet::Store)`.
-fn handle_struct(db: &dyn SyntaxGroup, struct_ast: &ast::ItemStruct) -> Option<RewriteNode> {
+///
+/// If the struct is annotated with `#[packed]`, its small fields are packed into shared storage
+/// slots, and the resulting layout is returned as well.
+fn handle_struct(
+    db: &dyn SyntaxGroup,
+    struct_ast: &ast::ItemStruct,
+) -> Option<(RewriteNode, Option<StoragePacking>)> {
+    let fields = struct_ast
+        .members(db)
+        .elements(db)
+        .iter()
+        .map(|field| {
+            (
+                SmolStr::from(field.name(db).as_syntax_node().get_text_without_trivia(db)),
+                SmolStr::from(
+                    field.type_clause(db).ty(db).as_syntax_node().get_text_without_trivia(db),
+                ),
+            )
+        })
+        .collect_vec();
+    let storage_packing =
+        struct_ast.has_attr(db, PACKED_ATTR).then(|| compute_storage_packing(&fields));
+    let items = match &storage_packing {
+        Some(storage_packing) => storage_packing.items.clone(),
+        None => fields.iter().map(|(name, _)| StorageItem::Field(name.clone())).collect(),
+    };
+
     let mut reads_values = Vec::new();
     let mut reads_values_at_offset = Vec::new();
     let mut reads_fields = Vec::new();
     let mut writes = Vec::new();
     let mut writes_at_offset = Vec::new();
     let mut sizes = Vec::new();
-    for (i, field) in struct_ast.members(db).elements(db).iter().enumerate() {
-        let field_name = field.name(db).as_syntax_node().get_text_without_trivia(db);
-        let field_type = field.type_clause(db).ty(db).as_syntax_node().get_text_without_trivia(db);
+    let mut unpacks = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        // The stored type, the binding of the read value and the wrapping of the read call, and the
+        // written value.
+        let (field_type, binding, (read_open, read_close), written_value) = match item {
+            StorageItem::Field(field_name) => {
+                let (_, field_type) = fields.iter().find(|(name, _)| name == field_name).unwrap();
+                (
+                    field_type.to_string(),
+                    format!("let {field_name}"),
+                    ("", ""),
+                    format!("value.{field_name}"),
+                )
+            }
+            StorageItem::PackedSlot(packed_fields) => {
+                unpacks.push(check_unused_bits(i, packed_fields));
+                unpacks.extend(packed_fields.iter().map(|field| unpack_field(i, field)));
+                (
+                    "felt252".to_string(),
+                    format!("let packed_{i}: u256"),
+                    ("core::traits::Into::<felt252, u256>::into(", ")"),
+                    packed_fields.iter().map(pack_field).join(" + "),
+                )
+            }
+        };
 
         if i == 0 {
             reads_values.push(format!(
-                "let {field_name} = {STORE_TRAIT}::<{field_type}>::read(address_domain, base)?;"
+                "{binding} = {read_open}{STORE_TRAIT}::<{field_type}>::read(address_domain, \
+                 base)?{read_close};"
             ));
             reads_values_at_offset.push(format!(
-                "let {field_name} = {STORE_TRAIT}::<{field_type}>::read_at_offset(address_domain, \
-                 base, offset)?;"
+                "{binding} = \
+                 {read_open}{STORE_TRAIT}::<{field_type}>::read_at_offset(address_domain, base, \
+                 offset)?{read_close};"
             ));
         } else {
             let subsequent_read = format!(
-                "let {field_name} = {STORE_TRAIT}::<{field_type}>::read_at_offset(address_domain, \
-                 base, current_offset)?;"
+                "{binding} = \
+                 {read_open}{STORE_TRAIT}::<{field_type}>::read_at_offset(address_domain, base, \
+                 current_offset)?{read_close};"
             );
             reads_values.push(subsequent_read.clone());
             reads_values_at_offset.push(subsequent_read);
         }
-        if i < struct_ast.members(db).elements(db).len() - 1 {
+        if i < items.len() - 1 {
             if i == 0 {
                 reads_values.push(format!(
                     "let mut current_offset = {STORE_TRAIT}::<{field_type}>::size();"
@@ -65,26 +122,24 @@ fn handle_struct(db: &dyn SyntaxGroup, struct_ast: &ast::ItemStruct) -> Option<R
             }
         }
 
-        reads_fields.push(format!("{field_name},"));
-
         if i == 0 {
             writes.push(format!(
-                "{STORE_TRAIT}::<{field_type}>::write(address_domain, base, value.{field_name})?;"
+                "{STORE_TRAIT}::<{field_type}>::write(address_domain, base, {written_value})?;"
             ));
             writes_at_offset.push(format!(
                 "{STORE_TRAIT}::<{field_type}>::write_at_offset(address_domain, base, offset, \
-                 value.{field_name})?;"
+                 {written_value})?;"
             ));
         } else {
             let subsequent_write = format!(
                 "{STORE_TRAIT}::<{field_type}>::write_at_offset(address_domain, base, \
-                 current_offset, value.{field_name})?;"
+                 current_offset, {written_value})?;"
             );
             writes.push(subsequent_write.clone());
             writes_at_offset.push(subsequent_write);
         }
 
-        if i < struct_ast.members(db).elements(db).len() - 1 {
+        if i < items.len() - 1 {
             if i == 0 {
                 writes.push(format!(
                     "let mut current_offset = {STORE_TRAIT}::<{field_type}>::size();"
@@ -101,6 +156,11 @@ fn handle_struct(db: &dyn SyntaxGroup, struct_ast: &ast::ItemStruct) -> Option<R
         }
         sizes.push(format!("{STORE_TRAIT}::<{field_type}>::size()"));
     }
+    reads_values.extend(unpacks.iter().cloned());
+    reads_values_at_offset.extend(unpacks);
+    for (field_name, _) in &fields {
+        reads_fields.push(format!("{field_name},"));
+    }
 
     let store_impl = formatdoc!(
         "
@@ -151,7 +211,52 @@ fn handle_struct(db: &dyn SyntaxGroup, struct_ast: &ast::ItemStruct) -> Option<R
         sizes = if sizes.is_empty() { "0".to_string() } else { sizes.join(" +\n        ") }
     );
 
-    Some(RewriteNode::Text(store_impl))
+    Some((RewriteNode::Text(store_impl), storage_packing))
+}
+
+/// Returns the term of the felt252 value of a packed slot contributed by `field` of `value`.
+fn pack_field(field: &PackedField) -> String {
+    let PackedField { name, ty, shift, .. } = field;
+    let value = format!("core::traits::Into::<{ty}, felt252>::into(value.{name})");
+    if *shift == 0 { value } else { format!("{value} * {}", hex_power_of_two(*shift)) }
+}
+
+/// Returns the statement returning an error if the bits of the value of the packed slot of item
+/// `item_idx` above the ones used by `packed_fields` are not all zero.
+fn check_unused_bits(item_idx: usize, packed_fields: &[PackedField]) -> String {
+    let used_bits = packed_fields.iter().map(|field| field.shift + field.bits).max().unwrap_or(0);
+    format!(
+        "if packed_{item_idx} / {}_u256 != 0_u256 {{
+            return starknet::SyscallResult::Err(array!['Invalid packed value']);
+        }}",
+        hex_power_of_two(used_bits)
+    )
+}
+
+/// Returns the statement extracting `field` from the value of the packed slot of item `item_idx`.
+fn unpack_field(item_idx: usize, field: &PackedField) -> String {
+    let PackedField { name, ty, shift, bits } = field;
+    let shifted = if *shift == 0 {
+        format!("packed_{item_idx}")
+    } else {
+        format!("(packed_{item_idx} / {}_u256)", hex_power_of_two(*shift))
+    };
+    let mask = format!("{:x}{}", (1u32 << (bits % 4)) - 1, "f".repeat(bits / 4));
+    let masked = format!("{shifted} & 0x{}_u256", mask.trim_start_matches('0'));
+    if ty == "bool" {
+        format!("let {name}: bool = ({masked}) != 0_u256;")
+    } else {
+        // The masked value has the width of the field, so the conversion cannot fail.
+        format!(
+            "let {name}: {ty} = core::option::OptionTrait::unwrap(core::traits::TryInto::<u256, \
+             {ty}>::try_into({masked}));"
+        )
+    }
+}
+
+/// Returns `2 ** exponent` as a hexadecimal literal.
+fn hex_power_of_two(exponent: usize) -> String {
+    format!("0x{:x}{}", 1u32 << (exponent % 4), "0".repeat(exponent / 4))
 }
 
 /// Derive the `starknet::Store` trait for enums annotated with `derive(starknet::Store)`.
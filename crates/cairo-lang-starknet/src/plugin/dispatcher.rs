@@ -8,6 +8,7 @@ use indoc::formatdoc;
 use itertools::Itertools;
 
 use super::consts::CALLDATA_PARAM_NAME;
+use super::interface_defaults::handle_interface_defaults;
 use super::utils::{AstPathExtract, ParamEx};
 use super::{DEPRECATED_ABI_ATTR, INTERFACE_ATTR, STORE_TRAIT};
 
@@ -69,6 +70,10 @@ pub fn handle_trait(db: &dyn SyntaxGroup, trait_ast: ast::ItemTrait) -> PluginRe
         };
     };
 
+    if let Some(result) = handle_interface_defaults(db, &trait_ast, &body, &single_generic_param) {
+        return result;
+    }
+
     let mut diagnostics = vec![];
     let mut dispatcher_signatures = vec![];
     let mut safe_dispatcher_signatures = vec![];
@@ -12,6 +12,7 @@ use super::consts::{
 };
 use super::entry_point::{
     handle_entry_point, EntryPointGenerationParams, EntryPointKind, EntryPointsGenerationData,
+    WrappedFunction,
 };
 use super::utils::{forbid_attributes_in_impl, GenericParamExtract};
 
@@ -134,7 +135,7 @@ pub fn handle_embeddable(db: &dyn SyntaxGroup, item_impl: ast::ItemImpl) -> Plug
             db,
             EntryPointGenerationParams {
                 entry_point_kind: EntryPointKind::External,
-                item_function: &item_function,
+                function: WrappedFunction::from_function_with_body(db, &item_function),
                 wrapped_function_path: function_path,
                 wrapper_identifier,
                 unsafe_new_contract_state_prefix: "UnsafeNewContractState::",
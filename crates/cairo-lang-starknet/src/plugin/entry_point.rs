@@ -65,6 +65,19 @@ impl EntryPointKind {
     }
 }
 
+/// The calldata validation performed by generated entry point wrappers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CalldataValidation {
+    /// Invalid calldata panics with a single short string describing the failure.
+    /// Generates the smallest wrappers.
+    #[default]
+    Permissive,
+    /// Invalid calldata panics with descriptive error felts: the failure reason followed by the
+    /// index of the parameter that failed to deserialize, or by the number of unconsumed calldata
+    /// felts. Costs some code size and gas in the failure paths.
+    Strict,
+}
+
 // Accumulated data for generation of contract entry points.
 #[derive(Default)]
 pub struct EntryPointsGenerationData {
@@ -72,6 +85,8 @@ pub struct EntryPointsGenerationData {
     pub external_functions: Vec<RewriteNode>,
     pub constructor_functions: Vec<RewriteNode>,
     pub l1_handler_functions: Vec<RewriteNode>,
+    /// The calldata validation of the generated wrappers.
+    pub calldata_validation: CalldataValidation,
 }
 impl EntryPointsGenerationData {
     pub fn into_rewrite_node(self) -> RewriteNode {
@@ -116,10 +131,26 @@ fn generate_submodule(module_name: &str, generated_functions_node: RewriteNode)
     )
 }
 
+/// The syntax of a function wrapped by an entry point - either a function with a body, or a
+/// default implementation of an interface function materialized into an impl.
+pub struct WrappedFunction {
+    pub declaration: ast::FunctionDeclaration,
+    pub attributes: ast::AttributeList,
+}
+impl WrappedFunction {
+    pub fn from_function_with_body(db: &dyn SyntaxGroup, function: &FunctionWithBody) -> Self {
+        Self { declaration: function.declaration(db), attributes: function.attributes(db) }
+    }
+
+    pub fn from_trait_function(db: &dyn SyntaxGroup, function: &ast::TraitItemFunction) -> Self {
+        Self { declaration: function.declaration(db), attributes: function.attributes(db) }
+    }
+}
+
 /// Parameters for generating an entry point, used when calling `handle_entry_point`.
 pub struct EntryPointGenerationParams<'a> {
     pub entry_point_kind: EntryPointKind,
-    pub item_function: &'a FunctionWithBody,
+    pub function: WrappedFunction,
     pub wrapped_function_path: RewriteNode,
     pub wrapper_identifier: String,
     pub unsafe_new_contract_state_prefix: &'a str,
@@ -131,7 +162,7 @@ pub fn handle_entry_point(
     db: &dyn SyntaxGroup,
     EntryPointGenerationParams {
         entry_point_kind,
-        item_function,
+        function,
         wrapped_function_path,
         wrapper_identifier,
         unsafe_new_contract_state_prefix,
@@ -140,7 +171,7 @@ pub fn handle_entry_point(
     diagnostics: &mut Vec<PluginDiagnostic>,
     data: &mut EntryPointsGenerationData,
 ) {
-    let declaration = item_function.declaration(db);
+    let declaration = &function.declaration;
     let name_node = declaration.name(db);
     if entry_point_kind == EntryPointKind::Constructor && name_node.text(db) != CONSTRUCTOR_NAME {
         diagnostics.push(PluginDiagnostic::error(
@@ -179,11 +210,12 @@ pub fn handle_entry_point(
     );
     match generate_entry_point_wrapper(
         db,
-        item_function,
+        &function,
         wrapped_function_path,
         wrapper_function_name.clone(),
         generic_params,
         unsafe_new_contract_state_prefix,
+        data.calldata_validation,
     ) {
         Ok(generated_function) => {
             data.generated_wrapper_functions.push(generated_function);
@@ -214,13 +246,13 @@ pub fn handle_entry_point(
 /// Generates Cairo code for an entry point wrapper.
 fn generate_entry_point_wrapper(
     db: &dyn SyntaxGroup,
-    function: &FunctionWithBody,
+    WrappedFunction { declaration, attributes }: &WrappedFunction,
     wrapped_function_path: RewriteNode,
     wrapper_function_name: RewriteNode,
     generic_params: RewriteNode,
     unsafe_new_contract_state_prefix: &str,
+    calldata_validation: CalldataValidation,
 ) -> Result<RewriteNode, Vec<PluginDiagnostic>> {
-    let declaration = function.declaration(db);
     let sig = declaration.signature(db);
     let mut params = sig.parameters(db).elements(db).into_iter().enumerate();
     let mut diagnostics = vec![];
@@ -243,7 +275,7 @@ fn generate_entry_point_wrapper(
     let is_snapshot = matches!(first_param.type_clause(db).ty(db), ast::Expr::Unary(_));
     // TODO(spapini): Check modifiers and type.
 
-    let raw_output = function.has_attr(db, RAW_OUTPUT_ATTR);
+    let raw_output = attributes.has_attr(db, RAW_OUTPUT_ATTR);
     for (param_idx, param) in params {
         let arg_name = format!("__arg_{}", param.name(db).text(db));
         let arg_type_ast = param.type_clause(db).ty(db);
@@ -259,13 +291,29 @@ fn generate_entry_point_wrapper(
         let ref_modifier = if is_ref { "ref " } else { "" };
         arg_names.push(format!("{ref_modifier}{arg_name}"));
         let mut_modifier = if is_ref { "mut " } else { "" };
-        let arg_definition = formatdoc!(
-            "
-            let {mut_modifier}{arg_name} = core::option::OptionTraitImpl::expect(
-                    core::serde::Serde::<{type_name}>::deserialize(ref data),
-                    'Failed to deserialize param #{param_idx}'
-                );"
-        );
+        let arg_definition = match calldata_validation {
+            CalldataValidation::Permissive => formatdoc!(
+                "
+                let {mut_modifier}{arg_name} = core::option::OptionTraitImpl::expect(
+                        core::serde::Serde::<{type_name}>::deserialize(ref data),
+                        'Failed to deserialize param #{param_idx}'
+                    );"
+            ),
+            CalldataValidation::Strict => formatdoc!(
+                "
+                let {mut_modifier}{arg_name} = match \
+                 core::serde::Serde::<{type_name}>::deserialize(ref data) {{
+                        core::option::Option::Some(value) => value,
+                        core::option::Option::None => {{
+                            let mut err_data = core::array::ArrayTrait::new();
+                            core::array::ArrayTrait::append(ref err_data, 'Failed to deserialize \
+                 param');
+                            core::array::ArrayTrait::append(ref err_data, {param_idx});
+                            core::panics::panic(err_data)
+                        }},
+                    }};"
+            ),
+        };
         arg_definitions.push(arg_definition);
 
         if is_ref {
@@ -333,6 +381,22 @@ fn generate_entry_point_wrapper(
     }));
 
     let arg_definitions = RewriteNode::Text(arg_definitions.join("\n    "));
+    let input_length_check = RewriteNode::Text(match calldata_validation {
+        CalldataValidation::Permissive => {
+            "assert(core::array::SpanTrait::is_empty(data), 'Input too long for arguments');"
+                .to_string()
+        }
+        CalldataValidation::Strict => indoc! {"
+            if !core::array::SpanTrait::is_empty(data) {
+                    let mut err_data = core::array::ArrayTrait::new();
+                    core::array::ArrayTrait::append(ref err_data, 'Input too long for arguments');
+                    core::array::ArrayTrait::append(
+                        ref err_data, core::traits::Into::<u32, felt252>::into(core::array::SpanTrait::len(data)),
+                    );
+                    core::panics::panic(err_data);
+                }"}
+        .to_string(),
+    });
     Ok(RewriteNode::interpolate_patched(
         &formatdoc! {"
             $implicit_precedence$
@@ -341,7 +405,7 @@ fn generate_entry_point_wrapper(
                 core::internal::revoke_ap_tracking();
                 core::option::OptionTraitImpl::expect(core::gas::withdraw_gas(), 'Out of gas');
                 $arg_definitions$
-                assert(core::array::SpanTrait::is_empty(data), 'Input too long for arguments');
+                $input_length_check$
                 core::option::OptionTraitImpl::expect(
                     core::gas::withdraw_gas_all(core::gas::get_builtin_costs()), 'Out of gas',
                 );
@@ -354,6 +418,7 @@ fn generate_entry_point_wrapper(
             ("generic_params".to_string(), generic_params),
             ("output_handling".to_string(), output_handling),
             ("arg_definitions".to_string(), arg_definitions),
+            ("input_length_check".to_string(), input_length_check),
             ("implicit_precedence".to_string(), implicit_precedence),
         ]
         .into(),
@@ -0,0 +1,187 @@
+use cairo_lang_defs::patcher::{PatchBuilder, RewriteNode};
+use cairo_lang_defs::plugin::{PluginDiagnostic, PluginGeneratedFile, PluginResult};
+use cairo_lang_syntax::node::ast::{self, MaybeTraitBody, OptionReturnTypeClause};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::BodyItems;
+use cairo_lang_syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode};
+use indoc::formatdoc;
+
+use super::consts::{CALLDATA_PARAM_NAME, EXTERNAL_SEGMENT_ATTR};
+use super::utils::ParamEx;
+
+/// The name of the variable that holds the data of the segment.
+const SEGMENT_DATA: &str = "__segment_data__";
+
+/// The maximal length of the segment and accessor names, which are passed as short strings.
+const MAX_NAME_LEN: usize = 31;
+
+/// Generates the accessors of a trait annotated with EXTERNAL_SEGMENT_ATTR.
+///
+/// Each function of the trait is an accessor of the external segment named after the trait. The
+/// generated impl serializes the name of the accessor and its arguments, and deserializes its
+/// return value from the data the runner fills the segment with, through a cheatcode whose
+/// selector is the segment name.
+pub fn handle_external_segment(db: &dyn SyntaxGroup, trait_ast: ast::ItemTrait) -> PluginResult {
+    let body = match trait_ast.body(db) {
+        MaybeTraitBody::Some(body) => body,
+        MaybeTraitBody::None(empty_body) => {
+            return PluginResult {
+                code: None,
+                diagnostics: vec![PluginDiagnostic::error(
+                    empty_body.stable_ptr().untyped(),
+                    "External segments without body are not supported.".to_string(),
+                )],
+                remove_original_item: false,
+            };
+        }
+    };
+    let mut diagnostics = vec![];
+    if let ast::OptionWrappedGenericParamList::WrappedGenericParamList(generic_params) =
+        trait_ast.generic_params(db)
+    {
+        diagnostics.push(PluginDiagnostic::error(
+            generic_params.stable_ptr().untyped(),
+            format!("`{EXTERNAL_SEGMENT_ATTR}` traits must not have generic parameters."),
+        ));
+    }
+    let segment_name = trait_ast.name(db).text(db);
+    if segment_name.len() > MAX_NAME_LEN {
+        diagnostics.push(PluginDiagnostic::error(
+            trait_ast.name(db).stable_ptr().untyped(),
+            format!("External segment names must be at most {MAX_NAME_LEN} characters long."),
+        ));
+    }
+    if !diagnostics.is_empty() {
+        return PluginResult { code: None, diagnostics, remove_original_item: false };
+    }
+
+    let mut accessor_impls = vec![];
+    for item_ast in body.items_vec(db) {
+        let func = match item_ast {
+            ast::TraitItem::Function(func) => func,
+            // Ignore the missing item.
+            ast::TraitItem::Missing(_) => continue,
+            _ => {
+                diagnostics.push(PluginDiagnostic::error(
+                    item_ast.stable_ptr().untyped(),
+                    format!("`{EXTERNAL_SEGMENT_ATTR}` traits only support function items."),
+                ));
+                continue;
+            }
+        };
+        if let ast::MaybeTraitFunctionBody::Some(body) = func.body(db) {
+            diagnostics.push(PluginDiagnostic::error(
+                body.stable_ptr().untyped(),
+                "External segment accessors must not have a default implementation.".to_string(),
+            ));
+            continue;
+        }
+        let declaration = func.declaration(db);
+        let accessor_name = declaration.name(db).text(db);
+        if accessor_name.len() > MAX_NAME_LEN {
+            diagnostics.push(PluginDiagnostic::error(
+                declaration.name(db).stable_ptr().untyped(),
+                format!("Accessor names must be at most {MAX_NAME_LEN} characters long."),
+            ));
+            continue;
+        }
+        let signature = declaration.signature(db);
+        let mut skip_generation = false;
+        let mut serialization_code = vec![];
+        for param in signature.parameters(db).elements(db) {
+            if param.is_ref_param(db) {
+                diagnostics.push(PluginDiagnostic::error(
+                    param.modifiers(db).stable_ptr().untyped(),
+                    "External segment accessors don't support `ref` parameters.".to_string(),
+                ));
+                skip_generation = true;
+            }
+            if param.name(db).text(db) == CALLDATA_PARAM_NAME {
+                diagnostics.push(PluginDiagnostic::error(
+                    param.name(db).stable_ptr().untyped(),
+                    "Parameter name `__calldata__` cannot be used.".to_string(),
+                ));
+                skip_generation = true;
+            }
+            let type_name = param.type_clause(db).ty(db).as_syntax_node().get_text(db);
+            serialization_code.push(RewriteNode::interpolate_patched(
+                &format!(
+                    "        core::serde::Serde::<{type_name}>::serialize(@$arg_name$, ref \
+                     {CALLDATA_PARAM_NAME});\n"
+                ),
+                &[(
+                    "arg_name".to_string(),
+                    RewriteNode::new_trimmed(param.name(db).as_syntax_node()),
+                )]
+                .into(),
+            ));
+        }
+        if skip_generation {
+            continue;
+        }
+        let ret_decode = match signature.ret_ty(db) {
+            OptionReturnTypeClause::Empty(_) => "()".to_string(),
+            OptionReturnTypeClause::ReturnTypeClause(ty) => {
+                let type_name = ty.ty(db).as_syntax_node().get_text(db);
+                formatdoc!(
+                    "
+                    core::option::OptionTrait::expect(
+                                core::serde::Serde::<{type_name}>::deserialize(ref {SEGMENT_DATA}),
+                                'External segment too short',
+                            )"
+                )
+            }
+        };
+        accessor_impls.push(RewriteNode::interpolate_patched(
+            &formatdoc!(
+                "
+                    $declaration$ {{
+                        let mut {CALLDATA_PARAM_NAME} = core::traits::Default::default();
+                        core::array::ArrayTrait::append(ref {CALLDATA_PARAM_NAME}, \
+                 '{accessor_name}');
+                $serialization_code$
+                        let mut {SEGMENT_DATA} = starknet::testing::cheatcode::<'{segment_name}'>(
+                            core::array::ArrayTrait::span(@{CALLDATA_PARAM_NAME}),
+                        );
+                        {ret_decode}
+                    }}
+                "
+            ),
+            &[
+                ("declaration".to_string(), RewriteNode::new_trimmed(declaration.as_syntax_node())),
+                ("serialization_code".to_string(), RewriteNode::new_modified(serialization_code)),
+            ]
+            .into(),
+        ));
+    }
+
+    let impl_name = format!("{segment_name}Impl");
+    let mut builder = PatchBuilder::new(db);
+    builder.add_modified(RewriteNode::interpolate_patched(
+        &formatdoc!(
+            "
+            $visibility$impl {impl_name} of {segment_name} {{
+            $accessor_impls$}}
+            "
+        ),
+        &[
+            ("accessor_impls".to_string(), RewriteNode::new_modified(accessor_impls)),
+            (
+                "visibility".to_string(),
+                RewriteNode::Copied(trait_ast.visibility(db).as_syntax_node()),
+            ),
+        ]
+        .into(),
+    ));
+
+    PluginResult {
+        code: Some(PluginGeneratedFile {
+            name: impl_name.into(),
+            content: builder.code,
+            code_mappings: builder.code_mappings,
+            aux_data: None,
+        }),
+        diagnostics,
+        remove_original_item: false,
+    }
+}
@@ -0,0 +1,407 @@
+use cairo_lang_defs::db::get_all_path_leaves;
+use cairo_lang_defs::patcher::{PatchBuilder, RewriteNode};
+use cairo_lang_defs::plugin::{PluginDiagnostic, PluginGeneratedFile, PluginResult};
+use cairo_lang_filesystem::cfg::CfgSet;
+use cairo_lang_plugins::plugins::HasItemsInCfgEx;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::{BodyItems, GetIdentifier, PathSegmentEx, QueryAttrs};
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedStablePtr, TypedSyntaxNode};
+use indoc::formatdoc;
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+use super::consts::{DEFAULT_IMPL_PREFIX, INTERFACE_ATTR};
+use super::utils::ParamEx;
+
+/// Handles a `starknet::interface` trait with default implementations for some of its functions.
+///
+/// The trait is regenerated without the default implementations, which are moved into generic free
+/// functions next to it. Impls of the interface in contracts call these functions for the functions
+/// they do not override (see [materialize_default_functions]).
+/// Returns `None` if the interface has no default implementations.
+pub fn handle_interface_defaults(
+    db: &dyn SyntaxGroup,
+    trait_ast: &ast::ItemTrait,
+    body: &ast::TraitBody,
+    generic_param: &str,
+) -> Option<PluginResult> {
+    let interface_name = trait_ast.name(db).text(db);
+    let mut diagnostics = vec![];
+    let mut stripped_trait = RewriteNode::from_ast(trait_ast);
+    let stripped_items = stripped_trait
+        .modify_child(db, ast::ItemTrait::INDEX_BODY)
+        .modify_child(db, ast::TraitBody::INDEX_ITEMS);
+    let mut default_functions = vec![];
+    for (idx, item) in body.items(db).elements(db).into_iter().enumerate() {
+        let ast::TraitItem::Function(function) = item else {
+            continue;
+        };
+        let ast::MaybeTraitFunctionBody::Some(function_body) = function.body(db) else {
+            continue;
+        };
+        stripped_items
+            .modify_child(db, idx)
+            .modify_child(db, ast::TraitItemFunction::INDEX_BODY)
+            .set_str(";\n".to_string());
+        let declaration = function.declaration(db);
+        if let ast::OptionWrappedGenericParamList::WrappedGenericParamList(generic_params) =
+            declaration.generic_params(db)
+        {
+            diagnostics.push(PluginDiagnostic::error(
+                generic_params.stable_ptr().untyped(),
+                "`starknet::interface` functions with generic parameters can't have a default \
+                 implementation."
+                    .to_string(),
+            ));
+            continue;
+        }
+        default_functions.push(RewriteNode::interpolate_patched(
+            &formatdoc!(
+                "
+
+                $visibility$fn {}<{generic_param}, impl __Interface: \
+                 $interface_name$<{generic_param}>, +Drop<{generic_param}>>$signature$ $body$
+                ",
+                default_function_name(&interface_name, &declaration.name(db).text(db))
+            ),
+            &[
+                (
+                    "visibility".to_string(),
+                    RewriteNode::Trimmed {
+                        node: trait_ast.visibility(db).as_syntax_node(),
+                        trim_left: true,
+                        trim_right: false,
+                    },
+                ),
+                (
+                    "interface_name".to_string(),
+                    RewriteNode::new_trimmed(trait_ast.name(db).as_syntax_node()),
+                ),
+                (
+                    "signature".to_string(),
+                    RewriteNode::new_trimmed(declaration.signature(db).as_syntax_node()),
+                ),
+                ("body".to_string(), RewriteNode::new_trimmed(function_body.as_syntax_node())),
+            ]
+            .into(),
+        ));
+    }
+    if default_functions.is_empty() && diagnostics.is_empty() {
+        return None;
+    }
+
+    let mut builder = PatchBuilder::new(db);
+    builder.add_modified(stripped_trait);
+    builder.add_modified(RewriteNode::new_modified(default_functions));
+    Some(PluginResult {
+        code: Some(PluginGeneratedFile {
+            name: "interface_defaults".into(),
+            content: builder.code,
+            code_mappings: builder.code_mappings,
+            aux_data: None,
+        }),
+        diagnostics,
+        remove_original_item: true,
+    })
+}
+
+/// Returns the name of the generated function holding the default implementation of the function
+/// `function_name` of the interface `interface_name`.
+pub fn default_function_name(interface_name: &str, function_name: &str) -> String {
+    format!("{DEFAULT_IMPL_PREFIX}{interface_name}__{function_name}")
+}
+
+/// Returns the functions of the `starknet::interface` implemented by `imp` that have a default
+/// implementation, and are not overridden by `imp`.
+///
+/// The plugin has no semantic information, so the interface is looked up in the file of `imp`: by
+/// resolving the path of the implemented trait through the modules and `use` items of the file, or
+/// if the path leads out of the file, as the single interface of the file with the name of the
+/// trait. Interfaces of other files are not found, so their default implementations are not
+/// materialized. Returns a diagnostic if the interface is ambiguous.
+pub fn missing_default_functions(
+    db: &dyn SyntaxGroup,
+    imp: &ast::ItemImpl,
+    cfg_set: &CfgSet,
+) -> Result<Vec<ast::TraitItemFunction>, PluginDiagnostic> {
+    let ast::MaybeImplBody::Some(impl_body) = imp.body(db) else {
+        return Ok(vec![]);
+    };
+    let implemented_functions: Vec<SmolStr> = impl_body
+        .iter_items_in_cfg(db, cfg_set)
+        .filter_map(|item| match item {
+            ast::ImplItem::Function(function) => Some(function.declaration(db).name(db).text(db)),
+            _ => None,
+        })
+        .collect();
+    let missing_functions = |interface: &ast::ItemTrait| {
+        let ast::MaybeTraitBody::Some(interface_body) = interface.body(db) else {
+            return vec![];
+        };
+        interface_body
+            .items_vec(db)
+            .into_iter()
+            .filter_map(|item| match item {
+                ast::TraitItem::Function(function)
+                    if matches!(function.body(db), ast::MaybeTraitFunctionBody::Some(_))
+                        && !implemented_functions
+                            .contains(&function.declaration(db).name(db).text(db)) =>
+                {
+                    Some(function)
+                }
+                _ => None,
+            })
+            .collect()
+    };
+
+    let trait_path = imp.trait_path(db);
+    let segments: Vec<SmolStr> =
+        trait_path.elements(db).iter().map(|segment| segment.identifier(db)).collect();
+    let (root, scope) = enclosing_modules(db, imp.as_syntax_node());
+    if let Some(interface) = resolve_trait(db, &root, scope, &segments, 0) {
+        return Ok(if interface.has_attr(db, INTERFACE_ATTR) {
+            missing_functions(&interface)
+        } else {
+            vec![]
+        });
+    }
+
+    let mut interfaces = vec![];
+    collect_interfaces(
+        db,
+        root.items(db).elements(db),
+        &trait_path.identifier(db),
+        &mut interfaces,
+    );
+    match interfaces.as_slice() {
+        [] => Ok(vec![]),
+        [interface] => Ok(missing_functions(interface)),
+        _ if interfaces.iter().all(|interface| missing_functions(interface).is_empty()) => {
+            Ok(vec![])
+        }
+        _ => Err(PluginDiagnostic::error(
+            trait_path.stable_ptr().untyped(),
+            format!(
+                "Ambiguous `starknet::interface` `{}`: several interfaces of this file have this \
+                 name, so the default implementations of its functions can't be used. Refer to \
+                 the interface by its path in the file.",
+                trait_path.identifier(db)
+            ),
+        )),
+    }
+}
+
+/// Collects the `starknet::interface` traits named `name` from `items` and their submodules.
+fn collect_interfaces(
+    db: &dyn SyntaxGroup,
+    items: Vec<ast::ModuleItem>,
+    name: &str,
+    interfaces: &mut Vec<ast::ItemTrait>,
+) {
+    for item in items {
+        match item {
+            ast::ModuleItem::Trait(trait_ast)
+                if trait_ast.name(db).text(db) == name
+                    && trait_ast.has_attr(db, INTERFACE_ATTR) =>
+            {
+                interfaces.push(trait_ast);
+            }
+            ast::ModuleItem::Module(module_ast) => {
+                if let ast::MaybeModuleBody::Some(body) = module_ast.body(db) {
+                    collect_interfaces(db, body.items(db).elements(db), name, interfaces);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the file containing `node`, and the chain of modules of the file enclosing `node`,
+/// outermost first.
+fn enclosing_modules(
+    db: &dyn SyntaxGroup,
+    mut node: SyntaxNode,
+) -> (ast::SyntaxFile, Vec<ast::ItemModule>) {
+    let mut modules = vec![];
+    while let Some(parent) = node.parent() {
+        if parent.kind(db) == SyntaxKind::ItemModule {
+            modules.push(ast::ItemModule::from_syntax_node(db, parent.clone()));
+        }
+        node = parent;
+    }
+    modules.reverse();
+    (ast::SyntaxFile::from_syntax_node(db, node), modules)
+}
+
+/// The maximal depth of `use` items followed when resolving a path, guarding against cycles.
+const MAX_USE_DEPTH: usize = 16;
+
+/// Resolves the path `segments` to a trait of the file `root`, from the module given by the chain
+/// of its ancestor modules `scope` (empty for the root of the file).
+///
+/// Returns `None` if the path does not lead to a trait of the file, e.g. if it refers to another
+/// crate or file.
+fn resolve_trait(
+    db: &dyn SyntaxGroup,
+    root: &ast::SyntaxFile,
+    mut scope: Vec<ast::ItemModule>,
+    segments: &[SmolStr],
+    depth: usize,
+) -> Option<ast::ItemTrait> {
+    let (first, rest) = segments.split_first()?;
+    match first.as_str() {
+        "super" => {
+            scope.pop()?;
+            return resolve_trait(db, root, scope, rest, depth);
+        }
+        "self" => return resolve_trait(db, root, scope, rest, depth),
+        _ => {}
+    }
+    let items = match scope.last() {
+        Some(module) => match module.body(db) {
+            ast::MaybeModuleBody::Some(body) => body.items(db).elements(db),
+            ast::MaybeModuleBody::None(_) => return None,
+        },
+        None => root.items(db).elements(db),
+    };
+    for item in items {
+        match item {
+            ast::ModuleItem::Trait(trait_ast)
+                if rest.is_empty() && trait_ast.name(db).text(db) == *first =>
+            {
+                return Some(trait_ast);
+            }
+            ast::ModuleItem::Module(module_ast)
+                if !rest.is_empty() && module_ast.name(db).text(db) == *first =>
+            {
+                scope.push(module_ast);
+                return resolve_trait(db, root, scope, rest, depth);
+            }
+            ast::ModuleItem::Use(use_ast) if depth < MAX_USE_DEPTH => {
+                for leaf in get_all_path_leaves(db, use_ast.use_path(db)) {
+                    let name = match leaf.alias_clause(db) {
+                        ast::OptionAliasClause::AliasClause(alias) => alias.alias(db).text(db),
+                        ast::OptionAliasClause::Empty(_) => leaf.ident(db).identifier(db),
+                    };
+                    if name != *first {
+                        continue;
+                    }
+                    let mut use_segments = use_leaf_segments(db, &leaf);
+                    use_segments.extend(rest.iter().cloned());
+                    return resolve_trait(db, root, scope, &use_segments, depth + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the segments of the full path imported by the `use` item leaf `leaf`.
+fn use_leaf_segments(db: &dyn SyntaxGroup, leaf: &ast::UsePathLeaf) -> Vec<SmolStr> {
+    let mut segments = vec![leaf.ident(db).identifier(db)];
+    let mut node = leaf.as_syntax_node();
+    while let Some(parent) = node.parent() {
+        match parent.kind(db) {
+            SyntaxKind::UsePathSingle => segments.push(
+                ast::UsePathSingle::from_syntax_node(db, parent.clone()).ident(db).identifier(db),
+            ),
+            SyntaxKind::ItemUse => break,
+            _ => {}
+        }
+        node = parent;
+    }
+    segments.reverse();
+    segments
+}
+
+/// Returns the code of the functions of `imp` materializing the default implementations of
+/// `default_functions`, by calling the functions generated for them next to the interface.
+///
+/// Returns `None` if the implemented trait is not an interface of a single state type.
+pub fn materialize_default_functions(
+    db: &dyn SyntaxGroup,
+    imp: &ast::ItemImpl,
+    default_functions: &[ast::TraitItemFunction],
+) -> Option<Vec<RewriteNode>> {
+    let trait_segment = imp.trait_path(db).elements(db).pop()?;
+    let generic_args = trait_segment.generic_args(db)?;
+    let [ast::GenericArg::Unnamed(state_arg)] = generic_args.as_slice() else {
+        return None;
+    };
+    let state = RewriteNode::new_trimmed(state_arg.as_syntax_node());
+    let first_function = default_functions.first()?;
+    // The interface is in the file of `imp`, so the generated functions are referred to by a path
+    // relative to the module of `imp`.
+    let (_, impl_modules) = enclosing_modules(db, imp.as_syntax_node());
+    let (_, interface_modules) = enclosing_modules(db, first_function.as_syntax_node());
+    let impl_module_names: Vec<_> =
+        impl_modules.iter().map(|module| module.name(db).text(db)).collect();
+    let interface_module_names: Vec<_> =
+        interface_modules.iter().map(|module| module.name(db).text(db)).collect();
+    let common_len = impl_module_names
+        .iter()
+        .zip(&interface_module_names)
+        .take_while(|(impl_module, interface_module)| impl_module == interface_module)
+        .count();
+    let path_prefix = RewriteNode::text(&format!(
+        "{}{}",
+        "super::".repeat(impl_module_names.len() - common_len),
+        interface_module_names[common_len..].iter().map(|name| format!("{name}::")).join("")
+    ));
+    let mut interface_node = first_function.as_syntax_node();
+    while interface_node.kind(db) != SyntaxKind::ItemTrait {
+        interface_node = interface_node.parent()?;
+    }
+    let interface_name = ast::ItemTrait::from_syntax_node(db, interface_node).name(db).text(db);
+    Some(
+        default_functions
+            .iter()
+            .map(|function| {
+                let declaration = function.declaration(db);
+                let params = declaration.signature(db).parameters(db).elements(db);
+                let mut materialized_declaration =
+                    RewriteNode::new_trimmed(declaration.as_syntax_node());
+                let mut args = vec![];
+                if let Some(self_param) = params.first() {
+                    let self_ty = if self_param.is_ref_param(db) {
+                        args.push("ref self".to_string());
+                        state.clone()
+                    } else {
+                        args.push("self".to_string());
+                        RewriteNode::interpolate_patched(
+                            "@$state$",
+                            &[("state".to_string(), state.clone())].into(),
+                        )
+                    };
+                    *materialized_declaration
+                        .modify_child(db, ast::FunctionDeclaration::INDEX_SIGNATURE)
+                        .modify_child(db, ast::FunctionSignature::INDEX_PARAMETERS)
+                        .modify_child(db, 0)
+                        .modify_child(db, ast::Param::INDEX_TYPE_CLAUSE)
+                        .modify_child(db, ast::TypeClause::INDEX_TY) = self_ty;
+                }
+                args.extend(params.iter().skip(1).map(|param| param.name(db).text(db).to_string()));
+                RewriteNode::interpolate_patched(
+                    &format!(
+                        "    $declaration$ {{\n        $path_prefix${}::<$state$, \
+                         $impl_name$>({})\n    }}\n",
+                        default_function_name(&interface_name, &declaration.name(db).text(db)),
+                        args.join(", ")
+                    ),
+                    &[
+                        ("declaration".to_string(), materialized_declaration),
+                        ("path_prefix".to_string(), path_prefix.clone()),
+                        ("state".to_string(), state.clone()),
+                        (
+                            "impl_name".to_string(),
+                            RewriteNode::new_trimmed(imp.name(db).as_syntax_node()),
+                        ),
+                    ]
+                    .into(),
+                )
+            })
+            .collect(),
+    )
+}
@@ -15,14 +15,18 @@ mod dispatcher;
 mod embeddable;
 mod entry_point;
 pub mod events;
+mod external_segment;
+mod interface_defaults;
 mod starknet_module;
 mod storage;
+pub mod storage_packing;
 mod utils;
 
 use dispatcher::handle_trait;
 
 use self::derive::{derive_needed, handle_derive};
 use self::embeddable::handle_embeddable;
+use self::external_segment::handle_external_segment;
 use self::starknet_module::{handle_module, handle_module_by_storage};
 
 #[derive(Debug, Default)]
@@ -38,10 +42,16 @@ impl MacroPlugin for StarkNetPlugin {
     ) -> PluginResult {
         match item_ast {
             ast::ModuleItem::Module(module_ast) => handle_module(db, module_ast),
+            ast::ModuleItem::Trait(trait_ast) if trait_ast.has_attr(db, EXTERNAL_SEGMENT_ATTR) => {
+                handle_external_segment(db, trait_ast)
+            }
             ast::ModuleItem::Trait(trait_ast) => handle_trait(db, trait_ast),
             ast::ModuleItem::Impl(impl_ast) if impl_ast.has_attr(db, EMBEDDABLE_ATTR) => {
                 handle_embeddable(db, impl_ast)
             }
+            ast::ModuleItem::Impl(impl_ast) => {
+                starknet_module::contract::handle_contract_impl_defaults(db, &impl_ast, metadata)
+            }
             ast::ModuleItem::Struct(struct_ast) if struct_ast.has_attr(db, STORAGE_ATTR) => {
                 handle_module_by_storage(db, struct_ast, metadata).unwrap_or_default()
             }
@@ -72,11 +82,13 @@ impl MacroPlugin for StarkNetPlugin {
             EMBEDDABLE_ATTR.to_string(),
             EVENT_ATTR.to_string(),
             EXTERNAL_ATTR.to_string(),
+            EXTERNAL_SEGMENT_ATTR.to_string(),
             FLAT_ATTR.to_string(),
             INTERFACE_ATTR.to_string(),
             KEY_ATTR.to_string(),
             L1_HANDLER_ATTR.to_string(),
             NESTED_ATTR.to_string(),
+            PACKED_ATTR.to_string(),
             RAW_OUTPUT_ATTR.to_string(),
             STORAGE_ATTR.to_string(),
             SUBSTORAGE_ATTR.to_string(),
@@ -35,6 +35,9 @@ impl ComponentSpecificGenerationData {
             use starknet::storage::{
                 StorageMapMemberAddressTrait, StorageMemberAddressTrait,
                 StorageMapMemberAccessTrait, StorageMemberAccessTrait,
+                StorageMapMemberPathTrait, StorageMemberPathTrait,
+                StoragePathTrait, StorageMapPathTrait,
+                ReadOnlyStoragePathTrait, ReadOnlyStorageMapPathTrait,
             };
             $has_component_trait$
 
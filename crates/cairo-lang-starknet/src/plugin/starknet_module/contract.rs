@@ -1,7 +1,10 @@
-use cairo_lang_defs::patcher::RewriteNode;
-use cairo_lang_defs::plugin::{MacroPluginMetadata, PluginDiagnostic, PluginResult};
+use cairo_lang_defs::patcher::{PatchBuilder, RewriteNode};
+use cairo_lang_defs::plugin::{
+    MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
+};
 use cairo_lang_plugins::plugins::HasItemsInCfgEx;
 use cairo_lang_starknet_classes::keccak::starknet_keccak;
+use cairo_lang_syntax::attribute::structured::AttributeStructurize;
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::{
     is_single_arg_attr, GetIdentifier, PathSegmentEx, QueryAttrs,
@@ -15,12 +18,15 @@ use super::generation_data::{ContractGenerationData, StarknetModuleCommonGenerat
 use super::{grand_grand_parent_starknet_module, StarknetModuleKind};
 use crate::plugin::consts::{
     ABI_ATTR, ABI_ATTR_EMBED_V0_ARG, ABI_ATTR_PER_ITEM_ARG, COMPONENT_INLINE_MACRO,
-    CONCRETE_COMPONENT_STATE_NAME, CONTRACT_STATE_NAME, EVENT_TRAIT, EVENT_TYPE_NAME,
-    EXTERNAL_ATTR, HAS_COMPONENT_TRAIT, STORAGE_STRUCT_NAME, SUBSTORAGE_ATTR,
+    CONCRETE_COMPONENT_STATE_NAME, CONTRACT_ATTR, CONTRACT_ATTR_STRICT_CALLDATA_ARG,
+    CONTRACT_STATE_NAME, EVENT_TRAIT, EVENT_TYPE_NAME, EXTERNAL_ATTR, HAS_COMPONENT_TRAIT,
+    STORAGE_STRUCT_NAME, SUBSTORAGE_ATTR,
 };
 use crate::plugin::entry_point::{
-    handle_entry_point, EntryPointGenerationParams, EntryPointKind, EntryPointsGenerationData,
+    handle_entry_point, CalldataValidation, EntryPointGenerationParams, EntryPointKind,
+    EntryPointsGenerationData, WrappedFunction,
 };
+use crate::plugin::interface_defaults::{materialize_default_functions, missing_default_functions};
 use crate::plugin::storage::handle_storage_struct;
 use crate::plugin::utils::{forbid_attributes_in_impl, has_v0_attribute_ex};
 
@@ -206,7 +212,10 @@ impl ContractSpecificGenerationData {
             &formatdoc! {"
                 use starknet::storage::{{
                     StorageMapMemberAddressTrait, StorageMemberAddressTrait,
-                    StorageMapMemberAccessTrait, StorageMemberAccessTrait
+                    StorageMapMemberAccessTrait, StorageMemberAccessTrait,
+                    StorageMapMemberPathTrait, StorageMemberPathTrait,
+                    StoragePathTrait, StorageMapPathTrait,
+                    ReadOnlyStoragePathTrait, ReadOnlyStorageMapPathTrait
                 }};
                 $test_config$
                 $entry_points_code$
@@ -314,6 +323,13 @@ pub(super) fn generate_contract_specific_code(
     event_variants: Vec<SmolStr>,
 ) -> RewriteNode {
     let mut generation_data = ContractGenerationData { common: common_data, ..Default::default() };
+    if module_ast
+        .query_attr(db, CONTRACT_ATTR)
+        .into_iter()
+        .any(|attr| attr.structurize(db).has_unnamed_arg(db, CONTRACT_ATTR_STRICT_CALLDATA_ARG))
+    {
+        generation_data.specific.entry_points_code.calldata_validation = CalldataValidation::Strict;
+    }
     generation_data.specific.components_data.nested_event_variants = event_variants;
     for item in body.iter_items_in_cfg(db, metadata.cfg_set) {
         handle_contract_item(db, diagnostics, &item, metadata, &mut generation_data);
@@ -336,7 +352,7 @@ pub(super) fn generate_contract_specific_code(
 /// Handles a contract entrypoint function.
 fn handle_contract_entry_point(
     entry_point_kind: EntryPointKind,
-    item_function: &ast::FunctionWithBody,
+    function: WrappedFunction,
     wrapped_function_path: RewriteNode,
     wrapper_identifier: String,
     db: &dyn SyntaxGroup,
@@ -347,7 +363,7 @@ fn handle_contract_entry_point(
         db,
         EntryPointGenerationParams {
             entry_point_kind,
-            item_function,
+            function,
             wrapped_function_path,
             wrapper_identifier,
             unsafe_new_contract_state_prefix: "",
@@ -374,7 +390,7 @@ fn handle_contract_free_function(
     let function_name_node = RewriteNode::new_trimmed(function_name.as_syntax_node());
     handle_contract_entry_point(
         entry_point_kind,
-        item_function,
+        WrappedFunction::from_function_with_body(db, item_function),
         function_name_node,
         function_name.text(db).into(),
         db,
@@ -398,8 +414,6 @@ fn handle_contract_impl(
     let ast::MaybeImplBody::Some(impl_body) = imp.body(db) else {
         return;
     };
-    let impl_name = imp.name(db);
-    let impl_name_node = RewriteNode::new_trimmed(impl_name.as_syntax_node());
     for item in impl_body.iter_items_in_cfg(db, metadata.cfg_set) {
         if abi_config == ImplAbiConfig::Embed {
             forbid_attributes_in_impl(db, diagnostics, &item, "#[abi(embed_v0)]");
@@ -421,21 +435,26 @@ fn handle_contract_impl(
             // matches!(abi_config, ImplAbiConfig::Embed | ImplAbiConfig::External)
             EntryPointKind::External
         };
-        let function_name = item_function.declaration(db).name(db);
-        let function_name_node = RewriteNode::interpolate_patched(
-            "$impl_name$::$func_name$",
-            &[
-                ("impl_name".to_string(), impl_name_node.clone()),
-                ("func_name".to_string(), RewriteNode::new_trimmed(function_name.as_syntax_node())),
-            ]
-            .into(),
-        );
-        let wrapper_identifier = format!("{}__{}", impl_name.text(db), function_name.text(db));
-        handle_contract_entry_point(
+        handle_contract_impl_entry_point(
             entry_point_kind,
-            &item_function,
-            function_name_node,
-            wrapper_identifier,
+            imp,
+            WrappedFunction::from_function_with_body(db, &item_function),
+            db,
+            diagnostics,
+            data,
+        );
+    }
+    if abi_config == ImplAbiConfig::PerItem {
+        return;
+    }
+    // The functions materialized from default implementations of the interface are embedded as
+    // well, see `handle_contract_impl_defaults`.
+    // An ambiguous interface is reported by `handle_contract_impl_defaults`.
+    for function in missing_default_functions(db, imp, metadata.cfg_set).unwrap_or_default() {
+        handle_contract_impl_entry_point(
+            EntryPointKind::External,
+            imp,
+            WrappedFunction::from_trait_function(db, &function),
             db,
             diagnostics,
             data,
@@ -443,6 +462,37 @@ fn handle_contract_impl(
     }
 }
 
+/// Handles an entry point function of an impl inside a contract module.
+fn handle_contract_impl_entry_point(
+    entry_point_kind: EntryPointKind,
+    imp: &ast::ItemImpl,
+    function: WrappedFunction,
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    data: &mut EntryPointsGenerationData,
+) {
+    let impl_name = imp.name(db);
+    let function_name = function.declaration.name(db);
+    let function_name_node = RewriteNode::interpolate_patched(
+        "$impl_name$::$func_name$",
+        &[
+            ("impl_name".to_string(), RewriteNode::new_trimmed(impl_name.as_syntax_node())),
+            ("func_name".to_string(), RewriteNode::new_trimmed(function_name.as_syntax_node())),
+        ]
+        .into(),
+    );
+    let wrapper_identifier = format!("{}__{}", impl_name.text(db), function_name.text(db));
+    handle_contract_entry_point(
+        entry_point_kind,
+        function,
+        function_name_node,
+        wrapper_identifier,
+        db,
+        diagnostics,
+        data,
+    );
+}
+
 /// The configuration of an impl addition to the abi.
 #[derive(PartialEq, Eq)]
 enum ImplAbiConfig {
@@ -618,6 +668,59 @@ pub fn remove_component_inline_macro(
     PluginResult { code: None, diagnostics: vec![], remove_original_item: false }
 }
 
+/// Handles an impl inside a contract module, materializing the default implementations of the
+/// functions of the implemented `starknet::interface` that the impl does not override.
+pub fn handle_contract_impl_defaults(
+    db: &dyn SyntaxGroup,
+    imp: &ast::ItemImpl,
+    metadata: &MacroPluginMetadata<'_>,
+) -> PluginResult {
+    if !matches!(
+        grand_grand_parent_starknet_module(imp.as_syntax_node(), db),
+        Some((_, StarknetModuleKind::Contract))
+    ) {
+        return PluginResult::default();
+    }
+    let default_functions = match missing_default_functions(db, imp, metadata.cfg_set) {
+        Ok(default_functions) => default_functions,
+        Err(diagnostic) => {
+            return PluginResult {
+                code: None,
+                diagnostics: vec![diagnostic],
+                remove_original_item: false,
+            };
+        }
+    };
+    if default_functions.is_empty() {
+        return PluginResult::default();
+    }
+    let Some(materialized_functions) = materialize_default_functions(db, imp, &default_functions)
+    else {
+        return PluginResult::default();
+    };
+    let mut impl_node = RewriteNode::from_ast(imp);
+    impl_node
+        .modify_child(db, ast::ItemImpl::INDEX_BODY)
+        .modify_child(db, ast::ImplBody::INDEX_ITEMS)
+        .modify(db)
+        .children
+        .as_mut()
+        .unwrap()
+        .extend(materialized_functions);
+    let mut builder = PatchBuilder::new(db);
+    builder.add_modified(impl_node);
+    PluginResult {
+        code: Some(PluginGeneratedFile {
+            name: "interface_defaults".into(),
+            content: builder.code,
+            code_mappings: builder.code_mappings,
+            aux_data: None,
+        }),
+        diagnostics: vec![],
+        remove_original_item: true,
+    }
+}
+
 /// Checks whether the first generic argument in the path segment is `CONTRACT_STATE_NAME`.
 fn is_first_generic_arg_contract_state(
     db: &dyn SyntaxGroup,
@@ -373,6 +373,10 @@ fn handle_simple_storage_member(address: &str, starknet_module_kind: StarknetMod
         pub struct {member_state_name} {{}}
         pub trait Internal{member_state_name}Trait {{
             fn address(self: @{member_state_name}) -> starknet::storage_access::StorageBaseAddress;
+            fn path(self: @{member_state_name}) -> \
+                 starknet::storage::ReadOnlyStoragePath<$type_path$>;
+            fn path_mut(ref self: {member_state_name}) -> \
+                 starknet::storage::StoragePath<$type_path$>;
             fn read(self: @{member_state_name}) -> $type_path$;
             fn write(ref self: {member_state_name}, value: $type_path$);
         }}
@@ -382,6 +386,17 @@ fn handle_simple_storage_member(address: &str, starknet_module_kind: StarknetMod
                  {{
                 starknet::storage_access::storage_base_address_const::<{address}>()
             }}
+            fn path(self: @{member_state_name}) -> \
+                 starknet::storage::ReadOnlyStoragePath<$type_path$> {{
+                starknet::storage::ReadOnlyStoragePathTrait::new(
+                    Internal{member_state_name}Impl::address(self)
+                )
+            }}
+            fn path_mut(ref self: {member_state_name}) -> \
+                 starknet::storage::StoragePath<$type_path$> {{
+                let mut member = Internal{member_state_name}Address {{}};
+                starknet::storage::StorageMemberPathTrait::path_mut(ref member)
+            }}
             fn read(self: @{member_state_name}) -> $type_path$ {{
                 // Only address_domain 0 is currently supported.
                 let address_domain = 0_u32;
@@ -404,6 +419,19 @@ fn handle_simple_storage_member(address: &str, starknet_module_kind: StarknetMod
                 )
             }}
         }}
+
+        // Implements the generic storage traits for a private type rather than for the member
+        // state, as these would clash with `Internal{member_state_name}Trait`.
+        #[derive(Copy, Drop)]
+        struct Internal{member_state_name}Address {{}}
+        impl Internal{member_state_name}AddressImpl of \
+                 starknet::storage::StorageMemberAddressTrait<Internal{member_state_name}Address, \
+                 $type_path$> {{
+            fn address(self: @Internal{member_state_name}Address) -> \
+                 starknet::storage_access::StorageBaseAddress nopanic {{
+                starknet::storage_access::storage_base_address_const::<{address}>()
+            }}
+        }}
     }}"
             )
         }
@@ -444,6 +472,10 @@ fn handle_legacy_mapping_storage_member(
         pub trait Internal{member_state_name}Trait {{
             fn address(self: @{member_state_name}, key: $key_type$) -> \
                  starknet::storage_access::StorageBaseAddress;
+            fn path(self: @{member_state_name}) -> \
+                 starknet::storage::ReadOnlyStorageMapPath<$key_type$, $value_type$>;
+            fn path_mut(ref self: {member_state_name}) -> \
+                 starknet::storage::StorageMapPath<$key_type$, $value_type$>;
             fn read(self: @{member_state_name}, key: $key_type$) -> $value_type$;
             fn write(ref self: {member_state_name}, key: $key_type$, value: $value_type$);
         }}
@@ -454,6 +486,15 @@ fn handle_legacy_mapping_storage_member(
                 starknet::storage_access::storage_base_address_from_felt252(
                     core::hash::LegacyHash::<$key_type$>::hash({address}, key))
             }}
+            fn path(self: @{member_state_name}) -> \
+                 starknet::storage::ReadOnlyStorageMapPath<$key_type$, $value_type$> {{
+                starknet::storage::ReadOnlyStorageMapPathTrait::new({address})
+            }}
+            fn path_mut(ref self: {member_state_name}) -> \
+                 starknet::storage::StorageMapPath<$key_type$, $value_type$> {{
+                let mut member = Internal{member_state_name}Base {{}};
+                starknet::storage::StorageMapMemberPathTrait::path_mut(ref member)
+            }}
             fn read(self: @{member_state_name}, key: $key_type$) -> $value_type$ {{
                 // Only address_domain 0 is currently supported.
                 let address_domain = 0_u32;
@@ -476,6 +517,18 @@ fn handle_legacy_mapping_storage_member(
                 )
             }}
         }}
+
+        // Implements the generic storage traits for a private type rather than for the member
+        // state, as these would clash with `Internal{member_state_name}Trait`.
+        #[derive(Copy, Drop)]
+        struct Internal{member_state_name}Base {{}}
+        impl Internal{member_state_name}BaseImpl of \
+                 starknet::storage::StorageMapMemberBaseTrait<Internal{member_state_name}Base, \
+                 $key_type$, $value_type$> {{
+            fn base(self: @Internal{member_state_name}Base) -> felt252 {{
+                {address}
+            }}
+        }}
     }}"
             )
         }
@@ -494,6 +547,13 @@ fn handle_legacy_mapping_storage_member(
                     core::hash::LegacyHash::<$key_type$>::hash({address}, key))
             }}
         }}
+        impl StorageMapBase{member_state_name}Impl of \
+             starknet::storage::StorageMapMemberBaseTrait<{member_state_name}, $key_type$, \
+             $value_type$> {{
+            fn base(self: @{member_state_name}) -> felt252 {{
+                {address}
+            }}
+        }}
     }}"
         ),
     }
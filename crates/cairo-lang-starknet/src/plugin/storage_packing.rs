@@ -0,0 +1,105 @@
+use cairo_lang_starknet_classes::abi::{PackedStructMember, StorageLayoutItem};
+use smol_str::SmolStr;
+
+#[cfg(test)]
+#[path = "storage_packing_test.rs"]
+mod test;
+
+/// The number of bits that can be stored in a single storage slot without overflowing a felt252.
+pub const STORAGE_SLOT_BITS: usize = 251;
+
+/// The storage layout of a struct annotated with `#[packed]`, as generated by
+/// `#[derive(starknet::Store)]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoragePacking {
+    /// The items stored for the struct, in storage order.
+    pub items: Vec<StorageItem>,
+}
+
+impl StoragePacking {
+    /// Returns the storage layout as described in the ABI of the struct.
+    pub fn abi_layout(&self) -> Vec<StorageLayoutItem> {
+        self.items
+            .iter()
+            .map(|item| match item {
+                StorageItem::Field(name) => StorageLayoutItem::Member { name: name.to_string() },
+                StorageItem::PackedSlot(fields) => StorageLayoutItem::PackedSlot {
+                    members: fields
+                        .iter()
+                        .map(|field| PackedStructMember {
+                            name: field.name.to_string(),
+                            shift: field.shift,
+                            bits: field.bits,
+                        })
+                        .collect(),
+                },
+            })
+            .collect()
+    }
+}
+
+/// An item of the storage layout of a packed struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageItem {
+    /// A field stored as is, using its own `Store` implementation.
+    Field(SmolStr),
+    /// A single storage slot holding multiple small fields.
+    PackedSlot(Vec<PackedField>),
+}
+
+/// A field packed into a shared storage slot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackedField {
+    pub name: SmolStr,
+    /// The type of the field, as written in the struct.
+    pub ty: SmolStr,
+    /// The index of the lowest bit of the field in the slot.
+    pub shift: usize,
+    /// The number of bits of the field.
+    pub bits: usize,
+}
+
+/// The names of the core types which can be packed, along with the number of bits of their values.
+pub const PACKABLE_TYPES: [(&str, usize); 6] =
+    [("bool", 1), ("u8", 8), ("u16", 16), ("u32", 32), ("u64", 64), ("u128", 128)];
+
+/// Returns the number of bits required for a value of the type written as `ty`, if it can be
+/// packed.
+///
+/// The type is recognized by its name, as the plugin has no semantic information. The ABI analyzer
+/// verifies that the members are packed according to their resolved types.
+pub fn packed_bits(ty: &str) -> Option<usize> {
+    PACKABLE_TYPES.iter().find(|(name, _)| *name == ty).map(|(_, bits)| *bits)
+}
+
+/// Computes the storage layout of a packed struct with the given fields, given by their names and
+/// types.
+///
+/// Each packable field is added to the last opened slot if it fits, and otherwise opens a new slot
+/// at its position. Other fields are stored as is.
+pub fn compute_storage_packing(fields: &[(SmolStr, SmolStr)]) -> StoragePacking {
+    let mut items = vec![];
+    // The index in `items` of the last opened slot and the number of bits it uses.
+    let mut open_slot: Option<(usize, usize)> = None;
+    for (name, ty) in fields {
+        let Some(bits) = packed_bits(ty) else {
+            items.push(StorageItem::Field(name.clone()));
+            continue;
+        };
+        let (slot_idx, used_bits) = match open_slot {
+            Some((slot_idx, used_bits)) if used_bits + bits <= STORAGE_SLOT_BITS => {
+                (slot_idx, used_bits)
+            }
+            _ => {
+                items.push(StorageItem::PackedSlot(vec![]));
+                (items.len() - 1, 0)
+            }
+        };
+        let StorageItem::PackedSlot(slot) = &mut items[slot_idx] else {
+            unreachable!("Expected a packed slot.");
+        };
+        slot.push(PackedField { name: name.clone(), ty: ty.clone(), shift: used_bits, bits });
+        open_slot = Some((slot_idx, used_bits + bits));
+    }
+    StoragePacking { items }
+}
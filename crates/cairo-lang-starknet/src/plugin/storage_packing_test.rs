@@ -0,0 +1,41 @@
+use pretty_assertions::assert_eq;
+use smol_str::SmolStr;
+
+use super::{compute_storage_packing, PackedField, StorageItem};
+
+/// Returns a packed field with the given name, type, shift and number of bits.
+fn packed(name: &str, ty: &str, shift: usize, bits: usize) -> PackedField {
+    PackedField { name: name.into(), ty: ty.into(), shift, bits }
+}
+
+#[test]
+fn test_compute_storage_packing() {
+    let fields: Vec<(SmolStr, SmolStr)> = [
+        ("enabled", "bool"),
+        ("fee", "u16"),
+        ("owner", "ContractAddress"),
+        ("limit", "u128"),
+        ("period", "u64"),
+        ("threshold", "u128"),
+        ("amount", "u256"),
+    ]
+    .into_iter()
+    .map(|(name, ty)| (name.into(), ty.into()))
+    .collect();
+
+    assert_eq!(
+        compute_storage_packing(&fields).items,
+        vec![
+            StorageItem::PackedSlot(vec![
+                packed("enabled", "bool", 0, 1),
+                packed("fee", "u16", 1, 16),
+                packed("limit", "u128", 17, 128),
+                packed("period", "u64", 145, 64),
+            ]),
+            StorageItem::Field("owner".into()),
+            // The field does not fit in the first slot, so it opens a new one.
+            StorageItem::PackedSlot(vec![packed("threshold", "u128", 0, 128)]),
+            StorageItem::Field("amount".into()),
+        ]
+    );
+}
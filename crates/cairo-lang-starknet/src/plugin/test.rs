@@ -73,6 +73,7 @@ cairo_lang_test_utils::test_file_test_with_runner!(
         events: "events",
         embedded_impl: "embedded_impl",
         raw_output: "raw_output",
+        strict_calldata: "strict_calldata",
         storage: "storage",
         dispatcher: "dispatcher",
         user_defined_types: "user_defined_types",
@@ -82,6 +83,8 @@ cairo_lang_test_utils::test_file_test_with_runner!(
         with_component: "with_component",
         with_component_diagnostics: "with_component_diagnostics",
         interfaces: "interfaces",
+        interface_defaults: "interface_defaults",
+        external_segment: "external_segment",
     },
     ExpandContractTestRunner
 );
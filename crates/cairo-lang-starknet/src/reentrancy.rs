@@ -0,0 +1,387 @@
+use cairo_lang_defs::ids::{
+    FunctionWithBodyId, LanguageElementId, ModuleId, TopLevelLanguageElementId,
+};
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_diagnostics::{error_code, ErrorCode, ErrorCodeRegistry};
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
+use cairo_lang_semantic::items::function_with_body::FunctionBody;
+use cairo_lang_semantic::items::functions::{ConcreteFunctionWithBodyId, GenericFunctionId};
+use cairo_lang_semantic::plugin::AnalyzerPlugin;
+use cairo_lang_semantic::substitution::{
+    GenericSubstitution, SemanticRewriter, SubstitutionRewriter,
+};
+use cairo_lang_semantic::{
+    Condition, Expr, ExprFunctionCall, ExprFunctionCallArg, ExprId, FixedSizeArrayItems, Statement,
+};
+use cairo_lang_syntax::attribute::consts::ALLOW_ATTR;
+use cairo_lang_syntax::attribute::structured::{Attribute, AttributeStructurize};
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::TypedStablePtr;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+
+use crate::contract::module_contract;
+use crate::plugin::consts::{ABI_ATTR, ABI_ATTR_EMBED_V0_ARG, EXTERNAL_ATTR, L1_HANDLER_ATTR};
+
+#[cfg(test)]
+#[path = "reentrancy_test.rs"]
+mod test;
+
+/// The lint of storage writes following external calls, which can be allowed with
+/// `#[allow(reentrancy)]`.
+pub const REENTRANCY_LINT: &str = "reentrancy";
+
+/// The error code of storage writes following external calls.
+const REENTRANCY_ERROR_CODE: ErrorCode = error_code!(E2001);
+
+/// The documentation of [REENTRANCY_ERROR_CODE].
+const REENTRANCY_ERROR_DOC: &str =
+    "A contract entry point writes to storage after making an external call.\n\nThe called \
+     contract may call back into the contract before the write happens, while its storage is \
+     still in the state before the call. Perform all the storage writes before the external calls \
+     (the checks-effects-interactions pattern). If the pattern is known to be safe, e.g. the \
+     called contract is trusted, allow the lint with `#[allow(reentrancy)]` on the statement, on \
+     the entry point or the function making the call or the write, or on an enclosing impl or \
+     module.";
+
+/// The extern function making external calls.
+const CALL_CONTRACT_SYSCALL: &str = "core::starknet::syscalls::call_contract_syscall";
+/// The extern function writing to storage.
+const STORAGE_WRITE_SYSCALL: &str = "core::starknet::syscalls::storage_write_syscall";
+
+/// Plugin to add diagnostics for storage writes following external calls in the entry points of
+/// contracts, through which the called contracts may re-enter the contract in an outdated state.
+///
+/// The analysis is opt-in, see [crate::reentrancy_analysis_plugin_suite]. Statements and functions
+/// where the [REENTRANCY_LINT] lint is allowed, directly or by an enclosing impl or module, are
+/// considered as having no effects, and entry points where it is allowed are not analyzed.
+#[derive(Default, Debug)]
+pub struct ReentrancyAnalyzer;
+
+impl AnalyzerPlugin for ReentrancyAnalyzer {
+    fn diagnostics(&self, db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<PluginDiagnostic> {
+        let Some(contract) = module_contract(db, module_id) else {
+            return vec![];
+        };
+        let mut analysis = EffectsAnalysis { db, functions_effects: Default::default() };
+        let mut diagnostics = vec![];
+        for function_id in contract_entry_points(db, contract.module_id()) {
+            if is_reentrancy_allowed(db, function_id) {
+                continue;
+            }
+            let Ok(function_id) = ConcreteFunctionWithBodyId::from_generic(db, function_id) else {
+                continue;
+            };
+            let Some(walker) = analysis.walk_function(function_id) else {
+                continue;
+            };
+            for stable_ptr in walker.writes_after_external_calls {
+                diagnostics.push(
+                    PluginDiagnostic::warning(
+                        stable_ptr,
+                        "Potential re-entrancy: Storage is written after an external call."
+                            .to_string(),
+                    )
+                    .with_error_code(REENTRANCY_ERROR_CODE),
+                );
+            }
+        }
+        diagnostics
+    }
+
+    fn register_error_codes(&self, registry: &mut ErrorCodeRegistry) {
+        registry.register(REENTRANCY_ERROR_CODE, REENTRANCY_ERROR_DOC);
+    }
+}
+
+/// Returns the entry points of the contract in the given module that can be called by other
+/// contracts.
+fn contract_entry_points(db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<FunctionWithBodyId> {
+    let is_entry_point = |function_id: FunctionWithBodyId| {
+        function_id.has_attr(db, EXTERNAL_ATTR).unwrap_or_default()
+            || function_id.has_attr(db, L1_HANDLER_ATTR).unwrap_or_default()
+    };
+    let mut entry_points = vec![];
+    for free_function_id in db.module_free_functions_ids(module_id).unwrap_or_default().iter() {
+        let function_id = FunctionWithBodyId::Free(*free_function_id);
+        if is_entry_point(function_id) {
+            entry_points.push(function_id);
+        }
+    }
+    for impl_def_id in db.module_impls_ids(module_id).unwrap_or_default().iter() {
+        // TODO(v3): Remove the check of the deprecated external attribute.
+        let is_embedded = impl_def_id.has_attr(db, EXTERNAL_ATTR).unwrap_or_default()
+            || impl_def_id
+                .has_attr_with_arg(db, ABI_ATTR, ABI_ATTR_EMBED_V0_ARG)
+                .unwrap_or_default();
+        for impl_function_id in db.impl_functions(*impl_def_id).unwrap_or_default().values() {
+            let function_id = FunctionWithBodyId::Impl(*impl_function_id);
+            if is_embedded || is_entry_point(function_id) {
+                entry_points.push(function_id);
+            }
+        }
+    }
+    entry_points
+}
+
+/// Returns whether the [REENTRANCY_LINT] lint is allowed in the function, by an `#[allow(...)]`
+/// attribute on it, on its impl or on an enclosing module.
+fn is_reentrancy_allowed(db: &dyn SemanticGroup, function_id: FunctionWithBodyId) -> bool {
+    let allows = |attrs: Vec<Attribute>| {
+        attrs.iter().any(|attr| attr.has_unnamed_arg(db.upcast(), REENTRANCY_LINT))
+    };
+    if allows(function_id.query_attr(db, ALLOW_ATTR).unwrap_or_default()) {
+        return true;
+    }
+    if let FunctionWithBodyId::Impl(impl_function_id) = function_id {
+        let impl_def_id = impl_function_id.impl_def_id(db.upcast());
+        if allows(impl_def_id.query_attr(db, ALLOW_ATTR).unwrap_or_default()) {
+            return true;
+        }
+    }
+    let mut module_id = function_id.parent_module(db.upcast());
+    loop {
+        if allows(module_id.query_attr(db, ALLOW_ATTR).unwrap_or_default()) {
+            return true;
+        }
+        match module_id {
+            ModuleId::CrateRoot(_) => return false,
+            ModuleId::Submodule(submodule_id) => {
+                module_id = submodule_id.parent_module(db.upcast())
+            }
+        }
+    }
+}
+
+/// The effects a function may have when called.
+#[derive(Clone, Copy, Debug, Default)]
+struct Effects {
+    /// Whether the function may make an external call.
+    external_call: bool,
+    /// Whether the function may write to storage.
+    storage_write: bool,
+    /// Whether the function may write to storage after making an external call.
+    write_after_external_call: bool,
+}
+
+/// Computes the effects of functions, caching them.
+struct EffectsAnalysis<'a> {
+    db: &'a dyn SemanticGroup,
+    functions_effects: OrderedHashMap<ConcreteFunctionWithBodyId, Effects>,
+}
+impl<'a> EffectsAnalysis<'a> {
+    /// Returns the effects of calling the given function.
+    fn function_effects(&mut self, function_id: ConcreteFunctionWithBodyId) -> Effects {
+        if let Some(effects) = self.functions_effects.get(&function_id) {
+            return *effects;
+        }
+        // Recursive calls are considered as having no effects, as the effects of the recursion
+        // are accounted for by the outermost call.
+        self.functions_effects.insert(function_id, Effects::default());
+        let effects = if is_reentrancy_allowed(self.db, function_id.function_with_body_id(self.db))
+        {
+            Effects::default()
+        } else {
+            self.walk_function(function_id).map(|state| state.effects()).unwrap_or_default()
+        };
+        self.functions_effects.insert(function_id, effects);
+        effects
+    }
+
+    /// Walks the body of the given function, returning `None` if it is not available.
+    fn walk_function(&mut self, function_id: ConcreteFunctionWithBodyId) -> Option<WalkState> {
+        let body = self.db.function_body(function_id.function_with_body_id(self.db)).ok()?;
+        let substitution = function_id.substitution(self.db).ok()?;
+        let mut walker = BodyWalker {
+            analysis: self,
+            body: &body,
+            substitution: &substitution,
+            state: WalkState::default(),
+        };
+        walker.walk_expr(body.body_expr);
+        Some(walker.state)
+    }
+}
+
+/// The state of a walk over a function body, in execution order.
+#[derive(Default)]
+struct WalkState {
+    /// Whether an external call may have been made before the current point.
+    after_external_call: bool,
+    /// Whether an external call may be made in the walked code.
+    external_call: bool,
+    /// Whether storage may be written in the walked code.
+    storage_write: bool,
+    /// The calls in the walked code that may write to storage after an external call.
+    writes_after_external_calls: OrderedHashSet<SyntaxStablePtrId>,
+}
+impl WalkState {
+    /// Returns the effects of the walked code.
+    fn effects(&self) -> Effects {
+        Effects {
+            external_call: self.external_call,
+            storage_write: self.storage_write,
+            write_after_external_call: !self.writes_after_external_calls.is_empty(),
+        }
+    }
+}
+
+/// Walks over the expressions of a function body, in execution order.
+struct BodyWalker<'a, 'b> {
+    analysis: &'b mut EffectsAnalysis<'a>,
+    body: &'b FunctionBody,
+    /// The substitution of the generic parameters of the function.
+    substitution: &'b GenericSubstitution,
+    state: WalkState,
+}
+impl<'a, 'b> BodyWalker<'a, 'b> {
+    fn walk_expr(&mut self, expr_id: ExprId) {
+        match &self.body.exprs[expr_id] {
+            Expr::Tuple(expr) => self.walk_exprs(&expr.items),
+            Expr::Snapshot(expr) => self.walk_expr(expr.inner),
+            Expr::Desnap(expr) => self.walk_expr(expr.inner),
+            Expr::Assignment(expr) => self.walk_expr(expr.rhs),
+            Expr::LogicalOperator(expr) => {
+                self.walk_expr(expr.lhs);
+                self.walk_branches([Some(expr.rhs), None]);
+            }
+            Expr::Block(expr) => {
+                for statement_id in &expr.statements {
+                    self.walk_statement(&self.body.statements[*statement_id]);
+                }
+                self.walk_exprs(&expr.tail);
+            }
+            // Loop bodies are walked twice, as writes in an iteration may follow external calls
+            // of the previous one.
+            Expr::Loop(expr) => {
+                self.walk_expr(expr.body);
+                self.walk_expr(expr.body);
+            }
+            Expr::While(expr) => {
+                for _ in 0..2 {
+                    self.walk_condition(&expr.condition);
+                    self.walk_branches([Some(expr.body), None]);
+                }
+            }
+            Expr::FunctionCall(expr) => self.walk_function_call(expr),
+            Expr::Match(expr) => {
+                self.walk_expr(expr.matched_expr);
+                self.walk_branches(expr.arms.iter().map(|arm| Some(arm.expression)));
+            }
+            Expr::If(expr) => {
+                self.walk_condition(&expr.condition);
+                self.walk_branches([Some(expr.if_block), expr.else_block]);
+            }
+            Expr::MemberAccess(expr) => self.walk_expr(expr.expr),
+            Expr::StructCtor(expr) => {
+                for (_, member_expr) in &expr.members {
+                    self.walk_expr(*member_expr);
+                }
+                self.walk_exprs(&expr.base_struct);
+            }
+            Expr::EnumVariantCtor(expr) => self.walk_expr(expr.value_expr),
+            Expr::PropagateError(expr) => self.walk_expr(expr.inner),
+            Expr::FixedSizeArray(expr) => match &expr.items {
+                FixedSizeArrayItems::Items(items) => self.walk_exprs(items),
+                FixedSizeArrayItems::ValueAndSize(value, _) => self.walk_expr(*value),
+            },
+            Expr::Var(_)
+            | Expr::Literal(_)
+            | Expr::StringLiteral(_)
+            | Expr::Constant(_)
+            | Expr::ParamConstant(_)
+            | Expr::Missing(_) => {}
+        }
+    }
+
+    /// Walks a statement. Writes in statements allowing the [REENTRANCY_LINT] lint are not
+    /// reported.
+    fn walk_statement(&mut self, statement: &Statement) {
+        let db = self.analysis.db;
+        let allowed = statement
+            .stable_ptr()
+            .lookup(db.upcast())
+            .query_attr(db.upcast(), ALLOW_ATTR)
+            .into_iter()
+            .any(|attr| {
+                attr.structurize(db.upcast()).has_unnamed_arg(db.upcast(), REENTRANCY_LINT)
+            });
+        let reported_writes =
+            allowed.then(|| std::mem::take(&mut self.state.writes_after_external_calls));
+        match statement {
+            Statement::Expr(statement) => self.walk_expr(statement.expr),
+            Statement::Let(statement) => self.walk_expr(statement.expr),
+            Statement::Return(statement) => self.walk_exprs(&statement.expr_option),
+            Statement::Break(statement) => self.walk_exprs(&statement.expr_option),
+            Statement::Continue(_) => {}
+        }
+        if let Some(reported_writes) = reported_writes {
+            self.state.writes_after_external_calls = reported_writes;
+        }
+    }
+
+    fn walk_exprs<'c>(&mut self, expr_ids: impl IntoIterator<Item = &'c ExprId>) {
+        for expr_id in expr_ids {
+            self.walk_expr(*expr_id);
+        }
+    }
+
+    fn walk_condition(&mut self, condition: &Condition) {
+        match condition {
+            Condition::BoolExpr(expr_id) | Condition::Let(expr_id, _) => self.walk_expr(*expr_id),
+        }
+    }
+
+    /// Walks alternative branches of the execution, where `None` is an empty branch. Each branch
+    /// starts from the current state, and an external call may have been made after the branches
+    /// if it may have been made in any of them.
+    fn walk_branches(&mut self, branches: impl IntoIterator<Item = Option<ExprId>>) {
+        let after_external_call = self.state.after_external_call;
+        let mut merged_after_external_call = after_external_call;
+        for branch in branches {
+            self.state.after_external_call = after_external_call;
+            self.walk_exprs(&branch);
+            merged_after_external_call |= self.state.after_external_call;
+        }
+        self.state.after_external_call = merged_after_external_call;
+    }
+
+    fn walk_function_call(&mut self, expr: &ExprFunctionCall) {
+        for arg in &expr.args {
+            if let ExprFunctionCallArg::Value(expr_id) = arg {
+                self.walk_expr(*expr_id);
+            }
+        }
+        self.walk_exprs(&expr.coupon_arg);
+        let effects = self.call_effects(expr).unwrap_or_default();
+        if (self.state.after_external_call && effects.storage_write)
+            || effects.write_after_external_call
+        {
+            self.state.writes_after_external_calls.insert(expr.stable_ptr.untyped());
+        }
+        self.state.external_call |= effects.external_call;
+        self.state.storage_write |= effects.storage_write;
+        self.state.after_external_call |= effects.external_call;
+    }
+
+    /// Returns the effects of the function called by the given expression, or `None` if they are
+    /// unknown.
+    fn call_effects(&mut self, expr: &ExprFunctionCall) -> Option<Effects> {
+        let db = self.analysis.db;
+        let function_id = SubstitutionRewriter { db, substitution: self.substitution }
+            .rewrite(expr.function)
+            .ok()?;
+        let concrete_function = function_id.get_concrete(db);
+        if let GenericFunctionId::Extern(extern_function_id) = concrete_function.generic_function {
+            let path = extern_function_id.full_path(db.upcast());
+            return Some(Effects {
+                external_call: path == CALL_CONTRACT_SYSCALL,
+                storage_write: path == STORAGE_WRITE_SYSCALL,
+                write_after_external_call: false,
+            });
+        }
+        Some(self.analysis.function_effects(concrete_function.body(db).ok()??))
+    }
+}
@@ -0,0 +1,36 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_test_utils::verify_diagnostics_expectation;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use crate::{reentrancy_analysis_plugin_suite, starknet_plugin_suite};
+
+/// Helper function for testing the re-entrancy analysis.
+pub fn test_reentrancy_analysis(
+    inputs: &OrderedHashMap<String, String>,
+    args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(starknet_plugin_suite())
+        .with_plugin_suite(reentrancy_analysis_plugin_suite())
+        .build()
+        .unwrap();
+    let (_, diagnostics) = setup_test_module(db, inputs["cairo_code"].as_str()).split();
+    let error = verify_diagnostics_expectation(args, &diagnostics);
+
+    TestRunnerResult {
+        outputs: OrderedHashMap::from([("expected_diagnostics".into(), diagnostics)]),
+        error,
+    }
+}
+
+cairo_lang_test_utils::test_file_test!(
+  reentrancy,
+  "src/test_data",
+  {
+      reentrancy: "reentrancy",
+  },
+  test_reentrancy_analysis
+);
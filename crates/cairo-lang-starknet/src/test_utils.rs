@@ -83,6 +83,13 @@ pub fn get_test_contract(example_file_name: &str) -> ContractClass {
             allowed_libfuncs_list_name: Some(BUILTIN_ALL_LIBFUNCS_LIST.to_string()),
             diagnostics_reporter,
             add_statements_functions: false,
+            revert_panic_abi: false,
+            explain_ownership: false,
+            whole_program: false,
+            sierra_inlining_threshold: None,
+            eliminate_redundant_drops: false,
+            embed_gas_estimate: false,
+            reentrancy_analysis: false,
         },
     )
     .expect("compile_path failed")
@@ -19,6 +19,9 @@ pub const UNSTABLE_ATTR: &str = "unstable";
 /// An attribute to allow usage of a feature under a statement.
 pub const FEATURE_ATTR: &str = "feature";
 
+/// An attribute to allow lints, suppressing their warnings, under an item or a statement.
+pub const ALLOW_ATTR: &str = "allow";
+
 /// An attribute to define the order of implicit arguments.
 pub const IMPLICIT_PRECEDENCE_ATTR: &str = "implicit_precedence";
 
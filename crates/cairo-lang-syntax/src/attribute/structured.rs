@@ -28,6 +28,17 @@ impl Attribute {
             _ => false,
         }
     }
+
+    /// Checks if the given attribute has an unnamed argument with the given name, possibly among
+    /// other arguments.
+    pub fn has_unnamed_arg(&self, db: &dyn SyntaxGroup, arg_name: &str) -> bool {
+        self.args.iter().any(|arg| match &arg.variant {
+            AttributeArgVariant::Unnamed { value, .. } => {
+                value.as_syntax_node().get_text_without_trivia(db) == arg_name
+            }
+            _ => false,
+        })
+    }
 }
 
 /// Easier to digest representation of a single attribute value.
@@ -145,6 +145,8 @@ pub struct TestCompilation {
         deserialize_with = "deserialize_ordered_hashmap_vec"
     )]
     pub function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    /// The test cases, named by the full paths of their functions - starting with the names of
+    /// their crates, which tells apart the tests of the different crates.
     pub named_tests: Vec<(String, TestConfig)>,
     pub sierra_program: Program,
     /// A map between sierra statement index and the string representation of the Cairo function
@@ -1,18 +1,21 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use std::vec::IntoIter;
 
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, ensure, Context, Result};
 use cairo_felt::Felt252;
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
-use cairo_lang_compiler::project::setup_project;
+use cairo_lang_compiler::project::setup_workspace;
 use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_filesystem::db::FilesGroupEx;
 use cairo_lang_filesystem::flag::Flag;
 use cairo_lang_filesystem::ids::{CrateId, FlagId};
-use cairo_lang_runner::casm_run::format_next_item;
+use cairo_lang_runner::casm_run::{decode_revert_reason, format_next_item};
 use cairo_lang_runner::profiling::{
     ProfilingInfo, ProfilingInfoProcessor, ProfilingInfoProcessorParams,
 };
@@ -52,7 +55,8 @@ impl TestRunner {
     ///
     /// # Arguments
     ///
-    /// * `path` - The path to compile and run its tests
+    /// * `path` - The path to compile and run its tests - of a project, or of a workspace of
+    ///   projects, see [setup_workspace]
     /// * `filter` - Run only tests containing the filter string
     /// * `include_ignored` - Include ignored tests as well
     /// * `ignored` - Run ignored tests only
@@ -67,6 +71,23 @@ impl TestRunner {
         Ok(Self { compiler, config })
     }
 
+    /// Configure a new test runner, reporting the compilation diagnostics with
+    /// `diagnostics_reporter` instead of printing them to stderr.
+    pub fn new_with_reporter(
+        path: &Path,
+        starknet: bool,
+        diagnostics_reporter: DiagnosticsReporter<'_>,
+        config: TestRunConfig,
+    ) -> Result<Self> {
+        let compiler = TestCompiler::try_new_with_reporter(
+            path,
+            starknet,
+            diagnostics_reporter,
+            config.gas_enabled,
+        )?;
+        Ok(Self { compiler, config })
+    }
+
     /// Runs the tests and process the results for a summary.
     pub fn run(&self) -> Result<Option<TestsSummary>> {
         let runner = CompiledTestRunner::new(self.compiler.build()?, self.config.clone());
@@ -98,8 +119,16 @@ impl CompiledTestRunner {
             self.config.ignored,
             &self.config.filter,
         );
+        let (compiled, filtered_out) = match self.config.shard {
+            Some(shard) => {
+                let (compiled, sharded_out) = shard_test_cases(compiled, shard);
+                (compiled, filtered_out + sharded_out)
+            }
+            None => (compiled, filtered_out),
+        };
 
-        let TestsSummary { passed, failed, ignored, failed_run_results } = run_tests(
+        let start = Instant::now();
+        let summary = run_tests(
             if self.config.run_profiler == RunProfilerConfig::Cairo { db } else { None },
             compiled.named_tests,
             compiled.sierra_program,
@@ -108,6 +137,22 @@ impl CompiledTestRunner {
             compiled.statements_functions,
             &self.config,
         )?;
+        let crates_summary = summary.crates_summary();
+        if crates_summary.len() > 1 {
+            for (crate_name, [passed, failed, ignored]) in crates_summary {
+                println!(
+                    "crate {crate_name}: {passed} passed; {failed} failed; {ignored} ignored;"
+                );
+            }
+        }
+        let TestsSummary { passed, failed, ignored, failed_run_results, failed_outputs } = summary;
+        if let Some(shard) = self.config.shard {
+            println!(
+                "shard {shard}: ran {} tests in {:.2}s.",
+                passed.len() + failed.len(),
+                start.elapsed().as_secs_f64()
+            );
+        }
 
         if failed.is_empty() {
             println!(
@@ -119,6 +164,11 @@ impl CompiledTestRunner {
             );
             Ok(None)
         } else {
+            for (failure, output) in failed.iter().zip_eq(failed_outputs) {
+                if let Some(output) = output.filter(|output| !output.is_empty()) {
+                    println!("---- {failure} stdout ----\n{output}");
+                }
+            }
             println!("failures:");
             for (failure, run_result) in failed.iter().zip_eq(failed_run_results) {
                 print!("   {failure} - ");
@@ -144,7 +194,10 @@ impl CompiledTestRunner {
 }
 
 /// Formats the given felts as a panic string.
-fn format_for_panic(mut felts: IntoIter<Felt252>) -> String {
+/// Panic data encoded with the revert-with-reason ABI is formatted as its reason.
+fn format_for_panic(felts: IntoIter<Felt252>) -> String {
+    let felts = felts.as_slice();
+    let mut felts = decode_revert_reason(felts).unwrap_or(felts).iter().cloned();
     let mut items = Vec::new();
     while let Some(item) = format_next_item(&mut felts) {
         items.push(item.quote_if_string());
@@ -178,6 +231,53 @@ pub struct TestRunConfig {
     pub gas_enabled: bool,
     /// Whether to print used resources after each test.
     pub print_resource_usage: bool,
+    /// Whether to print the output of tests as they run, rather than only for failing tests.
+    pub nocapture: bool,
+    /// The shard of the tests to run, or `None` to run all the tests.
+    pub shard: Option<TestShard>,
+}
+
+/// A part of the tests, for splitting a test run across multiple machines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestShard {
+    /// The 1-based index of the shard.
+    index: usize,
+    /// The number of shards the tests are split into.
+    count: usize,
+}
+impl TestShard {
+    /// Creates the shard `index` out of `count` shards, where `index` is 1-based.
+    pub fn new(index: usize, count: usize) -> Result<Self> {
+        ensure!(count > 0, "The number of shards must be positive.");
+        ensure!(
+            (1..=count).contains(&index),
+            "The shard index must be between 1 and the number of shards ({count}), got {index}."
+        );
+        Ok(Self { index, count })
+    }
+
+    /// Returns whether the test at `position` in the name-sorted test set belongs to the shard.
+    fn contains(&self, position: usize) -> bool {
+        position % self.count == self.index - 1
+    }
+}
+impl FromStr for TestShard {
+    type Err = anyhow::Error;
+
+    /// Parses a shard of the form `<index>/<count>`, e.g. `2/4`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (index, count) =
+            s.split_once('/').with_context(|| format!("Expected `<index>/<count>`, got `{s}`."))?;
+        let parse = |value: &str| {
+            value.trim().parse::<usize>().with_context(|| format!("Invalid shard value `{value}`."))
+        };
+        Self::new(parse(index)?, parse(count)?)
+    }
+}
+impl fmt::Display for TestShard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
 }
 
 /// The test cases compiler.
@@ -193,13 +293,29 @@ impl TestCompiler {
     ///
     /// # Arguments
     ///
-    /// * `path` - The path to compile and run its tests
+    /// * `path` - The path to compile and run its tests - of a project, or of a workspace of
+    ///   projects, see [setup_workspace]
     /// * `starknet` - Add the starknet plugin to run the tests
     pub fn try_new(
         path: &Path,
         starknet: bool,
         allow_warnings: bool,
         gas_enabled: bool,
+    ) -> Result<Self> {
+        let mut reporter = DiagnosticsReporter::stderr();
+        if allow_warnings {
+            reporter = reporter.allow_warnings();
+        }
+        Self::try_new_with_reporter(path, starknet, reporter, gas_enabled)
+    }
+
+    /// Configure a new test compiler, reporting the compilation diagnostics with
+    /// `diagnostics_reporter`.
+    pub fn try_new_with_reporter(
+        path: &Path,
+        starknet: bool,
+        diagnostics_reporter: DiagnosticsReporter<'_>,
+        gas_enabled: bool,
     ) -> Result<Self> {
         let db = &mut {
             let mut b = RootDatabase::builder();
@@ -217,11 +333,8 @@ impl TestCompiler {
         let add_redeposit_gas_flag_id = FlagId::new(db, "add_redeposit_gas");
         db.set_flag(add_redeposit_gas_flag_id, Some(Arc::new(Flag::AddRedepositGas(true))));
 
-        let main_crate_ids = setup_project(db, Path::new(&path))?;
-        let mut reporter = DiagnosticsReporter::stderr().with_crates(&main_crate_ids);
-        if allow_warnings {
-            reporter = reporter.allow_warnings();
-        }
+        let main_crate_ids = setup_workspace(db, Path::new(&path))?;
+        let mut reporter = diagnostics_reporter.with_crates(&main_crate_ids);
         if reporter.check(db) {
             bail!("failed to compile: {}", path.display());
         }
@@ -279,6 +392,33 @@ pub fn filter_test_cases(
     (tests, filtered_out)
 }
 
+/// Keeps only the test cases of the given shard.
+///
+/// The tests are distributed between the shards in a round-robin manner by their order in the
+/// name-sorted test set, so the same test set is always split the same way, and into parts whose
+/// sizes differ by at most one.
+///
+/// # Returns
+/// * (`TestCompilation`, `usize`) - The test cases of the shard and the number of test cases of
+///   other shards.
+pub fn shard_test_cases(compiled: TestCompilation, shard: TestShard) -> (TestCompilation, usize) {
+    let total_tests_count = compiled.named_tests.len();
+    let mut in_shard = vec![false; total_tests_count];
+    for (position, idx) in
+        (0..total_tests_count).sorted_by_key(|idx| &compiled.named_tests[*idx].0).enumerate()
+    {
+        in_shard[idx] = shard.contains(position);
+    }
+    let named_tests = compiled
+        .named_tests
+        .into_iter()
+        .zip(in_shard)
+        .filter_map(|(named_test, in_shard)| in_shard.then_some(named_test))
+        .collect_vec();
+    let sharded_out = total_tests_count - named_tests.len();
+    (TestCompilation { named_tests, ..compiled }, sharded_out)
+}
+
 /// The status of a ran test.
 enum TestStatus {
     Success,
@@ -295,6 +435,8 @@ struct TestResult {
     used_resources: StarknetExecutionResources,
     /// The profiling info of the run, if requested.
     profiling_info: Option<ProfilingInfo>,
+    /// The output printed by the run, if captured.
+    output: Option<String>,
 }
 
 /// Summary data of the ran tests.
@@ -303,6 +445,23 @@ pub struct TestsSummary {
     failed: Vec<String>,
     ignored: Vec<String>,
     failed_run_results: Vec<RunResultValue>,
+    failed_outputs: Vec<Option<String>>,
+}
+
+impl TestsSummary {
+    /// Returns the numbers of passed, failed and ignored tests of each crate, sorted by the crate
+    /// names - which prefix the names of their tests.
+    fn crates_summary(&self) -> Vec<(&str, [usize; 3])> {
+        let mut crates = OrderedHashMap::<&str, [usize; 3]>::default();
+        for (idx, names) in [&self.passed, &self.failed, &self.ignored].into_iter().enumerate() {
+            for name in names {
+                let crate_name =
+                    name.split_once("::").map_or(name.as_str(), |(crate_name, _)| crate_name);
+                crates.entry(crate_name).or_default()[idx] += 1;
+            }
+        }
+        crates.into_iter().sorted_by_key(|(crate_name, _)| *crate_name).collect()
+    }
 }
 
 /// Runs the tests and process the results for a summary.
@@ -336,7 +495,8 @@ pub fn run_tests(
             }
         },
     )
-    .with_context(|| "Failed setting up runner.")?;
+    .with_context(|| "Failed setting up runner.")?
+    .with_output_capture(!config.nocapture);
     let suffix = if named_tests.len() != 1 { "s" } else { "" };
     println!("running {} test{}", named_tests.len(), suffix);
     let wrapped_summary = Mutex::new(Ok(TestsSummary {
@@ -344,6 +504,7 @@ pub fn run_tests(
         failed: vec![],
         ignored: vec![],
         failed_run_results: vec![],
+        failed_outputs: vec![],
     }));
 
     // Run in parallel if possible. If running with db, parallelism is impossible.
@@ -411,7 +572,9 @@ fn run_single_test(
                 RunResultValue::Panic(value) => match test.expectation {
                     TestExpectation::Success => TestStatus::Fail(result.value),
                     TestExpectation::Panics(panic_expectation) => match panic_expectation {
-                        PanicExpectation::Exact(expected) if value != &expected => {
+                        PanicExpectation::Exact(expected)
+                            if decode_revert_reason(value).unwrap_or(value) != expected =>
+                        {
                             TestStatus::Fail(result.value)
                         }
                         _ => TestStatus::Success,
@@ -429,6 +592,7 @@ fn run_single_test(
                 }),
             used_resources: result.used_resources,
             profiling_info: result.profiling_info,
+            output: result.captured_output,
         }),
     ))
 }
@@ -461,6 +625,7 @@ fn update_summary(
                 TestStatus::Success => (&mut summary.passed, "ok".bright_green()),
                 TestStatus::Fail(run_result) => {
                     summary.failed_run_results.push(run_result);
+                    summary.failed_outputs.push(result.output);
                     (&mut summary.failed, "fail".bright_red())
                 }
             };
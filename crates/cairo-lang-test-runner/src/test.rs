@@ -1,8 +1,16 @@
 use cairo_felt::{felt_str, Felt252};
+use cairo_lang_runner::casm_run::REVERT_WITH_REASON_SELECTOR;
+use cairo_lang_runner::SierraCasmRunner;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_test_plugin::test_config::TestExpectation;
+use cairo_lang_test_plugin::TestConfig;
 use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
 use itertools::Itertools;
 
-use crate::{format_for_panic, TestCompilation, TestCompiler};
+use crate::{
+    format_for_panic, run_single_test, run_tests, shard_test_cases, RunProfilerConfig,
+    TestCompilation, TestCompiler, TestRunConfig, TestShard, TestStatus, TestsSummary,
+};
 
 #[test]
 fn test_compiled_serialization() {
@@ -170,6 +178,20 @@ fn test_format_for_panic() {
     ];
     assert_eq!(format_for_panic(felts.into_iter()), "Panicked with \"Hello\\x11world\".");
 
+    // Encoded with the revert-with-reason ABI.
+    let felts = vec![
+        Felt252::from(REVERT_WITH_REASON_SELECTOR),
+        // reason length
+        Felt252::from(1),
+        felt_str!("68656c6c6f", 16),
+    ];
+    assert_eq!(format_for_panic(felts.into_iter()), "Panicked with 0x68656c6c6f ('hello').");
+
+    // Reason length mismatch - not decoded.
+    let felts =
+        vec![Felt252::from(REVERT_WITH_REASON_SELECTOR), Felt252::from(2), Felt252::from(1)];
+    assert_eq!(format_for_panic(felts.into_iter()), "Panicked with (0x8c379a0, 0x2, 0x1).");
+
     // Valid string with a newline.
     let felts = vec![
         felt_str!(BYTE_ARRAY_MAGIC, 16),
@@ -201,3 +223,130 @@ fn test_format_for_panic() {
         "Panicked with (0x9999, \"hello\", 0x776f726c64 ('world'), 0x8888)."
     );
 }
+
+#[test]
+fn test_shard_parsing() {
+    assert_eq!("2/4".parse::<TestShard>().unwrap(), TestShard::new(2, 4).unwrap());
+    assert_eq!(TestShard::new(2, 4).unwrap().to_string(), "2/4");
+    for invalid in ["2", "0/4", "5/4", "1/0", "a/4", "1/4/2"] {
+        assert!(invalid.parse::<TestShard>().is_err(), "`{invalid}` should not parse.");
+    }
+}
+
+#[test]
+fn test_shard_test_cases() {
+    let names = ["e", "b", "a", "d", "c"];
+    let compilation = || TestCompilation {
+        contracts_info: Default::default(),
+        function_set_costs: Default::default(),
+        named_tests: names
+            .iter()
+            .map(|name| {
+                let config = TestConfig {
+                    available_gas: None,
+                    expectation: TestExpectation::Success,
+                    ignored: false,
+                };
+                (name.to_string(), config)
+            })
+            .collect(),
+        sierra_program: Program {
+            type_declarations: vec![],
+            libfunc_declarations: vec![],
+            statements: vec![],
+            funcs: vec![],
+        },
+        statements_functions: Default::default(),
+    };
+    let shard_names = |index| {
+        let (compiled, sharded_out) =
+            shard_test_cases(compilation(), TestShard::new(index, 2).unwrap());
+        let names = compiled.named_tests.into_iter().map(|(name, _)| name).collect_vec();
+        assert_eq!(names.len() + sharded_out, 5);
+        names
+    };
+    // Shards are assigned by the sorted order of the names, and keep the original order.
+    assert_eq!(shard_names(1), ["e", "a", "c"]);
+    assert_eq!(shard_names(2), ["b", "d"]);
+}
+
+#[test]
+fn test_crates_summary() {
+    let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect_vec();
+    let summary = TestsSummary {
+        passed: names(&["lib::tests::a", "app::b", "lib::c"]),
+        failed: names(&["app::tests::d"]),
+        ignored: names(&["lib::e"]),
+        failed_run_results: vec![],
+        failed_outputs: vec![None],
+    };
+    assert_eq!(summary.crates_summary(), [("app", [1, 1, 0]), ("lib", [2, 0, 1])]);
+}
+
+#[test]
+fn test_captured_output() {
+    use std::path::PathBuf;
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data").join("output_capture.cairo");
+    let compiled = TestCompiler::try_new(&path, false, true, false).unwrap().build().unwrap();
+
+    // The output of each test is captured into its result, whether it passes or fails.
+    let runner =
+        SierraCasmRunner::new(compiled.sierra_program.clone(), None, Default::default(), None)
+            .unwrap()
+            .with_output_capture(true);
+    let outputs = compiled
+        .named_tests
+        .iter()
+        .map(|(name, test)| {
+            let (_, result) = run_single_test(test.clone(), name.clone(), &runner).unwrap();
+            let result = result.unwrap();
+            (name.as_str(), matches!(result.status, TestStatus::Success), result.output)
+        })
+        .collect_vec();
+    assert_eq!(
+        outputs,
+        [
+            (
+                "output_capture::output_capture::test_passing",
+                true,
+                Some("passing output\n".to_string())
+            ),
+            (
+                "output_capture::output_capture::test_failing",
+                false,
+                Some("failing output\n".to_string())
+            ),
+        ]
+    );
+
+    // Only the output of the failing tests is kept for reporting.
+    let run = |nocapture| {
+        let config = TestRunConfig {
+            filter: String::new(),
+            include_ignored: false,
+            ignored: false,
+            run_profiler: RunProfilerConfig::None,
+            gas_enabled: false,
+            print_resource_usage: false,
+            nocapture,
+            shard: None,
+        };
+        let compiled = compiled.clone();
+        run_tests(
+            None,
+            compiled.named_tests,
+            compiled.sierra_program,
+            compiled.function_set_costs,
+            compiled.contracts_info,
+            compiled.statements_functions,
+            &config,
+        )
+        .unwrap()
+    };
+    let summary = run(false);
+    assert_eq!(summary.failed, ["output_capture::output_capture::test_failing"]);
+    assert_eq!(summary.failed_outputs, [Some("failing output\n".to_string())]);
+    // With `nocapture`, the output is printed as the tests run instead.
+    assert_eq!(run(true).failed_outputs, [None]);
+}
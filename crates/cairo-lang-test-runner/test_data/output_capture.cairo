@@ -0,0 +1,10 @@
+#[test]
+fn test_passing() {
+    println!("passing output");
+}
+
+#[test]
+fn test_failing() {
+    println!("failing output");
+    panic!("failure");
+}
@@ -1,3 +1,9 @@
+//! Utilities for golden-file tests, as used across the Cairo compiler.
+//!
+//! The same utilities can be used by plugin and tool authors to snapshot-test their generated
+//! code, diagnostics and Sierra output: declare test files with [`test_file_test!`] (or
+//! [`test_file_test_with_runner!`]) and rerun with `CAIRO_FIX_TESTS=1` to bless the current
+//! outputs. Using the macros requires `test-log` as a dev-dependency of the calling crate.
 #![cfg(feature = "testing")]
 
 pub mod parse_test_file;
@@ -19,11 +25,16 @@ fn set_contents(path: &Path, content: String) {
     fs::write(path, content).unwrap_or_else(|_| panic!("Could not write file: '{path:?}'"));
 }
 
+/// Returns true if expected test outputs should be overridden by the actual ones, i.e. the
+/// `CAIRO_FIX_TESTS` environment value is set to `1`.
+pub fn is_fix_mode() -> bool {
+    std::env::var("CAIRO_FIX_TESTS") == Ok("1".into())
+}
+
 /// Compares content to examples content, or overrides it if the `CAIRO_FIX_TESTS` environment
 /// value is set to `1`.
 pub fn compare_contents_or_fix_with_path(path: &Path, content: String) {
-    let is_fix_mode = std::env::var("CAIRO_FIX_TESTS") == Ok("1".into());
-    if is_fix_mode {
+    if is_fix_mode() {
         set_contents(path, content);
     } else {
         pretty_assertions::assert_eq!(content, get_expected_contents(path));
@@ -232,7 +232,7 @@ macro_rules! test_file_test_with_runner {
             #[test_log::test]
             fn $test_name() -> Result<(), std::io::Error> {
                 let path: std::path::PathBuf = [env!("CARGO_MANIFEST_DIR"), $base_dir, $test_file].iter().collect();
-                cairo_lang_test_utils::parse_test_file::run_test_file(
+                $crate::parse_test_file::run_test_file(
                     path.as_path(),
                     stringify!($runner),
                     &mut $runner::default(),
@@ -334,10 +334,10 @@ macro_rules! test_file_test {
             #[test_log::test]
             fn $test_name() -> Result<(), std::io::Error> {
                 let path: std::path::PathBuf = [env!("CARGO_MANIFEST_DIR"), $base_dir, $test_file].iter().collect();
-                cairo_lang_test_utils::parse_test_file::run_test_file(
+                $crate::parse_test_file::run_test_file(
                     path.as_path(),
                     stringify!($test_func),
-                    &mut cairo_lang_test_utils::parse_test_file::SimpleRunner { func: $test_func },
+                    &mut $crate::parse_test_file::SimpleRunner { func: $test_func },
                 )
             }
         )*
@@ -353,7 +353,7 @@ pub fn run_test_file(
     runner: &mut dyn TestFileRunner,
 ) -> Result<(), std::io::Error> {
     let filename = path.file_name().unwrap().to_str().unwrap();
-    let is_fix_mode = std::env::var("CAIRO_FIX_TESTS") == Ok("1".into());
+    let is_fix_mode = crate::is_fix_mode();
     let is_format_mode = std::env::var("CAIRO_SKIP_FORMAT_TESTS") != Ok("1".into());
     let filter = std::env::var("CAIRO_TEST_FILTER").unwrap_or_default();
 
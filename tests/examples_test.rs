@@ -161,7 +161,14 @@ fn cairo_to_casm(
             } else {
                 calc_metadata_ap_change_only(&program).unwrap()
             },
-            SierraToCasmConfig { gas_usage_check, max_bytecode_size: usize::MAX },
+            SierraToCasmConfig {
+                gas_usage_check,
+                max_bytecode_size: usize::MAX,
+                optimize: false,
+                eliminate_dead_code: false,
+                coalesce_ap_updates: false,
+                annotate_gas_costs: false,
+            },
         )
         .unwrap()
         .to_string(),
@@ -179,7 +186,14 @@ fn cairo_to_casm_auto_gas(#[case] name: &str, example_dir_data: &ExampleDirData)
         cairo_lang_sierra_to_casm::compiler::compile(
             &program,
             &calc_metadata(&program, Default::default()).unwrap(),
-            SierraToCasmConfig { gas_usage_check: true, max_bytecode_size: usize::MAX },
+            SierraToCasmConfig {
+                gas_usage_check: true,
+                max_bytecode_size: usize::MAX,
+                optimize: false,
+                eliminate_dead_code: false,
+                coalesce_ap_updates: false,
+                annotate_gas_costs: false,
+            },
         )
         .unwrap()
         .to_string(),
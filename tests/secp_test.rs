@@ -0,0 +1,467 @@
+//! Differential tests of the secp256k1 and secp256r1 syscalls, running Cairo code using them in the
+//! runner and comparing the results against a plain Rust reference implementation of the curves.
+
+use std::sync::Arc;
+
+use cairo_felt::Felt252;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_runner::{Arg, RunResultValue, SierraCasmRunner};
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use rstest::{fixture, rstest};
+
+/// The gas available to each run, enough for any of the tested functions.
+const AVAILABLE_GAS: usize = 1 << 40;
+
+/// The Cairo code of the tested functions. Points are passed and returned by their coordinates,
+/// where a `false` first output marks that the inputs aren't valid points.
+const CAIRO_CODE: &str = r#"
+use starknet::SyscallResultTrait;
+use starknet::secp256_trait::{Secp256Trait, Secp256PointTrait, is_valid_signature};
+use starknet::secp256k1::Secp256k1Point;
+use starknet::secp256r1::Secp256r1Point;
+
+fn coordinates<P, +Drop<P>, +Secp256PointTrait<P>>(p: Option<P>) -> (bool, u256, u256) {
+    match p {
+        Option::Some(p) => {
+            let (x, y) = p.get_coordinates().unwrap_syscall();
+            (true, x, y)
+        },
+        Option::None => (false, 0, 0),
+    }
+}
+
+fn mul<P, +Drop<P>, impl Secp256Impl: Secp256Trait<P>, +Secp256PointTrait<P>>(
+    x: u256, y: u256, scalar: u256
+) -> (bool, u256, u256) {
+    match Secp256Impl::secp256_ec_new_syscall(x, y).unwrap_syscall() {
+        Option::Some(p) => coordinates(Option::Some(p.mul(scalar).unwrap_syscall())),
+        Option::None => coordinates(Option::<P>::None),
+    }
+}
+
+fn add<P, +Drop<P>, impl Secp256Impl: Secp256Trait<P>, +Secp256PointTrait<P>>(
+    x0: u256, y0: u256, x1: u256, y1: u256
+) -> (bool, u256, u256) {
+    let p1 = Secp256Impl::secp256_ec_new_syscall(x1, y1).unwrap_syscall();
+    match Secp256Impl::secp256_ec_new_syscall(x0, y0).unwrap_syscall() {
+        Option::Some(p0) => match p1 {
+            Option::Some(p1) => coordinates(Option::Some(p0.add(p1).unwrap_syscall())),
+            Option::None => coordinates(Option::<P>::None),
+        },
+        Option::None => coordinates(Option::<P>::None),
+    }
+}
+
+fn mul_add<P, +Drop<P>, impl Secp256Impl: Secp256Trait<P>, +Secp256PointTrait<P>>(
+    x0: u256, y0: u256, scalar0: u256, x1: u256, y1: u256, scalar1: u256
+) -> (bool, u256, u256) {
+    let p1 = Secp256Impl::secp256_ec_new_syscall(x1, y1).unwrap_syscall();
+    match Secp256Impl::secp256_ec_new_syscall(x0, y0).unwrap_syscall() {
+        Option::Some(p0) => match p1 {
+            Option::Some(p1) => coordinates(
+                Option::Some(p0.mul_add(scalar0, p1, scalar1).unwrap_syscall())
+            ),
+            Option::None => coordinates(Option::<P>::None),
+        },
+        Option::None => coordinates(Option::<P>::None),
+    }
+}
+
+fn verify<P, +Drop<P>, impl Secp256Impl: Secp256Trait<P>, +Secp256PointTrait<P>>(
+    msg_hash: u256, r: u256, s: u256, x: u256, y: u256
+) -> bool {
+    let public_key = Secp256Impl::secp256_ec_new_syscall(x, y).unwrap_syscall().unwrap();
+    is_valid_signature::<P>(msg_hash, r, s, public_key)
+}
+
+fn k1_mul(x: u256, y: u256, scalar: u256) -> (bool, u256, u256) {
+    mul::<Secp256k1Point>(x, y, scalar)
+}
+
+fn k1_add(x0: u256, y0: u256, x1: u256, y1: u256) -> (bool, u256, u256) {
+    add::<Secp256k1Point>(x0, y0, x1, y1)
+}
+
+fn k1_mul_add(
+    x0: u256, y0: u256, scalar0: u256, x1: u256, y1: u256, scalar1: u256
+) -> (bool, u256, u256) {
+    mul_add::<Secp256k1Point>(x0, y0, scalar0, x1, y1, scalar1)
+}
+
+fn k1_verify(msg_hash: u256, r: u256, s: u256, x: u256, y: u256) -> bool {
+    verify::<Secp256k1Point>(msg_hash, r, s, x, y)
+}
+
+fn r1_mul(x: u256, y: u256, scalar: u256) -> (bool, u256, u256) {
+    mul::<Secp256r1Point>(x, y, scalar)
+}
+
+fn r1_add(x0: u256, y0: u256, x1: u256, y1: u256) -> (bool, u256, u256) {
+    add::<Secp256r1Point>(x0, y0, x1, y1)
+}
+
+fn r1_mul_add(
+    x0: u256, y0: u256, scalar0: u256, x1: u256, y1: u256, scalar1: u256
+) -> (bool, u256, u256) {
+    mul_add::<Secp256r1Point>(x0, y0, scalar0, x1, y1, scalar1)
+}
+
+fn r1_verify(msg_hash: u256, r: u256, s: u256, x: u256, y: u256) -> bool {
+    verify::<Secp256r1Point>(msg_hash, r, s, x, y)
+}
+"#;
+
+/// Runs the functions of a Cairo module with a Starknet context, for comparing their results and
+/// costs against reference implementations.
+struct DifferentialRunner {
+    runner: SierraCasmRunner,
+}
+impl DifferentialRunner {
+    /// Compiles `cairo_code` as the module of a test crate.
+    fn new(cairo_code: &str) -> Self {
+        let db = RootDatabase::builder().detect_corelib().build().unwrap();
+        let test_module = setup_test_module(&db, cairo_code).unwrap();
+        DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(&db).unwrap();
+        let SierraProgramWithDebug { program, .. } = Arc::unwrap_or_clone(
+            db.get_sierra_program(vec![test_module.crate_id]).expect("Failed compiling to Sierra."),
+        );
+        let runner = SierraCasmRunner::new(
+            replace_sierra_ids_in_program(&db, &program),
+            Some(Default::default()),
+            Default::default(),
+            None,
+        )
+        .expect("Failed setting up runner.");
+        Self { runner }
+    }
+
+    /// Runs the function `name` of the module with `args`, returning its result and its gas cost.
+    fn run(&self, name: &str, args: &[Felt252]) -> (RunResultValue, Felt252) {
+        let func = self
+            .runner
+            .find_function(&format!("test::{name}"))
+            .expect("Failed finding the function.");
+        let result = self
+            .runner
+            .run_function_with_starknet_context(
+                func,
+                &args.iter().cloned().map(Arg::Value).collect_vec(),
+                Some(AVAILABLE_GAS),
+                Default::default(),
+            )
+            .expect("Failed running the function.");
+        (result.value, AVAILABLE_GAS - result.gas_counter.as_ref().unwrap())
+    }
+}
+
+/// A point of a curve in affine coordinates, or `None` for the point at infinity.
+type Point = Option<(BigUint, BigUint)>;
+
+/// A reference implementation of a short Weierstrass curve `y^2 = x^3 + a*x + b` over the prime
+/// field of size `p`, whose generator `g` has the prime order `n`.
+///
+/// Both tested curves have a cofactor of 1, so every point on the curve is in the subgroup.
+struct Curve {
+    /// The prefix of the Cairo functions of the curve.
+    prefix: &'static str,
+    p: BigUint,
+    a: BigUint,
+    b: BigUint,
+    n: BigUint,
+    g: Point,
+}
+impl Curve {
+    fn secp256k1() -> Self {
+        Self {
+            prefix: "k1",
+            p: hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"),
+            a: BigUint::from(0u32),
+            b: BigUint::from(7u32),
+            n: hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"),
+            g: Some((
+                hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+                hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+            )),
+        }
+    }
+
+    fn secp256r1() -> Self {
+        Self {
+            prefix: "r1",
+            p: hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"),
+            a: hex("ffffffff00000001000000000000000000000000fffffffffffffffffffffffc"),
+            b: hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"),
+            n: hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"),
+            g: Some((
+                hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+                hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+            )),
+        }
+    }
+
+    /// Returns the point with the coordinates `(x, y)`, where `(0, 0)` is the point at infinity, or
+    /// `None` if it isn't on the curve.
+    fn point(&self, x: &BigUint, y: &BigUint) -> Option<Point> {
+        let zero = BigUint::from(0u32);
+        if *x == zero && *y == zero {
+            return Some(None);
+        }
+        let rhs = (x.modpow(&BigUint::from(3u32), &self.p) + &self.a * x + &self.b) % &self.p;
+        (y.modpow(&BigUint::from(2u32), &self.p) == rhs).then(|| Some((x.clone(), y.clone())))
+    }
+
+    /// Returns the inverse of `value` modulo the prime `modulus`.
+    fn inverse(value: &BigUint, modulus: &BigUint) -> BigUint {
+        value.modpow(&(modulus - BigUint::from(2u32)), modulus)
+    }
+
+    fn add(&self, p0: &Point, p1: &Point) -> Point {
+        let (Some((x0, y0)), Some((x1, y1))) = (p0, p1) else {
+            return p0.clone().or_else(|| p1.clone());
+        };
+        let p = &self.p;
+        let slope = if x0 == x1 {
+            if (y0 + y1) % p == BigUint::from(0u32) {
+                return None;
+            }
+            (BigUint::from(3u32) * x0 * x0 + &self.a)
+                * Self::inverse(&(BigUint::from(2u32) * y0), p)
+        } else {
+            (y1 + p - y0) * Self::inverse(&((x1 + p - x0) % p), p)
+        };
+        let slope = slope % p;
+        let x2 = (&slope * &slope + BigUint::from(2u32) * p - x0 - x1) % p;
+        let y2 = (slope * ((x0 + p - &x2) % p) + p - y0) % p;
+        Some((x2, y2))
+    }
+
+    fn mul(&self, point: &Point, scalar: &BigUint) -> Point {
+        let mut result = None;
+        for bit in (0..scalar.bits()).rev() {
+            result = self.add(&result, &result);
+            if scalar.bit(bit) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    /// Returns whether `(r, s)` is a valid ECDSA signature of `msg_hash` by `public_key`.
+    fn verify(&self, msg_hash: &BigUint, r: &BigUint, s: &BigUint, public_key: &Point) -> bool {
+        let zero = BigUint::from(0u32);
+        if *r == zero || *r >= self.n || *s == zero || *s >= self.n {
+            return false;
+        }
+        let s_inv = Self::inverse(s, &self.n);
+        let u1 = msg_hash * &s_inv % &self.n;
+        let u2 = r * &s_inv % &self.n;
+        let sum = self.add(&self.mul(&self.g, &u1), &self.mul(public_key, &u2));
+        coordinates(&sum).0 == *r
+    }
+
+    /// Returns an ECDSA signature `(r, s)` of `msg_hash` by the private key `d`, with the nonce
+    /// `k`.
+    fn sign(&self, msg_hash: &BigUint, d: &BigUint, k: &BigUint) -> (BigUint, BigUint) {
+        let r = coordinates(&self.mul(&self.g, k)).0 % &self.n;
+        let s = Self::inverse(k, &self.n) * (msg_hash + &r * d) % &self.n;
+        (r, s)
+    }
+
+    /// Runs the Cairo function `name` of the curve with `args` as `u256` values.
+    fn run(
+        &self,
+        runner: &DifferentialRunner,
+        name: &str,
+        args: &[&BigUint],
+    ) -> (RunResultValue, Felt252) {
+        let args = args.iter().flat_map(|value| u256_felts(value)).collect_vec();
+        runner.run(&format!("{}_{name}", self.prefix), &args)
+    }
+}
+
+/// Returns the number of the hex string `value`.
+fn hex(value: &str) -> BigUint {
+    BigUint::parse_bytes(value.as_bytes(), 16).unwrap()
+}
+
+/// Returns the coordinates of `point`, where the point at infinity is `(0, 0)`.
+fn coordinates(point: &Point) -> (BigUint, BigUint) {
+    point.clone().unwrap_or_default()
+}
+
+/// Returns the low and high felts of the `u256` value `value`.
+fn u256_felts(value: &BigUint) -> [Felt252; 2] {
+    let mask = (BigUint::from(1u32) << 128) - 1u32;
+    [Felt252::from(value & mask), Felt252::from(value >> 128)]
+}
+
+/// Returns the expected result of the Cairo functions returning `(bool, u256, u256)`, for `point`
+/// if the inputs are valid points, and for `None` otherwise.
+fn expected_point_result(point: Option<Point>) -> RunResultValue {
+    let (is_valid, (x, y)) = match point {
+        Some(point) => (true, coordinates(&point)),
+        None => (false, Default::default()),
+    };
+    RunResultValue::Success(
+        [Felt252::from(is_valid)].into_iter().chain(u256_felts(&x)).chain(u256_felts(&y)).collect(),
+    )
+}
+
+#[fixture]
+#[once]
+fn runner() -> DifferentialRunner {
+    DifferentialRunner::new(CAIRO_CODE)
+}
+
+/// The scalars the multiplication is tested with, including the edge cases around the curve size.
+fn scalars(curve: &Curve) -> Vec<BigUint> {
+    let one = BigUint::from(1u32);
+    vec![
+        BigUint::from(0u32),
+        one.clone(),
+        BigUint::from(2u32),
+        BigUint::from(0xdeadbeefu32),
+        &curve.n - &one,
+        curve.n.clone(),
+        &curve.n + &one,
+        (one.clone() << 256) - &one,
+        hex("1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"),
+    ]
+}
+
+#[rstest]
+fn test_mul(
+    runner: &DifferentialRunner,
+    #[values(Curve::secp256k1(), Curve::secp256r1())] curve: Curve,
+) {
+    let (gx, gy) = coordinates(&curve.g);
+    let double_g = curve.add(&curve.g, &curve.g);
+    let (dx, dy) = coordinates(&double_g);
+    let zero = BigUint::from(0u32);
+    let one = BigUint::from(1u32);
+    for scalar in scalars(&curve) {
+        for (x, y) in [(&gx, &gy), (&dx, &dy), (&zero, &zero), (&one, &one)] {
+            let expected = curve.point(x, y).map(|point| curve.mul(&point, &scalar));
+            let (result, _) = curve.run(runner, "mul", &[x, y, &scalar]);
+            assert_eq!(result, expected_point_result(expected), "{x} * {scalar}");
+        }
+    }
+}
+
+#[rstest]
+fn test_mul_cost(
+    runner: &DifferentialRunner,
+    #[values(Curve::secp256k1(), Curve::secp256r1())] curve: Curve,
+) {
+    let (gx, gy) = coordinates(&curve.g);
+    let costs = scalars(&curve)
+        .iter()
+        .map(|scalar| curve.run(runner, "mul", &[&gx, &gy, scalar]).1)
+        .collect_vec();
+    assert!(costs.iter().all_equal(), "The cost depends on the scalar: {costs:?}");
+}
+
+#[rstest]
+fn test_add(
+    runner: &DifferentialRunner,
+    #[values(Curve::secp256k1(), Curve::secp256r1())] curve: Curve,
+) {
+    let g = curve.g.clone();
+    let neg_g = g.as_ref().map(|(x, y)| (x.clone(), &curve.p - y));
+    let double_g = curve.add(&g, &g);
+    let points = [g, neg_g, double_g, None];
+    for (p0, p1) in points.iter().cartesian_product(&points) {
+        let (x0, y0) = coordinates(p0);
+        let (x1, y1) = coordinates(p1);
+        let expected = curve.add(p0, p1);
+        let (result, _) = curve.run(runner, "add", &[&x0, &y0, &x1, &y1]);
+        assert_eq!(result, expected_point_result(Some(expected)), "{p0:?} + {p1:?}");
+    }
+    // A point off the curve.
+    let (gx, gy) = coordinates(&curve.g);
+    let one = BigUint::from(1u32);
+    let (result, _) = curve.run(runner, "add", &[&gx, &gy, &one, &one]);
+    assert_eq!(result, expected_point_result(None));
+}
+
+#[rstest]
+fn test_mul_add(
+    runner: &DifferentialRunner,
+    #[values(Curve::secp256k1(), Curve::secp256r1())] curve: Curve,
+) {
+    let g = curve.g.clone();
+    let neg_g = g.as_ref().map(|(x, y)| (x.clone(), &curve.p - y));
+    let double_g = curve.add(&g, &g);
+    let points = [g, neg_g, double_g, None];
+    let scalars = scalars(&curve);
+    for (p0, p1) in points.iter().cartesian_product(&points) {
+        let (x0, y0) = coordinates(p0);
+        let (x1, y1) = coordinates(p1);
+        for (scalar0, scalar1) in scalars.iter().zip(scalars.iter().rev()) {
+            let expected = curve.add(&curve.mul(p0, scalar0), &curve.mul(p1, scalar1));
+            let (result, _) = curve.run(runner, "mul_add", &[&x0, &y0, scalar0, &x1, &y1, scalar1]);
+            assert_eq!(
+                result,
+                expected_point_result(Some(expected)),
+                "{p0:?} * {scalar0} + {p1:?} * {scalar1}"
+            );
+        }
+    }
+}
+
+#[rstest]
+fn test_coordinates_out_of_range(
+    runner: &DifferentialRunner,
+    #[values(Curve::secp256k1(), Curve::secp256r1())] curve: Curve,
+) {
+    let zero = BigUint::from(0u32);
+    let (result, _) = curve.run(runner, "mul", &[&curve.p, &zero, &zero]);
+    assert_eq!(
+        result,
+        RunResultValue::Panic(vec![Felt252::from_bytes_be(b"Coordinates out of range")])
+    );
+}
+
+#[rstest]
+fn test_verify(
+    runner: &DifferentialRunner,
+    #[values(Curve::secp256k1(), Curve::secp256r1())] curve: Curve,
+) {
+    let d = hex("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+    let k = hex("a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60");
+    let msg_hash = hex("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf");
+    let public_key = curve.mul(&curve.g, &d);
+    let (x, y) = coordinates(&public_key);
+    let (r, s) = curve.sign(&msg_hash, &d, &k);
+    let zero = BigUint::from(0u32);
+    let high_s = &curve.n - &s;
+    let other_hash = &msg_hash + 1u32;
+    let cases = [
+        (&msg_hash, &r, &s),
+        // The negated `s` of a valid signature is valid as well.
+        (&msg_hash, &r, &high_s),
+        (&other_hash, &r, &s),
+        (&msg_hash, &s, &r),
+        (&msg_hash, &zero, &s),
+        (&msg_hash, &r, &zero),
+        (&msg_hash, &r, &curve.n),
+        (&msg_hash, &curve.n, &s),
+    ];
+    for (msg_hash, r, s) in cases {
+        let expected = curve.verify(msg_hash, r, s, &public_key);
+        let (result, _) = curve.run(runner, "verify", &[msg_hash, r, s, &x, &y]);
+        assert_eq!(
+            result,
+            RunResultValue::Success(vec![Felt252::from(expected)]),
+            "hash: {msg_hash}, r: {r}, s: {s}"
+        );
+    }
+    assert!(curve.verify(&msg_hash, &r, &s, &public_key));
+    assert!(curve.verify(&msg_hash, &r, &high_s, &public_key));
+}